@@ -0,0 +1,9 @@
+-- Add migration script here
+CREATE TABLE active_sessions (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    voice_channel_id BIGINT NOT NULL,
+    thread_id BIGINT,
+    record_transcriptions BOOLEAN NOT NULL DEFAULT FALSE,
+    started_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,18 @@
+-- Add migration script here
+CREATE TABLE scheduled_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    -- recurring jobs are named, so re-registering them on startup doesn't duplicate them
+    name TEXT UNIQUE,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL DEFAULT '',
+    -- null for one-shot jobs
+    cron TEXT,
+    next_run TIMESTAMP NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_run TIMESTAMP,
+    last_error TEXT,
+    locked_until TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX scheduled_jobs_next_run_idx ON scheduled_jobs (next_run);
@@ -0,0 +1,17 @@
+CREATE TABLE delivery_queue (
+    id BIGSERIAL PRIMARY KEY,
+    dedupe_key TEXT NOT NULL UNIQUE,
+    -- 0 = webhook, 1 = channel, 2 = DM
+    target_kind SMALLINT NOT NULL,
+    target_id BIGINT NOT NULL,
+    thread_id BIGINT,
+    webhook_token TEXT,
+    -- JSON message body
+    body TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt TIMESTAMP NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX delivery_queue_next_attempt_idx ON delivery_queue (next_attempt);
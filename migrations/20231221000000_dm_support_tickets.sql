@@ -0,0 +1,26 @@
+-- Add migration script here
+CREATE TABLE dm_support_tickets (
+    id BIGSERIAL PRIMARY KEY,
+    -- hashed, like everywhere else
+    user_id BYTEA NOT NULL,
+    channel_id BIGINT NOT NULL UNIQUE,
+    opened_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMP,
+    closed_by BIGINT
+);
+
+CREATE INDEX dm_support_tickets_user_id_idx ON dm_support_tickets (user_id);
+
+CREATE TABLE dm_support_messages (
+    id BIGSERIAL PRIMARY KEY,
+    ticket_id BIGINT NOT NULL REFERENCES dm_support_tickets (id) ON DELETE CASCADE,
+    -- false if sent by the user who opened the ticket
+    from_staff BOOLEAN NOT NULL,
+    author_name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    -- newline-separated attachment URLs
+    attachments TEXT NOT NULL DEFAULT '',
+    sent_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX dm_support_messages_ticket_id_idx ON dm_support_messages (ticket_id);
@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE dm_support_tickets ADD COLUMN assignee BIGINT;
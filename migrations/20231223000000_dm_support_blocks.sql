@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE dm_support_blocks (
+    -- hashed, like everywhere else
+    user_id BYTEA PRIMARY KEY,
+    reason TEXT,
+    blocked_by BIGINT NOT NULL,
+    blocked_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    -- NULL if the block is permanent
+    expires_at TIMESTAMP
+);
@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE dm_support_tickets ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+-- 0 = low, 1 = normal, 2 = high, 3 = urgent
+ALTER TABLE dm_support_tickets ADD COLUMN priority SMALLINT NOT NULL DEFAULT 1;
+
+CREATE INDEX dm_support_tickets_open_idx ON dm_support_tickets (priority, opened_at) WHERE closed_at IS NULL;
@@ -0,0 +1,10 @@
+-- user ID hashes are moved to a keyed hash one user at a time (see `scripty_utils::rehash_user_id`),
+-- so let changes to users.user_id follow through to everything referencing it
+ALTER TABLE audio_store
+    DROP CONSTRAINT audio_store_source_id_fkey,
+    ADD CONSTRAINT audio_store_source_id_fkey FOREIGN KEY (source_id)
+        REFERENCES users(user_id) ON DELETE CASCADE ON UPDATE CASCADE;
+ALTER TABLE blocked_users
+    DROP CONSTRAINT blocked_users_user_id_fkey,
+    ADD CONSTRAINT blocked_users_user_id_fkey FOREIGN KEY (user_id)
+        REFERENCES users(user_id) ON DELETE CASCADE ON UPDATE CASCADE;
@@ -0,0 +1,9 @@
+-- Patreon members, as last seen in a pledge webhook, and who they've linked to with `/premium link`
+CREATE TABLE patreon_members (
+    patreon_user_id TEXT PRIMARY KEY,
+    -- hashed, like everywhere else. NULL until the patron links their account
+    user_id BYTEA UNIQUE REFERENCES users (user_id) ON DELETE SET NULL ON UPDATE CASCADE,
+    premium_level SMALLINT NOT NULL DEFAULT 0,
+    premium_expiry TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,2 @@
+-- milliseconds of audio transcribed this month, reset by the `transcription_quota_reset` job
+ALTER TABLE guilds ADD COLUMN ms_transcribed BIGINT NOT NULL DEFAULT 0;
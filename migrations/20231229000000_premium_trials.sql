@@ -0,0 +1,3 @@
+-- when a guild's `/premium trial` runs out
+-- trial_used on both the guild and the user who started it stays set afterwards, so each only gets one
+ALTER TABLE guilds ADD COLUMN trial_expires_at TIMESTAMP;
@@ -0,0 +1,31 @@
+-- API keys for the transcript API, made with `/config api_key`. One per guild
+-- only a hash is stored: the key itself is shown once, when it's made
+CREATE TABLE guild_api_keys (
+    guild_id BIGINT PRIMARY KEY REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    key_hash BYTEA NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMP WITH TIME ZONE
+);
+
+-- voice sessions of guilds with an API key, kept for `transcript_api.retention_days`
+CREATE TABLE transcript_sessions (
+    session_id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    voice_channel_id BIGINT NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- NULL while the session is going, or if the bot didn't shut down cleanly
+    ended_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX transcript_sessions_guild_id_started_at_idx ON transcript_sessions (guild_id, started_at);
+
+CREATE TABLE transcript_segments (
+    segment_id BIGSERIAL PRIMARY KEY,
+    session_id BIGINT NOT NULL REFERENCES transcript_sessions (session_id) ON DELETE CASCADE,
+    -- display name at the time, like in the transcript file sent after recorded sessions
+    speaker TEXT NOT NULL,
+    -- from the start of the session
+    start_ms BIGINT NOT NULL,
+    end_ms BIGINT NOT NULL,
+    content TEXT NOT NULL
+);
+CREATE INDEX transcript_segments_session_id_idx ON transcript_segments (session_id, start_ms);
@@ -0,0 +1,4 @@
+-- set with `/config webhook_url`: when a session ends, a summary is POSTed here
+ALTER TABLE guilds ADD COLUMN webhook_url TEXT;
+-- signs each request, so the guild can check it came from us. Shown once, when the URL is set
+ALTER TABLE guilds ADD COLUMN webhook_secret TEXT;
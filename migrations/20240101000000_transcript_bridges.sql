@@ -0,0 +1,13 @@
+-- set with `/config bridge`: transcripts are mirrored to Slack or Matrix as they come in
+CREATE TABLE transcript_bridges (
+    guild_id BIGINT PRIMARY KEY REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    -- only sessions in this voice channel are bridged, or all of them if NULL
+    voice_channel_id BIGINT,
+    -- 0: Slack incoming webhook, 1: Matrix room
+    kind SMALLINT NOT NULL,
+    -- Slack: the incoming webhook URL. Matrix: the homeserver's base URL
+    url TEXT NOT NULL,
+    -- Matrix only
+    matrix_room_id TEXT,
+    matrix_access_token TEXT
+);
@@ -0,0 +1,7 @@
+-- audio transcribed by the whole bot, ever, for public stats. Only ever has one row
+-- counted from here on, on top of what guilds had used this month when it was added
+CREATE TABLE transcription_totals (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    ms_transcribed BIGINT NOT NULL DEFAULT 0
+);
+INSERT INTO transcription_totals (ms_transcribed) SELECT COALESCE(SUM(ms_transcribed), 0) FROM guilds;
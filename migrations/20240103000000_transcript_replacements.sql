@@ -0,0 +1,9 @@
+-- set with `/config replacements`: regex find-and-replace rules, applied to every transcript
+CREATE TABLE transcript_replacements (
+    id SERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    pattern TEXT NOT NULL,
+    replacement TEXT NOT NULL,
+    -- adding a pattern that already exists replaces its replacement
+    UNIQUE (guild_id, pattern)
+);
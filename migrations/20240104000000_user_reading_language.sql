@@ -0,0 +1,2 @@
+-- set with `/reading_language`: transcript lines are translated to this for the user, on request
+ALTER TABLE users ADD COLUMN reading_language TEXT;
@@ -0,0 +1,2 @@
+-- set with `/config speaker_labels`. 0: off, 1: embed color per speaker, 2: emoji per speaker
+ALTER TABLE guilds ADD COLUMN speaker_labels SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- set with `/config merge_utterances`. 0 turns merging off
+ALTER TABLE guilds ADD COLUMN merge_gap_secs SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- set with `/config profanity_filter`. 0: off, 1: mask, 2: spoiler
+ALTER TABLE guilds ADD COLUMN profanity_filter SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- set with `/config timestamps`. 0: off, 1: time since the session started, 2: time of day
+ALTER TABLE guilds ADD COLUMN timestamps SMALLINT NOT NULL DEFAULT 0;
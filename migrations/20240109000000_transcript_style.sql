@@ -0,0 +1,2 @@
+-- set with `/config transcript_style`. 0: as the speaker, 1: compact text, 2: embeds
+ALTER TABLE guilds ADD COLUMN transcript_style SMALLINT NOT NULL DEFAULT 0;
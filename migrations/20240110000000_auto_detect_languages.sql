@@ -0,0 +1,5 @@
+-- languages auto-detect picks from, set with `/config auto_detect_lang`. Empty allows any
+ALTER TABLE guilds ADD COLUMN auto_detect_languages TEXT[] NOT NULL DEFAULT '{}';
+
+-- the language each segment was detected as, if auto-detect was on and it was clear enough
+ALTER TABLE transcript_segments ADD COLUMN language TEXT;
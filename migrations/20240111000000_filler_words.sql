@@ -0,0 +1,2 @@
+-- set with `/config filler_words`. Off by default, so transcripts stay verbatim
+ALTER TABLE guilds ADD COLUMN remove_filler_words BOOLEAN NOT NULL DEFAULT false;
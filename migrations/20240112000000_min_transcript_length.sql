@@ -0,0 +1,4 @@
+-- set with `/config min_length`. 0 sends every transcript
+ALTER TABLE guilds ADD COLUMN min_transcript_length SMALLINT NOT NULL DEFAULT 0;
+-- 0: characters, 1: words
+ALTER TABLE guilds ADD COLUMN min_transcript_length_unit SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- set with `/config numbers`. Off leaves numbers however the model wrote them
+ALTER TABLE guilds ADD COLUMN numbers_as_digits BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,2 @@
+-- set with `/config silence_timeout`. 0 ends an utterance on the first silent voice tick
+ALTER TABLE guilds ADD COLUMN silence_timeout_ms SMALLINT NOT NULL DEFAULT 0;
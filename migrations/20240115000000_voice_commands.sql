@@ -0,0 +1,2 @@
+-- set with `/config voice_commands`. Members with this role can control Scripty by voice, off if null
+ALTER TABLE guilds ADD COLUMN voice_command_role BIGINT;
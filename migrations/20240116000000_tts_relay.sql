@@ -0,0 +1,2 @@
+-- set with `/config tts_relay`. Messages from members with this role are read out in the voice chat, off if null
+ALTER TABLE guilds ADD COLUMN tts_relay_role BIGINT;
@@ -0,0 +1,6 @@
+-- set with `/config scheduled_events`. Voice channel events are transcribed into this channel when started, off if null
+ALTER TABLE guilds ADD COLUMN scheduled_event_channel BIGINT;
+-- create a thread in that channel for each event
+ALTER TABLE guilds ADD COLUMN scheduled_event_thread BOOLEAN NOT NULL DEFAULT false;
+-- send the transcript file when the event ends, like `/join record_transcriptions:True`
+ALTER TABLE guilds ADD COLUMN scheduled_event_record BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,11 @@
+-- the channel or thread transcripts were sent to, for linking to them from `/search`
+ALTER TABLE transcript_sessions ADD COLUMN transcript_channel_id BIGINT;
+
+-- who said it, as the speaker's name may have changed since
+ALTER TABLE transcript_segments ADD COLUMN speaker_id BIGINT;
+-- the transcript message it was sent in, NULL if it couldn't be sent or was queued
+ALTER TABLE transcript_segments ADD COLUMN message_id BIGINT;
+-- for `/search`. The simple config as transcripts can be in any language
+ALTER TABLE transcript_segments ADD COLUMN content_tsv tsvector
+    GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED;
+CREATE INDEX transcript_segments_content_tsv_idx ON transcript_segments USING GIN (content_tsv);
@@ -0,0 +1,15 @@
+-- how long each user spoke for in each guild, per hour, for `/analytics`. Kept for a year
+CREATE TABLE user_talk_time (
+    guild_id BIGINT NOT NULL,
+    -- hashed, like everywhere else user IDs are stored
+    user_id BYTEA NOT NULL,
+    -- start of the hour, in UTC
+    hour TIMESTAMP WITH TIME ZONE NOT NULL,
+    ms_spoken BIGINT NOT NULL DEFAULT 0,
+    utterances BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id, hour)
+);
+CREATE INDEX user_talk_time_hour_idx ON user_talk_time (hour);
+
+-- set with `/data_storage`: no talk time is kept for users who opted out
+ALTER TABLE users ADD COLUMN analytics_opt_out BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,2 @@
+-- set with `/config anonymous_speakers`. Speakers are numbered instead of named, and no user IDs are kept with transcripts
+ALTER TABLE guilds ADD COLUMN anonymous_speakers BOOLEAN NOT NULL DEFAULT false;
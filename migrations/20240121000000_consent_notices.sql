@@ -0,0 +1,9 @@
+-- set with `/config consent_dm`. Members joining a voice chat being transcribed are DMed about it, if they haven't been before
+ALTER TABLE guilds ADD COLUMN consent_dm BOOLEAN NOT NULL DEFAULT false;
+
+-- users who've been DMed that they're being transcribed, so they only ever are once
+CREATE TABLE consent_notices (
+    -- hashed, like everywhere else user IDs are stored
+    user_id BYTEA PRIMARY KEY,
+    notified_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
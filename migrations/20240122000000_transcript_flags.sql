@@ -0,0 +1,11 @@
+-- set with `/config flag_emoji`. Reacting to a transcript with it flags the transcript as wrong, NULL if off.
+-- Unicode emojis are stored as-is, custom ones by ID
+ALTER TABLE guilds ADD COLUMN flag_emoji TEXT;
+
+-- the transcript message it was sent in, so reactions to it can be traced back to the audio
+ALTER TABLE audio_store ADD COLUMN message_id BIGINT;
+CREATE INDEX audio_store_message_id_idx ON audio_store (message_id);
+-- when someone flagged the transcript as wrong. Flagged audio without an
+-- audio_transcript_verification row is waiting for a correction
+ALTER TABLE audio_store ADD COLUMN flagged_at TIMESTAMP WITH TIME ZONE;
+CREATE INDEX audio_store_flagged_at_idx ON audio_store (flagged_at) WHERE flagged_at IS NOT NULL;
@@ -0,0 +1,10 @@
+-- set with `/config watch`. While a guild has any, only these users, and members with these roles,
+-- are transcribed
+CREATE TABLE watched_speakers (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    -- a user or role ID
+    target_id BIGINT NOT NULL,
+    is_role BOOLEAN NOT NULL,
+    added_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (guild_id, target_id)
+);
@@ -0,0 +1,11 @@
+-- set with `/config role_policy`. Members with a denied role are never transcribed, and while a
+-- guild allows any roles, members need one of them to be transcribed
+CREATE TABLE role_policies (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    role_id BIGINT NOT NULL,
+    -- false if the role is denied
+    allow BOOLEAN NOT NULL,
+    -- allowed roles only: also allow every role above it
+    and_above BOOLEAN NOT NULL DEFAULT false,
+    PRIMARY KEY (guild_id, role_id)
+);
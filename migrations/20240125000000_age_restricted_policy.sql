@@ -0,0 +1,3 @@
+-- set with `/config age_restricted`: how age-restricted voice chats are transcribed.
+-- 0 = like any other, 1 = not at all, 2 = only into age-restricted channels, 3 = in spoilers
+ALTER TABLE guilds ADD COLUMN age_restricted_policy SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- set with `/config highlight_emoji`. Reacting to a transcript with it adds the transcript to the
+-- session's highlights, NULL if off. Stored like flag_emoji
+ALTER TABLE guilds ADD COLUMN highlight_emoji TEXT DEFAULT '📌';
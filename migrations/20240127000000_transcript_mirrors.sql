@@ -0,0 +1,9 @@
+-- set with `/config mirror`: extra channels a voice channel's transcripts are also sent to
+CREATE TABLE transcript_mirrors (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    voice_channel_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    -- how transcripts look in this channel, stored like guilds.transcript_style
+    style SMALLINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, voice_channel_id, channel_id)
+);
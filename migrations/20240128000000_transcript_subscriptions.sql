@@ -0,0 +1,14 @@
+-- set with `/config subscriptions`: whether members can have transcripts DMed to them with
+-- `/subscribe`
+ALTER TABLE guilds ADD COLUMN allow_subscriptions BOOLEAN NOT NULL DEFAULT false;
+
+-- members who get a voice channel's transcripts DMed to them when its sessions end
+CREATE TABLE transcript_subscriptions (
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    voice_channel_id BIGINT NOT NULL,
+    -- not hashed, as it's needed to DM them. Removed by `/delete_all_data`
+    user_id BIGINT NOT NULL,
+    -- only send the minutes, not the whole transcript
+    summary_only BOOLEAN NOT NULL DEFAULT false,
+    PRIMARY KEY (guild_id, voice_channel_id, user_id)
+);
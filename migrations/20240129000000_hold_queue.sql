@@ -0,0 +1,23 @@
+-- set with `/config hold_queue`: transcripts are held in this channel until a moderator approves
+-- them, NULL if off
+ALTER TABLE guilds ADD COLUMN hold_channel_id BIGINT;
+-- approve held transcripts automatically after this many seconds, NULL to wait for a moderator
+ALTER TABLE guilds ADD COLUMN hold_auto_approve_secs INTEGER;
+
+CREATE TABLE held_transcripts (
+    hold_id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    -- where it's posted once approved: the channel of the transcript webhook, and its thread
+    channel_id BIGINT NOT NULL,
+    thread_id BIGINT,
+    -- JSON webhook message body
+    body TEXT NOT NULL,
+    -- the message with the approve and reject buttons, once it's been sent
+    review_channel_id BIGINT NOT NULL,
+    review_message_id BIGINT,
+    held_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    -- NULL if it waits for a moderator
+    auto_approve_at TIMESTAMP
+);
+
+CREATE INDEX held_transcripts_auto_approve_at_idx ON held_transcripts (auto_approve_at);
@@ -0,0 +1,14 @@
+-- set with `/automation add`: things that happen when someone says a phrase in a transcribed
+-- voice chat
+CREATE TABLE automations (
+    id SERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL REFERENCES guilds (guild_id) ON DELETE CASCADE,
+    -- lowercase, matched anywhere in a transcript
+    phrase TEXT NOT NULL,
+    -- 0 posts message, 1 pings role_id, 2 fires the guild's webhook
+    action SMALLINT NOT NULL,
+    message TEXT,
+    role_id BIGINT,
+    -- adding a phrase that already exists replaces what it does
+    UNIQUE (guild_id, phrase)
+);
@@ -0,0 +1,3 @@
+-- set with `/config join_leave_notices`: post a line in the transcript when members join or leave
+-- the voice chat
+ALTER TABLE guilds ADD COLUMN join_leave_notices BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,2 @@
+-- whether the onboarding message was sent when Scripty was first added, so it's only ever sent once
+ALTER TABLE guilds ADD COLUMN onboarding_sent BOOLEAN NOT NULL DEFAULT false;
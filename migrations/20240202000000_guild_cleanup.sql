@@ -0,0 +1,2 @@
+-- the job deleting a guild's data after Scripty was removed from it, so adding Scripty back in time can cancel it
+ALTER TABLE guilds ADD COLUMN cleanup_job_id BIGINT;
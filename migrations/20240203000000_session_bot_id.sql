@@ -0,0 +1,5 @@
+-- the bot a session is in, as extra bots can each transcribe a voice chat in the same guild
+-- existing sessions are the main bot's: it claims them on startup, as its ID isn't known here
+ALTER TABLE active_sessions ADD COLUMN bot_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE active_sessions DROP CONSTRAINT active_sessions_pkey;
+ALTER TABLE active_sessions ADD PRIMARY KEY (guild_id, bot_id);
@@ -0,0 +1,5 @@
+-- queued webhook deliveries look their webhook's token up when they're sent, instead of storing it
+ALTER TABLE delivery_queue DROP COLUMN webhook_token;
+
+-- replayed in the order they were queued, per target
+CREATE INDEX delivery_queue_target_idx ON delivery_queue (target_kind, target_id, created_at);
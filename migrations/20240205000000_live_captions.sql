@@ -0,0 +1,2 @@
+-- live captions are only published for guilds that turn them on with `/config live_captions`
+ALTER TABLE guilds ADD COLUMN live_captions BOOLEAN NOT NULL DEFAULT false;
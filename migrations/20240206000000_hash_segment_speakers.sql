@@ -0,0 +1,4 @@
+-- speakers are hashed like every other user ID. Existing ones get the legacy hash,
+-- which `rehash_user_id` moves over to the current one
+ALTER TABLE transcript_segments
+    ALTER COLUMN speaker_id TYPE BYTEA USING sha512(convert_to(speaker_id::TEXT, 'UTF8'));
@@ -0,0 +1,84 @@
+//! How age-restricted voice chats are transcribed, set with `/config age_restricted`.
+//!
+//! Checked whenever Scripty joins, however it was asked to, so auto-joins and scheduled events
+//! follow the same rules as `/join`.
+
+use serenity::{
+	all::{ChannelId, GuildId},
+	client::Context,
+};
+
+use crate::Error;
+
+/// Stored in `guilds.age_restricted_policy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgeRestrictedPolicy {
+	/// Transcribed like any other voice chat.
+	Allow,
+	/// Never transcribed.
+	Refuse,
+	/// Only transcribed into age-restricted channels.
+	AgeRestrictedOnly,
+	/// Transcribed anywhere, with every transcript hidden behind a spoiler.
+	Spoiler,
+}
+
+impl AgeRestrictedPolicy {
+	/// Decode `guilds.age_restricted_policy`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Refuse,
+			2 => Self::AgeRestrictedOnly,
+			3 => Self::Spoiler,
+			_ => Self::Allow,
+		}
+	}
+}
+
+/// Whether `channel_id` is age-restricted. Threads are if the channel they're in is.
+///
+/// Channels that aren't cached are assumed not to be.
+pub(crate) fn is_age_restricted(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> bool {
+	let Some(guild) = ctx.cache.guild(guild_id) else {
+		return false;
+	};
+	let channel_id = guild
+		.threads
+		.iter()
+		.find(|thread| thread.id == channel_id)
+		.and_then(|thread| thread.parent_id)
+		.unwrap_or(channel_id);
+	guild
+		.channels
+		.get(&channel_id)
+		.map_or(false, |channel| channel.nsfw)
+}
+
+/// Check that `guild_id`'s policy lets transcripts of `voice_channel_id` be sent to `channel_id`.
+pub(crate) async fn check_policy(
+	ctx: &Context,
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	channel_id: ChannelId,
+) -> Result<(), Error> {
+	if !is_age_restricted(ctx, guild_id, voice_channel_id) {
+		return Ok(());
+	}
+	let policy = sqlx::query!(
+		"SELECT age_restricted_policy FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?
+	.map_or(AgeRestrictedPolicy::Allow, |row| {
+		AgeRestrictedPolicy::from_db(row.age_restricted_policy)
+	});
+
+	match policy {
+		AgeRestrictedPolicy::Refuse => Err(Error::age_restricted_refused()),
+		AgeRestrictedPolicy::AgeRestrictedOnly if !is_age_restricted(ctx, guild_id, channel_id) => {
+			Err(Error::age_restricted_target())
+		}
+		_ => Ok(()),
+	}
+}
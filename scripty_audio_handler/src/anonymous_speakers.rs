@@ -0,0 +1,61 @@
+//! Anonymous transcripts, set with `/config anonymous_speakers`, for servers that want captions
+//! without keeping who said what.
+//!
+//! Speakers are named "Speaker 1", "Speaker 2" and so on, in the order they first spoke in a
+//! session, and keep their number until it ends. Nothing ties the numbers to users afterwards:
+//! user IDs aren't stored with transcripts, and talk time isn't counted for `/analytics`.
+
+use std::{
+	collections::HashMap,
+	sync::atomic::{AtomicBool, Ordering},
+};
+
+use parking_lot::{Mutex, RwLock};
+use scripty_i18n::LanguageIdentifier;
+
+/// Discord's default avatars, which speakers get in turn instead of their own.
+const DEFAULT_AVATARS: usize = 6;
+
+#[derive(Default)]
+pub struct AnonymousSpeakers {
+	enabled:  AtomicBool,
+	/// The guild's language, for the speakers' names.
+	language: RwLock<LanguageIdentifier>,
+	/// User IDs mapped to their number, in the order they first spoke.
+	assigned: Mutex<HashMap<u64, usize>>,
+}
+
+impl AnonymousSpeakers {
+	pub fn set(&self, enabled: bool, language: LanguageIdentifier) {
+		self.enabled.store(enabled, Ordering::Relaxed);
+		*self.language.write() = language;
+	}
+
+	pub fn is_enabled(&self) -> bool {
+		self.enabled.load(Ordering::Relaxed)
+	}
+
+	/// Name and avatar URL to show `user_id` with, numbering them if they don't have a number yet.
+	/// None if the guild doesn't have anonymous speakers turned on.
+	pub fn get(&self, user_id: u64) -> Option<(String, String)> {
+		if !self.is_enabled() {
+			return None;
+		}
+
+		let number = {
+			let mut assigned = self.assigned.lock();
+			let next = assigned.len() + 1;
+			*assigned.entry(user_id).or_insert(next)
+		};
+		let name = format_message!(
+			*self.language.read(),
+			"anonymous-speaker-name",
+			number: number
+		);
+		let avatar_url = format!(
+			"https://cdn.discordapp.com/embed/avatars/{}.png",
+			(number - 1) % DEFAULT_AVATARS
+		);
+		Some((name, avatar_url))
+	}
+}
@@ -4,14 +4,25 @@ use std::{
 		atomic::{AtomicBool, AtomicU8, Ordering},
 		Arc,
 	},
+	time::{Duration, SystemTime},
 };
 
 use ahash::RandomState;
 use dashmap::{DashMap, DashSet};
 use parking_lot::RwLock;
 use scripty_automod::types::AutomodServerConfig;
+use scripty_stt::{
+	FillerWords,
+	LengthUnit,
+	MinLength,
+	Pipeline,
+	ProfanityMode,
+	Replacements,
+	SpokenNumbers,
+};
+use scripty_utils::{delivery::DeliveryTarget, spawn_logged};
 use serenity::{
-	all::RoleId,
+	all::{RoleId, UserId},
 	client::Context,
 	model::{
 		id::{ChannelId, GuildId},
@@ -21,19 +32,34 @@ use serenity::{
 use songbird::{Event, EventContext, EventHandler};
 
 use crate::{
+	age_restricted::AgeRestrictedPolicy,
+	anonymous_speakers::AnonymousSpeakers,
+	coalesce::Coalescer,
 	events::*,
+	hold_queue::Hold,
+	join_leave::Notices,
+	render::TranscriptStyle,
+	speaker_labels::{SpeakerLabelMode, SpeakerLabels},
+	timestamps::TimestampMode,
+	transcript_store::TranscriptSession,
+	tts_relay::Relay,
 	types::{
 		ActiveUserSet,
 		NextUserList,
 		SeenUsers,
 		SsrcIgnoredMap,
-		SsrcSpeakingSet,
+		SsrcLastSequenceMap,
+		SsrcLastSpokeMap,
+		SsrcSpokenSet,
 		SsrcStreamMap,
 		SsrcUserDataMap,
 		SsrcUserIdMap,
+		SsrcUtteranceAudioMap,
+		SsrcUtteranceStartMap,
 		SsrcVoiceIngestMap,
 		TranscriptResults,
 	},
+	SessionKey,
 };
 
 pub struct SsrcMaps {
@@ -42,30 +68,82 @@ pub struct SsrcMaps {
 	pub ssrc_user_data_map:    SsrcUserDataMap,
 	pub ssrc_ignored_map:      SsrcIgnoredMap,
 	pub ssrc_voice_ingest_map: SsrcVoiceIngestMap,
-	pub ssrc_speaking_set:     SsrcSpeakingSet,
+	pub ssrc_last_spoke:       SsrcLastSpokeMap,
+	pub ssrc_last_sequence:    SsrcLastSequenceMap,
+	pub ssrc_utterance_start:  SsrcUtteranceStartMap,
+	pub ssrc_utterance_audio:  SsrcUtteranceAudioMap,
+	pub ssrc_spoken:           SsrcSpokenSet,
 	pub active_user_set:       ActiveUserSet,
 	pub next_user_list:        NextUserList,
 }
 pub type ArcSsrcMaps = Arc<SsrcMaps>;
 
+/// A guild's settings for a session, as of the last `AudioHandler::reload_config`.
+/// Replaced whole, so a tick never sees half of one reload and half of the next.
+pub struct SessionSettings {
+	pub language:           String,
+	pub verbose:            bool,
+	/// Only on while the guild has premium.
+	pub auto_detect_lang:   bool,
+	pub allowed_languages:  Vec<String>,
+	pub translate:          bool,
+	pub post_process:       Pipeline,
+	pub profanity_filter:   ProfanityMode,
+	/// Label of the translate button on transcripts, if they get one.
+	pub translate_button:   Option<String>,
+	pub timestamps:         TimestampMode,
+	pub transcript_style:   TranscriptStyle,
+	/// How long someone has to be quiet for before what they said is transcribed.
+	pub silence_timeout:    Duration,
+	pub voice_command_role: Option<RoleId>,
+	/// Whether transcripts are hidden behind spoilers, as this is an age-restricted voice chat.
+	pub spoiler:            bool,
+}
+
+impl Default for SessionSettings {
+	fn default() -> Self {
+		Self {
+			language:           String::new(),
+			verbose:            false,
+			auto_detect_lang:   false,
+			allowed_languages:  Vec::new(),
+			translate:          false,
+			post_process:       Pipeline::default(),
+			profanity_filter:   ProfanityMode::Off,
+			translate_button:   None,
+			timestamps:         TimestampMode::Off,
+			transcript_style:   TranscriptStyle::Webhook,
+			silence_timeout:    Duration::ZERO,
+			voice_command_role: None,
+			spoiler:            false,
+		}
+	}
+}
+
 #[derive(Clone)]
 pub struct AudioHandler {
 	ssrc_state:           ArcSsrcMaps,
 	guild_id:             GuildId,
+	/// This session among those of every bot in the guild.
+	key:                  SessionKey,
 	channel_id:           ChannelId,
 	voice_channel_id:     ChannelId,
 	thread_id:            Option<ChannelId>,
 	webhook:              Arc<Webhook>,
 	context:              Context,
 	premium_level:        Arc<AtomicU8>,
-	verbose:              Arc<AtomicBool>,
-	language:             Arc<RwLock<String>>,
+	settings:             Arc<RwLock<Arc<SessionSettings>>>,
 	transcript_results:   TranscriptResults,
 	seen_users:           SeenUsers,
 	automod_server_cfg:   Arc<AutomodServerConfig>,
-	auto_detect_lang:     Arc<AtomicBool>,
 	transcribe_only_role: Arc<RwLock<Option<RoleId>>>,
-	translate:            Arc<AtomicBool>,
+	speaker_labels:       Arc<SpeakerLabels>,
+	anonymous_speakers:   Arc<AnonymousSpeakers>,
+	coalescer:            Arc<Coalescer>,
+	/// Set by voice commands, so transcripts can be stopped without leaving.
+	paused:               Arc<AtomicBool>,
+	transcript_session:   Arc<TranscriptSession>,
+	started_at:           SystemTime,
 }
 
 impl AudioHandler {
@@ -85,34 +163,48 @@ impl AudioHandler {
 			ssrc_user_data_map:    DashMap::with_hasher(RandomState::new()),
 			ssrc_ignored_map:      DashMap::with_hasher(RandomState::new()),
 			ssrc_voice_ingest_map: DashMap::with_hasher(RandomState::new()),
-			ssrc_speaking_set:     DashSet::with_hasher(RandomState::new()),
+			ssrc_last_spoke:       DashMap::with_hasher(RandomState::new()),
+			ssrc_last_sequence:    DashMap::with_hasher(RandomState::new()),
+			ssrc_utterance_start:  DashMap::with_hasher(RandomState::new()),
+			ssrc_utterance_audio:  DashMap::with_hasher(RandomState::new()),
+			ssrc_spoken:           DashSet::with_hasher(RandomState::new()),
 			active_user_set:       DashSet::with_hasher(RandomState::new()),
 			next_user_list:        RwLock::new(VecDeque::with_capacity(10)),
 		};
 
+		let key = SessionKey::new(&context, guild_id);
+		let transcript_session = Arc::new(
+			TranscriptSession::start(key, voice_channel_id, thread_id.unwrap_or(channel_id)).await,
+		);
+
 		let this = Self {
 			ssrc_state: Arc::new(maps),
 			guild_id,
+			key,
 			channel_id,
 			voice_channel_id,
 			thread_id,
 			webhook: Arc::new(webhook),
 			context,
 			premium_level: Arc::new(AtomicU8::new(0)),
-			verbose: Arc::new(AtomicBool::new(false)),
-			language: Arc::new(Default::default()),
+			settings: Arc::new(RwLock::new(Arc::new(SessionSettings::default()))),
 			transcript_results: record_transcriptions.then(|| Arc::new(RwLock::new(Vec::new()))),
 			seen_users: record_transcriptions
 				.then(|| Arc::new(DashSet::with_hasher(RandomState::new()))),
 			automod_server_cfg: Arc::new(automod_server_cfg),
-			auto_detect_lang: Arc::new(AtomicBool::new(false)),
 			transcribe_only_role: Arc::new(RwLock::new(None)),
-			translate: Arc::new(AtomicBool::new(false)),
+			speaker_labels: Arc::new(SpeakerLabels::default()),
+			anonymous_speakers: Arc::new(AnonymousSpeakers::default()),
+			coalescer: Arc::new(Coalescer::default()),
+			paused: Arc::new(AtomicBool::new(false)),
+			transcript_session,
+			started_at: SystemTime::now(),
 		};
 		this.reload_config().await?;
+		crate::session_stats::register_session(key, &this.ssrc_state);
 
 		let t2 = this.clone();
-		tokio::spawn(async move {
+		spawn_logged("audio_handler_config_reload", async move {
 			const RELOAD_TIME: std::time::Duration = std::time::Duration::from_secs(300);
 
 			loop {
@@ -121,7 +213,7 @@ impl AudioHandler {
 					error!("failed to reload config: {:?}", e);
 				};
 
-				if Arc::<_>::strong_count(&t2.verbose) == 1 {
+				if Arc::<_>::strong_count(&t2.settings) == 1 {
 					// this is the last strong pointer because all the others have been dropped
 					break;
 				}
@@ -134,91 +226,310 @@ impl AudioHandler {
 	pub async fn reload_config(&self) -> Result<(), sqlx::Error> {
 		let db = scripty_db::get_db();
 		let mut guild_res = sqlx::query!(
-			"SELECT be_verbose, language, auto_detect_lang, transcript_only_role, translate FROM \
+			"SELECT be_verbose, language, auto_detect_lang, transcript_only_role, translate, \
+			 ms_transcribed, speaker_labels, merge_gap_secs, profanity_filter, timestamps, \
+			 transcript_style, auto_detect_languages, remove_filler_words, min_transcript_length, \
+			 min_transcript_length_unit, numbers_as_digits, silence_timeout_ms, \
+			 voice_command_role, tts_relay_role, anonymous_speakers, age_restricted_policy, \
+			 hold_channel_id, hold_auto_approve_secs, join_leave_notices, live_captions FROM \
 			 guilds WHERE guild_id = $1",
 			self.guild_id.get() as i64
 		)
 		.fetch_one(db)
 		.await?;
 
-		self.verbose.store(guild_res.be_verbose, Ordering::Relaxed);
+		self.transcript_session
+			.set_live_captions(guild_res.live_captions);
+		self.speaker_labels
+			.set_mode(SpeakerLabelMode::from_db(guild_res.speaker_labels));
+		if guild_res.anonymous_speakers {
+			let language = scripty_i18n::get_guild_language(self.guild_id.get()).await;
+			self.anonymous_speakers.set(true, language);
+		} else {
+			self.anonymous_speakers.set(false, Default::default());
+		}
+		self.coalescer
+			.set_gap(u16::try_from(guild_res.merge_gap_secs).unwrap_or(0));
+		let timestamps = TimestampMode::from_db(guild_res.timestamps);
+		let age_restricted_policy = if crate::age_restricted::is_age_restricted(
+			&self.context,
+			self.guild_id,
+			self.voice_channel_id,
+		) {
+			AgeRestrictedPolicy::from_db(guild_res.age_restricted_policy)
+		} else {
+			AgeRestrictedPolicy::Allow
+		};
+		crate::tts_relay::set_relay(
+			self.key,
+			guild_res.tts_relay_role.map(|role| Relay {
+				channel_id: self.thread_id.unwrap_or(self.channel_id),
+				role:       RoleId::new(role as u64),
+				language:   guild_res.language.clone(),
+			}),
+		);
+		crate::hold_queue::set_hold(
+			self.key,
+			guild_res.hold_channel_id.map(|channel_id| Hold {
+				channel_id:         self.channel_id,
+				thread_id:          self.thread_id,
+				review_channel_id:  ChannelId::new(channel_id as u64),
+				auto_approve_after: guild_res
+					.hold_auto_approve_secs
+					.map(|secs| Duration::from_secs(secs.max(0) as u64)),
+			}),
+		);
+		crate::join_leave::set_notices(
+			self.key,
+			DeliveryTarget::webhook(&self.webhook, self.thread_id)
+				.filter(|_| guild_res.join_leave_notices)
+				.map(|target| Notices {
+					voice_channel_id: self.voice_channel_id,
+					target,
+					transcript_results: self.transcript_results.clone(),
+					anonymous_speakers: Arc::clone(&self.anonymous_speakers),
+					timestamps,
+					paused: Arc::clone(&self.paused),
+					started_at: self.started_at,
+				}),
+		);
 
-		if let Some(lvl) = scripty_premium::get_guild(self.guild_id.get()).await {
-			self.premium_level.store(lvl as u8, Ordering::Relaxed);
-			self.auto_detect_lang
-				.store(guild_res.auto_detect_lang, Ordering::Relaxed);
+		let premium_tier = scripty_premium::get_guild(self.guild_id.get()).await;
+		self.premium_level
+			.store(premium_tier.map_or(0, |lvl| lvl as u8), Ordering::Relaxed);
+		crate::quota::update(
+			self.guild_id,
+			guild_res.ms_transcribed as u64,
+			premium_tier.unwrap_or_default(),
+		);
+		let transcript_language = if guild_res.translate {
+			"en"
 		} else {
-			self.premium_level.store(0, Ordering::Relaxed);
-			self.auto_detect_lang.store(false, Ordering::Relaxed);
+			guild_res.language.as_str()
+		};
+		// filler words go first, so replacement rules don't have to account for them
+		let mut pipeline = Pipeline::standard(&guild_res.language, guild_res.translate);
+		if guild_res.remove_filler_words {
+			pipeline = pipeline.with_guild_stage(FillerWords::new(scripty_i18n::get_filler_words(
+				transcript_language,
+			)));
 		}
-		self.translate.store(guild_res.translate, Ordering::Relaxed);
-		std::mem::swap(&mut *self.language.write(), &mut guild_res.language);
+		if guild_res.numbers_as_digits {
+			pipeline = pipeline.with_guild_stage(SpokenNumbers::new(transcript_language));
+		}
+		let replacements = scripty_utils::replacements::get_replacements(self.guild_id).await?;
+		let mut pipeline = pipeline.with_guild_stage(Replacements::new(
+			replacements.into_iter().map(|r| (r.pattern, r.replacement)),
+		));
+		// last, so it measures what would actually be sent
+		if guild_res.min_transcript_length > 0 {
+			pipeline = pipeline.with_guild_stage(MinLength::new(
+				guild_res.min_transcript_length as usize,
+				LengthUnit::from_db(guild_res.min_transcript_length_unit),
+			));
+		}
+		let translate_button = self.translate_button_label(transcript_language).await?;
+		*self.settings.write() = Arc::new(SessionSettings {
+			language: std::mem::take(&mut guild_res.language),
+			verbose: guild_res.be_verbose,
+			auto_detect_lang: premium_tier.is_some() && guild_res.auto_detect_lang,
+			allowed_languages: std::mem::take(&mut guild_res.auto_detect_languages),
+			translate: guild_res.translate,
+			post_process: pipeline,
+			profanity_filter: ProfanityMode::from_db(guild_res.profanity_filter),
+			translate_button,
+			timestamps,
+			transcript_style: TranscriptStyle::from_db(guild_res.transcript_style),
+			silence_timeout: Duration::from_millis(
+				u16::try_from(guild_res.silence_timeout_ms)
+					.unwrap_or(0)
+					.into(),
+			),
+			voice_command_role: guild_res
+				.voice_command_role
+				.map(|role| RoleId::new(role as u64)),
+			spoiler: age_restricted_policy == AgeRestrictedPolicy::Spoiler,
+		});
 		std::mem::swap(
 			&mut *self.transcribe_only_role.write(),
 			&mut guild_res
 				.transcript_only_role
 				.map(|x| RoleId::new(x as u64)),
 		);
+		crate::mirrors::load_mirrors(
+			&self.context,
+			self.guild_id,
+			self.voice_channel_id,
+			self.thread_id.unwrap_or(self.channel_id),
+			age_restricted_policy == AgeRestrictedPolicy::AgeRestrictedOnly,
+		)
+		.await?;
+		let automations = scripty_utils::automations::get_automations(self.guild_id).await?;
+		crate::automations::set_automations(self.guild_id, automations);
+		let watched = scripty_utils::watch::get_watched(self.guild_id).await?;
+		let watch_changed = crate::watch::set_watchlist(self.guild_id, watched);
+		let role_policies = scripty_utils::role_policy::get_role_policies(self.guild_id).await?;
+		let policies_changed = crate::role_policy::set_role_policies(
+			&self.context.cache,
+			self.guild_id,
+			&role_policies,
+		);
+		if watch_changed || policies_changed {
+			// everyone who's already spoken was checked against the old rules
+			self.recheck_transcribed().await;
+		}
 
 		Ok(())
 	}
+
+	/// Decide again whether everyone who's spoken this session should be transcribed.
+	async fn recheck_transcribed(&self) {
+		let transcribe_only_role = *self.transcribe_only_role.read();
+		let speakers: Vec<(u32, u64)> = self
+			.ssrc_state
+			.ssrc_user_id_map
+			.iter()
+			.map(|x| (*x.key(), *x.value()))
+			.collect();
+
+		for (ssrc, user_id) in speakers {
+			let user_id = UserId::new(user_id);
+			let member = match scripty_utils::entity_cache::get_member_details(
+				&self.context,
+				self.guild_id,
+				user_id,
+			)
+			.await
+			{
+				Ok(member) => member,
+				Err(e) => {
+					warn!(%ssrc, "failed to fetch member to recheck who's transcribed: {}", e);
+					continue;
+				}
+			};
+			let transcribed = crate::watch::is_transcribed(
+				self.guild_id,
+				user_id,
+				&member.roles,
+				transcribe_only_role,
+			);
+			if let Some(mut user_data) = self.ssrc_state.ssrc_user_data_map.get_mut(&ssrc) {
+				user_data.2 = transcribed;
+			}
+		}
+	}
+
+	/// Label for the translate button on transcripts, if anyone in the voice channel reads a
+	/// different language to `transcript_language`.
+	async fn translate_button_label(
+		&self,
+		transcript_language: &str,
+	) -> Result<Option<String>, sqlx::Error> {
+		if !scripty_utils::translation::is_enabled() {
+			return Ok(None);
+		}
+		let members: Vec<UserId> =
+			self.context
+				.cache
+				.guild(self.guild_id)
+				.map_or_else(Vec::new, |guild| {
+					guild
+						.voice_states
+						.iter()
+						.filter(|(_, state)| state.channel_id == Some(self.voice_channel_id))
+						.map(|(user_id, _)| *user_id)
+						.collect()
+				});
+		if members.is_empty()
+			|| !scripty_utils::translation::any_need_translation(&members, transcript_language)
+				.await?
+		{
+			return Ok(None);
+		}
+
+		let language = scripty_i18n::get_guild_language(self.guild_id.get()).await;
+		Ok(Some(format_message!(
+			language,
+			"transcript-translate-button"
+		)))
+	}
 }
 
 #[async_trait::async_trait]
 impl EventHandler for AudioHandler {
 	async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
 		match ctx {
-			EventContext::SpeakingStateUpdate(state_update) => tokio::spawn(speaking_state_update(
-				*state_update,
-				self.context.clone(),
-				Arc::clone(&self.ssrc_state),
-				self.seen_users.clone(),
-				self.guild_id,
-				*self.transcribe_only_role.read(),
-			)),
-			EventContext::VoiceTick(voice_data) => tokio::spawn(voice_tick(
-				voice_data.clone(),
-				Arc::clone(&self.ssrc_state),
-				self.guild_id,
-				self.language.clone(),
-				self.verbose.clone(),
-				self.context.clone(),
-				Arc::clone(&self.webhook),
-				self.thread_id,
-				self.transcript_results.clone(),
-				Arc::clone(&self.automod_server_cfg),
-				Arc::clone(&self.auto_detect_lang),
-				Arc::clone(&self.translate),
-			)),
-			EventContext::ClientDisconnect(client_disconnect_data) => {
-				tokio::spawn(client_disconnect(
+			EventContext::SpeakingStateUpdate(state_update) => spawn_logged(
+				"speaking_state_update",
+				speaking_state_update(
+					*state_update,
+					self.context.clone(),
+					Arc::clone(&self.ssrc_state),
+					self.guild_id,
+					*self.transcribe_only_role.read(),
+				),
+			),
+			EventContext::VoiceTick(voice_data) => spawn_logged(
+				"voice_tick",
+				voice_tick(
+					voice_data.clone(),
+					Arc::clone(&self.ssrc_state),
+					self.guild_id,
+					Arc::clone(&self.settings.read()),
+					self.context.clone(),
+					Arc::clone(&self.webhook),
+					self.thread_id,
+					self.transcript_results.clone(),
+					self.seen_users.clone(),
+					self.transcript_session.clone(),
+					Arc::clone(&self.automod_server_cfg),
+					Arc::clone(&self.speaker_labels),
+					Arc::clone(&self.anonymous_speakers),
+					Arc::clone(&self.coalescer),
+					Arc::clone(&self.paused),
+					self.started_at,
+				),
+			),
+			EventContext::ClientDisconnect(client_disconnect_data) => spawn_logged(
+				"client_disconnect",
+				client_disconnect(
 					*client_disconnect_data,
+					self.guild_id,
 					Arc::clone(&self.ssrc_state),
 					Arc::clone(&self.premium_level),
 					self.context.clone(),
 					Arc::clone(&self.webhook),
 					self.thread_id,
 					self.transcript_results.clone(),
-				))
-			}
+					Arc::clone(&self.anonymous_speakers),
+				),
+			),
 			EventContext::DriverConnect(connect_data)
-			| EventContext::DriverReconnect(connect_data) => tokio::spawn(driver_connect(
-				connect_data.session_id.to_owned(),
-				connect_data.guild_id,
-				connect_data.ssrc,
-				Arc::clone(&self.ssrc_state),
-			)),
-			EventContext::DriverDisconnect(disconnect_data) => tokio::spawn(driver_disconnect(
-				disconnect_data.guild_id,
-				disconnect_data.reason,
-				self.context.clone(),
-				Arc::clone(&self.webhook),
-				self.channel_id,
-				self.voice_channel_id,
-				self.thread_id,
-				self.transcript_results.clone(),
-				self.seen_users.clone(),
-			)),
+			| EventContext::DriverReconnect(connect_data) => spawn_logged(
+				"driver_connect",
+				driver_connect(
+					connect_data.session_id.to_owned(),
+					connect_data.guild_id,
+					connect_data.ssrc,
+					Arc::clone(&self.ssrc_state),
+				),
+			),
+			EventContext::DriverDisconnect(disconnect_data) => spawn_logged(
+				"driver_disconnect",
+				driver_disconnect(
+					disconnect_data.guild_id,
+					disconnect_data.reason,
+					self.context.clone(),
+					Arc::clone(&self.webhook),
+					self.channel_id,
+					self.voice_channel_id,
+					self.thread_id,
+					self.transcript_results.clone(),
+					self.seen_users.clone(),
+					self.transcript_session.clone(),
+					self.started_at,
+				),
+			),
 			_ => return None,
 		};
 		None
@@ -0,0 +1,193 @@
+//! Spoken-keyword automations, set with `/automation`: saying a phrase in the voice chat posts a
+//! message, pings a role, or fires the guild's webhook.
+//!
+//! Phrases are matched against what was actually said, like automod, so profanity filtering
+//! doesn't stop them from matching.
+
+use std::{
+	sync::{Arc, OnceLock as OnceCell},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use scripty_automod::keywords::KeywordMatcher;
+use scripty_utils::{
+	automations::{Automation, AutomationAction},
+	delivery::{self, DeliveryStatus, DeliveryTarget},
+	spawn_logged,
+};
+use serde::Serialize;
+use serenity::{
+	all::GuildId,
+	builder::{CreateAllowedMentions, ExecuteWebhook},
+	client::Context,
+	prelude::Mentionable,
+};
+
+use crate::render::TranscriptLine;
+
+/// Shortest time between two runs of the same automation, so saying the phrase a few times in a
+/// row doesn't ping everyone for each one.
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+struct Trigger {
+	automation: Automation,
+	last_run:   Mutex<Option<Instant>>,
+}
+
+type GuildAutomations = DashMap<GuildId, KeywordMatcher<Arc<Trigger>>, ahash::RandomState>;
+
+static AUTOMATIONS: OnceCell<GuildAutomations> = OnceCell::new();
+
+fn get_automations() -> &'static GuildAutomations {
+	AUTOMATIONS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Set `guild_id`'s automations, where empty turns them off.
+pub(crate) fn set_automations(guild_id: GuildId, automations: Vec<Automation>) {
+	if automations.is_empty() {
+		get_automations().remove(&guild_id);
+		return;
+	}
+	let mut matcher = KeywordMatcher::new();
+	for automation in automations {
+		let phrase = automation.phrase.clone();
+		let trigger = Trigger {
+			automation,
+			last_run: Mutex::new(None),
+		};
+		matcher.insert(&phrase, Arc::new(trigger));
+	}
+	get_automations().insert(guild_id, matcher);
+}
+
+#[derive(Serialize)]
+struct AutomationTriggered<'a> {
+	event:         &'static str,
+	guild_id:      String,
+	automation_id: i32,
+	phrase:        &'a str,
+	message:       Option<&'a str>,
+	/// Who said it, as shown in the transcript.
+	speaker:       &'a str,
+	/// The transcript it was said in.
+	transcript:    &'a str,
+}
+
+/// Run the automations of `guild_id` whose phrases are in `text`, which was transcribed as `line`.
+/// Returns the messages to post in the transcript channel.
+pub(crate) async fn run(
+	ctx: &Context,
+	guild_id: GuildId,
+	text: &str,
+	line: &TranscriptLine,
+) -> Vec<ExecuteWebhook> {
+	let triggered: Vec<Arc<Trigger>> = match get_automations().get(&guild_id) {
+		Some(matcher) => matcher
+			.find_all(text)
+			.into_iter()
+			.filter(|trigger| {
+				let mut last_run = trigger.last_run.lock();
+				if last_run.map_or(false, |at| at.elapsed() < COOLDOWN) {
+					return false;
+				}
+				*last_run = Some(Instant::now());
+				true
+			})
+			.cloned()
+			.collect(),
+		None => return Vec::new(),
+	};
+
+	let mut hooks = Vec::new();
+	for trigger in triggered {
+		let automation = &trigger.automation;
+		debug!(%guild_id, automation_id = automation.id, "running automation");
+		match automation.action {
+			AutomationAction::Message => {
+				let Some(message) = automation.message.as_deref() else {
+					continue;
+				};
+				hooks.push(
+					ExecuteWebhook::new()
+						.content(message)
+						.allowed_mentions(CreateAllowedMentions::new()),
+				);
+			}
+			AutomationAction::PingRole => {
+				let Some(role_id) = automation.role_id else {
+					continue;
+				};
+				let content = match automation.message.as_deref() {
+					Some(message) => format!("{} {}", role_id.mention(), message),
+					None => {
+						let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+						format_message!(
+							language,
+							"automation-ping",
+							role: role_id.mention().to_string(),
+							speaker: line.username.as_str(),
+							phrase: automation.phrase.as_str()
+						)
+					}
+				};
+				hooks.push(
+					ExecuteWebhook::new()
+						.content(content)
+						.allowed_mentions(CreateAllowedMentions::new().roles(vec![role_id])),
+				);
+			}
+			AutomationAction::Webhook => {
+				let ctx = ctx.clone();
+				let trigger = Arc::clone(&trigger);
+				let line = line.clone();
+				spawn_logged("automation_webhook", async move {
+					fire_webhook(&ctx, guild_id, &trigger.automation, &line).await;
+				});
+			}
+		}
+	}
+	hooks
+}
+
+/// Tell `guild_id`'s webhook that `automation` was triggered by `line`.
+async fn fire_webhook(
+	ctx: &Context,
+	guild_id: GuildId,
+	automation: &Automation,
+	line: &TranscriptLine,
+) {
+	let payload = AutomationTriggered {
+		event:         "automation.triggered",
+		guild_id:      guild_id.to_string(),
+		automation_id: automation.id,
+		phrase:        &automation.phrase,
+		message:       automation.message.as_deref(),
+		speaker:       &line.username,
+		transcript:    &line.text,
+	};
+	let automation_id = automation.id;
+	let dedupe_key = format!(
+		"automation:{}:{}:{}",
+		guild_id,
+		automation_id,
+		SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |d| d.as_nanos())
+	);
+	match delivery::deliver(
+		&ctx.http,
+		DeliveryTarget::GuildWebhook(guild_id),
+		&payload,
+		&dedupe_key,
+	)
+	.await
+	{
+		Ok(DeliveryStatus::Sent) => {}
+		Ok(DeliveryStatus::Queued) => {
+			debug!(%guild_id, automation_id, "automation webhook queued for retry");
+		}
+		Err(e) => warn!(%guild_id, automation_id, "failed to fire automation webhook: {}", e),
+	}
+}
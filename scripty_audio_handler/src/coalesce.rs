@@ -0,0 +1,121 @@
+//! Merging a speaker's back-to-back transcripts into one message, set with
+//! `/config merge_utterances`.
+//!
+//! If the same person speaks again within the guild's gap, and nobody else was transcribed
+//! in between, their last message is edited to add the new transcript instead of sending another.
+
+use std::{
+	sync::atomic::{AtomicU16, AtomicU64, Ordering},
+	time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use serenity::{
+	all::{ChannelId, MessageId},
+	builder::EditWebhookMessage,
+};
+
+use crate::render::{TranscriptLine, TranscriptStyle};
+
+/// Longest merged transcript. Past this, a new message is started, leaving room under
+/// Discord's limit of 2000 for the timestamp, label and name around it.
+const MAX_MERGED_LEN: usize = 1800;
+
+/// What to do with a finished transcript.
+pub enum Merge {
+	/// Merging is off: send the transcript as normal.
+	Off,
+	/// Send a new message, then pass its ID to `Coalescer::sent` so later ones can merge into it.
+	New(u64),
+	/// Edit this message, which now holds both transcripts, instead of sending a new one.
+	Edit(MessageId, EditWebhookMessage),
+}
+
+struct LastTranscript {
+	seq:        u64,
+	ssrc:       u32,
+	/// None until the message has been sent.
+	message_id: Option<MessageId>,
+	line:       TranscriptLine,
+	style:      TranscriptStyle,
+	at:         Instant,
+}
+
+#[derive(Default)]
+pub struct Coalescer {
+	/// Longest pause between a speaker's transcripts that still merges them, 0 if off.
+	gap_secs: AtomicU16,
+	next_seq: AtomicU64,
+	last:     Mutex<Option<LastTranscript>>,
+}
+
+impl Coalescer {
+	pub fn set_gap(&self, gap_secs: u16) {
+		self.gap_secs.store(gap_secs, Ordering::Relaxed);
+	}
+
+	/// Decide whether `line`, just transcribed from `ssrc`, merges into the last message.
+	pub fn push(
+		&self,
+		ssrc: u32,
+		line: &TranscriptLine,
+		style: TranscriptStyle,
+		thread_id: Option<ChannelId>,
+	) -> Merge {
+		let gap = Duration::from_secs(self.gap_secs.load(Ordering::Relaxed).into());
+		let mut last = self.last.lock();
+		if gap.is_zero() {
+			*last = None;
+			return Merge::Off;
+		}
+
+		if let Some(prev) = last.as_mut().filter(|prev| {
+			prev.ssrc == ssrc
+				&& prev.at.elapsed() <= gap
+				&& prev.line.text.chars().count() + line.text.chars().count() < MAX_MERGED_LEN
+		}) {
+			// the last message may still be on its way, in which case there's nothing to edit yet
+			if let Some(message_id) = prev.message_id {
+				prev.line.text.push(' ');
+				prev.line.text.push_str(&line.text);
+				prev.line.duration += line.duration;
+				prev.at = Instant::now();
+
+				let mut edit = prev.line.to_edit(prev.style);
+				if let Some(thread_id) = thread_id {
+					edit = edit.in_thread(thread_id);
+				}
+				return Merge::Edit(message_id, edit);
+			}
+		}
+
+		let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+		*last = Some(LastTranscript {
+			seq,
+			ssrc,
+			message_id: None,
+			line: line.clone(),
+			style,
+			at: Instant::now(),
+		});
+		Merge::New(seq)
+	}
+
+	/// Record the ID of the message sent for `Merge::New(seq)`.
+	pub fn sent(&self, seq: u64, message_id: MessageId) {
+		if let Some(last) = self.last.lock().as_mut().filter(|last| last.seq == seq) {
+			last.message_id = Some(message_id);
+		}
+	}
+
+	/// Stop merging into `message_id`, ie because editing it failed.
+	pub fn forget(&self, message_id: MessageId) {
+		let mut last = self.last.lock();
+		if last
+			.as_ref()
+			.map_or(false, |last| last.message_id == Some(message_id))
+		{
+			*last = None;
+		}
+	}
+}
@@ -2,13 +2,13 @@ use ahash::RandomState;
 use dashmap::DashMap;
 use scripty_premium::PremiumTierList;
 use serenity::{
-	builder::{CreateWebhook, ExecuteWebhook},
+	builder::ExecuteWebhook,
 	model::id::{ChannelId, GuildId},
 	prelude::Context,
 };
 use songbird::{error::JoinError, events::Event, CoreEvent};
 
-use crate::Error;
+use crate::{Error, SessionKey};
 
 // TODO: implement `force`
 #[allow(clippy::let_unit_value)]
@@ -21,33 +21,31 @@ pub async fn connect_to_vc(
 	_force: bool,
 	record_transcriptions: bool,
 ) -> Result<(), Error> {
-	debug!(%guild_id, "fetching webhook");
-	// thanks to Discord undocumented breaking changes, we have to do this
-	// <3 shitcord
-	let hooks = channel_id.webhooks(&ctx).await?;
-	let webhook = if hooks.is_empty() {
-		channel_id
-			.create_webhook(&ctx, CreateWebhook::new("Scripty Transcriptions"))
-			.await?
-	} else {
-		// iterate through each hook and find one where token is not None
-		// if none are found, create a new one
-		let mut found = None;
-		for hook in hooks {
-			if hook.token.is_some() {
-				found = Some(hook);
-				break;
-			}
-		}
-		match found {
-			Some(hook) => hook,
-			None => {
-				channel_id
-					.create_webhook(&ctx, CreateWebhook::new("Scripty Transcriptions"))
-					.await?
-			}
+	// each bot has its own session in a guild
+	let key = SessionKey::new(&ctx, guild_id);
+
+	// sessions are counted across every guild sharing a subscription, on every cluster
+	debug!(%guild_id, "checking session limit");
+	if let Some((tier, other_sessions)) =
+		crate::session_store::get_subscription_sessions(key).await?
+	{
+		let limit = tier.limits().max_sessions;
+		if other_sessions >= i64::from(limit) {
+			debug!(%guild_id, ?tier, other_sessions, "subscription is at its session limit");
+			return Err(Error::session_limit(limit));
 		}
-	};
+	}
+
+	// members there are idle, so transcribing it is always a mistake
+	if crate::is_afk_channel(&ctx, guild_id, voice_channel_id) {
+		return Err(Error::afk_channel());
+	}
+
+	debug!(%guild_id, "checking age-restricted policy");
+	crate::age_restricted::check_policy(&ctx, guild_id, voice_channel_id, channel_id).await?;
+
+	debug!(%guild_id, "fetching webhook");
+	let webhook = scripty_utils::entity_cache::get_channel_webhook(&ctx, channel_id).await?;
 
 	// automatically leave after the specified time period
 	let premium_tier = scripty_premium::get_guild(guild_id.get()).await;
@@ -106,11 +104,22 @@ pub async fn connect_to_vc(
 	call.add_global_event(Event::Core(CoreEvent::DriverDisconnect), handler.clone());
 	call.add_global_event(Event::Core(CoreEvent::DriverReconnect), handler);
 
+	// remember this session, so it can be resumed if we restart
+	crate::session_store::persist_session(crate::PersistedSession {
+		guild_id,
+		bot_id: key.bot_id,
+		channel_id,
+		voice_channel_id,
+		thread_id,
+		record_transcriptions,
+	})
+	.await?;
+
 	// spawn background tasks to automatically leave the call after the specified time period
 	let (tx, rx) = tokio::sync::oneshot::channel::<()>();
 	let existing = super::AUTO_LEAVE_TASKS
 		.get_or_init(|| DashMap::with_hasher(RandomState::default()))
-		.insert(guild_id, tx);
+		.insert(key, tx);
 	if let Some(existing) = existing {
 		// cancel the existing task
 		let _ = existing.send(()); // ignore errors as the task may have already been cancelled
@@ -126,7 +135,7 @@ pub async fn connect_to_vc(
 		webhook_executor = webhook_executor.in_thread(thread_id);
 	}
 
-	tokio::spawn(async move {
+	scripty_utils::spawn_logged("auto_leave_timer", async move {
 		tokio::select! {
 			_ = tokio::time::sleep(std::time::Duration::from_secs(leave_delta)) => {},
 			_ = rx => {
@@ -140,6 +149,7 @@ pub async fn connect_to_vc(
 			error!(%guild_id, "failed to leave call: {}", e);
 			return;
 		}
+		crate::session_store::remove_session(key).await;
 
 		// send a message to the channel
 		let m = webhook.execute(ctx2, false, webhook_executor).await;
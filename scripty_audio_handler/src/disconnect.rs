@@ -2,19 +2,32 @@ use dashmap::DashMap;
 use serenity::{client::Context, model::id::GuildId};
 use songbird::error::JoinError;
 
-use crate::error::Error;
+use crate::{error::Error, SessionKey};
 
 pub async fn disconnect_from_vc(ctx: &Context, guild_id: GuildId) -> Result<bool, Error> {
+	let key = SessionKey::new(ctx, guild_id);
 	let sb = songbird::get(ctx).await.expect("songbird not initialized");
 	let res = match sb.remove(guild_id).await {
 		Ok(()) => Ok(true),
 		Err(JoinError::NoCall) => Ok(false),
 		Err(e) => Err(e.into()),
 	};
+	crate::session_stats::unregister_session(key);
+	crate::tts_relay::set_relay(key, None);
+	crate::hold_queue::set_hold(key, None);
+	crate::join_leave::set_notices(key, None);
+	// the rest is the guild's config, which another bot's session there still uses
+	if !crate::session_stats::has_session(guild_id) {
+		crate::mirrors::clear_mirrors(guild_id);
+		crate::automations::set_automations(guild_id, Vec::new());
+		crate::watch::set_watchlist(guild_id, Vec::new());
+		crate::role_policy::clear_role_policies(guild_id);
+	}
+	crate::session_store::remove_session(key).await;
 
 	let existing = super::AUTO_LEAVE_TASKS
 		.get_or_init(|| DashMap::with_hasher(ahash::RandomState::default()))
-		.remove(&guild_id);
+		.remove(&key);
 	if let Some(existing) = existing {
 		// cancel the existing auto-leave task
 		let _ = existing.1.send(()); // ignore errors as the task may have already been cancelled
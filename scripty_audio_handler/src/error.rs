@@ -5,6 +5,7 @@ use std::{
 
 use backtrace::Backtrace;
 use scripty_db::sqlx;
+use serenity::{all::GuildId, client::Context};
 use songbird::error::JoinError;
 
 pub struct Error {
@@ -17,9 +18,45 @@ pub enum ErrorKind {
 	Join(JoinError),
 	Database(sqlx::Error),
 	Serenity(serenity::Error),
+	/// The guild's premium subscription already has as many voice sessions going as it allows.
+	SessionLimit {
+		limit: u32,
+	},
+	/// The voice chat is the guild's AFK channel.
+	AfkChannel,
+	/// The voice chat is age-restricted, and this server doesn't transcribe those.
+	AgeRestrictedRefused,
+	/// The voice chat is age-restricted, and transcripts of it can only go to age-restricted
+	/// channels.
+	AgeRestrictedTarget,
+}
+
+impl ErrorKind {
+	/// Short name of this kind of error, used as a metric label.
+	pub fn name(&self) -> &'static str {
+		match self {
+			ErrorKind::Join(JoinError::TimedOut) => "join_timed_out",
+			ErrorKind::Join(JoinError::Dropped) => "join_dropped",
+			ErrorKind::Join(_) => "join",
+			ErrorKind::Database(_) => "database",
+			ErrorKind::Serenity(_) => "serenity",
+			ErrorKind::SessionLimit { .. } => "session_limit",
+			ErrorKind::AfkChannel => "afk_channel",
+			ErrorKind::AgeRestrictedRefused => "age_restricted_refused",
+			ErrorKind::AgeRestrictedTarget => "age_restricted_target",
+		}
+	}
 }
 
 impl Error {
+	fn new(kind: ErrorKind) -> Self {
+		scripty_metrics::record_error("scripty_audio_handler", kind.name());
+		Self {
+			kind,
+			backtrace: Backtrace::new_unresolved(),
+		}
+	}
+
 	pub fn is_timed_out(&self) -> bool {
 		matches!(self.kind, ErrorKind::Join(JoinError::TimedOut))
 	}
@@ -27,35 +64,65 @@ impl Error {
 	pub fn is_dropped(&self) -> bool {
 		matches!(self.kind, ErrorKind::Join(JoinError::Dropped))
 	}
+
+	/// Create an error for hitting a subscription's session limit.
+	pub(crate) fn session_limit(limit: u32) -> Self {
+		Self::new(ErrorKind::SessionLimit { limit })
+	}
+
+	/// Create an error for trying to transcribe the guild's AFK channel.
+	pub(crate) fn afk_channel() -> Self {
+		Self::new(ErrorKind::AfkChannel)
+	}
+
+	/// Create an error for trying to transcribe an age-restricted voice chat when that's refused.
+	pub(crate) fn age_restricted_refused() -> Self {
+		Self::new(ErrorKind::AgeRestrictedRefused)
+	}
+
+	/// Create an error for sending an age-restricted voice chat's transcripts to a channel that
+	/// isn't age-restricted.
+	pub(crate) fn age_restricted_target() -> Self {
+		Self::new(ErrorKind::AgeRestrictedTarget)
+	}
+
+	/// Report this error to Sentry, tagged with the guild and shard it happened on.
+	///
+	/// Does nothing if Sentry is not configured.
+	pub fn report(&mut self, ctx: &Context, guild_id: GuildId) {
+		self.backtrace.resolve();
+		let backtrace = format!("{:?}", self.backtrace);
+
+		sentry::with_scope(
+			|scope| {
+				scope.set_tag("guild_id", guild_id);
+				scope.set_tag("shard_id", ctx.shard_id);
+				scope.set_tag("source", "audio_handler");
+				scope.set_extra("backtrace", backtrace.into());
+			},
+			|| sentry::capture_message(&self.to_string(), sentry::Level::Error),
+		);
+	}
 }
 
 impl From<JoinError> for Error {
 	#[inline]
 	fn from(e: JoinError) -> Self {
-		Self {
-			kind:      ErrorKind::Join(e),
-			backtrace: Backtrace::new_unresolved(),
-		}
+		Self::new(ErrorKind::Join(e))
 	}
 }
 
 impl From<sqlx::Error> for Error {
 	#[inline]
 	fn from(e: sqlx::Error) -> Self {
-		Self {
-			kind:      ErrorKind::Database(e),
-			backtrace: Backtrace::new_unresolved(),
-		}
+		Self::new(ErrorKind::Database(e))
 	}
 }
 
 impl From<serenity::Error> for Error {
 	#[inline]
 	fn from(e: serenity::Error) -> Self {
-		Self {
-			kind:      ErrorKind::Serenity(e),
-			backtrace: Backtrace::new_unresolved(),
-		}
+		Self::new(ErrorKind::Serenity(e))
 	}
 }
 
@@ -65,6 +132,22 @@ impl Display for Error {
 			ErrorKind::Join(e) => write!(f, "JoinError: {}", e),
 			ErrorKind::Database(e) => write!(f, "DatabaseError: {}", e),
 			ErrorKind::Serenity(e) => write!(f, "SerenityError: {}", e),
+			ErrorKind::SessionLimit { limit } => write!(
+				f,
+				"SessionLimit: this server's premium subscription can only have {} voice sessions \
+				 at once",
+				limit
+			),
+			ErrorKind::AfkChannel => write!(f, "AfkChannel: the AFK channel is never transcribed"),
+			ErrorKind::AgeRestrictedRefused => write!(
+				f,
+				"AgeRestrictedRefused: this server doesn't transcribe age-restricted voice chats"
+			),
+			ErrorKind::AgeRestrictedTarget => write!(
+				f,
+				"AgeRestrictedTarget: transcripts of age-restricted voice chats can only be sent \
+				 to age-restricted channels"
+			),
 		}
 	}
 }
@@ -4,21 +4,28 @@ use std::sync::{
 };
 
 use serenity::{
-	all::{ChannelId, Context, Webhook},
+	all::{ChannelId, Context, GuildId, Webhook},
 	builder::ExecuteWebhook,
 };
 use songbird::model::payload::ClientDisconnect;
 
-use crate::{audio_handler::ArcSsrcMaps, types::TranscriptResults};
+use crate::{
+	anonymous_speakers::AnonymousSpeakers,
+	audio_handler::ArcSsrcMaps,
+	types::TranscriptResults,
+	SessionKey,
+};
 
 pub async fn client_disconnect(
 	client_disconnect_data: ClientDisconnect,
+	guild_id: GuildId,
 	ssrc_state: ArcSsrcMaps,
 	premium_level: Arc<AtomicU8>,
 	ctx: Context,
 	webhook: Arc<Webhook>,
 	thread_id: Option<ChannelId>,
 	transcript_results: TranscriptResults,
+	anonymous_speakers: Arc<AnonymousSpeakers>,
 ) {
 	let user_id = client_disconnect_data.user_id;
 
@@ -40,12 +47,19 @@ pub async fn client_disconnect(
 
 	assert!(ssrc_state.ssrc_user_id_map.remove(&ssrc).is_some());
 	ssrc_state.ssrc_stream_map.remove(&ssrc);
+	ssrc_state.ssrc_last_sequence.remove(&ssrc);
+	ssrc_state.ssrc_utterance_start.remove(&ssrc);
+	ssrc_state.ssrc_utterance_audio.remove(&ssrc);
 	ssrc_state.ssrc_ignored_map.remove(&ssrc);
 	ssrc_state.ssrc_voice_ingest_map.remove(&ssrc);
+	let spoke = ssrc_state.ssrc_spoken.remove(&ssrc).is_some();
 	let Some((_, (username, avatar_url, _))) = ssrc_state.ssrc_user_data_map.remove(&ssrc) else {
 		warn!(%ssrc, "got no user data for ssrc");
 		return;
 	};
+	let (username, avatar_url) = anonymous_speakers
+		.get(user_id.0)
+		.unwrap_or((username, avatar_url));
 
 	#[allow(clippy::wildcard_in_or_patterns)]
 	let max_users = match premium_level.load(Ordering::Relaxed) {
@@ -68,6 +82,12 @@ pub async fn client_disconnect(
 		}
 	}
 
+	// listeners who never spoke were never announced, so their leaving isn't either.
+	// join and leave notices already say when anyone leaves
+	if !spoke || crate::join_leave::is_enabled(SessionKey::new(&ctx, guild_id)) {
+		return;
+	}
+
 	let mut webhook_builder = ExecuteWebhook::new()
 		.content(format!("{} disconnected", &username))
 		.avatar_url(avatar_url)
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, sync::Arc, time::SystemTime};
 
 use serenity::{
 	all::UserId,
@@ -11,7 +11,10 @@ use songbird::{events::context_data::DisconnectReason, id::GuildId, model::Close
 use crate::{
 	connect_to_vc,
 	error::ErrorKind,
+	session_webhook,
+	transcript_store::TranscriptSession,
 	types::{SeenUsers, TranscriptResults},
+	SessionKey,
 };
 
 pub async fn driver_disconnect(
@@ -24,8 +27,26 @@ pub async fn driver_disconnect(
 	thread_id: Option<ChannelId>,
 	transcript_results: TranscriptResults,
 	seen_users: SeenUsers,
+	transcript_session: Arc<TranscriptSession>,
+	started_at: SystemTime,
 ) {
 	debug!(?guild_id, "handler disconnected");
+	// reconnecting makes a new handler, with a new session
+	transcript_session.end().await;
+	let highlights = transcript_session.highlights();
+	let transcript = transcript_results
+		.as_ref()
+		.map(|results| results.read().clone());
+	session_webhook::send_session_ended(
+		&ctx.http,
+		serenity::all::GuildId::new(guild_id.0.get()),
+		voice_channel_id,
+		started_at,
+		&transcript_session,
+		transcript.as_deref(),
+		&highlights,
+	)
+	.await;
 	let (should_reconnect, reason) = match reason {
 		Some(DisconnectReason::AttemptDiscarded) => {
 			warn!(?guild_id, "reconnection failed due to another request");
@@ -71,6 +92,25 @@ pub async fn driver_disconnect(
 		}
 	};
 
+	// disconnected for good, so there's nothing to resume later
+	// if there's no reason, we asked to leave, and whoever asked handles this
+	if !should_reconnect && reason.is_some() {
+		let guild_id = serenity::all::GuildId::new(guild_id.0.get());
+		crate::session_store::remove_session(SessionKey::new(&ctx, guild_id)).await;
+	}
+
+	// the voice chat isn't over if we're reconnecting, so subscribers wait for the end
+	if !should_reconnect {
+		crate::subscriptions::send(
+			&ctx,
+			serenity::all::GuildId::new(guild_id.0.get()),
+			voice_channel_id,
+			channel_id,
+			&transcript_session,
+		)
+		.await;
+	}
+
 	if should_reconnect {
 		debug!(?guild_id, "scheduling reconnect");
 		// retry connection in 30 seconds
@@ -78,14 +118,15 @@ pub async fn driver_disconnect(
 		let webhook2 = webhook.clone();
 		let ctx2 = ctx.clone();
 		let ctx3 = ctx.clone();
-		tokio::spawn(async move {
+		scripty_utils::spawn_logged("voice_reconnect", async move {
 			debug!(?guild_id, "sleeping 30 seconds");
 			tokio::time::sleep(std::time::Duration::from_secs(30)).await;
 			debug!(?guild_id, "attempting reconnect");
 
+			let serenity_guild_id = serenity::all::GuildId::new(guild_id.0.get());
 			if let Err(ErrorKind::Join(e)) = connect_to_vc(
 				ctx2,
-				serenity::all::GuildId::new(guild_id.0.get()),
+				serenity_guild_id,
 				channel_id,
 				voice_channel_id,
 				thread_id,
@@ -93,8 +134,10 @@ pub async fn driver_disconnect(
 				record_transcriptions,
 			)
 			.await
-			.map_err(|x| x.kind)
-			{
+			.map_err(|mut x| {
+				x.report(&ctx3, serenity_guild_id);
+				x.kind
+			}) {
 				if let Err(e) = webhook2
 					.execute(
 						ctx3,
@@ -139,8 +182,13 @@ pub async fn driver_disconnect(
 	}
 
 	// send all users the results of their transcriptions
-	if let (Some(transcript_results), Some(seen_users)) = (transcript_results, seen_users) {
-		let final_text_output = transcript_results.read().join("\n");
+	if let (Some(transcript), Some(seen_users)) = (transcript, seen_users) {
+		let mut final_text_output = transcript.join("\n");
+		// highlights go at the end, so they're easy to find
+		if !highlights.is_empty() {
+			final_text_output.push_str("\n\nHighlights:\n");
+			final_text_output.push_str(&highlights.join("\n"));
+		}
 		let attachment = CreateAttachment::bytes(final_text_output, "transcript.txt");
 		let message = CreateMessage::new().add_file(attachment.clone()).content(
 			"This transcript was automatically sent to all users who spoke in the voice chat.",
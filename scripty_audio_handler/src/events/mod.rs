@@ -8,4 +8,5 @@ pub use client_disconnect::client_disconnect;
 pub use driver_connect::driver_connect;
 pub use driver_disconnect::driver_disconnect;
 pub use speaking_state_update::speaking_state_update;
+pub(crate) use voice_tick::encode_wav;
 pub use voice_tick::voice_tick;
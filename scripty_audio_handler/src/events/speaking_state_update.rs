@@ -1,16 +1,15 @@
 use serenity::{
-	all::{GuildId, RoleId},
+	all::{GuildId, RoleId, UserId},
 	prelude::Context,
 };
 use songbird::model::payload::Speaking;
 
-use crate::{audio_handler::ArcSsrcMaps, types::SeenUsers};
+use crate::audio_handler::ArcSsrcMaps;
 
 pub async fn speaking_state_update(
 	state_update: Speaking,
 	ctx: Context,
 	ssrc_state: ArcSsrcMaps,
-	seen_users: SeenUsers,
 	guild_id: GuildId,
 	transcribe_only_role: Option<RoleId>,
 ) {
@@ -34,37 +33,35 @@ pub async fn speaking_state_update(
 		}
 	};
 
-	// add to seen users
-	if let Some(seen_users) = seen_users {
-		seen_users.insert(user_id);
-	}
-
 	debug!("checking if either ssrc_ignored_map or ssrc_user_data_map does not contain key");
 	if !ssrc_state.ssrc_ignored_map.contains_key(&ssrc)
 		|| !ssrc_state.ssrc_user_data_map.contains_key(&ssrc)
 	{
 		debug!("either does not contain key, updating data");
-		let user = match serenity::model::id::UserId::new(user_id)
-			.to_user(&ctx)
-			.await
+		// cached, since this runs for everyone who starts talking
+		let member = match scripty_utils::entity_cache::get_member_details(
+			&ctx,
+			guild_id,
+			UserId::new(user_id),
+		)
+		.await
 		{
-			Ok(u) => u,
+			Ok(m) => m,
 			Err(e) => {
-				error!("failed to fetch user: {}", e);
+				error!("failed to fetch member: {}", e);
 				return;
 			}
 		};
 
-		let has_role = if let Some(transcribe_only_role) = transcribe_only_role {
-			user.has_role(&ctx, guild_id, transcribe_only_role)
-				.await
-				.unwrap_or(true) // shouldn't happen often, but if it does, assume they have the role
-		} else {
-			true
-		};
+		let has_role = crate::watch::is_transcribed(
+			guild_id,
+			UserId::new(user_id),
+			&member.roles,
+			transcribe_only_role,
+		);
 
-		let ignored = user.bot;
-		let user_data = (user.tag(), user.face(), has_role);
+		let ignored = member.bot;
+		let user_data = (member.display_name, member.avatar_url, has_role);
 
 		ssrc_state.ssrc_ignored_map.insert(ssrc, ignored);
 		ssrc_state.ssrc_user_data_map.insert(ssrc, user_data);
@@ -1,81 +1,234 @@
 use std::{
+	collections::HashMap,
+	io::Cursor,
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		Arc,
 	},
-	time::Instant,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use ahash::RandomState;
-use dashmap::DashSet;
+use hound::{SampleFormat, WavSpec, WavWriter};
 use parking_lot::RwLock;
 use scripty_automod::types::{AutomodRuleAction, AutomodServerConfig};
 use scripty_metrics::Metrics;
-use scripty_stt::{ModelError, Stream};
+use scripty_stt::{ModelError, Pipeline, ProfanityFilter, Stream, Transformer};
+use scripty_utils::{
+	delivery::{self, DeliveryStatus, DeliveryTarget},
+	spawn_logged,
+	translation::TRANSLATE_BUTTON_ID,
+};
 use serenity::{
-	all::{ChannelId as SerenityChannelId, ChannelId, GuildId, Webhook},
-	builder::{CreateEmbed, CreateMessage, EditMember, ExecuteWebhook},
+	all::{
+		ButtonStyle,
+		ChannelId as SerenityChannelId,
+		ChannelId,
+		GuildId,
+		MessageId,
+		Timestamp,
+		UserId,
+		Webhook,
+	},
+	builder::{
+		CreateActionRow,
+		CreateAttachment,
+		CreateButton,
+		CreateEmbed,
+		CreateMessage,
+		EditMember,
+		ExecuteWebhook,
+	},
 	client::Context,
 };
 use songbird::events::context_data::VoiceTick;
+use tokio::sync::oneshot;
 
 use crate::{
-	audio_handler::SsrcMaps,
+	anonymous_speakers::AnonymousSpeakers,
+	audio_handler::{SessionSettings, SsrcMaps},
+	coalesce::{Coalescer, Merge},
 	consts::SIZE_OF_I16,
-	types::{SsrcUserDataMap, TranscriptResults},
+	quota::QuotaNotice,
+	render::TranscriptLine,
+	shutdown::InFlightGuard,
+	speaker_labels::SpeakerLabels,
+	timestamps::TimestampMode,
+	transcript_store::{TranscriptSession, MESSAGE_ID_TIMEOUT},
+	types::{SeenUsers, TranscriptResults},
+	voice_commands::{self, VoiceCommand},
 };
 
+/// How long `AutomodRuleAction::DeleteLogAndTimeout` times users out for.
+const AUTOMOD_TIMEOUT_SECS: i64 = 10 * 60;
+
+/// Longest recording automod attaches to its logs: 60 seconds at 16kHz.
+const MAX_UTTERANCE_AUDIO_SAMPLES: usize = 60 * 16_000;
+
 pub async fn voice_tick(
 	voice_data: VoiceTick,
 	ssrc_state: Arc<SsrcMaps>,
 	guild_id: GuildId,
-	language: Arc<RwLock<String>>,
-	verbose: Arc<AtomicBool>,
+	settings: Arc<SessionSettings>,
 	ctx: Context,
 	webhook: Arc<Webhook>,
 	thread_id: Option<ChannelId>,
 	transcript_results: Option<Arc<RwLock<Vec<String>>>>,
+	seen_users: SeenUsers,
+	transcript_session: Arc<TranscriptSession>,
 	automod_server_cfg: Arc<AutomodServerConfig>,
-	auto_detect_lang: Arc<AtomicBool>,
-	translate: Arc<AtomicBool>,
+	speaker_labels: Arc<SpeakerLabels>,
+	anonymous_speakers: Arc<AnonymousSpeakers>,
+	coalescer: Arc<Coalescer>,
+	paused: Arc<AtomicBool>,
+	started_at: SystemTime,
 ) {
 	let metrics = scripty_metrics::get_metrics();
 	let tick_start_time = Instant::now();
+	let _in_flight = InFlightGuard::new();
+	let draining = crate::is_draining();
+
+	// users who have finished speaking, and when they stopped
+	let mut finished_speakers = Vec::new();
+	if draining {
+		// shutting down: accept no new audio, and finalize every open stream
+		ssrc_state.ssrc_last_spoke.clear();
+		let now = Instant::now();
+		for stream in ssrc_state.ssrc_stream_map.iter() {
+			finished_speakers.push((*stream.key(), now));
+		}
+	} else {
+		// those who have been silent for at least the guild's timeout are done:
+		// a short one gives faster transcripts, a long one fewer lines cut off mid-sentence
+		let silence_timeout = settings.silence_timeout;
+		ssrc_state.ssrc_last_spoke.retain(|ssrc, last_spoke| {
+			// anyone speaking this tick is added back by handle_speakers
+			if !voice_data.silent.contains(ssrc) {
+				return false;
+			}
+			if last_spoke.elapsed() < silence_timeout {
+				return true;
+			}
+			finished_speakers.push((*ssrc, *last_spoke));
+			false
+		});
 
-	// get all users who were speaking last tick
-	let last_tick_speakers = ssrc_state.ssrc_speaking_set.clone();
-	ssrc_state.ssrc_speaking_set.clear();
-
-	// handle those who were speaking last tick but are now silent
-	last_tick_speakers.retain(|s| voice_data.silent.contains(s));
-
-	// handle those speaking this tick
-	handle_speakers(Arc::clone(&ssrc_state), Arc::clone(&metrics), voice_data).await;
+		// handle those speaking this tick
+		handle_speakers(
+			Arc::clone(&ssrc_state),
+			Arc::clone(&metrics),
+			voice_data,
+			seen_users,
+			guild_id,
+			ctx.shard_id.to_string(),
+			automod_server_cfg.enabled && automod_server_cfg.log_recording,
+		)
+		.await;
+	}
 
 	let hooks = handle_silent_speakers(SilentSpeakersContext {
 		ssrc_state: Arc::clone(&ssrc_state),
-		last_tick_speakers,
-		language: Arc::clone(&language),
-		verbose: Arc::clone(&verbose),
+		finished_speakers,
+		settings: &settings,
 		guild_id,
 		thread_id,
 		automod_server_cfg: Arc::clone(&automod_server_cfg),
 		transcript_results: transcript_results.clone(),
+		transcript_session,
 		ctx: &ctx,
-		auto_detect_lang,
-		translate,
+		speaker_labels,
+		anonymous_speakers,
+		coalescer: Arc::clone(&coalescer),
+		paused,
+		started_at,
 	})
 	.await;
 
 	// spawn background tasks to fire off hooks
-	for (hook, ssrc) in hooks {
+	let delivery_target = DeliveryTarget::webhook(&webhook, thread_id);
+	let webhook_channel = webhook.channel_id;
+	let session = format!(
+		"{}:{}:{}",
+		guild_id,
+		ctx.cache.current_user().id,
+		started_at
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |d| d.as_millis())
+	);
+	// an utterance can have several lines: its transcript, and what automations reply to it with
+	let mut utterance_lines: HashMap<(u32, u64), u32> = HashMap::new();
+	for (hook, merge, ssrc, start_ms, utterance_end, message_txs) in hooks {
 		debug!(%ssrc, "firing webhook");
-		let webhook1 = webhook.clone();
+		let Some(target) = delivery_target.clone() else {
+			warn!(%ssrc, "transcription webhook has no token, dropping transcript");
+			continue;
+		};
+		let line = utterance_lines.entry((ssrc, start_ms)).or_default();
+		*line += 1;
+		// the same line always gets the same key, so it can't be queued twice
+		let dedupe_key = format!("transcript:{}:{}:{}:{}", session, ssrc, start_ms, line);
 		let ctx1 = ctx.clone();
-		tokio::spawn(async move {
-			if let Err(e) = webhook1.execute(ctx1, false, hook).await {
-				warn!(%ssrc, "failed to send transcription final webhook: {}", e);
+		let metrics1 = Arc::clone(&metrics);
+		let webhook1 = Arc::clone(&webhook);
+		let coalescer1 = Arc::clone(&coalescer);
+		let in_flight = InFlightGuard::new();
+		spawn_logged("transcript_webhook", async move {
+			let _in_flight = in_flight;
+			let res = match merge {
+				Merge::Off => {
+					delivery::deliver_with_id(&ctx1.http, target, &hook, &dedupe_key).await
+				}
+				Merge::New(seq) => {
+					delivery::deliver_with_id(&ctx1.http, target, &hook, &dedupe_key)
+						.await
+						.map(|(status, message_id)| {
+							if let Some(message_id) = message_id {
+								coalescer1.sent(seq, message_id);
+							}
+							(status, message_id)
+						})
+				}
+				Merge::Edit(message_id, edit) => {
+					match webhook1.edit_message(&ctx1, message_id, edit).await {
+						Ok(_) => Ok((DeliveryStatus::Sent, Some(message_id))),
+						Err(e) => {
+							// probably deleted, so send this transcript on its own instead
+							debug!(%ssrc, "failed to merge transcript into last message: {}", e);
+							coalescer1.forget(message_id);
+							delivery::deliver_with_id(&ctx1.http, target, &hook, &dedupe_key).await
+						}
+					}
+				}
 			};
+			// lets the stored segment and audio link to the message they're in
+			let res = res.map(|(status, message_id)| {
+				if let Some(message_id) = message_id {
+					for tx in message_txs {
+						let _ = tx.send(message_id);
+					}
+				}
+				status
+			});
+			match res {
+				Ok(DeliveryStatus::Sent) => metrics1
+					.utterance_latency
+					.observe(utterance_end.elapsed().as_secs_f64()),
+				Ok(DeliveryStatus::Queued) => {}
+				Err(e) => {
+					warn!(%ssrc, "failed to send transcription final webhook: {}", e);
+					// it may have been deleted, so look it up again next time
+					if let Some(channel_id) = webhook_channel {
+						scripty_utils::entity_cache::forget_channel_webhook(channel_id).await;
+					}
+				}
+			}
+		});
+	}
+
+	if let Some(notice) = crate::quota::take_notice(guild_id) {
+		let ctx = ctx.clone();
+		let webhook = Arc::clone(&webhook);
+		spawn_logged("quota_notice", async move {
+			send_quota_notice(&ctx, &webhook, thread_id, guild_id, notice).await;
 		});
 	}
 
@@ -86,42 +239,76 @@ pub async fn voice_tick(
 
 struct SilentSpeakersContext<'a> {
 	ssrc_state:         Arc<SsrcMaps>,
-	last_tick_speakers: DashSet<u32, RandomState>,
-	language:           Arc<RwLock<String>>,
-	verbose:            Arc<AtomicBool>,
+	finished_speakers:  Vec<(u32, Instant)>,
+	settings:           &'a SessionSettings,
 	guild_id:           GuildId,
 	thread_id:          Option<ChannelId>,
 	automod_server_cfg: Arc<AutomodServerConfig>,
 	transcript_results: TranscriptResults,
+	transcript_session: Arc<TranscriptSession>,
 	ctx:                &'a Context,
-	auto_detect_lang:   Arc<AtomicBool>,
-	translate:          Arc<AtomicBool>,
+	speaker_labels:     Arc<SpeakerLabels>,
+	anonymous_speakers: Arc<AnonymousSpeakers>,
+	coalescer:          Arc<Coalescer>,
+	paused:             Arc<AtomicBool>,
+	started_at:         SystemTime,
 }
 async fn handle_silent_speakers(
 	SilentSpeakersContext {
 		ssrc_state,
-		last_tick_speakers,
-		language,
-		verbose,
+		finished_speakers,
+		settings,
 		guild_id,
 		thread_id,
 		automod_server_cfg,
 		transcript_results,
+		transcript_session,
 		ctx,
-		auto_detect_lang,
-		translate,
+		speaker_labels,
+		anonymous_speakers,
+		coalescer,
+		paused,
+		started_at,
 	}: SilentSpeakersContext<'_>,
-) -> Vec<(ExecuteWebhook, u32)> {
+) -> Vec<(
+	ExecuteWebhook,
+	Merge,
+	u32,
+	u64,
+	Instant,
+	Vec<oneshot::Sender<MessageId>>,
+)> {
 	// batch up webhooks to send
-	let mut hooks = Vec::with_capacity(last_tick_speakers.len());
+	let mut hooks = Vec::with_capacity(finished_speakers.len());
+	let verbose = settings.verbose;
+
+	// utterance_end is the point the user stopped speaking, used for end-to-end latency
+	for (ssrc, utterance_end) in finished_speakers {
+		let utterance_start = ssrc_state
+			.ssrc_utterance_start
+			.remove(&ssrc)
+			.map(|(_, start)| start);
+		let utterance_audio = ssrc_state
+			.ssrc_utterance_audio
+			.remove(&ssrc)
+			.map(|(_, audio)| audio);
+		let spoken_at = SystemTime::now() - utterance_start.map_or(Duration::ZERO, |s| s.elapsed());
+		// when they started speaking, in milliseconds since the session started
+		let start_ms = spoken_at
+			.duration_since(started_at)
+			.map_or(0, |d| d.as_millis() as u64);
 
-	for ssrc in last_tick_speakers {
 		// make a new stream for the next time they speak and remove their old one
-		let maybe_old_stream = match scripty_stt::get_stream().await {
-			Ok(s) => ssrc_state.ssrc_stream_map.insert(ssrc, s),
-			Err(e) => {
-				error!(?ssrc, "failed to create new stream: {}", e);
-				ssrc_state.ssrc_stream_map.remove(&ssrc).map(|x| x.1) // take what we have
+		// unless we're shutting down or out of quota, in which case there is no next time
+		let maybe_old_stream = if crate::is_draining() || crate::quota::is_exhausted(guild_id) {
+			ssrc_state.ssrc_stream_map.remove(&ssrc).map(|x| x.1)
+		} else {
+			match scripty_stt::get_stream().await {
+				Ok(s) => ssrc_state.ssrc_stream_map.insert(ssrc, s),
+				Err(e) => {
+					error!(?ssrc, "failed to create new stream: {}", e);
+					ssrc_state.ssrc_stream_map.remove(&ssrc).map(|x| x.1) // take what we have
+				}
 			}
 		};
 		let old_stream = if let Some(old_stream) = maybe_old_stream {
@@ -133,135 +320,316 @@ async fn handle_silent_speakers(
 					"no stream found for user (likely a bug): SSRC {}",
 					ssrc
 				)),
+				Merge::Off,
 				ssrc,
+				start_ms,
+				utterance_end,
+				Vec::new(),
 			));
 			continue;
 		};
 
 		// finalize the stream
-		let lang = language.read().clone();
-		let (final_result, hook) = finalize_stream(
+		let lang = if settings.auto_detect_lang {
+			"auto".to_owned()
+		} else {
+			settings.language.clone()
+		};
+		let Some(final_result) = finalize_stream(
 			old_stream,
-			ssrc_state.ssrc_user_data_map.clone(),
-			thread_id,
 			ssrc,
 			lang,
-			&verbose,
-			&translate,
+			verbose,
+			settings.translate,
+			&settings.post_process,
 		)
-		.await;
+		.await
+		else {
+			continue;
+		};
+		// skip garbage strings
+		if ["[BLANK_AUDIO]"].contains(&final_result.as_str()) {
+			continue;
+		}
 
-		if let Some(ref final_result) = final_result {
-			// skip garbage strings
-			if ["[BLANK_AUDIO]"].contains(&final_result.as_str()) {
-				continue;
+		let user_id = ssrc_state.ssrc_user_id_map.get(&ssrc).map(|x| *x.value());
+		let line = match ssrc_state.ssrc_user_data_map.get(&ssrc) {
+			Some(user_details) => {
+				let (username, avatar_url) = user_id
+					.and_then(|user_id| anonymous_speakers.get(user_id))
+					.unwrap_or_else(|| (user_details.0.clone(), user_details.1.clone()));
+				Some(TranscriptLine {
+					username,
+					avatar_url,
+					// only what's posted is filtered: automod and voice ingest need the real words
+					text: if verbose {
+						final_result.clone()
+					} else {
+						ProfanityFilter::new(settings.profanity_filter)
+							.transform(final_result.clone())
+					},
+					label: user_id.and_then(|user_id| speaker_labels.get(user_id)),
+					// verbose transcripts have timestamps of their own
+					timestamps: if verbose {
+						TimestampMode::Off
+					} else {
+						settings.timestamps
+					},
+					session_start: started_at,
+					spoken_at,
+					duration: utterance_start.map_or(Duration::ZERO, |s| utterance_end - s),
+					spoiler: settings.spoiler,
+				})
 			}
+			None => {
+				warn!("no user details for ssrc {}", ssrc);
+				None
+			}
+		};
 
-			// run automod
-			if !automod_server_cfg.enabled {
-				trace!("automod disabled, skipping");
-			} else if let Some(res) = automod_server_cfg.get_action(final_result) {
-				trace!(?res, ?ssrc, "automod action taken on rule match");
-				// user did something bad
-				let Some(user_id) = ssrc_state.ssrc_user_id_map.get(&ssrc).map(|x| *x.value())
-				else {
-					warn!(?ssrc, "no user ID found for ssrc");
+		// addressed to Scripty, by someone allowed to control it
+		if let (Some(role), Some(command), Some(line)) = (
+			settings.voice_command_role,
+			VoiceCommand::parse(&final_result),
+			&line,
+		) {
+			if let Some(user_id) = user_id {
+				if voice_commands::is_controller(ctx, guild_id, user_id, role).await {
+					debug!(?command, ?ssrc, "running voice command");
+					let notice = command.run(ctx, guild_id, &line.username, &paused).await;
+					let mut hook = ExecuteWebhook::new().content(notice);
+					if let Some(thread_id) = thread_id {
+						hook = hook.in_thread(thread_id);
+					}
+					hooks.push((hook, Merge::Off, ssrc, start_ms, utterance_end, Vec::new()));
 					continue;
-				};
+				}
+			}
+		}
+		if paused.load(Ordering::Relaxed) {
+			trace!(?ssrc, "transcripts stopped by voice command, skipping");
+			continue;
+		}
 
-				match res {
-					AutomodRuleAction::SilentDelete => continue, /* don't need to do anything more */
-					// we'll handle logging after each branch falls through
-					AutomodRuleAction::DeleteAndLog => {}
-					AutomodRuleAction::DeleteLogAndKick => {
-						// remove the user from the voice channel
-						if let Err(e) = guild_id.disconnect_member(&ctx, user_id).await {
-							error!("failed to remove user from VC: {}", e);
-						}
+		// run automod
+		if !automod_server_cfg.enabled {
+			trace!("automod disabled, skipping");
+		} else if let Some(res) = automod_server_cfg.get_action(&final_result) {
+			trace!(?res, ?ssrc, "automod action taken on rule match");
+			// user did something bad
+			let Some(user_id) = ssrc_state.ssrc_user_id_map.get(&ssrc).map(|x| *x.value()) else {
+				warn!(?ssrc, "no user ID found for ssrc");
+				continue;
+			};
+
+			match res {
+				AutomodRuleAction::SilentDelete => continue, // don't need to do anything more
+				// we'll handle logging after each branch falls through
+				AutomodRuleAction::DeleteAndLog => {}
+				AutomodRuleAction::DeleteLogAndKick => {
+					// remove the user from the voice channel
+					if let Err(e) = guild_id.disconnect_member(&ctx, user_id).await {
+						error!("failed to remove user from VC: {}", e);
 					}
-					AutomodRuleAction::DeleteLogAndSilence => {
-						// mute the user
-						if let Err(e) = guild_id
-							.edit_member(&ctx, user_id, EditMember::new().mute(true))
-							.await
-						{
-							error!("failed to mute user: {}", e);
-						}
+				}
+				AutomodRuleAction::DeleteLogAndSilence => {
+					// mute the user
+					if let Err(e) = guild_id
+						.edit_member(&ctx, user_id, EditMember::new().mute(true))
+						.await
+					{
+						error!("failed to mute user: {}", e);
 					}
 				}
-
-				if let Err(e) = SerenityChannelId::from(automod_server_cfg.log_channel_id)
-					.send_message(
-						&ctx,
-						CreateMessage::new().embed(
-							CreateEmbed::new()
-								.title("User said a forbidden word")
-								.description(format!(
-									"{}\nUser: <@{}>\nDetected word: {}",
-									match res {
-										AutomodRuleAction::SilentDelete => unreachable!(),
-										AutomodRuleAction::DeleteAndLog => "Deleted message",
-										AutomodRuleAction::DeleteLogAndKick =>
-											"Deleted message and kicked user from the VC",
-										AutomodRuleAction::DeleteLogAndSilence => {
-											"Deleted message and muted user"
-										}
-									},
+				AutomodRuleAction::DeleteLogAndTimeout => {
+					let until = SystemTime::now()
+						.duration_since(UNIX_EPOCH)
+						.map_or(0, |d| d.as_secs() as i64)
+						+ AUTOMOD_TIMEOUT_SECS;
+					match Timestamp::from_unix_timestamp(until) {
+						Ok(until) => {
+							if let Err(e) = guild_id
+								.edit_member(
+									&ctx,
 									user_id,
-									final_result
-								)),
-						),
-					)
-					.await
-				{
-					error!("failed to send log message: {}", e);
-				};
+									EditMember::new().disable_communication_until(until),
+								)
+								.await
+							{
+								error!("failed to time out user: {}", e);
+							}
+						}
+						Err(e) => error!("invalid timeout timestamp: {}", e),
+					}
+				}
+			}
 
-				continue;
-			} else {
-				trace!(?ssrc, "no automod action taken");
+			let mut log_message = CreateMessage::new().embed(
+				CreateEmbed::new()
+					.title("User said a forbidden word")
+					.description(format!(
+						"{}\nUser: <@{}>\nDetected word: {}",
+						match res {
+							AutomodRuleAction::SilentDelete => unreachable!(),
+							AutomodRuleAction::DeleteAndLog => "Deleted message",
+							AutomodRuleAction::DeleteLogAndKick => {
+								"Deleted message and kicked user from the VC"
+							}
+							AutomodRuleAction::DeleteLogAndSilence => {
+								"Deleted message and muted user"
+							}
+							AutomodRuleAction::DeleteLogAndTimeout => {
+								"Deleted message and timed out user for 10 minutes"
+							}
+						},
+						user_id,
+						final_result
+					)),
+			);
+			// only kept if the guild has automod recordings on
+			if let Some(wav) = utterance_audio
+				.as_deref()
+				.and_then(|audio| encode_wav(audio, 16_000))
+			{
+				log_message = log_message.add_file(CreateAttachment::bytes(wav, "speech.wav"));
 			}
-		}
+			if let Err(e) = SerenityChannelId::from(automod_server_cfg.log_channel_id)
+				.send_message(&ctx, log_message)
+				.await
+			{
+				error!("failed to send log message: {}", e);
+			};
 
-		if let Some(hook) = hook {
-			hooks.push((hook, ssrc));
+			continue;
+		} else {
+			trace!(?ssrc, "no automod action taken");
 		}
 
-		if let Some(final_result) = final_result {
-			if let Some((_, x)) = ssrc_state.ssrc_voice_ingest_map.remove(&ssrc) {
-				// we've already checked if the user is opted in or not
-				if let Some(ingest) = x {
-					trace!(?ssrc, "user has opted in, finalizing audio");
-					tokio::spawn(ingest.destroy(final_result.clone()));
+		let mut message_rx = None;
+		let mut ingest_message_rx = None;
+		if let Some(ref line) = line {
+			let style = settings.transcript_style;
+			let mut hook = line.to_webhook(style);
+			if let Some(thread_id) = thread_id {
+				hook = hook.in_thread(thread_id);
+			}
+			if let Some(label) = settings.translate_button.as_deref() {
+				hook = hook.components(vec![CreateActionRow::Buttons(vec![CreateButton::new(
+					TRANSLATE_BUTTON_ID,
+				)
+				.label(label)
+				.style(ButtonStyle::Secondary)])]);
+			}
+			// held transcripts are posted whenever they're approved, so they're never merged
+			if !crate::hold_queue::hold(ctx, guild_id, line, &hook) {
+				// verbose transcripts each have their own timestamps, so they're never merged
+				let merge = if verbose {
+					Merge::Off
 				} else {
-					trace!(?ssrc, "user has opted out, not attempting to finalize");
+					coalescer.push(ssrc, line, style, thread_id)
+				};
+				let (message_tx, rx) = oneshot::channel();
+				message_rx = Some(rx);
+				let (ingest_message_tx, rx) = oneshot::channel();
+				ingest_message_rx = Some(rx);
+				hooks.push((
+					hook,
+					merge,
+					ssrc,
+					start_ms,
+					utterance_end,
+					vec![message_tx, ingest_message_tx],
+				));
+			}
+			crate::mirrors::send(ctx, guild_id, line);
+
+			// after the transcript, so it's clear what they're responding to
+			for mut hook in crate::automations::run(ctx, guild_id, &final_result, line).await {
+				if let Some(thread_id) = thread_id {
+					hook = hook.in_thread(thread_id);
 				}
+				hooks.push((hook, Merge::Off, ssrc, start_ms, utterance_end, Vec::new()));
 			}
+		}
 
-			if let Some(transcript_results) = &transcript_results {
-				// place this in a block that way we don't try holding two locks at once
-				let fmt_transcript = {
-					// fetch user data
-					let Some(user_details) = ssrc_state.ssrc_user_data_map.get(&ssrc) else {
-						continue;
+		if let Some((_, x)) = ssrc_state.ssrc_voice_ingest_map.remove(&ssrc) {
+			// we've already checked if the user is opted in or not
+			if let Some(ingest) = x {
+				trace!(?ssrc, "user has opted in, finalizing audio");
+				let in_flight = InFlightGuard::new();
+				let final_result = final_result.clone();
+				spawn_logged("voice_ingest_destroy", async move {
+					let _in_flight = in_flight;
+					// needed for the audio to be flagged by reacting to the transcript
+					let message_id = match ingest_message_rx {
+						Some(rx) => tokio::time::timeout(MESSAGE_ID_TIMEOUT, rx)
+							.await
+							.ok()
+							.and_then(Result::ok),
+						None => None,
 					};
-					let username = &user_details.0;
-					format!("[{}]: {}", username, final_result)
-				};
-				transcript_results.write().push(fmt_transcript);
+					ingest.destroy(final_result, message_id).await
+				});
+			} else {
+				trace!(?ssrc, "user has opted out, not attempting to finalize");
 			}
 		}
+
+		let Some(line) = line else {
+			continue;
+		};
+		// anonymous speakers aren't tied back to who they are anywhere
+		let user_id = user_id
+			.filter(|_| !anonymous_speakers.is_enabled())
+			.map(UserId::new);
+		if let Some(user_id) = user_id {
+			crate::talk_time::record(guild_id, user_id, line.spoken_at, line.duration);
+		}
+		if let Some(utterance_start) = utterance_start {
+			let detected_language = if settings.auto_detect_lang {
+				scripty_stt::detect_language(&final_result, &settings.allowed_languages)
+			} else {
+				None
+			};
+			transcript_session.record(
+				line.username.clone(),
+				user_id,
+				utterance_start,
+				utterance_end,
+				final_result,
+				detected_language,
+				message_rx,
+			);
+		}
+		if let Some(transcript_results) = &transcript_results {
+			transcript_results.write().push(line.to_export());
+		}
 	}
 
 	hooks
 }
 
-async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice_data: VoiceTick) {
+async fn handle_speakers(
+	ssrc_state: Arc<SsrcMaps>,
+	metrics: Arc<Metrics>,
+	voice_data: VoiceTick,
+	seen_users: SeenUsers,
+	guild_id: GuildId,
+	shard_id: String,
+	keep_audio: bool,
+) {
+	let count_issue = |issue: &str| {
+		metrics
+			.audio_packet_issues
+			.with_label_values(&[&shard_id, issue])
+			.inc();
+	};
+
 	for (ssrc, data) in voice_data.speaking {
 		let st = Instant::now();
 
 		// always get RTCP data for debugging purposes
-		if let Some(pkt) = data.packet {
+		let has_packet = if let Some(pkt) = data.packet {
 			let rtp = pkt.rtp();
 			let version = rtp.get_version();
 			let sequence = rtp.get_sequence();
@@ -273,8 +641,38 @@ async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice
 				sequence,
 				timestamp
 			);
+
+			// any gap in sequence numbers since the last packet is packets that never arrived
+			if let Some(last_sequence) = ssrc_state.ssrc_last_sequence.insert(ssrc, sequence.0) {
+				let gap = sequence.0.wrapping_sub(last_sequence);
+				// a large gap is far more likely to be a reordered packet or a stream restart
+				if gap > 1 && gap < 1000 {
+					metrics
+						.audio_packet_issues
+						.with_label_values(&[&shard_id, "dropped_packet"])
+						.inc_by(u64::from(gap - 1));
+				}
+			}
+			true
 		} else {
+			// the jitter buffer had nothing for this user this tick
 			warn!(%ssrc, "no packet data: likely no audio too?");
+			count_issue("buffer_underrun");
+			false
+		};
+
+		let user_id = ssrc_state.ssrc_user_id_map.get(&ssrc).map(|x| *x.value());
+		if user_id.is_none() {
+			trace!(%ssrc, "got audio from an SSRC with no known user");
+			count_issue("unattributed_ssrc");
+		}
+
+		// only those who actually speak take part in the session: listeners are never counted
+		if data.decoded_voice.is_some() {
+			ssrc_state.ssrc_spoken.insert(ssrc);
+			if let (Some(seen_users), Some(user_id)) = (&seen_users, user_id) {
+				seen_users.insert(user_id);
+			}
 		}
 
 		if ssrc_state
@@ -294,12 +692,19 @@ async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice
 			continue;
 		}
 
+		// out of quota: let streams that are already open finish, but don't open any more
+		if crate::quota::is_exhausted(guild_id) && !ssrc_state.ssrc_stream_map.contains_key(&ssrc) {
+			continue;
+		}
+
 		// add to those speaking this tick
-		ssrc_state.ssrc_speaking_set.insert(ssrc);
+		ssrc_state.ssrc_last_spoke.insert(ssrc, st);
+		ssrc_state.ssrc_utterance_start.entry(ssrc).or_insert(st);
 
 		if let Some(audio) = data.decoded_voice {
 			trace!(%ssrc, "got {} bytes of audio", audio.len() * SIZE_OF_I16);
 			metrics.ms_transcribed.inc_by(20);
+			crate::quota::record(guild_id, 20);
 			metrics
 				.audio_bytes_processed
 				.inc_by((audio.len() * SIZE_OF_I16) as _);
@@ -340,6 +745,13 @@ async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice
 				}
 			}
 
+			if keep_audio {
+				let mut buffer = ssrc_state.ssrc_utterance_audio.entry(ssrc).or_default();
+				if buffer.len() < MAX_UTTERANCE_AUDIO_SAMPLES {
+					buffer.extend_from_slice(&audio);
+				}
+			}
+
 			// feed audio to transcription stream
 			if let Some(stream) = ssrc_state.ssrc_stream_map.get(&ssrc) {
 				if let Err(e) = stream.feed_audio(audio) {
@@ -359,6 +771,10 @@ async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice
 			}
 		} else {
 			error!(?ssrc, "no audio found in packet");
+			// only a decode failure if there was actually a packet to decode
+			if has_packet {
+				count_issue("decode_error");
+			}
 		}
 
 		let et = Instant::now();
@@ -367,59 +783,88 @@ async fn handle_speakers(ssrc_state: Arc<SsrcMaps>, metrics: Arc<Metrics>, voice
 	}
 }
 
+/// Encode mono `samples` at `sample_rate` Hz as a WAV file.
+pub(crate) fn encode_wav(samples: &[i16], sample_rate: u32) -> Option<Vec<u8>> {
+	let mut wav = Vec::new();
+	let spec = WavSpec {
+		channels: 1,
+		sample_rate,
+		bits_per_sample: 16,
+		sample_format: SampleFormat::Int,
+	};
+	let res = WavWriter::new(Cursor::new(&mut wav), spec).and_then(|mut writer| {
+		for sample in samples {
+			writer.write_sample(*sample)?;
+		}
+		writer.finalize()
+	});
+	match res {
+		Ok(()) => Some(wav),
+		Err(e) => {
+			error!("failed to encode audio as WAV: {}", e);
+			None
+		}
+	}
+}
+
+/// Tell the transcription channel about its quota, in the guild's language.
+async fn send_quota_notice(
+	ctx: &Context,
+	webhook: &Webhook,
+	thread_id: Option<ChannelId>,
+	guild_id: GuildId,
+	notice: QuotaNotice,
+) {
+	let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	let content = match notice {
+		QuotaNotice::Warning { used_ms, limit_ms } => format_message!(
+			language,
+			"quota-warning",
+			usedMinutes: used_ms / 60_000,
+			limitMinutes: limit_ms / 60_000
+		),
+		QuotaNotice::Exhausted { limit_ms } => format_message!(
+			language,
+			"quota-exhausted",
+			limitMinutes: limit_ms / 60_000
+		),
+	};
+
+	let mut hook = ExecuteWebhook::new().content(content);
+	if let Some(thread_id) = thread_id {
+		hook = hook.in_thread(thread_id);
+	}
+	if let Err(e) = webhook.execute(ctx, false, hook).await {
+		warn!(%guild_id, "failed to send quota notice: {}", e);
+	}
+}
+
 async fn finalize_stream(
 	stream: Stream,
-	user_data_map: SsrcUserDataMap,
-	thread_id: Option<ChannelId>,
 	ssrc: u32,
 	language: String,
-	verbose: &Arc<AtomicBool>,
-	translate: &Arc<AtomicBool>,
-) -> (Option<String>, Option<ExecuteWebhook>) {
-	let mut final_transcript = None;
-
+	verbose: bool,
+	translate: bool,
+	pipeline: &Pipeline,
+) -> Option<String> {
 	debug!(%ssrc, "finalizing stream");
 
 	let res = stream
-		.get_result(
-			language,
-			verbose.load(Ordering::Relaxed),
-			translate.load(Ordering::Relaxed),
-		)
-		.await;
-	let mut webhook_executor = match res {
+		.get_result(language, verbose, translate)
+		.await
+		// verbose output is for seeing exactly what the model produced, so leave it alone
+		.map(|res| if verbose { res } else { pipeline.run(res) });
+	match res {
 		Ok(res) if !res.is_empty() => {
-			let webhook_executor = ExecuteWebhook::new().content(&res);
-			final_transcript = Some(res);
-			webhook_executor
+			debug!(%ssrc, "got stream results");
+			Some(res)
 		}
-		Ok(_) => return (None, None),
+		Ok(_) => None,
 		Err(e) => {
 			error!(%ssrc, "failed to get stream result: {}", e);
-			return (None, None);
+			None
 		}
-	};
-
-	debug!(%ssrc, "got stream results");
-
-	let Some(user_details) = user_data_map.get(&ssrc) else {
-		warn!("no user details for ssrc {}", ssrc);
-		return (None, None);
-	};
-	debug!(%ssrc, "got user details for ssrc");
-
-	if let Some(thread_id) = thread_id {
-		webhook_executor = webhook_executor.in_thread(thread_id);
 	}
-
-	(
-		final_transcript,
-		Some(
-			webhook_executor
-				.avatar_url(&user_details.1)
-				.username(&user_details.0),
-		),
-	)
 }
 
 fn handle_error(error: ModelError, ssrc: u32) -> ExecuteWebhook {
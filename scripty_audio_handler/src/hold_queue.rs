@@ -0,0 +1,144 @@
+//! Holding transcripts for a moderator to approve before they're posted, set with
+//! `/config hold_queue`.
+//!
+//! Held transcripts are stored by `scripty_utils::hold_queue`, and posted from there once
+//! approved, so approving one still works after the session ends.
+
+use std::{sync::OnceLock as OnceCell, time::Duration};
+
+use dashmap::DashMap;
+use scripty_i18n::LanguageIdentifier;
+use scripty_utils::{
+	hold_queue::{self, APPROVE_BUTTON_PREFIX, REJECT_BUTTON_PREFIX},
+	spawn_logged,
+};
+use serenity::{
+	all::{ButtonStyle, ChannelId, GuildId},
+	builder::{
+		CreateActionRow,
+		CreateButton,
+		CreateEmbed,
+		CreateEmbedAuthor,
+		CreateEmbedFooter,
+		CreateMessage,
+		ExecuteWebhook,
+	},
+	client::Context,
+};
+
+use crate::{render::TranscriptLine, SessionKey};
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Hold {
+	/// Channel of the transcript webhook.
+	pub channel_id:         ChannelId,
+	/// Thread transcripts are sent to, if any.
+	pub thread_id:          Option<ChannelId>,
+	/// Where moderators approve or reject them.
+	pub review_channel_id:  ChannelId,
+	/// None if they wait for a moderator.
+	pub auto_approve_after: Option<Duration>,
+}
+
+static HOLDS: OnceCell<DashMap<SessionKey, Hold, ahash::RandomState>> = OnceCell::new();
+
+fn get_holds() -> &'static DashMap<SessionKey, Hold, ahash::RandomState> {
+	HOLDS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Start or stop holding the transcripts of `key`'s session for review.
+pub(crate) fn set_hold(key: SessionKey, hold: Option<Hold>) {
+	match hold {
+		Some(hold) => {
+			get_holds().insert(key, hold);
+		}
+		None => {
+			get_holds().remove(&key);
+		}
+	}
+}
+
+/// Hold `hook`, which was rendered from `line`, for review in the background, if the session of
+/// `ctx`'s bot in `guild_id` holds its transcripts. Returns whether it was held, in which case it
+/// mustn't be sent.
+pub(crate) fn hold(
+	ctx: &Context,
+	guild_id: GuildId,
+	line: &TranscriptLine,
+	hook: &ExecuteWebhook,
+) -> bool {
+	let key = SessionKey::new(ctx, guild_id);
+	let Some(hold) = get_holds().get(&key).map(|hold| *hold.value()) else {
+		return false;
+	};
+	let ctx = ctx.clone();
+	let line = line.clone();
+	let hook = hook.clone();
+	spawn_logged("hold_transcript", async move {
+		let hold_id = match hold_queue::hold(
+			guild_id,
+			hold.channel_id,
+			hold.thread_id,
+			&hook,
+			hold.review_channel_id,
+			hold.auto_approve_after,
+		)
+		.await
+		{
+			Ok(hold_id) => hold_id,
+			Err(e) => {
+				error!(%guild_id, "failed to hold transcript: {}", e);
+				return;
+			}
+		};
+
+		// without a review message, it's still approved automatically or dropped in time
+		let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+		let review = review_message(&language, &line, hold_id, hold.auto_approve_after);
+		match hold.review_channel_id.send_message(&ctx, review).await {
+			Ok(message) => {
+				if let Err(e) = hold_queue::set_review_message(hold_id, message.id).await {
+					error!(%guild_id, hold_id, "failed to store review message: {}", e);
+				}
+			}
+			Err(e) => warn!(%guild_id, hold_id, "failed to send review message: {}", e),
+		}
+	});
+	true
+}
+
+/// What moderators see of a held transcript, with buttons to approve or reject it.
+fn review_message(
+	language: &LanguageIdentifier,
+	line: &TranscriptLine,
+	hold_id: i64,
+	auto_approve_after: Option<Duration>,
+) -> CreateMessage {
+	let footer = match auto_approve_after {
+		Some(after) => format_message!(
+			language,
+			"hold-queue-review-auto-approve",
+			minutes: after.as_secs().div_ceil(60)
+		),
+		None => format_message!(
+			language,
+			"hold-queue-review-waiting",
+			hours: hold_queue::MAX_HOLD_TIME.as_secs() / 3600
+		),
+	};
+	CreateMessage::new()
+		.embed(
+			CreateEmbed::new()
+				.author(CreateEmbedAuthor::new(&line.username).icon_url(&line.avatar_url))
+				.description(&line.text)
+				.footer(CreateEmbedFooter::new(footer)),
+		)
+		.components(vec![CreateActionRow::Buttons(vec![
+			CreateButton::new(format!("{}{}", APPROVE_BUTTON_PREFIX, hold_id))
+				.label(format_message!(language, "hold-queue-approve-button"))
+				.style(ButtonStyle::Success),
+			CreateButton::new(format!("{}{}", REJECT_BUTTON_PREFIX, hold_id))
+				.label(format_message!(language, "hold-queue-reject-button"))
+				.style(ButtonStyle::Danger),
+		])])
+}
@@ -0,0 +1,155 @@
+//! Join and leave notices, set with `/config join_leave_notices`: a line in the transcript when
+//! someone joins or leaves the voice chat, so it makes sense when new speakers show up halfway
+//! through. Driven by the gateway's voice state updates, so listeners who never speak are noticed
+//! too.
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+		OnceLock as OnceCell,
+	},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use scripty_utils::{
+	delivery::{self, DeliveryTarget},
+	entity_cache::get_member_details,
+};
+use serenity::{
+	all::{ChannelId, GuildId, VoiceState},
+	builder::{CreateAllowedMentions, ExecuteWebhook},
+	client::Context,
+};
+
+use crate::{
+	anonymous_speakers::AnonymousSpeakers,
+	timestamps::TimestampMode,
+	types::TranscriptResults,
+	SessionKey,
+};
+
+#[derive(Clone)]
+pub(crate) struct Notices {
+	pub voice_channel_id:   ChannelId,
+	/// The transcript webhook, and its thread.
+	pub target:             DeliveryTarget,
+	pub transcript_results: TranscriptResults,
+	pub anonymous_speakers: Arc<AnonymousSpeakers>,
+	pub timestamps:         TimestampMode,
+	/// Set by voice commands: nothing's posted while transcription is stopped.
+	pub paused:             Arc<AtomicBool>,
+	pub started_at:         SystemTime,
+}
+
+static NOTICES: OnceCell<DashMap<SessionKey, Notices, ahash::RandomState>> = OnceCell::new();
+
+fn get_notices() -> &'static DashMap<SessionKey, Notices, ahash::RandomState> {
+	NOTICES.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Start or stop posting join and leave notices in the transcript of `key`'s session.
+pub(crate) fn set_notices(key: SessionKey, notices: Option<Notices>) {
+	match notices {
+		Some(notices) => {
+			get_notices().insert(key, notices);
+		}
+		None => {
+			get_notices().remove(&key);
+		}
+	}
+}
+
+/// Whether `key`'s session posts join and leave notices, so nothing else needs to say when
+/// someone leaves.
+pub(crate) fn is_enabled(key: SessionKey) -> bool {
+	get_notices().contains_key(&key)
+}
+
+/// Post a notice in the transcript of the session of `ctx`'s bot in `guild_id` if the voice state
+/// update from `old` to `new` is someone joining or leaving the voice chat it transcribes.
+pub async fn announce_join_leave(
+	ctx: &Context,
+	guild_id: GuildId,
+	old: Option<&VoiceState>,
+	new: &VoiceState,
+) {
+	let Some(notices) = get_notices()
+		.get(&SessionKey::new(ctx, guild_id))
+		.map(|notices| notices.value().clone())
+	else {
+		return;
+	};
+	let was_in = old.map_or(false, |old| {
+		old.channel_id == Some(notices.voice_channel_id)
+	});
+	let is_in = new.channel_id == Some(notices.voice_channel_id);
+	// anything else is someone muting, deafening, or moving between other channels
+	let joined = match (was_in, is_in) {
+		(false, true) => true,
+		(true, false) => false,
+		_ => return,
+	};
+	if new.user_id == ctx.cache.current_user().id
+		|| new.member.as_ref().map_or(false, |m| m.user.bot)
+		|| notices.paused.load(Ordering::Relaxed)
+	{
+		return;
+	}
+
+	let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	// numbers are given out in the order people speak, so joining doesn't get one
+	let notice = if notices.anonymous_speakers.is_enabled() {
+		format_message!(
+			language,
+			if joined {
+				"join-leave-anonymous-joined"
+			} else {
+				"join-leave-anonymous-left"
+			}
+		)
+	} else {
+		let name = match new.member {
+			Some(ref member) => member.display_name().to_string(),
+			None => match get_member_details(ctx, guild_id, new.user_id).await {
+				Ok(member) if member.bot => return,
+				Ok(member) => member.display_name,
+				Err(e) => {
+					debug!(%guild_id, "failed to get member for join/leave notice: {}", e);
+					return;
+				}
+			},
+		};
+		format_message!(
+			language,
+			if joined {
+				"join-leave-joined"
+			} else {
+				"join-leave-left"
+			},
+			name: name
+		)
+	};
+
+	let now = SystemTime::now();
+	if let Some(transcript_results) = &notices.transcript_results {
+		let prefix = notices.timestamps.export_prefix(notices.started_at, now);
+		transcript_results
+			.write()
+			.push(format!("{}{}", prefix, notice));
+	}
+
+	let hook = ExecuteWebhook::new()
+		.content(notice)
+		.allowed_mentions(CreateAllowedMentions::new());
+	let dedupe_key = format!(
+		"join_leave:{}:{}:{}",
+		guild_id,
+		new.user_id,
+		now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos())
+	);
+	if let Err(e) = delivery::deliver(&ctx.http, notices.target, &hook, &dedupe_key).await {
+		warn!(%guild_id, "failed to send join/leave notice: {}", e);
+	}
+}
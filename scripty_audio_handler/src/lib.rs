@@ -1,13 +1,39 @@
 #[macro_use]
 extern crate tracing;
+#[macro_use]
+extern crate scripty_i18n;
 
+mod age_restricted;
+mod anonymous_speakers;
 mod audio_handler;
+mod automations;
+mod coalesce;
 mod connect;
 mod consts;
 mod disconnect;
 mod error;
 mod events;
+mod hold_queue;
+mod join_leave;
+mod minutes;
+mod mirrors;
+mod quota;
+mod render;
+mod role_policy;
+mod session_key;
+mod session_stats;
+mod session_store;
+mod session_webhook;
+mod shutdown;
+mod speaker_labels;
+mod subscriptions;
+mod talk_time;
+mod timestamps;
+mod transcript_store;
+mod tts_relay;
 mod types;
+mod voice_commands;
+mod watch;
 
 use std::sync::{Arc, OnceLock as OnceCell};
 
@@ -16,14 +42,30 @@ pub use connect::connect_to_vc;
 use dashmap::DashMap;
 pub use disconnect::disconnect_from_vc;
 pub use error::{Error, ErrorKind};
+pub use join_leave::announce_join_leave;
+pub use minutes::{get_minutes, highlight, ActionItem, Attendee, Decision, Highlight, Minutes};
+pub use quota::{flush_quota_usage, reset_quotas};
 pub use scripty_stt::{check_model_language, get_model_languages};
 use serenity::{
 	all::{ChannelId, GuildId},
 	client::Context,
 };
+pub use session_key::SessionKey;
+pub use session_stats::{get_session_stats, SessionStats};
+pub use session_store::{
+	claim_legacy_sessions,
+	reap_stale_sessions,
+	resume_sessions,
+	PersistedSession,
+};
+pub use shutdown::{drain, is_draining, DRAIN_TIMEOUT};
 use songbird::{driver::DecodeMode, Config, Songbird};
 pub use songbird::{error::JoinError, serenity::SerenityInit};
+pub use talk_time::{delete_old_talk_time, flush_talk_time};
 use tokio::sync::oneshot::Sender;
+pub use transcript_store::end_orphaned_sessions;
+pub use tts_relay::{is_tts_enabled, relay_message};
+pub use watch::get_watchlist;
 
 pub fn get_songbird() -> Config {
 	Config::default().decode_mode(DecodeMode::Decode)
@@ -43,9 +85,19 @@ pub async fn get_voice_channel_id(ctx: &Context, guild_id: GuildId) -> Option<Ch
 	current_channel.map(|c| ChannelId::new(c.0.get()))
 }
 
+/// Whether `channel_id` is `guild_id`'s AFK channel, which is never transcribed.
+pub fn is_afk_channel(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> bool {
+	ctx.cache.guild(guild_id).map_or(false, |guild| {
+		guild
+			.afk_metadata
+			.as_ref()
+			.map_or(false, |afk| afk.afk_channel_id == channel_id)
+	})
+}
+
 pub async fn get_songbird_from_ctx(ctx: &Context) -> Arc<Songbird> {
 	songbird::get(ctx).await.expect("songbird not registered")
 }
 
-static AUTO_LEAVE_TASKS: OnceCell<DashMap<GuildId, Sender<()>, ahash::RandomState>> =
+static AUTO_LEAVE_TASKS: OnceCell<DashMap<SessionKey, Sender<()>, ahash::RandomState>> =
 	OnceCell::new();
@@ -0,0 +1,465 @@
+//! Meeting minutes for `/minutes`: who spoke, what was decided, and who's doing what,
+//! picked out of the guild's latest session.
+//!
+//! Decisions and action items are found by looking for phrases like "we decided" or
+//! "Alex will", so they only come out of English transcripts. Attendees are listed in any language.
+//!
+//! Members can also pin lines they want kept by reacting to them with the guild's highlight emoji.
+
+use std::{
+	sync::{Arc, OnceLock as OnceCell},
+	time::{Duration, Instant, SystemTime},
+};
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use scripty_i18n::LanguageIdentifier;
+use serenity::all::{GuildId, MessageId};
+
+use crate::SessionKey;
+
+/// Most lines kept per session, so a session left running for days can't use up memory.
+const MAX_LINES: usize = 20_000;
+
+/// How long minutes can still be made once a session has ended.
+const KEEP_AFTER_END: Duration = Duration::from_secs(60 * 60);
+
+/// Phrases that make a sentence a decision.
+const DECISION_PHRASES: &[&[&str]] = &[
+	&["we", "decided"],
+	&["we've", "decided"],
+	&["we", "have", "decided"],
+	&["we", "agreed"],
+	&["we've", "agreed"],
+	&["we", "have", "agreed"],
+	&["let's", "go", "with"],
+	&["we'll", "go", "with"],
+	&["we", "will", "go", "with"],
+	&["the", "decision", "is"],
+	&["it's", "decided"],
+];
+
+/// Phrases before a task the speaker is taking on.
+const OWN_TASK_PHRASES: &[&[&str]] = &[
+	&["i", "will"],
+	&["i'll"],
+	&["i", "am", "going", "to"],
+	&["i'm", "going", "to"],
+];
+
+/// Phrases before a task nobody has taken on yet.
+const UNASSIGNED_TASK_PHRASES: &[&[&str]] = &[
+	&["action", "item"],
+	&["todo"],
+	&["we", "need", "to"],
+	&["someone", "needs", "to"],
+	&["someone", "should"],
+];
+
+/// First words of "tasks" that are really just turns of phrase, ie "I'll be honest".
+const NOT_TASKS: &[&str] = &["be", "not", "never"];
+
+#[derive(Debug)]
+struct Line {
+	speaker:     String,
+	start_ms:    i64,
+	end_ms:      i64,
+	content:     String,
+	/// The message it was sent in, once it's been sent.
+	message_id:  Option<MessageId>,
+	highlighted: bool,
+}
+
+/// Everything said in one session, kept for `/minutes`.
+#[derive(Debug)]
+pub(crate) struct MinutesLog {
+	started_at: SystemTime,
+	started:    Instant,
+	lines:      Mutex<Vec<Line>>,
+	ended:      Mutex<Option<Instant>>,
+}
+
+impl MinutesLog {
+	/// Keep a line, returning its index if there was room for it.
+	pub(crate) fn push(
+		&self,
+		speaker: &str,
+		start_ms: i64,
+		end_ms: i64,
+		content: &str,
+	) -> Option<usize> {
+		let mut lines = self.lines.lock();
+		if lines.len() >= MAX_LINES {
+			return None;
+		}
+		lines.push(Line {
+			speaker: speaker.to_string(),
+			start_ms,
+			end_ms,
+			content: content.to_string(),
+			message_id: None,
+			highlighted: false,
+		});
+		Some(lines.len() - 1)
+	}
+
+	/// Link the line at `index` to the message it was sent in, so it can be highlighted.
+	pub(crate) fn set_message_id(&self, index: usize, message_id: MessageId) {
+		if let Some(line) = self.lines.lock().get_mut(index) {
+			line.message_id = Some(message_id);
+		}
+	}
+
+	/// Lines highlighted so far, in the order they were said.
+	pub(crate) fn highlights(&self) -> Vec<Highlight> {
+		self.lines
+			.lock()
+			.iter()
+			.filter(|line| line.highlighted)
+			.map(|line| Highlight {
+				text:    line.content.clone(),
+				said_by: line.speaker.clone(),
+				at:      Duration::from_millis(line.start_ms.max(0) as u64),
+			})
+			.collect()
+	}
+
+	pub(crate) fn end(&self) {
+		self.ended.lock().get_or_insert_with(Instant::now);
+	}
+
+	/// Minutes of everything said so far.
+	pub(crate) fn minutes(&self) -> Minutes {
+		let ended = *self.ended.lock();
+		let highlights = self.highlights();
+		let lines = self.lines.lock();
+
+		let mut attendees: Vec<Attendee> = Vec::new();
+		for line in lines.iter() {
+			let speaking_time = Duration::from_millis((line.end_ms - line.start_ms).max(0) as u64);
+			match attendees.iter_mut().find(|a| a.name == line.speaker) {
+				Some(attendee) => attendee.speaking_time += speaking_time,
+				None => attendees.push(Attendee {
+					name: line.speaker.clone(),
+					speaking_time,
+				}),
+			}
+		}
+
+		let mut decisions = Vec::new();
+		let mut action_items = Vec::new();
+		for line in lines.iter() {
+			let at = Duration::from_millis(line.start_ms.max(0) as u64);
+			for sentence in line.content.split(['.', '!', '?']) {
+				let original: Vec<&str> = sentence.split_whitespace().collect();
+				let words: Vec<String> = original.iter().map(|word| normalize(word)).collect();
+
+				if DECISION_PHRASES
+					.iter()
+					.any(|phrase| find_phrase(&words, phrase).is_some())
+				{
+					decisions.push(Decision {
+						text: capitalize(&original.join(" ")),
+						said_by: line.speaker.clone(),
+						at,
+					});
+				} else if let Some((owner, task_start)) =
+					find_action_item(&words, &line.speaker, &attendees)
+				{
+					action_items.push(ActionItem {
+						task: capitalize(&original[task_start..].join(" ")),
+						owner,
+						said_by: line.speaker.clone(),
+						at,
+					});
+				}
+			}
+		}
+
+		Minutes {
+			started_at: self.started_at,
+			duration: ended.unwrap_or_else(Instant::now) - self.started,
+			ongoing: ended.is_none(),
+			attendees,
+			decisions,
+			action_items,
+			highlights,
+		}
+	}
+
+	/// Everything said so far, as lines of a transcript file.
+	pub(crate) fn transcript(&self) -> Vec<String> {
+		self.lines
+			.lock()
+			.iter()
+			.map(|line| {
+				let at = Duration::from_millis(line.start_ms.max(0) as u64);
+				format!("[{}] {}: {}", clock(at), line.speaker, line.content)
+			})
+			.collect()
+	}
+
+	fn is_expired(&self) -> bool {
+		self.ended
+			.lock()
+			.map_or(false, |ended| ended.elapsed() > KEEP_AFTER_END)
+	}
+}
+
+static MINUTES_LOGS: OnceCell<DashMap<SessionKey, Arc<MinutesLog>, ahash::RandomState>> =
+	OnceCell::new();
+
+fn get_minutes_logs() -> &'static DashMap<SessionKey, Arc<MinutesLog>, ahash::RandomState> {
+	MINUTES_LOGS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Start keeping what's said in `key`'s new session, replacing the bot's last one in the guild.
+pub(crate) fn start_log(key: SessionKey) -> Arc<MinutesLog> {
+	let logs = get_minutes_logs();
+	logs.retain(|_, log| !log.is_expired());
+
+	let log = Arc::new(MinutesLog {
+		started_at: SystemTime::now(),
+		started:    Instant::now(),
+		lines:      Mutex::new(Vec::new()),
+		ended:      Mutex::new(None),
+	});
+	logs.insert(key, Arc::clone(&log));
+	log
+}
+
+#[derive(Clone, Debug)]
+pub struct Attendee {
+	pub name:          String,
+	pub speaking_time: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct Decision {
+	pub text:    String,
+	pub said_by: String,
+	/// Time since the session started.
+	pub at:      Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct ActionItem {
+	pub task:    String,
+	/// None if nobody took it on.
+	pub owner:   Option<String>,
+	pub said_by: String,
+	/// Time since the session started.
+	pub at:      Duration,
+}
+
+/// A line a member pinned by reacting to it.
+#[derive(Clone, Debug)]
+pub struct Highlight {
+	pub text:    String,
+	pub said_by: String,
+	/// Time since the session started.
+	pub at:      Duration,
+}
+
+impl Highlight {
+	/// Render as a line of a transcript file.
+	pub(crate) fn to_export(&self) -> String {
+		format!("[{}] {}: {}", clock(self.at), self.said_by, self.text)
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct Minutes {
+	pub started_at:   SystemTime,
+	pub duration:     Duration,
+	/// Whether the session is still going.
+	pub ongoing:      bool,
+	/// In the order they first spoke.
+	pub attendees:    Vec<Attendee>,
+	pub decisions:    Vec<Decision>,
+	pub action_items: Vec<ActionItem>,
+	pub highlights:   Vec<Highlight>,
+}
+
+impl Minutes {
+	/// Each section's heading, and its items, in `language`.
+	pub fn sections(&self, language: &LanguageIdentifier) -> Vec<(String, Vec<String>)> {
+		let unassigned = format_message!(language, "minutes-unassigned");
+		vec![
+			(
+				format_message!(language, "minutes-attendees"),
+				self.attendees
+					.iter()
+					.map(|attendee| {
+						format!("{} ({})", attendee.name, clock(attendee.speaking_time))
+					})
+					.collect(),
+			),
+			(
+				format_message!(language, "minutes-decisions"),
+				self.decisions
+					.iter()
+					.map(|decision| {
+						format!(
+							"{} ({}, {})",
+							decision.text,
+							decision.said_by,
+							clock(decision.at)
+						)
+					})
+					.collect(),
+			),
+			(
+				format_message!(language, "minutes-action-items"),
+				self.action_items
+					.iter()
+					.map(|item| {
+						format!(
+							"**{}**: {} ({})",
+							item.owner.as_deref().unwrap_or(&unassigned),
+							item.task,
+							clock(item.at)
+						)
+					})
+					.collect(),
+			),
+			(
+				format_message!(language, "minutes-highlights"),
+				self.highlights
+					.iter()
+					.map(|highlight| {
+						format!(
+							"{} ({}, {})",
+							highlight.text,
+							highlight.said_by,
+							clock(highlight.at)
+						)
+					})
+					.collect(),
+			),
+		]
+	}
+
+	/// Each section as Markdown, in `language`.
+	pub fn sections_markdown(&self, language: &LanguageIdentifier) -> String {
+		let mut markdown = String::new();
+		for (name, items) in self.sections(language) {
+			markdown.push_str(&format!("\n## {}\n\n", name));
+			if items.is_empty() {
+				markdown.push_str(&format_message!(language, "minutes-none"));
+				markdown.push('\n');
+			}
+			for item in items {
+				markdown.push_str(&format!("- {}\n", item));
+			}
+		}
+		markdown
+	}
+}
+
+/// Minutes of `key`'s current session, or the bot's last one in the guild if it ended recently.
+pub fn get_minutes(key: SessionKey) -> Option<Minutes> {
+	let log = get_minutes_logs()
+		.get(&key)
+		.map(|log| Arc::clone(log.value()))?;
+	if log.is_expired() {
+		return None;
+	}
+	Some(log.minutes())
+}
+
+/// Highlight the lines sent in `message_id`, if it's part of the current or last session of any
+/// bot in `guild_id`.
+///
+/// Returns whether any weren't highlighted already.
+pub fn highlight(guild_id: GuildId, message_id: MessageId) -> bool {
+	let logs: Vec<Arc<MinutesLog>> = get_minutes_logs()
+		.iter()
+		.filter(|log| log.key().guild_id == guild_id && !log.value().is_expired())
+		.map(|log| Arc::clone(log.value()))
+		.collect();
+
+	// merged lines are all sent in the same message
+	let mut highlighted = false;
+	for log in logs {
+		for line in log.lines.lock().iter_mut() {
+			if line.message_id == Some(message_id) && !line.highlighted {
+				line.highlighted = true;
+				highlighted = true;
+			}
+		}
+	}
+	highlighted
+}
+
+/// Lowercase, without punctuation around it, and with curly apostrophes straightened.
+fn normalize(word: &str) -> String {
+	word.replace('’', "'")
+		.trim_matches(|c: char| !c.is_alphanumeric())
+		.to_lowercase()
+}
+
+fn capitalize(text: &str) -> String {
+	let text = text.trim_matches(|c: char| !c.is_alphanumeric());
+	let mut chars = text.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
+
+/// Index of the word after the first occurrence of `phrase` in `words`.
+fn find_phrase(words: &[String], phrase: &[&str]) -> Option<usize> {
+	(0..words.len()).find_map(|i| {
+		let rest = &words[i..];
+		(rest.len() >= phrase.len() && rest.iter().zip(phrase).all(|(word, p)| word == p))
+			.then_some(i + phrase.len())
+	})
+}
+
+/// Who owns the task in a sentence, and the index of the word it starts at.
+fn find_action_item(
+	words: &[String],
+	speaker: &str,
+	attendees: &[Attendee],
+) -> Option<(Option<String>, usize)> {
+	let found = OWN_TASK_PHRASES
+		.iter()
+		.find_map(|phrase| find_phrase(words, phrase))
+		.map(|start| (Some(speaker.to_string()), start))
+		.or_else(|| {
+			attendees.iter().find_map(|attendee| {
+				let name = normalize(attendee.name.split_whitespace().next()?);
+				if name.is_empty() {
+					return None;
+				}
+				find_phrase(words, &[name.as_str(), "will"])
+					.or_else(|| find_phrase(words, &[format!("{}'ll", name).as_str()]))
+					.or_else(|| find_phrase(words, &[name.as_str(), "is", "going", "to"]))
+					.or_else(|| {
+						find_phrase(words, &[format!("{}'s", name).as_str(), "going", "to"])
+					})
+					.map(|start| (Some(attendee.name.clone()), start))
+			})
+		})
+		.or_else(|| {
+			UNASSIGNED_TASK_PHRASES
+				.iter()
+				.find_map(|phrase| find_phrase(words, phrase))
+				.map(|start| (None, start))
+		})?;
+
+	// a task needs at least a verb and what it's done to
+	let mut task = words[found.1..].iter().filter(|word| !word.is_empty());
+	let first = task.next()?;
+	if task.next().is_none() || NOT_TASKS.contains(&first.as_str()) {
+		return None;
+	}
+	Some(found)
+}
+
+/// `H:MM:SS`, for times in the session and how long someone spoke for.
+fn clock(duration: Duration) -> String {
+	let secs = duration.as_secs();
+	format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
@@ -0,0 +1,115 @@
+//! Sending transcripts to the extra channels set with `/config mirror`.
+//!
+//! Each mirror is sent to on its own, through the delivery queue, so one that's failing doesn't
+//! hold up the others or the session's own transcripts. Lines are never merged in mirrors.
+
+use std::{
+	sync::OnceLock as OnceCell,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use scripty_utils::{
+	delivery::{self, DeliveryTarget},
+	entity_cache::{forget_channel_webhook, get_channel_webhook},
+	spawn_logged,
+};
+use serenity::{
+	all::{ChannelId, GuildId},
+	client::Context,
+};
+
+use crate::render::{TranscriptLine, TranscriptStyle};
+
+#[derive(Clone)]
+struct MirrorTarget {
+	channel_id: ChannelId,
+	target:     DeliveryTarget,
+	style:      TranscriptStyle,
+}
+
+static MIRRORS: OnceCell<DashMap<GuildId, Vec<MirrorTarget>, ahash::RandomState>> = OnceCell::new();
+
+fn get_mirrors() -> &'static DashMap<GuildId, Vec<MirrorTarget>, ahash::RandomState> {
+	MIRRORS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Load the mirrors of `guild_id`'s session in `voice_channel_id`, which sends its own transcripts
+/// to the channel or thread `channel_id`. If `age_restricted_only`, mirrors that aren't
+/// age-restricted are skipped.
+pub(crate) async fn load_mirrors(
+	ctx: &Context,
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	channel_id: ChannelId,
+	age_restricted_only: bool,
+) -> Result<(), sqlx::Error> {
+	let mut mirrors = Vec::new();
+	for mirror in scripty_utils::mirrors::get_mirrors(guild_id).await? {
+		if mirror.voice_channel_id != voice_channel_id
+			// already getting them
+			|| mirror.channel_id == channel_id
+			|| (age_restricted_only
+				&& !crate::age_restricted::is_age_restricted(ctx, guild_id, mirror.channel_id))
+		{
+			continue;
+		}
+		// a mirror that can't be set up is skipped, rather than stopping the session
+		let channel_id = mirror.channel_id;
+		let webhook = match get_channel_webhook(ctx, channel_id).await {
+			Ok(webhook) => webhook,
+			Err(e) => {
+				debug!(%guild_id, %channel_id, "failed to get mirror webhook: {}", e);
+				continue;
+			}
+		};
+		let Some(target) = DeliveryTarget::webhook(&webhook, None) else {
+			continue;
+		};
+		mirrors.push(MirrorTarget {
+			channel_id: mirror.channel_id,
+			target,
+			style: TranscriptStyle::from_db(mirror.style),
+		});
+	}
+
+	if mirrors.is_empty() {
+		get_mirrors().remove(&guild_id);
+	} else {
+		get_mirrors().insert(guild_id, mirrors);
+	}
+	Ok(())
+}
+
+/// Stop mirroring `guild_id`'s session.
+pub(crate) fn clear_mirrors(guild_id: GuildId) {
+	get_mirrors().remove(&guild_id);
+}
+
+/// Send `line` to each of `guild_id`'s mirrors, in the background.
+pub(crate) fn send(ctx: &Context, guild_id: GuildId, line: &TranscriptLine) {
+	let Some(mirrors) = get_mirrors()
+		.get(&guild_id)
+		.map(|mirrors| mirrors.value().clone())
+	else {
+		return;
+	};
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_nanos());
+
+	for mirror in mirrors {
+		let hook = line.to_webhook(mirror.style);
+		let ctx = ctx.clone();
+		spawn_logged("transcript_mirror", async move {
+			// queued transcripts are retried, so this only needs to be unique per line
+			let dedupe_key = format!("mirror:{}:{}:{}", guild_id, mirror.channel_id, nanos);
+			if let Err(e) = delivery::deliver(&ctx.http, mirror.target, &hook, &dedupe_key).await {
+				let channel_id = mirror.channel_id;
+				warn!(%guild_id, %channel_id, "failed to mirror transcript: {}", e);
+				// it may have been deleted, so look it up again next time
+				forget_channel_webhook(channel_id).await;
+			}
+		});
+	}
+}
@@ -0,0 +1,177 @@
+//! Monthly per-guild transcription quotas.
+//!
+//! Usage is counted in memory as audio is fed to STT, and added to `guilds.ms_transcribed`
+//! (and the all-time total in `transcription_totals`) by `flush_quota_usage`.
+//! Once a guild runs out, no new streams are opened for it until `reset_quotas` runs at the start
+//! of the next month.
+
+use std::sync::OnceLock as OnceCell;
+
+use dashmap::DashMap;
+use scripty_premium::PremiumTierList;
+use serenity::all::GuildId;
+
+/// Guilds are warned once they've used this percentage of their quota.
+const WARN_PERCENT: u64 = 80;
+
+#[derive(Debug, Default)]
+struct GuildQuota {
+	/// Transcribed since the last flush.
+	pending_ms: u64,
+	/// Transcribed this month, as of the last flush or config reload.
+	used_ms:    u64,
+	/// None if unlimited.
+	limit_ms:   Option<u64>,
+	warned:     bool,
+	exhausted:  bool,
+	notice:     Option<QuotaNotice>,
+}
+
+impl GuildQuota {
+	/// Check the thresholds, queueing a notice if one was just crossed.
+	fn check(&mut self) {
+		let Some(limit_ms) = self.limit_ms else {
+			self.warned = false;
+			self.exhausted = false;
+			return;
+		};
+		let used_ms = self.used_ms + self.pending_ms;
+
+		if used_ms >= limit_ms {
+			if !self.exhausted {
+				self.exhausted = true;
+				self.warned = true;
+				self.notice = Some(QuotaNotice::Exhausted { limit_ms });
+			}
+		} else if used_ms * 100 >= limit_ms * WARN_PERCENT {
+			self.exhausted = false;
+			if !self.warned {
+				self.warned = true;
+				self.notice = Some(QuotaNotice::Warning { used_ms, limit_ms });
+			}
+		} else {
+			// only after a reset, or the guild's tier going up
+			self.warned = false;
+			self.exhausted = false;
+		}
+	}
+}
+
+/// Something to tell a guild about its quota, in its transcription channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QuotaNotice {
+	Warning { used_ms: u64, limit_ms: u64 },
+	Exhausted { limit_ms: u64 },
+}
+
+static QUOTAS: OnceCell<DashMap<GuildId, GuildQuota, ahash::RandomState>> = OnceCell::new();
+
+fn get_quotas() -> &'static DashMap<GuildId, GuildQuota, ahash::RandomState> {
+	QUOTAS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Refresh a guild's usage and limit from the database, ie when its config is reloaded.
+pub(crate) fn update(guild_id: GuildId, used_ms: u64, tier: PremiumTierList) {
+	let mut quota = get_quotas().entry(guild_id).or_default();
+	quota.used_ms = used_ms;
+	quota.limit_ms = tier.monthly_quota_ms();
+	quota.check();
+}
+
+/// Count `ms` of audio from `guild_id` as transcribed.
+pub(crate) fn record(guild_id: GuildId, ms: u64) {
+	let mut quota = get_quotas().entry(guild_id).or_default();
+	quota.pending_ms += ms;
+	quota.check();
+}
+
+/// Whether `guild_id` has used up this month's quota, so no new streams should be opened for it.
+pub(crate) fn is_exhausted(guild_id: GuildId) -> bool {
+	get_quotas()
+		.get(&guild_id)
+		.map_or(false, |quota| quota.exhausted)
+}
+
+/// Take the notice waiting to be sent to `guild_id`, if any.
+pub(crate) fn take_notice(guild_id: GuildId) -> Option<QuotaNotice> {
+	get_quotas().get_mut(&guild_id)?.notice.take()
+}
+
+/// Add usage counted since the last flush to the database.
+pub async fn flush_quota_usage() -> Result<(), sqlx::Error> {
+	let (guild_ids, pending): (Vec<i64>, Vec<i64>) = get_quotas()
+		.iter_mut()
+		.filter_map(|mut quota| {
+			// counted as used while it's written, so the thresholds don't reset in the meantime
+			let pending_ms = std::mem::take(&mut quota.pending_ms);
+			quota.used_ms += pending_ms;
+			(pending_ms > 0).then_some((quota.key().get() as i64, pending_ms as i64))
+		})
+		.unzip();
+	if guild_ids.is_empty() {
+		return Ok(());
+	}
+
+	let res = sqlx::query!(
+		"UPDATE guilds SET ms_transcribed = guilds.ms_transcribed + p.ms FROM \
+		 UNNEST($1::BIGINT[], $2::BIGINT[]) AS p(guild_id, ms) WHERE guilds.guild_id = p.guild_id \
+		 RETURNING guilds.guild_id, guilds.ms_transcribed",
+		&guild_ids,
+		&pending
+	)
+	.fetch_all(scripty_db::get_db())
+	.await;
+
+	let quotas = get_quotas();
+	match res {
+		Ok(rows) => {
+			// only for public stats, so not worth holding on to if it fails
+			if let Err(e) = sqlx::query!(
+				"UPDATE transcription_totals SET ms_transcribed = ms_transcribed + $1",
+				pending.iter().sum::<i64>()
+			)
+			.execute(scripty_db::get_db())
+			.await
+			{
+				warn!("failed to add to transcription total: {}", e);
+			}
+
+			for row in rows {
+				let Some(guild_id) = u64::try_from(row.guild_id).ok().filter(|id| *id != 0) else {
+					continue;
+				};
+				if let Some(mut quota) = quotas.get_mut(&GuildId::new(guild_id)) {
+					// picks up resets and usage from other processes too
+					quota.used_ms = row.ms_transcribed as u64;
+					quota.check();
+				}
+			}
+			Ok(())
+		}
+		Err(e) => {
+			// keep it for the next flush
+			for (guild_id, pending_ms) in guild_ids.into_iter().zip(pending) {
+				if let Some(mut quota) = quotas.get_mut(&GuildId::new(guild_id as u64)) {
+					quota.used_ms = quota.used_ms.saturating_sub(pending_ms as u64);
+					quota.pending_ms += pending_ms as u64;
+				}
+			}
+			Err(e)
+		}
+	}
+}
+
+/// Start a new month: clear every guild's usage. Returns how many guilds had used any.
+pub async fn reset_quotas() -> Result<u64, sqlx::Error> {
+	let reset = sqlx::query!("UPDATE guilds SET ms_transcribed = 0 WHERE ms_transcribed > 0")
+		.execute(scripty_db::get_db())
+		.await?
+		.rows_affected();
+
+	// other processes pick this up on their next flush or config reload
+	for mut quota in get_quotas().iter_mut() {
+		quota.used_ms = 0;
+		quota.check();
+	}
+	Ok(reset)
+}
@@ -0,0 +1,149 @@
+//! Turns transcripts into messages, in the style set with `/config transcript_style`.
+//!
+//! Live transcripts, merged ones, and the transcript file sent when a session ends
+//! are all rendered here, so they stay consistent with each other.
+
+use std::time::{Duration, SystemTime};
+
+use serenity::builder::{
+	CreateEmbed,
+	CreateEmbedAuthor,
+	CreateEmbedFooter,
+	EditWebhookMessage,
+	ExecuteWebhook,
+};
+
+use crate::{speaker_labels::SpeakerLabel, timestamps::TimestampMode};
+
+/// Stored in `guilds.transcript_style`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptStyle {
+	/// Posted as if the speaker sent it, with their name and avatar.
+	Webhook = 0,
+	/// Posted as `**name:** text` under Scripty's own name, so transcripts take less space.
+	Compact = 1,
+	/// An embed with the speaker as its author, and how long they spoke for in its footer.
+	Embed   = 2,
+}
+
+impl TranscriptStyle {
+	/// Decode `guilds.transcript_style`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Compact,
+			2 => Self::Embed,
+			_ => Self::Webhook,
+		}
+	}
+}
+
+/// One speaker's transcript, and everything else that's shown with it.
+#[derive(Clone, Debug)]
+pub struct TranscriptLine {
+	pub username:      String,
+	pub avatar_url:    String,
+	/// What was said, already filtered.
+	pub text:          String,
+	pub label:         Option<SpeakerLabel>,
+	pub timestamps:    TimestampMode,
+	pub session_start: SystemTime,
+	pub spoken_at:     SystemTime,
+	/// How long they spoke for.
+	pub duration:      Duration,
+	/// Hide what was said behind a spoiler, for age-restricted voice chats.
+	pub spoiler:       bool,
+}
+
+impl TranscriptLine {
+	/// Render as a new message.
+	pub fn to_webhook(&self, style: TranscriptStyle) -> ExecuteWebhook {
+		let (content, embed) = self.render(style);
+		let mut hook = ExecuteWebhook::new();
+		if let Some(content) = content {
+			hook = hook.content(content);
+		}
+		if let Some(embed) = embed {
+			hook = hook.embed(embed);
+		}
+		if style == TranscriptStyle::Webhook {
+			hook = hook.username(&self.username).avatar_url(&self.avatar_url);
+		}
+		hook
+	}
+
+	/// Render as an edit to a message sent by `to_webhook`, in the same `style`.
+	pub fn to_edit(&self, style: TranscriptStyle) -> EditWebhookMessage {
+		let (content, embed) = self.render(style);
+		let mut edit = EditWebhookMessage::new();
+		if let Some(content) = content {
+			edit = edit.content(content);
+		}
+		if let Some(embed) = embed {
+			edit = edit.embeds(vec![embed]);
+		}
+		edit
+	}
+
+	/// Render as a line of the transcript file, which is the same in every style.
+	pub fn to_export(&self) -> String {
+		format!(
+			"{}[{}]: {}",
+			self.timestamps
+				.export_prefix(self.session_start, self.spoken_at),
+			self.username,
+			self.text
+		)
+	}
+
+	/// The message's content and embed, at least one of which is set.
+	fn render(&self, style: TranscriptStyle) -> (Option<String>, Option<CreateEmbed>) {
+		let text = if self.spoiler {
+			format!("||{}||", self.text)
+		} else {
+			self.text.clone()
+		};
+		let mut body = self.timestamps.prefix(self.session_start, self.spoken_at);
+		if let Some(SpeakerLabel::Emoji(emoji)) = self.label {
+			body.push_str(emoji);
+			body.push(' ');
+		}
+		let color = match self.label {
+			Some(SpeakerLabel::Color(color)) => Some(color),
+			_ => None,
+		};
+
+		match style {
+			TranscriptStyle::Webhook => {
+				body.push_str(&text);
+				match color {
+					Some(color) => (
+						None,
+						Some(CreateEmbed::new().description(body).color(color)),
+					),
+					None => (Some(body), None),
+				}
+			}
+			// plain text has nowhere to put a color
+			TranscriptStyle::Compact => {
+				body.push_str(&format!("**{}:** {}", self.username, text));
+				(Some(body), None)
+			}
+			TranscriptStyle::Embed => {
+				body.push_str(&text);
+				let secs = self.duration.as_secs();
+				let mut embed = CreateEmbed::new()
+					.author(CreateEmbedAuthor::new(&self.username).icon_url(&self.avatar_url))
+					.description(body)
+					.footer(CreateEmbedFooter::new(format!(
+						"{}:{:02}",
+						secs / 60,
+						secs % 60
+					)));
+				if let Some(color) = color {
+					embed = embed.color(color);
+				}
+				(None, Some(embed))
+			}
+		}
+	}
+}
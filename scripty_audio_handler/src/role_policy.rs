@@ -0,0 +1,71 @@
+//! Role policies, set with `/config role_policy`, checked whenever a speaker is matched to a
+//! member. Denied roles always win, so they can't be worked around with watch mode.
+
+use std::sync::OnceLock as OnceCell;
+
+use dashmap::DashMap;
+use scripty_utils::role_policy::RolePolicy;
+use serenity::{
+	all::{GuildId, RoleId},
+	cache::Cache,
+};
+
+/// A guild's role policies, with "and above" resolved to the roles it covers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ResolvedPolicy {
+	/// Empty if every role is allowed.
+	allowed: Vec<RoleId>,
+	denied:  Vec<RoleId>,
+}
+
+static POLICIES: OnceCell<DashMap<GuildId, ResolvedPolicy, ahash::RandomState>> = OnceCell::new();
+
+fn get_policies() -> &'static DashMap<GuildId, ResolvedPolicy, ahash::RandomState> {
+	POLICIES.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Set `guild_id`'s role policies for its session.
+/// Returns whether that changed anything.
+pub(crate) fn set_role_policies(cache: &Cache, guild_id: GuildId, policies: &[RolePolicy]) -> bool {
+	let mut resolved = ResolvedPolicy::default();
+	for policy in policies {
+		if !policy.allow {
+			resolved.denied.push(policy.role_id);
+			continue;
+		}
+		resolved.allowed.push(policy.role_id);
+		if !policy.and_above {
+			continue;
+		}
+		// roles are moved around often enough that this is worked out whenever the config reloads
+		if let Some(guild) = cache.guild(guild_id) {
+			if let Some(base) = guild.roles.get(&policy.role_id) {
+				resolved.allowed.extend(
+					guild
+						.roles
+						.values()
+						.filter(|role| role.position > base.position)
+						.map(|role| role.id),
+				);
+			}
+		}
+	}
+
+	let policies = get_policies();
+	if resolved.allowed.is_empty() && resolved.denied.is_empty() {
+		return policies.remove(&guild_id).is_some();
+	}
+	policies.insert(guild_id, resolved.clone()).as_ref() != Some(&resolved)
+}
+
+pub(crate) fn clear_role_policies(guild_id: GuildId) {
+	get_policies().remove(&guild_id);
+}
+
+/// Whether a member of `guild_id` with `roles` may be transcribed.
+pub(crate) fn allows(guild_id: GuildId, roles: &[RoleId]) -> bool {
+	get_policies().get(&guild_id).map_or(true, |policy| {
+		!roles.iter().any(|role| policy.denied.contains(role))
+			&& (policy.allowed.is_empty() || roles.iter().any(|role| policy.allowed.contains(role)))
+	})
+}
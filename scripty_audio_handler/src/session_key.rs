@@ -0,0 +1,24 @@
+//! Identifies a voice session. A bot can only be in one voice chat per guild, so with extra bots
+//! set in `extra_bot_tokens`, a guild has one session per bot.
+
+use serenity::{
+	client::Context,
+	model::id::{GuildId, UserId},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+	pub guild_id: GuildId,
+	/// The bot in the voice chat.
+	pub bot_id:   UserId,
+}
+
+impl SessionKey {
+	/// The session of the bot `ctx` belongs to in `guild_id`.
+	pub fn new(ctx: &Context, guild_id: GuildId) -> Self {
+		Self {
+			guild_id,
+			bot_id: ctx.cache.current_user().id,
+		}
+	}
+}
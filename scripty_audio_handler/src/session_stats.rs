@@ -0,0 +1,78 @@
+//! Tracks active voice sessions, for exporting per-shard usage metrics.
+
+use std::sync::{Arc, OnceLock as OnceCell, Weak};
+
+use dashmap::DashMap;
+use serenity::all::{GuildId, UserId};
+
+use crate::{audio_handler::SsrcMaps, SessionKey};
+
+/// Weak pointers to the SSRC state of every active session.
+///
+/// Weak, so that a session that was dropped without being removed from here is not kept alive.
+static ACTIVE_SESSIONS: OnceCell<DashMap<SessionKey, Weak<SsrcMaps>, ahash::RandomState>> =
+	OnceCell::new();
+
+fn get_active_sessions() -> &'static DashMap<SessionKey, Weak<SsrcMaps>, ahash::RandomState> {
+	ACTIVE_SESSIONS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// A snapshot of a single voice session's usage.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionStats {
+	pub guild_id:          GuildId,
+	/// The bot in the voice chat.
+	pub bot_id:            UserId,
+	/// Number of open streams to STT servers.
+	pub stt_streams:       usize,
+	/// Number of users in the call who have spoken, and are not being ignored.
+	///
+	/// Stage audiences and muted listeners aren't counted, as they're never transcribed.
+	pub transcribed_users: usize,
+}
+
+pub(crate) fn register_session(key: SessionKey, ssrc_state: &Arc<SsrcMaps>) {
+	get_active_sessions().insert(key, Arc::downgrade(ssrc_state));
+}
+
+pub(crate) fn unregister_session(key: SessionKey) {
+	get_active_sessions().remove(&key);
+}
+
+/// Whether any bot has a session in `guild_id`.
+pub(crate) fn has_session(guild_id: GuildId) -> bool {
+	get_active_sessions()
+		.iter()
+		.any(|entry| entry.key().guild_id == guild_id && entry.value().strong_count() > 0)
+}
+
+/// Get stats for every active voice session, pruning any that have since been dropped.
+pub fn get_session_stats() -> Vec<SessionStats> {
+	let sessions = get_active_sessions();
+	sessions.retain(|_, state| state.strong_count() > 0);
+
+	sessions
+		.iter()
+		.filter_map(|entry| {
+			let state = entry.value().upgrade()?;
+			let transcribed_users = state
+				.ssrc_user_id_map
+				.iter()
+				.filter(|x| state.ssrc_spoken.contains(x.key()))
+				.filter(|x| {
+					!state
+						.ssrc_ignored_map
+						.get(x.key())
+						.map_or(false, |ignored| *ignored.value())
+				})
+				.count();
+
+			Some(SessionStats {
+				guild_id: entry.key().guild_id,
+				bot_id: entry.key().bot_id,
+				stt_streams: state.ssrc_stream_map.len(),
+				transcribed_users,
+			})
+		})
+		.collect()
+}
@@ -0,0 +1,211 @@
+//! Persists active voice sessions, so they can be resumed after a restart.
+
+use scripty_premium::PremiumTierList;
+use serenity::{
+	client::Context,
+	model::id::{ChannelId, GuildId, UserId},
+};
+
+use crate::SessionKey;
+
+/// A voice session that was active when the bot last stopped.
+#[derive(Debug, Clone, Copy)]
+pub struct PersistedSession {
+	pub guild_id:              GuildId,
+	/// The bot in the voice chat, which resumes it.
+	pub bot_id:                UserId,
+	pub channel_id:            ChannelId,
+	pub voice_channel_id:      ChannelId,
+	pub thread_id:             Option<ChannelId>,
+	pub record_transcriptions: bool,
+}
+
+pub(crate) async fn persist_session(session: PersistedSession) -> Result<(), sqlx::Error> {
+	sqlx::query!(
+		"INSERT INTO active_sessions (guild_id, bot_id, channel_id, voice_channel_id, thread_id, \
+		 record_transcriptions) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (guild_id, bot_id) DO \
+		 UPDATE SET channel_id = $3, voice_channel_id = $4, thread_id = $5, record_transcriptions \
+		 = $6, started_at = NOW()",
+		session.guild_id.get() as i64,
+		session.bot_id.get() as i64,
+		session.channel_id.get() as i64,
+		session.voice_channel_id.get() as i64,
+		session.thread_id.map(|t| t.get() as i64),
+		session.record_transcriptions,
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(())
+}
+
+/// The tier of the premium subscription of `key`'s guild, and how many other voice sessions are
+/// active in the subscription's guilds, including other bots' in the same guild. None if the guild
+/// has no premium.
+pub(crate) async fn get_subscription_sessions(
+	key: SessionKey,
+) -> Result<Option<(PremiumTierList, i64)>, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT u.premium_level, (SELECT COUNT(*) FROM active_sessions s INNER JOIN guilds o ON \
+		 o.guild_id = s.guild_id WHERE o.premium_owner_id = g.premium_owner_id AND NOT \
+		 (s.guild_id = g.guild_id AND s.bot_id = $2)) AS \"other_sessions!\" FROM guilds g INNER \
+		 JOIN users u ON u.user_id = g.premium_owner_id WHERE g.guild_id = $1",
+		key.guild_id.get() as i64,
+		key.bot_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row
+		.filter(|row| row.premium_level > 0)
+		.map(|row| (PremiumTierList::from(row.premium_level), row.other_sessions)))
+}
+
+/// Forget a session, so it is not resumed on the next startup.
+pub(crate) async fn remove_session(key: SessionKey) {
+	if let Err(e) = sqlx::query!(
+		"DELETE FROM active_sessions WHERE guild_id = $1 AND bot_id = $2",
+		key.guild_id.get() as i64,
+		key.bot_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await
+	{
+		error!(guild_id = %key.guild_id, "failed to remove persisted session: {}", e);
+	}
+}
+
+/// Give the main bot the sessions persisted before sessions were stored per bot.
+pub async fn claim_legacy_sessions(ctx: &Context) -> Result<(), sqlx::Error> {
+	let bot_id = ctx.cache.current_user().id.get() as i64;
+	let res = sqlx::query!(
+		"UPDATE active_sessions SET bot_id = $1 WHERE bot_id = 0",
+		bot_id
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	if res.rows_affected() > 0 {
+		info!(
+			"claimed {} sessions persisted without a bot",
+			res.rows_affected()
+		);
+	}
+	Ok(())
+}
+
+/// Forget persisted sessions of `ctx`'s bot in this process's guilds that it isn't in a voice chat
+/// for, like ones left behind by a crash. Returns how many were forgotten.
+pub async fn reap_stale_sessions(ctx: &Context) -> Result<u64, sqlx::Error> {
+	let db = scripty_db::get_db();
+	let bot_id = ctx.cache.current_user().id.get() as i64;
+	let guild_ids: Vec<i64> = ctx.cache.guilds().iter().map(|g| g.get() as i64).collect();
+	// newer ones may still be joining
+	let sessions = sqlx::query!(
+		"SELECT guild_id FROM active_sessions WHERE guild_id = ANY($1) AND bot_id = $2 AND \
+		 started_at < NOW() - INTERVAL '10 minutes'",
+		&guild_ids,
+		bot_id
+	)
+	.fetch_all(db)
+	.await?;
+
+	let mut stale = Vec::new();
+	for session in sessions {
+		let guild_id = GuildId::new(session.guild_id as u64);
+		if crate::get_voice_channel_id(ctx, guild_id).await.is_none() {
+			stale.push(session.guild_id);
+		}
+	}
+	if stale.is_empty() {
+		return Ok(0);
+	}
+
+	let res = sqlx::query!(
+		"DELETE FROM active_sessions WHERE guild_id = ANY($1) AND bot_id = $2 AND started_at < \
+		 NOW() - INTERVAL '10 minutes'",
+		&stale,
+		bot_id
+	)
+	.execute(db)
+	.await?;
+	Ok(res.rows_affected())
+}
+
+/// Rejoin every persisted session of `ctx`'s bot in the given guilds.
+///
+/// Sessions that fail to resume are forgotten.
+pub async fn resume_sessions(ctx: &Context, guilds: &[GuildId]) -> Result<(), sqlx::Error> {
+	let guild_ids: Vec<i64> = guilds.iter().map(|g| g.get() as i64).collect();
+	let bot_id = ctx.cache.current_user().id.get() as i64;
+	let sessions = sqlx::query!(
+		"SELECT guild_id, channel_id, voice_channel_id, thread_id, record_transcriptions FROM \
+		 active_sessions WHERE guild_id = ANY($1) AND bot_id = $2",
+		&guild_ids,
+		bot_id
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+	info!("resuming {} voice sessions", sessions.len());
+
+	for session in sessions {
+		let guild_id = GuildId::new(session.guild_id as u64);
+		let channel_id = ChannelId::new(session.channel_id as u64);
+		let thread_id = session.thread_id.map(|t| ChannelId::new(t as u64));
+
+		let res = crate::connect_to_vc(
+			ctx.clone(),
+			guild_id,
+			channel_id,
+			ChannelId::new(session.voice_channel_id as u64),
+			thread_id,
+			false,
+			session.record_transcriptions,
+		)
+		.await;
+
+		match res {
+			Ok(()) => {
+				debug!(%guild_id, "resumed voice session");
+				let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+				let _ = thread_id
+					.unwrap_or(channel_id)
+					.say(ctx, format_message!(language, "voice-session-resumed"))
+					.await;
+			}
+			Err(e) => {
+				warn!(%guild_id, "failed to resume voice session: {}", e);
+				remove_session(SessionKey::new(ctx, guild_id)).await;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Send a plain message to the transcript channel of each persisted session of `ctx`'s bot in the
+/// given guilds, in each guild's language.
+pub(crate) async fn notify_sessions(
+	ctx: &Context,
+	guilds: &[GuildId],
+	message_id: &str,
+) -> Result<(), sqlx::Error> {
+	let guild_ids: Vec<i64> = guilds.iter().map(|g| g.get() as i64).collect();
+	let bot_id = ctx.cache.current_user().id.get() as i64;
+	let sessions = sqlx::query!(
+		"SELECT guild_id, channel_id, thread_id FROM active_sessions WHERE guild_id = ANY($1) AND \
+		 bot_id = $2",
+		&guild_ids,
+		bot_id
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	for session in sessions {
+		let target = ChannelId::new(session.thread_id.unwrap_or(session.channel_id) as u64);
+		let language = scripty_i18n::get_guild_language(session.guild_id as u64).await;
+		if let Err(e) = target.say(ctx, format_message!(language, message_id)).await {
+			warn!(%target, "failed to notify session: {}", e);
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,98 @@
+//! Tells guilds with a webhook URL set (with `/config webhook_url`) when a session ends.
+//!
+//! Deliveries go through the delivery queue, which signs them and retries them if the guild's
+//! endpoint is having issues. Guilds without a webhook URL are skipped when sending.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use scripty_utils::delivery::{self, DeliveryStatus, DeliveryTarget};
+use serde::Serialize;
+use serenity::{
+	http::Http,
+	model::id::{ChannelId, GuildId},
+};
+
+use crate::transcript_store::TranscriptSession;
+
+#[derive(Serialize)]
+struct SessionEnded<'a> {
+	event:            &'static str,
+	guild_id:         String,
+	voice_channel_id: String,
+	/// Unix timestamps, in seconds.
+	started_at:       u64,
+	ended_at:         u64,
+	/// Only set if the guild has an API key, so the session was stored.
+	session_id:       Option<i64>,
+	/// Where to fetch the stored transcript from, if the transcript API has a public URL.
+	transcript_url:   Option<String>,
+	/// Only set if the session was recording transcripts.
+	transcript:       Option<&'a [String]>,
+	/// Lines members highlighted by reacting to them.
+	highlights:       &'a [String],
+}
+
+fn unix_secs(t: SystemTime) -> u64 {
+	t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Send a summary of the session in `voice_channel_id` to `guild_id`'s webhook, if it has one.
+pub(crate) async fn send_session_ended(
+	http: &Http,
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	started_at: SystemTime,
+	transcript_session: &TranscriptSession,
+	transcript: Option<&[String]>,
+	highlights: &[String],
+) {
+	let session_id = transcript_session.id();
+	let transcript_url = session_id.and_then(|id| {
+		let base = scripty_config::get_config()
+			.transcript_api
+			.public_url
+			.as_deref()?;
+		Some(format!(
+			"{}/api/sessions/{}",
+			base.trim_end_matches('/'),
+			id
+		))
+	});
+	let payload = SessionEnded {
+		event: "session.ended",
+		guild_id: guild_id.to_string(),
+		voice_channel_id: voice_channel_id.to_string(),
+		started_at: unix_secs(started_at),
+		ended_at: unix_secs(SystemTime::now()),
+		session_id,
+		transcript_url,
+		transcript,
+		highlights,
+	};
+
+	// a handler is only ever started once per guild at a time
+	let dedupe_key = format!(
+		"session_webhook:{}:{}",
+		guild_id,
+		started_at
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |d| d.as_millis())
+	);
+	match delivery::deliver(
+		http,
+		DeliveryTarget::GuildWebhook(guild_id),
+		&payload,
+		&dedupe_key,
+	)
+	.await
+	{
+		Ok(DeliveryStatus::Sent) => {}
+		Ok(DeliveryStatus::Queued) => {
+			debug!(%guild_id, "session webhook queued for retry");
+		}
+		Err(e) => {
+			// most likely a 4xx from their end, which retrying won't fix
+			warn!(%guild_id, "failed to send session webhook: {}", e);
+		}
+	}
+}
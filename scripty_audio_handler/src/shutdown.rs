@@ -0,0 +1,86 @@
+//! Graceful shutdown, draining in-flight transcriptions before leaving every call.
+
+use std::{
+	sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+	time::{Duration, Instant},
+};
+
+use serenity::{client::Context, model::id::GuildId};
+
+/// How long to wait for open transcriptions to finish before shutting down anyway.
+pub const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+static DRAINING: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether the bot is shutting down, and should stop accepting new audio.
+pub fn is_draining() -> bool {
+	DRAINING.load(Ordering::Relaxed)
+}
+
+/// Marks a unit of work (a voice tick, a transcript being sent) as in flight until dropped.
+pub(crate) struct InFlightGuard(());
+
+impl InFlightGuard {
+	pub(crate) fn new() -> Self {
+		IN_FLIGHT.fetch_add(1, Ordering::AcqRel);
+		Self(())
+	}
+}
+
+impl Drop for InFlightGuard {
+	fn drop(&mut self) {
+		IN_FLIGHT.fetch_sub(1, Ordering::AcqRel);
+	}
+}
+
+/// Stop accepting new audio, wait up to `timeout` for open streams to finalize and their
+/// transcripts to be sent, let every session of `ctx`'s bot know we're restarting, then leave
+/// every call it's in. Extra bots are drained one after another, as the wait is shared.
+///
+/// Sessions are left persisted, so they are resumed on the next startup.
+pub async fn drain(ctx: &Context, timeout: Duration) {
+	info!("draining voice sessions");
+	DRAINING.store(true, Ordering::Relaxed);
+
+	let start = Instant::now();
+	loop {
+		let open_streams: usize = crate::get_session_stats()
+			.iter()
+			.map(|s| s.stt_streams)
+			.sum();
+		let in_flight = IN_FLIGHT.load(Ordering::Acquire);
+		if open_streams == 0 && in_flight == 0 {
+			info!("all transcriptions drained after {:?}", start.elapsed());
+			break;
+		}
+		if start.elapsed() > timeout {
+			warn!(
+				%open_streams,
+				%in_flight,
+				"timed out draining transcriptions, shutting down anyway"
+			);
+			break;
+		}
+		tokio::time::sleep(Duration::from_millis(250)).await;
+	}
+
+	let songbird = crate::get_songbird_from_ctx(ctx).await;
+	let guild_ids: Vec<GuildId> = songbird
+		.iter()
+		.map(|(guild_id, _)| GuildId::new(guild_id.0.get()))
+		.collect();
+
+	if let Err(e) =
+		crate::session_store::notify_sessions(ctx, &guild_ids, "voice-session-restarting").await
+	{
+		error!("failed to notify sessions of restart: {}", e);
+	}
+
+	for guild_id in guild_ids {
+		// don't use `disconnect_from_vc`, that would forget the session
+		if let Err(e) = songbird.remove(guild_id).await {
+			warn!(%guild_id, "failed to leave call during shutdown: {}", e);
+		}
+	}
+}
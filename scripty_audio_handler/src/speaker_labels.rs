@@ -0,0 +1,95 @@
+//! Colors or emoji for each speaker, set with `/config speaker_labels`, so long transcripts with
+//! lots of people talking are quicker to scan.
+//!
+//! Speakers get the next free label the first time they're transcribed in a session,
+//! and keep it until the session ends, even if they leave and come back.
+
+use std::{
+	collections::HashMap,
+	sync::atomic::{AtomicU8, Ordering},
+};
+
+use parking_lot::Mutex;
+
+/// Stored in `guilds.speaker_labels`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeakerLabelMode {
+	Off   = 0,
+	/// The transcript goes in an embed, with the speaker's color as its accent.
+	Color = 1,
+	/// The transcript starts with the speaker's emoji.
+	Emoji = 2,
+}
+
+impl SpeakerLabelMode {
+	/// Decode `guilds.speaker_labels`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Color,
+			2 => Self::Emoji,
+			_ => Self::Off,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SpeakerLabel {
+	Color(u32),
+	Emoji(&'static str),
+}
+
+/// Emoji and embed colors, handed out in order. Once they run out, colors are reused
+/// with square emoji, so emoji stay distinct for longer.
+const LABELS: [(&str, u32); 18] = [
+	("🔴", 0xe74c3c),
+	("🔵", 0x3498db),
+	("🟢", 0x2ecc71),
+	("🟡", 0xf1c40f),
+	("🟣", 0x9b59b6),
+	("🟠", 0xe67e22),
+	("🟤", 0x8d6e63),
+	("⚪", 0xecf0f1),
+	("⚫", 0x23272a),
+	("🟥", 0xe74c3c),
+	("🟦", 0x3498db),
+	("🟩", 0x2ecc71),
+	("🟨", 0xf1c40f),
+	("🟪", 0x9b59b6),
+	("🟧", 0xe67e22),
+	("🟫", 0x8d6e63),
+	("⬜", 0xecf0f1),
+	("⬛", 0x23272a),
+];
+
+#[derive(Default)]
+pub struct SpeakerLabels {
+	mode:     AtomicU8,
+	/// User IDs mapped to their index in `LABELS`, in the order they first spoke.
+	assigned: Mutex<HashMap<u64, usize>>,
+}
+
+impl SpeakerLabels {
+	pub fn set_mode(&self, mode: SpeakerLabelMode) {
+		self.mode.store(mode as u8, Ordering::Relaxed);
+	}
+
+	/// Get `user_id`'s label, giving them one if they don't have one yet.
+	/// None if the guild has speaker labels turned off.
+	pub fn get(&self, user_id: u64) -> Option<SpeakerLabel> {
+		let mode = SpeakerLabelMode::from_db(self.mode.load(Ordering::Relaxed).into());
+		if mode == SpeakerLabelMode::Off {
+			return None;
+		}
+
+		let index = {
+			let mut assigned = self.assigned.lock();
+			let next = assigned.len();
+			*assigned.entry(user_id).or_insert(next)
+		};
+		let (emoji, color) = LABELS[index % LABELS.len()];
+		Some(match mode {
+			SpeakerLabelMode::Color => SpeakerLabel::Color(color),
+			_ => SpeakerLabel::Emoji(emoji),
+		})
+	}
+}
@@ -0,0 +1,105 @@
+//! DMing transcripts to members who subscribed to a voice chat with `/subscribe`, when its session
+//! ends.
+//!
+//! Subscribers who can't see the voice chat, or the channel it was transcribed into, are skipped,
+//! so subscribing can't be used to read a channel someone's been kept out of.
+
+use scripty_utils::subscriptions;
+use serenity::{
+	all::{ChannelId, GuildId, UserId},
+	builder::{CreateAttachment, CreateMessage},
+	client::Context,
+};
+
+use crate::transcript_store::TranscriptSession;
+
+/// DM `session`'s transcript, or its minutes, to everyone subscribed to `voice_channel_id`.
+/// `channel_id` is the channel it was transcribed into, or the parent of the thread it was.
+pub(crate) async fn send(
+	ctx: &Context,
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	channel_id: ChannelId,
+	session: &TranscriptSession,
+) {
+	let transcript = session.transcript();
+	if transcript.is_empty() {
+		return;
+	}
+	let subscribers = match subscriptions::get_subscribers(guild_id, voice_channel_id).await {
+		Ok(subscribers) => subscribers,
+		Err(e) => {
+			error!(%guild_id, "failed to fetch transcript subscribers: {}", e);
+			return;
+		}
+	};
+	if subscribers.is_empty() {
+		return;
+	}
+
+	let transcript = transcript.join("\n");
+	let minutes = session.minutes();
+	let guild_name = ctx
+		.cache
+		.guild(guild_id)
+		.map(|guild| guild.name.clone())
+		.unwrap_or_default();
+	for subscriber in subscribers {
+		let user_id = subscriber.user_id;
+		if !can_view(ctx, guild_id, user_id, [voice_channel_id, channel_id]).await {
+			debug!(%guild_id, %user_id, "subscriber can't see the transcripts, skipping");
+			continue;
+		}
+
+		let language =
+			scripty_i18n::get_resolved_language(user_id.get(), Some(guild_id.get())).await;
+		let (message_id, attachment) = if subscriber.summary_only {
+			let markdown = format!(
+				"# {}\n{}",
+				format_message!(language, "minutes-title"),
+				minutes.sections_markdown(&language)
+			);
+			(
+				"subscription-summary-message",
+				CreateAttachment::bytes(markdown, "minutes.md"),
+			)
+		} else {
+			(
+				"subscription-transcript-message",
+				CreateAttachment::bytes(transcript.clone(), "transcript.txt"),
+			)
+		};
+		let message = CreateMessage::new()
+			.content(format_message!(
+				language,
+				message_id,
+				guildName: guild_name.clone(),
+				voiceChannelId: voice_channel_id.get()
+			))
+			.add_file(attachment);
+		if let Err(e) = user_id.direct_message(ctx, message).await {
+			debug!(%guild_id, %user_id, "failed to DM transcript to subscriber: {}", e);
+		}
+	}
+}
+
+/// Whether `user_id` is still in `guild_id`, and can see every one of `channel_ids`.
+async fn can_view(
+	ctx: &Context,
+	guild_id: GuildId,
+	user_id: UserId,
+	channel_ids: [ChannelId; 2],
+) -> bool {
+	// they may have left the server since subscribing
+	let Ok(member) = guild_id.member(ctx, user_id).await else {
+		return false;
+	};
+	let Some(guild) = ctx.cache.guild(guild_id) else {
+		return false;
+	};
+	channel_ids.iter().all(|channel_id| {
+		guild.channels.get(channel_id).map_or(false, |channel| {
+			guild.user_permissions_in(channel, &member).view_channel()
+		})
+	})
+}
@@ -0,0 +1,108 @@
+//! Per-user talk time, for `/analytics`.
+//!
+//! Time spoken is counted in memory as transcripts come in, and added to `user_talk_time` by
+//! `flush_talk_time`, in hourly buckets. Users who opted out with `/data_storage` are skipped when
+//! flushing, so nothing is ever stored for them.
+
+use std::{
+	sync::OnceLock as OnceCell,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use serenity::all::{GuildId, UserId};
+
+/// How long talk time is kept for.
+const KEEP_DAYS: i32 = 365;
+
+#[derive(Debug, Default)]
+struct PendingTalkTime {
+	ms:         u64,
+	utterances: u64,
+}
+
+/// Keyed by guild, user and the Unix timestamp of the hour it was said in.
+type PendingMap = DashMap<(GuildId, UserId, u64), PendingTalkTime, ahash::RandomState>;
+
+static PENDING: OnceCell<PendingMap> = OnceCell::new();
+
+fn get_pending() -> &'static PendingMap {
+	PENDING.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Count an utterance by `user_id`, started at `spoken_at` and lasting `duration`.
+pub(crate) fn record(
+	guild_id: GuildId,
+	user_id: UserId,
+	spoken_at: SystemTime,
+	duration: Duration,
+) {
+	let secs = spoken_at
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_secs());
+	let mut pending = get_pending()
+		.entry((guild_id, user_id, secs - secs % 3600))
+		.or_default();
+	pending.ms += duration.as_millis() as u64;
+	pending.utterances += 1;
+}
+
+/// Add talk time counted since the last flush to the database.
+pub async fn flush_talk_time() -> Result<(), sqlx::Error> {
+	let pending = get_pending();
+	let keys = pending.iter().map(|x| *x.key()).collect::<Vec<_>>();
+
+	let mut guild_ids = Vec::with_capacity(keys.len());
+	let mut user_ids = Vec::with_capacity(keys.len());
+	let mut legacy_user_ids = Vec::with_capacity(keys.len());
+	let mut hours = Vec::with_capacity(keys.len());
+	let mut ms = Vec::with_capacity(keys.len());
+	let mut utterances = Vec::with_capacity(keys.len());
+	for key in keys {
+		let Some((_, talk_time)) = pending.remove(&key) else {
+			continue;
+		};
+		let (guild_id, user_id, hour) = key;
+		guild_ids.push(guild_id.get() as i64);
+		user_ids.push(scripty_utils::hash_user_id(user_id.get()));
+		legacy_user_ids.push(scripty_utils::legacy_hash_user_id(user_id.get()));
+		hours.push(hour as i64);
+		ms.push(talk_time.ms as i64);
+		utterances.push(talk_time.utterances as i64);
+	}
+	if guild_ids.is_empty() {
+		return Ok(());
+	}
+
+	// unlike quota usage, losing a few seconds of this to a failed flush doesn't matter
+	// users who haven't been rehashed yet still have their opt-out under the legacy hash
+	sqlx::query!(
+		"INSERT INTO user_talk_time (guild_id, user_id, hour, ms_spoken, utterances) SELECT \
+		 p.guild_id, p.user_id, to_timestamp(p.hour), p.ms, p.utterances FROM \
+		 UNNEST($1::BIGINT[], $2::BYTEA[], $3::BYTEA[], $4::BIGINT[], $5::BIGINT[], $6::BIGINT[]) \
+		 AS p(guild_id, user_id, legacy_user_id, hour, ms, utterances) WHERE NOT EXISTS (SELECT 1 \
+		 FROM users u WHERE u.user_id IN (p.user_id, p.legacy_user_id) AND u.analytics_opt_out) \
+		 ON CONFLICT (guild_id, user_id, hour) DO UPDATE SET ms_spoken = user_talk_time.ms_spoken \
+		 + EXCLUDED.ms_spoken, utterances = user_talk_time.utterances + EXCLUDED.utterances",
+		&guild_ids,
+		&user_ids,
+		&legacy_user_ids,
+		&hours,
+		&ms,
+		&utterances
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(())
+}
+
+/// Delete talk time older than `KEEP_DAYS`. Returns how many hours of it were deleted.
+pub async fn delete_old_talk_time() -> Result<u64, sqlx::Error> {
+	Ok(sqlx::query!(
+		"DELETE FROM user_talk_time WHERE hour < NOW() - make_interval(days => $1)",
+		KEEP_DAYS
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected())
+}
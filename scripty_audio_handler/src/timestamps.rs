@@ -0,0 +1,69 @@
+//! Timestamps before each transcript, set with `/config timestamps`,
+//! for servers that keep transcripts as meeting minutes.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Stored in `guilds.timestamps`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampMode {
+	Off      = 0,
+	/// Time since the session started, ie `[00:12:34]`.
+	Relative = 1,
+	/// Time of day, in each reader's own timezone.
+	Absolute = 2,
+}
+
+impl TimestampMode {
+	/// Decode `guilds.timestamps`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Relative,
+			2 => Self::Absolute,
+			_ => Self::Off,
+		}
+	}
+
+	/// Prefix for a transcript of speech starting at `spoken_at`, in a session started at
+	/// `session_start`. Empty if timestamps are off.
+	pub fn prefix(self, session_start: SystemTime, spoken_at: SystemTime) -> String {
+		match self {
+			Self::Off => String::new(),
+			Self::Relative => {
+				let secs = spoken_at
+					.duration_since(session_start)
+					.map_or(0, |d| d.as_secs());
+				format!(
+					"[{:02}:{:02}:{:02}] ",
+					secs / 3600,
+					secs / 60 % 60,
+					secs % 60
+				)
+			}
+			Self::Absolute => {
+				let secs = spoken_at
+					.duration_since(UNIX_EPOCH)
+					.map_or(0, |d| d.as_secs());
+				format!("<t:{}:T> ", secs)
+			}
+		}
+	}
+
+	/// Like `prefix`, but for the transcript file, where Discord's timestamp markup isn't
+	/// rendered. Absolute timestamps are in UTC instead.
+	pub fn export_prefix(self, session_start: SystemTime, spoken_at: SystemTime) -> String {
+		match self {
+			Self::Absolute => {
+				let secs = spoken_at
+					.duration_since(UNIX_EPOCH)
+					.map_or(0, |d| d.as_secs());
+				format!(
+					"[{:02}:{:02}:{:02} UTC] ",
+					secs / 3600 % 24,
+					secs / 60 % 60,
+					secs % 60
+				)
+			}
+			_ => self.prefix(session_start, spoken_at),
+		}
+	}
+}
@@ -0,0 +1,266 @@
+//! Publishes transcripts as live captions if the guild turned them on, mirrors them to the guild's
+//! bridge if it has one, keeps them for `/minutes`, and stores those of guilds with an API key, so
+//! they can be fetched from the transcript API.
+//!
+//! Every `AudioHandler` gets its own session, so reconnecting starts a new one.
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
+
+use scripty_utils::{
+	bridge::{self, BridgeSender},
+	live_captions::{self, LiveCaptionEvent},
+	spawn_logged,
+};
+use serenity::model::id::{ChannelId, GuildId, MessageId, UserId};
+use tokio::sync::oneshot;
+
+use crate::{minutes::MinutesLog, SessionKey};
+
+/// How long to wait for a segment's transcript message to be sent before storing it without one.
+pub(crate) const MESSAGE_ID_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub(crate) struct TranscriptSession {
+	/// None if the session isn't stored.
+	id:               Option<i64>,
+	guild_id:         GuildId,
+	voice_channel_id: ChannelId,
+	started:          Instant,
+	bridge:           Option<BridgeSender>,
+	minutes:          Arc<MinutesLog>,
+	/// Whether the guild has live captions on, kept up to date by `AudioHandler::reload_config`.
+	live_captions:    AtomicBool,
+	/// Whether anything was published, so followers get told when the session ends.
+	published:        AtomicBool,
+}
+
+impl TranscriptSession {
+	/// Start `key`'s session in `voice_channel_id`, storing it if the guild has an API key.
+	/// `transcript_channel_id` is the channel or thread transcripts are sent to.
+	pub(crate) async fn start(
+		key: SessionKey,
+		voice_channel_id: ChannelId,
+		transcript_channel_id: ChannelId,
+	) -> Self {
+		let guild_id = key.guild_id;
+		let row = sqlx::query!(
+			"INSERT INTO transcript_sessions (guild_id, voice_channel_id, transcript_channel_id) \
+			 SELECT $1, $2, $3 WHERE EXISTS (SELECT 1 FROM guild_api_keys WHERE guild_id = $1) \
+			 RETURNING session_id",
+			guild_id.get() as i64,
+			voice_channel_id.get() as i64,
+			transcript_channel_id.get() as i64
+		)
+		.fetch_optional(scripty_db::get_db())
+		.await;
+		// storing transcripts isn't worth failing to join over
+		let id = match row {
+			Ok(row) => row.map(|row| row.session_id),
+			Err(e) => {
+				error!(%guild_id, "failed to start transcript session: {}", e);
+				None
+			}
+		};
+
+		let bridge = match bridge::get_bridge(guild_id, voice_channel_id).await {
+			Ok(bridge) => bridge.map(|target| BridgeSender::new(guild_id, target)),
+			Err(e) => {
+				error!(%guild_id, "failed to fetch transcript bridge: {}", e);
+				None
+			}
+		};
+
+		let live_captions = live_captions::is_enabled(guild_id)
+			.await
+			.unwrap_or_else(|e| {
+				error!(%guild_id, "failed to fetch whether live captions are on: {}", e);
+				false
+			});
+		if live_captions {
+			live_captions::publish(
+				guild_id,
+				&LiveCaptionEvent::SessionStarted {
+					session_id:       id,
+					voice_channel_id: voice_channel_id.to_string(),
+				},
+			)
+			.await;
+		}
+
+		Self {
+			id,
+			guild_id,
+			voice_channel_id,
+			started: Instant::now(),
+			bridge,
+			minutes: crate::minutes::start_log(key),
+			live_captions: AtomicBool::new(live_captions),
+			published: AtomicBool::new(live_captions),
+		}
+	}
+
+	/// Start or stop publishing live captions, as the guild turned them on or off.
+	pub(crate) fn set_live_captions(&self, enabled: bool) {
+		self.live_captions.store(enabled, Ordering::Relaxed);
+	}
+
+	/// The ID the session is stored under, if it's stored.
+	pub(crate) fn id(&self) -> Option<i64> {
+		self.id
+	}
+
+	/// Publish and store what `speaker` said between `start` and `end`, in the background.
+	/// `language` is the language it was detected as, if the guild has auto-detect on.
+	/// `message_id` gets the ID of the message it was sent in, so `/search` can link to it.
+	pub(crate) fn record(
+		&self,
+		speaker: String,
+		speaker_id: Option<UserId>,
+		start: Instant,
+		end: Instant,
+		content: String,
+		language: Option<&'static str>,
+		message_id: Option<oneshot::Receiver<MessageId>>,
+	) {
+		let session_id = self.id;
+		let guild_id = self.guild_id;
+		let voice_channel_id = self.voice_channel_id;
+		let start_ms = start.saturating_duration_since(self.started).as_millis() as i64;
+		let end_ms = end.saturating_duration_since(self.started).as_millis() as i64;
+		if let Some(bridge) = &self.bridge {
+			bridge.send(speaker.clone(), content.clone());
+		}
+		let line_index = self.minutes.push(&speaker, start_ms, end_ms, &content);
+		let minutes = Arc::clone(&self.minutes);
+		let publish = self.live_captions.load(Ordering::Relaxed);
+		if publish {
+			self.published.store(true, Ordering::Relaxed);
+		}
+
+		spawn_logged("transcript_segment_store", async move {
+			if publish {
+				live_captions::publish(
+					guild_id,
+					&LiveCaptionEvent::Transcript {
+						session_id,
+						voice_channel_id: voice_channel_id.to_string(),
+						speaker: speaker.clone(),
+						start_ms,
+						end_ms,
+						content: content.clone(),
+						language: language.map(str::to_owned),
+					},
+				)
+				.await;
+			}
+
+			// dropped without an ID if the message couldn't be sent right away
+			let message_id = match message_id {
+				Some(rx) => tokio::time::timeout(MESSAGE_ID_TIMEOUT, rx)
+					.await
+					.ok()
+					.and_then(Result::ok),
+				None => None,
+			};
+			if let (Some(index), Some(message_id)) = (line_index, message_id) {
+				minutes.set_message_id(index, message_id);
+			}
+
+			let Some(session_id) = session_id else {
+				return;
+			};
+			if let Err(e) = sqlx::query!(
+				"INSERT INTO transcript_segments (session_id, speaker, speaker_id, start_ms, \
+				 end_ms, content, language, message_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+				session_id,
+				speaker,
+				speaker_id.map(|id| scripty_utils::hash_user_id(id.get())),
+				start_ms,
+				end_ms,
+				content,
+				language,
+				message_id.map(|id| id.get() as i64)
+			)
+			.execute(scripty_db::get_db())
+			.await
+			{
+				// the key may have been revoked, taking the session with it
+				debug!(session_id, "failed to store transcript segment: {}", e);
+			}
+		});
+	}
+
+	/// Lines highlighted so far, rendered as lines of a transcript file.
+	pub(crate) fn highlights(&self) -> Vec<String> {
+		self.minutes
+			.highlights()
+			.iter()
+			.map(|highlight| highlight.to_export())
+			.collect()
+	}
+
+	/// Minutes of the session so far.
+	pub(crate) fn minutes(&self) -> crate::minutes::Minutes {
+		self.minutes.minutes()
+	}
+
+	/// Everything said so far, as lines of a transcript file.
+	pub(crate) fn transcript(&self) -> Vec<String> {
+		self.minutes.transcript()
+	}
+
+	/// Mark the session as over.
+	pub(crate) async fn end(&self) {
+		self.minutes.end();
+		// even if they were turned off since, so followers aren't left waiting
+		if self.published.load(Ordering::Relaxed) {
+			live_captions::publish(
+				self.guild_id,
+				&LiveCaptionEvent::SessionEnded {
+					session_id:       self.id,
+					voice_channel_id: self.voice_channel_id.to_string(),
+				},
+			)
+			.await;
+		}
+		if let Err(e) =
+			live_captions::revoke_overlay_tokens(self.guild_id, self.voice_channel_id).await
+		{
+			warn!(guild_id = %self.guild_id, "failed to revoke caption overlay tokens: {}", e);
+		}
+
+		let Some(session_id) = self.id else {
+			return;
+		};
+		if let Err(e) = sqlx::query!(
+			"UPDATE transcript_sessions SET ended_at = NOW() WHERE session_id = $1 AND ended_at \
+			 IS NULL",
+			session_id
+		)
+		.execute(scripty_db::get_db())
+		.await
+		{
+			warn!(session_id, "failed to end transcript session: {}", e);
+		}
+	}
+}
+
+/// End stored sessions the bot stopped before ending, in guilds with no session going now.
+/// They're ended as of their last transcript. Returns how many were ended.
+pub async fn end_orphaned_sessions() -> Result<u64, sqlx::Error> {
+	let res = sqlx::query!(
+		"UPDATE transcript_sessions s SET ended_at = s.started_at + COALESCE((SELECT MAX(end_ms) \
+		 FROM transcript_segments seg WHERE seg.session_id = s.session_id), 0) * INTERVAL '1 \
+		 millisecond' WHERE s.ended_at IS NULL AND s.started_at < NOW() - INTERVAL '10 minutes' \
+		 AND NOT EXISTS (SELECT 1 FROM active_sessions a WHERE a.guild_id = s.guild_id)"
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(res.rows_affected())
+}
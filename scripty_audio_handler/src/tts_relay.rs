@@ -0,0 +1,141 @@
+//! Reading messages out in the voice chat, for members who can't speak, set with
+//! `/config tts_relay`.
+//!
+//! Messages sent in the transcript channel by members with the guild's relay role are spoken by
+//! the TTS engine set in the `tts` config, and played into the call being transcribed.
+
+use std::sync::OnceLock as OnceCell;
+
+use dashmap::DashMap;
+use scripty_config::TtsConfig;
+use scripty_tts::{EngineParameters, EspeakNgEngine, TtsEngine, TtsEngineOutput};
+use serenity::{
+	all::{ChannelId, Message, RoleId},
+	client::Context,
+};
+use songbird::input::Input;
+
+use crate::SessionKey;
+
+/// Longest message that's read out. Anything past this is cut off.
+const MAX_RELAY_CHARS: usize = 300;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Relay {
+	/// Where messages are read from, the same channel or thread transcripts go to.
+	pub channel_id: ChannelId,
+	pub role:       RoleId,
+	/// Transcription language, which picks the voice.
+	pub language:   String,
+}
+
+static RELAYS: OnceCell<DashMap<SessionKey, Relay, ahash::RandomState>> = OnceCell::new();
+
+fn get_relays() -> &'static DashMap<SessionKey, Relay, ahash::RandomState> {
+	RELAYS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Start or stop relaying messages in `key`'s session.
+pub(crate) fn set_relay(key: SessionKey, relay: Option<Relay>) {
+	match relay {
+		Some(relay) => {
+			get_relays().insert(key, relay);
+		}
+		None => {
+			get_relays().remove(&key);
+		}
+	}
+}
+
+/// Whether messages can be read out at all, which needs the `tts` config.
+pub fn is_tts_enabled() -> bool {
+	scripty_config::get_config().tts.is_some()
+}
+
+/// Read `msg` out in the voice chat `ctx`'s bot is in, if it was sent in that session's transcript
+/// channel by a member with the relay role.
+pub async fn relay_message(ctx: Context, msg: Message) {
+	if msg.author.bot || msg.webhook_id.is_some() {
+		return;
+	}
+	let Some(guild_id) = msg.guild_id else {
+		return;
+	};
+	let Some(relay) = get_relays()
+		.get(&SessionKey::new(&ctx, guild_id))
+		.map(|relay| relay.value().clone())
+	else {
+		return;
+	};
+	if msg.channel_id != relay.channel_id {
+		return;
+	}
+	let text: String = msg.content.trim().chars().take(MAX_RELAY_CHARS).collect();
+	if text.is_empty() {
+		return;
+	}
+	let Some(TtsConfig::EspeakNg { path, speed }) = scripty_config::get_config().tts.clone() else {
+		return;
+	};
+
+	let member = match scripty_utils::entity_cache::get_member_details(
+		&ctx,
+		guild_id,
+		msg.author.id,
+	)
+	.await
+	{
+		Ok(member) => member,
+		Err(e) => {
+			warn!(%guild_id, "failed to fetch member for tts relay: {}", e);
+			return;
+		}
+	};
+	if !member.roles.contains(&relay.role) {
+		return;
+	}
+
+	let Some(call) = crate::get_songbird_from_ctx(&ctx).await.get(guild_id) else {
+		return;
+	};
+
+	let guild_language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	let spoken = format_message!(
+		guild_language,
+		"tts-relay-message",
+		author: member.display_name,
+		message: text
+	);
+	let engine = EspeakNgEngine::new(path);
+	let params = EngineParameters {
+		voice: relay.language,
+		speed,
+		..Default::default()
+	};
+	let wav = match tokio::task::spawn_blocking(move || engine.get_waveform(&spoken, &params)).await
+	{
+		Ok(Ok(TtsEngineOutput::Wav(wav))) => wav,
+		Ok(Ok(TtsEngineOutput::RawPcm {
+			samples,
+			sample_rate,
+		})) => match crate::events::encode_wav(&samples, sample_rate) {
+			Some(wav) => wav,
+			None => return,
+		},
+		Ok(Err(e)) => {
+			error!(%guild_id, "failed to synthesize tts relay message: {}", e);
+			return;
+		}
+		Err(e) => {
+			error!(%guild_id, "tts relay synthesis panicked: {}", e);
+			return;
+		}
+	};
+
+	let mut call = call.lock().await;
+	// calls are joined muted, as nothing is played in them otherwise
+	if let Err(e) = call.mute(false).await {
+		warn!(%guild_id, "failed to unmute for tts relay: {}", e);
+	}
+	call.play_input(Input::from(wav));
+}
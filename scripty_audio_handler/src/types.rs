@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, sync::Arc, time::Instant};
 
 use ahash::RandomState;
 use dashmap::{DashMap, DashSet};
@@ -27,8 +27,24 @@ pub type SsrcIgnoredMap = DashMap<u32, bool, RandomState>;
 /// Type alias for a `DashMap` containing SSRCs mapped to a voice audio ingest struct.
 pub type SsrcVoiceIngestMap = DashMap<u32, Option<VoiceIngest>, RandomState>;
 
-/// Type alias for a `DashSet` containing the SSRCs that were speaking this tick.
-pub type SsrcSpeakingSet = DashSet<u32, RandomState>;
+/// Type alias for a `DashMap` containing SSRCs mapped to the last RTP sequence number received from them.
+pub type SsrcLastSequenceMap = DashMap<u32, u16, RandomState>;
+
+/// Type alias for a `DashMap` containing SSRCs mapped to when they started their current utterance.
+pub type SsrcUtteranceStartMap = DashMap<u32, Instant, RandomState>;
+
+/// Type alias for a `DashMap` containing SSRCs mapped to their current utterance's audio,
+/// at 16kHz mono, kept so automod can attach it to its logs.
+pub type SsrcUtteranceAudioMap = DashMap<u32, Vec<i16>, RandomState>;
+
+/// Type alias for a `DashMap` containing the SSRCs that are mid-utterance, mapped to when audio
+/// was last heard from them.
+pub type SsrcLastSpokeMap = DashMap<u32, Instant, RandomState>;
+
+/// Type alias for a `DashSet` containing the SSRCs that have sent audio this session.
+///
+/// Only they count as taking part: stage audiences and muted listeners never send any.
+pub type SsrcSpokenSet = DashSet<u32, RandomState>;
 
 /// Type alias for a `DashSet` containing the current list of active users
 pub type ActiveUserSet = DashSet<u32, RandomState>;
@@ -0,0 +1,101 @@
+//! Hands-free control by talking to Scripty, ie "Scripty, stop transcribing", for members with
+//! the role set with `/config voice_commands`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use scripty_utils::spawn_logged;
+use serenity::{
+	all::{GuildId, RoleId, UserId},
+	client::Context,
+};
+
+/// What "Scripty" comes out of the model as.
+const WAKE_WORDS: &[&str] = &["scripty", "scriptie", "scripti"];
+
+/// Said before the wake word, ie "hey Scripty".
+const GREETINGS: &[&str] = &["hey", "ok", "okay"];
+
+/// Longer than this, and it's more likely someone talking about Scripty than to it.
+const MAX_COMMAND_WORDS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoiceCommand {
+	/// Stop posting transcripts, but stay in the call so they can be started again.
+	Stop,
+	Start,
+	/// Leave the call, like `/leave`.
+	Leave,
+}
+
+impl VoiceCommand {
+	/// Parse a transcript as a command, if it's addressed to Scripty.
+	pub fn parse(text: &str) -> Option<Self> {
+		let words: Vec<String> = text
+			.split_whitespace()
+			.map(|word| {
+				word.trim_matches(|c: char| !c.is_alphanumeric())
+					.to_lowercase()
+			})
+			.filter(|word| !word.is_empty())
+			.collect();
+		if words.len() > MAX_COMMAND_WORDS {
+			return None;
+		}
+
+		let mut words = words
+			.iter()
+			.map(String::as_str)
+			.skip_while(|word| GREETINGS.contains(word));
+		if !WAKE_WORDS.contains(&words.next()?) {
+			return None;
+		}
+		match words.next()? {
+			"stop" | "pause" => Some(Self::Stop),
+			"start" | "resume" | "continue" => Some(Self::Start),
+			"leave" => Some(Self::Leave),
+			_ => None,
+		}
+	}
+
+	/// Carry out the command for `speaker`, returning what to post in the transcript channel.
+	pub async fn run(
+		self,
+		ctx: &Context,
+		guild_id: GuildId,
+		speaker: &str,
+		paused: &AtomicBool,
+	) -> String {
+		let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+		match self {
+			Self::Stop => {
+				paused.store(true, Ordering::Relaxed);
+				format_message!(language, "voice-command-stopped", speaker: speaker)
+			}
+			Self::Start => {
+				paused.store(false, Ordering::Relaxed);
+				format_message!(language, "voice-command-started", speaker: speaker)
+			}
+			Self::Leave => {
+				let ctx = ctx.clone();
+				spawn_logged("voice_command_leave", async move {
+					if let Err(e) = crate::disconnect_from_vc(&ctx, guild_id).await {
+						error!(%guild_id, "failed to leave voice chat by voice command: {}", e);
+					}
+				});
+				format_message!(language, "voice-command-leaving", speaker: speaker)
+			}
+		}
+	}
+}
+
+/// Whether `user_id` has `role`, and so can use voice commands.
+pub async fn is_controller(ctx: &Context, guild_id: GuildId, user_id: u64, role: RoleId) -> bool {
+	match scripty_utils::entity_cache::get_member_details(ctx, guild_id, UserId::new(user_id)).await
+	{
+		Ok(member) => member.roles.contains(&role),
+		Err(e) => {
+			warn!(%guild_id, "failed to fetch member for voice command: {}", e);
+			false
+		}
+	}
+}
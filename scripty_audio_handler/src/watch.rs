@@ -0,0 +1,55 @@
+//! Watch mode, set with `/config watch`: only the watched users, and members with the watched
+//! roles, are transcribed. Useful for only transcribing the GM of a game, or an interviewee.
+
+use std::sync::OnceLock as OnceCell;
+
+use dashmap::DashMap;
+use scripty_utils::watch::WatchTarget;
+use serenity::all::{GuildId, RoleId, UserId};
+
+static WATCHLISTS: OnceCell<DashMap<GuildId, Vec<WatchTarget>, ahash::RandomState>> =
+	OnceCell::new();
+
+fn get_watchlists() -> &'static DashMap<GuildId, Vec<WatchTarget>, ahash::RandomState> {
+	WATCHLISTS.get_or_init(|| DashMap::with_hasher(ahash::RandomState::new()))
+}
+
+/// Set who's watched in `guild_id`'s session, where empty turns watch mode off.
+/// Returns whether that changed anything.
+pub(crate) fn set_watchlist(guild_id: GuildId, watched: Vec<WatchTarget>) -> bool {
+	let watchlists = get_watchlists();
+	if watched.is_empty() {
+		return watchlists.remove(&guild_id).is_some();
+	}
+	watchlists.insert(guild_id, watched.clone()).as_ref() != Some(&watched)
+}
+
+/// Who's watched in `guild_id`'s session. Empty if watch mode is off, or nothing's being
+/// transcribed.
+pub fn get_watchlist(guild_id: GuildId) -> Vec<WatchTarget> {
+	get_watchlists()
+		.get(&guild_id)
+		.map_or_else(Vec::new, |watched| watched.value().clone())
+}
+
+/// Whether `user_id`, who has `roles`, should be transcribed in `guild_id`.
+///
+/// Also checks the guild's transcribe-only role and role policies, as they're all decided together.
+pub(crate) fn is_transcribed(
+	guild_id: GuildId,
+	user_id: UserId,
+	roles: &[RoleId],
+	transcribe_only_role: Option<RoleId>,
+) -> bool {
+	if transcribe_only_role.map_or(false, |role| !roles.contains(&role))
+		|| !crate::role_policy::allows(guild_id, roles)
+	{
+		return false;
+	}
+	get_watchlists().get(&guild_id).map_or(true, |watched| {
+		watched.iter().any(|target| match target {
+			WatchTarget::User(id) => *id == user_id,
+			WatchTarget::Role(id) => roles.contains(id),
+		})
+	})
+}
@@ -0,0 +1,55 @@
+//! Case-insensitive keyword matching against transcripts, used by automod rules and spoken
+//! automations.
+
+/// Keywords, each with what to do when one is said. A keyword matches anywhere in the text,
+/// ignoring case.
+#[derive(Debug, Clone)]
+pub struct KeywordMatcher<T> {
+	keywords: Vec<(String, T)>,
+}
+
+impl<T> Default for KeywordMatcher<T> {
+	fn default() -> Self {
+		Self {
+			keywords: Vec::new(),
+		}
+	}
+}
+
+impl<T> KeywordMatcher<T> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Match `keyword`, replacing what it's matched to if it's already there.
+	pub fn insert(&mut self, keyword: &str, value: T) {
+		let keyword = keyword.to_lowercase();
+		match self.keywords.iter_mut().find(|(k, _)| *k == keyword) {
+			Some((_, v)) => *v = value,
+			None => self.keywords.push((keyword, value)),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.keywords.is_empty()
+	}
+
+	/// What the first keyword found in `text` is matched to, in the order they were inserted.
+	pub fn find(&self, text: &str) -> Option<&T> {
+		let text = text.to_lowercase();
+		self.keywords
+			.iter()
+			.find(|(keyword, _)| text.contains(keyword.as_str()))
+			.map(|(_, value)| value)
+	}
+
+	/// What every keyword found in `text` is matched to, in the order they were inserted.
+	pub fn find_all(&self, text: &str) -> Vec<&T> {
+		let text = text.to_lowercase();
+		self.keywords
+			.iter()
+			.filter(|(keyword, _)| text.contains(keyword.as_str()))
+			.map(|(_, value)| value)
+			.collect()
+	}
+}
@@ -1,3 +1,4 @@
 pub mod db;
+pub mod keywords;
 pub mod types;
 pub mod utils;
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use crate::keywords::KeywordMatcher;
 
 #[repr(i16)]
 #[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
@@ -27,6 +27,8 @@ pub enum AutomodRuleAction {
 	DeleteLogAndKick    = 3,
 	#[name = "Delete, log, and silence user"]
 	DeleteLogAndSilence = 4,
+	#[name = "Delete, log, and time out user"]
+	DeleteLogAndTimeout = 5,
 }
 
 impl From<i16> for AutomodRuleAction {
@@ -36,6 +38,7 @@ impl From<i16> for AutomodRuleAction {
 			2 => AutomodRuleAction::DeleteAndLog,
 			3 => AutomodRuleAction::DeleteLogAndKick,
 			4 => AutomodRuleAction::DeleteLogAndSilence,
+			5 => AutomodRuleAction::DeleteLogAndTimeout,
 			_ => panic!("invalid value for AutomodRuleAction"),
 		}
 	}
@@ -62,8 +65,7 @@ pub struct AutomodServerConfig {
 	pub enabled:         bool,
 	pub groups:          Vec<AutomodRuleGroup>,
 	rules:               Vec<AutomodRule>,
-	rule_action_map:     HashMap<String, AutomodRuleAction>,
-	rule_array:          Vec<String>,
+	matcher:             KeywordMatcher<AutomodRuleAction>,
 	pub log_channel_id:  u64,
 	pub log_recording:   bool,
 	pub auto_join_voice: bool,
@@ -80,11 +82,10 @@ impl AutomodServerConfig {
 		log_recording: bool,
 		auto_join_voice: bool,
 	) -> Self {
-		let mut rule_action_map = HashMap::new();
+		let mut matcher = KeywordMatcher::new();
 		for rule in &rules {
-			rule_action_map.insert(rule.rule_data.clone(), rule.rule_action);
+			matcher.insert(&rule.rule_data, rule.rule_action);
 		}
-		let rule_array = rules.iter().map(|r| r.rule_data.clone()).collect();
 
 		Self {
 			guild_id,
@@ -92,8 +93,7 @@ impl AutomodServerConfig {
 			enabled,
 			groups,
 			rules,
-			rule_action_map,
-			rule_array,
+			matcher,
 			log_channel_id,
 			log_recording,
 			auto_join_voice,
@@ -101,9 +101,7 @@ impl AutomodServerConfig {
 	}
 
 	pub fn add_rule(&mut self, rule: AutomodRule) {
-		self.rule_action_map
-			.insert(rule.rule_data.clone(), rule.rule_action);
-		self.rule_array.push(rule.rule_data.clone());
+		self.matcher.insert(&rule.rule_data, rule.rule_action);
 		self.rules.push(rule);
 	}
 
@@ -112,18 +110,6 @@ impl AutomodServerConfig {
 			return None;
 		}
 
-		let m = msg.to_lowercase();
-		for rule in &self.rule_array {
-			if m.contains(rule) {
-				return Some(
-					self.rule_action_map
-						.get(rule)
-						.copied()
-						.unwrap_or(AutomodRuleAction::SilentDelete),
-				);
-			}
-		}
-
-		None
+		self.matcher.find(msg).copied()
 	}
 }
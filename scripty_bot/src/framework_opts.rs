@@ -1,6 +1,6 @@
 use poise::{FrameworkOptions, PrefixFrameworkOptions};
 use scripty_bot_utils::error::handler::on_error;
-use scripty_commands::build_commands;
+use scripty_commands::{build_commands, build_extra_bot_commands};
 use serenity::{builder::CreateAllowedMentions, model::id::UserId, prelude::GatewayIntents};
 
 pub fn get_framework_opts() -> FrameworkOptions<scripty_bot_utils::Data, scripty_bot_utils::Error> {
@@ -34,12 +34,31 @@ pub fn get_framework_opts() -> FrameworkOptions<scripty_bot_utils::Data, scripty
 	}
 }
 
+/// Options of the extra bots set in `extra_bot_tokens`. They only answer to mentions, not `~`,
+/// so a prefix command isn't run by every bot in the guild.
+pub fn get_extra_bot_framework_opts(
+) -> FrameworkOptions<scripty_bot_utils::Data, scripty_bot_utils::Error> {
+	FrameworkOptions {
+		commands: build_extra_bot_commands(),
+		prefix_options: PrefixFrameworkOptions {
+			prefix: None,
+			execute_self_messages: false,
+			execute_untracked_edits: true,
+			mention_as_prefix: true,
+			..Default::default()
+		},
+		..get_framework_opts()
+	}
+}
+
 pub fn get_gateway_intents() -> GatewayIntents {
 	GatewayIntents::GUILDS
 		| GatewayIntents::GUILD_MEMBERS
 		| GatewayIntents::GUILD_WEBHOOKS
 		| GatewayIntents::GUILD_VOICE_STATES
+		| GatewayIntents::GUILD_SCHEDULED_EVENTS
 		| GatewayIntents::GUILD_MESSAGES
+		| GatewayIntents::GUILD_MESSAGE_REACTIONS
 		| GatewayIntents::DIRECT_MESSAGES
 		| GatewayIntents::MESSAGE_CONTENT
 }
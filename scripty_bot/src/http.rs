@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use serenity::http::{Http, HttpBuilder};
+
+/// Number of times to try reaching the REST proxy at startup before giving up.
+const PROXY_HEALTH_CHECK_TRIES: u32 = 5;
+
+/// Build the HTTP client, routing all REST calls through the configured proxy if there is one.
+///
+/// When proxied, the proxy is responsible for rate limiting, so serenity's own ratelimiter is
+/// disabled, and the proxy is checked to be reachable before anything else uses it.
+pub async fn build_http(token: &str) -> Http {
+	let Some(proxy) = &scripty_config::get_config().rest_proxy else {
+		return HttpBuilder::new(token).build();
+	};
+
+	info!(%proxy, "routing REST calls through proxy");
+	let http = HttpBuilder::new(token)
+		.proxy(proxy.as_str())
+		.ratelimiter_disabled(true)
+		.build();
+
+	let mut tries = 0;
+	loop {
+		tries += 1;
+		match http.get_gateway().await {
+			Ok(_) => {
+				info!(%proxy, "REST proxy is healthy");
+				break;
+			}
+			Err(e) if tries < PROXY_HEALTH_CHECK_TRIES => {
+				warn!(
+					%proxy,
+					"REST proxy health check failed (try {}/{}): {}",
+					tries,
+					PROXY_HEALTH_CHECK_TRIES,
+					e
+				);
+				tokio::time::sleep(Duration::from_secs(2u64.pow(tries))).await;
+			}
+			Err(e) => panic!("REST proxy at {} is unreachable: {}", proxy, e),
+		}
+	}
+
+	http
+}
@@ -2,6 +2,7 @@
 //! core code to run the bot. All other code should be in other crates.
 
 mod framework_opts;
+mod http;
 
 #[macro_use]
 extern crate tracing;
@@ -10,11 +11,12 @@ use poise::FrameworkBuilder;
 use scripty_audio_handler::SerenityInit;
 use scripty_bot_utils::{
 	extern_utils::set_cache_http,
-	globals::{CLIENT_CACHE, CLIENT_DATA},
+	globals::{CLIENT_CACHE, CLIENT_DATA, EXTRA_BOT_CONTEXTS},
 	handler,
 	Data,
 };
-use serenity::{all::OnlineStatus, gateway::ActivityData};
+use serenity::{all::OnlineStatus, client::Context, gateway::ActivityData};
+use tokio::signal::unix::{signal, SignalKind};
 
 pub async fn entrypoint() {
 	// fetch the config
@@ -42,12 +44,35 @@ pub async fn entrypoint() {
 					.expect("user data setup called more than once: bug?");
 
 				let sm = c.shard_manager().clone();
+				let shutdown_ctx = ctx.clone();
 				tokio::spawn(async move {
-					tokio::signal::ctrl_c()
-						.await
-						.expect("failed to listen for ctrl+c");
+					// systemd stops services with SIGTERM
+					let mut terminate =
+						signal(SignalKind::terminate()).expect("failed to listen for SIGTERM");
+					tokio::select! {
+						res = tokio::signal::ctrl_c() => res.expect("failed to listen for ctrl+c"),
+						_ = terminate.recv() => {}
+					}
+					scripty_bot_utils::systemd::notify_stopping();
+					scripty_audio_handler::drain(
+						&shutdown_ctx,
+						scripty_audio_handler::DRAIN_TIMEOUT,
+					)
+					.await;
+					let extra_bots: Vec<Context> = EXTRA_BOT_CONTEXTS
+						.iter()
+						.map(|ctx| ctx.value().clone())
+						.collect();
+					for ctx in extra_bots {
+						scripty_audio_handler::drain(&ctx, scripty_audio_handler::DRAIN_TIMEOUT)
+							.await;
+					}
+					// extra bots stop along with the process
 					sm.shutdown_all().await;
 				});
+				tokio::spawn(scripty_bot_utils::reload::reload_on_sighup());
+				tokio::spawn(scripty_bot_utils::error::report_panics(ctx.clone()));
+				tokio::spawn(scripty_bot_utils::control::serve(ctx.clone()));
 
 				Ok(Data {
 					shard_manager: c.shard_manager().clone(),
@@ -57,15 +82,90 @@ pub async fn entrypoint() {
 		.options(framework_opts::get_framework_opts())
 		.build();
 
-	let mut client = serenity::Client::builder(&cfg.token, framework_opts::get_gateway_intents())
-		.framework(framework)
-		.event_handler(handler::BotEventHandler)
-		.raw_event_handler(handler::RawEventHandler)
-		.register_songbird_from_config(scripty_audio_handler::get_songbird())
-		.status(OnlineStatus::Idle)
-		.activity(ActivityData::custom("Starting up..."))
-		.await
-		.expect("failed to create serenity client");
+	let http = http::build_http(&cfg.token).await;
+	let mut client =
+		serenity::client::ClientBuilder::new_with_http(http, framework_opts::get_gateway_intents())
+			.framework(framework)
+			.event_handler(handler::BotEventHandler)
+			.raw_event_handler(handler::RawEventHandler)
+			.register_songbird_from_config(scripty_audio_handler::get_songbird())
+			.status(OnlineStatus::Idle)
+			.activity(ActivityData::custom("Starting up..."))
+			.await
+			.expect("failed to create serenity client");
+
+	if let Some(cluster_cfg) = &cfg.cluster {
+		let cluster_id = scripty_bot_utils::cluster::claim_cluster_slot(cluster_cfg)
+			.await
+			.expect("failed to claim cluster slot");
+		let shard_manager = client.shard_manager.clone();
+		tokio::spawn(async move {
+			scripty_bot_utils::cluster::keep_cluster_slot().await;
+			// another process runs our shards now, so running them too would handle
+			// every event twice
+			error!(%cluster_id, "lost cluster slot, shutting down");
+			shard_manager.shutdown_all().await;
+		});
+		let shard_range = scripty_bot_utils::cluster::get_shard_range(cluster_id, cluster_cfg);
+		info!(
+			%cluster_id,
+			?shard_range,
+			"running as cluster {} of {}",
+			cluster_id,
+			cluster_cfg.cluster_count
+		);
+
+		// released once our cache is ready, so clusters restart one at a time
+		scripty_bot_utils::cluster::acquire_startup_lock()
+			.await
+			.expect("failed to acquire cluster startup lock");
+
+		// extra bots aren't sharded across clusters, so only one runs them
+		if cluster_id == 0 {
+			start_extra_bots();
+		}
+
+		client
+			.start_shard_range(shard_range, cluster_cfg.shard_count)
+			.await
+			.expect("failed to run bot");
+	} else {
+		start_extra_bots();
+		client.start_autosharded().await.expect("failed to run bot");
+	}
+}
+
+/// Start a client for each of the extra bots in `extra_bot_tokens`, in the background.
+fn start_extra_bots() {
+	for token in &scripty_config::get_config().extra_bot_tokens {
+		tokio::spawn(run_extra_bot(token));
+	}
+}
+
+/// Run an extra bot, which only transcribes voice chats, with its own voice connections.
+async fn run_extra_bot(token: &str) {
+	let framework = FrameworkBuilder::default()
+		.setup(move |_, _, c| {
+			Box::pin(async move {
+				Ok(Data {
+					shard_manager: c.shard_manager().clone(),
+				})
+			})
+		})
+		.options(framework_opts::get_extra_bot_framework_opts())
+		.build();
+
+	let http = http::build_http(token).await;
+	let mut client =
+		serenity::client::ClientBuilder::new_with_http(http, framework_opts::get_gateway_intents())
+			.framework(framework)
+			.event_handler(handler::ExtraBotEventHandler)
+			.register_songbird_from_config(scripty_audio_handler::get_songbird())
+			.await
+			.expect("failed to create extra bot client");
 
-	client.start_autosharded().await.expect("failed to run bot");
+	// the main bot keeps running without it
+	if let Err(e) = client.start_autosharded().await {
+		error!("extra bot stopped: {}", e);
+	}
 }
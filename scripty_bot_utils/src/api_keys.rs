@@ -0,0 +1,108 @@
+//! Per-guild keys for the transcript API, managed with `/config api_key`.
+//!
+//! A guild having a key is also what turns on storing its transcripts for the API.
+
+use serenity::model::id::GuildId;
+use sha2::{Digest, Sha256};
+
+/// Prefix of every API key, so they're easy to spot if leaked.
+const KEY_PREFIX: &str = "scripty_";
+
+/// Keys are random, so a plain hash is enough to look them up without storing them.
+fn hash_api_key(key: &str) -> Vec<u8> {
+	Sha256::digest(key.as_bytes()).to_vec()
+}
+
+/// Make a new API key for `guild_id`, replacing any it already has.
+///
+/// The key is only ever returned here: it can't be shown again later.
+pub async fn create_api_key(guild_id: GuildId) -> Result<String, sqlx::Error> {
+	let key = format!(
+		"{}{}{}",
+		KEY_PREFIX,
+		uuid::Uuid::new_v4().simple(),
+		uuid::Uuid::new_v4().simple()
+	);
+
+	sqlx::query!(
+		"INSERT INTO guild_api_keys (guild_id, key_hash) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET key_hash = $2, created_at = NOW(), last_used_at = NULL",
+		guild_id.get() as i64,
+		hash_api_key(&key)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(key)
+}
+
+/// Remove `guild_id`'s API key, and delete the transcripts stored for it.
+///
+/// Returns whether it had a key.
+pub async fn revoke_api_key(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	let mut tx = scripty_db::get_db().begin().await?;
+
+	let revoked = sqlx::query!(
+		"DELETE FROM guild_api_keys WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.execute(&mut *tx)
+	.await?
+	.rows_affected();
+	// segments go along with ON DELETE CASCADE
+	sqlx::query!(
+		"DELETE FROM transcript_sessions WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.execute(&mut *tx)
+	.await?;
+
+	tx.commit().await?;
+	Ok(revoked > 0)
+}
+
+/// When `guild_id`'s API key was made and last used, if it has one.
+pub async fn get_api_key_info(
+	guild_id: GuildId,
+) -> Result<Option<(time::OffsetDateTime, Option<time::OffsetDateTime>)>, sqlx::Error> {
+	Ok(sqlx::query!(
+		"SELECT created_at, last_used_at FROM guild_api_keys WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?
+	.map(|row| (row.created_at, row.last_used_at)))
+}
+
+/// Find the guild an API key belongs to, and mark the key as used.
+pub async fn authenticate_api_key(key: &str) -> Result<Option<GuildId>, sqlx::Error> {
+	if !key.starts_with(KEY_PREFIX) {
+		return Ok(None);
+	}
+
+	let row = sqlx::query!(
+		"UPDATE guild_api_keys SET last_used_at = NOW() WHERE key_hash = $1 RETURNING guild_id",
+		hash_api_key(key)
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row
+		.and_then(|row| u64::try_from(row.guild_id).ok())
+		.filter(|id| *id != 0)
+		.map(GuildId::new))
+}
+
+/// Delete stored transcripts older than `transcript_api.retention_days`.
+///
+/// Returns how many sessions were deleted.
+pub async fn delete_expired_transcripts() -> Result<u64, sqlx::Error> {
+	let retention_days = scripty_config::get_config().transcript_api.retention_days;
+	Ok(sqlx::query!(
+		"DELETE FROM transcript_sessions WHERE started_at < NOW() - make_interval(days => $1)",
+		retention_days
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected())
+}
@@ -26,29 +26,34 @@ pub trait BackgroundTask: Sized {
 
 /// Initialize a task. Accepts one argument, the full path to the task struct from the crate root.
 /// Spawns the background task required.
+///
+/// If the task panics, it's initialized again and restarted.
 macro_rules! init_task {
 	($path: ty, $ctx: expr) => {{
 		let ctx = $ctx.clone();
-		tokio::spawn(async move {
-			let mut task = match <$path as BackgroundTask>::init(ctx).await {
-				Ok(t) => t,
-				Err(e) => {
-					error!("background task failed to initialize: {:?}", e);
-					return;
-				}
-			};
-			let mut interval;
-			loop {
-				match task.timeout() {
-					Some(timeout) => {
-						if tokio::time::timeout(timeout, task.run()).await.is_err() {
-							error!(concat!("background task timed out: ", stringify!($path)));
+		scripty_utils::spawn_supervised(stringify!($path), move || {
+			let ctx = ctx.clone();
+			async move {
+				let mut task = match <$path as BackgroundTask>::init(ctx).await {
+					Ok(t) => t,
+					Err(e) => {
+						error!("background task failed to initialize: {:?}", e);
+						return;
+					}
+				};
+				let mut interval;
+				loop {
+					match task.timeout() {
+						Some(timeout) => {
+							if tokio::time::timeout(timeout, task.run()).await.is_err() {
+								error!(concat!("background task timed out: ", stringify!($path)));
+							}
 						}
+						None => task.run().await,
 					}
-					None => task.run().await,
+					interval = task.interval();
+					tokio::time::sleep(interval).await;
 				}
-				interval = task.interval();
-				tokio::time::sleep(interval).await;
 			}
 		});
 	}};
@@ -66,7 +71,20 @@ pub fn init_background_tasks(ctx: Context) {
 	init_task!(crate::background_tasks::tasks::LatencyUpdater, ctx);
 	init_task!(crate::background_tasks::tasks::BasicStatsUpdater, ctx);
 	init_task!(crate::background_tasks::tasks::StatusUpdater, ctx);
+	init_task!(crate::background_tasks::tasks::SessionPresenceUpdater, ctx);
 	init_task!(crate::background_tasks::tasks::CommandLatencyClearer, ctx);
 	init_task!(crate::background_tasks::tasks::BotListUpdater, ctx);
 	init_task!(crate::background_tasks::tasks::VoteReminderTask, ctx);
+	init_task!(crate::background_tasks::tasks::HeartbeatSender, ctx);
+	init_task!(crate::background_tasks::tasks::ShardWatchdog, ctx);
+	init_task!(crate::background_tasks::tasks::ClusterHeartbeat, ctx);
+	init_task!(crate::background_tasks::tasks::I18nWatcher, ctx);
+	init_task!(crate::background_tasks::tasks::JobScheduler, ctx);
+	init_task!(crate::background_tasks::tasks::DeliveryReplayer, ctx);
+	init_task!(crate::background_tasks::tasks::HoldQueueReleaser, ctx);
+	init_task!(crate::background_tasks::tasks::QuotaUsageFlusher, ctx);
+	init_task!(crate::background_tasks::tasks::SystemdNotifier, ctx);
+	init_task!(crate::background_tasks::tasks::TranscriptCleaner, ctx);
+	init_task!(crate::background_tasks::tasks::TalkTimeFlusher, ctx);
+	init_task!(crate::background_tasks::tasks::OrphanReaper, ctx);
 }
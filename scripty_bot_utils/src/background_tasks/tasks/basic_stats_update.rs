@@ -31,5 +31,32 @@ impl BackgroundTask for BasicStatsUpdater {
 				})
 				.sum(),
 		);
+
+		// voice session stats, per shard
+		let shard_count = self.1.cache.shard_count();
+		let mut per_shard = vec![(0_i64, 0_i64, 0_i64); shard_count.get() as usize];
+		for session in scripty_audio_handler::get_session_stats() {
+			let shard_id = serenity::utils::shard_id(session.guild_id, shard_count.get());
+			if let Some(shard) = per_shard.get_mut(shard_id as usize) {
+				shard.0 += 1;
+				shard.1 += session.stt_streams as i64;
+				shard.2 += session.transcribed_users as i64;
+			}
+		}
+		for (shard_id, (connections, streams, users)) in per_shard.into_iter().enumerate() {
+			let shard_id = shard_id.to_string();
+			self.0
+				.voice_connections
+				.with_label_values(&[&shard_id])
+				.set(connections);
+			self.0
+				.stt_streams
+				.with_label_values(&[&shard_id])
+				.set(streams);
+			self.0
+				.transcribed_users
+				.with_label_values(&[&shard_id])
+				.set(users);
+		}
 	}
 }
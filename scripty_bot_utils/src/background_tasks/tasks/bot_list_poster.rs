@@ -17,9 +17,13 @@ use scripty_botlists::{
 };
 use scripty_config::BotListsConfig;
 use serenity::client::Context;
+use time::OffsetDateTime;
 
 use crate::{background_tasks::core::BackgroundTask, Error};
 
+/// How many times to try posting to a bot list before waiting for the next run.
+const MAX_POST_ATTEMPTS: u32 = 3;
+
 pub struct BotListUpdater {
 	ctx:       Context,
 	bot_lists: Arc<Vec<BotLists>>,
@@ -47,14 +51,50 @@ impl BackgroundTask for BotListUpdater {
 	}
 
 	async fn run(&mut self) {
-		let stats = PostStats {
-			server_count: self.ctx.cache.guild_count(),
-			shard_count:  self.ctx.cache.shard_count().get(),
+		// only one cluster needs to post, and it posts the combined stats of all of them
+		if crate::cluster::get_cluster_id().map_or(false, |id| id != 0) {
+			return;
+		}
+		let stats = match crate::cluster::get_bot_counts(&self.ctx.cache).await {
+			Ok((guilds, shards)) => PostStats {
+				server_count: guilds as usize,
+				shard_count:  shards,
+			},
+			Err(e) => {
+				error!("failed to fetch cluster stats: {}", e);
+				return;
+			}
 		};
 
+		let metrics = scripty_metrics::get_metrics();
 		for list in self.bot_lists.iter() {
-			if let Err(e) = list.post_stats(&self.client, stats).await {
-				error!("Failed to post stats to bot list: {}", e);
+			let mut attempt = 1;
+			loop {
+				match list.post_stats(&self.client, stats).await {
+					Ok(_) => {
+						metrics
+							.bot_list_last_post
+							.with_label_values(&[list.name()])
+							.set(OffsetDateTime::now_utc().unix_timestamp());
+						break;
+					}
+					Err(e) if e.is_transient() && attempt < MAX_POST_ATTEMPTS => {
+						warn!(
+							site = list.name(),
+							%attempt,
+							"failed to post stats to bot list, retrying: {}", e
+						);
+						tokio::time::sleep(Duration::from_secs(10 * attempt as u64)).await;
+						attempt += 1;
+					}
+					Err(e) => {
+						error!(
+							site = list.name(),
+							"failed to post stats to bot list: {}", e
+						);
+						break;
+					}
+				}
 			}
 		}
 	}
@@ -73,6 +113,24 @@ enum BotLists {
 	VoidBotsNet(VoidBotsNet),
 }
 
+impl BotLists {
+	/// The site's key in the `bot_lists` config, also used as its metric label.
+	fn name(&self) -> &'static str {
+		match self {
+			BotLists::BotListMe(_) => "botlist_me",
+			BotLists::DiscordBotsGG(_) => "discord_bots_gg",
+			BotLists::DiscordBotListCom(_) => "discordbotlist_com",
+			BotLists::DiscordExtremeListXyz(_) => "discordextremelist_xyz",
+			BotLists::DiscordsCom(_) => "discords_com",
+			BotLists::DiscordServicesNet(_) => "discordservices_net",
+			BotLists::DisforgeCom(_) => "disforge_com",
+			BotLists::InfinityBotsGG(_) => "infinitybots_gg",
+			BotLists::TopGG(_) => "top_gg",
+			BotLists::VoidBotsNet(_) => "voidbots_net",
+		}
+	}
+}
+
 #[async_trait]
 impl StatPoster for BotLists {
 	async fn post_stats(
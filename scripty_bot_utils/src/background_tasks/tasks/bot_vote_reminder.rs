@@ -1,5 +1,6 @@
 use std::{fmt, time::Duration};
 
+use scripty_utils::delivery::{self, DeliveryTarget};
 use serenity::{
 	all::UserId,
 	builder::{CreateEmbed, CreateMessage},
@@ -57,13 +58,11 @@ impl BackgroundTask for VoteReminderTask {
 						site.vote_url()
 					),
 				));
+			let dedupe_key = format!("vote_reminder:{}:{}:{}", user_id, site, reminder_unix_ts);
 			let ctx2 = self.ctx.clone();
 			tokio::spawn(async move {
-				let res = match UserId::new(user_id).create_dm_channel(&ctx2.http).await {
-					Ok(channel) => channel.send_message(&ctx2.http, msg).await.map(|_| ()),
-					Err(e) => Err(e),
-				};
-				if let Err(e) = res {
+				let target = DeliveryTarget::Dm(UserId::new(user_id));
+				if let Err(e) = delivery::deliver(&ctx2.http, target, &msg, &dedupe_key).await {
 					error!("failed to send vote reminder: {}", e);
 				}
 			});
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{
+	background_tasks::core::BackgroundTask,
+	cluster::{self, ClusterStats},
+	Error,
+};
+
+/// Publishes this process's cluster stats every 20 seconds.
+///
+/// The cluster slot itself is renewed by `cluster::keep_cluster_slot`.
+///
+/// Does nothing if not running clustered.
+pub struct ClusterHeartbeat(Context);
+
+#[async_trait]
+impl BackgroundTask for ClusterHeartbeat {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		Ok(Self(ctx))
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(20)
+	}
+
+	async fn run(&mut self) {
+		if cluster::get_cluster_id().is_none() {
+			return;
+		}
+
+		let stats = ClusterStats {
+			guilds: self.0.cache.guild_count() as u64,
+			users:  self
+				.0
+				.cache
+				.guilds()
+				.into_iter()
+				.filter_map(|g| g.to_guild_cached(&self.0.cache).map(|g| g.member_count))
+				.sum(),
+		};
+		if let Err(e) = cluster::publish_cluster_stats(stats).await {
+			error!("failed to publish cluster stats: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(15))
+	}
+}
@@ -19,5 +19,6 @@ impl BackgroundTask for CommandLatencyClearer {
 
 	async fn run(&mut self) {
 		scripty_metrics::clear_latency_start_times();
+		scripty_metrics::clear_command_start_times();
 	}
 }
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Retries queued outbound messages every 5 seconds.
+pub struct DeliveryReplayer {
+	ctx: Context,
+}
+
+#[async_trait]
+impl BackgroundTask for DeliveryReplayer {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		Ok(Self { ctx })
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(5)
+	}
+
+	async fn run(&mut self) {
+		if let Err(e) = scripty_utils::delivery::replay_pending(&self.ctx.http).await {
+			error!("failed to replay queued deliveries: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(60))
+	}
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Pings the configured heartbeat URL every minute, but only while the bot is fully healthy.
+///
+/// An external uptime monitor watching that URL will then notice if the whole process dies,
+/// or if it is still running but degraded.
+pub struct HeartbeatSender {
+	url:    Option<String>,
+	client: reqwest::Client,
+}
+
+#[async_trait]
+impl BackgroundTask for HeartbeatSender {
+	async fn init(_: Context) -> Result<Self, Error> {
+		Ok(Self {
+			url:    scripty_config::get_config().heartbeat_url.clone(),
+			client: reqwest::Client::new(),
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(60)
+	}
+
+	async fn run(&mut self) {
+		let Some(url) = &self.url else {
+			return;
+		};
+
+		if !crate::health::is_healthy().await {
+			warn!("bot unhealthy, skipping heartbeat");
+			return;
+		}
+
+		if let Err(e) = self
+			.client
+			.get(url)
+			.send()
+			.await
+			.and_then(|res| res.error_for_status())
+		{
+			error!("failed to send heartbeat: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(30))
+	}
+}
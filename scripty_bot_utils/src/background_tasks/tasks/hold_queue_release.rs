@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Posts held transcripts that are due to be approved automatically, and drops those nobody
+/// reviewed in time, every 5 seconds.
+pub struct HoldQueueReleaser {
+	ctx: Context,
+}
+
+#[async_trait]
+impl BackgroundTask for HoldQueueReleaser {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		Ok(Self { ctx })
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(5)
+	}
+
+	async fn run(&mut self) {
+		if let Err(e) = crate::hold_review::release_held(&self.ctx).await {
+			error!("failed to release held transcripts: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(60))
+	}
+}
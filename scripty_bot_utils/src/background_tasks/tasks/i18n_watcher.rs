@@ -0,0 +1,56 @@
+use std::{
+	fs,
+	time::{Duration, SystemTime},
+};
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Reloads translations whenever a file in the i18n directory changes, if `i18n_watch` is set.
+pub struct I18nWatcher {
+	last_modified: Option<SystemTime>,
+}
+
+/// Latest modification time of any file in the i18n directory.
+fn latest_modification() -> Option<SystemTime> {
+	fs::read_dir(&scripty_config::get_config().i18n_dir)
+		.ok()?
+		.filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+		.max()
+}
+
+#[async_trait]
+impl BackgroundTask for I18nWatcher {
+	async fn init(_: Context) -> Result<Self, Error> {
+		Ok(Self {
+			last_modified: latest_modification(),
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(10)
+	}
+
+	async fn run(&mut self) {
+		if !scripty_config::get_config().i18n_watch {
+			return;
+		}
+
+		let last_modified = latest_modification();
+		if last_modified == self.last_modified {
+			return;
+		}
+		self.last_modified = last_modified;
+
+		info!("i18n files changed, reloading translations");
+		let summary = scripty_i18n::reload_i18n();
+		if !summary.errors.is_empty() {
+			warn!("reloaded translations with {} errors", summary.errors.len());
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(5))
+	}
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Runs due scheduled jobs every 5 seconds.
+pub struct JobScheduler {
+	ctx: Context,
+}
+
+#[async_trait]
+impl BackgroundTask for JobScheduler {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		crate::premium_trial::register_job_handlers();
+		crate::guild_cleanup::register_job_handlers();
+		Ok(Self { ctx })
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(5)
+	}
+
+	async fn run(&mut self) {
+		crate::scheduler::run_due_jobs(&self.ctx).await;
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(10))
+	}
+}
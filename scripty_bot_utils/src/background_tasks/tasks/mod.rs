@@ -1,13 +1,39 @@
 mod basic_stats_update;
 mod bot_list_poster;
 mod bot_vote_reminder;
+mod cluster_heartbeat;
 mod cmd_latency_clear;
+mod delivery_replay;
+mod heartbeat;
+mod hold_queue_release;
+mod i18n_watcher;
+mod job_scheduler;
+mod orphan_reaper;
 mod prometheus_latency_update;
+mod quota_flush;
+mod session_presence;
+mod shard_watchdog;
 mod status_update;
+mod systemd_notifier;
+mod talk_time_flush;
+mod transcript_cleanup;
 
 pub use basic_stats_update::*;
 pub use bot_list_poster::*;
 pub use bot_vote_reminder::*;
+pub use cluster_heartbeat::*;
 pub use cmd_latency_clear::*;
+pub use delivery_replay::*;
+pub use heartbeat::*;
+pub use hold_queue_release::*;
+pub use i18n_watcher::*;
+pub use job_scheduler::*;
+pub use orphan_reaper::*;
 pub use prometheus_latency_update::*;
+pub use quota_flush::*;
+pub use session_presence::*;
+pub use shard_watchdog::*;
 pub use status_update::*;
+pub use systemd_notifier::*;
+pub use talk_time_flush::*;
+pub use transcript_cleanup::*;
@@ -0,0 +1,91 @@
+use std::{fmt::Display, time::Duration};
+
+use serenity::client::Context;
+
+use crate::{
+	background_tasks::core::BackgroundTask,
+	globals::EXTRA_BOT_CONTEXTS,
+	orphan_reaper,
+	Error,
+};
+
+/// Removes data that's been left behind every hour, like mirrors of deleted channels and
+/// sessions that were never ended because the bot crashed.
+pub struct OrphanReaper {
+	ctx:       Context,
+	first_run: bool,
+}
+
+#[async_trait]
+impl BackgroundTask for OrphanReaper {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		Ok(Self {
+			ctx,
+			first_run: true,
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(60 * 60)
+	}
+
+	async fn run(&mut self) {
+		if self.first_run {
+			// sessions are resumed once the cache is ready, so they'd look stale until then
+			self.first_run = false;
+			return;
+		}
+
+		report(
+			"active_session",
+			scripty_audio_handler::reap_stale_sessions(&self.ctx).await,
+		);
+		// each bot only finds its own sessions
+		let extra_bots: Vec<Context> = EXTRA_BOT_CONTEXTS
+			.iter()
+			.map(|ctx| ctx.value().clone())
+			.collect();
+		for ctx in extra_bots {
+			report(
+				"active_session",
+				scripty_audio_handler::reap_stale_sessions(&ctx).await,
+			);
+		}
+
+		// everything else is shared by all clusters, so only one needs to do it
+		if crate::cluster::get_cluster_id().map_or(false, |id| id != 0) {
+			return;
+		}
+		report(
+			"transcript_session",
+			scripty_audio_handler::end_orphaned_sessions().await,
+		);
+		report(
+			"channel_mapping",
+			orphan_reaper::reap_deleted_channels(&self.ctx).await,
+		);
+		report(
+			"webhook",
+			orphan_reaper::reap_deleted_webhooks(&self.ctx).await,
+		);
+		report("cache_key", orphan_reaper::reap_user_cache_keys().await);
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(5 * 60))
+	}
+}
+
+fn report(kind: &str, res: Result<u64, impl Display>) {
+	match res {
+		Ok(0) => {}
+		Ok(count) => {
+			info!(kind, count, "removed orphaned data");
+			scripty_metrics::get_metrics()
+				.orphans_reaped
+				.with_label_values(&[kind])
+				.inc_by(count);
+		}
+		Err(e) => error!(kind, "failed to remove orphaned data: {}", e),
+	}
+}
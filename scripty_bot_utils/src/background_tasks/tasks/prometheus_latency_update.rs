@@ -19,19 +19,42 @@ impl BackgroundTask for LatencyUpdater {
 	}
 
 	async fn run(&mut self) {
+		let shard_manager = CLIENT_DATA
+			.get()
+			.expect("client data not set yet")
+			.shard_manager
+			.clone();
+
 		self.0.latency.websocket.set(
-			scripty_utils::latency::get_ws_latency(
-				&CLIENT_DATA
-					.get()
-					.expect("client data not set yet")
-					.shard_manager
-					.clone(),
-				self.1.shard_id.0,
-			)
-			.await
-			.unwrap_or(0) as i64,
+			scripty_utils::latency::get_ws_latency(&shard_manager, self.1.shard_id.0)
+				.await
+				.unwrap_or(0) as i64,
 		);
 
+		// record every shard's latency into its history, then export the summary
+		for (shard_id, latency) in
+			scripty_utils::latency::get_all_ws_latencies(&shard_manager).await
+		{
+			if let Some(latency) = latency {
+				scripty_utils::latency::record_ws_latency(shard_id, latency);
+			}
+		}
+		for (shard_id, stats) in scripty_utils::latency::get_all_latency_stats() {
+			let shard_id = shard_id.to_string();
+			for (stat, value) in [
+				("current", stats.current),
+				("average", stats.average),
+				("min", stats.min),
+				("max", stats.max),
+				("jitter", stats.jitter),
+			] {
+				self.0
+					.shard_latency
+					.with_label_values(&[&shard_id, stat])
+					.set(value as i64);
+			}
+		}
+
 		let http_latency = tokio::time::timeout(
 			Duration::from_secs(10),
 			scripty_utils::latency::get_http_latency(&self.1, ChannelId::new(983575000034455584)),
@@ -48,9 +71,9 @@ impl BackgroundTask for LatencyUpdater {
 			}
 		}
 
-		self.0
-			.latency
-			.db
-			.set(scripty_utils::latency::get_db_latency().await as i64);
+		match scripty_utils::latency::get_db_latency().await {
+			Ok(latency) => self.0.latency.db.set(latency as i64),
+			Err(e) => error!("Failed to get database latency: {}", e),
+		}
 	}
 }
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{
+	background_tasks::core::BackgroundTask,
+	scheduler::{self, CronSchedule, Job, JobHandler},
+	Error,
+};
+
+/// Name and kind of the recurring job that resets transcription quotas.
+const QUOTA_RESET_JOB: &str = "transcription_quota_reset";
+
+/// Adds transcription quota usage to the database every 30 seconds,
+/// and schedules the monthly quota reset.
+pub struct QuotaUsageFlusher;
+
+#[async_trait]
+impl BackgroundTask for QuotaUsageFlusher {
+	async fn init(_: Context) -> Result<Self, Error> {
+		scheduler::register_job_handler(QUOTA_RESET_JOB, QuotaReset);
+		let schedule: CronSchedule = "@monthly".parse().expect("static cron schedule is valid");
+		scheduler::schedule_recurring(QUOTA_RESET_JOB, QUOTA_RESET_JOB, "", &schedule).await?;
+
+		Ok(Self)
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(30)
+	}
+
+	async fn run(&mut self) {
+		if let Err(e) = scripty_audio_handler::flush_quota_usage().await {
+			error!("failed to flush transcription quota usage: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(30))
+	}
+}
+
+struct QuotaReset;
+
+#[async_trait]
+impl JobHandler for QuotaReset {
+	async fn run(&self, _: &Context, _: &Job) -> Result<(), Error> {
+		// last month's final seconds go to last month
+		scripty_audio_handler::flush_quota_usage().await?;
+		let reset = scripty_audio_handler::reset_quotas().await?;
+		info!("reset transcription quotas of {} guilds", reset);
+		Ok(())
+	}
+}
@@ -0,0 +1,87 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use serenity::{
+	all::{ActivityType, OnlineStatus},
+	client::Context as SerenityContext,
+	gateway::{ActivityData, ShardManager},
+};
+
+use crate::{background_tasks::core::BackgroundTask, globals::CLIENT_DATA, Error};
+
+/// Always refresh the presence this often, even if nothing changed,
+/// since Discord drops it when a shard reconnects.
+const FORCE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Shows the number of active transcription sessions as the bot's status, if `status_show_sessions` is set.
+///
+/// The session count is checked every 10 seconds, but the presence is only sent when it changes,
+/// so a burst of joins and leaves results in a single update.
+pub struct SessionPresenceUpdater {
+	shard_manager: Arc<ShardManager>,
+	last_count:    Option<usize>,
+	last_update:   Instant,
+}
+
+#[async_trait]
+impl BackgroundTask for SessionPresenceUpdater {
+	async fn init(_: SerenityContext) -> Result<Self, Error> {
+		Ok(Self {
+			shard_manager: CLIENT_DATA
+				.get()
+				.expect("client data not initialized")
+				.shard_manager
+				.clone(),
+			last_count:    None,
+			last_update:   Instant::now(),
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(10)
+	}
+
+	async fn run(&mut self) {
+		if !scripty_config::get_config().status_show_sessions {
+			self.last_count = None;
+			return;
+		}
+
+		let session_count = scripty_audio_handler::get_session_stats().len();
+		if self.last_count == Some(session_count)
+			&& self.last_update.elapsed() < FORCE_REFRESH_INTERVAL
+		{
+			return;
+		}
+		self.last_count = Some(session_count);
+		self.last_update = Instant::now();
+
+		let state = match session_count {
+			0 => "Ready to transcribe | /help".to_string(),
+			1 => "Transcribing 1 call | /help".to_string(),
+			n => format!(
+				"Transcribing {} calls | /help",
+				scripty_utils::separate_num(n, super::status_update::STATUS_LANGUAGE)
+			),
+		};
+
+		let runners = self.shard_manager.runners.lock().await;
+		for shard_info in runners.values() {
+			let activity = ActivityData {
+				name:  "UwU~".to_string().into(),
+				kind:  ActivityType::Custom,
+				state: Some(state.clone().into()),
+				url:   None,
+			};
+			shard_info
+				.runner_tx
+				.set_presence(Some(activity), OnlineStatus::Online);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(5))
+	}
+}
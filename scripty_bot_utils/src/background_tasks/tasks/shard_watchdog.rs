@@ -0,0 +1,147 @@
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+use serenity::{
+	all::ShardId,
+	builder::{CreateEmbed, ExecuteWebhook},
+	client::Context,
+	gateway::ConnectionStage,
+	model::webhook::Webhook,
+};
+
+use crate::{background_tasks::core::BackgroundTask, globals::CLIENT_DATA, Error};
+
+/// How long a shard may be unhealthy before it is restarted.
+const RESTART_AFTER: Duration = Duration::from_secs(5 * 60);
+/// How many restarts to attempt before giving up and alerting the owners.
+const MAX_RESTARTS: u8 = 3;
+/// A heartbeat latency above this is treated as a stale heartbeat.
+const STALE_HEARTBEAT: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct ShardHealth {
+	/// When this shard was first seen unhealthy, if it currently is.
+	unhealthy_since: Option<Instant>,
+	/// Restarts attempted since the shard was last healthy.
+	restarts:        u8,
+	/// Whether the owners have already been alerted about this shard.
+	alerted:         bool,
+}
+
+/// Watches every shard, restarting any that are disconnected or have stale heartbeats for too long,
+/// and alerting the error webhook if a shard doesn't recover after several restarts.
+pub struct ShardWatchdog {
+	ctx:    Context,
+	health: HashMap<u16, ShardHealth>,
+}
+
+#[async_trait]
+impl BackgroundTask for ShardWatchdog {
+	async fn init(ctx: Context) -> Result<Self, Error> {
+		Ok(Self {
+			ctx,
+			health: HashMap::new(),
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(30)
+	}
+
+	async fn run(&mut self) {
+		let shard_manager = CLIENT_DATA
+			.get()
+			.expect("client data not set yet")
+			.shard_manager
+			.clone();
+
+		// collect state first, so the runners lock isn't held while restarting
+		let shard_states: Vec<(u16, bool, ConnectionStage, Option<Duration>)> = {
+			let runners = shard_manager.runners.lock().await;
+			runners
+				.iter()
+				.map(|(id, runner)| {
+					let healthy = matches!(runner.stage, ConnectionStage::Connected)
+						&& runner.latency.map_or(true, |l| l < STALE_HEARTBEAT);
+					(id.0, healthy, runner.stage, runner.latency)
+				})
+				.collect()
+		};
+
+		let mut to_restart = Vec::new();
+		let mut to_alert = Vec::new();
+		for (shard_id, healthy, stage, latency) in shard_states {
+			let health = self.health.entry(shard_id).or_default();
+
+			if healthy {
+				if health.unhealthy_since.is_some() {
+					info!(%shard_id, "shard recovered");
+				}
+				*health = ShardHealth::default();
+				continue;
+			}
+
+			let unhealthy_since = *health.unhealthy_since.get_or_insert_with(Instant::now);
+			if unhealthy_since.elapsed() < RESTART_AFTER {
+				continue;
+			}
+
+			if health.restarts < MAX_RESTARTS {
+				warn!(%shard_id, ?stage, ?latency, restarts = health.restarts, "restarting unhealthy shard");
+				health.restarts += 1;
+				// give the restarted shard another full window to recover
+				health.unhealthy_since = Some(Instant::now());
+				to_restart.push(shard_id);
+			} else if !health.alerted {
+				error!(%shard_id, ?stage, ?latency, "shard failed to recover after restarts");
+				health.alerted = true;
+				to_alert.push((shard_id, stage, latency));
+			}
+		}
+
+		for shard_id in to_restart {
+			shard_manager.restart(ShardId(shard_id)).await;
+		}
+
+		for (shard_id, stage, latency) in to_alert {
+			self.alert(shard_id, stage, latency).await;
+		}
+	}
+}
+
+impl ShardWatchdog {
+	async fn alert(&self, shard_id: u16, stage: ConnectionStage, latency: Option<Duration>) {
+		let hook =
+			match Webhook::from_url(&self.ctx, &scripty_config::get_config().error_webhook).await {
+				Ok(hook) => hook,
+				Err(e) => {
+					error!("failed to fetch error webhook: {}", e);
+					return;
+				}
+			};
+
+		let embed = CreateEmbed::default()
+			.title(format!("Shard {} is not recovering", shard_id))
+			.color((255, 0, 0))
+			.description(format!(
+				"Restarted {} times, but it is still unhealthy. Guilds on this shard are not \
+				 being served.",
+				MAX_RESTARTS
+			))
+			.field("Stage", format!("{:?}", stage), true)
+			.field(
+				"Latency",
+				latency.map_or_else(|| "none".to_string(), |l| format!("{:?}", l)),
+				true,
+			);
+
+		if let Err(e) = hook
+			.execute(&self.ctx, false, ExecuteWebhook::default().embed(embed))
+			.await
+		{
+			error!("failed to send shard alert: {}", e);
+		}
+	}
+}
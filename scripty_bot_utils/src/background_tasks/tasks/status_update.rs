@@ -8,7 +8,13 @@ use serenity::{
 
 use crate::{background_tasks::core::BackgroundTask, globals::CLIENT_DATA, Error};
 
-/// Updates the bot status every minute.
+/// Statuses are the same for everyone who sees them, and written in English, so numbers in them
+/// are formatted the English way.
+pub(crate) const STATUS_LANGUAGE: &str = "en";
+
+/// Updates the bot status every minute, or every `status_interval` seconds.
+///
+/// If `status_rotation` is set, each update shows the next template in it instead of the default.
 pub struct StatusUpdater {
 	ctx:           SerenityContext,
 	shard_manager: Arc<ShardManager>,
@@ -30,7 +36,7 @@ impl BackgroundTask for StatusUpdater {
 	}
 
 	fn interval(&mut self) -> Duration {
-		Duration::from_secs(60)
+		Duration::from_secs(scripty_config::get_config().status_interval.unwrap_or(60))
 	}
 
 	async fn run(&mut self) {
@@ -41,9 +47,27 @@ impl BackgroundTask for StatusUpdater {
 			return;
 		}
 
-		let guild_count = self.ctx.cache.guild_count();
-		let mut guild_count_fmt = num_format::Buffer::new();
-		guild_count_fmt.write_formatted(&guild_count, &num_format::Locale::en);
+		// the session count is shown instead, by `SessionPresenceUpdater`
+		if scripty_config::get_config().status_show_sessions {
+			return;
+		}
+
+		let guild_count =
+			scripty_utils::separate_num(self.ctx.cache.guild_count(), STATUS_LANGUAGE);
+
+		// read on every run, so reloading the config applies on the next update
+		let cfg = scripty_config::get_config();
+		let template = if cfg.status_rotation.is_empty() {
+			None
+		} else {
+			// skip the first two runs we didn't update on, so rotation starts at the first template
+			let idx = (self.run_number as usize - 3) % cfg.status_rotation.len();
+			Some(cfg.status_rotation[idx].as_str())
+		};
+		let session_count = scripty_utils::separate_num(
+			scripty_audio_handler::get_session_stats().len(),
+			STATUS_LANGUAGE,
+		);
 
 		let runners = self.shard_manager.runners.lock().await;
 		for (shard_id, shard_info) in runners.iter() {
@@ -51,17 +75,25 @@ impl BackgroundTask for StatusUpdater {
 				.latency
 				.unwrap_or_else(|| Duration::from_nanos(0))
 				.as_millis();
+			let shard_latency = scripty_utils::separate_num(shard_latency, STATUS_LANGUAGE);
 
-			let mut shard_latency_fmt = num_format::Buffer::new();
-			shard_latency_fmt.write_formatted(&shard_latency, &num_format::Locale::en);
-
-			// format the latency as a decimal to three decimal places
-			let shard_status = format!(
-				"{} guilds | {:.3}ms latency | shard ID {}",
-				guild_count_fmt.as_str(),
-				shard_latency_fmt.as_str(),
-				shard_id.0
-			);
+			let shard_status = if let Some(template) = template {
+				template
+					.replace("{guilds}", &guild_count)
+					.replace("{sessions}", &session_count)
+					.replace("{latency}", &format!("{}ms", shard_latency))
+					.replace("{shard}", &shard_id.0.to_string())
+					.replace("{help_command}", "/help")
+			} else {
+				let shard_status = format!(
+					"{} guilds | {}ms latency | shard ID {}",
+					guild_count, shard_latency, shard_id.0
+				);
+				match &cfg.status_text {
+					Some(status_text) => format!("{} | {}", status_text, shard_status),
+					None => shard_status,
+				}
+			};
 
 			// create activity
 			let activity = ActivityData {
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, systemd, Error};
+
+/// Tells systemd once the bot is fully up, then keeps its watchdog fed.
+///
+/// If the runtime hangs, this stops running, and systemd restarts the process once the watchdog
+/// times out.
+pub struct SystemdNotifier {
+	enabled:          bool,
+	watchdog_timeout: Option<Duration>,
+	ready:            bool,
+	healthy:          bool,
+}
+
+#[async_trait]
+impl BackgroundTask for SystemdNotifier {
+	async fn init(_: Context) -> Result<Self, Error> {
+		let enabled = systemd::is_enabled();
+		let watchdog_timeout = systemd::watchdog_timeout();
+		if let Some(timeout) = watchdog_timeout {
+			info!("systemd watchdog enabled, timeout {:?}", timeout);
+		}
+
+		Ok(Self {
+			enabled,
+			watchdog_timeout,
+			ready: false,
+			healthy: false,
+		})
+	}
+
+	fn interval(&mut self) -> Duration {
+		// ping twice per timeout, as systemd recommends
+		self.watchdog_timeout
+			.map_or(Duration::from_secs(10), |timeout| timeout / 2)
+	}
+
+	async fn run(&mut self) {
+		if !self.enabled {
+			return;
+		}
+
+		// feed the watchdog first, so a slow health check doesn't get the process killed
+		if self.watchdog_timeout.is_some() {
+			systemd::notify_watchdog();
+		}
+
+		let healthy = crate::health::is_healthy().await;
+		if !self.ready {
+			// the gateway, database, Redis and STT pool all have to be up before we're ready
+			if healthy {
+				systemd::notify_ready();
+				self.ready = true;
+				self.healthy = true;
+			}
+		} else if healthy != self.healthy {
+			systemd::notify_status(if healthy { "running" } else { "degraded" });
+			self.healthy = healthy;
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(self.interval())
+	}
+}
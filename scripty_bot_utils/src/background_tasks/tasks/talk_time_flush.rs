@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{
+	background_tasks::core::BackgroundTask,
+	scheduler::{self, CronSchedule, Job, JobHandler},
+	Error,
+};
+
+/// Name and kind of the recurring job that deletes old talk time.
+const TALK_TIME_CLEANUP_JOB: &str = "talk_time_cleanup";
+
+/// Adds talk time for `/analytics` to the database every minute,
+/// and schedules deleting it once it's too old.
+pub struct TalkTimeFlusher;
+
+#[async_trait]
+impl BackgroundTask for TalkTimeFlusher {
+	async fn init(_: Context) -> Result<Self, Error> {
+		scheduler::register_job_handler(TALK_TIME_CLEANUP_JOB, TalkTimeCleanup);
+		let schedule: CronSchedule = "@daily".parse().expect("static cron schedule is valid");
+		scheduler::schedule_recurring(TALK_TIME_CLEANUP_JOB, TALK_TIME_CLEANUP_JOB, "", &schedule)
+			.await?;
+
+		Ok(Self)
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(60)
+	}
+
+	async fn run(&mut self) {
+		if let Err(e) = scripty_audio_handler::flush_talk_time().await {
+			error!("failed to flush talk time: {}", e);
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(30))
+	}
+}
+
+struct TalkTimeCleanup;
+
+#[async_trait]
+impl JobHandler for TalkTimeCleanup {
+	async fn run(&self, _: &Context, _: &Job) -> Result<(), Error> {
+		let deleted = scripty_audio_handler::delete_old_talk_time().await?;
+		info!("deleted {} hours of old talk time", deleted);
+		Ok(())
+	}
+}
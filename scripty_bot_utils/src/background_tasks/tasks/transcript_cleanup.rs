@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+
+use crate::{background_tasks::core::BackgroundTask, Error};
+
+/// Deletes transcripts stored for the transcript API once they're past retention, every hour.
+pub struct TranscriptCleaner;
+
+#[async_trait]
+impl BackgroundTask for TranscriptCleaner {
+	async fn init(_: Context) -> Result<Self, Error> {
+		Ok(Self)
+	}
+
+	fn interval(&mut self) -> Duration {
+		Duration::from_secs(60 * 60)
+	}
+
+	async fn run(&mut self) {
+		match crate::api_keys::delete_expired_transcripts().await {
+			Ok(0) => {}
+			Ok(deleted) => info!("deleted {} expired transcript sessions", deleted),
+			Err(e) => error!("failed to delete expired transcripts: {}", e),
+		}
+	}
+
+	fn timeout(&mut self) -> Option<Duration> {
+		Some(Duration::from_secs(60))
+	}
+}
@@ -0,0 +1,14 @@
+use serenity::model::id::{ChannelId, GuildId};
+
+use crate::{Context, Error};
+
+/// Whether the command was run in the DM support category, where only staff can see.
+pub async fn is_dm_support_staff_channel(ctx: Context<'_>) -> Result<bool, Error> {
+	let cfg = &scripty_config::get_config().dm_support;
+	if ctx.guild_id() != Some(GuildId::new(cfg.guild_id)) {
+		return Ok(false);
+	}
+
+	let channel = ctx.channel_id().to_channel(&ctx).await?;
+	Ok(channel.guild().and_then(|c| c.parent_id) == Some(ChannelId::new(cfg.forwarding_category)))
+}
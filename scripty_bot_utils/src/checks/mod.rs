@@ -1,3 +1,5 @@
+mod dm_support;
 mod guild_only;
 
+pub use dm_support::is_dm_support_staff_channel;
 pub use guild_only::is_guild;
@@ -0,0 +1,203 @@
+//! Multi-process clustering.
+//!
+//! When a cluster config is set, each process claims one of `cluster_count` slots in Redis,
+//! and runs only the range of shards belonging to that slot. Redis acts as the broker:
+//! slots expire if a process stops renewing them, so a replacement process can take over,
+//! and a startup lock ensures only one cluster is identifying shards at a time during rolling restarts.
+
+use std::{
+	ops::Range,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::OnceCell;
+use scripty_config::ClusterConfig;
+use scripty_redis::{redis::AsyncCommands, TransactionError};
+use serenity::client::Cache;
+
+/// How long a claimed slot survives without being renewed.
+const SLOT_TTL_SECS: u64 = 60;
+/// How often a claimed slot is renewed, well within `SLOT_TTL_SECS`.
+const SLOT_RENEW_INTERVAL: Duration = Duration::from_secs(20);
+/// How long the startup lock can be held before it is considered abandoned.
+const STARTUP_LOCK_TTL_SECS: u64 = 15 * 60;
+const STARTUP_LOCK_KEY: &str = "cluster:startup_lock";
+
+static CLUSTER_ID: OnceCell<u16> = OnceCell::new();
+static CLUSTER_TOKEN: OnceCell<String> = OnceCell::new();
+
+/// Unique token identifying this process, so it only ever renews or releases its own claims.
+fn get_token() -> &'static str {
+	CLUSTER_TOKEN.get_or_init(|| {
+		let nanos = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |d| d.as_nanos());
+		format!("{}-{}", std::process::id(), nanos)
+	})
+}
+
+/// The cluster ID claimed by this process, or `None` if not running clustered.
+pub fn get_cluster_id() -> Option<u16> {
+	CLUSTER_ID.get().copied()
+}
+
+/// Claim the first free cluster slot, waiting until one becomes available.
+pub async fn claim_cluster_slot(cfg: &ClusterConfig) -> Result<u16, TransactionError> {
+	let token = get_token();
+	loop {
+		for cluster_id in 0..cfg.cluster_count {
+			let claimed: Option<String> = scripty_redis::run_transaction("SET", |cmd| {
+				cmd.arg(format!("cluster:{}:slot", cluster_id))
+					.arg(token)
+					.arg("NX")
+					.arg("EX")
+					.arg(SLOT_TTL_SECS);
+			})
+			.await?;
+
+			if claimed.is_some() {
+				info!(%cluster_id, "claimed cluster slot");
+				CLUSTER_ID
+					.set(cluster_id)
+					.expect("claimed a cluster slot more than once");
+				return Ok(cluster_id);
+			}
+		}
+
+		warn!("all cluster slots are taken, waiting for one to free up");
+		tokio::time::sleep(Duration::from_secs(10)).await;
+	}
+}
+
+/// Keep renewing this process's cluster slot, from the moment it's claimed.
+///
+/// Only returns once the slot was lost to another process, which will run our shards from then
+/// on, so the caller must stop running them.
+pub async fn keep_cluster_slot() {
+	loop {
+		tokio::time::sleep(SLOT_RENEW_INTERVAL).await;
+		match renew_cluster_slot().await {
+			Ok(true) => {}
+			Ok(false) => return,
+			// the slot survives a few failed renewals, and if it doesn't, the next one notices
+			Err(e) => error!("failed to renew cluster slot: {}", e),
+		}
+	}
+}
+
+/// Renew this process's cluster slot. Returns false if another process holds it now.
+async fn renew_cluster_slot() -> Result<bool, TransactionError> {
+	let Some(cluster_id) = get_cluster_id() else {
+		return Ok(true);
+	};
+	let key = format!("cluster:{}:slot", cluster_id);
+
+	let mut conn = scripty_redis::get_pool().get().await?;
+	let holder: Option<String> = conn.get(&key).await?;
+	if holder.as_deref() == Some(get_token()) {
+		conn.expire(&key, SLOT_TTL_SECS as i64).await?;
+		Ok(true)
+	} else {
+		// another process took over our slot, most likely after we stalled for over a minute
+		error!(%cluster_id, ?holder, "lost cluster slot to another process");
+		Ok(false)
+	}
+}
+
+/// The range of shards a cluster is responsible for.
+///
+/// Shards are split as evenly as possible, with lower clusters taking any remainder.
+pub fn get_shard_range(cluster_id: u16, cfg: &ClusterConfig) -> Range<u16> {
+	let per_cluster = cfg.shard_count / cfg.cluster_count;
+	let remainder = cfg.shard_count % cfg.cluster_count;
+
+	let start = cluster_id * per_cluster + cluster_id.min(remainder);
+	let len = per_cluster + u16::from(cluster_id < remainder);
+	start..start + len
+}
+
+/// Wait until no other cluster is starting up, then take the startup lock.
+pub async fn acquire_startup_lock() -> Result<(), TransactionError> {
+	loop {
+		let acquired: Option<String> = scripty_redis::run_transaction("SET", |cmd| {
+			cmd.arg(STARTUP_LOCK_KEY)
+				.arg(get_token())
+				.arg("NX")
+				.arg("EX")
+				.arg(STARTUP_LOCK_TTL_SECS);
+		})
+		.await?;
+		if acquired.is_some() {
+			return Ok(());
+		}
+
+		info!("another cluster is starting up, waiting");
+		tokio::time::sleep(Duration::from_secs(5)).await;
+	}
+}
+
+/// Release the startup lock, if this process holds it.
+pub async fn release_startup_lock() -> Result<(), TransactionError> {
+	let mut conn = scripty_redis::get_pool().get().await?;
+	let holder: Option<String> = conn.get(STARTUP_LOCK_KEY).await?;
+	if holder.as_deref() == Some(get_token()) {
+		conn.del(STARTUP_LOCK_KEY).await?;
+	}
+	Ok(())
+}
+
+/// Combined stats of every running cluster.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClusterStats {
+	pub guilds: u64,
+	pub users:  u64,
+}
+
+/// Publish this cluster's stats, for other clusters to aggregate.
+pub async fn publish_cluster_stats(stats: ClusterStats) -> Result<(), TransactionError> {
+	let Some(cluster_id) = get_cluster_id() else {
+		return Ok(());
+	};
+	let key = format!("cluster:{}:stats", cluster_id);
+
+	let mut conn = scripty_redis::get_pool().get().await?;
+	scripty_redis::redis::pipe()
+		.hset(&key, "guilds", stats.guilds)
+		.hset(&key, "users", stats.users)
+		.expire(&key, SLOT_TTL_SECS as i64)
+		.ignore()
+		.query_async(&mut conn)
+		.await?;
+	Ok(())
+}
+
+/// Sum the published stats of every cluster.
+///
+/// Clusters that haven't published recently are not counted.
+pub async fn get_aggregate_stats(cfg: &ClusterConfig) -> Result<ClusterStats, TransactionError> {
+	let mut conn = scripty_redis::get_pool().get().await?;
+	let mut total = ClusterStats::default();
+	for cluster_id in 0..cfg.cluster_count {
+		let (guilds, users): (Option<u64>, Option<u64>) = conn
+			.hget(
+				format!("cluster:{}:stats", cluster_id),
+				&["guilds", "users"],
+			)
+			.await?;
+		total.guilds += guilds.unwrap_or(0);
+		total.users += users.unwrap_or(0);
+	}
+	Ok(total)
+}
+
+/// Guild and shard counts of the whole bot, as posted to bot lists:
+/// every cluster's combined if clustered, or just `cache`'s otherwise.
+pub async fn get_bot_counts(cache: &Cache) -> Result<(u64, u16), TransactionError> {
+	match &scripty_config::get_config().cluster {
+		Some(cluster_cfg) => {
+			let aggregate = get_aggregate_stats(cluster_cfg).await?;
+			Ok((aggregate.guilds, cluster_cfg.shard_count))
+		}
+		None => Ok((cache.guild_count() as u64, cache.shard_count().get())),
+	}
+}
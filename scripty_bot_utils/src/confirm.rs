@@ -0,0 +1,86 @@
+//! Yes/No confirmation prompts, for commands that do something that can't be undone.
+
+use std::time::Duration;
+
+use poise::CreateReply;
+use serenity::{
+	all::ButtonStyle,
+	builder::{
+		CreateActionRow,
+		CreateButton,
+		CreateEmbed,
+		CreateInteractionResponse,
+		CreateInteractionResponseMessage,
+	},
+	collector::ComponentInteractionCollector,
+};
+
+use crate::{Context, Error};
+
+/// How long the author has to answer before it counts as a no.
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Show `prompt` with Yes and No buttons, and wait for the author to pick one.
+///
+/// Nobody else can answer. Returns false if the author picks No, or doesn't answer in time.
+/// The buttons are disabled afterwards, and the prompt is left in place.
+pub async fn confirm(ctx: Context<'_>, prompt: CreateEmbed) -> Result<bool, Error> {
+	let resolved_language = crate::get_ctx_language(ctx).await;
+	// the invocation ID keeps the buttons of two prompts in the same channel apart
+	let yes_id = format!("confirm_yes:{}", ctx.id());
+	let no_id = format!("confirm_no:{}", ctx.id());
+	let buttons = |disabled: bool| {
+		vec![CreateActionRow::Buttons(vec![
+			CreateButton::new(&yes_id)
+				.style(ButtonStyle::Danger)
+				.label(format_message!(resolved_language, "confirm-yes"))
+				.disabled(disabled),
+			CreateButton::new(&no_id)
+				.style(ButtonStyle::Secondary)
+				.label(format_message!(resolved_language, "confirm-no"))
+				.disabled(disabled),
+		])]
+	};
+
+	let reply = ctx
+		.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.embed(prompt.clone())
+				.components(buttons(false)),
+		)
+		.await?;
+	let msg = reply.message().await?;
+
+	let interaction = ComponentInteractionCollector::new(&ctx.serenity_context().shard)
+		.author_id(ctx.author().id)
+		.message_id(msg.id)
+		.timeout(CONFIRM_TIMEOUT)
+		.next()
+		.await;
+
+	let Some(interaction) = interaction else {
+		reply
+			.edit(
+				ctx,
+				CreateReply::default()
+					.content(format_message!(resolved_language, "confirm-timed-out"))
+					.embed(prompt)
+					.components(buttons(true)),
+			)
+			.await?;
+		return Ok(false);
+	};
+
+	interaction
+		.create_response(
+			ctx,
+			CreateInteractionResponse::UpdateMessage(
+				CreateInteractionResponseMessage::default()
+					.embed(prompt)
+					.components(buttons(true)),
+			),
+		)
+		.await?;
+	Ok(interaction.data.custom_id == yes_id)
+}
@@ -0,0 +1,97 @@
+//! DMs telling members that the voice chat they just joined is being transcribed, for guilds that
+//! turned it on with `/config consent_dm`.
+//!
+//! Each user is only ever DMed once, across every guild, so it doesn't get annoying.
+
+use serenity::{
+	all::{ButtonStyle, ChannelId, GuildId, UserId},
+	builder::{CreateActionRow, CreateButton, CreateEmbed, CreateMessage},
+	client::Context,
+};
+
+/// DM `user_id` about `voice_channel_id` being transcribed, if its guild wants members told and
+/// they haven't been already.
+pub async fn notify_if_needed(
+	ctx: &Context,
+	guild_id: GuildId,
+	user_id: UserId,
+	voice_channel_id: ChannelId,
+) {
+	let db = scripty_db::get_db();
+	let enabled = match sqlx::query!(
+		"SELECT consent_dm FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(db)
+	.await
+	{
+		Ok(row) => row.map_or(false, |row| row.consent_dm),
+		Err(e) => {
+			error!(%guild_id, "failed to fetch consent DM config: {}", e);
+			return;
+		}
+	};
+	if !enabled {
+		return;
+	}
+
+	let hashed_user_id = scripty_utils::hash_user_id(user_id.get());
+	// claiming the notice first means joining two channels at once can't DM twice
+	match sqlx::query!(
+		"INSERT INTO consent_notices (user_id) VALUES ($1) ON CONFLICT DO NOTHING",
+		hashed_user_id
+	)
+	.execute(db)
+	.await
+	{
+		Ok(res) if res.rows_affected() == 0 => return,
+		Ok(_) => {}
+		Err(e) => {
+			error!(%guild_id, "failed to record consent notice: {}", e);
+			return;
+		}
+	}
+
+	let language = match scripty_i18n::get_user_language(user_id.get()).await {
+		Some(language) => language,
+		None => scripty_i18n::get_guild_language(guild_id.get()).await,
+	};
+	let guild_name = guild_id.name(ctx).unwrap_or_else(|| guild_id.to_string());
+	let embed = CreateEmbed::default()
+		.title(format_message!(language, "consent-notice-title"))
+		.description(format_message!(
+			language,
+			"consent-notice-description",
+			guildName: guild_name,
+			voiceChannelId: voice_channel_id.to_string()
+		));
+	let buttons = CreateActionRow::Buttons(vec![
+		CreateButton::new_link("https://scripty.org/privacy")
+			.label(format_message!(language, "consent-notice-privacy-button")),
+		CreateButton::new_link("https://scripty.org/terms")
+			.label(format_message!(language, "consent-notice-terms-button"))
+			.style(ButtonStyle::Secondary),
+	]);
+
+	if let Err(e) = user_id
+		.direct_message(
+			ctx,
+			CreateMessage::default()
+				.embed(embed)
+				.components(vec![buttons]),
+		)
+		.await
+	{
+		// most likely DMs closed. They weren't told, so try again next time
+		debug!(%user_id, "failed to send consent notice: {}", e);
+		if let Err(e) = sqlx::query!(
+			"DELETE FROM consent_notices WHERE user_id = $1",
+			hashed_user_id
+		)
+		.execute(db)
+		.await
+		{
+			warn!("failed to forget unsent consent notice: {}", e);
+		}
+	}
+}
@@ -0,0 +1,276 @@
+//! Local admin control socket.
+//!
+//! Offers the owner-only admin commands over a unix socket or TCP,
+//! so operators can script maintenance without going through Discord.
+//! See `scripty_control` for the protocol.
+
+use std::io;
+
+use scripty_config::ControlConfig;
+use scripty_control::{
+	BlacklistEntry,
+	CacheField,
+	Command,
+	EntityKind,
+	Request,
+	Response,
+	ResponseData,
+	Session,
+	SttWorker,
+};
+use scripty_utils::constant_time_eq;
+use serenity::{
+	all::{GuildId, UserId},
+	client::Context,
+};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+	net::{TcpListener, UnixListener},
+};
+
+use crate::{entity_block, globals::CLIENT_DATA};
+
+/// Longest request line accepted, in bytes.
+const MAX_REQUEST_LEN: usize = 64 * 1024;
+
+/// Serve the control socket, if it is configured. Runs forever.
+pub async fn serve(ctx: Context) {
+	let Some(cfg) = scripty_config::get_config().control.clone() else {
+		return;
+	};
+
+	if let Some(path) = cfg.bind_address.strip_prefix("unix:") {
+		let listener = match bind_private(path) {
+			Ok(l) => l,
+			Err(e) => {
+				error!(%path, "failed to bind control socket: {}", e);
+				return;
+			}
+		};
+		info!(%path, "serving control socket");
+
+		loop {
+			match listener.accept().await {
+				Ok((stream, _)) => spawn_connection(&ctx, &cfg, stream),
+				Err(e) => error!("failed to accept control connection: {}", e),
+			}
+		}
+	} else {
+		let listener = match TcpListener::bind(&cfg.bind_address).await {
+			Ok(l) => l,
+			Err(e) => {
+				error!(bind_address = %cfg.bind_address, "failed to bind control socket: {}", e);
+				return;
+			}
+		};
+		info!(bind_address = %cfg.bind_address, "serving control socket");
+
+		loop {
+			match listener.accept().await {
+				Ok((stream, addr)) => {
+					debug!(%addr, "accepted control connection");
+					spawn_connection(&ctx, &cfg, stream)
+				}
+				Err(e) => error!("failed to accept control connection: {}", e),
+			}
+		}
+	}
+}
+
+/// Bind a unix socket at `path` that only the user the bot runs as may connect to.
+///
+/// The socket is created in a directory only that user can enter, and only moved to `path` once
+/// its permissions are restricted, so nobody else can connect in between.
+fn bind_private(path: &str) -> io::Result<UnixListener> {
+	use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+	let dir = format!("{}.{}.tmp", path, std::process::id());
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+	let tmp_path = format!("{}/control.sock", dir);
+
+	let res = UnixListener::bind(&tmp_path).and_then(|listener| {
+		std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))?;
+		// replaces any stale socket left over from a previous run
+		std::fs::rename(&tmp_path, path)?;
+		Ok(listener)
+	});
+	let _ = std::fs::remove_dir_all(&dir);
+	res
+}
+
+fn spawn_connection<S>(ctx: &Context, cfg: &ControlConfig, stream: S)
+where
+	S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+	let ctx = ctx.clone();
+	let token = cfg.token.clone();
+	scripty_utils::spawn_logged("control_connection", async move {
+		if let Err(e) = handle_connection(ctx, &token, stream).await {
+			debug!("control connection closed: {}", e);
+		}
+	});
+}
+
+async fn handle_connection<S>(ctx: Context, token: &str, stream: S) -> io::Result<()>
+where
+	S: AsyncRead + AsyncWrite + Unpin,
+{
+	let mut stream = BufReader::new(stream);
+	let mut line = String::new();
+
+	loop {
+		line.clear();
+		let read = (&mut stream)
+			.take(MAX_REQUEST_LEN as u64)
+			.read_line(&mut line)
+			.await?;
+		if read == 0 {
+			return Ok(());
+		}
+		if read == MAX_REQUEST_LEN && !line.ends_with('\n') {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				"request too long",
+			));
+		}
+
+		let response = match serde_json::from_str::<Request>(&line) {
+			Ok(req) if !constant_time_eq(req.token.as_bytes(), token.as_bytes()) => {
+				warn!("rejected control request with an invalid token");
+				Response::Error("invalid token".to_string())
+			}
+			Ok(req) => {
+				info!(command = ?req.command, "running control command");
+				run_command(&ctx, req.command).await
+			}
+			Err(e) => Response::Error(format!("invalid request: {}", e)),
+		};
+
+		let mut out = serde_json::to_vec(&response).expect("responses always serialize");
+		out.push(b'\n');
+		stream.get_mut().write_all(&out).await?;
+	}
+}
+
+async fn run_command(ctx: &Context, command: Command) -> Response {
+	// Discord IDs are never 0, and serenity panics on them
+	if let Command::BlacklistAdd { id: 0, .. } | Command::BlacklistRemove { id: 0, .. } = command {
+		return Response::Error("invalid ID: IDs can't be 0".to_string());
+	}
+
+	match command {
+		Command::Shutdown => {
+			let ctx = ctx.clone();
+			// respond first, as the connection won't survive the shutdown
+			tokio::spawn(async move {
+				crate::systemd::notify_stopping();
+				scripty_audio_handler::drain(&ctx, scripty_audio_handler::DRAIN_TIMEOUT).await;
+				if let Some(data) = CLIENT_DATA.get() {
+					data.shard_manager.shutdown_all().await;
+				}
+			});
+			message("draining active sessions, then shutting down")
+		}
+		Command::Reload => match crate::reload::reload_config().await {
+			Ok(()) => message("reloaded config"),
+			Err(e) => Response::Error(e.to_string()),
+		},
+		Command::ReloadI18n => {
+			let summary = scripty_i18n::reload_i18n();
+			if summary.errors.is_empty() {
+				message(format!("reloaded {} languages", summary.languages.len()))
+			} else {
+				Response::Error(format!(
+					"reloaded {} languages, with errors:\n{}",
+					summary.languages.len(),
+					summary.errors.join("\n")
+				))
+			}
+		}
+		Command::BlacklistAdd { kind, id, reason } => {
+			let res = match kind {
+				EntityKind::User => entity_block::add_blocked_user(UserId::new(id), reason).await,
+				EntityKind::Guild => {
+					entity_block::add_blocked_guild(GuildId::new(id), reason).await
+				}
+			};
+			match res {
+				Ok(()) => message(format!("blacklisted {}", id)),
+				Err(e) => Response::Error(e.to_string()),
+			}
+		}
+		Command::BlacklistRemove { kind, id } => {
+			let res = match kind {
+				EntityKind::User => entity_block::remove_blocked_user(UserId::new(id)).await,
+				EntityKind::Guild => entity_block::remove_blocked_guild(GuildId::new(id)).await,
+			};
+			match res {
+				Ok(true) => message(format!("removed {} from the blacklist", id)),
+				Ok(false) => message(format!("{} was not blacklisted", id)),
+				Err(e) => Response::Error(e.to_string()),
+			}
+		}
+		Command::BlacklistList { kind } => {
+			let res = match kind {
+				EntityKind::User => entity_block::list_blocked_users().await,
+				EntityKind::Guild => entity_block::list_blocked_guilds().await,
+			};
+			match res {
+				Ok(entities) => Response::Ok(ResponseData::Blacklist {
+					entries: entities
+						.into_iter()
+						.map(|e| BlacklistEntry {
+							id:            e.id,
+							reason:        e.reason,
+							blocked_since: e.blocked_since.map(|t| t.unix_timestamp()),
+						})
+						.collect(),
+				}),
+				Err(e) => Response::Error(e.to_string()),
+			}
+		}
+		Command::SttList => Response::Ok(ResponseData::SttWorkers {
+			workers: scripty_stt::list_workers()
+				.into_iter()
+				.map(|w| SttWorker {
+					address:    w.address.to_string(),
+					overloaded: w.overloaded,
+					errored:    w.errored,
+				})
+				.collect(),
+		}),
+		Command::SttReload => match scripty_stt::reload_stt().await {
+			Ok(()) => message("reconnected to STT services"),
+			Err(e) => Response::Error(e.to_string()),
+		},
+		Command::CacheStats => Response::Ok(ResponseData::CacheStats {
+			fields: ctx
+				.cache
+				.get_size_details()
+				.into_iter()
+				.map(|f| CacheField {
+					name:  f.name.to_string(),
+					size:  f.size,
+					items: f.collection_items,
+				})
+				.collect(),
+		}),
+		Command::Sessions => Response::Ok(ResponseData::Sessions {
+			sessions: scripty_audio_handler::get_session_stats()
+				.into_iter()
+				.map(|s| Session {
+					guild_id:          s.guild_id.get(),
+					stt_streams:       s.stt_streams,
+					transcribed_users: s.transcribed_users,
+				})
+				.collect(),
+		}),
+	}
+}
+
+fn message(message: impl Into<String>) -> Response {
+	Response::Ok(ResponseData::Message {
+		message: message.into(),
+	})
+}
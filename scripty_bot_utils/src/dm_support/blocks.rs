@@ -0,0 +1,61 @@
+//! Users blocked from opening tickets.
+
+use scripty_utils::to_primitive;
+use serenity::model::id::UserId;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone)]
+pub struct Block {
+	pub reason:     Option<String>,
+	/// None if the block is permanent.
+	pub expires_at: Option<OffsetDateTime>,
+}
+
+/// Block a user from opening tickets, replacing any existing block.
+pub async fn block_user(
+	user_id: UserId,
+	blocked_by: UserId,
+	reason: Option<String>,
+	expires_at: Option<OffsetDateTime>,
+) -> Result<(), sqlx::Error> {
+	sqlx::query!(
+		"INSERT INTO dm_support_blocks (user_id, reason, blocked_by, expires_at) VALUES ($1, $2, \
+		 $3, $4) ON CONFLICT (user_id) DO UPDATE SET reason = EXCLUDED.reason, blocked_by = \
+		 EXCLUDED.blocked_by, blocked_at = NOW(), expires_at = EXCLUDED.expires_at",
+		scripty_utils::hash_user_id(user_id.get()),
+		reason,
+		blocked_by.get() as i64,
+		expires_at.map(to_primitive)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(())
+}
+
+/// Unblock a user. Returns true if they were blocked.
+pub async fn unblock_user(user_id: UserId) -> Result<bool, sqlx::Error> {
+	sqlx::query!(
+		"DELETE FROM dm_support_blocks WHERE user_id = $1",
+		scripty_utils::hash_user_id(user_id.get())
+	)
+	.execute(scripty_db::get_db())
+	.await
+	.map(|res| res.rows_affected() > 0)
+}
+
+/// The user's current block, if they have one that hasn't expired.
+pub async fn get_block(user_id: UserId) -> Result<Option<Block>, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT reason, expires_at FROM dm_support_blocks WHERE user_id = $1 AND (expires_at IS \
+		 NULL OR expires_at > NOW())",
+		scripty_utils::hash_user_id(user_id.get())
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.map(|row| Block {
+		reason:     row.reason,
+		expires_at: row.expires_at.map(|t| t.assume_utc()),
+	}))
+}
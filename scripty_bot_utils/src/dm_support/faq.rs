@@ -0,0 +1,22 @@
+//! Answering common questions before a ticket is opened.
+
+use scripty_config::FaqEntry;
+
+/// Custom ID prefix of the "talk to a human" button on FAQ answers.
+/// The ID of the message that was answered follows it.
+pub const OPEN_TICKET_BUTTON_PREFIX: &str = "dm_support_open_ticket:";
+
+/// The first FAQ entry with a pattern found in `content`, if any.
+pub fn find_entry(content: &str) -> Option<&'static FaqEntry> {
+	let content = content.to_lowercase();
+	scripty_config::get_config()
+		.dm_support
+		.faq
+		.iter()
+		.find(|entry| {
+			entry
+				.patterns
+				.iter()
+				.any(|pattern| content.contains(&pattern.to_lowercase()))
+		})
+}
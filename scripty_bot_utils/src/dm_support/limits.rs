@@ -0,0 +1,88 @@
+//! Reasons a user may not open a new ticket right now.
+
+use scripty_i18n::LanguageIdentifier;
+use serenity::model::id::UserId;
+use time::OffsetDateTime;
+
+use super::{blocks, blocks::Block, tickets};
+
+pub enum Refusal {
+	Blocked(Block),
+	/// The user's last ticket was closed too recently.
+	Cooldown {
+		until: OffsetDateTime,
+	},
+	/// The user has too many tickets open.
+	OpenLimit {
+		max: u32,
+	},
+}
+
+impl Refusal {
+	/// The translated explanation sent to the user.
+	pub fn describe(&self, language: &LanguageIdentifier) -> String {
+		match self {
+			Self::Blocked(block) => {
+				let reason = match &block.reason {
+					Some(reason) => format_message!(
+						language,
+						"blocked-entity-reason-given",
+						reason: reason.clone()
+					),
+					None => format_message!(language, "blocked-entity-no-reason-given"),
+				};
+				match block.expires_at {
+					Some(expires_at) => format_message!(
+						language,
+						"dm-support-blocked-until",
+						expiry: format!("<t:{}:f>", expires_at.unix_timestamp()),
+						reason: reason
+					),
+					None => format_message!(language, "dm-support-blocked", reason: reason),
+				}
+			}
+			Self::Cooldown { until } => format_message!(
+				language,
+				"dm-support-cooldown",
+				expiry: format!("<t:{}:R>", until.unix_timestamp())
+			),
+			Self::OpenLimit { max } => {
+				format_message!(language, "dm-support-open-limit", max: *max)
+			}
+		}
+	}
+}
+
+/// Why `user_id` may not open a new ticket, if they may not.
+///
+/// Database errors are logged and ignored, so an outage doesn't lock everyone out.
+pub async fn get_refusal(user_id: UserId) -> Option<Refusal> {
+	match blocks::get_block(user_id).await {
+		Ok(Some(block)) => return Some(Refusal::Blocked(block)),
+		Ok(None) => {}
+		Err(e) => error!("failed to check dm support block: {}", e),
+	}
+
+	let cfg = &scripty_config::get_config().dm_support;
+	if cfg.ticket_cooldown == 0 && cfg.max_open_tickets.is_none() {
+		return None;
+	}
+	let recent = match tickets::recent_tickets(user_id).await {
+		Ok(recent) => recent,
+		Err(e) => {
+			error!("failed to check recent dm support tickets: {}", e);
+			return None;
+		}
+	};
+
+	if let Some(closed_at) = recent.last_closed_at {
+		let until = closed_at + time::Duration::seconds(cfg.ticket_cooldown as i64);
+		if until > OffsetDateTime::now_utc() {
+			return Some(Refusal::Cooldown { until });
+		}
+	}
+	match cfg.max_open_tickets {
+		Some(max) if recent.open >= i64::from(max) => Some(Refusal::OpenLimit { max }),
+		_ => None,
+	}
+}
@@ -0,0 +1,844 @@
+mod blocks;
+mod faq;
+mod limits;
+mod tickets;
+mod transcript;
+
+use std::{
+	cmp::Ordering,
+	time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use serenity::{
+	all::{ButtonStyle, ComponentInteraction},
+	builder::{
+		CreateActionRow,
+		CreateAllowedMentions,
+		CreateAttachment,
+		CreateButton,
+		CreateChannel,
+		CreateEmbed,
+		CreateEmbedAuthor,
+		CreateEmbedFooter,
+		CreateInteractionResponse,
+		CreateInteractionResponseMessage,
+		CreateMessage,
+		CreateWebhook,
+		EditChannel,
+		ExecuteWebhook,
+	},
+	client::Context,
+	model::{
+		channel::{ChannelType, GuildChannel, Message},
+		id::{ChannelId, GuildId, MessageId, UserId},
+		user::User,
+		webhook::Webhook,
+	},
+};
+use time::OffsetDateTime;
+
+pub use self::tickets::{OpenTicket, Priority, TicketState};
+use crate::Error;
+
+/// Messages in ticket channels starting with this are staff commands, and aren't relayed.
+pub const COMMAND_PREFIX: &str = "~ps ";
+
+/// Staff messages starting with this are sent anonymously, even if the ticket isn't.
+pub const ANONYMOUS_PREFIX: &str = "!anon ";
+
+/// Who anonymous staff replies are attributed to.
+const ANONYMOUS_NAME: &str = "Scripty Support";
+
+/// Tags are limited so they fit comfortably in the channel topic.
+const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LEN: usize = 32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddTagResult {
+	Added,
+	AlreadyTagged,
+	/// The ticket already has the maximum number of tags, given here.
+	TooManyTags(usize),
+}
+
+/// How often a user is told they can't open a ticket, if they keep sending messages.
+const REFUSAL_NOTICE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How long after an FAQ answer a user's messages open a ticket, rather than being checked again.
+const FAQ_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct DmSupportStatus {
+	webhook_cache:        DashMap<ChannelId, Webhook>,
+	/// When each user was last told they can't open a ticket.
+	refusal_notice_cache: DashMap<UserId, Instant>,
+	/// When each user last had a message answered from the FAQ.
+	faq_cache:            DashMap<UserId, Instant>,
+}
+
+impl Default for DmSupportStatus {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl DmSupportStatus {
+	pub fn new() -> Self {
+		Self {
+			webhook_cache:        DashMap::new(),
+			refusal_notice_cache: DashMap::new(),
+			faq_cache:            DashMap::new(),
+		}
+	}
+
+	pub async fn handle_message(&self, ctx: Context, message: Message) {
+		// ignore bots
+		if message.author.bot {
+			return;
+		}
+
+		// ignore messages if they have no content and no attachments (usually embeds only)
+		if message.content.is_empty() && message.attachments.is_empty() {
+			return;
+		}
+
+		if message.guild_id.is_none() {
+			self.handle_dm_message(ctx, message, true).await;
+		} else {
+			self.handle_guild_message(ctx, message).await;
+		}
+	}
+
+	/// Handle a press of the "talk to a human" button on an FAQ answer,
+	/// by opening a ticket with the message that was answered.
+	pub async fn handle_component(&self, ctx: Context, interaction: ComponentInteraction) {
+		let Some(message_id) = interaction
+			.data
+			.custom_id
+			.strip_prefix(faq::OPEN_TICKET_BUTTON_PREFIX)
+		else {
+			return;
+		};
+		let Ok(message_id) = message_id.parse::<u64>() else {
+			return;
+		};
+		if interaction.guild_id.is_some() {
+			return;
+		}
+
+		// remove the button, so it can't be pressed twice
+		if let Err(e) = interaction
+			.create_response(
+				&ctx,
+				CreateInteractionResponse::UpdateMessage(
+					CreateInteractionResponseMessage::new().components(vec![]),
+				),
+			)
+			.await
+		{
+			warn!("failed to acknowledge dm support button: {}", e);
+		}
+
+		match interaction
+			.channel_id
+			.message(&ctx, MessageId::new(message_id))
+			.await
+		{
+			Ok(message) => self.handle_dm_message(ctx, message, false).await,
+			Err(e) => warn!("failed to fetch message answered from the faq: {}", e),
+		}
+	}
+
+	async fn handle_dm_message(&self, ctx: Context, message: Message, check_faq: bool) {
+		let Some(channel) = self.get_or_create_channel(&ctx, &message, check_faq).await else {
+			return;
+		};
+		let hook = self.get_webhook(&ctx, &channel.id).await;
+
+		let mut webhook_execute = ExecuteWebhook::default()
+			.avatar_url(message.author.face())
+			.username(
+				message
+					.author
+					.global_name
+					.as_ref()
+					.unwrap_or(&message.author.name)
+					.to_string(),
+			);
+
+		if !message.attachments.is_empty() {
+			let mut attachments = Vec::new();
+			for attachment in message.attachments.iter() {
+				attachments.push(
+					CreateAttachment::url(&ctx, attachment.url.as_str())
+						.await
+						.expect("failed to handle message attachments"),
+				);
+			}
+			webhook_execute = webhook_execute.files(attachments);
+		}
+
+		let resp = hook
+			.execute(
+				&ctx,
+				true,
+				webhook_execute
+					.content(message.content.clone())
+					.allowed_mentions(CreateAllowedMentions::default()),
+			)
+			.await;
+
+		if let Err(e) = resp {
+			warn!("failed to send message to webhook: {:?}", e);
+			let _ = message
+				.reply(ctx, format!("failed to send message: {}", e))
+				.await;
+			return;
+		}
+
+		// let whoever claimed the ticket know there's something to answer
+		match tickets::get_state(channel.id).await.map(|s| s.assignee) {
+			Ok(Some(assignee)) => {
+				let _ = channel
+					.send_message(
+						&ctx,
+						CreateMessage::default()
+							.content(format!("<@{}>: new reply", assignee))
+							.allowed_mentions(CreateAllowedMentions::default().users([assignee])),
+					)
+					.await;
+			}
+			Ok(None) => {}
+			Err(e) => warn!("failed to fetch ticket assignee: {}", e),
+		}
+	}
+
+	async fn handle_guild_message(&self, ctx: Context, message: Message) {
+		let config = scripty_config::get_config();
+		if message.guild_id != Some(GuildId::new(config.dm_support.guild_id)) {
+			return;
+		}
+		let message_channel = message
+			.channel(&ctx)
+			.await
+			.expect("failed to get message channel")
+			.guild()
+			.expect("should be in guild");
+
+		let Some(user_id) = self.ticket_user(&ctx, &message_channel).await else {
+			return;
+		};
+
+		// staff commands aren't meant for the user
+		if message.content.starts_with(COMMAND_PREFIX) {
+			return;
+		}
+
+		let state = match tickets::get_state(message_channel.id).await {
+			Ok(state) => state,
+			Err(e) => {
+				warn!("failed to fetch ticket state: {}", e);
+				TicketState::default()
+			}
+		};
+
+		// only whoever claimed the ticket may reply to it
+		if let Some(assignee) = state.assignee.filter(|a| *a != message.author.id) {
+			let _ = message_channel
+				.send_message(
+					&ctx,
+					CreateMessage::default()
+						.content(format!(
+							"Not sent: this ticket is claimed by <@{}>. Use `{}unassign` to \
+							 release it first.",
+							assignee, COMMAND_PREFIX
+						))
+						.allowed_mentions(CreateAllowedMentions::default()),
+				)
+				.await;
+			return;
+		}
+
+		// staff can also stay anonymous for a single message
+		let (content, anonymous) = match message.content.strip_prefix(ANONYMOUS_PREFIX) {
+			Some(content) => (content.to_string(), true),
+			None => (message.content.to_string(), state.anonymous),
+		};
+
+		let mut embed_builder = CreateEmbed::default();
+
+		match message.attachments.len().cmp(&1) {
+			Ordering::Less => {}
+			Ordering::Equal => {
+				let attachment = message
+					.attachments
+					.first()
+					.expect("asserted one element already exists");
+				if message_channel.is_nsfw() {
+					embed_builder = embed_builder.field("Attached", attachment.url.as_str(), true);
+				} else {
+					embed_builder = embed_builder.image(attachment.url.as_str());
+				}
+			}
+			Ordering::Greater => {
+				for attached_file in message.attachments.iter() {
+					embed_builder =
+						embed_builder.field("Attached", attached_file.url.as_str(), true);
+				}
+			}
+		}
+
+		let author = if anonymous {
+			let avatar = ctx.cache.current_user().face();
+			CreateEmbedAuthor::new(ANONYMOUS_NAME).icon_url(avatar)
+		} else {
+			CreateEmbedAuthor::new(message.author.name.clone()).icon_url(message.author.face())
+		};
+		embed_builder = embed_builder
+			.author(author)
+			.title("Support Response")
+			.description(content);
+
+		let resp = {
+			let user = match user_id.to_user(&ctx).await {
+				Ok(user) => user,
+				Err(e) => {
+					warn!("failed to get user from user id: {}", e);
+					return;
+				}
+			};
+
+			user.direct_message(&ctx, CreateMessage::default().embed(embed_builder))
+				.await
+		};
+
+		if let Err(e) = resp {
+			let _ = message_channel
+				.send_message(
+					&ctx,
+					CreateMessage::default().content(format!("Couldn't send message: {}", e)),
+				)
+				.await;
+		}
+	}
+
+	/// Returns None if the user may not open a new ticket,
+	/// or if `check_faq` is set and their message was answered from the FAQ instead.
+	async fn get_or_create_channel(
+		&self,
+		ctx: &Context,
+		message: &Message,
+		check_faq: bool,
+	) -> Option<GuildChannel> {
+		let user = &message.author;
+		let config = scripty_config::get_config();
+		let category = get_forwarding_category(ctx).await;
+		let guild_id = GuildId::new(config.dm_support.guild_id);
+
+		let user_id_str = user.id.to_string();
+
+		let channel = {
+			let channels = ctx
+				.cache
+				.guild_channels(guild_id)
+				.expect("failed to get guild channels");
+			channels
+				.iter()
+				.find_map(|(_, c)| {
+					if c.parent_id == Some(category.id) && c.name == user_id_str {
+						Some(c)
+					} else {
+						None
+					}
+				})
+				.cloned()
+		};
+
+		if let Some(channel) = channel {
+			return Some(channel);
+		}
+
+		if !self.may_open_ticket(ctx, message).await {
+			return None;
+		}
+		if check_faq && self.answer_faq(ctx, message).await {
+			return None;
+		}
+
+		let channel = guild_id
+			.create_channel(
+				&ctx,
+				CreateChannel::new(user_id_str)
+					.category(category.id)
+					.kind(ChannelType::Text),
+			)
+			.await
+			.expect("failed to create channel");
+
+		let hook = channel
+			.create_webhook(
+				ctx,
+				CreateWebhook::new(user.tag()).avatar(
+					&CreateAttachment::url(ctx, user.face().as_str())
+						.await
+						.expect("failed to handle message attachments"),
+				),
+			)
+			.await
+			.expect("failed to create webhook");
+		self.webhook_cache.insert(channel.id, hook);
+
+		if let Err(e) = tickets::get_or_open_ticket(user.id, channel.id).await {
+			error!("failed to record new ticket: {}", e);
+		}
+
+		if let Err(e) = self.handle_opening(ctx, user).await {
+			warn!("failed to handle opening: {}", e);
+			channel
+				.send_message(
+					ctx,
+					CreateMessage::default().content(format!("failed to handle opening: {}", e)),
+				)
+				.await
+				.expect("failed to send message");
+		}
+
+		Some(channel)
+	}
+
+	/// Check whether the author of `message` may open a new ticket, and tell them if not.
+	async fn may_open_ticket(&self, ctx: &Context, message: &Message) -> bool {
+		let user_id = message.author.id;
+		let Some(refusal) = limits::get_refusal(user_id).await else {
+			return true;
+		};
+
+		let recently_notified = self
+			.refusal_notice_cache
+			.get(&user_id)
+			.map_or(false, |t| t.elapsed() < REFUSAL_NOTICE_INTERVAL);
+		if recently_notified {
+			return false;
+		}
+		self.refusal_notice_cache.insert(user_id, Instant::now());
+
+		let language = scripty_i18n::get_resolved_language(user_id.get(), None).await;
+		let _ = message
+			.channel_id
+			.send_message(
+				ctx,
+				CreateMessage::default().embed(
+					CreateEmbed::default()
+						.title(format_message!(language, "dm-support-refused-title"))
+						.description(refusal.describe(&language)),
+				),
+			)
+			.await;
+
+		false
+	}
+
+	/// If `message` looks like a common question, answer it, and return true.
+	///
+	/// Only one message per user is answered in a while,
+	/// so anyone who needs more help reaches a human by just sending another message.
+	async fn answer_faq(&self, ctx: &Context, message: &Message) -> bool {
+		let user_id = message.author.id;
+		let recently_answered = self
+			.faq_cache
+			.get(&user_id)
+			.map_or(false, |t| t.elapsed() < FAQ_INTERVAL);
+		if recently_answered {
+			return false;
+		}
+		let Some(entry) = faq::find_entry(&message.content) else {
+			return false;
+		};
+
+		let language = scripty_i18n::get_resolved_language(user_id.get(), None).await;
+		let Some((answer, _)) =
+			scripty_i18n::get_formatted_message(&language, &entry.answer, None, None, false)
+		else {
+			warn!(answer = %entry.answer, "faq answer has no translation");
+			return false;
+		};
+
+		let embed = CreateEmbed::default()
+			.title(format_message!(language, "dm-support-faq-title"))
+			.description(answer)
+			.footer(CreateEmbedFooter::new(format_message!(
+				language,
+				"dm-support-faq-footer"
+			)));
+		let button = CreateButton::new(format!("{}{}", faq::OPEN_TICKET_BUTTON_PREFIX, message.id))
+			.style(ButtonStyle::Primary)
+			.label(format_message!(language, "dm-support-faq-talk-to-human"));
+
+		let res = message
+			.channel_id
+			.send_message(
+				ctx,
+				CreateMessage::default()
+					.embed(embed)
+					.components(vec![CreateActionRow::Buttons(vec![button])]),
+			)
+			.await;
+		if let Err(e) = res {
+			// open a ticket instead, so the user isn't left without an answer
+			warn!("failed to send faq answer: {}", e);
+			return false;
+		}
+
+		// nothing older than the interval is checked, so don't keep it around
+		self.faq_cache.retain(|_, t| t.elapsed() < FAQ_INTERVAL);
+		self.faq_cache.insert(user_id, Instant::now());
+		true
+	}
+
+	async fn handle_opening(&self, ctx: &Context, user: &User) -> serenity::Result<()> {
+		user.direct_message(
+			ctx,
+			CreateMessage::default().embed(
+				CreateEmbed::default()
+					.title("DM Ticket Opened")
+					.description(
+						"You have opened a ticket. If you did this by accident, please type \
+						 `close`, and **WAIT FOR A STAFF MEMBER TO CLOSE IT**.",
+					),
+			),
+		)
+		.await
+		.map(|_| ())
+	}
+
+	async fn get_webhook(&self, ctx: &Context, channel: &ChannelId) -> Webhook {
+		let hook = self.webhook_cache.get(channel).map(|x| x.clone());
+		if let Some(hook) = hook {
+			return hook;
+		}
+
+		if let Some(hook) = channel
+			.webhooks(&ctx)
+			.await
+			.expect("error fetching hooks")
+			.pop()
+		{
+			return hook;
+		}
+
+		let hook = channel
+			.create_webhook(ctx, CreateWebhook::new("Scripty"))
+			.await
+			.expect("failed to create webhook");
+		self.webhook_cache.insert(*channel, hook.clone());
+		hook
+	}
+
+	pub async fn close_ticket(&self, ctx: &Context, channel: GuildChannel, closed_by: &User) {
+		let Some(user_id) = self.ticket_user(ctx, &channel).await else {
+			return;
+		};
+
+		// better to leave the ticket open than to lose the conversation
+		if let Err(e) = self.archive_ticket(ctx, &channel, user_id, closed_by).await {
+			error!("failed to archive ticket: {}", e);
+			let _ = channel
+				.say(
+					ctx,
+					format!("Failed to archive this ticket, so it was not closed: {}", e),
+				)
+				.await;
+			return;
+		}
+
+		{
+			let user = match user_id.to_user(&ctx).await {
+				Ok(user) => user,
+				Err(e) => {
+					warn!("failed to get user from user id: {}", e);
+					return;
+				}
+			};
+
+			let _ = user
+				.direct_message(
+					&ctx,
+					CreateMessage::default().embed(
+						CreateEmbed::default()
+							.title("Closed Support Ticket")
+							.description(
+								"This support ticket has now been closed. Thank you for using \
+								 Scripty's support system. If you require more assistance, simply \
+								 send another message here to reopen a new ticket.",
+							),
+					),
+				)
+				.await;
+		}
+
+		self.webhook_cache.remove(&channel.id);
+
+		let _ = channel.delete(ctx).await;
+	}
+
+	/// The user a ticket channel belongs to, or None if `channel` isn't a ticket.
+	pub async fn ticket_user(&self, ctx: &Context, channel: &GuildChannel) -> Option<UserId> {
+		let config = scripty_config::get_config();
+		if channel.guild_id != GuildId::new(config.dm_support.guild_id) {
+			return None;
+		}
+
+		let category = get_forwarding_category(ctx).await;
+		if channel.parent_id != Some(category.id) {
+			return None;
+		}
+
+		match channel.name.parse::<u64>() {
+			Ok(id) => Some(UserId::new(id)),
+			Err(e) => {
+				warn!("failed to parse user id from channel name: {:?}", e);
+				None
+			}
+		}
+	}
+
+	/// Block a user from opening new tickets, for `duration` or permanently.
+	pub async fn block_user(
+		&self,
+		user_id: UserId,
+		blocked_by: UserId,
+		reason: Option<String>,
+		duration: Option<Duration>,
+	) -> Result<(), Error> {
+		let expires_at = duration.map(|d| OffsetDateTime::now_utc() + d);
+		blocks::block_user(user_id, blocked_by, reason, expires_at).await?;
+		Ok(())
+	}
+
+	/// Let a user open tickets again. Returns true if they were blocked.
+	pub async fn unblock_user(&self, user_id: UserId) -> Result<bool, Error> {
+		self.refusal_notice_cache.remove(&user_id);
+		Ok(blocks::unblock_user(user_id).await?)
+	}
+
+	/// Assign the ticket in `channel`, which belongs to `user_id`, to `staff`,
+	/// unless someone else already has it. Returns whoever has the ticket afterwards.
+	pub async fn claim_ticket(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		user_id: UserId,
+		staff: UserId,
+	) -> Result<Option<UserId>, Error> {
+		tickets::get_or_open_ticket(user_id, channel.id).await?;
+		let assignee = tickets::claim_ticket(channel.id, staff).await?;
+		if assignee == Some(staff) {
+			refresh_topic(ctx, channel.id);
+		}
+		Ok(assignee)
+	}
+
+	/// Release the ticket in `channel`, so anyone can reply to it. Returns who had it, if anyone.
+	pub async fn unassign_ticket(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+	) -> Result<Option<UserId>, Error> {
+		let previous = tickets::unassign_ticket(channel.id).await?;
+		if previous.is_some() {
+			refresh_topic(ctx, channel.id);
+		}
+		Ok(previous)
+	}
+
+	/// Toggle whether staff replies in the ticket in `channel`, which belongs to `user_id`,
+	/// are attributed to "Scripty Support". Returns the new setting.
+	pub async fn toggle_anonymous(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		user_id: UserId,
+	) -> Result<bool, Error> {
+		tickets::get_or_open_ticket(user_id, channel.id).await?;
+		let anonymous = tickets::toggle_anonymous(channel.id).await?;
+		refresh_topic(ctx, channel.id);
+		Ok(anonymous)
+	}
+
+	/// Tag the ticket in `channel`, which belongs to `user_id`.
+	/// `tag` must already be normalized with `normalize_tag`.
+	pub async fn add_tag(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		user_id: UserId,
+		tag: &str,
+	) -> Result<AddTagResult, Error> {
+		tickets::get_or_open_ticket(user_id, channel.id).await?;
+		let tags = tickets::get_state(channel.id).await?.tags;
+		if tags.iter().any(|t| t == tag) {
+			return Ok(AddTagResult::AlreadyTagged);
+		}
+		if tags.len() >= MAX_TAGS {
+			return Ok(AddTagResult::TooManyTags(MAX_TAGS));
+		}
+
+		tickets::add_tag(channel.id, tag).await?;
+		refresh_topic(ctx, channel.id);
+		Ok(AddTagResult::Added)
+	}
+
+	/// Remove a tag from the ticket in `channel`. Returns false if it didn't have the tag.
+	/// `tag` must already be normalized with `normalize_tag`.
+	pub async fn remove_tag(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		tag: &str,
+	) -> Result<bool, Error> {
+		let removed = tickets::remove_tag(channel.id, tag).await?;
+		if removed {
+			refresh_topic(ctx, channel.id);
+		}
+		Ok(removed)
+	}
+
+	/// Set the priority of the ticket in `channel`, which belongs to `user_id`.
+	pub async fn set_priority(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		user_id: UserId,
+		priority: Priority,
+	) -> Result<(), Error> {
+		tickets::get_or_open_ticket(user_id, channel.id).await?;
+		tickets::set_priority(channel.id, priority).await?;
+		refresh_topic(ctx, channel.id);
+		Ok(())
+	}
+
+	/// Open tickets, optionally only those tagged `tag`, most urgent and then oldest first.
+	///
+	/// Tickets whose channel was deleted by hand rather than closed are left out.
+	pub async fn list_tickets(
+		&self,
+		ctx: &Context,
+		tag: Option<&str>,
+	) -> Result<Vec<OpenTicket>, Error> {
+		let tickets = tickets::list_open_tickets(tag).await?;
+
+		let guild_id = GuildId::new(scripty_config::get_config().dm_support.guild_id);
+		let Some(channels) = ctx.cache.guild_channels(guild_id) else {
+			return Ok(tickets);
+		};
+		Ok(tickets
+			.into_iter()
+			.filter(|t| channels.contains_key(&t.channel_id))
+			.collect())
+	}
+
+	/// Save the conversation in a ticket, and post its transcript to the log channel if one is set.
+	async fn archive_ticket(
+		&self,
+		ctx: &Context,
+		channel: &GuildChannel,
+		user_id: UserId,
+		closed_by: &User,
+	) -> Result<(), Error> {
+		let ticket = tickets::get_or_open_ticket(user_id, channel.id).await?;
+		let messages = transcript::fetch_history(ctx, channel.id).await?;
+		tickets::archive_ticket(ticket.id, closed_by.id, &messages).await?;
+
+		let Some(log_channel) = scripty_config::get_config().dm_support.log_channel else {
+			return Ok(());
+		};
+		let user_messages = messages.iter().filter(|m| !m.from_staff).count();
+		let embed = CreateEmbed::default()
+			.title(format!("Ticket #{} closed", ticket.id))
+			.field("User", format!("<@{}> ({})", user_id, user_id), true)
+			.field("Closed by", format!("<@{}>", closed_by.id), true)
+			.field(
+				"Opened",
+				format!("<t:{}:f>", ticket.opened_at.unix_timestamp()),
+				true,
+			)
+			.field(
+				"Messages",
+				format!(
+					"{} from the user, {} from staff",
+					user_messages,
+					messages.len() - user_messages
+				),
+				true,
+			);
+		ChannelId::new(log_channel)
+			.send_message(
+				ctx,
+				CreateMessage::default()
+					.embed(embed)
+					.add_file(CreateAttachment::bytes(
+						transcript::render(&messages).into_bytes(),
+						format!("ticket-{}.txt", ticket.id),
+					))
+					.allowed_mentions(CreateAllowedMentions::default()),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+/// Show a ticket's state in its channel topic.
+fn refresh_topic(ctx: &Context, channel_id: ChannelId) {
+	// topic edits are heavily rate limited, so don't make the caller wait on them
+	let ctx = ctx.clone();
+	tokio::spawn(async move {
+		let state = match tickets::get_state(channel_id).await {
+			Ok(state) => state,
+			Err(e) => {
+				warn!("failed to fetch ticket state: {}", e);
+				return;
+			}
+		};
+		let topic = render_topic(&state);
+		if let Err(e) = channel_id.edit(&ctx, EditChannel::new().topic(topic)).await {
+			warn!("failed to update ticket topic: {}", e);
+		}
+	});
+}
+
+fn render_topic(state: &TicketState) -> String {
+	let mut parts = vec![
+		format!("Priority: {}", state.priority),
+		match state.assignee {
+			Some(id) => format!("Claimed by <@{}>", id),
+			None => "Unclaimed".to_string(),
+		},
+	];
+	if !state.tags.is_empty() {
+		parts.push(format!("Tags: {}", state.tags.join(", ")));
+	}
+	if state.anonymous {
+		parts.push("Anonymous replies".to_string());
+	}
+	parts.join(" | ")
+}
+
+/// Tags are lowercase, and 1 to `MAX_TAG_LEN` letters, numbers and dashes.
+/// Returns None if `tag` isn't a valid tag.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+	let tag = tag.trim().to_lowercase();
+	let valid = !tag.is_empty()
+		&& tag.chars().count() <= MAX_TAG_LEN
+		&& tag.chars().all(|c| c.is_alphanumeric() || c == '-');
+	valid.then_some(tag)
+}
+
+async fn get_forwarding_category(ctx: &Context) -> GuildChannel {
+	ChannelId::new(scripty_config::get_config().dm_support.forwarding_category)
+		.to_channel(&ctx)
+		.await
+		.expect("failed to get forwarding category")
+		.guild()
+		.expect("forwarding category is not a guild channel")
+}
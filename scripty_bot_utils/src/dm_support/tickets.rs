@@ -0,0 +1,298 @@
+//! Ticket records, stored in Postgres.
+
+use std::fmt;
+
+use scripty_utils::to_primitive;
+use serenity::model::id::{ChannelId, UserId};
+use time::OffsetDateTime;
+
+use super::transcript::ArchivedMessage;
+
+#[derive(Debug, Clone)]
+pub struct Ticket {
+	pub id:        i64,
+	pub opened_at: OffsetDateTime,
+}
+
+/// Settings staff can change on an open ticket.
+#[derive(Debug, Clone, Default)]
+pub struct TicketState {
+	pub assignee:  Option<UserId>,
+	/// Whether staff replies are attributed to "Scripty Support" rather than whoever sent them.
+	pub anonymous: bool,
+	pub tags:      Vec<String>,
+	pub priority:  Priority,
+}
+
+/// An open ticket, as listed by `list_open_tickets`.
+#[derive(Debug, Clone)]
+pub struct OpenTicket {
+	pub channel_id: ChannelId,
+	pub opened_at:  OffsetDateTime,
+	pub state:      TicketState,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+	Low,
+	#[default]
+	Normal,
+	High,
+	Urgent,
+}
+
+impl Priority {
+	/// Parse a priority from its name, ie `high`.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name.to_ascii_lowercase().as_str() {
+			"low" => Some(Self::Low),
+			"normal" => Some(Self::Normal),
+			"high" => Some(Self::High),
+			"urgent" => Some(Self::Urgent),
+			_ => None,
+		}
+	}
+
+	fn from_db(value: i16) -> Self {
+		match value {
+			i16::MIN..=0 => Self::Low,
+			1 => Self::Normal,
+			2 => Self::High,
+			3..=i16::MAX => Self::Urgent,
+		}
+	}
+
+	fn to_db(self) -> i16 {
+		self as i16
+	}
+}
+
+impl fmt::Display for Priority {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Low => "low",
+			Self::Normal => "normal",
+			Self::High => "high",
+			Self::Urgent => "urgent",
+		})
+	}
+}
+
+/// Record a newly opened ticket in `channel_id`.
+///
+/// If the channel already has a ticket (ie one opened before tickets were stored), returns that.
+pub async fn get_or_open_ticket(
+	user_id: UserId,
+	channel_id: ChannelId,
+) -> Result<Ticket, sqlx::Error> {
+	let row = sqlx::query!(
+		"INSERT INTO dm_support_tickets (user_id, channel_id) VALUES ($1, $2) ON CONFLICT \
+		 (channel_id) DO UPDATE SET channel_id = EXCLUDED.channel_id RETURNING id, opened_at",
+		scripty_utils::hash_user_id(user_id.get()),
+		channel_id.get() as i64
+	)
+	.fetch_one(scripty_db::get_db())
+	.await?;
+
+	Ok(Ticket {
+		id:        row.id,
+		opened_at: row.opened_at.assume_utc(),
+	})
+}
+
+/// A user's ticket history, for rate limiting.
+#[derive(Debug, Clone)]
+pub struct RecentTickets {
+	pub last_closed_at: Option<OffsetDateTime>,
+	/// How many of their tickets haven't been closed.
+	pub open:           i64,
+}
+
+pub async fn recent_tickets(user_id: UserId) -> Result<RecentTickets, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT MAX(closed_at) AS last_closed_at, COUNT(*) FILTER (WHERE closed_at IS NULL) AS \
+		 \"open!\" FROM dm_support_tickets WHERE user_id = $1",
+		scripty_utils::hash_user_id(user_id.get())
+	)
+	.fetch_one(scripty_db::get_db())
+	.await?;
+
+	Ok(RecentTickets {
+		last_closed_at: row.last_closed_at.map(|t| t.assume_utc()),
+		open:           row.open,
+	})
+}
+
+/// Mark a ticket as closed, and archive its messages.
+pub async fn archive_ticket(
+	ticket_id: i64,
+	closed_by: UserId,
+	messages: &[ArchivedMessage],
+) -> Result<(), sqlx::Error> {
+	let mut tx = scripty_db::get_db().begin().await?;
+
+	for msg in messages {
+		sqlx::query!(
+			"INSERT INTO dm_support_messages (ticket_id, from_staff, author_name, content, \
+			 attachments, sent_at) VALUES ($1, $2, $3, $4, $5, $6)",
+			ticket_id,
+			msg.from_staff,
+			msg.author_name,
+			msg.content,
+			msg.attachments.join("\n"),
+			to_primitive(msg.sent_at)
+		)
+		.execute(&mut *tx)
+		.await?;
+	}
+
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET closed_at = NOW(), closed_by = $2 WHERE id = $1",
+		ticket_id,
+		closed_by.get() as i64
+	)
+	.execute(&mut *tx)
+	.await?;
+
+	tx.commit().await
+}
+
+/// Assign the ticket in `channel_id` to `staff`, unless someone else already has it.
+///
+/// Returns whoever has the ticket afterwards.
+pub async fn claim_ticket(
+	channel_id: ChannelId,
+	staff: UserId,
+) -> Result<Option<UserId>, sqlx::Error> {
+	let db = scripty_db::get_db();
+	let claimed = sqlx::query!(
+		"UPDATE dm_support_tickets SET assignee = $2 WHERE channel_id = $1 AND (assignee IS NULL \
+		 OR assignee = $2) RETURNING assignee",
+		channel_id.get() as i64,
+		staff.get() as i64
+	)
+	.fetch_optional(db)
+	.await?;
+	if claimed.is_some() {
+		return Ok(Some(staff));
+	}
+
+	Ok(get_state(channel_id).await?.assignee)
+}
+
+/// Remove the assignee from the ticket in `channel_id`. Returns who had it, if anyone.
+pub async fn unassign_ticket(channel_id: ChannelId) -> Result<Option<UserId>, sqlx::Error> {
+	let previous = get_state(channel_id).await?.assignee;
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET assignee = NULL WHERE channel_id = $1",
+		channel_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(previous)
+}
+
+/// Toggle whether staff replies in the ticket in `channel_id` are anonymous.
+/// Returns the new setting.
+pub async fn toggle_anonymous(channel_id: ChannelId) -> Result<bool, sqlx::Error> {
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET anonymous = NOT anonymous WHERE channel_id = $1 RETURNING \
+		 anonymous",
+		channel_id.get() as i64
+	)
+	.fetch_one(scripty_db::get_db())
+	.await
+	.map(|row| row.anonymous)
+}
+
+/// Add a tag to the ticket in `channel_id`. Returns false if it already had it.
+pub async fn add_tag(channel_id: ChannelId, tag: &str) -> Result<bool, sqlx::Error> {
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET tags = array_append(tags, $2) WHERE channel_id = $1 AND \
+		 NOT ($2 = ANY(tags))",
+		channel_id.get() as i64,
+		tag
+	)
+	.execute(scripty_db::get_db())
+	.await
+	.map(|res| res.rows_affected() > 0)
+}
+
+/// Remove a tag from the ticket in `channel_id`. Returns false if it didn't have it.
+pub async fn remove_tag(channel_id: ChannelId, tag: &str) -> Result<bool, sqlx::Error> {
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET tags = array_remove(tags, $2) WHERE channel_id = $1 AND $2 \
+		 = ANY(tags)",
+		channel_id.get() as i64,
+		tag
+	)
+	.execute(scripty_db::get_db())
+	.await
+	.map(|res| res.rows_affected() > 0)
+}
+
+pub async fn set_priority(channel_id: ChannelId, priority: Priority) -> Result<(), sqlx::Error> {
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET priority = $2 WHERE channel_id = $1",
+		channel_id.get() as i64,
+		priority.to_db()
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(())
+}
+
+/// The current state of the ticket in `channel_id`. Tickets with no record have the defaults.
+pub async fn get_state(channel_id: ChannelId) -> Result<TicketState, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT assignee, anonymous, tags, priority FROM dm_support_tickets WHERE channel_id = $1",
+		channel_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.map_or_else(TicketState::default, |row| {
+		state_from_row(row.assignee, row.anonymous, row.tags, row.priority)
+	}))
+}
+
+/// Open tickets, optionally only those tagged `tag`, most urgent and then oldest first.
+pub async fn list_open_tickets(tag: Option<&str>) -> Result<Vec<OpenTicket>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT channel_id, opened_at, assignee, anonymous, tags, priority FROM \
+		 dm_support_tickets WHERE closed_at IS NULL AND ($1::TEXT IS NULL OR $1 = ANY(tags)) \
+		 ORDER BY priority DESC, opened_at",
+		tag
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.filter_map(|row| {
+			let channel_id = u64::try_from(row.channel_id).ok().filter(|id| *id != 0)?;
+			Some(OpenTicket {
+				channel_id: ChannelId::new(channel_id),
+				opened_at:  row.opened_at.assume_utc(),
+				state:      state_from_row(row.assignee, row.anonymous, row.tags, row.priority),
+			})
+		})
+		.collect())
+}
+
+fn state_from_row(
+	assignee: Option<i64>,
+	anonymous: bool,
+	tags: Vec<String>,
+	priority: i16,
+) -> TicketState {
+	TicketState {
+		assignee: assignee
+			.and_then(|id| u64::try_from(id).ok())
+			.filter(|id| *id != 0)
+			.map(UserId::new),
+		anonymous,
+		tags,
+		priority: Priority::from_db(priority),
+	}
+}
@@ -0,0 +1,83 @@
+//! Rebuilding a ticket's conversation from its channel history.
+
+use std::fmt::Write;
+
+use serenity::{
+	client::Context,
+	futures::StreamExt,
+	model::{channel::Message, id::ChannelId},
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// One message in a ticket, from either side.
+#[derive(Debug, Clone)]
+pub struct ArchivedMessage {
+	/// False if relayed from the user who opened the ticket.
+	pub from_staff:  bool,
+	pub author_name: String,
+	pub content:     String,
+	pub attachments: Vec<String>,
+	pub sent_at:     OffsetDateTime,
+}
+
+impl ArchivedMessage {
+	/// User messages are relayed by webhook, so anything else not sent by a bot is staff.
+	/// Returns None for bot messages, like relay errors.
+	fn from_message(msg: &Message) -> Option<Self> {
+		let from_staff = msg.webhook_id.is_none();
+		if from_staff && msg.author.bot {
+			return None;
+		}
+
+		Some(Self {
+			from_staff,
+			author_name: msg
+				.author
+				.global_name
+				.as_deref()
+				.unwrap_or(&msg.author.name)
+				.to_string(),
+			content: msg.content.to_string(),
+			attachments: msg.attachments.iter().map(|a| a.url.to_string()).collect(),
+			sent_at: OffsetDateTime::from_unix_timestamp(msg.timestamp.unix_timestamp()).ok()?,
+		})
+	}
+}
+
+/// Fetch every message in a ticket channel, oldest first.
+pub async fn fetch_history(
+	ctx: &Context,
+	channel_id: ChannelId,
+) -> serenity::Result<Vec<ArchivedMessage>> {
+	let mut messages = Vec::new();
+	let mut iter = channel_id.messages_iter(ctx.http.clone()).boxed();
+	while let Some(msg) = iter.next().await {
+		if let Some(msg) = ArchivedMessage::from_message(&msg?) {
+			messages.push(msg);
+		}
+	}
+	// the API returns newest first
+	messages.reverse();
+	Ok(messages)
+}
+
+/// Render messages as a plain text transcript, one line per message.
+pub fn render(messages: &[ArchivedMessage]) -> String {
+	let mut out = String::new();
+	for msg in messages {
+		let side = if msg.from_staff { "staff" } else { "user" };
+		let sent_at = msg
+			.sent_at
+			.format(&Rfc3339)
+			.unwrap_or_else(|_| msg.sent_at.to_string());
+		let _ = writeln!(
+			out,
+			"[{}] {} ({}): {}",
+			sent_at, msg.author_name, side, msg.content
+		);
+		for attachment in &msg.attachments {
+			let _ = writeln!(out, "    attachment: {}", attachment);
+		}
+	}
+	out
+}
@@ -71,11 +71,7 @@ async fn _check_block(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Erro
 			.await?
 		{
 			trace!(%ctx_id, "guild is blocked");
-			let resolved_language = scripty_i18n::get_resolved_language(
-				ctx.author().id.get(),
-				ctx.guild_id().map(|g| g.get()),
-			)
-			.await;
+			let resolved_language = crate::get_ctx_language(ctx).await;
 
 			let reason = if reason.is_empty() {
 				format_message!(resolved_language, "blocked-entity-no-reason-given")
@@ -97,6 +93,10 @@ async fn _check_block(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Erro
 		}
 	}
 
+	// data stored under a legacy hash has to be moved before looking anything up
+	if let Err(e) = scripty_utils::rehash_user_id(ctx.author().id.get()).await {
+		error!(%ctx_id, "failed to rehash user ID: {}", e);
+	}
 	let hashed_user_id = scripty_utils::hash_user_id(ctx.author().id.get());
 	if let Some(reason) = redis
 		.get::<_, Option<String>>(format!(
@@ -106,11 +106,7 @@ async fn _check_block(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Erro
 		.await?
 	{
 		trace!(%ctx_id, "user is blocked");
-		let resolved_language = scripty_i18n::get_resolved_language(
-			ctx.author().id.get(),
-			ctx.guild_id().map(|g| g.get()),
-		)
-		.await;
+		let resolved_language = crate::get_ctx_language(ctx).await;
 
 		let reason = if reason.is_empty() {
 			format_message!(resolved_language, "blocked-entity-no-reason-given")
@@ -142,8 +138,17 @@ pub async fn add_blocked_user(user_id: UserId, reason: Option<String>) -> Result
 	let hashed_user_id = scripty_utils::hash_user_id(user_id.get());
 	let current_timestamp = OffsetDateTime::now_utc();
 
+	// blocked_users references users, so make sure they exist first
+	sqlx::query!(
+		"INSERT INTO users (user_id) VALUES ($1) ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING",
+		hashed_user_id
+	)
+	.execute(db)
+	.await?;
+
 	sqlx::query!(
-		"INSERT INTO blocked_users (user_id, reason, blocked_since) VALUES ($1, $2, $3)",
+		"INSERT INTO blocked_users (user_id, reason, blocked_since) VALUES ($1, $2, $3) ON \
+		 CONFLICT (user_id) DO UPDATE SET reason = $2",
 		hashed_user_id,
 		reason,
 		current_timestamp
@@ -173,7 +178,8 @@ pub async fn add_blocked_guild(guild_id: GuildId, reason: Option<String>) -> Res
 	let current_timestamp = OffsetDateTime::now_utc();
 
 	sqlx::query!(
-		"INSERT INTO blocked_guilds (guild_id, reason, blocked_since) VALUES ($1, $2, $3)",
+		"INSERT INTO blocked_guilds (guild_id, reason, blocked_since) VALUES ($1, $2, $3) ON \
+		 CONFLICT (guild_id) DO UPDATE SET reason = $2",
 		signed_guild_id,
 		reason,
 		current_timestamp
@@ -191,6 +197,103 @@ pub async fn add_blocked_guild(guild_id: GuildId, reason: Option<String>) -> Res
 	Ok(())
 }
 
+/// Removes a blocked user from the database and Redis.
+///
+/// Returns false if the user wasn't blocked.
+pub async fn remove_blocked_user(user_id: UserId) -> Result<bool, Error> {
+	let db = scripty_db::get_db();
+	let mut redis = scripty_redis::get_pool().get().await?;
+
+	let hashed_user_id = scripty_utils::hash_user_id(user_id.get());
+
+	let res = sqlx::query!(
+		"DELETE FROM blocked_users WHERE user_id = $1",
+		hashed_user_id
+	)
+	.execute(db)
+	.await?;
+
+	redis
+		.del::<_, ()>(format!(
+			"user:{{{}}}:blocked",
+			scripty_utils::vec_to_hex(&hashed_user_id)
+		))
+		.await?;
+
+	Ok(res.rows_affected() > 0)
+}
+
+/// Removes a blocked guild from the database and Redis.
+///
+/// Returns false if the guild wasn't blocked.
+pub async fn remove_blocked_guild(guild_id: GuildId) -> Result<bool, Error> {
+	let db = scripty_db::get_db();
+	let mut redis = scripty_redis::get_pool().get().await?;
+
+	let res = sqlx::query!(
+		"DELETE FROM blocked_guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	redis
+		.del::<_, ()>(format!("guild:{{{}}}:blocked", guild_id))
+		.await?;
+
+	Ok(res.rows_affected() > 0)
+}
+
+/// A blocked user or guild, as stored in the database.
+pub struct BlockedEntity {
+	/// The guild ID, or the hex-encoded hash of the user ID, since user IDs are never stored.
+	pub id:            String,
+	pub reason:        Option<String>,
+	pub blocked_since: Option<OffsetDateTime>,
+}
+
+/// Lists all blocked users, most recently blocked first.
+pub async fn list_blocked_users() -> Result<Vec<BlockedEntity>, Error> {
+	Ok(sqlx::query!(
+		"SELECT user_id, reason, blocked_since FROM blocked_users ORDER BY blocked_since DESC \
+		 NULLS LAST"
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?
+	.into_iter()
+	.map(|row| BlockedEntity {
+		id:            scripty_utils::vec_to_hex(&row.user_id),
+		reason:        row.reason,
+		blocked_since: row.blocked_since,
+	})
+	.collect())
+}
+
+/// Lists all blocked guilds, most recently blocked first.
+pub async fn list_blocked_guilds() -> Result<Vec<BlockedEntity>, Error> {
+	Ok(sqlx::query!(
+		"SELECT guild_id, reason, blocked_since FROM blocked_guilds ORDER BY blocked_since DESC \
+		 NULLS LAST"
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?
+	.into_iter()
+	.map(|row| BlockedEntity {
+		id:            row.guild_id.to_string(),
+		reason:        row.reason,
+		blocked_since: row.blocked_since,
+	})
+	.collect())
+}
+
+/// Checks Redis to see if a guild is blocked.
+pub async fn is_guild_blocked(guild_id: GuildId) -> Result<bool, Error> {
+	let mut redis = scripty_redis::get_pool().get().await?;
+	Ok(redis
+		.exists(format!("guild:{{{}}}:blocked", guild_id))
+		.await?)
+}
+
 #[inline]
 pub fn check_block(ctx: poise::Context<'_, Data, Error>) -> BoxFuture<Result<bool, Error>> {
 	Box::pin(_check_block(ctx))
@@ -138,6 +138,24 @@ impl Error {
 		}
 	}
 
+	/// Short name of this kind of error, used as a metric label.
+	pub fn kind(&self) -> &'static str {
+		match &self.err {
+			ErrorEnum::Serenity(_) => "serenity",
+			ErrorEnum::InvalidChannelType { .. } => "invalid_channel_type",
+			ErrorEnum::Db(_) => "db",
+			ErrorEnum::ExpectedGuild => "expected_guild",
+			ErrorEnum::Join(_) => "join",
+			ErrorEnum::ManualError => "manual",
+			ErrorEnum::Redis(_) => "redis",
+			ErrorEnum::RedisPool(_) => "redis_pool",
+			ErrorEnum::VoiceMessageDecode(_) => "voice_message_decode",
+			ErrorEnum::Transcription(_) => "transcription",
+			ErrorEnum::ExpectedPremiumValue => "expected_premium_value",
+			ErrorEnum::Custom(_) => "custom",
+		}
+	}
+
 	#[inline]
 	pub fn backtrace(&mut self) -> &Backtrace {
 		self.bt.resolve();
@@ -256,6 +274,19 @@ impl From<scripty_audio_handler::Error> for Error {
 			scripty_audio_handler::ErrorKind::Join(e) => Self::join(e),
 			scripty_audio_handler::ErrorKind::Database(e) => Self::db(e),
 			scripty_audio_handler::ErrorKind::Serenity(e) => Self::serenity(e),
+			scripty_audio_handler::ErrorKind::SessionLimit { limit } => {
+				Self::custom(format!("voice session limit of {} reached", limit))
+			}
+			scripty_audio_handler::ErrorKind::AfkChannel => {
+				Self::custom("the AFK channel is never transcribed".to_string())
+			}
+			scripty_audio_handler::ErrorKind::AgeRestrictedRefused => {
+				Self::custom("age-restricted voice chats aren't transcribed here".to_string())
+			}
+			scripty_audio_handler::ErrorKind::AgeRestrictedTarget => Self::custom(
+				"age-restricted voice chats can only be transcribed into age-restricted channels"
+					.to_string(),
+			),
 		};
 		err.bt = e.backtrace;
 		err
@@ -292,6 +323,20 @@ impl From<scripty_redis::PoolError> for Error {
 	}
 }
 
+impl From<scripty_redis::TransactionError> for Error {
+	#[inline]
+	fn from(e: scripty_redis::TransactionError) -> Self {
+		let err = match e {
+			scripty_redis::TransactionError::Deadpool(e) => ErrorEnum::RedisPool(e),
+			scripty_redis::TransactionError::Redis(e) => ErrorEnum::Redis(e),
+		};
+		Self {
+			err,
+			bt: Backtrace::new(),
+		}
+	}
+}
+
 impl From<String> for Error {
 	#[inline]
 	fn from(e: String) -> Self {
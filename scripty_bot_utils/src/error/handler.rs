@@ -8,7 +8,12 @@ use serenity::{
 };
 
 use crate::{
-	error::{error_type::ErrorEnum, log_error_message, message::send_err_msg},
+	error::{
+		error_type::ErrorEnum,
+		log_error_message,
+		message::send_err_msg,
+		report::new_error_id,
+	},
 	Data,
 	Error,
 };
@@ -19,6 +24,9 @@ pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
 	match error {
 		FrameworkError::Setup { error, .. } => panic!("error during bot init: {}", error),
 		FrameworkError::Command { error, ctx, .. } => {
+			scripty_metrics::measure_command_end(ctx.id(), &ctx.command().qualified_name, false);
+			scripty_metrics::record_error("scripty_commands", error.kind());
+
 			if !error.should_handle() {
 				return;
 			}
@@ -56,14 +64,21 @@ pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
 					.await;
 				}
 
-				ref e => {
+				_ => {
+					// the full error is only shown to owners, via `admin error <id>`
+					let error_id = new_error_id();
+					let resolved_language = crate::get_ctx_language(ctx).await;
 					send_err_msg(
 						ctx,
-						format!("An error happened while processing {}", cmd_name),
-						format!(
-							"```\n{:?}\n```\nThis has been automatically reported. Please do not \
-							 attempt to repeatedly use this command.",
-							e
+						format_message!(
+							resolved_language,
+							"general-error-command-process-title",
+							command: cmd_name.to_string()
+						),
+						format_message!(
+							resolved_language,
+							"general-error-command-process-reference",
+							errorId: error_id.clone()
 						),
 					)
 					.await;
@@ -72,6 +87,7 @@ pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
 						&ctx,
 						error,
 						Some(format!("running command {}", ctx.command().name)),
+						&error_id,
 					)
 					.await;
 				}
@@ -3,8 +3,13 @@ use serenity::{
 	builder::{CreateAttachment, CreateEmbed, CreateEmbedAuthor, CreateMessage, ExecuteWebhook},
 	model::webhook::Webhook,
 };
+use time::OffsetDateTime;
 
-use crate::{Context, Error};
+use crate::{
+	error::report::{store_error_report, ErrorReport},
+	Context,
+	Error,
+};
 
 pub async fn send_err_msg(
 	ctx: Context<'_>,
@@ -29,23 +34,53 @@ pub async fn send_err_msg(
 	}
 }
 
+/// Report an unexpected error to the error webhook, Sentry, and the logs,
+/// and store it under `error_id` so it can be looked up later.
 pub async fn log_error_message(
 	ctx: &Context<'_>,
 	mut err: Error,
 	invocation_context: Option<String>,
+	error_id: &str,
 ) {
 	// build embed
 	let mut e = CreateEmbed::default();
 	// build message
 	let mut m = ExecuteWebhook::default();
 
-	if let Some(inv_ctx) = invocation_context {
-		e = e.title(format!("Error while {}", inv_ctx));
-	} else {
-		e = e.title("Error while doing something");
-	}
+	let inv_ctx = invocation_context.unwrap_or_else(|| "doing something".to_string());
+	e = e.title(format!("Error while {}", inv_ctx));
+	e = e.field("Error ID", error_id, false);
 
 	let fmt_bt = format!("{:#?}", err.backtrace());
+
+	let report = ErrorReport {
+		id:         error_id.to_string(),
+		context:    inv_ctx,
+		display:    err.to_string(),
+		debug:      format!("{:?}", err),
+		backtrace:  fmt_bt.clone(),
+		guild_id:   ctx.guild_id().map(|g| g.get()),
+		channel_id: Some(ctx.channel_id().get()),
+		timestamp:  OffsetDateTime::now_utc().unix_timestamp(),
+	};
+	if let Err(e) = store_error_report(&report).await {
+		error!(%error_id, "failed to store error report: {}", e);
+	}
+
+	// report to sentry before the backtrace is moved into the embed
+	sentry::with_scope(
+		|scope| {
+			if let Some(guild_id) = ctx.guild_id() {
+				scope.set_tag("guild_id", guild_id);
+			}
+			scope.set_tag("shard_id", ctx.serenity_context().shard_id);
+			scope.set_tag("command", &ctx.command().qualified_name);
+			scope.set_tag("error_id", error_id);
+			scope.set_extra("backtrace", fmt_bt.as_str().into());
+		},
+		|| sentry::capture_message(&err.to_string(), sentry::Level::Error),
+	);
+
 	if fmt_bt.len() > 2048 {
 		e = e.field("Backtrace", "See attached file", false);
 		m = m.add_file(CreateAttachment::bytes(
@@ -103,5 +138,5 @@ pub async fn log_error_message(
 		error!("failed to log error to discord: {}", e);
 	}
 
-	error!(?guild_id, ?guild_name, %channel_id, %author_id, %author_name, "error while doing something: {}", err);
+	error!(%error_id, ?guild_id, ?guild_name, %channel_id, %author_id, %author_name, "error while doing something: {}", err);
 }
@@ -1,5 +1,8 @@
 pub(super) mod error_type;
 pub mod handler;
 mod message;
+mod panic_report;
+pub mod report;
 
 pub use message::log_error_message;
+pub use panic_report::report_panics;
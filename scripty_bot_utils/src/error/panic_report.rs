@@ -0,0 +1,60 @@
+use scripty_utils::panic::{take_panic_reports, PanicReport};
+use serenity::{
+	builder::{CreateAttachment, CreateEmbed, ExecuteWebhook},
+	client::Context,
+	model::webhook::Webhook,
+};
+
+/// Forward panics captured by the panic hook to the error webhook, until the hook goes away.
+///
+/// Does nothing if the panic hook isn't installed, or another reporter is already running.
+pub async fn report_panics(ctx: Context) {
+	let Some(mut reports) = take_panic_reports() else {
+		warn!("panic reports are unavailable, not forwarding panics to the error webhook");
+		return;
+	};
+
+	while let Some(report) = reports.recv().await {
+		send_panic_report(&ctx, report).await;
+	}
+}
+
+async fn send_panic_report(ctx: &Context, report: PanicReport) {
+	let hook = match Webhook::from_url(ctx, &scripty_config::get_config().error_webhook).await {
+		Ok(hook) => hook,
+		Err(e) => {
+			error!("failed to fetch error webhook: {}", e);
+			return;
+		}
+	};
+
+	let mut m = ExecuteWebhook::default();
+	let mut e = CreateEmbed::default()
+		.title("Panic")
+		.color((255, 0, 0))
+		.description(report.message)
+		.field(
+			"Location",
+			report.location.unwrap_or_else(|| "unknown".to_string()),
+			true,
+		)
+		.field(
+			"Thread",
+			report.thread.unwrap_or_else(|| "unnamed".to_string()),
+			true,
+		);
+
+	if report.backtrace.len() > 1024 {
+		e = e.field("Backtrace", "See attached file", false);
+		m = m.add_file(CreateAttachment::bytes(
+			report.backtrace.into_bytes(),
+			"backtrace.txt",
+		));
+	} else {
+		e = e.field("Backtrace", report.backtrace, false);
+	}
+
+	if let Err(e) = hook.execute(ctx, false, m.embed(e)).await {
+		error!("failed to log panic to discord: {}", e);
+	}
+}
@@ -0,0 +1,91 @@
+//! Short IDs for unexpected errors.
+//!
+//! Users are shown the ID instead of the raw error, and owners can look up the full details with
+//! `admin error <id>`. Reports are kept in Redis, so any cluster can look up any other's errors.
+
+use std::collections::HashMap;
+
+use scripty_redis::redis::AsyncCommands;
+
+use crate::Error;
+
+/// How long error reports are kept for, in seconds. (30 days)
+const ERROR_REPORT_TTL: usize = 60 * 60 * 24 * 30;
+
+/// Everything we know about an unexpected error.
+pub struct ErrorReport {
+	pub id:         String,
+	/// What we were doing when the error happened, ie "running command help".
+	pub context:    String,
+	pub display:    String,
+	pub debug:      String,
+	pub backtrace:  String,
+	pub guild_id:   Option<u64>,
+	pub channel_id: Option<u64>,
+	/// Unix timestamp of when the error happened.
+	pub timestamp:  i64,
+}
+
+/// Generate a new short error ID, ie `3F9A0C1E`.
+pub fn new_error_id() -> String {
+	let mut id = uuid::Uuid::new_v4().simple().to_string();
+	id.truncate(8);
+	id.make_ascii_uppercase();
+	id
+}
+
+pub async fn store_error_report(report: &ErrorReport) -> Result<(), Error> {
+	let mut redis = scripty_redis::get_pool().get().await?;
+	let key = format!("error:{{{}}}", report.id);
+
+	let mut fields = vec![
+		("context", report.context.clone()),
+		("display", report.display.clone()),
+		("debug", report.debug.clone()),
+		("backtrace", report.backtrace.clone()),
+		("timestamp", report.timestamp.to_string()),
+	];
+	if let Some(guild_id) = report.guild_id {
+		fields.push(("guild_id", guild_id.to_string()));
+	}
+	if let Some(channel_id) = report.channel_id {
+		fields.push(("channel_id", channel_id.to_string()));
+	}
+
+	scripty_redis::redis::pipe()
+		.hset_multiple(&key, &fields)
+		.ignore()
+		.expire(&key, ERROR_REPORT_TTL)
+		.ignore()
+		.query_async(&mut redis)
+		.await?;
+
+	Ok(())
+}
+
+/// Fetch an error report by its ID. IDs are case-insensitive.
+///
+/// Returns `None` if there's no such error, or it has expired.
+pub async fn get_error_report(id: &str) -> Result<Option<ErrorReport>, Error> {
+	let mut redis = scripty_redis::get_pool().get().await?;
+	let id = id.to_ascii_uppercase();
+
+	let mut fields: HashMap<String, String> = redis.hgetall(format!("error:{{{}}}", id)).await?;
+	if fields.is_empty() {
+		return Ok(None);
+	}
+
+	Ok(Some(ErrorReport {
+		context: fields.remove("context").unwrap_or_default(),
+		display: fields.remove("display").unwrap_or_default(),
+		debug: fields.remove("debug").unwrap_or_default(),
+		backtrace: fields.remove("backtrace").unwrap_or_default(),
+		guild_id: fields.get("guild_id").and_then(|x| x.parse().ok()),
+		channel_id: fields.get("channel_id").and_then(|x| x.parse().ok()),
+		timestamp: fields
+			.get("timestamp")
+			.and_then(|x| x.parse().ok())
+			.unwrap_or_default(),
+		id,
+	}))
+}
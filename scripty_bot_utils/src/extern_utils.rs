@@ -4,7 +4,7 @@ use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 pub use serenity::{
 	builder::{CreateEmbed, CreateEmbedFooter, CreateMessage},
-	model::id::UserId,
+	model::id::{GuildId, UserId},
 	Error as SerenityError,
 };
 use serenity::{
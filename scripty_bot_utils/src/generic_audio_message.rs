@@ -8,7 +8,15 @@ use std::{
 };
 
 use scripty_premium::PremiumTierList;
-use scripty_stt::FfprobeParsingError;
+use scripty_stt::{
+	FfprobeParsingError,
+	FillerWords,
+	Pipeline,
+	ProfanityFilter,
+	ProfanityMode,
+	Replacements,
+	SpokenNumbers,
+};
 use serenity::{
 	all::{Attachment, Context, EditMessage, Message},
 	builder::{CreateAttachment, CreateMessage},
@@ -108,24 +116,21 @@ pub async fn handle_message(ctx: Context, msg: Message) -> Result<(), GenericMes
 	}
 
 	// does the guild even have it enabled?
-	let (language, audio_enabled, video_enabled, translate) = sqlx::query!(
-		"SELECT language, transcribe_audio_files, transcribe_video_files, translate FROM guilds \
-		 WHERE guild_id = $1",
+	let Some(guild_res) = sqlx::query!(
+		"SELECT language, transcribe_audio_files, transcribe_video_files, translate, \
+		 profanity_filter, remove_filler_words, numbers_as_digits FROM guilds WHERE guild_id = $1",
 		guild_id.get() as i64
 	)
 	.fetch_optional(scripty_db::get_db())
 	.await?
-	.map_or_else(
-		|| (String::new(), false, false, false),
-		|row| {
-			(
-				row.language,
-				row.transcribe_audio_files,
-				row.transcribe_video_files,
-				row.translate,
-			)
-		},
-	);
+	else {
+		debug!(%msg.id, "guild has no settings");
+		return Ok(());
+	};
+	let language = guild_res.language;
+	let translate = guild_res.translate;
+	let audio_enabled = guild_res.transcribe_audio_files;
+	let video_enabled = guild_res.transcribe_video_files;
 	if !(audio_enabled || video_enabled) {
 		debug!(%msg.id, "neither audio nor video enabled");
 		return Ok(());
@@ -166,20 +171,46 @@ pub async fn handle_message(ctx: Context, msg: Message) -> Result<(), GenericMes
 		}
 	};
 
+	let transcript_language = if translate { "en" } else { language.as_str() };
+	let mut pipeline = Pipeline::standard(&language, translate);
+	if guild_res.remove_filler_words {
+		pipeline = pipeline.with_guild_stage(FillerWords::new(scripty_i18n::get_filler_words(
+			transcript_language,
+		)));
+	}
+	if guild_res.numbers_as_digits {
+		pipeline = pipeline.with_guild_stage(SpokenNumbers::new(transcript_language));
+	}
+	let replacements = scripty_utils::replacements::get_replacements(guild_id).await?;
+	let pipeline = pipeline
+		.with_guild_stage(Replacements::new(
+			replacements.into_iter().map(|r| (r.pattern, r.replacement)),
+		))
+		.then(ProfanityFilter::new(ProfanityMode::from_db(
+			guild_res.profanity_filter,
+		)));
+
 	// and then transcribe it
-	let transcripts =
-		match handle_transcripts(attached_files, language, premium_tier, translate).await {
-			Ok(transcripts) => transcripts,
-			Err(e) => {
-				new_msg
-					.edit(
-						ctx,
-						EditMessage::new().content(format!("Failed to transcribe files: {:?}", e)),
-					)
-					.await?;
-				return Err(e);
-			}
-		};
+	let transcripts = match handle_transcripts(
+		attached_files,
+		language,
+		premium_tier,
+		translate,
+		&pipeline,
+	)
+	.await
+	{
+		Ok(transcripts) => transcripts,
+		Err(e) => {
+			new_msg
+				.edit(
+					ctx,
+					EditMessage::new().content(format!("Failed to transcribe files: {:?}", e)),
+				)
+				.await?;
+			return Err(e);
+		}
+	};
 
 	// sometimes to prevent spam we get no transcripts returned
 	// (ie premium tier is too low for one file)
@@ -372,6 +403,7 @@ async fn handle_transcripts(
 	language: String,
 	premium_tier: PremiumTierList,
 	translate: bool,
+	pipeline: &Pipeline,
 ) -> Result<Vec<TranscriptResult>, GenericMessageError> {
 	let mut output = Vec::with_capacity(files.len());
 	for file in files {
@@ -453,6 +485,7 @@ async fn handle_transcripts(
 		let transcript = stream
 			.get_result(language.clone(), false, translate)
 			.await?;
+		let transcript = pipeline.run(transcript);
 		let transcript = transcript.trim();
 		if transcript.is_empty() {
 			output.push(TranscriptResult::EmptyTranscript {
@@ -1,10 +1,16 @@
 use std::sync::Arc;
 
-use once_cell::sync::OnceCell;
-use serenity::client::Cache;
+use dashmap::DashMap;
+use once_cell::sync::{Lazy, OnceCell};
+use serenity::{
+	client::{Cache, Context},
+	model::id::UserId,
+};
 
 use crate::{dm_support::DmSupportStatus, Data};
 
 pub static CLIENT_CACHE: OnceCell<Arc<Cache>> = OnceCell::new();
 pub static CLIENT_DATA: OnceCell<Data> = OnceCell::new();
 pub static DM_SUPPORT_GLOBAL: OnceCell<DmSupportStatus> = OnceCell::new();
+/// Contexts of the extra bots set in `extra_bot_tokens`, by their user ID, once they're ready.
+pub static EXTRA_BOT_CONTEXTS: Lazy<DashMap<UserId, Context>> = Lazy::new(DashMap::new);
@@ -0,0 +1,233 @@
+//! Deleting a guild's data once Scripty has been removed from it.
+//!
+//! Nothing is deleted straight away, in case Scripty was removed by mistake: adding it back within
+//! the grace period cancels the cleanup. Once it's done, the owner is DMed what was deleted.
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+	builder::{CreateEmbed, CreateEmbedFooter, CreateMessage},
+	client::Context,
+	http::HttpError,
+	model::id::{GuildId, UserId},
+};
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+	scheduler::{self, Job, JobHandler},
+	Error,
+};
+
+/// How long after Scripty is removed from a guild its data is deleted.
+pub const GRACE_PERIOD: Duration = Duration::days(7);
+
+const CLEANUP_JOB: &str = "guild_data_cleanup";
+
+#[derive(Serialize, Deserialize)]
+struct CleanupPayload {
+	guild_id:   GuildId,
+	/// Who to tell once it's done. None if the guild wasn't cached when Scripty was removed.
+	owner_id:   Option<UserId>,
+	name:       Option<String>,
+	/// Unix timestamp of when Scripty was removed.
+	removed_at: i64,
+}
+
+/// Schedule `guild_id`'s data to be deleted after the grace period, replacing any cleanup that's
+/// already scheduled.
+pub async fn schedule_cleanup(
+	guild_id: GuildId,
+	owner_id: Option<UserId>,
+	name: Option<String>,
+) -> Result<(), Error> {
+	let db = scripty_db::get_db();
+	let guild = sqlx::query!(
+		"SELECT cleanup_job_id FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(db)
+	.await?;
+	let Some(guild) = guild else {
+		// never set up, so there's nothing to delete
+		return Ok(());
+	};
+	if let Some(job_id) = guild.cleanup_job_id {
+		scheduler::cancel_job(job_id).await?;
+	}
+
+	let now = OffsetDateTime::now_utc();
+	let payload = CleanupPayload {
+		guild_id,
+		owner_id,
+		name,
+		removed_at: now.unix_timestamp(),
+	};
+	let payload = serde_json::to_string(&payload).expect("payloads always serialize");
+	let job_id = scheduler::schedule_once(CLEANUP_JOB, &payload, now + GRACE_PERIOD).await?;
+	sqlx::query!(
+		"UPDATE guilds SET cleanup_job_id = $2 WHERE guild_id = $1",
+		guild_id.get() as i64,
+		job_id
+	)
+	.execute(db)
+	.await?;
+
+	info!(%guild_id, job_id, "scheduled guild data cleanup");
+	scripty_metrics::get_metrics()
+		.guild_cleanups
+		.with_label_values(&["scheduled"])
+		.inc();
+	Ok(())
+}
+
+/// Cancel `guild_id`'s pending cleanup, if it has one, as Scripty was added back.
+pub async fn cancel_cleanup(guild_id: GuildId) -> Result<(), Error> {
+	let job = sqlx::query!(
+		"UPDATE guilds g SET cleanup_job_id = NULL FROM guilds old WHERE g.guild_id = $1 AND \
+		 old.guild_id = g.guild_id AND old.cleanup_job_id IS NOT NULL RETURNING \
+		 old.cleanup_job_id AS \"cleanup_job_id!\"",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+	if let Some(job) = job
+		&& scheduler::cancel_job(job.cleanup_job_id).await?
+	{
+		info!(%guild_id, "cancelled guild data cleanup, as Scripty was added back");
+		scripty_metrics::get_metrics()
+			.guild_cleanups
+			.with_label_values(&["cancelled"])
+			.inc();
+	}
+	Ok(())
+}
+
+/// Register the handler for guild cleanup jobs.
+pub fn register_job_handlers() {
+	scheduler::register_job_handler(CLEANUP_JOB, GuildCleanup);
+}
+
+struct GuildCleanup;
+
+#[async_trait]
+impl JobHandler for GuildCleanup {
+	async fn run(&self, ctx: &Context, job: &Job) -> Result<(), Error> {
+		let Ok(payload) = serde_json::from_str::<CleanupPayload>(&job.payload) else {
+			// retrying won't help, so just drop it
+			warn!(id = job.id, payload = %job.payload, "invalid guild cleanup job payload");
+			return Ok(());
+		};
+		let guild_id = payload.guild_id;
+		let db = scripty_db::get_db();
+
+		// it may have been added back without the cleanup being cancelled, like while the bot was
+		// down, so make sure it's really gone
+		match ctx.http.get_guild(guild_id).await {
+			Ok(_) => {
+				sqlx::query!(
+					"UPDATE guilds SET cleanup_job_id = NULL WHERE guild_id = $1 AND \
+					 cleanup_job_id = $2",
+					guild_id.get() as i64,
+					job.id
+				)
+				.execute(db)
+				.await?;
+				info!(%guild_id, "skipped guild data cleanup, as Scripty is in the guild again");
+				scripty_metrics::get_metrics()
+					.guild_cleanups
+					.with_label_values(&["cancelled"])
+					.inc();
+				return Ok(());
+			}
+			Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(res)))
+				if res.status_code.is_client_error() => {}
+			Err(e) => return Err(e.into()),
+		}
+
+		// looked up before it's deleted along with everything else
+		let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+		let mut tx = db.begin().await?;
+		let transcripts = sqlx::query!(
+			"SELECT COUNT(*) AS \"count!\" FROM transcript_sessions WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.fetch_one(&mut *tx)
+		.await?
+		.count;
+		// everything else kept for the guild references this row, so goes with it
+		let guild = sqlx::query!(
+			"DELETE FROM guilds WHERE guild_id = $1 AND cleanup_job_id = $2 RETURNING trial_used, \
+			 onboarding_sent",
+			guild_id.get() as i64,
+			job.id
+		)
+		.fetch_optional(&mut *tx)
+		.await?;
+		let Some(guild) = guild else {
+			// cancelled, or replaced by a newer cleanup
+			return Ok(());
+		};
+		sqlx::query!(
+			"DELETE FROM prefixes WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.execute(&mut *tx)
+		.await?;
+		sqlx::query!(
+			"DELETE FROM active_sessions WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.execute(&mut *tx)
+		.await?;
+		sqlx::query!(
+			"DELETE FROM user_talk_time WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.execute(&mut *tx)
+		.await?;
+		if guild.trial_used || guild.onboarding_sent {
+			// so adding Scripty back doesn't get another trial, or onboarding message
+			sqlx::query!(
+				"INSERT INTO guilds (guild_id, trial_used, onboarding_sent) VALUES ($1, $2, $3)",
+				guild_id.get() as i64,
+				guild.trial_used,
+				guild.onboarding_sent
+			)
+			.execute(&mut *tx)
+			.await?;
+		}
+		tx.commit().await?;
+		scripty_i18n::remove_guild_language(guild_id.get());
+
+		info!(%guild_id, transcripts, "deleted guild data");
+		scripty_metrics::get_metrics()
+			.guild_cleanups
+			.with_label_values(&["performed"])
+			.inc();
+
+		let Some(owner_id) = payload.owner_id else {
+			return Ok(());
+		};
+		let guild_name = payload.name.unwrap_or_else(|| guild_id.to_string());
+		let embed = CreateEmbed::default()
+			.title(format_message!(language, "guild-cleanup-title"))
+			.description(format_message!(
+				language,
+				"guild-cleanup-description",
+				guildName: guild_name,
+				removedAt: payload.removed_at,
+				transcripts: transcripts
+			))
+			.footer(CreateEmbedFooter::new(format_message!(
+				language,
+				"guild-cleanup-footer"
+			)));
+		if let Err(e) = owner_id
+			.direct_message(ctx, CreateMessage::default().embed(embed))
+			.await
+		{
+			// most likely DMs closed, or they don't share a server with Scripty anymore
+			debug!(%guild_id, "failed to tell owner their guild's data was deleted: {}", e);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,54 @@
+//! Events of the extra bots set in `extra_bot_tokens`. They only transcribe voice chats: anything
+//! else, like background tasks and cleaning up after guilds, is left to the main bot.
+
+use poise::serenity_prelude::EventHandler;
+use serenity::{
+	all::VoiceState,
+	client::Context as SerenityContext,
+	model::{channel::Message, gateway::Ready, id::GuildId},
+};
+
+use crate::globals::EXTRA_BOT_CONTEXTS;
+
+pub struct ExtraBotEventHandler;
+
+#[async_trait]
+impl EventHandler for ExtraBotEventHandler {
+	async fn cache_ready(&self, ctx: SerenityContext, guilds: Vec<GuildId>) {
+		// rejoin this bot's voice sessions that were active when we last stopped
+		tokio::spawn(async move {
+			if let Err(e) = scripty_audio_handler::resume_sessions(&ctx, &guilds).await {
+				error!("failed to resume extra bot voice sessions: {}", e);
+			}
+		});
+	}
+
+	async fn message(&self, ctx: SerenityContext, new_message: Message) {
+		tokio::spawn(scripty_audio_handler::relay_message(ctx, new_message));
+	}
+
+	async fn ready(&self, ctx: SerenityContext, ready: Ready) {
+		info!(
+			"extra bot ready: logged in as {}, in {} guilds",
+			ready.user.tag(),
+			ready.guilds.len()
+		);
+		// so it's drained on shutdown, and its stale sessions are removed
+		EXTRA_BOT_CONTEXTS.insert(ready.user.id, ctx);
+	}
+
+	async fn voice_state_update(
+		&self,
+		ctx: SerenityContext,
+		old: Option<VoiceState>,
+		new: VoiceState,
+	) {
+		let Some(guild_id) = new.guild_id else {
+			return;
+		};
+		// joining voice chats automatically is left to the main bot
+		if let Some(cid) = scripty_audio_handler::get_voice_channel_id(&ctx, guild_id).await {
+			super::normal::session_voice_state_update(ctx, guild_id, cid, old, new).await;
+		}
+	}
+}
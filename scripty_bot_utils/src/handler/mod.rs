@@ -1,8 +1,10 @@
+mod extra;
 mod normal;
 mod post_command;
 mod pre_command;
 mod raw;
 
+pub use extra::ExtraBotEventHandler;
 pub use normal::BotEventHandler;
 pub use post_command::post_command;
 pub use pre_command::pre_command;
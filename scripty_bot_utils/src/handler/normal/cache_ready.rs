@@ -4,7 +4,7 @@ use crate::{dm_support::DmSupportStatus, globals::DM_SUPPORT_GLOBAL};
 
 const SIZE_OF_GUILD_ID: usize = std::mem::size_of::<GuildId>();
 
-pub async fn cache_ready(_ctx: Context, guilds: Vec<GuildId>) {
+pub async fn cache_ready(ctx: Context, guilds: Vec<GuildId>) {
 	let guild_count = guilds.len();
 	info!(
 		"cache is primed, {} guilds in cache for {} bytes",
@@ -14,4 +14,22 @@ pub async fn cache_ready(_ctx: Context, guilds: Vec<GuildId>) {
 
 	let dm_support = DmSupportStatus::new();
 	let _ = DM_SUPPORT_GLOBAL.set(dm_support);
+
+	// rejoin any voice sessions that were active when we last stopped
+	tokio::spawn(async move {
+		if let Err(e) = scripty_audio_handler::claim_legacy_sessions(&ctx).await {
+			error!(
+				"failed to claim voice sessions persisted without a bot: {}",
+				e
+			);
+		}
+		if let Err(e) = scripty_audio_handler::resume_sessions(&ctx, &guilds).await {
+			error!("failed to resume voice sessions: {}", e);
+		}
+	});
+
+	// let the next cluster start up
+	if let Err(e) = crate::cluster::release_startup_lock().await {
+		error!("failed to release cluster startup lock: {}", e);
+	}
 }
@@ -0,0 +1,23 @@
+use serenity::{client::Context as SerenityContext, model::guild::Guild};
+
+pub async fn guild_create(ctx: SerenityContext, guild: Guild, is_new: Option<bool>) {
+	match crate::entity_block::is_guild_blocked(guild.id).await {
+		Ok(true) => {
+			info!(guild_id = %guild.id, "leaving blacklisted guild");
+			if let Err(e) = guild.id.leave(&ctx).await {
+				error!(guild_id = %guild.id, "failed to leave blacklisted guild: {}", e);
+			}
+			return;
+		}
+		Ok(false) => {}
+		Err(e) => error!(guild_id = %guild.id, "failed to check if guild is blacklisted: {}", e),
+	}
+
+	// guilds Scripty was already in are also created on startup, and when it reconnects
+	if is_new == Some(true) {
+		if let Err(e) = crate::guild_cleanup::cancel_cleanup(guild.id).await {
+			error!(guild_id = %guild.id, "failed to cancel guild data cleanup: {}", e);
+		}
+		crate::onboarding::send_if_needed(&ctx, &guild).await;
+	}
+}
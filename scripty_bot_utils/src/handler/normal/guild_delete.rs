@@ -0,0 +1,23 @@
+use serenity::{
+	client::Context as SerenityContext,
+	model::guild::{Guild, UnavailableGuild},
+};
+
+pub async fn guild_delete(
+	_ctx: SerenityContext,
+	incomplete: UnavailableGuild,
+	full: Option<Guild>,
+) {
+	if incomplete.unavailable {
+		// an outage, not Scripty being removed
+		return;
+	}
+
+	let (owner_id, name) = match full {
+		Some(guild) => (Some(guild.owner_id), Some(guild.name)),
+		None => (None, None),
+	};
+	if let Err(e) = crate::guild_cleanup::schedule_cleanup(incomplete.id, owner_id, name).await {
+		error!(guild_id = %incomplete.id, "failed to schedule guild data cleanup: {}", e);
+	}
+}
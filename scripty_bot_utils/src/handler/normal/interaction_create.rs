@@ -1,7 +1,28 @@
+use scripty_utils::{hold_queue, translation};
 use serenity::{all::Interaction, client::Context};
 
-pub async fn interaction_create(_ctx: Context, interaction: Interaction) {
-	if let Some(cmd) = interaction.command() {
-		info!("got data {:?}", cmd.data);
+use crate::globals::DM_SUPPORT_GLOBAL;
+
+pub async fn interaction_create(ctx: Context, interaction: Interaction) {
+	match interaction {
+		Interaction::Command(cmd) => {
+			info!("got data {:?}", cmd.data);
+		}
+		Interaction::Component(component)
+			if component.data.custom_id == translation::TRANSLATE_BUTTON_ID =>
+		{
+			crate::transcript_translation::handle_translate_button(ctx, component).await;
+		}
+		Interaction::Component(component)
+			if hold_queue::is_review_button(&component.data.custom_id) =>
+		{
+			crate::hold_review::handle_review_button(ctx, component).await;
+		}
+		Interaction::Component(component) => {
+			if let Some(st) = DM_SUPPORT_GLOBAL.get() {
+				st.handle_component(ctx, component).await;
+			}
+		}
+		_ => {}
 	}
 }
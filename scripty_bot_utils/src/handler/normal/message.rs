@@ -8,6 +8,10 @@ pub async fn message(ctx: Context, msg: Message) {
 		tokio::spawn(st.handle_message(ctx.clone(), msg.clone()));
 	}
 
+	tokio::spawn(scripty_audio_handler::relay_message(
+		ctx.clone(),
+		msg.clone(),
+	));
 	tokio::spawn(crate::voice_message::handle_message(
 		ctx.clone(),
 		msg.clone(),
@@ -1,17 +1,29 @@
 use poise::serenity_prelude::EventHandler;
 use serenity::{
-	all::{Interaction, VoiceState},
+	all::{Interaction, Reaction, ScheduledEvent, VoiceState},
 	client::Context as SerenityContext,
-	model::{channel::Message, event::ResumedEvent, gateway::Ready, id::GuildId},
+	model::{
+		channel::Message,
+		event::ResumedEvent,
+		gateway::Ready,
+		guild::{Guild, UnavailableGuild},
+		id::GuildId,
+	},
 };
 
 mod cache_ready;
+mod guild_create;
+mod guild_delete;
 mod interaction_create;
 mod message;
+mod reaction_add;
 mod ready;
 mod resume;
+mod scheduled_event;
 mod voice_state_update;
 
+pub(super) use voice_state_update::session_voice_state_update;
+
 pub struct BotEventHandler;
 
 #[async_trait]
@@ -21,11 +33,31 @@ impl EventHandler for BotEventHandler {
 		cache_ready::cache_ready(ctx, guilds).await;
 	}
 
+	#[inline]
+	async fn guild_create(&self, ctx: SerenityContext, guild: Guild, is_new: Option<bool>) {
+		guild_create::guild_create(ctx, guild, is_new).await;
+	}
+
+	#[inline]
+	async fn guild_delete(
+		&self,
+		ctx: SerenityContext,
+		incomplete: UnavailableGuild,
+		full: Option<Guild>,
+	) {
+		guild_delete::guild_delete(ctx, incomplete, full).await;
+	}
+
 	#[inline]
 	async fn message(&self, ctx: SerenityContext, new_message: Message) {
 		message::message(ctx, new_message).await;
 	}
 
+	#[inline]
+	async fn reaction_add(&self, ctx: SerenityContext, add_reaction: Reaction) {
+		reaction_add::reaction_add(ctx, add_reaction).await;
+	}
+
 	#[inline]
 	async fn ready(&self, ctx: SerenityContext, ready: Ready) {
 		ready::ready(ctx, ready).await;
@@ -36,6 +68,16 @@ impl EventHandler for BotEventHandler {
 		resume::resume(ctx, resume).await;
 	}
 
+	#[inline]
+	async fn guild_scheduled_event_update(&self, ctx: SerenityContext, event: ScheduledEvent) {
+		scheduled_event::guild_scheduled_event_update(ctx, event).await;
+	}
+
+	#[inline]
+	async fn guild_scheduled_event_delete(&self, ctx: SerenityContext, event: ScheduledEvent) {
+		scheduled_event::guild_scheduled_event_delete(ctx, event).await;
+	}
+
 	#[inline]
 	async fn voice_state_update(
 		&self,
@@ -0,0 +1,67 @@
+//! Reacting to transcripts with the emojis set in `/config flag_emoji` and
+//! `/config highlight_emoji`.
+//!
+//! Flagging marks a transcript as wrong. Only audio from users who opted in to audio storage is
+//! kept, so only their transcripts are queued for a correction.
+//!
+//! Highlighting adds a transcript to its session's highlights, which only works while the session
+//! can still be found for `/minutes`. Reactions to anything else are ignored.
+
+use serenity::{
+	all::{Reaction, ReactionType},
+	client::Context,
+};
+
+pub async fn reaction_add(ctx: Context, reaction: Reaction) {
+	let Some(guild_id) = reaction.guild_id else {
+		return;
+	};
+	if reaction.user_id == Some(ctx.cache.current_user().id)
+		|| reaction.member.as_ref().map_or(false, |m| m.user.bot)
+	{
+		return;
+	}
+	let emoji = match reaction.emoji {
+		ReactionType::Custom { id, .. } => id.to_string(),
+		ReactionType::Unicode(emoji) => emoji.to_string(),
+		_ => return,
+	};
+
+	let config = match sqlx::query!(
+		"SELECT flag_emoji, highlight_emoji FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await
+	{
+		Ok(Some(config)) => config,
+		Ok(None) => return,
+		Err(e) => {
+			error!(%guild_id, "failed to fetch reaction config: {}", e);
+			return;
+		}
+	};
+
+	if config.highlight_emoji.as_deref() == Some(emoji.as_str())
+		&& scripty_audio_handler::highlight(guild_id, reaction.message_id)
+	{
+		debug!(%guild_id, message_id = %reaction.message_id, "transcript highlighted");
+	}
+
+	if config.flag_emoji.as_deref() != Some(emoji.as_str()) {
+		return;
+	}
+	match sqlx::query!(
+		"UPDATE audio_store SET flagged_at = NOW() WHERE message_id = $1 AND flagged_at IS NULL",
+		reaction.message_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await
+	{
+		Ok(res) if res.rows_affected() > 0 => {
+			debug!(%guild_id, message_id = %reaction.message_id, "transcript flagged as wrong");
+		}
+		Ok(_) => {}
+		Err(e) => error!(%guild_id, "failed to flag transcript: {}", e),
+	}
+}
@@ -0,0 +1,160 @@
+//! Transcribing scheduled events automatically, for guilds that set it up with
+//! `/config scheduled_events`.
+//!
+//! Scripty joins the event's voice channel when it's started, and leaves when it's ended,
+//! canceled or deleted.
+
+use serenity::{
+	all::{
+		AutoArchiveDuration,
+		ChannelId,
+		ChannelType,
+		ScheduledEvent,
+		ScheduledEventStatus,
+		ScheduledEventType,
+	},
+	builder::CreateThread,
+	client::Context,
+};
+
+pub async fn guild_scheduled_event_update(ctx: Context, event: ScheduledEvent) {
+	match event.status {
+		ScheduledEventStatus::Active => start_event_session(ctx, event).await,
+		ScheduledEventStatus::Completed | ScheduledEventStatus::Canceled => {
+			end_event_session(ctx, event).await
+		}
+		_ => {}
+	}
+}
+
+pub async fn guild_scheduled_event_delete(ctx: Context, event: ScheduledEvent) {
+	end_event_session(ctx, event).await;
+}
+
+struct EventConfig {
+	channel_id:            ChannelId,
+	create_thread:         bool,
+	record_transcriptions: bool,
+}
+
+/// The guild's `/config scheduled_events` settings, if it's turned on.
+async fn get_event_config(event: &ScheduledEvent) -> Option<EventConfig> {
+	let res = sqlx::query!(
+		"SELECT scheduled_event_channel, scheduled_event_thread, scheduled_event_record FROM \
+		 guilds WHERE guild_id = $1",
+		event.guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await;
+	match res {
+		Ok(Some(row)) => row.scheduled_event_channel.map(|channel_id| EventConfig {
+			channel_id:            ChannelId::new(channel_id as u64),
+			create_thread:         row.scheduled_event_thread,
+			record_transcriptions: row.scheduled_event_record,
+		}),
+		Ok(None) => None,
+		Err(e) => {
+			error!(guild_id = %event.guild_id, "failed to fetch scheduled event config: {}", e);
+			None
+		}
+	}
+}
+
+async fn start_event_session(ctx: Context, event: ScheduledEvent) {
+	let guild_id = event.guild_id;
+	let Some(voice_channel_id) = event.channel_id else {
+		return;
+	};
+	if !matches!(
+		event.kind,
+		ScheduledEventType::Voice | ScheduledEventType::StageInstance
+	) {
+		return;
+	}
+	let Some(cfg) = get_event_config(&event).await else {
+		return;
+	};
+	// don't take over a session someone started themselves
+	if scripty_audio_handler::get_voice_channel_id(&ctx, guild_id)
+		.await
+		.is_some()
+	{
+		debug!(%guild_id, "already in a voice channel, not joining scheduled event");
+		return;
+	}
+
+	let thread_id = if cfg.create_thread {
+		match cfg
+			.channel_id
+			.create_thread(
+				&ctx,
+				CreateThread::new(event.name.to_string())
+					.auto_archive_duration(AutoArchiveDuration::OneDay)
+					.kind(ChannelType::PublicThread),
+			)
+			.await
+		{
+			Ok(thread) => Some(thread.id),
+			Err(e) => {
+				// the channel itself is the next best place for transcripts
+				warn!(%guild_id, "failed to create scheduled event thread: {}", e);
+				None
+			}
+		}
+	} else {
+		None
+	};
+	let output_channel_id = thread_id.unwrap_or(cfg.channel_id);
+
+	debug!(%guild_id, %voice_channel_id, "joining voice channel for scheduled event");
+	let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	let message = match scripty_audio_handler::connect_to_vc(
+		ctx.clone(),
+		guild_id,
+		cfg.channel_id,
+		voice_channel_id,
+		thread_id,
+		false,
+		cfg.record_transcriptions,
+	)
+	.await
+	{
+		Ok(()) => format_message!(
+			language,
+			"scheduled-event-started",
+			eventName: event.name.to_string()
+		),
+		Err(mut e) => {
+			error!(%guild_id, "failed to join voice channel for scheduled event: {:?}", e);
+			e.report(&ctx, guild_id);
+			format_message!(
+				language,
+				"scheduled-event-join-failed",
+				eventName: event.name.to_string(),
+				error: e.to_string()
+			)
+		}
+	};
+	if let Err(e) = output_channel_id.say(&ctx.http, message).await {
+		debug!(%guild_id, "failed to send scheduled event notice: {}", e);
+	}
+}
+
+async fn end_event_session(ctx: Context, event: ScheduledEvent) {
+	let guild_id = event.guild_id;
+	let Some(voice_channel_id) = event.channel_id else {
+		return;
+	};
+	if get_event_config(&event).await.is_none() {
+		return;
+	}
+	// sessions don't remember what started them, so this also ends a `/join` in the event's channel
+	if scripty_audio_handler::get_voice_channel_id(&ctx, guild_id).await != Some(voice_channel_id) {
+		return;
+	}
+
+	debug!(%guild_id, %voice_channel_id, "leaving voice channel as scheduled event ended");
+	if let Err(e) = scripty_audio_handler::disconnect_from_vc(&ctx, guild_id).await {
+		error!(%guild_id, "failed to leave voice channel after scheduled event: {:?}", e);
+	}
+}
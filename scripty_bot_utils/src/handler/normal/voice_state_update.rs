@@ -2,62 +2,18 @@ use std::time::Duration;
 
 use scripty_audio_handler::get_voice_channel_id;
 use serenity::{
-	all::{ChannelId, VoiceState},
+	all::{ChannelId, GuildId, VoiceState},
 	client::Context,
 };
 
-pub async fn voice_state_update(ctx: Context, _: Option<VoiceState>, new: VoiceState) {
+pub async fn voice_state_update(ctx: Context, old: Option<VoiceState>, new: VoiceState) {
 	let Some(guild_id) = new.guild_id else {
 		warn!("no guild id in voice_state_update");
 		return;
 	};
 
 	if let Some(cid) = get_voice_channel_id(&ctx, guild_id).await {
-		let own_user_id = ctx.cache.current_user().id;
-
-		// GuildRef forces a block here to prevent hold over await
-		{
-			let guild = match guild_id.to_guild_cached(&ctx) {
-				Some(g) => g,
-				None => {
-					warn!("guild id {} not found in cache", guild_id);
-					return;
-				}
-			};
-
-			// iterate through voice states in the guild
-			// if there are any more than 1 in this channel, return
-			// if there are 0, leave the channel
-			let mut user_count = 0;
-			for (_, vs) in guild.voice_states.iter() {
-				// is the voice state in the channel we're in, and is it not us?
-				if !(vs.channel_id == Some(cid) || vs.user_id != own_user_id) {
-					continue;
-				}
-				// is the user a bot? if so, they don't count
-				if vs.user_id.to_user_cached(&ctx).map_or(false, |u| u.bot) {
-					continue;
-				}
-				user_count += 1;
-			}
-			if user_count > 0 {
-				debug!(
-					"not leaving voice channel {} in guild {} ({} users)",
-					cid, guild_id, user_count
-				);
-				return;
-			}
-		}
-
-		// if we get here, we are the only one in the channel
-		// so we should leave
-		debug!(
-			"leaving voice channel {} in guild {} (we're last user)",
-			cid, guild_id
-		);
-		if let Err(e) = scripty_audio_handler::disconnect_from_vc(&ctx, guild_id).await {
-			error!("error disconnecting from voice channel: {:?}", e);
-		};
+		session_voice_state_update(ctx, guild_id, cid, old, new).await;
 	} else {
 		debug!("not in a voice channel in guild {}", guild_id);
 
@@ -138,13 +94,21 @@ pub async fn voice_state_update(ctx: Context, _: Option<VoiceState>, new: VoiceS
 			warn!("user id {} not in a voice channel", new.user_id);
 			return;
 		};
+		// members are moved there when idle, so following them would transcribe nobody
+		if scripty_audio_handler::is_afk_channel(&ctx, guild_id, voice_channel_id) {
+			debug!(
+				"user {} joined the AFK channel in guild {}, not continuing with join",
+				new.user_id, guild_id
+			);
+			return;
+		}
 
 		// join the channel
 		debug!(
 			"joining voice channel {} in guild {} as guild has auto join enabled",
 			voice_channel_id, guild_id
 		);
-		if let Err(e) = scripty_audio_handler::connect_to_vc(
+		if let Err(mut e) = scripty_audio_handler::connect_to_vc(
 			ctx.clone(),
 			guild_id,
 			log_channel_id,
@@ -156,6 +120,7 @@ pub async fn voice_state_update(ctx: Context, _: Option<VoiceState>, new: VoiceS
 		.await
 		{
 			error!("error joining voice channel: {:?}", e);
+			e.report(&ctx, guild_id);
 			// fire a message to the log channel
 			let _ = log_channel_id
 				.say(
@@ -173,3 +138,88 @@ pub async fn voice_state_update(ctx: Context, _: Option<VoiceState>, new: VoiceS
 		tokio::time::sleep(FIFTEEN_HUNDRED_MS).await;
 	};
 }
+
+/// Handle a voice state update in `guild_id`, where the bot is transcribing `cid`: note members
+/// joining and leaving, and leave once nobody's left. Extra bots only handle these.
+pub(crate) async fn session_voice_state_update(
+	ctx: Context,
+	guild_id: GuildId,
+	cid: ChannelId,
+	old: Option<VoiceState>,
+	new: VoiceState,
+) {
+	let own_user_id = ctx.cache.current_user().id;
+
+	// a line in the transcript when someone joins or leaves, if the guild wants that
+	{
+		let ctx = ctx.clone();
+		let (old, new) = (old.clone(), new.clone());
+		tokio::spawn(async move {
+			scripty_audio_handler::announce_join_leave(&ctx, guild_id, old.as_ref(), &new).await;
+		});
+	}
+
+	// someone can now be heard in the channel being transcribed: let them know, if the guild
+	// wants that. Stage audiences and muted listeners are never transcribed, so they're only
+	// told once they can speak
+	if can_speak_in(&new, cid)
+		&& !old.as_ref().map_or(false, |old| can_speak_in(old, cid))
+		&& new.user_id != own_user_id
+		&& !new.member.as_ref().map_or(false, |m| m.user.bot)
+	{
+		let ctx = ctx.clone();
+		let user_id = new.user_id;
+		tokio::spawn(async move {
+			crate::consent_notice::notify_if_needed(&ctx, guild_id, user_id, cid).await;
+		});
+	}
+
+	// GuildRef forces a block here to prevent hold over await
+	{
+		let guild = match guild_id.to_guild_cached(&ctx) {
+			Some(g) => g,
+			None => {
+				warn!("guild id {} not found in cache", guild_id);
+				return;
+			}
+		};
+
+		// iterate through voice states in the guild
+		// if there are any more than 1 in this channel, return
+		// if there are 0, leave the channel
+		let mut user_count = 0;
+		for (_, vs) in guild.voice_states.iter() {
+			// is the voice state in the channel we're in, and is it not us?
+			if !(vs.channel_id == Some(cid) || vs.user_id != own_user_id) {
+				continue;
+			}
+			// is the user a bot? if so, they don't count
+			if vs.user_id.to_user_cached(&ctx).map_or(false, |u| u.bot) {
+				continue;
+			}
+			user_count += 1;
+		}
+		if user_count > 0 {
+			debug!(
+				"not leaving voice channel {} in guild {} ({} users)",
+				cid, guild_id, user_count
+			);
+			return;
+		}
+	}
+
+	// if we get here, we are the only one in the channel
+	// so we should leave
+	debug!(
+		"leaving voice channel {} in guild {} (we're last user)",
+		cid, guild_id
+	);
+	if let Err(e) = scripty_audio_handler::disconnect_from_vc(&ctx, guild_id).await {
+		error!("error disconnecting from voice channel: {:?}", e);
+	};
+}
+
+/// Whether `state` is in `channel_id`, and can be heard there.
+fn can_speak_in(state: &VoiceState, channel_id: ChannelId) -> bool {
+	state.channel_id == Some(channel_id) && !(state.suppress || state.mute || state.self_mute)
+}
@@ -1,7 +1,8 @@
 use poise::BoxFuture;
 
-async fn _post_command(_: crate::Context<'_>) {
-	// now unused
+async fn _post_command(ctx: crate::Context<'_>) {
+	// only successful commands reach here, failures are recorded in the error handler
+	scripty_metrics::measure_command_end(ctx.id(), &ctx.command().qualified_name, true);
 }
 
 #[inline]
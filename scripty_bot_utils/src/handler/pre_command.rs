@@ -2,6 +2,7 @@ use poise::BoxFuture;
 
 async fn _pre_command(ctx: crate::Context<'_>) {
 	scripty_metrics::measure_end_latency(ctx.id());
+	scripty_metrics::measure_command_start(ctx.id());
 
 	let metrics = scripty_metrics::get_metrics();
 	metrics.total_commands.inc();
@@ -0,0 +1,47 @@
+//! Process-wide health checks, shared by the heartbeat and the systemd watchdog.
+
+use scripty_utils::latency;
+use serenity::gateway::ConnectionStage;
+
+use crate::globals::CLIENT_DATA;
+
+/// The bot is healthy if every shard is connected, the database and Redis respond,
+/// and an STT worker completes a handshake.
+///
+/// The measurements are recorded in the latency history, which `/ping` reports.
+pub async fn is_healthy() -> bool {
+	let shard_manager = match CLIENT_DATA.get() {
+		Some(data) => data.shard_manager.clone(),
+		None => return false,
+	};
+	{
+		let runners = shard_manager.runners.lock().await;
+		if runners.is_empty() {
+			return false;
+		}
+		if let Some((id, runner)) = runners
+			.iter()
+			.find(|(_, runner)| !matches!(runner.stage, ConnectionStage::Connected))
+		{
+			warn!(shard_id = %id, stage = ?runner.stage, "shard not connected");
+			return false;
+		}
+	}
+
+	if let Err(e) = latency::get_db_latency().await {
+		warn!("database unhealthy: {}", e);
+		return false;
+	}
+
+	if let Err(e) = latency::get_redis_latency().await {
+		warn!("redis unhealthy: {}", e);
+		return false;
+	}
+
+	if let Err(e) = scripty_stt::get_stt_latency().await {
+		warn!("no STT workers available: {}", e);
+		return false;
+	}
+
+	true
+}
@@ -0,0 +1,160 @@
+//! The Approve and Reject buttons on transcripts held by `/config hold_queue`, and closing their
+//! review messages once they're decided on.
+
+use scripty_utils::hold_queue::{
+	self,
+	HeldTranscript,
+	APPROVE_BUTTON_PREFIX,
+	REJECT_BUTTON_PREFIX,
+};
+use serenity::{
+	all::ComponentInteraction,
+	builder::{
+		CreateInteractionResponse,
+		CreateInteractionResponseMessage,
+		EditInteractionResponse,
+		EditMessage,
+	},
+	client::Context,
+	prelude::Mentionable,
+};
+
+/// Approve or reject the held transcript of the button that was pressed.
+pub async fn handle_review_button(ctx: Context, interaction: ComponentInteraction) {
+	let custom_id = interaction.data.custom_id.as_str();
+	let (approve, hold_id) = match (
+		custom_id.strip_prefix(APPROVE_BUTTON_PREFIX),
+		custom_id.strip_prefix(REJECT_BUTTON_PREFIX),
+	) {
+		(Some(hold_id), _) => (true, hold_id),
+		(None, Some(hold_id)) => (false, hold_id),
+		(None, None) => return,
+	};
+	let (Some(guild_id), Ok(hold_id)) = (interaction.guild_id, hold_id.parse::<i64>()) else {
+		return;
+	};
+	let language = scripty_i18n::get_resolved_language_with_locale(
+		interaction.user.id.get(),
+		Some(guild_id.get()),
+		Some(&interaction.locale),
+		interaction.guild_locale.as_deref(),
+	)
+	.await;
+	let respond = |key: &str| {
+		CreateInteractionResponse::Message(
+			CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(format_message!(language, key)),
+		)
+	};
+
+	// anyone who can manage messages in the review channel can review what's in it
+	let can_review = interaction
+		.member
+		.as_ref()
+		.and_then(|member| member.permissions)
+		.map_or(false, |p| p.manage_messages());
+	if !can_review {
+		if let Err(e) = interaction
+			.create_response(&ctx, respond("hold-queue-not-allowed"))
+			.await
+		{
+			warn!(%guild_id, "failed to respond to review button: {}", e);
+		}
+		return;
+	}
+
+	// taking it first means only one moderator's decision counts
+	let held = match hold_queue::take(guild_id, hold_id).await {
+		Ok(Some(held)) => held,
+		Ok(None) => {
+			if let Err(e) = interaction
+				.create_response(&ctx, respond("hold-queue-already-reviewed"))
+				.await
+			{
+				warn!(%guild_id, "failed to respond to review button: {}", e);
+			}
+			return;
+		}
+		Err(e) => {
+			error!(%guild_id, hold_id, "failed to take held transcript: {}", e);
+			return;
+		}
+	};
+	// posting can take longer than Discord waits for a response
+	if let Err(e) = interaction.defer(&ctx).await {
+		warn!(%guild_id, "failed to acknowledge review button: {}", e);
+	}
+
+	let outcome_id = if !approve {
+		"hold-queue-rejected"
+	} else if let Err(e) = hold_queue::post(&ctx, &held).await {
+		warn!(%guild_id, hold_id, "failed to post approved transcript: {}", e);
+		"hold-queue-post-failed"
+	} else {
+		"hold-queue-approved"
+	};
+	// the review message is seen by every moderator, so it's in the guild's language
+	let guild_language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	let outcome = format_message!(
+		guild_language,
+		outcome_id,
+		reviewer: interaction.user.id.mention().to_string()
+	);
+	if let Err(e) = interaction
+		.edit_response(
+			&ctx,
+			EditInteractionResponse::new()
+				.content(outcome)
+				.components(vec![]),
+		)
+		.await
+	{
+		warn!(%guild_id, hold_id, "failed to close review message: {}", e);
+	}
+}
+
+/// Post held transcripts that are due to be approved automatically, and drop those nobody
+/// reviewed in time, closing their review messages.
+pub async fn release_held(ctx: &Context) -> Result<(), sqlx::Error> {
+	for held in hold_queue::take_due().await? {
+		let outcome_id = match hold_queue::post(ctx, &held).await {
+			Ok(_) => "hold-queue-auto-approved",
+			Err(e) => {
+				let (guild_id, hold_id) = (held.guild_id, held.hold_id);
+				warn!(%guild_id, hold_id, "failed to post approved transcript: {}", e);
+				"hold-queue-auto-post-failed"
+			}
+		};
+		close_review(ctx, &held, outcome_id).await;
+	}
+	for held in hold_queue::take_expired().await? {
+		close_review(ctx, &held, "hold-queue-expired").await;
+	}
+	Ok(())
+}
+
+/// Replace the buttons on `held`'s review message with what happened to it.
+async fn close_review(ctx: &Context, held: &HeldTranscript, outcome_id: &str) {
+	let Some(message_id) = held.review_message_id else {
+		return;
+	};
+	let language = scripty_i18n::get_guild_language(held.guild_id.get()).await;
+	let outcome = format_message!(
+		language,
+		outcome_id,
+		hours: hold_queue::MAX_HOLD_TIME.as_secs() / 3600
+	);
+	if let Err(e) = held
+		.review_channel_id
+		.edit_message(
+			ctx,
+			message_id,
+			EditMessage::new().content(outcome).components(vec![]),
+		)
+		.await
+	{
+		let (guild_id, hold_id) = (held.guild_id, held.hold_id);
+		debug!(%guild_id, hold_id, "failed to close review message: {}", e);
+	}
+}
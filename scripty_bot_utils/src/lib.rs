@@ -7,21 +7,54 @@ extern crate scripty_i18n;
 #[macro_use]
 extern crate async_trait;
 
+pub mod api_keys;
 pub mod background_tasks;
 pub mod checks;
+pub mod cluster;
+pub mod confirm;
+pub mod consent_notice;
+pub mod control;
 pub mod dm_support;
 pub mod entity_block;
 pub mod error;
 pub mod extern_utils;
 mod generic_audio_message;
 pub mod globals;
+mod guild_cleanup;
 pub mod handler;
+pub mod health;
+mod hold_review;
+mod onboarding;
+mod orphan_reaper;
+pub mod patreon;
+pub mod premium_trial;
+pub mod reload;
+pub mod scheduler;
+pub mod session_webhooks;
+pub mod systemd;
+mod transcript_translation;
 pub mod types;
 mod voice_message;
 
 pub use error::error_type::Error;
 pub use types::{Context, Data};
 
+/// Get the language to respond in for this context,
+/// falling back to the Discord client's locale for users who haven't picked one.
+pub async fn get_ctx_language(ctx: Context<'_>) -> scripty_i18n::LanguageIdentifier {
+	let guild_locale = match ctx {
+		poise::Context::Application(actx) => actx.interaction.guild_locale.as_deref(),
+		poise::Context::Prefix(_) => None,
+	};
+	scripty_i18n::get_resolved_language_with_locale(
+		ctx.author().id.get(),
+		ctx.guild_id().map(|g| g.get()),
+		ctx.locale(),
+		guild_locale,
+	)
+	.await
+}
+
 pub async fn available_language_autocomplete<'a>(
 	_: Context<'a>,
 	partial: &'a str,
@@ -0,0 +1,113 @@
+//! The message sent when Scripty is added to a server, explaining how to set it up, what it keeps,
+//! and how members consent to being transcribed.
+//!
+//! It goes to the server's system channel, or the owner's DMs if Scripty can't post there, and is
+//! only ever sent once per server, even if Scripty is removed and added again.
+
+use scripty_i18n::LanguageIdentifier;
+use serenity::{
+	all::{ButtonStyle, ChannelId},
+	builder::{CreateActionRow, CreateButton, CreateEmbed, CreateMessage},
+	client::Context,
+	model::{guild::Guild, permissions::Permissions},
+};
+
+/// Send `guild` the onboarding message, unless it's already had it.
+pub async fn send_if_needed(ctx: &Context, guild: &Guild) {
+	let db = scripty_db::get_db();
+	let guild_id = guild.id;
+	// looked up before claiming it, as that stores the guild with the default language. New
+	// servers haven't set one, so go by what their community settings say
+	let language = match scripty_i18n::get_stored_guild_language(guild_id.get()).await {
+		Some(language) => language,
+		None => match scripty_i18n::get_supported_language_for_locale(&guild.preferred_locale) {
+			Some(language) => language,
+			None => scripty_i18n::get_guild_language(guild_id.get()).await,
+		},
+	};
+
+	// claiming it first means a reconnect replaying the join can't send it twice
+	match sqlx::query!(
+		"INSERT INTO guilds (guild_id, onboarding_sent) VALUES ($1, true) ON CONFLICT (guild_id) \
+		 DO UPDATE SET onboarding_sent = true WHERE guilds.onboarding_sent = false",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await
+	{
+		Ok(res) if res.rows_affected() == 0 => return,
+		Ok(_) => {}
+		Err(e) => {
+			error!(%guild_id, "failed to record onboarding message: {}", e);
+			return;
+		}
+	}
+
+	let message = onboarding_message(&language);
+	if let Some(channel_id) = system_channel(ctx, guild) {
+		match channel_id.send_message(ctx, message.clone()).await {
+			Ok(_) => return,
+			Err(e) => debug!(%guild_id, "failed to send onboarding message to channel: {}", e),
+		}
+	}
+	if let Err(e) = guild.owner_id.direct_message(ctx, message).await {
+		// most likely DMs closed. Nobody was told, so try again if Scripty is ever added again
+		debug!(%guild_id, "failed to send onboarding message to owner: {}", e);
+		if let Err(e) = sqlx::query!(
+			"UPDATE guilds SET onboarding_sent = false WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.execute(db)
+		.await
+		{
+			warn!(%guild_id, "failed to forget unsent onboarding message: {}", e);
+		}
+	}
+}
+
+/// The guild's system channel, if Scripty can post the onboarding message there.
+fn system_channel(ctx: &Context, guild: &Guild) -> Option<ChannelId> {
+	let channel = guild.channels.get(&guild.system_channel_id?)?;
+	let member = guild.members.get(&ctx.cache.current_user().id)?;
+	let required_permissions =
+		Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS;
+	guild
+		.user_permissions_in(channel, member)
+		.contains(required_permissions)
+		.then_some(channel.id)
+}
+
+fn onboarding_message(language: &LanguageIdentifier) -> CreateMessage {
+	let embed = CreateEmbed::default()
+		.title(format_message!(language, "onboarding-title"))
+		.description(format_message!(language, "onboarding-description"))
+		.field(
+			format_message!(language, "onboarding-setup-title"),
+			format_message!(language, "onboarding-setup-description"),
+			false,
+		)
+		.field(
+			format_message!(language, "onboarding-privacy-title"),
+			format_message!(language, "onboarding-privacy-description"),
+			false,
+		)
+		.field(
+			format_message!(language, "onboarding-consent-title"),
+			format_message!(language, "onboarding-consent-description"),
+			false,
+		);
+	let buttons = CreateActionRow::Buttons(vec![
+		CreateButton::new_link(&scripty_config::get_config().support_invite)
+			.label(format_message!(language, "onboarding-support-button")),
+		CreateButton::new_link("https://scripty.org/privacy")
+			.label(format_message!(language, "consent-notice-privacy-button"))
+			.style(ButtonStyle::Secondary),
+		CreateButton::new_link("https://scripty.org/terms")
+			.label(format_message!(language, "consent-notice-terms-button"))
+			.style(ButtonStyle::Secondary),
+	]);
+
+	CreateMessage::default()
+		.embed(embed)
+		.components(vec![buttons])
+}
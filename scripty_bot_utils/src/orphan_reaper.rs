@@ -0,0 +1,158 @@
+//! Finds and removes data that's been left behind: mirrors of channels that were deleted, queued
+//! deliveries to webhooks that were deleted, and cached data storage choices of users that have no
+//! row.
+//!
+//! Sessions left behind by crashes are handled by the audio handler. All of it is run by the
+//! `OrphanReaper` background task.
+
+use std::collections::HashSet;
+
+use serenity::{
+	client::Context,
+	http::{HttpError, StatusCode},
+	model::id::{ChannelId, WebhookId},
+};
+
+use crate::Error;
+
+/// How many channels, or webhooks, are checked with Discord per run, to stay well within rate
+/// limits. They're picked at random, so everything is checked eventually.
+const CHECKS_PER_RUN: i64 = 50;
+
+/// Cache keys of users' data storage choices, from `scripty_data_storage`. Users with no row have
+/// `false` cached, so these build up as users come and go.
+const USER_CACHE_KEYS: &str = "user:{*}:store_*";
+
+/// How many keys to ask Redis for at a time.
+const SCAN_COUNT: usize = 500;
+
+/// Delete mirrors of channels that no longer exist. Returns how many rows were deleted.
+pub(crate) async fn reap_deleted_channels(ctx: &Context) -> Result<u64, Error> {
+	let db = scripty_db::get_db();
+	let channels = sqlx::query!(
+		"SELECT channel_id AS \"channel_id!\" FROM (SELECT channel_id FROM transcript_mirrors \
+		 UNION SELECT voice_channel_id FROM transcript_mirrors) c ORDER BY random() LIMIT $1",
+		CHECKS_PER_RUN
+	)
+	.fetch_all(db)
+	.await?;
+
+	let mut deleted = 0;
+	for channel in channels {
+		let channel_id = ChannelId::new(channel.channel_id as u64);
+		if !is_not_found(ctx.http.get_channel(channel_id).await) {
+			continue;
+		}
+		deleted += sqlx::query!(
+			"DELETE FROM transcript_mirrors WHERE channel_id = $1 OR voice_channel_id = $1",
+			channel.channel_id
+		)
+		.execute(db)
+		.await?
+		.rows_affected();
+	}
+	Ok(deleted)
+}
+
+/// Delete queued deliveries to webhooks that no longer exist. Returns how many were deleted.
+pub(crate) async fn reap_deleted_webhooks(ctx: &Context) -> Result<u64, Error> {
+	let db = scripty_db::get_db();
+	// 0 is `DeliveryTarget::Webhook`
+	let webhooks = sqlx::query!(
+		"SELECT target_id FROM (SELECT DISTINCT target_id FROM delivery_queue WHERE target_kind = \
+		 0) w ORDER BY random() LIMIT $1",
+		CHECKS_PER_RUN
+	)
+	.fetch_all(db)
+	.await?;
+
+	let mut deleted = 0;
+	for webhook in webhooks {
+		let webhook_id = WebhookId::new(webhook.target_id as u64);
+		if !is_not_found(ctx.http.get_webhook(webhook_id).await) {
+			continue;
+		}
+		deleted += sqlx::query!(
+			"DELETE FROM delivery_queue WHERE target_kind = 0 AND target_id = $1",
+			webhook.target_id
+		)
+		.execute(db)
+		.await?
+		.rows_affected();
+	}
+	Ok(deleted)
+}
+
+/// Delete cached data storage choices of users with no row. Returns how many keys were deleted.
+pub(crate) async fn reap_user_cache_keys() -> Result<u64, Error> {
+	let mut cursor = 0_u64;
+	let mut deleted = 0;
+	loop {
+		let (next, keys): (u64, Vec<String>) = scripty_redis::run_transaction("SCAN", |cmd| {
+			cmd.arg(cursor)
+				.arg("MATCH")
+				.arg(USER_CACHE_KEYS)
+				.arg("COUNT")
+				.arg(SCAN_COUNT);
+		})
+		.await?;
+		deleted += delete_orphaned_keys(keys).await?;
+
+		if next == 0 {
+			return Ok(deleted);
+		}
+		cursor = next;
+	}
+}
+
+async fn delete_orphaned_keys(keys: Vec<String>) -> Result<u64, Error> {
+	// keys look like `user:{<hex encoded user ID>}:store_audio`
+	let (keys, user_ids): (Vec<String>, Vec<String>) = keys
+		.into_iter()
+		.filter_map(|key| {
+			let user_id = key.strip_prefix("user:{")?.split_once('}')?.0;
+			let is_hex = user_id.len() % 2 == 0 && user_id.bytes().all(|b| b.is_ascii_hexdigit());
+			let user_id = is_hex.then(|| user_id.to_string())?;
+			Some((key, user_id))
+		})
+		.unzip();
+	if keys.is_empty() {
+		return Ok(0);
+	}
+
+	let missing: HashSet<String> = sqlx::query!(
+		"SELECT id AS \"id!\" FROM unnest($1::TEXT[]) id WHERE NOT EXISTS (SELECT 1 FROM users u \
+		 WHERE u.user_id = decode(id, 'hex'))",
+		&user_ids
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?
+	.into_iter()
+	.map(|row| row.id)
+	.collect();
+	let orphaned: Vec<String> = keys
+		.into_iter()
+		.zip(user_ids)
+		.filter(|(_, user_id)| missing.contains(user_id))
+		.map(|(key, _)| key)
+		.collect();
+	if orphaned.is_empty() {
+		return Ok(0);
+	}
+
+	let deleted = scripty_redis::run_transaction::<u64>("DEL", move |cmd| {
+		cmd.arg(orphaned);
+	})
+	.await?;
+	Ok(deleted)
+}
+
+/// Whether Discord said what was asked for doesn't exist, as opposed to any other error,
+/// like Scripty not being allowed to see it.
+fn is_not_found<T>(res: Result<T, serenity::Error>) -> bool {
+	matches!(
+		res,
+		Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(ref e)))
+			if e.status_code == StatusCode::NOT_FOUND
+	)
+}
@@ -0,0 +1,285 @@
+//! Linking Patreon accounts to Discord users, and granting premium for their pledges.
+//!
+//! Pledges arrive through a webhook whether or not the patron has linked their account yet,
+//! so they're stored per Patreon account, and copied over to the user when they link.
+//!
+//! Users can have premium some other way too, so a pledge only ever raises their premium,
+//! and only lowers or removes premium it gave them itself.
+
+use std::{fmt, time::Duration};
+
+use serde::Deserialize;
+use serenity::model::id::UserId;
+use time::OffsetDateTime;
+
+/// How long a `/premium link` URL stays valid.
+pub const LINK_STATE_TTL: Duration = Duration::from_secs(60 * 15);
+
+#[derive(Debug)]
+pub enum PatreonError {
+	/// There's no `patreon` section in the config.
+	NotConfigured,
+	/// The link expired, was already used, or never existed.
+	InvalidState,
+	Redis(scripty_redis::TransactionError),
+	Http(reqwest::Error),
+	Database(sqlx::Error),
+}
+
+impl fmt::Display for PatreonError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotConfigured => f.write_str("Patreon isn't configured"),
+			Self::InvalidState => f.write_str("link state is invalid or expired"),
+			Self::Redis(e) => write!(f, "redis error: {}", e),
+			Self::Http(e) => write!(f, "Patreon API error: {}", e),
+			Self::Database(e) => write!(f, "database error: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for PatreonError {}
+
+impl From<scripty_redis::TransactionError> for PatreonError {
+	fn from(e: scripty_redis::TransactionError) -> Self {
+		Self::Redis(e)
+	}
+}
+
+impl From<reqwest::Error> for PatreonError {
+	fn from(e: reqwest::Error) -> Self {
+		Self::Http(e)
+	}
+}
+
+impl From<sqlx::Error> for PatreonError {
+	fn from(e: sqlx::Error) -> Self {
+		Self::Database(e)
+	}
+}
+
+fn get_patreon_config() -> Result<&'static scripty_config::PatreonConfig, PatreonError> {
+	scripty_config::get_config()
+		.patreon
+		.as_ref()
+		.ok_or(PatreonError::NotConfigured)
+}
+
+fn link_state_key(state: &str) -> String {
+	format!("patreon_link:{{{}}}", state)
+}
+
+/// Start linking `user_id` to a Patreon account. Returns the URL to send them to,
+/// which is valid for `LINK_STATE_TTL`.
+pub async fn start_link(user_id: UserId) -> Result<String, PatreonError> {
+	let cfg = get_patreon_config()?;
+
+	// the state comes back to us with the OAuth code, and is the only thing tying it to the user
+	let state = uuid::Uuid::new_v4().simple().to_string();
+	scripty_redis::run_transaction::<()>("SETEX", |cmd| {
+		cmd.arg(link_state_key(&state))
+			.arg(LINK_STATE_TTL.as_secs())
+			.arg(user_id.get());
+	})
+	.await?;
+
+	let mut url = reqwest::Url::parse("https://www.patreon.com/oauth2/authorize")
+		.expect("static URL is valid");
+	url.query_pairs_mut()
+		.append_pair("response_type", "code")
+		.append_pair("client_id", &cfg.client_id)
+		.append_pair("redirect_uri", &cfg.redirect_uri)
+		.append_pair("scope", "identity")
+		.append_pair("state", &state);
+	Ok(url.into())
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+	access_token: String,
+}
+
+#[derive(Deserialize)]
+struct IdentityResponse {
+	data: IdentityData,
+}
+
+#[derive(Deserialize)]
+struct IdentityData {
+	id: String,
+}
+
+/// Exchange an OAuth `code` for the ID of the Patreon account that granted it.
+async fn fetch_patreon_user_id(
+	cfg: &scripty_config::PatreonConfig,
+	code: &str,
+) -> Result<String, reqwest::Error> {
+	let client = reqwest::Client::new();
+	let TokenResponse { access_token } = client
+		.post("https://www.patreon.com/api/oauth2/token")
+		.form(&[
+			("code", code),
+			("grant_type", "authorization_code"),
+			("client_id", &cfg.client_id),
+			("client_secret", &cfg.client_secret),
+			("redirect_uri", &cfg.redirect_uri),
+		])
+		.send()
+		.await?
+		.error_for_status()?
+		.json()
+		.await?;
+
+	let IdentityResponse { data } = client
+		.get("https://www.patreon.com/api/oauth2/v2/identity")
+		.bearer_auth(access_token)
+		.send()
+		.await?
+		.error_for_status()?
+		.json()
+		.await?;
+	Ok(data.id)
+}
+
+/// Finish linking a Patreon account, once Patreon sends the user back with `code` and `state`.
+///
+/// A user can only have one Patreon account linked: linking another replaces it.
+/// Returns who was linked, and the premium tier their pledge gives them.
+pub async fn finish_link(code: &str, state: &str) -> Result<(UserId, i16), PatreonError> {
+	let cfg = get_patreon_config()?;
+
+	let user_id = scripty_redis::run_transaction::<Option<u64>>("GETDEL", |cmd| {
+		cmd.arg(link_state_key(state));
+	})
+	.await?
+	.filter(|id| *id != 0)
+	.map(UserId::new)
+	.ok_or(PatreonError::InvalidState)?;
+	let patreon_user_id = fetch_patreon_user_id(cfg, code).await?;
+
+	scripty_utils::rehash_user_id(user_id.get()).await?;
+	let hashed_user_id = scripty_utils::hash_user_id(user_id.get());
+	let mut tx = scripty_db::get_db().begin().await?;
+
+	sqlx::query!(
+		"INSERT INTO users (user_id) VALUES ($1) ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING",
+		hashed_user_id
+	)
+	.execute(&mut *tx)
+	.await?;
+	// linking another account replaces what the old one gave them
+	let previous = sqlx::query!(
+		"UPDATE patreon_members SET user_id = NULL WHERE user_id = $1 AND patreon_user_id != $2 \
+		 RETURNING premium_level, premium_expiry",
+		hashed_user_id,
+		patreon_user_id
+	)
+	.fetch_optional(&mut *tx)
+	.await?
+	.map(|row| (row.premium_level, row.premium_expiry));
+	// the pledge webhook may well have come in before they linked
+	let member = sqlx::query!(
+		"INSERT INTO patreon_members (patreon_user_id, user_id) VALUES ($1, $2) ON CONFLICT \
+		 (patreon_user_id) DO UPDATE SET user_id = $2 RETURNING premium_level, premium_expiry",
+		patreon_user_id,
+		hashed_user_id
+	)
+	.fetch_one(&mut *tx)
+	.await?;
+	apply_pledge(
+		&mut tx,
+		&hashed_user_id,
+		member.premium_level,
+		member.premium_expiry,
+		previous,
+	)
+	.await?;
+
+	tx.commit().await?;
+	Ok((user_id, member.premium_level))
+}
+
+/// The premium tier for a patron entitled to `tier_ids`, per the configured tier mapping.
+///
+/// Tiers that aren't in the mapping give nothing.
+pub fn premium_level_for_tiers<'a>(tier_ids: impl IntoIterator<Item = &'a str>) -> i16 {
+	let Ok(cfg) = get_patreon_config() else {
+		return 0;
+	};
+	tier_ids
+		.into_iter()
+		.filter_map(|id| cfg.tiers.get(id).copied())
+		.max()
+		.unwrap_or(0)
+}
+
+/// Record a patron's current pledge, and apply it to the user they've linked, if any.
+///
+/// `premium_expiry` is pushed back by the configured grace period.
+/// Returns whether the patron has linked a user.
+pub async fn update_member(
+	patreon_user_id: &str,
+	premium_level: i16,
+	premium_expiry: Option<OffsetDateTime>,
+) -> Result<bool, PatreonError> {
+	let cfg = get_patreon_config()?;
+	let premium_expiry = premium_expiry.map(|t| t + time::Duration::days(cfg.grace_days));
+
+	let mut tx = scripty_db::get_db().begin().await?;
+	let previous = sqlx::query!(
+		"SELECT premium_level, premium_expiry FROM patreon_members WHERE patreon_user_id = $1 FOR \
+		 UPDATE",
+		patreon_user_id
+	)
+	.fetch_optional(&mut *tx)
+	.await?
+	.map(|row| (row.premium_level, row.premium_expiry));
+	let member = sqlx::query!(
+		"INSERT INTO patreon_members (patreon_user_id, premium_level, premium_expiry) VALUES ($1, \
+		 $2, $3) ON CONFLICT (patreon_user_id) DO UPDATE SET premium_level = $2, premium_expiry = \
+		 $3, updated_at = NOW() RETURNING user_id",
+		patreon_user_id,
+		premium_level,
+		premium_expiry
+	)
+	.fetch_one(&mut *tx)
+	.await?;
+
+	let Some(user_id) = member.user_id else {
+		tx.commit().await?;
+		return Ok(false);
+	};
+	apply_pledge(&mut tx, &user_id, premium_level, premium_expiry, previous).await?;
+
+	tx.commit().await?;
+	Ok(true)
+}
+
+/// Give a linked user the premium their pledge grants, without touching premium they have some
+/// other way.
+///
+/// It's only written if it's higher than what they have, or if what they have is `previous`, the
+/// premium their pledge gave them before.
+async fn apply_pledge(
+	tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+	hashed_user_id: &[u8],
+	premium_level: i16,
+	premium_expiry: Option<OffsetDateTime>,
+	previous: Option<(i16, Option<OffsetDateTime>)>,
+) -> Result<(), sqlx::Error> {
+	let (previous_level, previous_expiry) = previous.unzip();
+	sqlx::query!(
+		"UPDATE users SET premium_level = $2, premium_expiry = $3, is_trialing = false WHERE \
+		 user_id = $1 AND (premium_level < $2 OR premium_expiry < NOW() OR (is_trialing AND $2 > \
+		 0) OR (NOT is_trialing AND premium_level = $4 AND premium_expiry IS NOT DISTINCT FROM \
+		 $5))",
+		hashed_user_id,
+		premium_level,
+		premium_expiry,
+		previous_level,
+		previous_expiry.flatten()
+	)
+	.execute(&mut **tx)
+	.await?;
+	Ok(())
+}
@@ -0,0 +1,156 @@
+//! One-time premium trials for guilds, started with `/premium trial`.
+//!
+//! Each guild, and each user starting one, only ever gets one trial.
+//! The trial itself ends on its own (see `scripty_premium::get_guild`):
+//! the scheduled jobs here just warn the guild beforehand, and tell it once it's over.
+
+use scripty_utils::to_primitive;
+use serenity::{
+	client::Context,
+	model::id::{ChannelId, GuildId, UserId},
+};
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+	scheduler::{self, Job, JobHandler},
+	Error,
+};
+
+/// How long a trial lasts.
+pub const TRIAL_LENGTH: Duration = Duration::days(7);
+
+/// How long before the trial ends to warn the guild.
+const REMINDER_BEFORE_END: Duration = Duration::days(1);
+
+const REMINDER_JOB: &str = "premium_trial_reminder";
+const ENDED_JOB: &str = "premium_trial_ended";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrialOutcome {
+	/// The trial started, and ends at this time.
+	Started(OffsetDateTime),
+	/// The guild has already had its trial.
+	GuildUsed,
+	/// The user has already started a trial, in some guild.
+	UserUsed,
+	/// The guild hasn't agreed to the ToS yet, so it has no row to put the trial in.
+	NotSetUp,
+}
+
+/// Start `guild_id`'s trial, on behalf of `user_id`.
+///
+/// Reminders are sent to `channel_id`, normally where the trial was started from.
+pub async fn start_trial(
+	guild_id: GuildId,
+	user_id: UserId,
+	channel_id: ChannelId,
+) -> Result<TrialOutcome, Error> {
+	scripty_utils::rehash_user_id(user_id.get()).await?;
+	let ends_at = OffsetDateTime::now_utc() + TRIAL_LENGTH;
+	let mut tx = scripty_db::get_db().begin().await?;
+
+	let guild = sqlx::query!(
+		"SELECT trial_used FROM guilds WHERE guild_id = $1 FOR UPDATE",
+		guild_id.get() as i64
+	)
+	.fetch_optional(&mut *tx)
+	.await?;
+	match guild {
+		None => return Ok(TrialOutcome::NotSetUp),
+		Some(guild) if guild.trial_used => return Ok(TrialOutcome::GuildUsed),
+		Some(_) => {}
+	}
+
+	// alt accounts can still get around this, but it stops one person trialing every server they
+	// own
+	let claimed_user = sqlx::query!(
+		"INSERT INTO users (user_id, trial_used) VALUES ($1, true) ON CONFLICT ON CONSTRAINT \
+		 users_pkey DO UPDATE SET trial_used = true WHERE users.trial_used = false RETURNING \
+		 user_id",
+		scripty_utils::hash_user_id(user_id.get())
+	)
+	.fetch_optional(&mut *tx)
+	.await?;
+	if claimed_user.is_none() {
+		return Ok(TrialOutcome::UserUsed);
+	}
+
+	sqlx::query!(
+		"UPDATE guilds SET trial_used = true, trial_expires_at = $2 WHERE guild_id = $1",
+		guild_id.get() as i64,
+		to_primitive(ends_at)
+	)
+	.execute(&mut *tx)
+	.await?;
+	tx.commit().await?;
+
+	let payload = format!("{}:{}", guild_id, channel_id);
+	scheduler::schedule_once(REMINDER_JOB, &payload, ends_at - REMINDER_BEFORE_END).await?;
+	scheduler::schedule_once(ENDED_JOB, &payload, ends_at).await?;
+
+	Ok(TrialOutcome::Started(ends_at))
+}
+
+/// Register the handlers for trial reminder jobs.
+pub fn register_job_handlers() {
+	scheduler::register_job_handler(REMINDER_JOB, TrialNotice { ended: false });
+	scheduler::register_job_handler(ENDED_JOB, TrialNotice { ended: true });
+}
+
+struct TrialNotice {
+	ended: bool,
+}
+
+#[async_trait]
+impl JobHandler for TrialNotice {
+	async fn run(&self, ctx: &Context, job: &Job) -> Result<(), Error> {
+		let Some((guild_id, channel_id)) = parse_payload(&job.payload) else {
+			// retrying won't help, so just drop it
+			warn!(id = job.id, payload = %job.payload, "invalid premium trial job payload");
+			return Ok(());
+		};
+
+		let trial = sqlx::query!(
+			"SELECT trial_expires_at, premium_owner_id IS NOT NULL AS \"has_premium!\" FROM \
+			 guilds WHERE guild_id = $1",
+			guild_id.get() as i64
+		)
+		.fetch_optional(scripty_db::get_db())
+		.await?;
+		let Some(trial) = trial else {
+			// the guild's data was deleted
+			return Ok(());
+		};
+		if trial.has_premium {
+			// they subscribed, so there's nothing to warn them about
+			return Ok(());
+		}
+
+		let language = scripty_i18n::get_guild_language(guild_id.get()).await;
+		let message = if self.ended {
+			format_message!(language, "premium-trial-ended")
+		} else {
+			let Some(ends_at) = trial.trial_expires_at else {
+				return Ok(());
+			};
+			format_message!(
+				language,
+				"premium-trial-ending",
+				endsAt: ends_at.assume_utc().unix_timestamp()
+			)
+		};
+
+		if let Err(e) = channel_id.say(ctx, message).await {
+			// the channel may well be gone after a week
+			debug!(%guild_id, "failed to send premium trial notice: {}", e);
+		}
+		Ok(())
+	}
+}
+
+fn parse_payload(payload: &str) -> Option<(GuildId, ChannelId)> {
+	let (guild_id, channel_id) = payload.split_once(':')?;
+	let guild_id = guild_id.parse::<u64>().ok().filter(|id| *id != 0)?;
+	let channel_id = channel_id.parse::<u64>().ok().filter(|id| *id != 0)?;
+	Some((GuildId::new(guild_id), ChannelId::new(channel_id)))
+}
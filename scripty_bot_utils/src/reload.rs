@@ -0,0 +1,53 @@
+//! Runtime config reloading, triggered by `SIGHUP` or the `admin reload` command.
+
+use std::fmt;
+
+use tokio::signal::unix::{signal, SignalKind};
+
+/// Re-read the config file, and apply everything that can safely change at runtime.
+///
+/// Log filters apply immediately, the status text applies on the next status update,
+/// and the STT worker pool is rebuilt from the new service list.
+pub async fn reload_config() -> Result<(), ConfigReloadError> {
+	scripty_config::reload_config().map_err(ConfigReloadError::Config)?;
+	scripty_stt::reload_stt()
+		.await
+		.map_err(ConfigReloadError::Stt)?;
+	info!("reloaded config");
+	Ok(())
+}
+
+/// Reload the config every time this process receives `SIGHUP`.
+pub async fn reload_on_sighup() {
+	let mut hangup = match signal(SignalKind::hangup()) {
+		Ok(s) => s,
+		Err(e) => {
+			error!("failed to listen for SIGHUP: {}", e);
+			return;
+		}
+	};
+
+	while hangup.recv().await.is_some() {
+		info!("got SIGHUP, reloading config");
+		if let Err(e) = reload_config().await {
+			error!("failed to reload config: {}", e);
+		}
+	}
+}
+
+#[derive(Debug)]
+pub enum ConfigReloadError {
+	Config(scripty_config::ReloadError),
+	Stt(scripty_stt::ModelError),
+}
+
+impl fmt::Display for ConfigReloadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Config(e) => write!(f, "{}", e),
+			Self::Stt(e) => write!(f, "failed to connect to STT services: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for ConfigReloadError {}
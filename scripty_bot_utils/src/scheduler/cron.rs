@@ -0,0 +1,307 @@
+//! A minimal cron expression parser.
+//!
+//! Supports the standard five fields (minute, hour, day of month, month, day of week),
+//! each made up of comma-separated `*`, `n`, `a-b` and `x/step` parts,
+//! plus the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shorthands.
+//! Names (ie `MON` or `JAN`) are not supported. All times are in UTC.
+
+use std::{fmt, str::FromStr};
+
+use time::{Date, Duration, Month, OffsetDateTime, Time, UtcOffset};
+
+/// How far ahead to look for the next run before giving up (ie for `0 0 30 2 *`).
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+	source:         String,
+	minutes:        u64,
+	hours:          u64,
+	days_of_month:  u64,
+	months:         u64,
+	days_of_week:   u64,
+	/// If both day fields are restricted, cron runs when *either* matches.
+	dom_restricted: bool,
+	dow_restricted: bool,
+}
+
+impl CronSchedule {
+	/// The next time this schedule fires, strictly after `after`.
+	///
+	/// Returns None if the schedule never fires in the next five years.
+	pub fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+		let after = after.to_offset(UtcOffset::UTC);
+		let mut t = after
+			.replace_time(Time::from_hms(after.hour(), after.minute(), 0).ok()?)
+			.checked_add(Duration::minutes(1))?;
+		let limit = t.checked_add(Duration::days(MAX_LOOKAHEAD_DAYS))?;
+
+		while t < limit {
+			if !has_bit(self.months, u8::from(t.month()) as u32) {
+				t = start_of_next_month(t.date())?;
+			} else if !self.day_matches(t.date()) {
+				t = t.replace_time(Time::MIDNIGHT) + Duration::days(1);
+			} else if !has_bit(self.hours, t.hour() as u32) {
+				t = t.replace_time(Time::from_hms(t.hour(), 0, 0).ok()?) + Duration::hours(1);
+			} else if !has_bit(self.minutes, t.minute() as u32) {
+				t += Duration::minutes(1);
+			} else {
+				return Some(t);
+			}
+		}
+
+		None
+	}
+
+	fn day_matches(&self, date: Date) -> bool {
+		let dom = has_bit(self.days_of_month, date.day() as u32);
+		let dow = has_bit(
+			self.days_of_week,
+			date.weekday().number_days_from_sunday() as u32,
+		);
+		if self.dom_restricted && self.dow_restricted {
+			dom || dow
+		} else {
+			dom && dow
+		}
+	}
+}
+
+impl FromStr for CronSchedule {
+	type Err = CronParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let expanded = match s.trim() {
+			"@hourly" => "0 * * * *",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@weekly" => "0 0 * * 0",
+			"@monthly" => "0 0 1 * *",
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			other => other,
+		};
+
+		let fields: Vec<&str> = expanded.split_whitespace().collect();
+		let [minute, hour, dom, month, dow] = fields[..] else {
+			return Err(CronParseError::FieldCount(fields.len()));
+		};
+
+		let mut days_of_week = parse_field(dow, 0, 7)?;
+		// both 0 and 7 are sunday
+		if has_bit(days_of_week, 7) {
+			days_of_week |= 1;
+		}
+
+		Ok(Self {
+			source: s.trim().to_string(),
+			minutes: parse_field(minute, 0, 59)?,
+			hours: parse_field(hour, 0, 23)?,
+			days_of_month: parse_field(dom, 1, 31)?,
+			months: parse_field(month, 1, 12)?,
+			days_of_week,
+			dom_restricted: dom != "*",
+			dow_restricted: dow != "*",
+		})
+	}
+}
+
+impl fmt::Display for CronSchedule {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.source)
+	}
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, CronParseError> {
+	let invalid = || CronParseError::InvalidField(field.to_string());
+	let parse_num = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+
+	let mut bits = 0;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => (range, Some(parse_num(step)?)),
+			None => (part, None),
+		};
+		if step == Some(0) {
+			return Err(invalid());
+		}
+
+		let (start, end) = match range.split_once('-') {
+			_ if range == "*" => (min, max),
+			Some((start, end)) => (parse_num(start)?, parse_num(end)?),
+			// `5/15` means "every 15, starting at 5"
+			None if step.is_some() => (parse_num(range)?, max),
+			None => {
+				let n = parse_num(range)?;
+				(n, n)
+			}
+		};
+		if start < min || end > max || start > end {
+			return Err(CronParseError::OutOfRange {
+				field: field.to_string(),
+				min,
+				max,
+			});
+		}
+
+		for n in (start..=end).step_by(step.unwrap_or(1) as usize) {
+			bits |= 1 << n;
+		}
+	}
+
+	Ok(bits)
+}
+
+fn has_bit(bits: u64, n: u32) -> bool {
+	bits & (1 << n) != 0
+}
+
+fn start_of_next_month(date: Date) -> Option<OffsetDateTime> {
+	let (year, month) = match date.month() {
+		Month::December => (date.year() + 1, Month::January),
+		month => (date.year(), month.next()),
+	};
+	Some(
+		Date::from_calendar_date(year, month, 1)
+			.ok()?
+			.midnight()
+			.assume_utc(),
+	)
+}
+
+#[derive(Debug)]
+pub enum CronParseError {
+	FieldCount(usize),
+	InvalidField(String),
+	OutOfRange {
+		field: String,
+		min:   u32,
+		max:   u32,
+	},
+}
+
+impl fmt::Display for CronParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::FieldCount(n) => write!(f, "expected 5 fields, got {}", n),
+			Self::InvalidField(field) => write!(f, "invalid field `{}`", field),
+			Self::OutOfRange { field, min, max } => {
+				write!(f, "field `{}` is outside {}-{}", field, min, max)
+			}
+		}
+	}
+}
+
+impl std::error::Error for CronParseError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn utc(year: i32, month: Month, day: u8, hour: u8, minute: u8) -> OffsetDateTime {
+		Date::from_calendar_date(year, month, day)
+			.unwrap()
+			.with_hms(hour, minute, 0)
+			.unwrap()
+			.assume_utc()
+	}
+
+	fn bits(ns: &[u32]) -> u64 {
+		ns.iter().fold(0, |bits, &n| bits | 1 << n)
+	}
+
+	#[test]
+	fn test_ranges() {
+		assert_eq!(
+			parse_field("9-17", 0, 23).unwrap(),
+			bits(&[9, 10, 11, 12, 13, 14, 15, 16, 17])
+		);
+		assert_eq!(parse_field("3", 0, 23).unwrap(), bits(&[3]));
+		assert_eq!(
+			parse_field("*", 1, 12).unwrap(),
+			bits(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12])
+		);
+	}
+
+	#[test]
+	fn test_steps() {
+		assert_eq!(parse_field("*/15", 0, 59).unwrap(), bits(&[0, 15, 30, 45]));
+		assert_eq!(parse_field("5/20", 0, 59).unwrap(), bits(&[5, 25, 45]));
+		assert_eq!(parse_field("10-30/10", 0, 59).unwrap(), bits(&[10, 20, 30]));
+	}
+
+	#[test]
+	fn test_lists() {
+		assert_eq!(parse_field("0,30", 0, 59).unwrap(), bits(&[0, 30]));
+		assert_eq!(
+			parse_field("1-3,10,*/20", 0, 59).unwrap(),
+			bits(&[0, 1, 2, 3, 10, 20, 40])
+		);
+	}
+
+	#[test]
+	fn test_sunday_is_0_and_7() {
+		let schedule: CronSchedule = "0 0 * * 7".parse().unwrap();
+		assert!(has_bit(schedule.days_of_week, 0));
+	}
+
+	#[test]
+	fn test_invalid() {
+		for expr in [
+			"",
+			"* * * *",
+			"* * * * * *",
+			"60 * * * *",
+			"* 24 * * *",
+			"* * 0 * *",
+			"* * * 13 *",
+			"* * * * 8",
+			"*/0 * * * *",
+			"5-1 * * * *",
+			"a * * * *",
+			"1,,2 * * * *",
+			"MON * * * *",
+		] {
+			assert!(
+				expr.parse::<CronSchedule>().is_err(),
+				"`{}` should be invalid",
+				expr
+			);
+		}
+	}
+
+	#[test]
+	fn test_next_after() {
+		// 2024-01-01 is a monday
+		let now = utc(2024, Month::January, 1, 12, 34);
+		let next = |expr: &str| expr.parse::<CronSchedule>().unwrap().next_after(now);
+
+		assert_eq!(next("@hourly"), Some(utc(2024, Month::January, 1, 13, 0)));
+		assert_eq!(next("@daily"), Some(utc(2024, Month::January, 2, 0, 0)));
+		assert_eq!(
+			next("*/15 * * * *"),
+			Some(utc(2024, Month::January, 1, 12, 45))
+		);
+		assert_eq!(
+			next("30 9 * * 3"),
+			Some(utc(2024, Month::January, 3, 9, 30))
+		);
+		assert_eq!(next("0 0 1 * *"), Some(utc(2024, Month::February, 1, 0, 0)));
+		assert_eq!(
+			next("0 0 29 2 *"),
+			Some(utc(2024, Month::February, 29, 0, 0))
+		);
+		// either day field matches when both are restricted
+		assert_eq!(next("0 0 15 * 5"), Some(utc(2024, Month::January, 5, 0, 0)));
+		// never fires
+		assert_eq!(next("0 0 30 2 *"), None);
+	}
+
+	#[test]
+	fn test_next_after_is_strictly_after() {
+		let now = utc(2024, Month::January, 1, 12, 0);
+		let schedule: CronSchedule = "0 12 * * *".parse().unwrap();
+		assert_eq!(
+			schedule.next_after(now),
+			Some(utc(2024, Month::January, 2, 12, 0))
+		);
+	}
+}
@@ -0,0 +1,275 @@
+//! Persistent job scheduler.
+//!
+//! Jobs live in Postgres, so they survive restarts, and are leased while running,
+//! so with several processes running only one of them picks up any given job.
+//!
+//! How to use:
+//! 1) Implement `JobHandler` for a struct, and register it with `register_job_handler`.
+//! 2) Queue jobs of that kind with `schedule_once`, or `schedule_recurring` for cron-like jobs.
+//!
+//! Jobs of a kind with no registered handler are left alone, so another process can run them.
+
+mod cron;
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use scripty_utils::to_primitive;
+use serenity::client::Context;
+use time::{Duration, OffsetDateTime};
+
+pub use self::cron::{CronParseError, CronSchedule};
+use crate::Error;
+
+/// Maximum number of jobs claimed in one poll.
+const MAX_JOBS_PER_POLL: i64 = 16;
+
+/// How many times a one-shot job is tried before it is dropped.
+const MAX_ATTEMPTS: i32 = 5;
+
+/// How often the lease of a running job is extended. Leases last 5 minutes.
+const LEASE_RENEW_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+static HANDLERS: Lazy<DashMap<&'static str, Arc<dyn JobHandler>>> = Lazy::new(DashMap::new);
+
+/// A job, as passed to its handler.
+#[derive(Debug)]
+pub struct Job {
+	pub id:       i64,
+	/// Only set for recurring jobs.
+	pub name:     Option<String>,
+	pub kind:     String,
+	pub payload:  String,
+	/// How many times this job has failed in a row.
+	pub attempts: i32,
+}
+
+#[async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+	/// Run the job. Failed one-shot jobs are retried with backoff,
+	/// failed recurring jobs just wait for their next run.
+	async fn run(&self, ctx: &Context, job: &Job) -> Result<(), Error>;
+}
+
+/// Register the handler for jobs of `kind`, replacing any existing one.
+pub fn register_job_handler(kind: &'static str, handler: impl JobHandler) {
+	if HANDLERS.insert(kind, Arc::new(handler)).is_some() {
+		warn!(%kind, "replaced existing job handler");
+	}
+}
+
+/// Queue a job to run once, at or shortly after `run_at`. Returns the ID of the new job.
+pub async fn schedule_once(
+	kind: &str,
+	payload: &str,
+	run_at: OffsetDateTime,
+) -> Result<i64, sqlx::Error> {
+	sqlx::query!(
+		"INSERT INTO scheduled_jobs (kind, payload, next_run) VALUES ($1, $2, $3) RETURNING id",
+		kind,
+		payload,
+		to_primitive(run_at)
+	)
+	.fetch_one(scripty_db::get_db())
+	.await
+	.map(|row| row.id)
+}
+
+/// Create or update the recurring job `name`.
+///
+/// Safe to call on every startup: if the schedule hasn't changed, the next run is kept as-is.
+pub async fn schedule_recurring(
+	name: &str,
+	kind: &str,
+	payload: &str,
+	schedule: &CronSchedule,
+) -> Result<(), Error> {
+	let next_run = schedule
+		.next_after(OffsetDateTime::now_utc())
+		.ok_or_else(|| Error::custom(format!("cron schedule `{}` never fires", schedule)))?;
+
+	sqlx::query!(
+		"INSERT INTO scheduled_jobs (name, kind, payload, cron, next_run) VALUES ($1, $2, $3, $4, \
+		 $5) ON CONFLICT (name) DO UPDATE SET kind = EXCLUDED.kind, payload = EXCLUDED.payload, \
+		 cron = EXCLUDED.cron, next_run = CASE WHEN scheduled_jobs.cron IS DISTINCT FROM \
+		 EXCLUDED.cron THEN EXCLUDED.next_run ELSE scheduled_jobs.next_run END",
+		name,
+		kind,
+		payload,
+		schedule.to_string(),
+		to_primitive(next_run)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(())
+}
+
+/// Cancel a job by ID. Returns true if it existed.
+pub async fn cancel_job(id: i64) -> Result<bool, sqlx::Error> {
+	sqlx::query!("DELETE FROM scheduled_jobs WHERE id = $1", id)
+		.execute(scripty_db::get_db())
+		.await
+		.map(|res| res.rows_affected() > 0)
+}
+
+/// Cancel a recurring job by name. Returns true if it existed.
+pub async fn cancel_recurring(name: &str) -> Result<bool, sqlx::Error> {
+	sqlx::query!("DELETE FROM scheduled_jobs WHERE name = $1", name)
+		.execute(scripty_db::get_db())
+		.await
+		.map(|res| res.rows_affected() > 0)
+}
+
+/// Claim all due jobs this process has handlers for, and spawn them.
+pub(crate) async fn run_due_jobs(ctx: &Context) {
+	let kinds: Vec<String> = HANDLERS.iter().map(|h| h.key().to_string()).collect();
+	if kinds.is_empty() {
+		return;
+	}
+
+	// if this process dies mid-job, the lease runs out and another process picks the job up
+	let jobs = match sqlx::query!(
+		"UPDATE scheduled_jobs SET locked_until = NOW() + INTERVAL '5 minutes', last_run = NOW() \
+		 WHERE id IN (SELECT id FROM scheduled_jobs WHERE next_run <= NOW() AND kind = ANY($1) \
+		 AND (locked_until IS NULL OR locked_until < NOW()) ORDER BY next_run LIMIT $2 FOR UPDATE \
+		 SKIP LOCKED) RETURNING id, name, kind, payload, cron, attempts",
+		&kinds[..],
+		MAX_JOBS_PER_POLL
+	)
+	.fetch_all(scripty_db::get_db())
+	.await
+	{
+		Ok(jobs) => jobs,
+		Err(e) => {
+			error!("failed to claim scheduled jobs: {}", e);
+			return;
+		}
+	};
+
+	for row in jobs {
+		let Some(handler) = HANDLERS
+			.get(row.kind.as_str())
+			.map(|h| Arc::clone(h.value()))
+		else {
+			continue;
+		};
+		let job = Job {
+			id:       row.id,
+			name:     row.name,
+			kind:     row.kind,
+			payload:  row.payload,
+			attempts: row.attempts,
+		};
+		let cron = row.cron;
+		let ctx = ctx.clone();
+
+		scripty_utils::spawn_logged("scheduled_job", async move {
+			debug!(id = job.id, kind = %job.kind, "running scheduled job");
+			let lease = LeaseRenewal(tokio::spawn(keep_lease(job.id)));
+			let res = handler.run(&ctx, &job).await;
+			drop(lease);
+			if let Err(e) = finish_job(&job, cron.as_deref(), res).await {
+				error!(id = job.id, "failed to update scheduled job: {}", e);
+			}
+		});
+	}
+}
+
+/// Stops renewing a job's lease once dropped, including if the job panics.
+struct LeaseRenewal(tokio::task::JoinHandle<()>);
+
+impl Drop for LeaseRenewal {
+	fn drop(&mut self) {
+		self.0.abort();
+	}
+}
+
+/// Keep extending the lease of a running job, so no other process picks it up while it's running
+/// for longer than the lease.
+async fn keep_lease(id: i64) {
+	loop {
+		tokio::time::sleep(LEASE_RENEW_INTERVAL).await;
+		if let Err(e) = sqlx::query!(
+			"UPDATE scheduled_jobs SET locked_until = NOW() + INTERVAL '5 minutes' WHERE id = $1",
+			id
+		)
+		.execute(scripty_db::get_db())
+		.await
+		{
+			warn!(id, "failed to renew scheduled job lease: {}", e);
+		}
+	}
+}
+
+async fn finish_job(
+	job: &Job,
+	cron: Option<&str>,
+	res: Result<(), Error>,
+) -> Result<(), sqlx::Error> {
+	let db = scripty_db::get_db();
+	let now = OffsetDateTime::now_utc();
+
+	let last_error = match res {
+		Ok(()) => None,
+		Err(e) => {
+			error!(id = job.id, kind = %job.kind, "scheduled job failed: {}", e);
+			Some(e.to_string())
+		}
+	};
+	let attempts = if last_error.is_some() {
+		job.attempts + 1
+	} else {
+		0
+	};
+
+	let next_run = match cron {
+		Some(cron) => match cron.parse::<CronSchedule>() {
+			Ok(schedule) => schedule.next_after(now),
+			Err(e) => {
+				error!(
+					id = job.id,
+					"dropping recurring job with invalid schedule `{}`: {}", cron, e
+				);
+				None
+			}
+		},
+		None if last_error.is_some() && attempts < MAX_ATTEMPTS => {
+			// back off 2, 4, 8, 16 minutes
+			Some(now + Duration::minutes(2i64.pow(attempts as u32)))
+		}
+		None => {
+			if last_error.is_some() {
+				error!(
+					id = job.id,
+					kind = %job.kind,
+					"giving up on scheduled job after {} attempts", attempts
+				);
+			}
+			None
+		}
+	};
+
+	match next_run {
+		Some(next_run) => {
+			sqlx::query!(
+				"UPDATE scheduled_jobs SET next_run = $2, attempts = $3, last_error = $4, \
+				 locked_until = NULL WHERE id = $1",
+				job.id,
+				to_primitive(next_run),
+				attempts,
+				last_error
+			)
+			.execute(db)
+			.await?;
+		}
+		None => {
+			sqlx::query!("DELETE FROM scheduled_jobs WHERE id = $1", job.id)
+				.execute(db)
+				.await?;
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,124 @@
+//! Per-guild webhook URLs, set with `/config webhook_url`, that get a summary of every session.
+//!
+//! Sending them is done by the audio handler through the delivery queue: this just manages them.
+
+use std::{fmt, net::IpAddr};
+
+use reqwest::{url::Host, Url};
+use serenity::model::id::GuildId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookUrlError {
+	/// Not a URL at all.
+	Invalid,
+	/// Only HTTPS URLs are allowed, so transcripts aren't sent in the clear.
+	NotHttps,
+	/// Points at our own network, rather than somewhere on the internet.
+	NotPublic,
+}
+
+impl fmt::Display for WebhookUrlError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Invalid => f.write_str("not a valid URL"),
+			Self::NotHttps => f.write_str("not an HTTPS URL"),
+			Self::NotPublic => f.write_str("not a public address"),
+		}
+	}
+}
+
+impl std::error::Error for WebhookUrlError {}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+	match ip {
+		IpAddr::V4(ip) => {
+			!(ip.is_private()
+				|| ip.is_loopback()
+				|| ip.is_link_local()
+				|| ip.is_unspecified()
+				|| ip.is_broadcast()
+				|| ip.is_documentation()
+				// carrier-grade NAT
+				|| (ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64))
+		}
+		IpAddr::V6(ip) => {
+			if let Some(ip) = ip.to_ipv4_mapped() {
+				return is_public_ip(IpAddr::V4(ip));
+			}
+			let first = ip.segments()[0];
+			!(ip.is_loopback()
+				|| ip.is_unspecified()
+				// unique local and link local
+				|| (first & 0xfe00) == 0xfc00
+				|| (first & 0xffc0) == 0xfe80)
+		}
+	}
+}
+
+/// Check `url` is somewhere session webhooks can be sent to.
+///
+/// This only catches the obvious cases: a public name can still resolve to a private address.
+pub fn check_webhook_url(url: &str) -> Result<Url, WebhookUrlError> {
+	let url = Url::parse(url).map_err(|_| WebhookUrlError::Invalid)?;
+	if url.scheme() != "https" {
+		return Err(WebhookUrlError::NotHttps);
+	}
+
+	let public = match url.host() {
+		None => return Err(WebhookUrlError::Invalid),
+		Some(Host::Domain(domain)) => {
+			let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+			// no dots means it can only be resolved locally
+			domain.contains('.')
+				&& !["localhost", "local", "internal"]
+					.iter()
+					.any(|tld| domain.ends_with(&format!(".{}", tld)))
+		}
+		Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+		Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+	};
+	if !public {
+		return Err(WebhookUrlError::NotPublic);
+	}
+	Ok(url)
+}
+
+/// Send `guild_id`'s session summaries to `url`, which must have passed `check_webhook_url`.
+///
+/// Returns the new secret requests are signed with. It's only ever returned here,
+/// and changes every time the URL is set.
+pub async fn set_webhook_url(guild_id: GuildId, url: &Url) -> Result<String, sqlx::Error> {
+	let secret = format!(
+		"{}{}",
+		uuid::Uuid::new_v4().simple(),
+		uuid::Uuid::new_v4().simple()
+	);
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, webhook_url, webhook_secret) VALUES ($1, $2, $3) ON \
+		 CONFLICT (guild_id) DO UPDATE SET webhook_url = $2, webhook_secret = $3",
+		guild_id.get() as i64,
+		url.as_str(),
+		secret
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(secret)
+}
+
+/// Stop sending `guild_id`'s session summaries anywhere. Returns whether it had a URL set.
+///
+/// Queued deliveries are dropped when they're next retried.
+pub async fn clear_webhook_url(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	let cleared = sqlx::query!(
+		"UPDATE guilds SET webhook_url = NULL, webhook_secret = NULL WHERE guild_id = $1 AND \
+		 webhook_url IS NOT NULL",
+		guild_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(cleared > 0)
+}
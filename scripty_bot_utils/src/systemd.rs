@@ -0,0 +1,44 @@
+//! systemd service notifications.
+//!
+//! All of these are no-ops when not running under systemd with `Type=notify`.
+
+use std::time::Duration;
+
+use sd_notify::NotifyState;
+
+/// Whether systemd is listening for notifications from this process.
+pub fn is_enabled() -> bool {
+	std::env::var_os("NOTIFY_SOCKET").is_some()
+}
+
+/// How often systemd expects to be pinged, if `WatchdogSec` is set for this service.
+pub fn watchdog_timeout() -> Option<Duration> {
+	let mut usec = 0;
+	sd_notify::watchdog_enabled(false, &mut usec).then(|| Duration::from_micros(usec))
+}
+
+/// Tell systemd startup has finished.
+pub fn notify_ready() {
+	notify(&[NotifyState::Ready, NotifyState::Status("running")]);
+}
+
+/// Ping the systemd watchdog.
+pub fn notify_watchdog() {
+	notify(&[NotifyState::Watchdog]);
+}
+
+/// Update the status line shown in `systemctl status`.
+pub fn notify_status(status: &str) {
+	notify(&[NotifyState::Status(status)]);
+}
+
+/// Tell systemd we've started shutting down.
+pub fn notify_stopping() {
+	notify(&[NotifyState::Stopping, NotifyState::Status("shutting down")]);
+}
+
+fn notify(state: &[NotifyState]) {
+	if let Err(e) = sd_notify::notify(false, state) {
+		warn!("failed to notify systemd: {}", e);
+	}
+}
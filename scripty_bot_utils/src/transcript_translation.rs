@@ -0,0 +1,71 @@
+//! The translate button on transcripts, shown while someone in the session reads a different
+//! language to the one being transcribed.
+
+use scripty_utils::translation;
+use serenity::{
+	all::ComponentInteraction,
+	builder::CreateInteractionResponseFollowup,
+	client::Context,
+};
+
+/// Discord's limit on message length.
+const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Show whoever pressed the button what the transcript says, in their reading language.
+pub async fn handle_translate_button(ctx: Context, interaction: ComponentInteraction) {
+	let language = scripty_i18n::get_resolved_language_with_locale(
+		interaction.user.id.get(),
+		interaction.guild_id.map(|g| g.get()),
+		Some(&interaction.locale),
+		interaction.guild_locale.as_deref(),
+	)
+	.await;
+
+	// translating can take a few seconds
+	if let Err(e) = interaction.defer_ephemeral(&ctx).await {
+		warn!("failed to acknowledge translate button: {}", e);
+		return;
+	}
+
+	let target = match translation::get_reading_language(interaction.user.id).await {
+		Ok(Some(target)) => Some(target),
+		// fall back to the language they use Scripty in
+		Ok(None) => Some(language.language.as_str().to_owned()),
+		Err(e) => {
+			error!("failed to fetch reading language: {}", e);
+			None
+		}
+	};
+	// transcripts are in an embed when speaker colors are on
+	let transcript = match interaction.message.embeds.first() {
+		Some(embed) if interaction.message.content.is_empty() => {
+			embed.description.as_deref().unwrap_or_default()
+		}
+		_ => interaction.message.content.as_str(),
+	};
+	let translated = match target {
+		Some(target) => translation::translate(transcript, &target)
+			.await
+			.map_err(|e| warn!(%target, "failed to translate transcript: {}", e))
+			.ok(),
+		None => None,
+	};
+	let content = match translated {
+		Some(translated) if !translated.is_empty() => {
+			translated.chars().take(MAX_MESSAGE_LEN).collect()
+		}
+		_ => format_message!(language, "transcript-translate-failed"),
+	};
+
+	if let Err(e) = interaction
+		.create_followup(
+			&ctx,
+			CreateInteractionResponseFollowup::new()
+				.ephemeral(true)
+				.content(content),
+		)
+		.await
+	{
+		warn!("failed to send transcript translation: {}", e);
+	}
+}
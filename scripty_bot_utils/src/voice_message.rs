@@ -53,9 +53,11 @@ async fn internal_handle_message(
 	debug!(%msg.id, "decoded voice message, feeding to speech-to-text");
 	// fetch guild language
 	let db = scripty_db::get_db();
+	let guild_id = msg.guild_id.ok_or_else(crate::Error::expected_guild)?;
 	let res = sqlx::query!(
-		"SELECT language, translate FROM guilds WHERE guild_id = $1",
-		msg.guild_id.ok_or_else(crate::Error::expected_guild)?.get() as i64
+		"SELECT language, translate, profanity_filter, remove_filler_words, numbers_as_digits \
+		 FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
 	)
 	.fetch_one(db)
 	.await?;
@@ -64,7 +66,26 @@ async fn internal_handle_message(
 
 	let stream = scripty_stt::get_stream().await?;
 	stream.feed_audio(output)?;
+	let transcript_language = if translate { "en" } else { lang.as_str() };
+	let mut pipeline = scripty_stt::Pipeline::standard(&lang, translate);
+	if res.remove_filler_words {
+		pipeline = pipeline.with_guild_stage(scripty_stt::FillerWords::new(
+			scripty_i18n::get_filler_words(transcript_language),
+		));
+	}
+	if res.numbers_as_digits {
+		pipeline = pipeline.with_guild_stage(scripty_stt::SpokenNumbers::new(transcript_language));
+	}
+	let replacements = scripty_utils::replacements::get_replacements(guild_id).await?;
+	let pipeline = pipeline
+		.with_guild_stage(scripty_stt::Replacements::new(
+			replacements.into_iter().map(|r| (r.pattern, r.replacement)),
+		))
+		.then(scripty_stt::ProfanityFilter::new(
+			scripty_stt::ProfanityMode::from_db(res.profanity_filter),
+		));
 	let transcript = stream.get_result(lang, false, translate).await?;
+	let transcript = pipeline.run(transcript);
 	let transcript = transcript.trim();
 	let mut msg_builder = EditMessage::new();
 
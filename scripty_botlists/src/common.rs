@@ -28,6 +28,19 @@ pub enum Error {
 	StatusCode(StatusCode),
 }
 
+impl Error {
+	/// Whether posting again later might succeed: network errors, rate limits and server errors.
+	pub fn is_transient(&self) -> bool {
+		match self {
+			Error::Reqwest(_) => true,
+			Error::Json(_) => false,
+			Error::StatusCode(status) => {
+				status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+			}
+		}
+	}
+}
+
 impl From<ReqwestError> for Error {
 	fn from(error: ReqwestError) -> Self {
 		Self::Reqwest(error)
@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use poise::CreateReply;
+use scripty_bot_utils::entity_block::{self, BlockedEntity};
+use serenity::{
+	builder::{CreateEmbed, CreateEmbedFooter},
+	model::{id::GuildId, user::User},
+	prelude::Mentionable,
+};
+
+use crate::{Context, Error};
+
+/// Maximum number of entries shown by the list commands, to stay under the embed length limit.
+const MAX_LISTED: usize = 40;
+
+/// Manage the global blacklist.
+#[poise::command(prefix_command, hide_in_help, owners_only)]
+pub async fn blacklist(ctx: Context<'_>) -> Result<(), Error> {
+	ctx.say("use `blacklist guild` or `blacklist user`").await?;
+	Ok(())
+}
+
+/// Manage blacklisted guilds.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "guild")]
+pub async fn blacklist_guild(ctx: Context<'_>) -> Result<(), Error> {
+	ctx.say("use `add`, `remove`, or `list`").await?;
+	Ok(())
+}
+
+/// Blacklist a guild, and leave it if we're in it.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "add")]
+pub async fn blacklist_guild_add(
+	ctx: Context<'_>,
+	#[description = "The guild to blacklist."] guild_id: GuildId,
+	#[description = "The reason for blacklisting the guild."] reason: Option<String>,
+) -> Result<(), Error> {
+	entity_block::add_blocked_guild(guild_id, reason).await?;
+
+	let left = if ctx.cache().guild(guild_id).is_some() {
+		guild_id.leave(ctx).await?;
+		", and left it"
+	} else {
+		""
+	};
+
+	ctx.say(format!("Blacklisted guild {}{}", guild_id, left))
+		.await?;
+	Ok(())
+}
+
+/// Remove a guild from the blacklist.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "remove")]
+pub async fn blacklist_guild_remove(
+	ctx: Context<'_>,
+	#[description = "The guild to remove from the blacklist."] guild_id: GuildId,
+) -> Result<(), Error> {
+	if entity_block::remove_blocked_guild(guild_id).await? {
+		ctx.say(format!("Removed guild {} from the blacklist", guild_id))
+			.await?;
+	} else {
+		ctx.say(format!("Guild {} isn't blacklisted", guild_id))
+			.await?;
+	}
+	Ok(())
+}
+
+/// List blacklisted guilds.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "list")]
+pub async fn blacklist_guild_list(ctx: Context<'_>) -> Result<(), Error> {
+	let guilds = entity_block::list_blocked_guilds().await?;
+	send_list(ctx, "Blacklisted Guilds", guilds).await
+}
+
+/// Manage blacklisted users.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "user")]
+pub async fn blacklist_user(ctx: Context<'_>) -> Result<(), Error> {
+	ctx.say("use `add`, `remove`, or `list`").await?;
+	Ok(())
+}
+
+/// Blacklist a user from using the entire bot.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "add")]
+pub async fn blacklist_user_add(
+	ctx: Context<'_>,
+	#[description = "The user to blacklist."] user: User,
+	#[description = "The reason for blacklisting the user."] reason: Option<String>,
+) -> Result<(), Error> {
+	entity_block::add_blocked_user(user.id, reason).await?;
+
+	ctx.say(format!("Blacklisted {}", user.mention())).await?;
+	Ok(())
+}
+
+/// Remove a user from the blacklist.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "remove")]
+pub async fn blacklist_user_remove(
+	ctx: Context<'_>,
+	#[description = "The user to remove from the blacklist."] user: User,
+) -> Result<(), Error> {
+	if entity_block::remove_blocked_user(user.id).await? {
+		ctx.say(format!("Removed {} from the blacklist", user.mention()))
+			.await?;
+	} else {
+		ctx.say(format!("{} isn't blacklisted", user.mention()))
+			.await?;
+	}
+	Ok(())
+}
+
+/// List blacklisted users. Only hashed user IDs are stored, so those are what's shown.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "list")]
+pub async fn blacklist_user_list(ctx: Context<'_>) -> Result<(), Error> {
+	let users = entity_block::list_blocked_users().await?;
+	send_list(ctx, "Blacklisted Users", users).await
+}
+
+async fn send_list(
+	ctx: Context<'_>,
+	title: &str,
+	entities: Vec<BlockedEntity>,
+) -> Result<(), Error> {
+	let mut description = String::new();
+	for entity in entities.iter().take(MAX_LISTED) {
+		let since = entity
+			.blocked_since
+			.map_or_else(String::new, |t| format!(" <t:{}:d>", t.unix_timestamp()));
+		writeln!(
+			&mut description,
+			"`{}`{}: {}",
+			entity.id,
+			since,
+			entity.reason.as_deref().unwrap_or("no reason given")
+		)
+		.expect("failed to write to string");
+	}
+	if entities.len() > MAX_LISTED {
+		writeln!(
+			&mut description,
+			"...and {} more",
+			entities.len() - MAX_LISTED
+		)
+		.expect("failed to write to string");
+	}
+	if description.is_empty() {
+		description.push_str("none");
+	}
+
+	ctx.send(
+		CreateReply::default().embed(
+			CreateEmbed::default()
+				.title(title)
+				.description(description)
+				.footer(CreateEmbedFooter::new(format!("{} total", entities.len()))),
+		),
+	)
+	.await?;
+	Ok(())
+}
@@ -1,6 +1,5 @@
 use std::borrow::Cow;
 
-use num_format::{Locale, ToFormattedString};
 use poise::CreateReply;
 use serenity::builder::CreateEmbed;
 use typesize::TypeSize;
@@ -16,19 +15,20 @@ pub async fn cache_info(ctx: Context<'_>) -> Result<(), Error> {
 		is_collection: bool,
 	}
 
+	let language = scripty_bot_utils::get_ctx_language(ctx).await.to_string();
 	let serenity_cache = ctx.cache();
 	let cache_stats = serenity_cache.get_size_details();
 
 	let mut fields = Vec::new();
 	for field in cache_stats {
 		let name = format!("`{}`", field.name);
-		let size = field.size.to_formatted_string(&Locale::en);
+		let size = scripty_utils::separate_num(field.size, &language);
 		if let Some(count) = field.collection_items {
 			let (count, size_per) = if count == 0 {
 				(Cow::Borrowed("0"), Cow::Borrowed("N/A"))
 			} else {
-				let count_fmt = count.to_formatted_string(&Locale::en);
-				let mut size_per = (field.size / count).to_formatted_string(&Locale::en);
+				let count_fmt = scripty_utils::separate_num(count, &language);
+				let mut size_per = scripty_utils::separate_num(field.size / count, &language);
 				size_per.push('b');
 
 				(Cow::Owned(count_fmt), Cow::Owned(size_per))
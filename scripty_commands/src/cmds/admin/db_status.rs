@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::{Context, Error};
+
+#[poise::command(prefix_command, hide_in_help, owners_only)]
+pub async fn db_status(ctx: Context<'_>) -> Result<(), Error> {
+	let status = scripty_db::get_schema_status(scripty_db::get_db()).await?;
+
+	let current_version = status
+		.current_version
+		.map_or_else(|| "none".to_string(), |v| v.to_string());
+
+	let mut pending = String::new();
+	for migration in status.pending() {
+		writeln!(
+			&mut pending,
+			"`{}` {}",
+			migration.version, migration.description
+		)
+		.expect("failed to write to string");
+	}
+	if pending.is_empty() {
+		pending.push_str("none");
+	}
+
+	let mut mismatched = String::new();
+	for migration in status.migrations.iter().filter(|m| m.mismatched) {
+		writeln!(
+			&mut mismatched,
+			"`{}` {}",
+			migration.version, migration.description
+		)
+		.expect("failed to write to string");
+	}
+
+	let mut embed = CreateEmbed::default()
+		.title("Database Status")
+		.field("Schema version", format!("`{}`", current_version), true)
+		.field(
+			"Embedded migrations",
+			status.migrations.len().to_string(),
+			true,
+		)
+		.field("Pending migrations", pending, false);
+	if !mismatched.is_empty() {
+		embed = embed.field("Checksum mismatches", mismatched, false);
+	}
+
+	ctx.send(CreateReply::default().embed(embed)).await?;
+	Ok(())
+}
@@ -0,0 +1,53 @@
+use poise::CreateReply;
+use scripty_bot_utils::error::report::get_error_report;
+use serenity::builder::{CreateAttachment, CreateEmbed};
+
+use crate::{Context, Error};
+
+/// Look up the details of an error by the ID that was shown to the user.
+#[poise::command(prefix_command, hide_in_help, owners_only, rename = "error")]
+pub async fn error_lookup(ctx: Context<'_>, error_id: String) -> Result<(), Error> {
+	let Some(report) = get_error_report(&error_id).await? else {
+		ctx.say(format!(
+			"no error with ID `{}`, it may have expired",
+			error_id
+		))
+		.await?;
+		return Ok(());
+	};
+
+	let mut embed = CreateEmbed::default()
+		.title(format!("Error {}", report.id))
+		.description(format!("Error while {}", report.context))
+		.field("Happened", format!("<t:{}:R>", report.timestamp), true)
+		.field(
+			"Guild ID",
+			report
+				.guild_id
+				.map_or_else(|| "None (DM ctx)".to_string(), |g| g.to_string()),
+			true,
+		)
+		.field(
+			"Channel ID",
+			report
+				.channel_id
+				.map_or_else(|| "unknown".to_string(), |c| c.to_string()),
+			true,
+		)
+		.field("Error (display)", report.display, false)
+		.field("Error (debug)", report.debug, false);
+
+	let mut reply = CreateReply::default();
+	if report.backtrace.len() > 1024 {
+		embed = embed.field("Backtrace", "See attached file", false);
+		reply = reply.attachment(CreateAttachment::bytes(
+			report.backtrace.into_bytes(),
+			"backtrace.txt",
+		));
+	} else {
+		embed = embed.field("Backtrace", report.backtrace, false);
+	}
+
+	ctx.send(reply.embed(embed)).await?;
+	Ok(())
+}
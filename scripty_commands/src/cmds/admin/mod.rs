@@ -1,13 +1,26 @@
 use crate::{Context, Error};
 
+mod blacklist;
 mod cache_info;
+mod db_status;
+mod error_lookup;
 mod guild_check;
 mod hash_user_id;
+mod profiling;
+mod reload;
+mod reload_i18n;
 mod shutdown;
 
+pub use blacklist::*;
 pub use cache_info::cache_info;
+pub use db_status::db_status;
+pub use error_lookup::error_lookup;
 pub use guild_check::*;
 pub use hash_user_id::hash_user_id;
+pub use profiling::profiling;
+pub use reload::reload;
+pub use reload_i18n::reload_i18n;
+pub use shutdown::shutdown;
 
 #[poise::command(prefix_command, hide_in_help, owners_only)]
 pub async fn admin(ctx: Context<'_>) -> Result<(), Error> {
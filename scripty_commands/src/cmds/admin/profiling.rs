@@ -0,0 +1,23 @@
+use crate::{Context, Error};
+
+/// Enable or disable the profiling endpoints on the internal webserver.
+///
+/// These only exist if the bot was built with the `profiling` feature.
+#[poise::command(prefix_command, hide_in_help, owners_only)]
+pub async fn profiling(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
+	scripty_metrics::set_profiling_enabled(enabled);
+
+	let note = if cfg!(feature = "profiling") {
+		""
+	} else {
+		"\nnote: this build does not have the `profiling` feature, so there are no endpoints to \
+		 enable"
+	};
+	ctx.say(format!(
+		"profiling endpoints are now {}{}",
+		if enabled { "enabled" } else { "disabled" },
+		note
+	))
+	.await?;
+	Ok(())
+}
@@ -0,0 +1,12 @@
+use scripty_bot_utils::{Context, Error};
+
+/// Reload the runtime-safe parts of the config, same as sending the process SIGHUP.
+#[poise::command(prefix_command, hide_in_help, owners_only)]
+pub async fn reload(ctx: Context<'_>) -> Result<(), Error> {
+	match scripty_bot_utils::reload::reload_config().await {
+		Ok(()) => ctx.say("reloaded config").await?,
+		Err(e) => ctx.say(format!("failed to reload config: {}", e)).await?,
+	};
+
+	Ok(())
+}
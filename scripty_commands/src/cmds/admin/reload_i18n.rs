@@ -0,0 +1,34 @@
+use crate::{Context, Error};
+
+/// Reload translations from disk.
+///
+/// Slash command localizations are only sent to Discord at startup, so those won't change.
+#[poise::command(prefix_command, hide_in_help, owners_only)]
+pub async fn reload_i18n(ctx: Context<'_>) -> Result<(), Error> {
+	let summary = scripty_i18n::reload_i18n();
+
+	let mut msg = format!("reloaded {} languages", summary.languages.len());
+	if !summary.errors.is_empty() {
+		msg.push_str(&format!(" with {} errors:\n```\n", summary.errors.len()));
+		for error in summary.errors.iter().take(10) {
+			msg.push_str(error);
+			msg.push('\n');
+		}
+		if summary.errors.len() > 10 {
+			msg.push_str("...\n");
+		}
+		msg.push_str("```");
+	}
+	// keep under the message length limit, errors can be long
+	if msg.len() > 2000 {
+		let mut end = 1990;
+		while !msg.is_char_boundary(end) {
+			end -= 1;
+		}
+		msg.truncate(end);
+		msg.push_str("...\n```");
+	}
+
+	ctx.say(msg).await?;
+	Ok(())
+}
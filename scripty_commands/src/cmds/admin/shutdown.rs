@@ -1,17 +1,28 @@
-use scripty_bot_utils::{Context, Error};
+use scripty_bot_utils::{confirm::confirm, Context, Error};
+use serenity::builder::CreateEmbed;
 
 #[poise::command(prefix_command, hide_in_help, owners_only)]
 pub async fn shutdown(ctx: Context<'_>) -> Result<(), Error> {
-	let msg = ctx.say("shutting down").await?;
+	let prompt = CreateEmbed::default()
+		.title("Shut down?")
+		.description("Active transcriptions will be drained first, then every shard disconnects.");
+	if !confirm(ctx, prompt).await? {
+		ctx.say("not shutting down").await?;
+		return Ok(());
+	}
 
-	// iterate over all active voice connections and notify
-	let songbird = scripty_audio_handler::get_songbird_from_ctx(ctx.serenity_context()).await;
-	for (guild_id, call) in songbird.iter() {
-		// leave the call
-		call.lock().await.leave().await?;
+	let msg = ctx
+		.say("draining active transcriptions, this may take a moment")
+		.await?;
 
-		// notify the guild
-	}
+	// stop taking new audio, let open streams finish, then notify and leave every call
+	scripty_audio_handler::drain(ctx.serenity_context(), scripty_audio_handler::DRAIN_TIMEOUT)
+		.await;
+
+	msg.edit(ctx, poise::CreateReply::default().content("shutting down"))
+		.await?;
+
+	ctx.data().shard_manager.shutdown_all().await;
 
 	Ok(())
 }
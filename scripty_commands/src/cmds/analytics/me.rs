@@ -0,0 +1,25 @@
+use scripty_bot_utils::checks::is_guild;
+
+use super::{send_report, AnalyticsPeriod};
+use crate::{Context, Error};
+
+/// See how much you've spoken in this server's voice chats.
+///
+/// Only counts what Scripty transcribed. Opt out with `/data_storage`.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	user_cooldown = 10,
+	check = "is_guild",
+	rename = "me"
+)]
+pub async fn analytics_me(
+	ctx: Context<'_>,
+	#[description = "How far back to look. Defaults to a month."] period: Option<AnalyticsPeriod>,
+) -> Result<(), Error> {
+	let display_name = match ctx.author_member().await {
+		Some(member) => member.display_name().to_string(),
+		None => ctx.author().name.clone(),
+	};
+	send_report(ctx, ctx.author(), &display_name, period).await
+}
@@ -0,0 +1,199 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use humantime::format_duration;
+use poise::CreateReply;
+use serenity::{builder::CreateEmbed, model::user::User};
+
+use crate::{Context, Error};
+
+mod me;
+mod user;
+
+pub use me::*;
+pub use user::*;
+
+/// How many of the hours someone speaks most in are shown.
+const TOP_HOURS: i64 = 3;
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum AnalyticsPeriod {
+	#[name = "Week"]
+	Week,
+	#[name = "Month"]
+	Month,
+	#[name = "Quarter"]
+	Quarter,
+	#[name = "Year"]
+	Year,
+}
+
+impl AnalyticsPeriod {
+	fn days(self) -> i32 {
+		match self {
+			AnalyticsPeriod::Week => 7,
+			AnalyticsPeriod::Month => 30,
+			AnalyticsPeriod::Quarter => 90,
+			AnalyticsPeriod::Year => 365,
+		}
+	}
+}
+
+/// Talk time analytics commands
+#[poise::command(prefix_command, slash_command)]
+pub async fn analytics(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::default()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "analytics"
+				)),
+		),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Reply with how much `user` has spoken in this server over `period`, unless they opted out.
+async fn send_report(
+	ctx: Context<'_>,
+	user: &User,
+	display_name: &str,
+	period: Option<AnalyticsPeriod>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	let days = period.unwrap_or(AnalyticsPeriod::Month).days();
+	let is_author = user.id == ctx.author().id;
+	// so an opt-out stored under the legacy hash is found
+	scripty_utils::rehash_user_id(user.id.get()).await?;
+	let hashed_user_id = scripty_utils::hash_user_id(user.id.get());
+	let db = scripty_db::get_db();
+
+	let opted_out = sqlx::query!(
+		"SELECT analytics_opt_out FROM users WHERE user_id = $1",
+		hashed_user_id
+	)
+	.fetch_optional(db)
+	.await?
+	.map_or(false, |row| row.analytics_opt_out);
+	if opted_out {
+		let message = if is_author {
+			format_message!(resolved_language, "analytics-opted-out-self")
+		} else {
+			format_message!(resolved_language, "analytics-opted-out", user: display_name)
+		};
+		ctx.send(CreateReply::default().ephemeral(true).content(message))
+			.await?;
+		return Ok(());
+	}
+
+	let totals = sqlx::query!(
+		"SELECT COALESCE(SUM(ms_spoken), 0)::BIGINT AS \"ms_spoken!\", COALESCE(SUM(utterances), \
+		 0)::BIGINT AS \"utterances!\", COUNT(DISTINCT (hour AT TIME ZONE 'UTC')::DATE)::BIGINT \
+		 AS \"active_days!\" FROM user_talk_time WHERE guild_id = $1 AND user_id = $2 AND hour > \
+		 NOW() - make_interval(days => $3)",
+		guild_id.get() as i64,
+		hashed_user_id,
+		days
+	)
+	.fetch_one(db)
+	.await?;
+	if totals.utterances == 0 {
+		let message = if is_author {
+			format_message!(resolved_language, "analytics-no-data-self", days: days)
+		} else {
+			format_message!(resolved_language, "analytics-no-data", user: display_name, days: days)
+		};
+		ctx.send(CreateReply::default().ephemeral(true).content(message))
+			.await?;
+		return Ok(());
+	}
+
+	let top_hours = sqlx::query!(
+		"SELECT EXTRACT(HOUR FROM hour AT TIME ZONE 'UTC')::INT AS \"hour!\", \
+		 SUM(ms_spoken)::BIGINT AS \"ms_spoken!\" FROM user_talk_time WHERE guild_id = $1 AND \
+		 user_id = $2 AND hour > NOW() - make_interval(days => $3) GROUP BY 1 ORDER BY 2 DESC \
+		 LIMIT $4",
+		guild_id.get() as i64,
+		hashed_user_id,
+		days,
+		TOP_HOURS
+	)
+	.fetch_all(db)
+	.await?;
+
+	// shown as timestamps, so they're in each reader's own timezone
+	let today = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_secs() as i64 / 86400 * 86400);
+	let active_hours = top_hours
+		.iter()
+		.map(|row| {
+			let start = today + row.hour as i64 * 3600;
+			format_message!(
+				resolved_language,
+				"analytics-active-hour",
+				start: start.to_string(),
+				end: (start + 3600).to_string(),
+				percent: (row.ms_spoken * 100 / totals.ms_spoken.max(1)).to_string()
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	let speaking_time = Duration::from_secs(totals.ms_spoken as u64 / 1000);
+	let average = totals.ms_spoken as f64 / totals.utterances as f64 / 1000.0;
+	let embed = CreateEmbed::default()
+		.title(format_message!(
+			resolved_language,
+			"analytics-title",
+			user: display_name
+		))
+		.description(format_message!(
+			resolved_language,
+			"analytics-description",
+			days: days
+		))
+		.thumbnail(user.face())
+		.field(
+			format_message!(resolved_language, "analytics-speaking-time"),
+			format_duration(speaking_time).to_string(),
+			true,
+		)
+		.field(
+			format_message!(resolved_language, "analytics-utterances"),
+			totals.utterances.to_string(),
+			true,
+		)
+		.field(
+			format_message!(resolved_language, "analytics-average-utterance"),
+			format_message!(
+				resolved_language,
+				"analytics-seconds",
+				seconds: format!("{:.1}", average)
+			),
+			true,
+		)
+		.field(
+			format_message!(resolved_language, "analytics-active-days"),
+			totals.active_days.to_string(),
+			true,
+		)
+		.field(
+			format_message!(resolved_language, "analytics-active-hours"),
+			active_hours,
+			false,
+		);
+	ctx.send(CreateReply::default().ephemeral(true).embed(embed))
+		.await?;
+	Ok(())
+}
@@ -0,0 +1,23 @@
+use scripty_bot_utils::checks::is_guild;
+use serenity::model::guild::Member;
+
+use super::{send_report, AnalyticsPeriod};
+use crate::{Context, Error};
+
+/// See how much a member has spoken in this server's voice chats.
+///
+/// Members who opted out with `/data_storage` can't be looked up.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "user"
+)]
+pub async fn analytics_user(
+	ctx: Context<'_>,
+	#[description = "Member to look up."] member: Member,
+	#[description = "How far back to look. Defaults to a month."] period: Option<AnalyticsPeriod>,
+) -> Result<(), Error> {
+	send_report(ctx, &member.user, member.display_name(), period).await
+}
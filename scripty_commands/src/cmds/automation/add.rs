@@ -0,0 +1,116 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::automations::{
+	self,
+	AutomationAction,
+	MAX_AUTOMATIONS,
+	MAX_MESSAGE_LEN,
+	MAX_PHRASE_LEN,
+};
+use serenity::{builder::CreateAllowedMentions, model::guild::Role};
+
+use crate::{Context, Error};
+
+/// Shorter phrases than this come up in almost everything anyone says.
+const MIN_PHRASE_LEN: usize = 3;
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum AutomationActionChoice {
+	#[name = "Post a message"]
+	Message,
+	#[name = "Ping a role"]
+	PingRole,
+	#[name = "Fire the server's webhook"]
+	Webhook,
+}
+
+impl From<AutomationActionChoice> for AutomationAction {
+	fn from(choice: AutomationActionChoice) -> Self {
+		match choice {
+			AutomationActionChoice::Message => Self::Message,
+			AutomationActionChoice::PingRole => Self::PingRole,
+			AutomationActionChoice::Webhook => Self::Webhook,
+		}
+	}
+}
+
+/// Do something whenever a phrase is said, ie ping @Raiders when someone says "start the raid".
+///
+/// Phrases are matched anywhere in what's said, ignoring case. Adding a phrase that already has an
+/// automation replaces what it does.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "add"
+)]
+pub async fn automation_add(
+	ctx: Context<'_>,
+	#[description = "What has to be said, like \"start the raid\""] phrase: String,
+	#[description = "What happens when it's said"] action: AutomationActionChoice,
+	#[description = "Message to post. Sent after the ping, or along to the webhook, if set"]
+	message: Option<String>,
+	#[description = "Role to ping, for Ping a role"] role: Option<Role>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	let phrase = phrase.trim().to_lowercase();
+	let message = message
+		.as_deref()
+		.map(str::trim)
+		.filter(|message| !message.is_empty());
+	let action = AutomationAction::from(action);
+
+	let reply = if !(MIN_PHRASE_LEN..=MAX_PHRASE_LEN).contains(&phrase.chars().count()) {
+		format_message!(
+			resolved_language,
+			"automation-add-invalid-phrase",
+			minLength: MIN_PHRASE_LEN,
+			maxLength: MAX_PHRASE_LEN
+		)
+	} else if message.map_or(false, |message| message.chars().count() > MAX_MESSAGE_LEN) {
+		format_message!(
+			resolved_language,
+			"automation-add-message-too-long",
+			maxLength: MAX_MESSAGE_LEN
+		)
+	} else if action == AutomationAction::Message && message.is_none() {
+		format_message!(resolved_language, "automation-add-needs-message")
+	} else if action == AutomationAction::PingRole && role.is_none() {
+		format_message!(resolved_language, "automation-add-needs-role")
+	} else if action == AutomationAction::Webhook
+		&& !automations::has_guild_webhook(guild_id).await?
+	{
+		format_message!(resolved_language, "automation-add-no-webhook")
+	} else {
+		// only pings use the role, so don't keep one around that does nothing
+		let role_id = role
+			.as_ref()
+			.filter(|_| action == AutomationAction::PingRole)
+			.map(|role| role.id);
+		match automations::add_automation(guild_id, &phrase, action, message, role_id).await? {
+			Some(id) => format_message!(
+				resolved_language,
+				"automation-add-success",
+				id: id,
+				phrase: phrase,
+				action: super::describe_action(&resolved_language, action, role_id)
+			),
+			None => format_message!(
+				resolved_language,
+				"automation-add-full",
+				maxAutomations: MAX_AUTOMATIONS
+			),
+		}
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(reply),
+	)
+	.await?;
+	Ok(())
+}
@@ -0,0 +1,72 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::{automations, do_paginate};
+
+use crate::{Context, Error};
+
+/// List this server's automations.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "list"
+)]
+pub async fn automation_list(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let automations = automations::get_automations(guild_id).await?;
+	if automations.is_empty() {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "automation-none")),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let formatted_automations = automations
+		.into_iter()
+		.map(|automation| {
+			let action =
+				super::describe_action(&resolved_language, automation.action, automation.role_id);
+			let value = match automation.message {
+				Some(message) => format_message!(
+					resolved_language,
+					"automation-list-field-value-message",
+					action: action,
+					message: message
+				),
+				None => format_message!(
+					resolved_language,
+					"automation-list-field-value",
+					action: action
+				),
+			};
+			(
+				format_message!(
+					resolved_language,
+					"automation-list-field-name",
+					id: automation.id,
+					phrase: automation.phrase
+				),
+				value,
+			)
+		})
+		.collect::<Vec<_>>();
+
+	do_paginate(
+		ctx.serenity_context(),
+		ctx.channel_id(),
+		formatted_automations,
+		format_message!(resolved_language, "automation-list-title"),
+		None,
+		None,
+		Some(ctx.author().id),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,61 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_i18n::LanguageIdentifier;
+use scripty_utils::automations::AutomationAction;
+use serenity::{builder::CreateEmbed, model::id::RoleId, prelude::Mentionable};
+
+use crate::{Context, Error};
+
+mod add;
+mod list;
+mod remove;
+
+pub use add::*;
+pub use list::*;
+pub use remove::*;
+
+/// Make things happen when someone says a phrase in a voice chat Scripty is transcribing.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD"
+)]
+pub async fn automation(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::default()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "automation"
+				)),
+		),
+	)
+	.await?;
+	Ok(())
+}
+
+/// What an automation does, ie "ping @Raiders".
+fn describe_action(
+	language: &LanguageIdentifier,
+	action: AutomationAction,
+	role_id: Option<RoleId>,
+) -> String {
+	match action {
+		AutomationAction::Message => format_message!(language, "automation-action-message"),
+		AutomationAction::PingRole => format_message!(
+			language,
+			"automation-action-ping-role",
+			role: role_id.map_or_else(String::new, |role_id| role_id.mention().to_string())
+		),
+		AutomationAction::Webhook => format_message!(language, "automation-action-webhook"),
+	}
+}
@@ -0,0 +1,31 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::automations;
+
+use crate::{Context, Error};
+
+/// Stop doing something when a phrase is said.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "remove"
+)]
+pub async fn automation_remove(
+	ctx: Context<'_>,
+	#[description = "ID of the automation to remove, from the list command"] id: i32,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = if automations::remove_automation(guild_id, id).await? {
+		format_message!(resolved_language, "automation-removed", id: id)
+	} else {
+		format_message!(resolved_language, "automation-not-found", id: id)
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
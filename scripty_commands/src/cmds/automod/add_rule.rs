@@ -24,9 +24,7 @@ pub async fn automod_add_rule(
 
 	#[description = "The action to take when the rule is triggered."] action: AutomodRuleAction,
 ) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	// fetch the current guild's rule count
 	let db = scripty_db::get_db();
@@ -13,8 +13,7 @@ pub async fn automod_list_rules(ctx: Context<'_>) -> Result<(), Error> {
 	// fetch the current guild's rule count
 	let db = scripty_db::get_db();
 	let gid = ctx.guild_id().expect("asserted in guild").get();
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(gid)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let rules: Vec<_> = sqlx::query!(
 		"SELECT item_id, rule_type, rule_action, rule_data FROM automod_rules WHERE source_id = \
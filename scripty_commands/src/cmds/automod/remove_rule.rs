@@ -18,8 +18,7 @@ pub async fn automod_remove_rule(
 	// fetch the current guild's rule count
 	let db = scripty_db::get_db();
 	let gid = ctx.guild_id().expect("asserted in guild").get();
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(gid)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let source_id: i32 = match sqlx::query!(
 		"SELECT item_id FROM automod_config WHERE guild_id = $1",
@@ -5,9 +5,7 @@ use crate::{Context, Error};
 /// Does nothing, instead check out the sub-commands of this command.
 #[poise::command(prefix_command, slash_command, guild_only, rename = "automod")]
 pub async fn automod_root(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	ctx.say(
 		format_message!(resolved_language, "automod-root-response", contextPrefix: ctx.prefix()),
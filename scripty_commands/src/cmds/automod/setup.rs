@@ -34,8 +34,7 @@ pub async fn automod_setup(
 
 	let guild_id = ctx.guild_id().expect("asserted in guild").get();
 
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(guild_id)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	// filter and see if we have permissions to send messages, embed links, and attach files
 	let target_permissions = target_channel.permissions_for_user(ctx, ctx.framework().bot_id)?;
@@ -0,0 +1,154 @@
+use std::{
+	collections::VecDeque,
+	pin::pin,
+	time::{Duration, Instant},
+};
+
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::live_captions::{self, LiveCaptionEvent, OverlaySession};
+use serenity::futures::StreamExt;
+
+use crate::{Context, Error};
+
+/// Lines of captions shown at once.
+const MAX_LINES: usize = 10;
+
+/// Longest a line can be, so all of them and the footer fit in one message.
+const MAX_LINE_CHARS: usize = 180;
+
+/// Shortest time between edits, to stay well clear of rate limits.
+const EDIT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long captions are followed for. Interaction responses can't be edited after 15 minutes.
+const MAX_FOLLOW_TIME: Duration = Duration::from_secs(14 * 60);
+
+/// Get live captions of this voice session, in a message only you can see.
+///
+/// You have to be in the voice chat Scripty is transcribing.
+/// The message is kept up to date with the last few lines said, until you leave, the session
+/// ends, or 14 minutes have passed.
+#[poise::command(slash_command, user_cooldown = 15, check = "is_guild", rename = "live")]
+pub async fn captions_live(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	if !super::check_enabled(ctx, guild_id, &resolved_language).await? {
+		return Ok(());
+	}
+
+	let session_channel =
+		scripty_audio_handler::get_voice_channel_id(ctx.serenity_context(), guild_id).await;
+	let Some(session_channel) = session_channel else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"captions-overlay-no-session"
+				)),
+		)
+		.await?;
+		return Ok(());
+	};
+	if super::user_voice_channel(ctx) != Some(session_channel) {
+		let message = format_message!(
+			resolved_language,
+			"captions-overlay-not-in-channel",
+			channelId: session_channel.get()
+		);
+		ctx.send(CreateReply::default().ephemeral(true).content(message))
+			.await?;
+		return Ok(());
+	}
+
+	// follow before replying, so nothing said in between is missed
+	let mut events = pin!(live_captions::subscribe(guild_id).await?);
+	let minutes = MAX_FOLLOW_TIME.as_secs() / 60;
+	let footer = format_message!(resolved_language, "captions-live-footer", minutes: minutes);
+	let waiting = render(
+		&VecDeque::new(),
+		&format_message!(resolved_language, "captions-live-waiting"),
+	);
+	let reply = ctx
+		.send(CreateReply::default().ephemeral(true).content(waiting))
+		.await?;
+
+	// events are for the whole guild, and can be left over from an earlier session
+	let session = OverlaySession {
+		guild_id,
+		voice_channel_id: session_channel,
+		session_id: live_captions::get_current_session_id(guild_id, session_channel).await?,
+	};
+	let deadline = Instant::now() + MAX_FOLLOW_TIME;
+	let mut lines = VecDeque::with_capacity(MAX_LINES);
+	let mut changed = false;
+	let mut last_edit = Instant::now();
+	let stopped_id = loop {
+		if Instant::now() >= deadline {
+			break "captions-live-expired";
+		}
+		match tokio::time::timeout(EDIT_INTERVAL, events.next()).await {
+			// other voice chats, or a session that ended before this one
+			Ok(Some(event)) if !event.is_in(&session) => {}
+			Ok(Some(LiveCaptionEvent::Transcript {
+				speaker, content, ..
+			})) => {
+				if lines.len() == MAX_LINES {
+					lines.pop_front();
+				}
+				let line = format!("**{}**: {}", speaker, content);
+				lines.push_back(line.chars().take(MAX_LINE_CHARS).collect::<String>());
+				changed = true;
+			}
+			Ok(Some(LiveCaptionEvent::SessionEnded { .. })) => break "captions-live-ended",
+			Ok(Some(LiveCaptionEvent::SessionStarted { .. })) => {}
+			// the Redis connection went away
+			Ok(None) => break "captions-live-disconnected",
+			// nobody said anything
+			Err(_) => {}
+		}
+
+		if last_edit.elapsed() < EDIT_INTERVAL {
+			continue;
+		}
+		if super::user_voice_channel(ctx) != Some(session_channel) {
+			break "captions-live-left";
+		}
+		if changed {
+			let edit = CreateReply::default().content(render(&lines, &footer));
+			// the message was dismissed, or can't be edited anymore
+			if let Err(e) = reply.edit(ctx, edit).await {
+				debug!(%guild_id, "stopped live captions: {}", e);
+				return Ok(());
+			}
+			changed = false;
+		}
+		last_edit = Instant::now();
+	};
+
+	let stopped = format_message!(resolved_language, stopped_id, minutes: minutes);
+	if let Err(e) = reply
+		.edit(
+			ctx,
+			CreateReply::default().content(render(&lines, &stopped)),
+		)
+		.await
+	{
+		debug!(%guild_id, "failed to stop live captions: {}", e);
+	}
+	Ok(())
+}
+
+/// `lines`, oldest first, with `footer` in italics under them.
+fn render(lines: &VecDeque<String>, footer: &str) -> String {
+	let mut message = String::new();
+	for line in lines {
+		message.push_str(line);
+		message.push('\n');
+	}
+	if !lines.is_empty() {
+		message.push('\n');
+	}
+	message.push_str(&format!("*{}*", footer));
+	message
+}
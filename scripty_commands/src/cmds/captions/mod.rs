@@ -0,0 +1,67 @@
+use poise::CreateReply;
+use scripty_i18n::LanguageIdentifier;
+use scripty_utils::live_captions;
+use serenity::{
+	all::{ChannelId, GuildId},
+	builder::CreateEmbed,
+};
+
+use crate::{Context, Error};
+
+mod live;
+mod overlay;
+
+pub use live::*;
+pub use overlay::*;
+
+/// Live caption commands
+#[poise::command(prefix_command, slash_command)]
+pub async fn captions(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::default()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "captions"
+				)),
+		),
+	)
+	.await?;
+	Ok(())
+}
+
+/// The voice chat the author is in, if any.
+fn user_voice_channel(ctx: Context<'_>) -> Option<ChannelId> {
+	ctx.guild().and_then(|guild| {
+		guild
+			.voice_states
+			.get(&ctx.author().id)
+			.and_then(|state| state.channel_id)
+	})
+}
+
+/// Whether the guild has live captions on. If it doesn't, tells the author how to turn them on.
+async fn check_enabled(
+	ctx: Context<'_>,
+	guild_id: GuildId,
+	language: &LanguageIdentifier,
+) -> Result<bool, Error> {
+	if live_captions::is_enabled(guild_id).await? {
+		return Ok(true);
+	}
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.content(format_message!(language, "captions-disabled")),
+	)
+	.await?;
+	Ok(false)
+}
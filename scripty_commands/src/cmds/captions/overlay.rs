@@ -0,0 +1,73 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::live_captions::{self, OverlaySession};
+
+use crate::{Context, Error};
+
+/// Get a URL that shows live captions of this voice session, for a stream overlay.
+///
+/// You have to be in the voice chat Scripty is transcribing.
+/// The URL is only shown to you, as anyone with it can read the captions.
+/// It stops working when the session ends.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	user_cooldown = 15,
+	check = "is_guild",
+	rename = "overlay"
+)]
+pub async fn captions_overlay(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	if !super::check_enabled(ctx, guild_id, &resolved_language).await? {
+		return Ok(());
+	}
+
+	let Some(public_url) = scripty_config::get_config()
+		.transcript_api
+		.public_url
+		.as_deref()
+	else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"captions-overlay-unavailable"
+				)),
+		)
+		.await?;
+		return Ok(());
+	};
+
+	let session_channel =
+		scripty_audio_handler::get_voice_channel_id(ctx.serenity_context(), guild_id).await;
+	let user_channel = super::user_voice_channel(ctx);
+	let message = match session_channel {
+		None => format_message!(resolved_language, "captions-overlay-no-session"),
+		Some(session_channel) if user_channel != Some(session_channel) => format_message!(
+			resolved_language,
+			"captions-overlay-not-in-channel",
+			channelId: session_channel.get()
+		),
+		Some(session_channel) => {
+			let session = OverlaySession {
+				guild_id,
+				voice_channel_id: session_channel,
+				session_id: live_captions::get_current_session_id(guild_id, session_channel)
+					.await?,
+			};
+			let token = live_captions::create_overlay_token(session).await?;
+			format_message!(
+				resolved_language,
+				"captions-overlay",
+				url: format!("{}/overlay/{}", public_url.trim_end_matches('/'), token),
+				hours: live_captions::OVERLAY_TOKEN_TTL.as_secs() / 3600
+			)
+		}
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
@@ -0,0 +1,58 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum AgeRestrictedChoice {
+	#[name = "Allow"]
+	Allow,
+	#[name = "Refuse"]
+	Refuse,
+	#[name = "Restricted"]
+	Restricted,
+	#[name = "Spoiler"]
+	Spoiler,
+}
+
+/// Choose how age-restricted voice chats are transcribed.
+///
+/// Allow transcribes them like any other. Refuse never transcribes them. Restricted only sends
+/// their transcripts to age-restricted channels. Spoiler hides every transcript behind a spoiler.
+/// Applies to `/join`, automatic joins, and scheduled events alike.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "age_restricted"
+)]
+pub async fn config_age_restricted(
+	ctx: Context<'_>,
+	#[description = "Defaults to Allow"] policy: AgeRestrictedChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	// matches scripty_audio_handler's AgeRestrictedPolicy
+	let value: i16 = match policy {
+		AgeRestrictedChoice::Allow => 0,
+		AgeRestrictedChoice::Refuse => 1,
+		AgeRestrictedChoice::Restricted => 2,
+		AgeRestrictedChoice::Spoiler => 3,
+	};
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, age_restricted_policy) VALUES ($1, $2) ON CONFLICT \
+		 (guild_id) DO UPDATE SET age_restricted_policy = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		value
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		"config-age-restricted-updated"
+	))
+	.await?;
+
+	Ok(())
+}
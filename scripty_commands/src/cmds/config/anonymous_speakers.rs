@@ -0,0 +1,42 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle numbering speakers instead of naming them, for transcripts that don't identify anyone.
+///
+/// Speakers are shown as "Speaker 1", "Speaker 2" and so on, numbered afresh each session.
+/// No user IDs are stored with transcripts, and talk time isn't counted for `/analytics`.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "anonymous_speakers"
+)]
+pub async fn config_anonymous_speakers(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] anonymous_speakers: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, anonymous_speakers) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET anonymous_speakers = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		anonymous_speakers
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if anonymous_speakers {
+			"config-anonymous-speakers-enabled"
+		} else {
+			"config-anonymous-speakers-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
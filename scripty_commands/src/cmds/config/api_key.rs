@@ -0,0 +1,59 @@
+use poise::CreateReply;
+use scripty_bot_utils::{api_keys, checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum ApiKeyAction {
+	#[name = "Show"]
+	Show,
+	#[name = "Create"]
+	Create,
+	#[name = "Revoke"]
+	Revoke,
+}
+
+/// Manage this server's API key, for fetching its transcripts from Scripty's API.
+///
+/// While the server has a key, transcripts of its voice sessions are stored for the API.
+/// Slash command only, as the key is only ever shown to whoever made it.
+#[poise::command(
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "api_key"
+)]
+pub async fn config_api_key(ctx: Context<'_>, action: ApiKeyAction) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = match action {
+		ApiKeyAction::Show => match api_keys::get_api_key_info(guild_id).await? {
+			Some((created_at, last_used_at)) => format_message!(
+				resolved_language,
+				"config-api-key-info",
+				createdAt: created_at.unix_timestamp(),
+				lastUsedAt: last_used_at.map_or(0, |t| t.unix_timestamp())
+			),
+			None => format_message!(resolved_language, "config-api-key-none"),
+		},
+		ApiKeyAction::Create => {
+			let api_key = api_keys::create_api_key(guild_id).await?;
+			format_message!(
+				resolved_language,
+				"config-api-key-created",
+				apiKey: api_key,
+				retentionDays: scripty_config::get_config().transcript_api.retention_days
+			)
+		}
+		ApiKeyAction::Revoke => {
+			if api_keys::revoke_api_key(guild_id).await? {
+				format_message!(resolved_language, "config-api-key-revoked")
+			} else {
+				format_message!(resolved_language, "config-api-key-none")
+			}
+		}
+	};
+
+	ctx.send(CreateReply::default().content(message).ephemeral(true))
+		.await?;
+	Ok(())
+}
@@ -3,6 +3,9 @@ use scripty_bot_utils::{checks::is_guild, Context, Error};
 /// Try to automatically detect the language being spoken?
 /// Very inaccurate vs setting a language.
 ///
+/// Optionally, only pick from a comma-separated list of languages, ie `en, de, fr`,
+/// which is far more accurate.
+///
 /// Requires Premium, and is disabled by default.
 #[poise::command(
 	prefix_command,
@@ -14,13 +17,34 @@ use scripty_bot_utils::{checks::is_guild, Context, Error};
 pub async fn config_auto_detect_lang(
 	ctx: Context<'_>,
 	#[description = "Defaults to false"] auto_detect_lang: bool,
+	#[description = "Only pick from these languages, ie `en, de, fr`. Defaults to all of them"]
+	languages: Option<String>,
 ) -> Result<(), Error> {
 	let guild_id = ctx
 		.guild_id()
 		.map(|g| g.get())
 		.ok_or_else(Error::expected_guild)?;
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(guild_id)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let languages: Vec<String> = languages
+		.as_deref()
+		.unwrap_or_default()
+		.split(',')
+		.map(|code| code.trim().to_lowercase())
+		.filter(|code| !code.is_empty())
+		.collect();
+	if let Some(invalid) = languages
+		.iter()
+		.find(|code| !scripty_stt::is_detectable_language(code))
+	{
+		ctx.say(format_message!(
+			resolved_language,
+			"config-auto-detect-lang-invalid-language",
+			language: invalid.as_str()
+		))
+		.await?;
+		return Ok(());
+	}
 
 	let mut forcibly_disable = false;
 	let premium_tier = scripty_premium::get_guild(guild_id)
@@ -36,10 +60,12 @@ pub async fn config_auto_detect_lang(
 	}
 
 	sqlx::query!(
-		"INSERT INTO guilds (guild_id, auto_detect_lang) VALUES ($1, $2) ON CONFLICT (guild_id) \
-		 DO UPDATE SET auto_detect_lang = $2",
+		"INSERT INTO guilds (guild_id, auto_detect_lang, auto_detect_languages) VALUES ($1, $2, \
+		 $3) ON CONFLICT (guild_id) DO UPDATE SET auto_detect_lang = $2, auto_detect_languages = \
+		 $3",
 		guild_id as i64,
-		!forcibly_disable && auto_detect_lang
+		!forcibly_disable && auto_detect_lang,
+		&languages
 	)
 	.execute(scripty_db::get_db())
 	.await?;
@@ -47,14 +73,17 @@ pub async fn config_auto_detect_lang(
 		return Ok(());
 	}
 
-	ctx.say(format_message!(
-		resolved_language,
-		if auto_detect_lang {
-			"config-auto-detect-lang-enabled"
-		} else {
-			"config-auto-detect-lang-disabled"
-		}
-	))
+	ctx.say(if !auto_detect_lang {
+		format_message!(resolved_language, "config-auto-detect-lang-disabled")
+	} else if languages.is_empty() {
+		format_message!(resolved_language, "config-auto-detect-lang-enabled")
+	} else {
+		format_message!(
+			resolved_language,
+			"config-auto-detect-lang-enabled-languages",
+			languages: languages.join(", ")
+		)
+	})
 	.await?;
 
 	Ok(())
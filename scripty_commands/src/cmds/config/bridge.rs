@@ -0,0 +1,131 @@
+use poise::CreateReply;
+use scripty_bot_utils::{
+	checks::is_guild,
+	session_webhooks::{self, WebhookUrlError},
+	Context,
+	Error,
+};
+use scripty_utils::bridge::{self, BridgeTarget};
+use serenity::model::channel::GuildChannel;
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum BridgePlatform {
+	#[name = "Slack"]
+	Slack,
+	#[name = "Matrix"]
+	Matrix,
+	#[name = "Off"]
+	Off,
+}
+
+/// Mirror transcripts to a Slack channel or Matrix room, as they come in.
+///
+/// Slash command only, as Matrix access tokens shouldn't end up in chat.
+#[poise::command(
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "bridge"
+)]
+pub async fn config_bridge(
+	ctx: Context<'_>,
+	platform: BridgePlatform,
+	#[description = "Slack incoming webhook URL, or Matrix homeserver URL"] url: Option<String>,
+	#[description = "Matrix room ID, like !abc:example.org"] matrix_room: Option<String>,
+	#[description = "Access token of the Matrix account to post as"] matrix_token: Option<String>,
+	#[description = "Only bridge sessions in this voice chat. Defaults to all of them."]
+	#[channel_types("Voice", "Stage")]
+	voice_channel: Option<GuildChannel>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	ctx.defer_ephemeral().await?;
+
+	let target = match platform {
+		BridgePlatform::Off => {
+			let message = if bridge::remove_bridge(guild_id).await? {
+				format_message!(resolved_language, "config-bridge-disabled")
+			} else {
+				format_message!(resolved_language, "config-bridge-none")
+			};
+			ctx.send(CreateReply::default().ephemeral(true).content(message))
+				.await?;
+			return Ok(());
+		}
+		BridgePlatform::Slack => url.ok_or("config-bridge-missing-options").and_then(|url| {
+			match session_webhooks::check_webhook_url(&url) {
+				// incoming webhooks are all on this host, so anything else is a mistake
+				Ok(url) if url.host_str() == Some("hooks.slack.com") => Ok(BridgeTarget::Slack {
+					webhook_url: url.into(),
+				}),
+				_ => Err("config-bridge-invalid-slack-url"),
+			}
+		}),
+		BridgePlatform::Matrix => match (url, matrix_room, matrix_token) {
+			(Some(url), Some(room_id), Some(access_token)) => {
+				match session_webhooks::check_webhook_url(&url) {
+					Err(WebhookUrlError::NotHttps) => Err("config-bridge-not-https"),
+					Err(_) => Err("config-bridge-invalid-homeserver"),
+					// room aliases (#room:example.org) would need resolving first
+					Ok(_) if !room_id.starts_with('!') || !room_id.contains(':') => {
+						Err("config-bridge-invalid-matrix-room")
+					}
+					Ok(url) => Ok(BridgeTarget::Matrix {
+						homeserver: url.into(),
+						room_id,
+						access_token,
+					}),
+				}
+			}
+			_ => Err("config-bridge-missing-options"),
+		},
+	};
+	let target = match target {
+		Ok(target) => target,
+		Err(message_id) => {
+			ctx.send(
+				CreateReply::default()
+					.ephemeral(true)
+					.content(format_message!(resolved_language, message_id)),
+			)
+			.await?;
+			return Ok(());
+		}
+	};
+
+	// check it works now, rather than failing quietly mid-session
+	if let Err(e) = target
+		.send(
+			"Scripty",
+			&format_message!(resolved_language, "config-bridge-test-message"),
+		)
+		.await
+	{
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"config-bridge-test-failed",
+					error: e.to_string()
+				)),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let voice_channel_id = voice_channel.map(|channel| channel.id);
+	bridge::set_bridge(guild_id, voice_channel_id, &target).await?;
+
+	let message = match voice_channel_id {
+		Some(channel_id) => format_message!(
+			resolved_language,
+			"config-bridge-enabled-channel",
+			channelId: channel_id.get()
+		),
+		None => format_message!(resolved_language, "config-bridge-enabled"),
+	};
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
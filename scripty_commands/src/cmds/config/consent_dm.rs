@@ -0,0 +1,42 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle DMing members that a voice chat they join is being transcribed, if they've never been
+/// told.
+///
+/// Each user is only DMed once, no matter how many servers they're in.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "consent_dm"
+)]
+pub async fn config_consent_dm(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] consent_dm: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, consent_dm) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET consent_dm = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		consent_dm
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if consent_dm {
+			"config-consent-dm-enabled"
+		} else {
+			"config-consent-dm-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
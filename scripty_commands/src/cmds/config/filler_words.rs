@@ -0,0 +1,41 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle removing filler words, like "um" and "uh", from transcripts.
+///
+/// Which words are removed depends on the server's language.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "filler_words"
+)]
+pub async fn config_filler_words(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] remove_filler_words: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, remove_filler_words) VALUES ($1, $2) ON CONFLICT \
+		 (guild_id) DO UPDATE SET remove_filler_words = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		remove_filler_words
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if remove_filler_words {
+			"config-filler-words-enabled"
+		} else {
+			"config-filler-words-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
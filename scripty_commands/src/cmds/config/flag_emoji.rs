@@ -0,0 +1,81 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use serenity::all::ReactionType;
+
+/// Set an emoji users can react to transcripts with to flag them as wrong.
+///
+/// Flagged transcripts of users who opted in to audio storage are queued for a correction,
+/// to help improve transcriptions. Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "flag_emoji"
+)]
+pub async fn config_flag_emoji(
+	ctx: Context<'_>,
+	#[description = "Emoji to flag transcripts with: set empty to disable."]
+	#[max_length = 100]
+	emoji: Option<String>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let flag_emoji = match emoji.as_deref().map(str::trim) {
+		None => None,
+		Some(emoji) => match to_stored_emoji(emoji) {
+			Some(emoji) => Some(emoji),
+			None => {
+				ctx.say(format_message!(
+					resolved_language,
+					"config-flag-emoji-invalid",
+					emoji: emoji
+				))
+				.await?;
+				return Ok(());
+			}
+		},
+	};
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, flag_emoji) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET flag_emoji = $2",
+		guild_id as i64,
+		flag_emoji
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(match (flag_emoji, emoji) {
+		(Some(_), Some(emoji)) => format_message!(
+			resolved_language,
+			"config-flag-emoji-enabled",
+			emoji: emoji.trim()
+		),
+		_ => format_message!(resolved_language, "config-flag-emoji-disabled"),
+	})
+	.await?;
+
+	Ok(())
+}
+
+/// How `emoji` is stored to match reactions against, or None if it isn't an emoji.
+///
+/// Custom emojis are stored by ID, as they can be renamed.
+pub(super) fn to_stored_emoji(emoji: &str) -> Option<String> {
+	match ReactionType::try_from(emoji) {
+		Ok(ReactionType::Custom { id, .. }) => Some(id.to_string()),
+		Ok(ReactionType::Unicode(emoji))
+			if !emoji.is_empty()
+				&& !emoji
+					.chars()
+					.any(|c| c.is_ascii_alphabetic() || c.is_whitespace()) =>
+		{
+			Some(emoji.to_string())
+		}
+		_ => None,
+	}
+}
@@ -0,0 +1,64 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+use super::flag_emoji::to_stored_emoji;
+
+/// Set an emoji users can react to transcripts with to add them to the session's highlights.
+///
+/// Highlights are listed in `/minutes`, and at the end of the session's transcript file.
+/// Defaults to 📌.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "highlight_emoji"
+)]
+pub async fn config_highlight_emoji(
+	ctx: Context<'_>,
+	#[description = "Emoji to highlight transcripts with: set empty to disable."]
+	#[max_length = 100]
+	emoji: Option<String>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let highlight_emoji = match emoji.as_deref().map(str::trim) {
+		None => None,
+		Some(emoji) => match to_stored_emoji(emoji) {
+			Some(emoji) => Some(emoji),
+			None => {
+				ctx.say(format_message!(
+					resolved_language,
+					"config-highlight-emoji-invalid",
+					emoji: emoji
+				))
+				.await?;
+				return Ok(());
+			}
+		},
+	};
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, highlight_emoji) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET highlight_emoji = $2",
+		guild_id as i64,
+		highlight_emoji
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(match (highlight_emoji, emoji) {
+		(Some(_), Some(emoji)) => format_message!(
+			resolved_language,
+			"config-highlight-emoji-enabled",
+			emoji: emoji.trim()
+		),
+		_ => format_message!(resolved_language, "config-highlight-emoji-disabled"),
+	})
+	.await?;
+
+	Ok(())
+}
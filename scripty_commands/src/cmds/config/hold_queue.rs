@@ -0,0 +1,92 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use serenity::{
+	model::{channel::GuildChannel, permissions::Permissions},
+	prelude::Mentionable,
+};
+
+/// Longest held transcripts can wait before they're approved automatically: a day.
+const MAX_AUTO_APPROVE_MINUTES: u16 = 24 * 60;
+
+/// Hold transcripts in a private channel until a moderator approves them, ie during a public event.
+///
+/// Held transcripts get Approve and Reject buttons there, which anyone who can manage messages in
+/// that channel can use. Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "hold_queue"
+)]
+pub async fn config_hold_queue(
+	ctx: Context<'_>,
+
+	#[description = "Review transcripts here before they're posted: set empty to disable."]
+	#[channel_types("Text")]
+	channel: Option<GuildChannel>,
+
+	#[description = "Post transcripts nobody rejected after this many minutes. Defaults to \
+	                 waiting for a moderator."]
+	auto_approve_minutes: Option<u16>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	if let Some(minutes) = auto_approve_minutes {
+		if minutes == 0 || minutes > MAX_AUTO_APPROVE_MINUTES {
+			ctx.say(format_message!(
+				resolved_language,
+				"config-hold-queue-invalid-timeout",
+				maxMinutes: MAX_AUTO_APPROVE_MINUTES
+			))
+			.await?;
+			return Ok(());
+		}
+	}
+	if let Some(ref channel) = channel {
+		let required_permissions = Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS;
+		let permissions = channel.permissions_for_user(ctx, ctx.framework().bot_id)?;
+		if !permissions.contains(required_permissions) {
+			ctx.say(format_message!(
+				resolved_language,
+				"config-hold-queue-missing-permissions",
+				channelMention: channel.mention().to_string(),
+				missingPermissions: ((!permissions) & required_permissions).to_string()
+			))
+			.await?;
+			return Ok(());
+		}
+	}
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, hold_channel_id, hold_auto_approve_secs) VALUES ($1, $2, \
+		 $3) ON CONFLICT (guild_id) DO UPDATE SET hold_channel_id = $2, hold_auto_approve_secs = \
+		 $3",
+		guild_id as i64,
+		channel.as_ref().map(|x| x.id.get() as i64),
+		auto_approve_minutes.map(|minutes| i32::from(minutes) * 60)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(match (channel, auto_approve_minutes) {
+		(Some(channel), Some(minutes)) => format_message!(
+			resolved_language,
+			"config-hold-queue-enabled-auto-approve",
+			channelMention: channel.mention().to_string(),
+			minutes: minutes
+		),
+		(Some(channel), None) => format_message!(
+			resolved_language,
+			"config-hold-queue-enabled",
+			channelMention: channel.mention().to_string()
+		),
+		(None, _) => format_message!(resolved_language, "config-hold-queue-disabled"),
+	})
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,42 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle posting a line in the transcript when someone joins or leaves the voice chat.
+///
+/// Also goes in the transcript file, so it's clear why new speakers show up partway through.
+/// Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "join_leave_notices"
+)]
+pub async fn config_join_leave_notices(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] join_leave_notices: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, join_leave_notices) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET join_leave_notices = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		join_leave_notices
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if join_leave_notices {
+			"config-join-leave-notices-enabled"
+		} else {
+			"config-join-leave-notices-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,41 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle live captions of voice chats, for `/captions`, the `/api/live` WebSocket and overlays.
+///
+/// They're off until turned on, so nobody can follow a voice chat live unless the server wants it.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "live_captions"
+)]
+pub async fn config_live_captions(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] live_captions: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, live_captions) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET live_captions = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		live_captions
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if live_captions {
+			"config-live-captions-enabled"
+		} else {
+			"config-live-captions-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,52 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Longest gap that can be set, so old messages aren't edited long after they were sent.
+const MAX_MERGE_GAP_SECS: u16 = 60;
+
+/// Merge each speaker's back-to-back transcripts into one message, instead of sending one each.
+///
+/// Only transcripts less than `gap` seconds apart, with nobody else speaking between, are merged.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "merge_utterances"
+)]
+pub async fn config_merge_utterances(
+	ctx: Context<'_>,
+	#[description = "Seconds between transcripts to still merge them. 0 turns merging off."]
+	gap: u16,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	if gap > MAX_MERGE_GAP_SECS {
+		ctx.say(format_message!(
+			resolved_language,
+			"config-merge-utterances-too-long",
+			maxGap: MAX_MERGE_GAP_SECS
+		))
+		.await?;
+		return Ok(());
+	}
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, merge_gap_secs) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET merge_gap_secs = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		gap as i16
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(if gap == 0 {
+		format_message!(resolved_language, "config-merge-utterances-disabled")
+	} else {
+		format_message!(resolved_language, "config-merge-utterances-enabled", gap: gap)
+	})
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,74 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Longest minimum that can be set, so real sentences aren't dropped by mistake.
+const MAX_MIN_LENGTH: u16 = 50;
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum MinLengthUnitChoice {
+	#[name = "Characters"]
+	Characters,
+	#[name = "Words"]
+	Words,
+}
+
+/// Drop transcripts shorter than this, like coughs or a lone "yeah", to keep the channel quieter.
+///
+/// Only applies to voice chats, not voice messages or audio files.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "min_length"
+)]
+pub async fn config_min_length(
+	ctx: Context<'_>,
+	#[description = "Shortest transcript to send. 0 sends everything."] length: u16,
+	#[description = "Defaults to Characters"] unit: Option<MinLengthUnitChoice>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	if length > MAX_MIN_LENGTH {
+		ctx.say(format_message!(
+			resolved_language,
+			"config-min-length-too-long",
+			maxLength: MAX_MIN_LENGTH
+		))
+		.await?;
+		return Ok(());
+	}
+
+	let unit = unit.unwrap_or(MinLengthUnitChoice::Characters);
+	// matches scripty_stt's LengthUnit
+	let unit_value: i16 = match unit {
+		MinLengthUnitChoice::Characters => 0,
+		MinLengthUnitChoice::Words => 1,
+	};
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, min_transcript_length, min_transcript_length_unit) VALUES \
+		 ($1, $2, $3) ON CONFLICT (guild_id) DO UPDATE SET min_transcript_length = $2, \
+		 min_transcript_length_unit = $3",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		length as i16,
+		unit_value
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(match (length, unit) {
+		(0, _) => format_message!(resolved_language, "config-min-length-disabled"),
+		(_, MinLengthUnitChoice::Characters) => format_message!(
+			resolved_language,
+			"config-min-length-characters",
+			length: length
+		),
+		(_, MinLengthUnitChoice::Words) => {
+			format_message!(resolved_language, "config-min-length-words", length: length)
+		}
+	})
+	.await?;
+
+	Ok(())
+}
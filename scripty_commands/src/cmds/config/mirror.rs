@@ -0,0 +1,212 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use scripty_utils::{
+	entity_cache::get_channel_webhook,
+	mirrors::{self, Mirror, MAX_MIRRORS},
+};
+use serenity::{
+	builder::{CreateAllowedMentions, CreateEmbed},
+	model::channel::GuildChannel,
+	prelude::Mentionable,
+};
+
+use super::transcript_style::TranscriptStyleChoice;
+
+/// Also send a voice chat's transcripts to other channels, ie a moderation archive.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "mirror"
+)]
+pub async fn config_mirror(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "config mirror"
+				)),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
+
+/// Also send this voice chat's transcripts to a channel, in its own style.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "add"
+)]
+pub async fn config_mirror_add(
+	ctx: Context<'_>,
+	#[description = "Voice chat to mirror the transcripts of"]
+	#[channel_types("Voice", "Stage")]
+	voice_channel: GuildChannel,
+	#[description = "Channel to send them to"]
+	#[channel_types("Text")]
+	channel: GuildChannel,
+	#[description = "How they look in that channel. Defaults to Webhook"] style: Option<
+		TranscriptStyleChoice,
+	>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	// transcripts are sent with a webhook, so fail now rather than silently every time
+	if get_channel_webhook(ctx.serenity_context(), channel.id)
+		.await
+		.is_err()
+	{
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"config-mirror-no-webhook",
+					channel: channel.mention().to_string()
+				)),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let mirror = Mirror {
+		voice_channel_id: voice_channel.id,
+		channel_id:       channel.id,
+		style:            style.unwrap_or(TranscriptStyleChoice::Webhook).to_db(),
+	};
+	let message = if mirrors::set_mirror(guild_id, mirror).await? {
+		format_message!(
+			resolved_language,
+			"config-mirror-added",
+			voiceChannel: voice_channel.mention().to_string(),
+			channel: channel.mention().to_string()
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"config-mirror-full",
+			maxMirrors: MAX_MIRRORS
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Stop sending this voice chat's transcripts to a channel.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "remove"
+)]
+pub async fn config_mirror_remove(
+	ctx: Context<'_>,
+	#[description = "Voice chat being mirrored"]
+	#[channel_types("Voice", "Stage")]
+	voice_channel: GuildChannel,
+	#[description = "Channel to stop sending its transcripts to"]
+	#[channel_types("Text")]
+	channel: GuildChannel,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = if mirrors::remove_mirror(guild_id, voice_channel.id, channel.id).await? {
+		"config-mirror-removed"
+	} else {
+		"config-mirror-not-found"
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(format_message!(
+				resolved_language,
+				message,
+				voiceChannel: voice_channel.mention().to_string(),
+				channel: channel.mention().to_string()
+			)),
+	)
+	.await?;
+	Ok(())
+}
+
+/// List this server's transcript mirrors.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "list"
+)]
+pub async fn config_mirror_list(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let mirrors = mirrors::get_mirrors(guild_id).await?;
+	if mirrors.is_empty() {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "config-mirror-none")),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let list = mirrors
+		.into_iter()
+		.map(|mirror| {
+			let style = match mirror.style {
+				1 => "Compact",
+				2 => "Embed",
+				_ => "Webhook",
+			};
+			format_message!(
+				resolved_language,
+				"config-mirror-list-entry",
+				voiceChannel: mirror.voice_channel_id.mention().to_string(),
+				channel: mirror.channel_id.mention().to_string(),
+				style: style
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"config-mirror-list-title"
+				))
+				.description(list),
+		),
+	)
+	.await?;
+	Ok(())
+}
@@ -1,23 +1,90 @@
+mod age_restricted;
+mod anonymous_speakers;
+mod api_key;
 mod auto_detect_lang;
+mod bridge;
+mod consent_dm;
+mod filler_words;
+mod flag_emoji;
+mod highlight_emoji;
+mod hold_queue;
+mod join_leave_notices;
 mod language;
+mod live_captions;
+mod merge_utterances;
+mod min_length;
+mod mirror;
+mod numbers;
+mod profanity_filter;
+mod replacements;
+mod role_policy;
+mod scheduled_events;
+mod silence_timeout;
+mod speaker_labels;
+mod subscriptions;
+mod timestamps;
 mod transcribe_audio;
 mod transcribe_only_role;
 mod transcribe_video;
 mod transcribe_voice_messages;
+mod transcript_style;
 mod translate;
+mod tts_relay;
 mod verbose;
+mod voice_commands;
+mod watch;
+mod webhook_url;
 
+pub use age_restricted::config_age_restricted;
+pub use anonymous_speakers::config_anonymous_speakers;
+pub use api_key::config_api_key;
 pub use auto_detect_lang::config_auto_detect_lang;
+pub use bridge::config_bridge;
+pub use consent_dm::config_consent_dm;
+pub use filler_words::config_filler_words;
+pub use flag_emoji::config_flag_emoji;
+pub use highlight_emoji::config_highlight_emoji;
+pub use hold_queue::config_hold_queue;
+pub use join_leave_notices::config_join_leave_notices;
 pub use language::config_server_language;
+pub use live_captions::config_live_captions;
+pub use merge_utterances::config_merge_utterances;
+pub use min_length::config_min_length;
+pub use mirror::{config_mirror, config_mirror_add, config_mirror_list, config_mirror_remove};
+pub use numbers::config_numbers;
 use poise::CreateReply;
+pub use profanity_filter::config_profanity_filter;
+pub use replacements::{
+	config_replacements,
+	config_replacements_add,
+	config_replacements_list,
+	config_replacements_remove,
+};
+pub use role_policy::{
+	config_role_policy,
+	config_role_policy_allow,
+	config_role_policy_deny,
+	config_role_policy_list,
+	config_role_policy_remove,
+};
+pub use scheduled_events::config_scheduled_events;
 use scripty_bot_utils::{checks::is_guild, Context, Error};
 use serenity::builder::CreateEmbed;
+pub use silence_timeout::config_silence_timeout;
+pub use speaker_labels::config_speaker_labels;
+pub use subscriptions::config_subscriptions;
+pub use timestamps::config_timestamps;
 pub use transcribe_audio::config_transcribe_audio;
 pub use transcribe_only_role::config_transcribe_only_role;
 pub use transcribe_video::config_transcribe_video;
 pub use transcribe_voice_messages::config_transcribe_voice_messages;
+pub use transcript_style::config_transcript_style;
 pub use translate::config_translate;
+pub use tts_relay::config_tts_relay;
 pub use verbose::config_verbose;
+pub use voice_commands::config_voice_commands;
+pub use watch::{config_watch, config_watch_add, config_watch_list, config_watch_remove};
+pub use webhook_url::config_webhook_url;
 
 /// Configure Scripty's settings
 #[poise::command(
@@ -28,9 +95,7 @@ pub use verbose::config_verbose;
 	rename = "config"
 )]
 pub async fn config_root(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	ctx.send(
 		CreateReply::default().ephemeral(true).embed(
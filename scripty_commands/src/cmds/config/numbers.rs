@@ -0,0 +1,51 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum NumberFormatChoice {
+	#[name = "Words"]
+	Words,
+	#[name = "Digits"]
+	Digits,
+}
+
+/// Choose whether numbers in transcripts are written as words or digits.
+///
+/// With digits, "at three thirty pm" becomes "at 3:30 pm". Only works for English.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "numbers"
+)]
+pub async fn config_numbers(
+	ctx: Context<'_>,
+	#[description = "Defaults to Words, or however the transcription model writes them"]
+	format: NumberFormatChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let digits = matches!(format, NumberFormatChoice::Digits);
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, numbers_as_digits) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET numbers_as_digits = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		digits
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if digits {
+			"config-numbers-digits"
+		} else {
+			"config-numbers-words"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
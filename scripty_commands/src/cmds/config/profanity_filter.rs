@@ -0,0 +1,58 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum ProfanityFilterChoice {
+	#[name = "Off"]
+	Off,
+	#[name = "Mask"]
+	Mask,
+	#[name = "Spoiler"]
+	Spoiler,
+}
+
+/// Hide swear words in transcripts.
+///
+/// Mask replaces all but their first letter with asterisks.
+/// Spoiler hides them behind spoiler tags, so anyone who wants to can still read them.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "profanity_filter"
+)]
+pub async fn config_profanity_filter(
+	ctx: Context<'_>,
+	#[description = "Defaults to Off"] mode: ProfanityFilterChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	// matches scripty_stt's ProfanityMode
+	let value: i16 = match mode {
+		ProfanityFilterChoice::Off => 0,
+		ProfanityFilterChoice::Mask => 1,
+		ProfanityFilterChoice::Spoiler => 2,
+	};
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, profanity_filter) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET profanity_filter = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		value
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		match mode {
+			ProfanityFilterChoice::Off => "config-profanity-filter-disabled",
+			ProfanityFilterChoice::Mask => "config-profanity-filter-mask",
+			ProfanityFilterChoice::Spoiler => "config-profanity-filter-spoiler",
+		}
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,181 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use scripty_utils::{
+	do_paginate,
+	replacements::{self, MAX_REPLACEMENTS, MAX_REPLACEMENT_LEN},
+};
+use serenity::builder::CreateEmbed;
+
+/// Fix words Scripty keeps getting wrong in this server's transcripts.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "replacements"
+)]
+pub async fn config_replacements(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "config replacements"
+				)),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
+
+/// Replace text matching a regex in every transcript. Matching ignores case.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "add"
+)]
+pub async fn config_replacements_add(
+	ctx: Context<'_>,
+	#[description = "Regex to find, like scriptee or \\bteh\\b"] pattern: String,
+	#[description = "Text to put in its place, or empty to remove it. $1 inserts the first group"]
+	replacement: Option<String>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	let replacement = replacement.unwrap_or_default();
+
+	let message = if pattern.len() > MAX_REPLACEMENT_LEN || replacement.len() > MAX_REPLACEMENT_LEN
+	{
+		format_message!(
+			resolved_language,
+			"config-replacements-too-long",
+			maxLength: MAX_REPLACEMENT_LEN
+		)
+	} else {
+		match scripty_stt::Replacements::compile(&pattern) {
+			Err(e) => format_message!(
+				resolved_language,
+				"config-replacements-invalid-pattern",
+				error: e.to_string()
+			),
+			// it would insert the replacement between every letter
+			Ok(regex) if regex.is_match("") => {
+				format_message!(resolved_language, "config-replacements-matches-empty")
+			}
+			Ok(_) => match replacements::add_replacement(guild_id, &pattern, &replacement).await? {
+				Some(id) => format_message!(
+					resolved_language,
+					"config-replacements-added",
+					id: id,
+					pattern: pattern,
+					replacement: replacement
+				),
+				None => format_message!(
+					resolved_language,
+					"config-replacements-full",
+					maxReplacements: MAX_REPLACEMENTS
+				),
+			},
+		}
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
+
+/// Stop replacing text in transcripts.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "remove"
+)]
+pub async fn config_replacements_remove(
+	ctx: Context<'_>,
+	#[description = "ID of the replacement to remove, from the list command"] id: i32,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = if replacements::remove_replacement(guild_id, id).await? {
+		format_message!(resolved_language, "config-replacements-removed", id: id)
+	} else {
+		format_message!(resolved_language, "config-replacements-not-found", id: id)
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
+
+/// List this server's transcript replacements.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "list"
+)]
+pub async fn config_replacements_list(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let rules = replacements::get_replacements(guild_id).await?;
+	if rules.is_empty() {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"config-replacements-none"
+				)),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let formatted_rules = rules
+		.into_iter()
+		.map(|rule| {
+			(
+				format_message!(
+					resolved_language,
+					"config-replacements-list-field-name",
+					id: rule.id
+				),
+				format_message!(
+					resolved_language,
+					"config-replacements-list-field-value",
+					pattern: rule.pattern,
+					replacement: rule.replacement
+				),
+			)
+		})
+		.collect::<Vec<_>>();
+
+	do_paginate(
+		ctx.serenity_context(),
+		ctx.channel_id(),
+		formatted_rules,
+		format_message!(resolved_language, "config-replacements-list-title"),
+		None,
+		None,
+		Some(ctx.author().id),
+	)
+	.await?;
+
+	Ok(())
+}
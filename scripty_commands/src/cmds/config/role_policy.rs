@@ -0,0 +1,225 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use scripty_utils::role_policy::{self, RolePolicy, MAX_ROLE_POLICIES};
+use serenity::{
+	all::RoleId,
+	builder::{CreateAllowedMentions, CreateEmbed},
+	prelude::Mentionable,
+};
+
+/// Choose which roles are transcribed, ie never transcribing guests.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "role_policy"
+)]
+pub async fn config_role_policy(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "config role_policy"
+				)),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
+
+/// Only transcribe members with this role, or any other allowed role.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "allow"
+)]
+pub async fn config_role_policy_allow(
+	ctx: Context<'_>,
+	#[description = "Role to allow"] role: RoleId,
+	#[description = "Also allow every role above it? Defaults to false."] and_above: Option<bool>,
+) -> Result<(), Error> {
+	let and_above = and_above.unwrap_or(false);
+	set_policy(
+		ctx,
+		RolePolicy {
+			role_id: role,
+			allow: true,
+			and_above,
+		},
+		if and_above {
+			"config-role-policy-allowed-and-above"
+		} else {
+			"config-role-policy-allowed"
+		},
+	)
+	.await
+}
+
+/// Never transcribe members with this role.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "deny"
+)]
+pub async fn config_role_policy_deny(
+	ctx: Context<'_>,
+	#[description = "Role to never transcribe"] role: RoleId,
+) -> Result<(), Error> {
+	set_policy(
+		ctx,
+		RolePolicy {
+			role_id:   role,
+			allow:     false,
+			and_above: false,
+		},
+		"config-role-policy-denied",
+	)
+	.await
+}
+
+/// Remove this role's policy.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "remove"
+)]
+pub async fn config_role_policy_remove(
+	ctx: Context<'_>,
+	#[description = "Role to remove the policy of"] role: RoleId,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = if role_policy::remove_role_policy(guild_id, role).await? {
+		format_message!(
+			resolved_language,
+			"config-role-policy-removed",
+			role: role.mention().to_string()
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"config-role-policy-not-found",
+			role: role.mention().to_string()
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
+
+/// List this server's role policies.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "list"
+)]
+pub async fn config_role_policy_list(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let policies = role_policy::get_role_policies(guild_id).await?;
+	if policies.is_empty() {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(
+					resolved_language,
+					"config-role-policy-none"
+				)),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let list = policies
+		.into_iter()
+		.map(|policy| {
+			let role = policy.role_id.mention().to_string();
+			match (policy.allow, policy.and_above) {
+				(true, true) => format_message!(
+					resolved_language,
+					"config-role-policy-list-allowed-and-above",
+					role: role
+				),
+				(true, false) => format_message!(
+					resolved_language,
+					"config-role-policy-list-allowed",
+					role: role
+				),
+				(false, _) => format_message!(
+					resolved_language,
+					"config-role-policy-list-denied",
+					role: role
+				),
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"config-role-policy-list-title"
+				))
+				.description(list),
+		),
+	)
+	.await?;
+	Ok(())
+}
+
+async fn set_policy(ctx: Context<'_>, policy: RolePolicy, message_id: &str) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = if role_policy::set_role_policy(guild_id, policy).await? {
+		format_message!(
+			resolved_language,
+			message_id,
+			role: policy.role_id.mention().to_string()
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"config-role-policy-full",
+			maxPolicies: MAX_ROLE_POLICIES
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
@@ -0,0 +1,80 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use serenity::{
+	model::{channel::GuildChannel, permissions::Permissions},
+	prelude::Mentionable,
+};
+
+/// Transcribe voice channel events automatically, from when they're started until they end.
+///
+/// Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "scheduled_events"
+)]
+pub async fn config_scheduled_events(
+	ctx: Context<'_>,
+
+	#[description = "Send event transcripts here: set empty to disable."]
+	#[channel_types("Text")]
+	channel: Option<GuildChannel>,
+
+	#[description = "Create a thread in that channel for each event? Defaults to false."]
+	create_thread: Option<bool>,
+
+	#[description = "Send the transcript as a file when the event ends? Users who spoke will be \
+	                 DMed it too. Defaults to false."]
+	record_transcriptions: Option<bool>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let create_thread = create_thread.unwrap_or(false);
+	let record_transcriptions = record_transcriptions.unwrap_or(false);
+
+	if let Some(ref channel) = channel {
+		let mut required_permissions = Permissions::SEND_MESSAGES | Permissions::MANAGE_WEBHOOKS;
+		if create_thread {
+			required_permissions |= Permissions::CREATE_PUBLIC_THREADS;
+		}
+		let permissions = channel.permissions_for_user(ctx, ctx.framework().bot_id)?;
+		if !permissions.contains(required_permissions) {
+			ctx.say(format_message!(
+				resolved_language,
+				"config-scheduled-events-missing-permissions",
+				channelMention: channel.mention().to_string(),
+				missingPermissions: ((!permissions) & required_permissions).to_string()
+			))
+			.await?;
+			return Ok(());
+		}
+	}
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, scheduled_event_channel, scheduled_event_thread, \
+		 scheduled_event_record) VALUES ($1, $2, $3, $4) ON CONFLICT (guild_id) DO UPDATE SET \
+		 scheduled_event_channel = $2, scheduled_event_thread = $3, scheduled_event_record = $4",
+		guild_id as i64,
+		channel.as_ref().map(|x| x.id.get() as i64),
+		create_thread,
+		record_transcriptions
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(match channel {
+		Some(channel) => format_message!(
+			resolved_language,
+			"config-scheduled-events-enabled",
+			channelMention: channel.mention().to_string()
+		),
+		None => format_message!(resolved_language, "config-scheduled-events-disabled"),
+	})
+	.await?;
+
+	Ok(())
+}
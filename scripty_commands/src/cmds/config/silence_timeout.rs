@@ -0,0 +1,52 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Longest timeout that can be set, as nothing is sent until it's passed.
+const MAX_SILENCE_TIMEOUT_MS: u16 = 5000;
+
+/// How long someone has to stop talking for before their transcript is sent.
+///
+/// Shorter is faster, but splits sentences across more messages when people pause mid-thought.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "silence_timeout"
+)]
+pub async fn config_silence_timeout(
+	ctx: Context<'_>,
+	#[description = "In milliseconds. Defaults to 0, sending as soon as someone stops talking."]
+	timeout_ms: u16,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	if timeout_ms > MAX_SILENCE_TIMEOUT_MS {
+		ctx.say(format_message!(
+			resolved_language,
+			"config-silence-timeout-too-long",
+			maxTimeout: MAX_SILENCE_TIMEOUT_MS
+		))
+		.await?;
+		return Ok(());
+	}
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, silence_timeout_ms) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET silence_timeout_ms = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		timeout_ms as i16
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		"config-silence-timeout-updated",
+		timeout: timeout_ms
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,58 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum SpeakerLabelChoice {
+	#[name = "Off"]
+	Off,
+	#[name = "Color"]
+	Color,
+	#[name = "Emoji"]
+	Emoji,
+}
+
+/// Give each speaker their own color or emoji on transcripts, so it's easier to tell who's who.
+///
+/// Colors put transcripts in an embed, using the speaker's color as its accent.
+/// Emoji go before each transcript.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "speaker_labels"
+)]
+pub async fn config_speaker_labels(
+	ctx: Context<'_>,
+	#[description = "Defaults to Off"] mode: SpeakerLabelChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	// matches scripty_audio_handler's SpeakerLabelMode
+	let value: i16 = match mode {
+		SpeakerLabelChoice::Off => 0,
+		SpeakerLabelChoice::Color => 1,
+		SpeakerLabelChoice::Emoji => 2,
+	};
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, speaker_labels) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET speaker_labels = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		value
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		match mode {
+			SpeakerLabelChoice::Off => "config-speaker-labels-disabled",
+			SpeakerLabelChoice::Color => "config-speaker-labels-color",
+			SpeakerLabelChoice::Emoji => "config-speaker-labels-emoji",
+		}
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,41 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+/// Toggle letting members get voice chats' transcripts DMed to them with `/subscribe`.
+///
+/// Turning this off keeps everyone's subscriptions, but nothing is sent until it's turned back on.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "subscriptions"
+)]
+pub async fn config_subscriptions(
+	ctx: Context<'_>,
+	#[description = "Defaults to false"] subscriptions: bool,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, allow_subscriptions) VALUES ($1, $2) ON CONFLICT \
+		 (guild_id) DO UPDATE SET allow_subscriptions = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		subscriptions
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		if subscriptions {
+			"config-subscriptions-enabled"
+		} else {
+			"config-subscriptions-disabled"
+		}
+	))
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,58 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum TimestampChoice {
+	#[name = "Off"]
+	Off,
+	#[name = "Relative"]
+	Relative,
+	#[name = "Absolute"]
+	Absolute,
+}
+
+/// Start each transcript with when it was said, for using transcripts as meeting minutes.
+///
+/// Relative timestamps count from when Scripty joined. Absolute ones are the time of day,
+/// shown in each reader's own timezone.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "timestamps"
+)]
+pub async fn config_timestamps(
+	ctx: Context<'_>,
+	#[description = "Defaults to Off"] mode: TimestampChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	// matches scripty_audio_handler's TimestampMode
+	let value: i16 = match mode {
+		TimestampChoice::Off => 0,
+		TimestampChoice::Relative => 1,
+		TimestampChoice::Absolute => 2,
+	};
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, timestamps) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET timestamps = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		value
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		match mode {
+			TimestampChoice::Off => "config-timestamps-disabled",
+			TimestampChoice::Relative => "config-timestamps-relative",
+			TimestampChoice::Absolute => "config-timestamps-absolute",
+		}
+	))
+	.await?;
+
+	Ok(())
+}
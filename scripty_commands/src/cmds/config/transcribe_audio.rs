@@ -16,8 +16,7 @@ pub async fn config_transcribe_audio(
 		.guild_id()
 		.map(|g| g.get())
 		.ok_or_else(Error::expected_guild)?;
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(guild_id)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let mut forcibly_disable = false;
 	let premium_tier = scripty_premium::get_guild(guild_id)
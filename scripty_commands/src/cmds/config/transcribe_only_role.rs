@@ -18,8 +18,7 @@ pub async fn config_transcribe_only_role(
 		.guild_id()
 		.map(|g| g.get())
 		.ok_or_else(Error::expected_guild)?;
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), Some(guild_id)).await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	sqlx::query!(
 		"INSERT INTO guilds (guild_id, transcript_only_role) VALUES ($1, $2) ON CONFLICT \
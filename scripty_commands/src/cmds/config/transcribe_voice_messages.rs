@@ -12,9 +12,7 @@ pub async fn config_transcribe_voice_messages(
 	ctx: Context<'_>,
 	#[description = "Defaults to true"] transcribe_voice_messages: bool,
 ) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	sqlx::query!(
 		"INSERT INTO guilds (guild_id, transcribe_voice_messages) VALUES ($1, $2) ON CONFLICT \
@@ -0,0 +1,59 @@
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum TranscriptStyleChoice {
+	#[name = "Webhook"]
+	Webhook,
+	#[name = "Compact"]
+	Compact,
+	#[name = "Embed"]
+	Embed,
+}
+
+impl TranscriptStyleChoice {
+	/// Matches scripty_audio_handler's TranscriptStyle.
+	pub(super) fn to_db(self) -> i16 {
+		match self {
+			Self::Webhook => 0,
+			Self::Compact => 1,
+			Self::Embed => 2,
+		}
+	}
+}
+
+/// Choose how transcripts look.
+///
+/// Webhook posts them as if each speaker sent them. Compact posts them as plain text, starting
+/// with the speaker's name. Embed puts them in embeds, with the speaker's avatar and name.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "transcript_style"
+)]
+pub async fn config_transcript_style(
+	ctx: Context<'_>,
+	#[description = "Defaults to Webhook"] style: TranscriptStyleChoice,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, transcript_style) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET transcript_style = $2",
+		ctx.guild_id()
+			.map(|g| g.get())
+			.ok_or_else(Error::expected_guild)? as i64,
+		style.to_db()
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.say(format_message!(
+		resolved_language,
+		"config-transcript-style-updated"
+	))
+	.await?;
+
+	Ok(())
+}
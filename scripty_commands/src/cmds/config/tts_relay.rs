@@ -0,0 +1,58 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use serenity::{all::RoleId, builder::CreateAllowedMentions, prelude::Mentionable};
+
+/// Read out messages sent in the transcript channel by members with this role, in the voice chat.
+///
+/// For members who can't or don't want to speak. Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "tts_relay"
+)]
+pub async fn config_tts_relay(
+	ctx: Context<'_>,
+	#[description = "Role whose messages are read out: set empty to disable."] role: Option<RoleId>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	if !scripty_audio_handler::is_tts_enabled() {
+		ctx.say(format_message!(
+			resolved_language,
+			"config-tts-relay-unavailable"
+		))
+		.await?;
+		return Ok(());
+	}
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, tts_relay_role) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
+		 UPDATE SET tts_relay_role = $2",
+		guild_id as i64,
+		role.map(|x| x.get() as i64)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.send(
+		CreateReply::default()
+			.allowed_mentions(CreateAllowedMentions::new().empty_roles())
+			.content(match role {
+				Some(role) => format_message!(
+					resolved_language,
+					"config-tts-relay-enabled",
+					roleId: role.mention().to_string()
+				),
+				None => format_message!(resolved_language, "config-tts-relay-disabled"),
+			}),
+	)
+	.await?;
+
+	Ok(())
+}
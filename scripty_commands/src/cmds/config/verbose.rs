@@ -14,9 +14,7 @@ pub async fn config_verbose(
 	ctx: Context<'_>,
 	#[description = "Defaults to false"] verbose: bool,
 ) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	sqlx::query!(
 		"INSERT INTO guilds (guild_id, be_verbose) VALUES ($1, $2) ON CONFLICT (guild_id) DO \
@@ -0,0 +1,49 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use serenity::{all::RoleId, builder::CreateAllowedMentions, prelude::Mentionable};
+
+/// Let members with this role control Scripty by voice, ie "Scripty, stop transcribing".
+///
+/// Scripty understands "stop", "start" and "leave". Disabled by default.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "voice_commands"
+)]
+pub async fn config_voice_commands(
+	ctx: Context<'_>,
+	#[description = "Role that can use voice commands: set empty to disable."] role: Option<RoleId>,
+) -> Result<(), Error> {
+	let guild_id = ctx
+		.guild_id()
+		.map(|g| g.get())
+		.ok_or_else(Error::expected_guild)?;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id, voice_command_role) VALUES ($1, $2) ON CONFLICT (guild_id) \
+		 DO UPDATE SET voice_command_role = $2",
+		guild_id as i64,
+		role.map(|x| x.get() as i64)
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	ctx.send(
+		CreateReply::default()
+			.allowed_mentions(CreateAllowedMentions::new().empty_roles())
+			.content(match role {
+				Some(role) => format_message!(
+					resolved_language,
+					"config-voice-commands-enabled",
+					roleId: role.mention().to_string()
+				),
+				None => format_message!(resolved_language, "config-voice-commands-disabled"),
+			}),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,193 @@
+use poise::CreateReply;
+use scripty_bot_utils::{checks::is_guild, Context, Error};
+use scripty_utils::watch::{self, WatchTarget, MAX_WATCHED};
+use serenity::{
+	all::RoleId,
+	builder::{CreateAllowedMentions, CreateEmbed},
+	model::user::User,
+	prelude::Mentionable,
+};
+
+/// Only transcribe certain users or roles, ie the GM of a game, or an interviewee.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "watch"
+)]
+pub async fn config_watch(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "config watch"
+				)),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
+
+/// Transcribe this user or role. Once anyone's watched, only those watched are transcribed.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "add"
+)]
+pub async fn config_watch_add(
+	ctx: Context<'_>,
+	#[description = "User to transcribe"] user: Option<User>,
+	#[description = "Role to transcribe the members of"] role: Option<RoleId>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let Some(target) = get_target(user, role) else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "config-watch-no-target")),
+		)
+		.await?;
+		return Ok(());
+	};
+
+	let message = if watch::add_watched(guild_id, target).await? {
+		format_message!(
+			resolved_language,
+			"config-watch-added",
+			target: mention(target)
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"config-watch-full",
+			maxWatched: MAX_WATCHED
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Stop only transcribing this user or role.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "remove"
+)]
+pub async fn config_watch_remove(
+	ctx: Context<'_>,
+	#[description = "User to stop watching"] user: Option<User>,
+	#[description = "Role to stop watching"] role: Option<RoleId>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let Some(target) = get_target(user, role) else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "config-watch-no-target")),
+		)
+		.await?;
+		return Ok(());
+	};
+
+	let message = if watch::remove_watched(guild_id, target).await? {
+		if watch::get_watched(guild_id).await?.is_empty() {
+			format_message!(resolved_language, "config-watch-removed-last")
+		} else {
+			format_message!(
+				resolved_language,
+				"config-watch-removed",
+				target: mention(target)
+			)
+		}
+	} else {
+		format_message!(
+			resolved_language,
+			"config-watch-not-found",
+			target: mention(target)
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
+
+/// List who's being watched in this server.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "list"
+)]
+pub async fn config_watch_list(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let watched = watch::get_watched(guild_id).await?;
+	let message = if watched.is_empty() {
+		format_message!(resolved_language, "config-watch-none")
+	} else {
+		format_message!(
+			resolved_language,
+			"config-watch-list",
+			watched: watched.into_iter().map(mention).collect::<Vec<_>>().join(", ")
+		)
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::new())
+			.content(message),
+	)
+	.await?;
+	Ok(())
+}
+
+/// The user or role to change, if exactly one of them was picked.
+fn get_target(user: Option<User>, role: Option<RoleId>) -> Option<WatchTarget> {
+	match (user, role) {
+		(Some(user), None) => Some(WatchTarget::User(user.id)),
+		(None, Some(role)) => Some(WatchTarget::Role(role)),
+		_ => None,
+	}
+}
+
+fn mention(target: WatchTarget) -> String {
+	match target {
+		WatchTarget::User(id) => id.mention().to_string(),
+		WatchTarget::Role(id) => id.mention().to_string(),
+	}
+}
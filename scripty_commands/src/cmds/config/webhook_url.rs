@@ -0,0 +1,56 @@
+use poise::CreateReply;
+use scripty_bot_utils::{
+	checks::is_guild,
+	session_webhooks::{self, WebhookUrlError},
+	Context,
+	Error,
+};
+
+/// Set a URL to send a summary of every voice session to, when it ends.
+///
+/// Slash command only, as the signing secret is only ever shown to whoever set the URL.
+#[poise::command(
+	slash_command,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "webhook_url"
+)]
+pub async fn config_webhook_url(
+	ctx: Context<'_>,
+	#[description = "HTTPS URL to send summaries to: set empty to disable"] url: Option<String>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let message = match url.as_deref().map(session_webhooks::check_webhook_url) {
+		None => {
+			if session_webhooks::clear_webhook_url(guild_id).await? {
+				format_message!(resolved_language, "config-webhook-url-disabled")
+			} else {
+				format_message!(resolved_language, "config-webhook-url-none")
+			}
+		}
+		Some(Ok(url)) => {
+			let secret = session_webhooks::set_webhook_url(guild_id, &url).await?;
+			format_message!(
+				resolved_language,
+				"config-webhook-url-set",
+				url: url.as_str(),
+				secret: secret
+			)
+		}
+		Some(Err(WebhookUrlError::Invalid)) => {
+			format_message!(resolved_language, "config-webhook-url-invalid")
+		}
+		Some(Err(WebhookUrlError::NotHttps)) => {
+			format_message!(resolved_language, "config-webhook-url-not-https")
+		}
+		Some(Err(WebhookUrlError::NotPublic)) => {
+			format_message!(resolved_language, "config-webhook-url-not-public")
+		}
+	};
+
+	ctx.send(CreateReply::default().content(message).ephemeral(true))
+		.await?;
+	Ok(())
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::entity_cache::get_channel_webhook;
+use serenity::{
+	all::{InputTextStyle, Message},
+	builder::{
+		CreateEmbed,
+		CreateInputText,
+		CreateInteractionResponse,
+		CreateInteractionResponseMessage,
+		CreateQuickModal,
+		EditWebhookMessage,
+	},
+	collector::QuickModalResponse,
+};
+
+use crate::{Context, Error};
+
+/// How long the speaker has to submit their correction.
+const MODAL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Longest a correction can be, leaving room for the marker in a 2000 character message.
+const MAX_CORRECTION_LEN: u16 = 1900;
+
+/// Fix a transcript Scripty got wrong.
+///
+/// Only the person who said it, or someone who can manage messages, can correct a transcript.
+/// If the speaker opted in to audio storage, the correction is kept to help improve transcriptions.
+#[poise::command(context_menu_command = "Correct transcript", check = "is_guild")]
+pub async fn correct(ctx: Context<'_>, message: Message) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	let poise::Context::Application(app_ctx) = ctx else {
+		return Ok(());
+	};
+	let not_transcript = || {
+		CreateReply::default()
+			.ephemeral(true)
+			.content(format_message!(resolved_language, "correct-not-transcript"))
+	};
+
+	let Some(webhook_id) = message.webhook_id else {
+		ctx.send(not_transcript()).await?;
+		return Ok(());
+	};
+	let Some(channel) = message.channel_id.to_channel(&ctx).await?.guild() else {
+		ctx.send(not_transcript()).await?;
+		return Ok(());
+	};
+	// transcripts in threads are sent with the parent channel's webhook
+	let webhook_channel_id = match channel.thread_metadata {
+		Some(_) => channel.parent_id.unwrap_or(channel.id),
+		None => channel.id,
+	};
+	let webhook = get_channel_webhook(ctx.serenity_context(), webhook_channel_id).await?;
+	if webhook.id != webhook_id {
+		ctx.send(not_transcript()).await?;
+		return Ok(());
+	}
+
+	let author_id = ctx.author().id;
+	let is_speaker = sqlx::query!(
+		"SELECT EXISTS (SELECT 1 FROM transcript_segments WHERE message_id = $1 AND speaker_id = \
+		 $2) OR EXISTS (SELECT 1 FROM audio_store WHERE message_id = $1 AND source_id = $2) AS \
+		 \"is_speaker!\"",
+		message.id.get() as i64,
+		scripty_utils::hash_user_id(author_id.get())
+	)
+	.fetch_one(scripty_db::get_db())
+	.await?
+	.is_speaker;
+	let is_mod = channel
+		.permissions_for_user(&ctx, author_id)
+		.map_or(false, |p| p.manage_messages());
+	if !(is_speaker || is_mod) {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "correct-not-allowed")),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	// transcripts are in an embed when speaker colors are on
+	let embed = message
+		.embeds
+		.first()
+		.filter(|_| message.content.is_empty());
+	let body = match embed {
+		Some(embed) => embed.description.as_deref().unwrap_or_default(),
+		None => message.content.as_str(),
+	};
+	// corrected messages are shown in the guild's language, as everyone sees them
+	let guild_language = scripty_i18n::get_guild_language(guild_id.get()).await;
+	let marker = format!(" *({})*", format_message!(guild_language, "correct-marker"));
+	let original = body.strip_suffix(&marker).unwrap_or(body);
+
+	let modal = CreateQuickModal::new(format_message!(resolved_language, "correct-modal-title"))
+		.field(
+			CreateInputText::new(
+				InputTextStyle::Paragraph,
+				format_message!(resolved_language, "correct-modal-label"),
+				"correction",
+			)
+			.value(
+				original
+					.chars()
+					.take(MAX_CORRECTION_LEN as usize)
+					.collect::<String>(),
+			)
+			.max_length(MAX_CORRECTION_LEN)
+			.required(true),
+		)
+		.timeout(MODAL_TIMEOUT);
+	let Some(QuickModalResponse {
+		interaction,
+		inputs,
+	}) = app_ctx
+		.interaction
+		.quick_modal(ctx.serenity_context(), modal)
+		.await?
+	else {
+		return Ok(());
+	};
+	let respond = |key: &str| {
+		CreateInteractionResponse::Message(
+			CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, key)),
+		)
+	};
+
+	let correction = inputs.first().map_or("", |input| input.trim());
+	if correction.is_empty() || correction == original {
+		interaction
+			.create_response(&ctx, respond("correct-unchanged"))
+			.await?;
+		return Ok(());
+	}
+
+	let corrected = format!("{}{}", correction, marker);
+	let mut edit = EditWebhookMessage::new();
+	edit = match embed {
+		Some(embed) => edit.embeds(vec![CreateEmbed::from(embed.clone()).description(corrected)]),
+		None => edit.content(corrected),
+	};
+	if channel.thread_metadata.is_some() {
+		edit = edit.in_thread(channel.id);
+	}
+	if let Err(e) = webhook.edit_message(&ctx, message.id, edit).await {
+		debug!(message_id = %message.id, "failed to edit corrected transcript: {}", e);
+		interaction
+			.create_response(&ctx, respond("correct-failed"))
+			.await?;
+		return Ok(());
+	}
+
+	// only audio of speakers who opted in to audio storage is kept, so this is where consent is
+	// checked. Merged messages can't be split back up between their audio, so they're skipped
+	sqlx::query!(
+		"INSERT INTO audio_transcript_verification (id, audio_matches_transcript, \
+		 updated_transcript) SELECT id, false, $2 FROM audio_store WHERE message_id = $1 AND \
+		 (SELECT COUNT(*) FROM audio_store WHERE message_id = $1) = 1 ON CONFLICT (id) DO UPDATE \
+		 SET audio_matches_transcript = false, updated_transcript = $2",
+		message.id.get() as i64,
+		correction
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	interaction
+		.create_response(&ctx, respond("correct-success"))
+		.await?;
+	Ok(())
+}
@@ -10,7 +10,6 @@ use serenity::{
 		CreateEmbed,
 		CreateInteractionResponse,
 		CreateInteractionResponseMessage,
-		EditMessage,
 	},
 	collector::ComponentInteractionCollector,
 	futures::StreamExt,
@@ -21,9 +20,7 @@ use crate::{Context, Error};
 /// Configure storage settings for your data
 #[poise::command(prefix_command, slash_command)]
 pub async fn data_storage(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let msg = ctx
 		.send(
@@ -94,6 +91,32 @@ VALUES ($1)
 					"data-storage-opted-out-msgs"
 				})
 			}
+			"toggle_analytics" => {
+				let opted_out: bool = sqlx::query!(
+					"UPDATE users SET analytics_opt_out = NOT analytics_opt_out WHERE user_id = \
+					 $1 RETURNING analytics_opt_out",
+					hashed_author_id
+				)
+				.fetch_one(db)
+				.await?
+				.analytics_opt_out;
+
+				if opted_out {
+					// nothing more is stored from here on, so get rid of what already was
+					sqlx::query!(
+						"DELETE FROM user_talk_time WHERE user_id = $1",
+						hashed_author_id
+					)
+					.execute(db)
+					.await?;
+				}
+
+				Some(if opted_out {
+					"data-storage-opted-out-analytics"
+				} else {
+					"data-storage-opted-in-analytics"
+				})
+			}
 			_ => None,
 		};
 
@@ -130,118 +153,131 @@ VALUES ($1)
 ///
 /// This command will irreversibly, permanently, delete all your data. There is no undoing this action.
 #[poise::command(prefix_command, slash_command)]
-pub async fn delete_all_data(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+pub async fn delete_all_data(
+	ctx: Context<'_>,
+	#[description = "Also ban yourself from the bot, so you don't add yourself back by accident."]
+	ban: Option<bool>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let ban = ban.unwrap_or(false);
 
-	let mut msg = ctx
-		.send(
-			CreateReply::default()
-				.ephemeral(true)
-				.embed(
-					CreateEmbed::default()
-						.title(format_message!(resolved_language, "delete-data-title"))
-						.description(format_message!(
-							resolved_language,
-							"delete-data-description"
-						))
-						.color((255, 0, 0)),
-				)
-				.components(vec![CreateActionRow::Buttons(vec![
-					CreateButton::new("delete_data_confirm")
-						.style(ButtonStyle::Danger)
-						.label(format_message!(resolved_language, "delete-data-confirm")),
-					CreateButton::new("delete_data_confirm_with_ban")
-						.style(ButtonStyle::Danger)
-						.label(format_message!(
-							resolved_language,
-							"delete-data-confirm-banned"
-						)),
-					CreateButton::new("delete_data_cancel")
-						.style(ButtonStyle::Success)
-						.label(format_message!(resolved_language, "delete-data-cancel")),
-				])]),
+	let prompt = CreateEmbed::default()
+		.title(format_message!(resolved_language, "delete-data-title"))
+		.description(format_message!(
+			resolved_language,
+			if ban {
+				"delete-data-description-banned"
+			} else {
+				"delete-data-description"
+			}
+		))
+		.color((255, 0, 0));
+	if !scripty_bot_utils::confirm::confirm(ctx, prompt).await? {
+		ctx.send(
+			CreateReply::default().ephemeral(true).embed(
+				CreateEmbed::default()
+					.title(format_message!(
+						resolved_language,
+						"delete-data-cancelled-title"
+					))
+					.description(format_message!(
+						resolved_language,
+						"delete-data-cancelled-description"
+					)),
+			),
 		)
-		.await?
-		.into_message()
 		.await?;
+		return Ok(());
+	}
 
-	let author_id = ctx.author().id;
-	let hashed_author_id = scripty_utils::hash_user_id(author_id.get());
+	let hashed_author_id = scripty_utils::hash_user_id(ctx.author().id.get());
 	let db = scripty_db::get_db();
+	sqlx::query!("DELETE FROM users WHERE user_id = $1", hashed_author_id)
+		.execute(db)
+		.await?;
+	sqlx::query!(
+		"DELETE FROM user_talk_time WHERE user_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	sqlx::query!(
+		"DELETE FROM consent_notices WHERE user_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	// their messages go with them
+	sqlx::query!(
+		"DELETE FROM dm_support_tickets WHERE user_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	sqlx::query!(
+		"DELETE FROM dm_support_blocks WHERE user_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	// what they said in stored transcripts
+	sqlx::query!(
+		"DELETE FROM transcript_segments WHERE speaker_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	// the pledge comes back with the next webhook, but not who it's from
+	sqlx::query!(
+		"DELETE FROM patreon_members WHERE user_id = $1",
+		hashed_author_id
+	)
+	.execute(db)
+	.await?;
+	// subscriptions need the real user ID to DM them, so aren't hashed
+	sqlx::query!(
+		"DELETE FROM transcript_subscriptions WHERE user_id = $1",
+		ctx.author().id.get() as i64
+	)
+	.execute(db)
+	.await?;
 
-	let one = ComponentInteractionCollector::new(&ctx.serenity_context().shard)
-		.author_id(author_id)
-		.message_id(msg.id)
-		.timeout(Duration::from_secs(120))
-		.next()
-		.await;
-
-	if let Some(interaction) = one {
-		let status = match interaction.data.custom_id.as_str() {
-			"delete_data_confirm" => {
-				sqlx::query!("DELETE FROM users WHERE user_id = $1", hashed_author_id)
-					.execute(db)
-					.await?;
-				Some(false)
-			}
-			"delete_data_confirm_with_ban" => {
-				sqlx::query!("DELETE FROM users WHERE user_id = $1", hashed_author_id)
-					.execute(db)
-					.await?;
-				// add the user to the banned list
-				// user_id is obvious
-				// reason is also obvious, user requested the ban
-				// blocked_since is the current time in UTC
-				sqlx::query!(
-					"INSERT INTO blocked_users (user_id, reason, blocked_since) VALUES ($1, $2, \
-					 localtimestamp)",
-					hashed_author_id,
-					"requested ban",
-				)
-				.execute(db)
-				.await?;
-				Some(true)
-			}
-			"delete_data_cancel" => None,
-			_ => None,
-		};
+	let embed = if ban {
+		// add the user to the banned list
+		// user_id is obvious
+		// reason is also obvious, user requested the ban
+		// blocked_since is the current time in UTC
+		sqlx::query!(
+			"INSERT INTO blocked_users (user_id, reason, blocked_since) VALUES ($1, $2, \
+			 localtimestamp)",
+			hashed_author_id,
+			"requested ban",
+		)
+		.execute(db)
+		.await?;
 
-		let embed = match status {
-			// user was deleted and banned
-			Some(true) => CreateEmbed::default()
-				.title(format_message!(
-					resolved_language,
-					"delete-data-success-banned-title"
-				))
-				.description(format_message!(
-					resolved_language,
-					"delete-data-success-banned-description"
-				)),
-			// user was deleted, but not banned
-			Some(false) => CreateEmbed::default()
-				.title(format_message!(
-					resolved_language,
-					"delete-data-success-title"
-				))
-				.description(format_message!(
-					resolved_language,
-					"delete-data-success-description"
-				)),
-			// user cancelled deletion
-			None => CreateEmbed::default()
-				.title(format_message!(
-					resolved_language,
-					"delete-data-cancelled-title"
-				))
-				.description(format_message!(
-					resolved_language,
-					"delete-data-cancelled-description"
-				)),
-		};
-		msg.edit(&ctx, EditMessage::default().embed(embed)).await?;
-	}
+		CreateEmbed::default()
+			.title(format_message!(
+				resolved_language,
+				"delete-data-success-banned-title"
+			))
+			.description(format_message!(
+				resolved_language,
+				"delete-data-success-banned-description"
+			))
+	} else {
+		CreateEmbed::default()
+			.title(format_message!(
+				resolved_language,
+				"delete-data-success-title"
+			))
+			.description(format_message!(
+				resolved_language,
+				"delete-data-success-description"
+			))
+	};
+	ctx.send(CreateReply::default().ephemeral(true).embed(embed))
+		.await?;
 
 	Ok(())
 }
@@ -278,5 +314,12 @@ fn build_components(
 				"data-storage-toggle-msgs-btn"
 			))
 			.disabled(disabled),
+		CreateButton::new("toggle_analytics")
+			.style(ButtonStyle::Primary)
+			.label(format_message!(
+				resolved_language,
+				"data-storage-toggle-analytics-btn"
+			))
+			.disabled(disabled),
 	])]
 }
@@ -0,0 +1,33 @@
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::{Context, Error};
+
+mod session;
+
+pub use session::*;
+
+/// Commands for figuring out why Scripty is behaving the way it is
+#[poise::command(prefix_command, slash_command)]
+pub async fn debug(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::default()
+				.title(format_message!(
+					resolved_language,
+					"root-command-invoked-title"
+				))
+				.description(format_message!(
+					resolved_language,
+					"root-command-invoked-description",
+					contextPrefix: ctx.prefix(),
+					commandName: "debug"
+				)),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,79 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::watch::WatchTarget;
+use serenity::{builder::CreateEmbed, prelude::Mentionable};
+
+use crate::{Context, Error};
+
+/// See what Scripty is doing in this server's voice chat, and who it's transcribing.
+#[poise::command(prefix_command, slash_command, check = "is_guild", rename = "session")]
+pub async fn debug_session(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let Some(voice_channel_id) =
+		scripty_audio_handler::get_voice_channel_id(ctx.serenity_context(), guild_id).await
+	else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "debug-session-none")),
+		)
+		.await?;
+		return Ok(());
+	};
+
+	// extra bots may be in another voice chat in the guild
+	let bot_id = ctx.serenity_context().cache.current_user().id;
+	let stats = scripty_audio_handler::get_session_stats()
+		.into_iter()
+		.find(|stats| stats.guild_id == guild_id && stats.bot_id == bot_id);
+	let watched = scripty_audio_handler::get_watchlist(guild_id);
+	// the mode changes who gets transcribed, so it's spelled out up front
+	let watch_mode = if watched.is_empty() {
+		format_message!(resolved_language, "debug-session-watch-off")
+	} else {
+		format_message!(
+			resolved_language,
+			"debug-session-watch-on",
+			watched: watched
+				.into_iter()
+				.map(|target| match target {
+					WatchTarget::User(id) => id.mention().to_string(),
+					WatchTarget::Role(id) => id.mention().to_string(),
+				})
+				.collect::<Vec<_>>()
+				.join(", ")
+		)
+	};
+
+	ctx.send(
+		CreateReply::default().ephemeral(true).embed(
+			CreateEmbed::new()
+				.title(format_message!(resolved_language, "debug-session-title"))
+				.field(
+					format_message!(resolved_language, "debug-session-voice-chat"),
+					voice_channel_id.mention().to_string(),
+					true,
+				)
+				.field(
+					format_message!(resolved_language, "debug-session-speakers"),
+					stats.map_or(0, |stats| stats.transcribed_users).to_string(),
+					true,
+				)
+				.field(
+					format_message!(resolved_language, "debug-session-streams"),
+					stats.map_or(0, |stats| stats.stt_streams).to_string(),
+					true,
+				)
+				.field(
+					format_message!(resolved_language, "debug-session-watch-mode"),
+					watch_mode,
+					false,
+				),
+		),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -1,11 +1,36 @@
-use scripty_bot_utils::{checks::is_guild, globals::DM_SUPPORT_GLOBAL};
+use std::fmt::Write;
+
+use poise::CreateReply;
+use scripty_bot_utils::{
+	checks::{is_dm_support_staff_channel, is_guild},
+	dm_support::{
+		normalize_tag,
+		AddTagResult,
+		DmSupportStatus,
+		Priority,
+		ANONYMOUS_PREFIX,
+		MAX_TAG_LEN,
+	},
+	globals::DM_SUPPORT_GLOBAL,
+};
+use serenity::{
+	builder::{CreateAllowedMentions, CreateEmbed, CreateEmbedFooter},
+	model::{channel::GuildChannel, id::UserId},
+};
 
 use crate::{Context, Error};
 
+/// Maximum number of tickets shown by `ps list`, to stay under the embed length limit.
+const MAX_LISTED: usize = 25;
+
 #[poise::command(prefix_command, hide_in_help)]
 pub async fn ps(ctx: Context<'_>) -> Result<(), Error> {
-	ctx.say(format!("subcommands: `{}ps close`", ctx.prefix()))
-		.await?;
+	ctx.say(format!(
+		"subcommands: `{0}ps close`, `{0}ps claim`, `{0}ps unassign`, `{0}ps anon`, `{0}ps tag`, \
+		 `{0}ps untag`, `{0}ps priority`, `{0}ps list`, `{0}ps block`, `{0}ps unblock`",
+		ctx.prefix()
+	))
+	.await?;
 	Ok(())
 }
 
@@ -19,6 +44,7 @@ pub async fn ps_close(ctx: Context<'_>) -> Result<(), Error> {
 				.await?
 				.guild()
 				.expect("should be in guild"),
+			ctx.author(),
 		)
 		.await;
 	} else {
@@ -26,3 +52,303 @@ pub async fn ps_close(ctx: Context<'_>) -> Result<(), Error> {
 	}
 	Ok(())
 }
+
+/// Take ownership of a ticket, so only you can reply to it
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "claim")]
+pub async fn ps_claim(ctx: Context<'_>) -> Result<(), Error> {
+	let Some((st, channel, user_id)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	let assignee = st
+		.claim_ticket(ctx.serenity_context(), &channel, user_id, ctx.author().id)
+		.await?;
+	match assignee {
+		Some(id) if id == ctx.author().id => ctx.say("You have claimed this ticket.").await?,
+		Some(id) => {
+			ctx.send(
+				CreateReply::default()
+					.content(format!(
+						"This ticket is already claimed by <@{}>. They need to `{}ps unassign` it \
+						 first.",
+						id,
+						ctx.prefix()
+					))
+					.allowed_mentions(CreateAllowedMentions::default()),
+			)
+			.await?
+		}
+		None => ctx.say("Failed to claim this ticket.").await?,
+	};
+	Ok(())
+}
+
+/// Release a claimed ticket, so anyone can reply to it
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "unassign")]
+pub async fn ps_unassign(ctx: Context<'_>) -> Result<(), Error> {
+	let Some((st, channel, _)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	match st.unassign_ticket(ctx.serenity_context(), &channel).await? {
+		Some(_) => ctx.say("This ticket is no longer claimed.").await?,
+		None => ctx.say("This ticket wasn't claimed.").await?,
+	};
+	Ok(())
+}
+
+/// Toggle whether your replies in a ticket are sent as "Scripty Support"
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "anon")]
+pub async fn ps_anon(ctx: Context<'_>) -> Result<(), Error> {
+	let Some((st, channel, user_id)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	if st
+		.toggle_anonymous(ctx.serenity_context(), &channel, user_id)
+		.await?
+	{
+		ctx.say(format!(
+			"Replies in this ticket are now sent as \"Scripty Support\". To send a single message \
+			 anonymously instead, start it with `{}`.",
+			ANONYMOUS_PREFIX.trim_end()
+		))
+		.await?;
+	} else {
+		ctx.say("Replies in this ticket now show who sent them.")
+			.await?;
+	}
+	Ok(())
+}
+
+/// Tag a ticket, ie `billing`
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "tag")]
+pub async fn ps_tag(
+	ctx: Context<'_>,
+	#[description = "The tag to add."] tag: String,
+) -> Result<(), Error> {
+	let Some(tag) = normalize_tag(&tag) else {
+		return say_invalid_tag(ctx).await;
+	};
+	let Some((st, channel, user_id)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	let message = match st
+		.add_tag(ctx.serenity_context(), &channel, user_id, &tag)
+		.await?
+	{
+		AddTagResult::Added => format!("Tagged this ticket `{}`.", tag),
+		AddTagResult::AlreadyTagged => format!("This ticket is already tagged `{}`.", tag),
+		AddTagResult::TooManyTags(max) => format!("Tickets can have at most {} tags.", max),
+	};
+	ctx.say(message).await?;
+	Ok(())
+}
+
+/// Remove a tag from a ticket
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "untag")]
+pub async fn ps_untag(
+	ctx: Context<'_>,
+	#[description = "The tag to remove."] tag: String,
+) -> Result<(), Error> {
+	let Some(tag) = normalize_tag(&tag) else {
+		return say_invalid_tag(ctx).await;
+	};
+	let Some((st, channel, _)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	if st
+		.remove_tag(ctx.serenity_context(), &channel, &tag)
+		.await?
+	{
+		ctx.say(format!("Removed the `{}` tag.", tag)).await?;
+	} else {
+		ctx.say(format!("This ticket isn't tagged `{}`.", tag))
+			.await?;
+	}
+	Ok(())
+}
+
+/// Set a ticket's priority: low, normal, high or urgent
+#[poise::command(prefix_command, hide_in_help, check = "is_guild", rename = "priority")]
+pub async fn ps_priority(
+	ctx: Context<'_>,
+	#[description = "low, normal, high or urgent."] priority: String,
+) -> Result<(), Error> {
+	let Some(priority) = Priority::from_name(&priority) else {
+		ctx.say("Priority must be one of `low`, `normal`, `high` or `urgent`.")
+			.await?;
+		return Ok(());
+	};
+	let Some((st, channel, user_id)) = current_ticket(ctx).await? else {
+		return Ok(());
+	};
+
+	st.set_priority(ctx.serenity_context(), &channel, user_id, priority)
+		.await?;
+	ctx.say(format!("Set this ticket's priority to {}.", priority))
+		.await?;
+	Ok(())
+}
+
+/// List open tickets, most urgent and then oldest first
+#[poise::command(
+	prefix_command,
+	hide_in_help,
+	check = "is_dm_support_staff_channel",
+	rename = "list"
+)]
+pub async fn ps_list(
+	ctx: Context<'_>,
+	#[description = "Only list tickets with this tag."] tag: Option<String>,
+) -> Result<(), Error> {
+	let Some(st) = DM_SUPPORT_GLOBAL.get() else {
+		ctx.say("error").await?;
+		return Ok(());
+	};
+	let tag = match tag {
+		Some(tag) => match normalize_tag(&tag) {
+			Some(tag) => Some(tag),
+			None => return say_invalid_tag(ctx).await,
+		},
+		None => None,
+	};
+
+	let tickets = st
+		.list_tickets(ctx.serenity_context(), tag.as_deref())
+		.await?;
+
+	let mut description = String::new();
+	for ticket in tickets.iter().take(MAX_LISTED) {
+		let mut line = format!(
+			"<#{}> **{}**, opened <t:{}:R>",
+			ticket.channel_id,
+			ticket.state.priority,
+			ticket.opened_at.unix_timestamp()
+		);
+		if let Some(assignee) = ticket.state.assignee {
+			write!(&mut line, ", claimed by <@{}>", assignee).expect("failed to write to string");
+		}
+		if !ticket.state.tags.is_empty() {
+			write!(&mut line, " `{}`", ticket.state.tags.join("` `"))
+				.expect("failed to write to string");
+		}
+		writeln!(&mut description, "{}", line).expect("failed to write to string");
+	}
+	if tickets.len() > MAX_LISTED {
+		writeln!(
+			&mut description,
+			"...and {} more",
+			tickets.len() - MAX_LISTED
+		)
+		.expect("failed to write to string");
+	}
+	if description.is_empty() {
+		description.push_str("none");
+	}
+
+	let title = match tag {
+		Some(tag) => format!("Open Tickets Tagged `{}`", tag),
+		None => "Open Tickets".to_string(),
+	};
+	ctx.send(
+		CreateReply::default()
+			.embed(
+				CreateEmbed::default()
+					.title(title)
+					.description(description)
+					.footer(CreateEmbedFooter::new(format!("{} total", tickets.len()))),
+			)
+			.allowed_mentions(CreateAllowedMentions::default()),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Block a user from opening new tickets
+#[poise::command(
+	prefix_command,
+	hide_in_help,
+	check = "is_dm_support_staff_channel",
+	rename = "block"
+)]
+pub async fn ps_block(
+	ctx: Context<'_>,
+	#[description = "The user to block."] user_id: UserId,
+	#[description = "How long to block them for, ie `7d`. Permanent if not given."]
+	duration: Option<humantime::Duration>,
+	#[description = "The reason for the block. This is shown to the user."]
+	#[rest]
+	reason: Option<String>,
+) -> Result<(), Error> {
+	let Some(st) = DM_SUPPORT_GLOBAL.get() else {
+		ctx.say("error").await?;
+		return Ok(());
+	};
+
+	st.block_user(user_id, ctx.author().id, reason, duration.map(Into::into))
+		.await?;
+	let message = match duration {
+		Some(duration) => format!("Blocked {} from opening tickets for {}.", user_id, duration),
+		None => format!("Blocked {} from opening tickets.", user_id),
+	};
+	ctx.say(message).await?;
+	Ok(())
+}
+
+/// Let a blocked user open tickets again
+#[poise::command(
+	prefix_command,
+	hide_in_help,
+	check = "is_dm_support_staff_channel",
+	rename = "unblock"
+)]
+pub async fn ps_unblock(
+	ctx: Context<'_>,
+	#[description = "The user to unblock."] user_id: UserId,
+) -> Result<(), Error> {
+	let Some(st) = DM_SUPPORT_GLOBAL.get() else {
+		ctx.say("error").await?;
+		return Ok(());
+	};
+
+	if st.unblock_user(user_id).await? {
+		ctx.say(format!("Unblocked {}.", user_id)).await?;
+	} else {
+		ctx.say(format!("{} isn't blocked.", user_id)).await?;
+	}
+	Ok(())
+}
+
+/// The DM support state, ticket channel and ticket owner for the channel the command was run in.
+/// Replies saying why and returns None if it wasn't run in a ticket.
+async fn current_ticket(
+	ctx: Context<'_>,
+) -> Result<Option<(&'static DmSupportStatus, GuildChannel, UserId)>, Error> {
+	let Some(st) = DM_SUPPORT_GLOBAL.get() else {
+		ctx.say("error").await?;
+		return Ok(None);
+	};
+	let channel = ctx
+		.channel_id()
+		.to_channel(&ctx)
+		.await?
+		.guild()
+		.expect("should be in guild");
+	let Some(user_id) = st.ticket_user(ctx.serenity_context(), &channel).await else {
+		ctx.say("This isn't a ticket channel.").await?;
+		return Ok(None);
+	};
+	Ok(Some((st, channel, user_id)))
+}
+
+async fn say_invalid_tag(ctx: Context<'_>) -> Result<(), Error> {
+	ctx.say(format!(
+		"Tags must be 1 to {} letters, numbers or dashes.",
+		MAX_TAG_LEN
+	))
+	.await?;
+	Ok(())
+}
@@ -10,9 +10,7 @@ use crate::{Context, Error};
 /// Blocking commands
 #[poise::command(prefix_command, hide_in_help)]
 pub async fn block(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	ctx.send(
 		CreateReply::default().ephemeral(true)
@@ -14,9 +14,7 @@ pub async fn help(
 	#[autocomplete = "autocomplete_command"]
 	command: Option<String>,
 ) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	match command {
 		Some(command_name) => {
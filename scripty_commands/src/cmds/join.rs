@@ -3,9 +3,12 @@ use std::{borrow::Cow, time::SystemTime};
 use humantime::format_rfc3339_seconds;
 use scripty_bot_utils::checks::is_guild;
 use serenity::{
-	all::{AutoArchiveDuration, ChannelFlags},
+	all::{AutoArchiveDuration, ChannelFlags, ForumTagId},
 	builder::{CreateForumPost, CreateMessage, CreateThread},
-	model::channel::{ChannelType, GuildChannel},
+	model::{
+		channel::{ChannelType, GuildChannel},
+		permissions::Permissions,
+	},
 	prelude::Mentionable,
 };
 
@@ -40,10 +43,12 @@ pub async fn join(
 
 	#[description = "Create a new thread for this transcription? Defaults to false."]
 	create_thread: Option<bool>,
+
+	#[description = "Send transcripts to the voice chat's own text chat, instead of another \
+	                 channel? Defaults to false."]
+	voice_chat: Option<bool>,
 ) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 	let _typing = ctx.defer_or_broadcast().await;
 	let db = scripty_db::get_db();
 	let cfg = scripty_config::get_config();
@@ -51,6 +56,22 @@ pub async fn join(
 	// validate arguments
 	let record_transcriptions = record_transcriptions.unwrap_or(false);
 	let mut create_thread = create_thread.unwrap_or(false);
+	let voice_chat = voice_chat.unwrap_or(false);
+	if voice_chat && target_channel.is_some() {
+		ctx.say(format_message!(
+			resolved_language,
+			"join-voice-chat-with-target"
+		))
+		.await?;
+		return Ok(());
+	} else if voice_chat && create_thread {
+		ctx.say(format_message!(
+			resolved_language,
+			"join-voice-chat-with-thread"
+		))
+		.await?;
+		return Ok(());
+	}
 	let target_channel = match target_channel {
 		Some(c) => c,
 		None => ctx
@@ -99,15 +120,7 @@ pub async fn join(
 			)
 	);
 
-	if target_channel.kind == ChannelType::Forum
-		&& target_channel.flags.contains(ChannelFlags::REQUIRE_TAG)
-	{
-		ctx.say(
-			format_message!(resolved_language, "join-forum-requires-tags", targetMention: target_channel.mention().to_string()),
-		)
-			.await?;
-		return Ok(());
-	} else if !is_text_based {
+	if !is_text_based {
 		ctx.say(
 			format_message!(resolved_language, "join-target-not-text-based", targetMention: target_channel.mention().to_string()),
 		).await?;
@@ -128,7 +141,7 @@ pub async fn join(
 	};
 
 	let res = sqlx::query!(
-		"SELECT trial_used, agreed_tos FROM guilds WHERE guild_id = $1",
+		"SELECT trial_used, agreed_tos, language, translate FROM guilds WHERE guild_id = $1",
 		guild_id.get() as i64
 	)
 	.fetch_optional(db)
@@ -136,6 +149,10 @@ pub async fn join(
 	let (trial_used, agreed_tos) = res
 		.as_ref()
 		.map_or((false, false), |row| (row.trial_used, row.agreed_tos));
+	// translated transcripts are always in English
+	let transcript_language = res
+		.filter(|row| !row.translate)
+		.map_or_else(|| "en".to_string(), |row| row.language);
 
 	if !agreed_tos {
 		ctx.say(
@@ -182,6 +199,26 @@ pub async fn join(
 		return Ok(());
 	}
 
+	// voice chats' text chats are only visible to those who can view the voice chat itself,
+	// so they need their own check
+	let target_channel = if voice_chat {
+		let required_permissions =
+			Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MANAGE_WEBHOOKS;
+		if !permissions.contains(required_permissions) {
+			ctx.say(format_message!(
+				resolved_language,
+				"join-voice-chat-no-permission",
+				targetMention: voice_channel.mention().to_string(),
+				missingPermissions: ((!permissions) & required_permissions).to_string()
+			))
+			.await?;
+			return Ok(());
+		}
+		voice_channel.clone()
+	} else {
+		target_channel
+	};
+
 	// check if there are any users in the channel
 	// prevents Join(Dropped) errors being thrown, as this would be confusing to the user
 	if voice_channel
@@ -226,16 +263,31 @@ pub async fn join(
 			target_channel.id,
 		)
 	} else if create_thread && target_channel.kind == ChannelType::Forum {
+		let tags = forum_post_tags(&target_channel, &voice_channel.name, &transcript_language);
+		if tags.is_empty() && target_channel.flags.contains(ChannelFlags::REQUIRE_TAG) {
+			ctx.say(
+				format_message!(resolved_language, "join-forum-requires-tags", targetMention: target_channel.mention().to_string()),
+			)
+			.await?;
+			return Ok(());
+		}
+
 		let timestamp = format_rfc3339_seconds(SystemTime::now()).to_string();
+		// post titles can be at most 100 characters, which a long channel name could go over
+		let title: String = format_message!(resolved_language, "join-forum-post-title", voiceChannel: voice_channel.name.to_string(), date: &timestamp[..10])
+			.chars()
+			.take(100)
+			.collect();
 		(
 			Some(target_channel.create_forum_post(
 				&ctx,
 				CreateForumPost::new(
-					format_message!(resolved_language, "join-thread-title", timestamp: &*timestamp),
+					title,
 					CreateMessage::new().content(
 						format_message!(resolved_language, "join-forum-thread-content", timestamp: timestamp, authorMention: ctx.author().mention().to_string())
 					)
-				),
+				)
+				.set_applied_tags(tags),
 			).await?),
 			target_channel.id,
 		)
@@ -307,8 +359,78 @@ pub async fn join(
 			)
 			.await?;
 		}
+		Err(scripty_audio_handler::Error {
+			kind: scripty_audio_handler::ErrorKind::SessionLimit { limit },
+			..
+		}) => {
+			ctx.say(format_message!(
+				resolved_language,
+				"join-failed-session-limit",
+				maxSessions: limit,
+				contextPrefix: ctx.prefix()
+			))
+			.await?;
+		}
+		Err(scripty_audio_handler::Error {
+			kind: scripty_audio_handler::ErrorKind::AfkChannel,
+			..
+		}) => {
+			ctx.say(format_message!(
+				resolved_language,
+				"join-failed-afk-channel",
+				targetMention: voice_channel.mention().to_string()
+			))
+			.await?;
+		}
+		Err(scripty_audio_handler::Error {
+			kind: scripty_audio_handler::ErrorKind::AgeRestrictedRefused,
+			..
+		}) => {
+			ctx.say(format_message!(
+				resolved_language,
+				"join-failed-age-restricted-refused"
+			))
+			.await?;
+		}
+		Err(scripty_audio_handler::Error {
+			kind: scripty_audio_handler::ErrorKind::AgeRestrictedTarget,
+			..
+		}) => {
+			ctx.say(format_message!(
+				resolved_language,
+				"join-failed-age-restricted-target"
+			))
+			.await?;
+		}
 		Err(e) => return Err(e.into()),
 	};
 
 	Ok(())
 }
+
+/// Most tags a forum post can have.
+const MAX_FORUM_POST_TAGS: usize = 5;
+
+/// Tags of `forum` named after the voice channel or transcription language of a session,
+/// so its post can be found by either. Tags are matched case-insensitively, and a language
+/// matches by code or by name, ie `de`, `German` or `Deutsch`.
+fn forum_post_tags(
+	forum: &GuildChannel,
+	voice_channel_name: &str,
+	language: &str,
+) -> Vec<ForumTagId> {
+	let (native_name, english_name) = scripty_i18n::get_pretty_language_name(language);
+	let names = [voice_channel_name, language, &native_name, &english_name];
+
+	forum
+		.available_tags
+		.iter()
+		.filter(|tag| {
+			names
+				.iter()
+				.any(|name| tag.name.trim().eq_ignore_ascii_case(name.trim()))
+		})
+		.map(|tag| tag.id)
+		.take(MAX_FORUM_POST_TAGS)
+		.collect()
+}
@@ -10,9 +10,7 @@ use crate::{Context, Error};
 /// Base command of this group. See subcommands for more information.
 #[poise::command(prefix_command, slash_command)]
 pub async fn language(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	ctx.send(        CreateReply::default().ephemeral(true)
         .embed(            CreateEmbed::default()
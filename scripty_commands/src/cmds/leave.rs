@@ -5,9 +5,7 @@ use crate::{Context, Error};
 /// Leave any current voice call.
 #[poise::command(prefix_command, slash_command, guild_cooldown = 15, check = "is_guild")]
 pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let _typing = ctx.defer_or_broadcast().await;
 	let guild_id = {
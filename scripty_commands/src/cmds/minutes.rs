@@ -0,0 +1,104 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use humantime::{format_duration, format_rfc3339_seconds};
+use poise::CreateReply;
+use scripty_audio_handler::SessionKey;
+use scripty_bot_utils::checks::is_guild;
+use scripty_i18n::LanguageIdentifier;
+use serenity::builder::{CreateAttachment, CreateEmbed, CreateEmbedFooter};
+
+use crate::{Context, Error};
+
+/// Longest an embed field's value can be.
+const MAX_FIELD_LEN: usize = 1024;
+
+/// Get meeting minutes of the voice chat being transcribed, or the one that just ended.
+///
+/// Lists who spoke, the decisions made, action items with who's doing them, and lines members
+/// highlighted, as an embed and an attached Markdown file.
+/// Available for an hour after the session ends.
+#[poise::command(prefix_command, slash_command, user_cooldown = 15, check = "is_guild")]
+pub async fn minutes(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	// each bot keeps minutes of its own sessions, so this is the one the command was sent to
+	let key = SessionKey::new(ctx.serenity_context(), guild_id);
+	let Some(minutes) = scripty_audio_handler::get_minutes(key) else {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "minutes-no-session")),
+		)
+		.await?;
+		return Ok(());
+	};
+
+	let description_id = if minutes.ongoing {
+		"minutes-description-ongoing"
+	} else {
+		"minutes-description-ended"
+	};
+	let duration = format_duration(Duration::from_secs(minutes.duration.as_secs())).to_string();
+	let started_secs = minutes
+		.started_at
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_secs());
+	let title = format_message!(resolved_language, "minutes-title");
+
+	let mut embed = CreateEmbed::new()
+		.title(&title)
+		.description(format_message!(
+			resolved_language,
+			description_id,
+			startedAt: format!("<t:{}:f>", started_secs),
+			duration: duration.clone()
+		))
+		.footer(CreateEmbedFooter::new(format_message!(
+			resolved_language,
+			"minutes-footer"
+		)));
+	// the file has no Discord markup, so the start time is written out in UTC
+	let mut markdown = format!(
+		"# {}\n\n{}\n",
+		title,
+		format_message!(
+			resolved_language,
+			description_id,
+			startedAt: format_rfc3339_seconds(minutes.started_at).to_string(),
+			duration: duration
+		)
+	);
+	markdown.push_str(&minutes.sections_markdown(&resolved_language));
+	for (name, items) in minutes.sections(&resolved_language) {
+		embed = embed.field(name, embed_list(&items, &resolved_language), false);
+	}
+
+	ctx.send(
+		CreateReply::default()
+			.embed(embed)
+			.attachment(CreateAttachment::bytes(markdown.into_bytes(), "minutes.md")),
+	)
+	.await?;
+	Ok(())
+}
+
+/// `items` as a bulleted list, cut short to fit in an embed field.
+fn embed_list(items: &[String], language: &LanguageIdentifier) -> String {
+	if items.is_empty() {
+		return format_message!(language, "minutes-none");
+	}
+
+	let mut list = String::new();
+	for (i, item) in items.iter().enumerate() {
+		let line = format!("- {}\n", item);
+		let more = format_message!(language, "minutes-more", count: items.len() - i);
+		// always leave room to say how many were cut off
+		if list.chars().count() + line.chars().count() + more.chars().count() > MAX_FIELD_LEN {
+			list.push_str(&more);
+			break;
+		}
+		list.push_str(&line);
+	}
+	list
+}
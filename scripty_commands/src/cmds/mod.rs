@@ -1,21 +1,31 @@
 mod admin;
+pub mod analytics;
+pub mod automation;
 pub mod automod;
+pub mod captions;
 pub mod config;
+mod correct;
 mod data_storage;
+pub mod debug;
 pub mod dm_support;
 mod entity_block;
 mod help;
 mod join;
 mod language;
 mod leave;
+mod minutes;
 mod ping;
 pub mod premium;
+mod reading_language;
 mod register_cmds;
+mod search;
+mod subscribe;
 mod terms_of_service;
 mod throw_error;
 mod vote_reminders;
 
 pub use admin::*;
+pub use correct::correct;
 pub use data_storage::*;
 pub use dm_support::*;
 pub use entity_block::*;
@@ -23,8 +33,12 @@ pub use help::help;
 pub use join::join;
 pub use language::*;
 pub use leave::leave;
+pub use minutes::minutes;
 pub use ping::ping;
+pub use reading_language::reading_language;
 pub use register_cmds::register_cmds;
+pub use search::search;
+pub use subscribe::{subscribe, unsubscribe};
 pub use terms_of_service::terms_of_service;
 pub use throw_error::throw_error;
 pub use vote_reminders::vote_reminder;
@@ -1,4 +1,5 @@
 use poise::CreateReply;
+use scripty_i18n::LanguageIdentifier;
 use scripty_utils::latency::*;
 use serenity::builder::CreateEmbed;
 
@@ -7,40 +8,56 @@ use crate::{Context, Error};
 /// Get the bot latency
 #[poise::command(prefix_command, slash_command)]
 pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
-	// all latency methods return a latency in nanoseconds
+	// all latency methods return a latency in nanoseconds, or None if it couldn't be measured
+	let ws_latency =
+		get_ws_latency(&ctx.data().shard_manager, ctx.serenity_context().shard_id.0).await;
+	let http_latency = get_http_latency(ctx.serenity_context(), ctx.channel_id()).await;
+	let pg_latency = get_db_latency().await.ok();
+	let redis_latency = get_redis_latency().await.ok();
+	let stt_latency = scripty_stt::get_stt_latency().await.ok();
 
-	// get WebSocket latency in nanoseconds and milliseconds, defaulting to 0 if it fails
-	let ws_latency_ns =
-		get_ws_latency(&ctx.data().shard_manager, ctx.serenity_context().shard_id.0)
-			.await
-			.unwrap_or(0);
-	let ws_latency_ms = (ws_latency_ns as f64 / 1_000_000.0).round();
-	// get HTTP latency in nanoseconds and milliseconds
-	let http_latency_ns = get_http_latency(ctx.serenity_context(), ctx.channel_id()).await;
-	let http_latency_ms = (http_latency_ns as f64 / 1_000_000.0).round();
-	// get Postgres latency in nanoseconds and milliseconds
-	let pg_latency_ns = get_db_latency().await;
-	let pg_latency_ms = (pg_latency_ns as f64 / 1_000_000.0).round();
+	let mut description = format_message!(
+		resolved_language,
+		"latency-description",
+		wsLatency: describe_latency(&resolved_language, ws_latency),
+		httpLatency: describe_latency(&resolved_language, Some(http_latency)),
+		pgLatency: describe_latency(&resolved_language, pg_latency),
+		redisLatency: describe_latency(&resolved_language, redis_latency),
+		sttLatency: describe_latency(&resolved_language, stt_latency)
+	);
+	// recent history for this shard, if the latency updater has collected any yet
+	if let Some(stats) = get_latency_stats(ctx.serenity_context().shard_id.0) {
+		description.push_str("\n\n");
+		description.push_str(&format_message!(
+			resolved_language,
+			"latency-ws-history",
+			samples: stats.samples,
+			avgMs: (stats.average as f64 / 1_000_000.0).round(),
+			minMs: (stats.min as f64 / 1_000_000.0).round(),
+			maxMs: (stats.max as f64 / 1_000_000.0).round(),
+			jitterMs: (stats.jitter as f64 / 1_000_000.0).round()
+		));
+	}
 
 	ctx.send(
-		CreateReply::default().embed(CreateEmbed::default().title("🏓").description(
-			format_message!(
-				resolved_language,
-				"latency-description",
-				wsLatencyMs: ws_latency_ms,
-				wsLatencyNs: ws_latency_ns,
-				httpLatencyMs: http_latency_ms,
-				httpLatencyNs: http_latency_ns,
-				pgLatencyMs: pg_latency_ms,
-				pgLatencyNs: pg_latency_ns
-			),
-		)),
+		CreateReply::default().embed(CreateEmbed::default().title("🏓").description(description)),
 	)
 	.await?;
 
 	Ok(())
 }
+
+/// A latency in nanoseconds as shown in the description, or that it couldn't be measured.
+fn describe_latency(language: &LanguageIdentifier, latency_ns: Option<u128>) -> String {
+	match latency_ns {
+		Some(ns) => format_message!(
+			language,
+			"latency-value",
+			ms: (ns as f64 / 1_000_000.0).round(),
+			ns: ns
+		),
+		None => format_message!(language, "latency-unavailable"),
+	}
+}
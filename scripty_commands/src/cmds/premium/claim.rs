@@ -1,4 +1,5 @@
 use scripty_bot_utils::checks::is_guild;
+use scripty_premium::PremiumTierList;
 
 use crate::{Context, Error};
 
@@ -11,9 +12,7 @@ use crate::{Context, Error};
 	rename = "claim"
 )]
 pub async fn premium_claim(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let hashed_author_id = scripty_utils::hash_user_id(ctx.author().id.get());
 
@@ -33,22 +32,22 @@ pub async fn premium_claim(ctx: Context<'_>) -> Result<(), Error> {
 		return Ok(());
 	}
 
-	let max_servers = match lvl {
-		1 | 2 => 1,
-		3 | 4 => 3,
-		5 | 6 => 5,
-		_ => return Err(Error::expected_guild()),
-	};
+	let max_servers = i64::from(PremiumTierList::from(lvl).limits().max_guilds);
+	let guild_id = ctx.guild().ok_or_else(Error::expected_guild)?.id.get() as i64;
 
-	// fetch the number of guilds this user has linked to their account
+	// fetch the number of other guilds this user has linked to their account:
+	// claiming this one needs a free slot, so they must have fewer than the max.
+	// this guild isn't counted, so claiming it again is fine
 	let guild_count = sqlx::query!(
-		r#"SELECT count(*) AS "guild_count!" FROM guilds WHERE premium_owner_id = $1"#,
-		hashed_author_id
+		"SELECT count(*) AS \"guild_count!\" FROM guilds WHERE premium_owner_id = $1 AND guild_id \
+		 != $2",
+		hashed_author_id,
+		guild_id
 	)
 	.fetch_one(db)
 	.await?
 	.guild_count;
-	if guild_count > max_servers {
+	if guild_count >= max_servers {
 		ctx.say(format_message!(
 			resolved_language,
 			"premium-too-many-guilds",
@@ -59,7 +58,6 @@ pub async fn premium_claim(ctx: Context<'_>) -> Result<(), Error> {
 		return Ok(());
 	}
 
-	let guild_id = ctx.guild().ok_or_else(Error::expected_guild)?.id.get() as i64;
 	let rows_affected: u64 = sqlx::query!(
 		"UPDATE guilds SET premium_owner_id = $1 WHERE guild_id = $2",
 		hashed_author_id,
@@ -0,0 +1,46 @@
+use poise::CreateReply;
+use scripty_bot_utils::patreon::{self, PatreonError};
+use serenity::builder::{CreateActionRow, CreateButton};
+
+use crate::{Context, Error};
+
+/// Link your Patreon account, to get premium for your pledge.
+#[poise::command(prefix_command, slash_command, user_cooldown = 15, rename = "link")]
+pub async fn premium_link(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let url = match patreon::start_link(ctx.author().id).await {
+		Ok(url) => url,
+		Err(PatreonError::NotConfigured) => {
+			ctx.send(
+				CreateReply::default()
+					.ephemeral(true)
+					.content(format_message!(
+						resolved_language,
+						"premium-link-unavailable"
+					)),
+			)
+			.await?;
+			return Ok(());
+		}
+		Err(PatreonError::Redis(e)) => return Err(e.into()),
+		Err(e) => return Err(Error::custom(e.to_string())),
+	};
+
+	ctx.send(
+		CreateReply::default()
+			.ephemeral(true)
+			.content(format_message!(
+				resolved_language,
+				"premium-link",
+				minutes: patreon::LINK_STATE_TTL.as_secs() / 60
+			))
+			.components(vec![CreateActionRow::Buttons(vec![
+				CreateButton::new_link(url)
+					.label(format_message!(resolved_language, "premium-link-button")),
+			])]),
+	)
+	.await?;
+
+	Ok(())
+}
@@ -4,17 +4,21 @@ use serenity::builder::CreateEmbed;
 use crate::{Context, Error};
 
 mod claim;
+mod link;
 mod remove;
+mod status;
+mod trial;
 
 pub use claim::*;
+pub use link::*;
 pub use remove::*;
+pub use status::*;
+pub use trial::*;
 
 /// Premium commands
 #[poise::command(prefix_command, slash_command)]
 pub async fn premium(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	ctx.send(
 		CreateReply::default().ephemeral(true).embed(
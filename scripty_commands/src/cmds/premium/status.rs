@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use poise::CreateReply;
+use scripty_automod::utils::get_tier_rule_count;
+use scripty_bot_utils::checks::is_guild;
+use scripty_premium::{PremiumSource, PremiumTierList};
+use serenity::builder::CreateEmbed;
+
+use crate::{Context, Error};
+
+/// See this server's premium tier, where it comes from, and what it includes.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	guild_cooldown = 5,
+	check = "is_guild",
+	rename = "status"
+)]
+pub async fn premium_status(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	let status = scripty_premium::get_guild_status(guild_id.get()).await?;
+	let tier = status.tier;
+
+	let source = match status.source {
+		PremiumSource::None => "premium-status-source-none",
+		PremiumSource::Subscription => "premium-status-source-subscription",
+		PremiumSource::Patreon => "premium-status-source-patreon",
+		PremiumSource::Trial => "premium-status-source-trial",
+	};
+	let expiry = match (status.source, status.expiration) {
+		(PremiumSource::None, _) => None,
+		(PremiumSource::Patreon, Some(expiration)) => Some(format_message!(
+			resolved_language,
+			"premium-status-patreon-expires",
+			expiresAt: expiration.unix_timestamp()
+		)),
+		(_, Some(expiration)) => Some(format_message!(
+			resolved_language,
+			"premium-status-expires",
+			expiresAt: expiration.unix_timestamp()
+		)),
+		(_, None) => Some(format_message!(resolved_language, "premium-status-renews")),
+	};
+
+	let used_minutes = status.ms_transcribed / 60_000;
+	let usage = match tier.monthly_quota_ms() {
+		Some(limit_ms) => format_message!(
+			resolved_language,
+			"premium-status-usage",
+			usedMinutes: used_minutes,
+			limitMinutes: limit_ms / 60_000
+		),
+		None => format_message!(
+			resolved_language,
+			"premium-status-usage-unlimited",
+			usedMinutes: used_minutes
+		),
+	};
+
+	// keep these in sync with the checks in the commands and handlers they gate
+	let mut features = String::new();
+	for (enabled, feature) in [
+		(
+			tier > PremiumTierList::None,
+			"premium-status-feature-audio-files",
+		),
+		(
+			tier >= PremiumTierList::Tier2,
+			"premium-status-feature-video-files",
+		),
+		(
+			tier > PremiumTierList::None,
+			"premium-status-feature-auto-detect-lang",
+		),
+	] {
+		writeln!(
+			&mut features,
+			"{} {}",
+			if enabled { "✅" } else { "❌" },
+			format_message!(resolved_language, feature)
+		)
+		.expect("failed to write to string");
+	}
+	writeln!(
+		&mut features,
+		"{}",
+		format_message!(
+			resolved_language,
+			"premium-status-feature-automod-rules",
+			maxRules: get_tier_rule_count(tier)
+		)
+	)
+	.expect("failed to write to string");
+	// trials and free servers aren't part of a subscription, so these don't apply to them
+	if matches!(
+		status.source,
+		PremiumSource::Subscription | PremiumSource::Patreon
+	) {
+		let limits = tier.limits();
+		writeln!(
+			&mut features,
+			"{}",
+			format_message!(
+				resolved_language,
+				"premium-status-feature-sessions",
+				maxSessions: limits.max_sessions,
+				maxGuilds: limits.max_guilds
+			)
+		)
+		.expect("failed to write to string");
+	}
+
+	let mut embed = CreateEmbed::default()
+		.title(format_message!(resolved_language, "premium-status-title"))
+		.field(
+			format_message!(resolved_language, "premium-status-tier-field"),
+			format_message!(resolved_language, "premium-status-tier", tier: tier as i16),
+			true,
+		)
+		.field(
+			format_message!(resolved_language, "premium-status-source-field"),
+			format_message!(resolved_language, source),
+			true,
+		);
+	if let Some(expiry) = expiry {
+		embed = embed.field(
+			format_message!(resolved_language, "premium-status-expiry-field"),
+			expiry,
+			true,
+		);
+	}
+	embed = embed
+		.field(
+			format_message!(resolved_language, "premium-status-usage-field"),
+			usage,
+			false,
+		)
+		.field(
+			format_message!(resolved_language, "premium-status-features-field"),
+			features,
+			false,
+		);
+	if tier == PremiumTierList::None {
+		embed = embed.description(format_message!(
+			resolved_language,
+			"premium-status-upsell",
+			commandPrefix: ctx.prefix()
+		));
+	}
+
+	ctx.send(CreateReply::default().embed(embed)).await?;
+	Ok(())
+}
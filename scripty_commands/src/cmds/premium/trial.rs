@@ -0,0 +1,53 @@
+use scripty_bot_utils::{
+	checks::is_guild,
+	premium_trial::{self, TrialOutcome},
+};
+use scripty_premium::PremiumTierList;
+
+use crate::{Context, Error};
+
+/// Start this server's free trial of premium.
+#[poise::command(
+	prefix_command,
+	slash_command,
+	guild_cooldown = 15,
+	check = "is_guild",
+	required_permissions = "MANAGE_GUILD",
+	rename = "trial"
+)]
+pub async fn premium_trial(ctx: Context<'_>) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	if scripty_premium::get_guild(guild_id.get())
+		.await
+		.map_or(false, |tier| tier != PremiumTierList::None)
+	{
+		ctx.say(format_message!(
+			resolved_language,
+			"premium-trial-already-premium"
+		))
+		.await?;
+		return Ok(());
+	}
+
+	let outcome = premium_trial::start_trial(guild_id, ctx.author().id, ctx.channel_id()).await?;
+	let message = match outcome {
+		TrialOutcome::Started(ends_at) => format_message!(
+			resolved_language,
+			"premium-trial-started",
+			days: premium_trial::TRIAL_LENGTH.whole_days(),
+			endsAt: ends_at.unix_timestamp()
+		),
+		TrialOutcome::GuildUsed => format_message!(resolved_language, "premium-trial-guild-used"),
+		TrialOutcome::UserUsed => format_message!(resolved_language, "premium-trial-user-used"),
+		TrialOutcome::NotSetUp => format_message!(
+			resolved_language,
+			"premium-server-not-set-up",
+			commandPrefix: ctx.prefix()
+		),
+	};
+	ctx.say(message).await?;
+
+	Ok(())
+}
@@ -0,0 +1,36 @@
+use poise::CreateReply;
+use scripty_utils::translation;
+
+use crate::{Context, Error};
+
+/// Set the language you read transcripts in, to get a button that translates them.
+#[poise::command(prefix_command, slash_command)]
+pub async fn reading_language(
+	ctx: Context<'_>,
+	#[description = "Language code, like de or pt-BR. Leave empty to stop translating."]
+	language: Option<String>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+
+	let message = if !translation::is_enabled() {
+		format_message!(resolved_language, "reading-language-unavailable")
+	} else {
+		match language {
+			None => {
+				translation::set_reading_language(ctx.author().id, None).await?;
+				format_message!(resolved_language, "reading-language-cleared")
+			}
+			Some(language) if translation::is_valid_language_code(&language) => {
+				translation::set_reading_language(ctx.author().id, Some(&language)).await?;
+				format_message!(resolved_language, "reading-language-set", language: language)
+			}
+			Some(language) => {
+				format_message!(resolved_language, "reading-language-invalid", language: language)
+			}
+		}
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
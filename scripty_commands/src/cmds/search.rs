@@ -0,0 +1,221 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::{PaginateOptions, Pagination};
+use serenity::model::{
+	channel::{ChannelType, GuildChannel},
+	user::User,
+};
+
+use crate::{Context, Error};
+
+/// Most matches fetched per search. Searches with more should be narrowed down.
+const MAX_RESULTS: i64 = 100;
+
+/// Matches shown on each page.
+const RESULTS_PER_PAGE: usize = 5;
+
+/// Longest the lines before and after a match can be, before they're cut off.
+const MAX_CONTEXT_CHARS: usize = 150;
+
+#[derive(Debug, poise::ChoiceParameter, Copy, Clone)]
+pub enum SearchRange {
+	#[name = "Day"]
+	Day,
+	#[name = "Week"]
+	Week,
+	#[name = "Month"]
+	Month,
+	#[name = "Year"]
+	Year,
+}
+
+impl SearchRange {
+	fn days(self) -> i32 {
+		match self {
+			SearchRange::Day => 1,
+			SearchRange::Week => 7,
+			SearchRange::Month => 30,
+			SearchRange::Year => 365,
+		}
+	}
+}
+
+/// Search this server's stored transcripts.
+///
+/// Only transcripts stored for the transcript API can be searched, and only those of voice chats
+/// you can see.
+// no prefix version, since only slash commands can reply to just the author
+#[poise::command(slash_command, user_cooldown = 5, check = "is_guild")]
+pub async fn search(
+	ctx: Context<'_>,
+	#[description = "Words to look for. Put phrases in \"quotes\", and -exclude words."]
+	#[max_length = 100]
+	query: String,
+
+	#[description = "Only search transcripts of this voice chat."]
+	#[channel_types("Voice", "Stage")]
+	channel: Option<GuildChannel>,
+
+	#[description = "Only search what this user said."] user: Option<User>,
+
+	#[description = "Only search transcripts from the past day, week, month or year."]
+	range: Option<SearchRange>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	// transcripts are only as private as the voice chats they're from
+	let voice_channels = {
+		let guild = ctx.guild().ok_or_else(Error::expected_guild)?;
+		guild
+			.channels
+			.values()
+			.filter(|c| matches!(c.kind, ChannelType::Voice | ChannelType::Stage))
+			.filter(|c| channel.as_ref().map_or(true, |channel| channel.id == c.id))
+			.cloned()
+			.collect::<Vec<_>>()
+	};
+	let visible_channels = voice_channels
+		.iter()
+		.filter(|c| {
+			c.permissions_for_user(ctx, ctx.author().id)
+				.map_or(false, |p| p.view_channel())
+		})
+		.map(|c| c.id.get() as i64)
+		.collect::<Vec<_>>();
+
+	// speakers are stored hashed, maybe still under their legacy hash
+	let speaker_id = match &user {
+		Some(user) => {
+			scripty_utils::rehash_user_id(user.id.get()).await?;
+			Some(scripty_utils::hash_user_id(user.id.get()))
+		}
+		None => None,
+	};
+	let results = sqlx::query!(
+		"SELECT seg.speaker, seg.start_ms, seg.message_id, sess.voice_channel_id, \
+		 sess.transcript_channel_id, EXTRACT(EPOCH FROM sess.started_at)::BIGINT AS \
+		 \"started_at!\", ts_headline('simple', seg.content, q, 'StartSel=**, StopSel=**') AS \
+		 \"content!\", (SELECT p.speaker || ': ' || p.content FROM transcript_segments p WHERE \
+		 p.session_id = seg.session_id AND p.start_ms < seg.start_ms ORDER BY p.start_ms DESC \
+		 LIMIT 1) AS before, (SELECT n.speaker || ': ' || n.content FROM transcript_segments n \
+		 WHERE n.session_id = seg.session_id AND n.start_ms > seg.start_ms ORDER BY n.start_ms \
+		 ASC LIMIT 1) AS after FROM transcript_segments seg JOIN transcript_sessions sess ON \
+		 sess.session_id = seg.session_id, websearch_to_tsquery('simple', $2) q WHERE \
+		 sess.guild_id = $1 AND seg.content_tsv @@ q AND sess.voice_channel_id = ANY($3) AND \
+		 ($4::BYTEA IS NULL OR seg.speaker_id = $4) AND ($5::INT IS NULL OR sess.started_at > \
+		 NOW() - make_interval(days => $5)) ORDER BY sess.started_at DESC, seg.start_ms DESC \
+		 LIMIT $6",
+		guild_id.get() as i64,
+		query,
+		&visible_channels[..],
+		speaker_id,
+		range.map(SearchRange::days),
+		MAX_RESULTS
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	if results.is_empty() {
+		ctx.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(format_message!(resolved_language, "search-no-results")),
+		)
+		.await?;
+		return Ok(());
+	}
+
+	let count = results.len();
+	let formatted_results = results
+		.into_iter()
+		.map(|result| {
+			let link = match (result.transcript_channel_id, result.message_id) {
+				(Some(channel_id), Some(message_id)) => format!(
+					"https://discord.com/channels/{}/{}/{}",
+					guild_id, channel_id, message_id
+				),
+				(Some(channel_id), None) => {
+					format!("https://discord.com/channels/{}/{}", guild_id, channel_id)
+				}
+				// stored before transcripts could be searched
+				(None, _) => format!(
+					"https://discord.com/channels/{}/{}",
+					guild_id, result.voice_channel_id
+				),
+			};
+
+			let mut value = String::new();
+			if let Some(before) = result.before {
+				value.push_str(&format!("> {}\n", shorten(&before)));
+			}
+			value.push_str(&format!("> **{}**: {}\n", result.speaker, result.content));
+			if let Some(after) = result.after {
+				value.push_str(&format!("> {}\n", shorten(&after)));
+			}
+			value.push_str(&format_message!(
+				resolved_language,
+				"search-result-footer",
+				timestamp: (result.started_at + result.start_ms / 1000).to_string(),
+				voiceChannel: result.voice_channel_id.to_string(),
+				link: link
+			));
+
+			(result.speaker, value)
+		})
+		.collect::<Vec<_>>();
+
+	// results can come from any voice chat the author can see, so only they get to see them
+	let pagination = Pagination::new(
+		formatted_results,
+		format_message!(resolved_language, "search-title", query: query),
+		PaginateOptions {
+			max_per_page: RESULTS_PER_PAGE,
+			allowed_user: Some(ctx.author().id),
+			..Default::default()
+		},
+	);
+	let summary = if count as i64 >= MAX_RESULTS {
+		format_message!(resolved_language, "search-results-limited", max: MAX_RESULTS)
+	} else {
+		format_message!(resolved_language, "search-results-found", count: count)
+	};
+	let reply = ctx
+		.send(
+			CreateReply::default()
+				.ephemeral(true)
+				.content(summary.clone())
+				.embed(pagination.embed(0))
+				.components(pagination.components()),
+		)
+		.await?;
+	let last_page = pagination
+		.run(ctx.serenity_context(), reply.message().await?.id)
+		.await?;
+
+	// nobody can use the controls anymore, so don't leave them there
+	if let Err(e) = reply
+		.edit(
+			ctx,
+			CreateReply::default()
+				.content(summary)
+				.embed(pagination.embed(last_page))
+				.components(vec![]),
+		)
+		.await
+	{
+		debug!("failed to remove search pagination controls: {}", e);
+	}
+
+	Ok(())
+}
+
+/// `line`, cut off if it's too long to show as context.
+fn shorten(line: &str) -> String {
+	if line.chars().count() <= MAX_CONTEXT_CHARS {
+		return line.to_string();
+	}
+	let mut shortened: String = line.chars().take(MAX_CONTEXT_CHARS - 1).collect();
+	shortened.push('…');
+	shortened
+}
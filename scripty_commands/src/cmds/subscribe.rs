@@ -0,0 +1,99 @@
+use poise::CreateReply;
+use scripty_bot_utils::checks::is_guild;
+use scripty_utils::subscriptions::{self, Subscriber, MAX_SUBSCRIBERS};
+use serenity::{model::channel::GuildChannel, prelude::Mentionable};
+
+use crate::{Context, Error};
+
+/// Get a voice chat's transcripts DMed to you when its sessions end, for when you can't make it.
+///
+/// Only works in servers that allow it with `/config subscriptions`, and only for voice chats you
+/// can see.
+#[poise::command(prefix_command, slash_command, check = "is_guild")]
+pub async fn subscribe(
+	ctx: Context<'_>,
+	#[description = "Voice chat to get the transcripts of"]
+	#[channel_types("Voice", "Stage")]
+	voice_channel: GuildChannel,
+	#[description = "Only get the meeting minutes, not the whole transcript. Defaults to false."]
+	summary_only: Option<bool>,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+	let summary_only = summary_only.unwrap_or(false);
+
+	let message = if !subscriptions::subscriptions_allowed(guild_id).await? {
+		format_message!(resolved_language, "subscribe-not-allowed")
+	} else if !can_view(ctx, &voice_channel) {
+		format_message!(resolved_language, "subscribe-no-access")
+	} else if subscriptions::subscribe(
+		guild_id,
+		voice_channel.id,
+		Subscriber {
+			user_id: ctx.author().id,
+			summary_only,
+		},
+	)
+	.await?
+	{
+		format_message!(
+			resolved_language,
+			if summary_only {
+				"subscribe-summary-success"
+			} else {
+				"subscribe-success"
+			},
+			voiceChannel: voice_channel.mention().to_string()
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"subscribe-full",
+			maxSubscribers: MAX_SUBSCRIBERS
+		)
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
+
+/// Stop getting a voice chat's transcripts DMed to you.
+#[poise::command(prefix_command, slash_command, check = "is_guild")]
+pub async fn unsubscribe(
+	ctx: Context<'_>,
+	#[description = "Voice chat to stop getting the transcripts of"]
+	#[channel_types("Voice", "Stage")]
+	voice_channel: GuildChannel,
+) -> Result<(), Error> {
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
+	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
+
+	// always allowed, even once the server has turned subscriptions off
+	let unsubscribed =
+		subscriptions::unsubscribe(guild_id, voice_channel.id, ctx.author().id).await?;
+	let message = if unsubscribed {
+		format_message!(
+			resolved_language,
+			"unsubscribe-success",
+			voiceChannel: voice_channel.mention().to_string()
+		)
+	} else {
+		format_message!(
+			resolved_language,
+			"unsubscribe-not-subscribed",
+			voiceChannel: voice_channel.mention().to_string()
+		)
+	};
+
+	ctx.send(CreateReply::default().ephemeral(true).content(message))
+		.await?;
+	Ok(())
+}
+
+/// Transcripts are only as private as the voice chats they're from.
+fn can_view(ctx: Context<'_>, voice_channel: &GuildChannel) -> bool {
+	voice_channel
+		.permissions_for_user(ctx, ctx.author().id)
+		.map_or(false, |p| p.view_channel())
+}
@@ -21,9 +21,7 @@ use crate::{Context, Error};
 	required_permissions = "MANAGE_GUILD"
 )]
 pub async fn terms_of_service(ctx: Context<'_>) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 	let db = scripty_db::get_db();
 
 	let guild_id = ctx.guild_id().ok_or_else(Error::expected_guild)?;
@@ -3,9 +3,7 @@ use crate::{Context, Error};
 /// Opt in or out of vote reminders
 #[poise::command(prefix_command, slash_command)]
 pub async fn vote_reminder(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
-	let resolved_language =
-		scripty_i18n::get_resolved_language(ctx.author().id.get(), ctx.guild_id().map(|g| g.get()))
-			.await;
+	let resolved_language = scripty_bot_utils::get_ctx_language(ctx).await;
 
 	let db = scripty_db::get_db();
 	let hashed_user_id = scripty_utils::hash_user_id(ctx.author().id.get());
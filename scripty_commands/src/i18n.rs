@@ -2,88 +2,105 @@ use poise::Command;
 use scripty_bot_utils::{Data, Error};
 use scripty_i18n::LanguageIdentifier;
 
-const DISCORD_SUPPORTED_LOCALES: [&str; 31] = [
-	"id", "da", "de", "en-GB", "en-US", "es-ES", "fr", "hr", "it", "lt", "hu", "nl", "no", "pl",
-	"pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th", "zh-CN",
-	"ja", "zh-TW", "ko",
+/// Every locale Discord accepts localizations for.
+const DISCORD_SUPPORTED_LOCALES: [&str; 32] = [
+	"id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+	"no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+	"zh-CN", "ja", "zh-TW", "ko",
 ];
 
+/// Attach localized names and descriptions from our translation files to every slash command,
+/// and all of their subcommands, parameters, and choices.
+///
+/// Each Discord locale uses the closest translation we have, so ie `es-ES` and `es-419` both use `es`.
 pub fn localize_commands(cmds: &mut Vec<Command<Data, Error>>) {
-	let languages = scripty_i18n::get_all_bundle_languages();
+	let locales: Vec<(&str, LanguageIdentifier)> = DISCORD_SUPPORTED_LOCALES
+		.iter()
+		.filter_map(|locale| {
+			Some((
+				*locale,
+				scripty_i18n::get_supported_language_for_locale(locale)?,
+			))
+		})
+		.collect();
+
 	for cmd in cmds {
-		if cmd.slash_action.is_none() {
-			// prefix-only commands don't need to be localized
-			continue;
-		}
+		localize_command(cmd, &locales);
+	}
+}
+
+fn localize_command(cmd: &mut Command<Data, Error>, locales: &[(&str, LanguageIdentifier)]) {
+	for subcommand in cmd.subcommands.iter_mut() {
+		localize_command(subcommand, locales);
+	}
 
-		// translation key is "cmds_{function_name}"
-		let key = format!("cmds_{}", cmd.identifying_name);
-		let command_name = cmd.name.as_str();
+	if cmd.slash_action.is_none() && cmd.subcommands.iter().all(|c| c.slash_action.is_none()) {
+		// prefix-only commands don't need to be localized
+		return;
+	}
 
-		for language in languages.iter() {
-			// we filter to only discord supported locales
-			let language_fmt = language.to_string();
-			if !DISCORD_SUPPORTED_LOCALES.contains(&language_fmt.as_str()) {
-				continue;
-			}
+	// translation key is "cmds_{function_name}"
+	let key = format!("cmds_{}", cmd.identifying_name);
+	let command_name = cmd.name.as_str();
+
+	for (locale, language) in locales {
+		let locale = locale.to_string();
 
-			let Some(formatted_command_name) =
-				get_fmt_msg(language, &key, None, command_name, true)
+		let Some(formatted_command_name) = get_fmt_msg(language, &key, None, command_name, true)
+		else {
+			continue;
+		};
+		cmd.name_localizations
+			.insert(locale.clone(), formatted_command_name);
+
+		let Some(formatted_command_description) =
+			get_fmt_msg(language, &key, Some("description"), command_name, false)
+		else {
+			continue;
+		};
+		if formatted_command_description.len() <= 100 {
+			cmd.description_localizations
+				.insert(locale.clone(), formatted_command_description);
+		}
+
+		for parameter in cmd.parameters.iter_mut() {
+			let Some(formatted_parameter_name) =
+				get_fmt_msg(language, &key, Some(&*parameter.name), command_name, true)
 			else {
 				continue;
 			};
-			cmd.name_localizations
-				.insert(language_fmt.clone(), formatted_command_name);
+			parameter
+				.name_localizations
+				.insert(locale.clone(), formatted_parameter_name);
 
-			let Some(formatted_command_description) =
-				get_fmt_msg(language, &key, Some("description"), command_name, false)
-			else {
+			let Some(formatted_parameter_description) = get_fmt_msg(
+				language,
+				&key,
+				Some(&format!("{}-description", &parameter.name)),
+				command_name,
+				false,
+			) else {
 				continue;
 			};
-			if formatted_command_description.len() <= 100 {
-				cmd.description_localizations
-					.insert(language_fmt.clone(), formatted_command_description);
-			}
-
-			for parameter in cmd.parameters.iter_mut() {
-				let Some(formatted_parameter_name) =
-					get_fmt_msg(language, &key, Some(&*parameter.name), command_name, true)
-				else {
-					continue;
-				};
+			if formatted_parameter_description.len() <= 100 {
 				parameter
-					.name_localizations
-					.insert(language_fmt.clone(), formatted_parameter_name);
+					.description_localizations
+					.insert(locale.clone(), formatted_parameter_description);
+			}
 
-				let Some(formatted_parameter_description) = get_fmt_msg(
+			for choice in parameter.choices.iter_mut() {
+				let Some(formatted_choice_name) = get_fmt_msg(
 					language,
 					&key,
-					Some(&format!("{}-description", &parameter.name)),
+					Some(&format!("{}-choice-{}", parameter.name, choice.name)),
 					command_name,
 					false,
 				) else {
 					continue;
 				};
-				if formatted_parameter_description.len() <= 100 {
-					parameter
-						.description_localizations
-						.insert(language_fmt.clone(), formatted_parameter_description);
-				}
-
-				for choice in parameter.choices.iter_mut() {
-					let Some(formatted_choice_name) = get_fmt_msg(
-						language,
-						&key,
-						Some(&format!("{}-choice-{}", parameter.name, choice.name)),
-						command_name,
-						false,
-					) else {
-						continue;
-					};
-					choice
-						.localizations
-						.insert(language_fmt.clone(), formatted_choice_name);
-				}
+				choice
+					.localizations
+					.insert(locale.clone(), formatted_choice_name);
 			}
 		}
 	}
@@ -15,10 +15,16 @@ pub fn build_commands() -> Vec<poise::Command<Data, Error>> {
 		cmds::data_storage(),
 		cmds::ping(),
 		cmds::leave(),
+		cmds::minutes(),
+		cmds::search(),
+		cmds::subscribe(),
+		cmds::unsubscribe(),
+		cmds::correct(),
 		cmds::delete_all_data(),
 		cmds::throw_error(),
 		cmds::terms_of_service(),
 		cmds::user_language(),
+		cmds::reading_language(),
 		cmds::vote_reminder(),
 		poise::Command {
 			subcommands: vec![cmds::block_user(), cmds::block_guild()],
@@ -29,20 +35,87 @@ pub fn build_commands() -> Vec<poise::Command<Data, Error>> {
 				cmds::check_guilds(),
 				cmds::hash_user_id(),
 				cmds::cache_info(),
+				cmds::db_status(),
+				cmds::error_lookup(),
+				cmds::profiling(),
+				cmds::shutdown(),
+				cmds::reload(),
+				cmds::reload_i18n(),
+				poise::Command {
+					subcommands: vec![
+						poise::Command {
+							subcommands: vec![
+								cmds::blacklist_guild_add(),
+								cmds::blacklist_guild_remove(),
+								cmds::blacklist_guild_list(),
+							],
+							..cmds::blacklist_guild()
+						},
+						poise::Command {
+							subcommands: vec![
+								cmds::blacklist_user_add(),
+								cmds::blacklist_user_remove(),
+								cmds::blacklist_user_list(),
+							],
+							..cmds::blacklist_user()
+						},
+					],
+					..cmds::blacklist()
+				},
 			],
 			..cmds::admin()
 		},
 		poise::Command {
-			subcommands: vec![cmds::ps_close()],
+			subcommands: vec![
+				cmds::ps_close(),
+				cmds::ps_claim(),
+				cmds::ps_unassign(),
+				cmds::ps_anon(),
+				cmds::ps_tag(),
+				cmds::ps_untag(),
+				cmds::ps_priority(),
+				cmds::ps_list(),
+				cmds::ps_block(),
+				cmds::ps_unblock(),
+			],
 			..cmds::ps()
 		},
 		poise::Command {
 			subcommands: vec![
 				cmds::premium::premium_remove(),
 				cmds::premium::premium_claim(),
+				cmds::premium::premium_link(),
+				cmds::premium::premium_trial(),
+				cmds::premium::premium_status(),
 			],
 			..cmds::premium::premium()
 		},
+		poise::Command {
+			subcommands: vec![
+				cmds::captions::captions_overlay(),
+				cmds::captions::captions_live(),
+			],
+			..cmds::captions::captions()
+		},
+		poise::Command {
+			subcommands: vec![
+				cmds::analytics::analytics_me(),
+				cmds::analytics::analytics_user(),
+			],
+			..cmds::analytics::analytics()
+		},
+		poise::Command {
+			subcommands: vec![cmds::debug::debug_session()],
+			..cmds::debug::debug()
+		},
+		poise::Command {
+			subcommands: vec![
+				cmds::automation::automation_add(),
+				cmds::automation::automation_remove(),
+				cmds::automation::automation_list(),
+			],
+			..cmds::automation::automation()
+		},
 		poise::Command {
 			subcommands: vec![
 				cmds::automod::automod_setup(),
@@ -62,6 +135,67 @@ pub fn build_commands() -> Vec<poise::Command<Data, Error>> {
 				cmds::config::config_auto_detect_lang(),
 				cmds::config::config_transcribe_only_role(),
 				cmds::config::config_translate(),
+				cmds::config::config_api_key(),
+				cmds::config::config_webhook_url(),
+				cmds::config::config_bridge(),
+				cmds::config::config_speaker_labels(),
+				cmds::config::config_merge_utterances(),
+				cmds::config::config_profanity_filter(),
+				cmds::config::config_timestamps(),
+				cmds::config::config_transcript_style(),
+				cmds::config::config_filler_words(),
+				cmds::config::config_min_length(),
+				cmds::config::config_numbers(),
+				cmds::config::config_silence_timeout(),
+				cmds::config::config_voice_commands(),
+				cmds::config::config_tts_relay(),
+				cmds::config::config_scheduled_events(),
+				cmds::config::config_anonymous_speakers(),
+				cmds::config::config_consent_dm(),
+				cmds::config::config_flag_emoji(),
+				cmds::config::config_age_restricted(),
+				cmds::config::config_highlight_emoji(),
+				cmds::config::config_subscriptions(),
+				cmds::config::config_hold_queue(),
+				cmds::config::config_join_leave_notices(),
+				cmds::config::config_live_captions(),
+				poise::Command {
+					subcommands: vec![
+						cmds::config::config_replacements_add(),
+						cmds::config::config_replacements_remove(),
+						cmds::config::config_replacements_list(),
+					],
+					subcommand_required: true,
+					..cmds::config::config_replacements()
+				},
+				poise::Command {
+					subcommands: vec![
+						cmds::config::config_watch_add(),
+						cmds::config::config_watch_remove(),
+						cmds::config::config_watch_list(),
+					],
+					subcommand_required: true,
+					..cmds::config::config_watch()
+				},
+				poise::Command {
+					subcommands: vec![
+						cmds::config::config_role_policy_allow(),
+						cmds::config::config_role_policy_deny(),
+						cmds::config::config_role_policy_remove(),
+						cmds::config::config_role_policy_list(),
+					],
+					subcommand_required: true,
+					..cmds::config::config_role_policy()
+				},
+				poise::Command {
+					subcommands: vec![
+						cmds::config::config_mirror_add(),
+						cmds::config::config_mirror_remove(),
+						cmds::config::config_mirror_list(),
+					],
+					subcommand_required: true,
+					..cmds::config::config_mirror()
+				},
 			],
 			subcommand_required: true,
 			..cmds::config::config_root()
@@ -71,4 +205,18 @@ pub fn build_commands() -> Vec<poise::Command<Data, Error>> {
 	cmds
 }
 
+/// Commands of the extra bots set in `extra_bot_tokens`, which only transcribe voice chats.
+/// Everything else is done with the main bot.
+pub fn build_extra_bot_commands() -> Vec<poise::Command<Data, Error>> {
+	let mut cmds = vec![
+		cmds::register_cmds(),
+		cmds::help(),
+		cmds::join(),
+		cmds::leave(),
+		cmds::minutes(),
+	];
+	i18n::localize_commands(&mut cmds);
+	cmds
+}
+
 use scripty_bot_utils::{Context, Data, Error};
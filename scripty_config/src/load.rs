@@ -1,24 +1,141 @@
-use std::fs;
+use std::{
+	fmt,
+	fs,
+	io,
+	ptr,
+	sync::atomic::{AtomicPtr, Ordering},
+};
 
 use once_cell::sync::OnceCell;
 
 use crate::cfg::BotConfig;
 
-static GLOBAL_CONFIG: OnceCell<BotConfig> = OnceCell::new();
+/// The current config.
+///
+/// Every config ever loaded is leaked, so references handed out by `get_config()` stay valid
+/// across reloads. Reloads are rare and operator-triggered, so this is fine.
+static GLOBAL_CONFIG: AtomicPtr<BotConfig> = AtomicPtr::new(ptr::null_mut());
 
-pub fn load_config(cfg_path: &str) {
-	let cfg = fs::read(cfg_path).expect("failed to read config");
-	let cfg_str = String::from_utf8(cfg).expect("config is not valid utf8");
+/// Path the config was loaded from, so it can be re-read later.
+static CONFIG_PATH: OnceCell<String> = OnceCell::new();
+
+/// Shortest allowed `status_interval`.
+const MIN_STATUS_INTERVAL_SECS: u64 = 10;
+
+fn read_config(cfg_path: &str) -> Result<BotConfig, ReloadError> {
+	let cfg = fs::read(cfg_path)?;
+	let cfg_str = String::from_utf8(cfg).map_err(|_| ReloadError::InvalidUtf8)?;
+	let cfg = toml::from_str(&cfg_str)?;
+	validate(&cfg)?;
+	Ok(cfg)
+}
+
+/// Check the values that parse fine, but can't be used.
+fn validate(cfg: &BotConfig) -> Result<(), ReloadError> {
+	// Discord only allows a few presence updates a minute
+	if cfg
+		.status_interval
+		.map_or(false, |secs| secs < MIN_STATUS_INTERVAL_SECS)
+	{
+		return Err(ReloadError::Invalid(
+			"status_interval must be at least 10 seconds",
+		));
+	}
+	if let Some(cluster) = &cfg.cluster {
+		if cluster.cluster_count == 0 {
+			return Err(ReloadError::Invalid(
+				"cluster.cluster_count must be at least 1",
+			));
+		}
+		if cluster.shard_count < cluster.cluster_count {
+			return Err(ReloadError::Invalid(
+				"cluster.shard_count must be at least cluster.cluster_count",
+			));
+		}
+	}
+	// it's all that stops anyone who can connect from shutting the bot down
+	if cfg
+		.control
+		.as_ref()
+		.map_or(false, |control| control.token.trim().is_empty())
+	{
+		return Err(ReloadError::Invalid("control.token must not be empty"));
+	}
+	Ok(())
+}
 
-	let parsed_cfg: BotConfig = toml::from_str(&cfg_str).expect("config invalid");
+pub fn load_config(cfg_path: &str) {
+	let parsed_cfg = read_config(cfg_path).unwrap_or_else(|e| panic!("{}", e));
 
-	GLOBAL_CONFIG
-		.set(parsed_cfg)
+	CONFIG_PATH
+		.set(cfg_path.to_string())
 		.unwrap_or_else(|_| panic!("don't call `load_config()` more than once"));
+	GLOBAL_CONFIG.store(Box::leak(Box::new(parsed_cfg)), Ordering::Release);
 }
 
 pub fn get_config() -> &'static BotConfig {
-	GLOBAL_CONFIG
+	let cfg = GLOBAL_CONFIG.load(Ordering::Acquire);
+	assert!(
+		!cfg.is_null(),
+		"called `get_config()` before config was initialized"
+	);
+	// SAFETY: the pointer is non-null, and was leaked from a box, so it is valid forever
+	unsafe { &*cfg }
+}
+
+/// Re-read the config file, and apply the fields that are safe to change at runtime:
+/// `log_level`, `log_filters`, the `status_*` settings, and `stt_services`.
+///
+/// Everything else keeps its current value until the next restart.
+/// Callers are responsible for reacting to changed values that aren't read on every use.
+pub fn reload_config() -> Result<(), ReloadError> {
+	let cfg_path = CONFIG_PATH
 		.get()
-		.expect("called `get_config()` before config was initialized")
+		.expect("called `reload_config()` before config was initialized");
+	let new_cfg = read_config(cfg_path)?;
+
+	let mut cfg = get_config().clone();
+	cfg.log_level = new_cfg.log_level;
+	cfg.log_filters = new_cfg.log_filters;
+	cfg.status_text = new_cfg.status_text;
+	cfg.status_rotation = new_cfg.status_rotation;
+	cfg.status_interval = new_cfg.status_interval;
+	cfg.status_show_sessions = new_cfg.status_show_sessions;
+	cfg.stt_services = new_cfg.stt_services;
+
+	GLOBAL_CONFIG.store(Box::leak(Box::new(cfg)), Ordering::Release);
+	Ok(())
+}
+
+#[derive(Debug)]
+pub enum ReloadError {
+	Io(io::Error),
+	InvalidUtf8,
+	Toml(toml::de::Error),
+	Invalid(&'static str),
+}
+
+impl From<io::Error> for ReloadError {
+	fn from(e: io::Error) -> Self {
+		Self::Io(e)
+	}
 }
+
+impl From<toml::de::Error> for ReloadError {
+	fn from(e: toml::de::Error) -> Self {
+		Self::Toml(e)
+	}
+}
+
+impl fmt::Display for ReloadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to read config: {}", e),
+			Self::InvalidUtf8 => f.write_str("config is not valid utf8"),
+			Self::Toml(e) => write!(f, "config invalid: {}", e),
+			Self::Invalid(e) => write!(f, "config invalid: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for ReloadError {}
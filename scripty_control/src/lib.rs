@@ -0,0 +1,112 @@
+//! Wire protocol for the admin control socket.
+//!
+//! Connections carry newline-delimited JSON: the client sends a `Request` per line,
+//! and the server answers each with exactly one `Response` line.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Request {
+	/// Must match `control.token` in the bot's config.
+	pub token:   String,
+	#[serde(flatten)]
+	pub command: Command,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+	/// Drain active sessions, then shut the bot down.
+	Shutdown,
+	/// Reload the config file and STT services.
+	Reload,
+	/// Reload translations from disk.
+	ReloadI18n,
+	BlacklistAdd {
+		kind:   EntityKind,
+		id:     u64,
+		reason: Option<String>,
+	},
+	BlacklistRemove {
+		kind: EntityKind,
+		id:   u64,
+	},
+	BlacklistList {
+		kind: EntityKind,
+	},
+	/// List STT workers and their state.
+	SttList,
+	/// Reconnect to the STT services in the config.
+	SttReload,
+	/// Sizes of everything in the Discord cache.
+	CacheStats,
+	/// List active voice sessions.
+	Sessions,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+	User,
+	Guild,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+	Ok(ResponseData),
+	Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseData {
+	/// The command ran, and has nothing to return besides a human-readable summary.
+	Message {
+		message: String,
+	},
+	Blacklist {
+		entries: Vec<BlacklistEntry>,
+	},
+	SttWorkers {
+		workers: Vec<SttWorker>,
+	},
+	CacheStats {
+		fields: Vec<CacheField>,
+	},
+	Sessions {
+		sessions: Vec<Session>,
+	},
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BlacklistEntry {
+	/// The guild ID, or the hex-encoded hash of the user ID.
+	pub id:            String,
+	pub reason:        Option<String>,
+	/// Unix timestamp.
+	pub blocked_since: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SttWorker {
+	pub address:    String,
+	pub overloaded: bool,
+	pub errored:    bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheField {
+	pub name:  String,
+	/// Approximate size, in bytes.
+	pub size:  usize,
+	/// Number of items, if this field is a collection.
+	pub items: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Session {
+	pub guild_id:          u64,
+	pub stt_streams:       usize,
+	pub transcribed_users: usize,
+}
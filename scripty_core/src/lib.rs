@@ -1,6 +1,8 @@
 #[macro_use]
 extern crate tracing;
 
+mod spans;
+
 use std::{fs::OpenOptions, time::SystemTime};
 
 use fenrir_rs::{NetworkingBackend, SerializationFormat};
@@ -9,15 +11,26 @@ use fern::{
 	Dispatch,
 };
 use rlimit::Resource;
+use scripty_config::{LogFormat, LogLevel};
 use url::Url;
 
 pub fn start() {
 	load_config();
 
+	// must be held for the lifetime of the program, otherwise events are not sent
+	let _sentry = init_sentry();
+	// installed after sentry, so sentry's hook still runs after ours
+	scripty_utils::panic::install_panic_hook();
+
 	let rt = get_tokio_rt();
 
 	rt.block_on(init_logging());
 
+	// tokio-console takes over as the global tracing subscriber,
+	// but with `log-always` set, events are still passed through to our logger
+	#[cfg(feature = "profiling")]
+	rt.block_on(async { console_subscriber::init() });
+
 	increase_open_file_limit();
 
 	scripty_i18n::init_i18n();
@@ -45,6 +58,13 @@ async fn init_logging() {
 	// just clone `colors_line` and overwrite our changes
 	let colors_level = colors_line.info(Color::Green);
 
+	let log_format = cfg.log_format;
+	// tokio-console sets its own global subscriber, which spans are tracked by instead
+	#[cfg(not(feature = "profiling"))]
+	if log_format == LogFormat::Json {
+		spans::init();
+	}
+
 	let mut builder = fenrir_rs::Fenrir::builder()
 		.endpoint(Url::parse(&cfg.loki.url).expect("invalid loki url"))
 		.network(NetworkingBackend::Reqwest)
@@ -61,8 +81,8 @@ async fn init_logging() {
 		.chain(
 			Dispatch::new()
 				// configure this logger instance to make a fancy, colorful output
-				.format(move |out, message, record| {
-					out.finish(format_args!(
+				.format(move |out, message, record| match log_format {
+					LogFormat::Pretty => out.finish(format_args!(
 						"{color_line}[{date} {level} {target} {color_line}] {message}\x1B[0m",
 						color_line = format_args!(
 							"\x1B[{}m",
@@ -72,14 +92,14 @@ async fn init_logging() {
 						target = record.target(),
 						level = colors_level.color(record.level()),
 						message = message,
-					));
+					)),
+					LogFormat::Json => out.finish(format_args!("{}", json_line(message, record))),
 				})
-				// just log messages with INFO or higher log level
-				.level(tracing::log::LevelFilter::Info)
+				// let everything through here, `stdout_filter` applies the configured levels
+				.level(tracing::log::LevelFilter::Trace)
+				.filter(stdout_filter)
 				// completely ignore ureq logs
 				.level_for("ureq", tracing::log::LevelFilter::Off)
-				// boost fenrir_rs logs to TRACE
-				.level_for("fenrir_rs", tracing::log::LevelFilter::Trace)
 				// quieten tracing spans
 				.level_for("tracing::span", tracing::log::LevelFilter::Off)
 				// print this setup of log messages to the console
@@ -97,14 +117,15 @@ async fn init_logging() {
 		.chain(
 			Dispatch::new()
 				// configure this logger instance to make a slightly more informative output, but without colors
-				.format(move |out, message, record| {
-					out.finish(format_args!(
+				.format(move |out, message, record| match log_format {
+					LogFormat::Pretty => out.finish(format_args!(
 						"[{date} {level} {target}] {message}",
 						date = humantime::format_rfc3339(SystemTime::now()),
 						target = record.target(),
 						level = record.level(),
 						message = message,
-					))
+					)),
+					LogFormat::Json => out.finish(format_args!("{}", json_line(message, record))),
 				})
 				// log most things
 				.level(tracing::log::LevelFilter::Debug)
@@ -131,6 +152,62 @@ async fn init_logging() {
 		.expect("failed to init logger");
 }
 
+/// Filter stdout by `log_level` and `log_filters`.
+///
+/// The config is read on every line, so reloading it applies immediately.
+fn stdout_filter(metadata: &tracing::log::Metadata) -> bool {
+	let cfg = scripty_config::get_config();
+	let level = cfg
+		.log_filters
+		.iter()
+		.filter(|(target, _)| metadata.target().starts_with(target.as_str()))
+		.max_by_key(|(target, _)| target.len())
+		.map_or(cfg.log_level, |(_, level)| *level);
+
+	metadata.level()
+		<= match level {
+			LogLevel::Off => tracing::log::LevelFilter::Off,
+			LogLevel::Error => tracing::log::LevelFilter::Error,
+			LogLevel::Warn => tracing::log::LevelFilter::Warn,
+			LogLevel::Info => tracing::log::LevelFilter::Info,
+			LogLevel::Debug => tracing::log::LevelFilter::Debug,
+			LogLevel::Trace => tracing::log::LevelFilter::Trace,
+		}
+}
+
+/// Format a log record as a single line of JSON.
+///
+/// Fields recorded on the event itself (ie `%guild_id`) are already part of `message`.
+/// `span` is the span it was logged in and `spans` all the spans it was logged in, outermost first.
+fn json_line(message: &std::fmt::Arguments, record: &tracing::log::Record) -> String {
+	let spans = spans::current_spans();
+	serde_json::json!({
+		"timestamp": humantime::format_rfc3339(SystemTime::now()).to_string(),
+		"level": record.level().as_str(),
+		"target": record.target(),
+		"module": record.module_path(),
+		"file": record.file(),
+		"line": record.line(),
+		"message": message.to_string(),
+		"span": spans.last(),
+		"spans": spans,
+	})
+	.to_string()
+}
+
+fn init_sentry() -> Option<sentry::ClientInitGuard> {
+	let dsn = scripty_config::get_config().sentry_dsn.as_ref()?;
+
+	Some(sentry::init((
+		dsn.as_str(),
+		sentry::ClientOptions {
+			release: sentry::release_name!(),
+			attach_stacktrace: true,
+			..Default::default()
+		},
+	)))
+}
+
 async fn async_init() {
 	scripty_redis::init_redis().await;
 
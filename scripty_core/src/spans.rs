@@ -0,0 +1,114 @@
+//! Keeps track of the spans each thread is in, so JSON log lines can say which spans they were
+//! logged in.
+//!
+//! Only spans are handled here: events still go through `log` to our logger (see `log-always`).
+
+use serde_json::{Map, Value};
+use tracing::{
+	field::{Field, Visit},
+	span::{Attributes, Id, Record},
+	Subscriber,
+};
+use tracing_subscriber::{
+	layer::{Context, SubscriberExt},
+	registry::LookupSpan,
+	Layer,
+	Registry,
+};
+
+/// The fields of a span, stored in its extensions.
+struct SpanFields(Map<String, Value>);
+
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for FieldVisitor<'_> {
+	fn record_f64(&mut self, field: &Field, value: f64) {
+		self.0.insert(field.name().to_string(), value.into());
+	}
+
+	fn record_i64(&mut self, field: &Field, value: i64) {
+		self.0.insert(field.name().to_string(), value.into());
+	}
+
+	fn record_u64(&mut self, field: &Field, value: u64) {
+		self.0.insert(field.name().to_string(), value.into());
+	}
+
+	fn record_bool(&mut self, field: &Field, value: bool) {
+		self.0.insert(field.name().to_string(), value.into());
+	}
+
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.0.insert(field.name().to_string(), value.into());
+	}
+
+	fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+		self.0
+			.insert(field.name().to_string(), format!("{:?}", value).into());
+	}
+}
+
+struct SpanFieldsLayer;
+
+impl<S> Layer<S> for SpanFieldsLayer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(id) else {
+			return;
+		};
+		let mut fields = Map::new();
+		attrs.record(&mut FieldVisitor(&mut fields));
+		span.extensions_mut().insert(SpanFields(fields));
+	}
+
+	fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(id) else {
+			return;
+		};
+		if let Some(SpanFields(fields)) = span.extensions_mut().get_mut::<SpanFields>() {
+			values.record(&mut FieldVisitor(fields));
+		}
+	}
+}
+
+/// Start keeping track of spans. Must be called before anything else sets a global subscriber.
+// tokio-console sets its own subscriber instead
+#[cfg_attr(feature = "profiling", allow(dead_code))]
+pub fn init() {
+	tracing::subscriber::set_global_default(Registry::default().with(SpanFieldsLayer))
+		.expect("global tracing subscriber was already set");
+}
+
+/// The spans the current thread is in, from the outermost one in, as JSON objects with the span's
+/// name and fields.
+///
+/// Empty if `init` wasn't called.
+pub fn current_spans() -> Vec<Value> {
+	tracing::dispatcher::get_default(|dispatch| {
+		let Some(registry) = dispatch.downcast_ref::<Registry>() else {
+			return Vec::new();
+		};
+		let Some(span) = registry
+			.current_span()
+			.id()
+			.and_then(|id| registry.span(id))
+		else {
+			return Vec::new();
+		};
+
+		span.scope()
+			.from_root()
+			.map(|span| {
+				let mut obj = span
+					.extensions()
+					.get::<SpanFields>()
+					.map(|fields| fields.0.clone())
+					.unwrap_or_default();
+				obj.insert("name".to_string(), span.name().into());
+				Value::Object(obj)
+			})
+			.collect()
+	})
+}
@@ -0,0 +1,253 @@
+//! `scripty-ctl`: talks to a running bot over its admin control socket.
+//!
+//! The socket address and token are read from `SCRIPTY_CONTROL_ADDR` and `SCRIPTY_CONTROL_TOKEN`,
+//! or `--addr` and `--token`. Pass `--json` to print raw responses for scripting.
+
+use std::{
+	io::{self, BufRead, BufReader, Write},
+	net::TcpStream,
+	os::unix::net::UnixStream,
+	process::ExitCode,
+};
+
+use scripty_control::{Command, EntityKind, Request, Response, ResponseData};
+
+const DEFAULT_ADDR: &str = "unix:/run/scripty/control.sock";
+
+const USAGE: &str = "usage: scripty-ctl [--addr ADDR] [--token TOKEN] [--json] COMMAND
+
+commands:
+  sessions                                list active voice sessions
+  stt list                                list STT workers
+  stt reload                              reconnect to the STT services in the config
+  reload                                  reload the config file
+  reload i18n                             reload translations
+  cache                                   show Discord cache sizes
+  blacklist list (user|guild)             list blacklisted users or guilds
+  blacklist add (user|guild) ID [REASON]  blacklist a user or guild
+  blacklist remove (user|guild) ID        remove a user or guild from the blacklist
+  shutdown                                drain active sessions, then shut down";
+
+struct Options {
+	addr:    String,
+	token:   String,
+	json:    bool,
+	command: Command,
+}
+
+fn main() -> ExitCode {
+	let opts = match parse_args(std::env::args().skip(1).collect()) {
+		Ok(opts) => opts,
+		Err(e) => {
+			eprintln!("{}\n\n{}", e, USAGE);
+			return ExitCode::from(2);
+		}
+	};
+
+	let request = Request {
+		token:   opts.token,
+		command: opts.command,
+	};
+	let response = match send_request(&opts.addr, &request) {
+		Ok(res) => res,
+		Err(e) => {
+			eprintln!("failed to talk to {}: {}", opts.addr, e);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	if opts.json {
+		println!(
+			"{}",
+			serde_json::to_string_pretty(&response).expect("responses always serialize")
+		);
+	}
+
+	match response {
+		Response::Ok(data) => {
+			if !opts.json {
+				print_data(data);
+			}
+			ExitCode::SUCCESS
+		}
+		Response::Error(e) => {
+			if !opts.json {
+				eprintln!("error: {}", e);
+			}
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+	let mut addr = std::env::var("SCRIPTY_CONTROL_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.into());
+	let mut token = std::env::var("SCRIPTY_CONTROL_TOKEN").ok();
+	let mut json = false;
+	let mut positional = Vec::new();
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--addr" => addr = args.next().ok_or("--addr needs a value")?,
+			"--token" => token = Some(args.next().ok_or("--token needs a value")?),
+			"--json" => json = true,
+			"-h" | "--help" => return Err("".into()),
+			_ => positional.push(arg),
+		}
+	}
+
+	let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+	let command = match positional[..] {
+		["sessions"] => Command::Sessions,
+		["stt", "list"] => Command::SttList,
+		["stt", "reload"] => Command::SttReload,
+		["reload"] => Command::Reload,
+		["reload", "i18n"] => Command::ReloadI18n,
+		["cache"] => Command::CacheStats,
+		["shutdown"] => Command::Shutdown,
+		["blacklist", "list", kind] => Command::BlacklistList {
+			kind: parse_kind(kind)?,
+		},
+		["blacklist", "add", kind, id, ref reason @ ..] => Command::BlacklistAdd {
+			kind:   parse_kind(kind)?,
+			id:     parse_id(id)?,
+			reason: (!reason.is_empty()).then(|| reason.join(" ")),
+		},
+		["blacklist", "remove", kind, id] => Command::BlacklistRemove {
+			kind: parse_kind(kind)?,
+			id:   parse_id(id)?,
+		},
+		[] => return Err("no command given".into()),
+		_ => return Err(format!("unknown command `{}`", positional.join(" "))),
+	};
+
+	Ok(Options {
+		addr,
+		token: token.ok_or("no token given: set SCRIPTY_CONTROL_TOKEN or pass --token")?,
+		json,
+		command,
+	})
+}
+
+fn parse_kind(kind: &str) -> Result<EntityKind, String> {
+	match kind {
+		"user" => Ok(EntityKind::User),
+		"guild" => Ok(EntityKind::Guild),
+		_ => Err(format!("expected `user` or `guild`, got `{}`", kind)),
+	}
+}
+
+fn parse_id(id: &str) -> Result<u64, String> {
+	id.parse().map_err(|_| format!("invalid ID `{}`", id))
+}
+
+fn send_request(addr: &str, request: &Request) -> io::Result<Response> {
+	let mut line = serde_json::to_string(request)?;
+	line.push('\n');
+
+	let mut response = String::new();
+	if let Some(path) = addr.strip_prefix("unix:") {
+		let mut stream = UnixStream::connect(path)?;
+		stream.write_all(line.as_bytes())?;
+		BufReader::new(stream).read_line(&mut response)?;
+	} else {
+		let mut stream = TcpStream::connect(addr)?;
+		stream.write_all(line.as_bytes())?;
+		BufReader::new(stream).read_line(&mut response)?;
+	}
+
+	if response.is_empty() {
+		return Err(io::Error::new(
+			io::ErrorKind::UnexpectedEof,
+			"connection closed without a response",
+		));
+	}
+	Ok(serde_json::from_str(&response)?)
+}
+
+fn print_data(data: ResponseData) {
+	match data {
+		ResponseData::Message { message } => println!("{}", message),
+		ResponseData::Blacklist { entries } => print_table(
+			&["ID", "SINCE", "REASON"],
+			entries
+				.into_iter()
+				.map(|e| {
+					vec![
+						e.id,
+						e.blocked_since
+							.map_or_else(|| "-".into(), |t| t.to_string()),
+						e.reason.unwrap_or_else(|| "-".into()),
+					]
+				})
+				.collect(),
+		),
+		ResponseData::SttWorkers { workers } => print_table(
+			&["ADDRESS", "STATE"],
+			workers
+				.into_iter()
+				.map(|w| {
+					let state = if w.errored {
+						"errored"
+					} else if w.overloaded {
+						"overloaded"
+					} else {
+						"ok"
+					};
+					vec![w.address, state.into()]
+				})
+				.collect(),
+		),
+		ResponseData::CacheStats { fields } => print_table(
+			&["FIELD", "BYTES", "ITEMS"],
+			fields
+				.into_iter()
+				.map(|f| {
+					vec![
+						f.name,
+						f.size.to_string(),
+						f.items.map_or_else(|| "-".into(), |i| i.to_string()),
+					]
+				})
+				.collect(),
+		),
+		ResponseData::Sessions { sessions } => print_table(
+			&["GUILD", "STT STREAMS", "TRANSCRIBED USERS"],
+			sessions
+				.into_iter()
+				.map(|s| {
+					vec![
+						s.guild_id.to_string(),
+						s.stt_streams.to_string(),
+						s.transcribed_users.to_string(),
+					]
+				})
+				.collect(),
+		),
+	}
+}
+
+/// Print rows as left-aligned columns, padded to the widest cell in each.
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+	let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+	for row in &rows {
+		for (width, cell) in widths.iter_mut().zip(row) {
+			*width = (*width).max(cell.chars().count());
+		}
+	}
+
+	let print_row = |cells: Vec<&str>| {
+		let line = cells
+			.iter()
+			.zip(&widths)
+			.map(|(cell, width)| format!("{:<width$}", cell, width = width))
+			.collect::<Vec<_>>()
+			.join("  ");
+		println!("{}", line.trim_end());
+	};
+
+	print_row(headers.to_vec());
+	for row in &rows {
+		print_row(row.iter().map(String::as_str).collect());
+	}
+}
@@ -3,6 +3,7 @@ use std::io::Cursor;
 use hound::{SampleFormat, WavSpec, WavWriter};
 use ouroboros::self_referencing;
 use parking_lot::Mutex;
+use serenity::all::MessageId;
 
 #[self_referencing]
 struct Audio {
@@ -72,7 +73,9 @@ impl VoiceIngest {
 	}
 
 	/// Completes the audio ingest and adds the audio to the database.
-	pub async fn destroy(self, transcription: String) {
+	///
+	/// `message_id` is the transcript message it was sent in, if any, so it can be flagged later.
+	pub async fn destroy(self, transcription: String, message_id: Option<MessageId>) {
 		if transcription.is_empty() {
 			return;
 		}
@@ -89,12 +92,13 @@ impl VoiceIngest {
 		// this was processed on-demand to a WAV file, so we can just write it to the DB
 
 		let res = sqlx::query!(
-			"INSERT INTO audio_store (source_id, audio_data, transcript, transcript_language) \
-			 VALUES ($1, $2, $3, $4)",
+			"INSERT INTO audio_store (source_id, audio_data, transcript, transcript_language, \
+			 message_id) VALUES ($1, $2, $3, $4, $5)",
 			user_id,
 			audio_buffer,
 			transcription,
-			language
+			language,
+			message_id.map(|id| id.get() as i64)
 		)
 		.execute(scripty_db::get_db())
 		.await;
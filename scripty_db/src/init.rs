@@ -22,8 +22,7 @@ pub async fn init_db() {
 		.await
 		.expect("failed to connect to db");
 
-	sqlx::migrate!("../migrations")
-		.run(&pool)
+	crate::migrate::run_migrations(&pool)
 		.await
 		.expect("failed to run migrations");
 
@@ -1,6 +1,11 @@
+#[macro_use]
+extern crate tracing;
+
 mod init;
+mod migrate;
 mod store;
 
 pub use init::init_db;
+pub use migrate::{get_schema_status, MigrationStatus, SchemaStatus};
 pub use sqlx;
 pub use store::get_db;
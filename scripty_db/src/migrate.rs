@@ -0,0 +1,85 @@
+//! Embedded migration runner.
+//!
+//! Migrations are compiled into the binary, and run at startup while holding a Postgres advisory lock,
+//! so if multiple processes start at once only one of them will actually apply migrations.
+
+use sqlx::{migrate::Migrator, PgPool};
+
+static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+/// Advisory lock key used while migrating. Arbitrary, but must be the same across all processes.
+const MIGRATION_LOCK_KEY: i64 = 0x5c_12_17_d8;
+
+/// A single embedded migration and whether it has been applied to the database.
+#[derive(Debug)]
+pub struct MigrationStatus {
+	pub version:     i64,
+	pub description: String,
+	pub applied:     bool,
+	/// True if the migration was applied, but its checksum does not match the embedded copy.
+	pub mismatched:  bool,
+}
+
+/// Summary of the current schema state.
+#[derive(Debug)]
+pub struct SchemaStatus {
+	/// The latest successfully applied migration version, if any.
+	pub current_version: Option<i64>,
+	pub migrations:      Vec<MigrationStatus>,
+}
+
+impl SchemaStatus {
+	pub fn pending(&self) -> impl Iterator<Item = &MigrationStatus> {
+		self.migrations.iter().filter(|m| !m.applied)
+	}
+}
+
+/// Run all pending migrations, holding an advisory lock for the duration.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
+	let mut conn = pool.acquire().await?;
+
+	debug!("acquiring migration lock");
+	sqlx::query("SELECT pg_advisory_lock($1)")
+		.bind(MIGRATION_LOCK_KEY)
+		.execute(&mut *conn)
+		.await?;
+
+	info!("running database migrations");
+	let res = MIGRATOR.run(&mut *conn).await;
+
+	// always release the lock, even if migrating failed
+	sqlx::query("SELECT pg_advisory_unlock($1)")
+		.bind(MIGRATION_LOCK_KEY)
+		.execute(&mut *conn)
+		.await?;
+
+	res.map_err(|e| sqlx::Error::Migrate(Box::new(e)))
+}
+
+/// Compare the migrations embedded in this binary against those recorded in the database.
+pub async fn get_schema_status(pool: &PgPool) -> Result<SchemaStatus, sqlx::Error> {
+	let applied: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+		"SELECT version, checksum FROM _sqlx_migrations WHERE success = true ORDER BY version",
+	)
+	.fetch_all(pool)
+	.await?;
+
+	let migrations = MIGRATOR
+		.iter()
+		.filter(|m| !m.migration_type.is_down_migration())
+		.map(|m| {
+			let found = applied.iter().find(|(version, _)| *version == m.version);
+			MigrationStatus {
+				version:     m.version,
+				description: m.description.to_string(),
+				applied:     found.is_some(),
+				mismatched:  found.map_or(false, |(_, checksum)| *checksum != *m.checksum),
+			}
+		})
+		.collect();
+
+	Ok(SchemaStatus {
+		current_version: applied.last().map(|(version, _)| *version),
+		migrations,
+	})
+}
@@ -124,12 +124,9 @@ join-success =
 # This message is shown when the user requests latency information.
 # Note: the numbers here will be formatted according to the language set for the context.
 latency-description =
-    WebSocket-Latenz: { $wsLatencyMs }ms ({ $wsLatencyNs }ns)
-    HTTP-Latenz: { $httpLatencyMs }ms ({ $httpLatencyNs }ns)
-    Datenbanklatenz: { $pgLatencyMs }ms ({ $pgLatencyNs }ns)
-
-    Hinweis: eine Latenzzeit von 0ms bedeutet, dass diese Latenzzeit gerade nicht verfügbar ist.
-    Versuche es später noch einmal.
+    WebSocket-Latenz: { $wsLatency }
+    HTTP-Latenz: { $httpLatency }
+    Datenbanklatenz: { $pgLatency }
 voice-connection-error-ws-closed-server-not-found = Voiceserver konnte nicht gefunden werden
 voice-connection-error-ws-closed-session-timeout = Zeitüberschreitung der Session
 delete-data-title = Daten löschen
@@ -460,3 +457,6 @@ cmds_user_language = Nutzer
     .description = Setze deine Benutzersprache auf eine der verfügbaren Sprachen.
     .language = Sprache
     .language-description = Die Sprache, auf die du deine Benutzersprache einstellen willst.
+
+## transcription post-processing
+filler-words = äh, ähm, öh, hm, mhm
@@ -26,6 +26,8 @@ cmds_join = join
     .target_channel-description = Send transcripts here, instead of the current channel. Target a forum to create a new post.
     .create_thread = create_thread
     .create_thread-description = Create a new thread for this transcription? Defaults to false.
+    .voice_chat = voice_chat
+    .voice_chat-description = Send transcripts to the voice chat's own text chat, instead of another channel? Defaults to false.
 
 # This message is shown when the user is not in a voice channel, nor was a voice channel specified.
 no-channel-specified = You're not in a voice chat, nor did you tell me a channel to join. Try `{ $contextPrefix }join <channel>` to specify a voice chat, or join a voice chat yourself and re-run this command.
@@ -43,6 +45,14 @@ join-success = Successfully joined { $voiceTargetMention }, and sending transcri
 join-no-one-in-channel = There's no one in { $targetMention }. I'm not joining if there's no one there, as that's a waste of limited resources.
 # This message is shown when Discord tosses a Dropped or TimedOut error when trying to join a voice channel.
 join-failed-dropped = Discord appears to be having issues, we cannot do anything about this. Please try again later.
+# This message is shown when the server's premium subscription is already transcribing in as many servers as it can at once.
+join-failed-session-limit = The premium subscription on this server can only transcribe in { $maxSessions } servers at once, and that many are being transcribed right now. Stop one of them with `{ $contextPrefix }leave`, or upgrade at <https://dash.scripty.org/premium> to transcribe in more servers at once.
+# This message is shown when the user tries to make the bot join the server's AFK channel.
+join-failed-afk-channel = { $targetMention } is this server's AFK channel, where members are moved when they're idle, so I won't transcribe it. Join a different voice chat and try again.
+# This message is shown when the user tries to make the bot join an age-restricted voice chat, but the server doesn't allow transcribing those.
+join-failed-age-restricted-refused = This server doesn't transcribe age-restricted voice chats. A server manager can change this with `/config age_restricted`.
+# This message is shown when the user tries to send an age-restricted voice chat's transcripts to a channel that isn't age-restricted, but the server only allows sending them to age-restricted channels.
+join-failed-age-restricted-target = Transcripts of age-restricted voice chats can only be sent to age-restricted channels on this server. Pick an age-restricted channel to send them to, or a server manager can change this with `/config age_restricted`.
 # This message is shown when the bot does not have permissions for the voice channel it is trying to join.
 join-no-permission = I don't have permission to join { $targetMention }. Please give me the View Channel and Join permissions, or join a different voice chat where I do have permissions.
 # This message is shown when the user tries to tell the bot to join, but they have not agreed to the ToS.
@@ -53,12 +63,20 @@ join-create-thread-in-thread = I can't create a thread while in a thread. Please
 join-thread-title = Transcription from { $timestamp }
 # If the user specifies they would like to create a forum post, this is the contents of the initial message. { $timestamp } is the current timestamp, in ISO format, and { $authorMention } is the mention of the user who ran the command.
 join-forum-thread-content = { $authorMention } started a transcription at { $timestamp }.
-# This message is shown when the user has told a bot to join a forum channel, but the forum requires tags. This is not possible for the bot to work around as it has no way of knowing what tags to use.
-join-forum-requires-tags = The forum channel you tried to make me use requires tags. I have no way of knowing what tags to use, so I cannot join that channel. Please use a different channel, or ask an admin to remove the tag requirement.
+# This message is shown when the user has told a bot to join a forum channel, but the forum requires tags, and none of its tags are named after the voice chat or the transcription language.
+join-forum-requires-tags = The forum channel you tried to make me use requires tags, but none of its tags are named after the voice chat or the language being transcribed, so I don't know which to use. Please ask an admin to add a tag like that, or remove the tag requirement.
+# Title of the forum post made when the user tells the bot to join a forum channel. { $voiceChannel } is the name of the voice chat, and { $date } the date in ISO format, ie 2024-01-31.
+join-forum-post-title = { $voiceChannel } - { $date }
 # This message is shown when the user has told the bot to send transcripts to a non-text-based channel (ie category). `target_channel` should be translated, as slash command arguments are localized.
 join-target-not-text-based = The channel you told me to send transcripts to ({ $targetMention }) is not a text-based channel. Please use a text-based channel, or pick a different channel in the `target_channel` argument.
 # This message is shown when the user requests the bot create a new thread in a channel, but the channel doesn't support threads being created (usually voice channels)
 join-create-thread-in-unsupported = Discord does not support threads in { $targetMention }. Please use a different channel, or do not create a thread.
+# This message is shown when the user tells the bot to send transcripts to the voice chat's text chat, and to another channel too.
+join-voice-chat-with-target = Transcripts can go to either the voice chat's text chat or a target channel, not both. Please choose one.
+# This message is shown when the user tells the bot to send transcripts to the voice chat's text chat, and to create a thread.
+join-voice-chat-with-thread = Discord does not support threads in a voice chat's text chat. Please either send transcripts to the voice chat, or create a thread.
+# This message is shown when the user tells the bot to send transcripts to the voice chat's text chat, but the bot can't post there. { $missingPermissions } is a list of permissions.
+join-voice-chat-no-permission = I can't send transcripts in { $targetMention }'s text chat. Please give me these permissions in that voice chat: { $missingPermissions }
 
 ## Leave command
 # This and all attributes show up exclusively in the slash command picker when `leave` is selected.
@@ -81,9 +99,18 @@ cmds_premium = premium
 # This and all attributes show up exclusively in the slash command picker when `premium claim` is selected.
 cmds_premium_claim = claim
     .description = Claim your premium within the server where this command is executed.
+# This and all attributes show up exclusively in the slash command picker when `premium link` is selected.
+cmds_premium_link = link
+    .description = Link your Patreon account, to get premium for your pledge.
 # This and all attributes show up exclusively in the slash command picker when `premium remove` is selected.
 cmds_premium_remove = remove
     .description = Remove your premium from the server where this command is executed.
+# This and all attributes show up exclusively in the slash command picker when `premium trial` is selected.
+cmds_premium_trial = trial
+    .description = Start this server's free trial of premium.
+# This and all attributes show up exclusively in the slash command picker when `premium status` is selected.
+cmds_premium_status = status
+    .description = See this server's premium tier, where it comes from, and what it includes.
 # This is shown to the user when they are not subscribed to premium.
 premium-not-premium = You are not a premium subscriber. Subscribe at https://scripty.org/premium. If you know you are one, please DM the bot that way we can reinstate your premium.
 # This is shown to the user when they have too many used servers to add more.
@@ -92,9 +119,152 @@ premium-too-many-guilds = You have claimed { $totalServers } premium keys. You c
 premium-server-not-set-up = This server has not yet agreed to Scripty's ToS and Privacy Policy. Do that first with the `{ $commandPrefix }terms_of_service` command.
 # This is shown when the user successfully claims one of their premium subscriptions.
 premium-claimed = You have successfully claimed premium on this server. If you would like to upgrade, or purchase more slots, head to <https://dash.scripty.org/premium>. If you would like to remove your premium from this guild, run `{ $commandPrefix }premium remove`.
+# This is shown when the server's free trial starts. { $endsAt } is a Unix timestamp.
+premium-trial-started = This server's { $days }-day free trial of Scripty Premium has started! It ends <t:{ $endsAt }:F> (<t:{ $endsAt }:R>), and I'll remind you here the day before. If you'd like to keep premium after that, head to <https://dash.scripty.org/premium>.
+# This is shown when the user tries to start a trial in a server that already has premium.
+premium-trial-already-premium = This server already has premium, so there's no need for a trial.
+# This is shown when the server has already had its free trial.
+premium-trial-guild-used = This server has already had its free trial. If you'd like premium, head to <https://dash.scripty.org/premium>.
+# This is shown when the user has already started a free trial, in this server or another.
+premium-trial-user-used = You've already started a free trial in another server, and each person can only start one. If you'd like premium, head to <https://dash.scripty.org/premium>.
+# This is sent in the channel the trial was started from, a day before it ends. { $endsAt } is a Unix timestamp.
+premium-trial-ending = This server's free trial of Scripty Premium ends <t:{ $endsAt }:R>. To keep premium, subscribe at <https://dash.scripty.org/premium>, then run `/premium claim` here.
+# This is sent in the channel the trial was started from, once it has ended.
+premium-trial-ended = This server's free trial of Scripty Premium has ended. Thanks for trying it out! If you'd like premium back, subscribe at <https://dash.scripty.org/premium>, then run `/premium claim` here.
+# This is the title of the embed shown by `premium status`.
+premium-status-title = Premium status
+# This is the name of the field showing the server's premium tier.
+premium-status-tier-field = Tier
+# This is the server's premium tier. { $tier } is the tier number, 0 meaning the server has no premium.
+premium-status-tier = { $tier ->
+    [0] Free
+   *[other] Tier { $tier }
+}
+# This is the name of the field showing where the server's premium comes from.
+premium-status-source-field = Granted by
+premium-status-source-none = Nothing yet
+premium-status-source-subscription = A premium subscription
+premium-status-source-patreon = A Patreon pledge
+premium-status-source-trial = This server's free trial
+# This is the name of the field showing when the server's premium ends or renews.
+premium-status-expiry-field = Renewal
+# { $expiresAt } is a Unix timestamp.
+premium-status-expires = Ends <t:{ $expiresAt }:F> (<t:{ $expiresAt }:R>)
+# { $expiresAt } is a Unix timestamp.
+premium-status-patreon-expires = Renews with each pledge payment. If the next one doesn't come through, ends <t:{ $expiresAt }:F> (<t:{ $expiresAt }:R>)
+premium-status-renews = Renews automatically
+# This is the name of the field showing how much of the monthly transcription quota has been used.
+premium-status-usage-field = Transcription this month
+premium-status-usage = { $usedMinutes } of { $limitMinutes } minutes used
+premium-status-usage-unlimited = { $usedMinutes } minutes used, with no limit
+# This is the name of the field listing what the server's tier includes.
+premium-status-features-field = Features
+premium-status-feature-audio-files = Audio file transcription
+premium-status-feature-video-files = Video file transcription
+premium-status-feature-auto-detect-lang = Automatic language detection
+premium-status-feature-automod-rules = Up to { $maxRules } automod rules
+# This is only shown for servers with a subscription, which is shared between { $maxGuilds } servers.
+premium-status-feature-sessions = Transcribing in { $maxSessions } of the subscription's { $maxGuilds } servers at once
+# This is shown at the top of `premium status` for servers without premium.
+premium-status-upsell = This server doesn't have premium. Get it at <https://dash.scripty.org/premium>, or if this server hasn't had its free trial yet, try it for a week with `{ $commandPrefix }premium trial`.
+# This is shown with a button to link their Patreon account. { $minutes } is how long the button works for.
+premium-link = Press the button below to link your Patreon account. Your premium will be activated automatically for as long as you're a patron. This link works for { $minutes } minutes, and only for you, so don't share it.
+# This is the label of the button that takes the user to Patreon.
+premium-link-button = Link Patreon
+# This is shown when this instance of the bot isn't set up to link Patreon accounts.
+premium-link-unavailable = Linking Patreon accounts isn't available on this bot.
 # This is shown when the user successfully removes their premium from this guild.
 premium-removed = If you are the user who had claimed Premium, you have now successfully removed your premium from this server. If you would like to upgrade, or purchase more slots, head to <https://dash.scripty.org/premium>.
 
+## captions command
+# This and all attributes show up exclusively in the slash command picker when `captions` is selected.
+cmds_captions = captions
+    .description = Live caption commands
+# This and all attributes show up exclusively in the slash command picker when `captions overlay` is selected.
+cmds_captions_overlay = overlay
+    .description = Get a URL that shows live captions of this voice session, for a stream overlay.
+# This is shown with the overlay URL. { $hours } is how long the URL works for at most. The options after "?" must be kept as-is.
+captions-overlay = Add this URL as a browser source in OBS (or any other streaming software) to show live captions of this voice chat. It works until I stop transcribing it, for up to { $hours } hours, and anyone with it can read the captions, so don't share it.
+  { $url }
+  Add `?lines=3&speakers=1&fade=10` to the end to change how many lines are shown, whether speakers are named, and how many seconds lines stay up for. The text can be restyled with the browser source's custom CSS.
+# This is shown when Scripty isn't in a voice chat in this server.
+captions-overlay-no-session = I'm not transcribing a voice chat in this server right now. Start with `/join`, then run this again.
+# This is shown when the user isn't in the voice chat being transcribed. { $channelId } is that voice chat.
+captions-overlay-not-in-channel = You need to be in <#{ $channelId }> to get captions for it.
+# This is shown when this instance of the bot isn't set up to serve overlays.
+captions-overlay-unavailable = Caption overlays aren't available on this bot.
+# This is shown by `/captions live` and `/captions overlay` when the server hasn't turned live captions on.
+captions-disabled = Live captions are off in this server. Someone with Manage Server can turn them on with `/config live_captions`.
+# This and all attributes show up exclusively in the slash command picker when `captions live` is selected.
+cmds_captions_live = live
+    .description = Get live captions of this voice session, in a message only you can see.
+# Shown until the first line is said.
+captions-live-waiting = Captions will show up here as people speak.
+# Shown under the captions while they're being updated. { $minutes } is how long they're updated for.
+captions-live-footer = Live captions, updated every few seconds for { $minutes } minutes.
+# Shown under the last captions once they stop being updated.
+captions-live-expired = Captions stopped after { $minutes } minutes. Run `/captions live` again to keep going.
+captions-live-ended = The voice chat stopped being transcribed, so captions have stopped.
+captions-live-left = You left the voice chat, so captions have stopped.
+captions-live-disconnected = Captions stopped unexpectedly. Run `/captions live` again to keep going.
+
+## analytics command
+# This and all attributes show up exclusively in the slash command picker when `analytics` is selected.
+cmds_analytics = analytics
+    .description = Talk time analytics commands
+# This and all attributes show up exclusively in the slash command picker when `analytics me` is selected.
+cmds_analytics_me = me
+    .description = See how much you've spoken in this server's voice chats.
+    .period = period
+    .period-description = How far back to look. Defaults to a month.
+    .period-choice-Week = Past week
+    .period-choice-Month = Past month
+    .period-choice-Quarter = Past 3 months
+    .period-choice-Year = Past year
+# This and all attributes show up exclusively in the slash command picker when `analytics user` is selected.
+cmds_analytics_user = user
+    .description = See how much a member has spoken in this server's voice chats.
+    .member = member
+    .member-description = Member to look up.
+    .period = period
+    .period-description = How far back to look. Defaults to a month.
+    .period-choice-Week = Past week
+    .period-choice-Month = Past month
+    .period-choice-Quarter = Past 3 months
+    .period-choice-Year = Past year
+# { $user } is the display name of who it's about.
+analytics-title = Talk time of { $user }
+analytics-description = Over the past { $days } days, in voice chats Scripty transcribed.
+analytics-speaking-time = Speaking time
+analytics-utterances = Times spoken
+analytics-average-utterance = Average length
+analytics-seconds = { $seconds } seconds
+analytics-active-days = Days active
+analytics-active-hours = Most active hours
+# One of the hours of the day the user speaks most in. { $start } and { $end } are Unix timestamps, and must be kept as-is.
+analytics-active-hour = <t:{ $start }:t> to <t:{ $end }:t>: { $percent }% of speaking time
+analytics-opted-out-self = You've opted out of talk time analytics. Opt back in with `/data_storage`.
+analytics-opted-out = { $user } has opted out of talk time analytics.
+analytics-no-data-self = You haven't spoken in a voice chat Scripty transcribed in the past { $days } days.
+analytics-no-data = { $user } hasn't spoken in a voice chat Scripty transcribed in the past { $days } days.
+
+## debug command
+# This and all attributes show up exclusively in the slash command picker when `debug` is selected.
+cmds_debug = debug
+    .description = Commands for figuring out why Scripty is behaving the way it is
+# This and all attributes show up exclusively in the slash command picker when `debug session` is selected.
+cmds_debug_session = session
+    .description = See what Scripty is doing in this server's voice chat, and who it's transcribing.
+debug-session-none = Scripty isn't transcribing a voice chat in this server right now.
+debug-session-title = Current session
+debug-session-voice-chat = Voice chat
+debug-session-speakers = People who've spoken
+debug-session-streams = Open transcription streams
+debug-session-watch-mode = Watch mode
+debug-session-watch-off = Off: everyone is transcribed.
+# { $watched } is a list of user and role mentions.
+debug-session-watch-on = **On**: only { $watched } are transcribed. Change this with `/config watch`.
+
 
 ## config - verbose command
 cmds_config_verbose = verbose
@@ -139,8 +309,14 @@ config_auto_detect_lang = auto_detect_lang
     .description = Try to automatically detect the language being spoken? Very inaccurate vs setting a language.
     .auto_detect_lang = auto_detect_lang
     .auto_detect_lang-description = Defaults to false
+    .languages = languages
+    .languages-description = Only pick from these languages, ie `en, de, fr`. Defaults to all of them
 
 config-auto-detect-lang-enabled = Scripty will now automatically detect the language being spoken.
+# This message is shown when auto-detect is turned on with a list of languages to pick from.
+config-auto-detect-lang-enabled-languages = Scripty will now automatically detect the language being spoken, out of { $languages }.
+# This message is shown when one of the languages given can't be detected. Nothing was changed.
+config-auto-detect-lang-invalid-language = `{ $language }` isn't a language that can be detected. Use two-letter codes, ie `en, de, fr`.
 config-auto-detect-lang-disabled = Scripty will no longer automatically detect the language being spoken.
 config-auto-detect-lang-requires-premium = Automatically detecting the language is a Premium feature, as it is extremely computationally expensive to re-run the model twice to figure out the language.
   If you would like to upgrade to Premium, head to https://dash.scripty.org/premium. You can also request a free trial of Premium by DMing the bot.
@@ -164,6 +340,470 @@ config_translate = translate
 config-translate-enabled = Scripty will now translate transcriptions to English.
 config-translate-disabled = Scripty will now attempt to match the phrases being spoken to English words, but will not translate. 
 
+## config - api key command
+config_api_key = api_key
+    .description = Manage this server's API key, for fetching its transcripts from Scripty's API.
+    .action = action
+    .action-description = What to do with the key.
+    .action-choice-Show = Show whether this server has a key
+    .action-choice-Create = Create a new key, replacing any old one
+    .action-choice-Revoke = Revoke the key, and delete stored transcripts
+
+# { $apiKey } is the key itself, and must be kept as-is.
+config-api-key-created = Here's this server's new API key. Keep it secret: anyone with it can read this server's transcripts. It won't be shown again, and any old key has stopped working.
+  `{ $apiKey }`
+  Transcripts of voice sessions started from now on will be stored for { $retentionDays } days, and can be fetched from the API by sending this key in the `Authorization` header.
+config-api-key-revoked = This server's API key has been revoked, and its stored transcripts have been deleted. No more transcripts will be stored.
+config-api-key-none = This server doesn't have an API key. Make one with `/config api_key`.
+# { $createdAt } and { $lastUsedAt } are Unix timestamps. { $lastUsedAt } is 0 if the key hasn't been used.
+config-api-key-info = This server has an API key, made <t:{ $createdAt }:R>. { $lastUsedAt ->
+    [0] It hasn't been used yet.
+   *[other] It was last used <t:{ $lastUsedAt }:R>.
+}
+
+## config - webhook url command
+config_webhook_url = webhook_url
+    .description = Set a URL to send a summary of every voice session to, when it ends.
+    .url = url
+    .url-description = HTTPS URL to send summaries to: set empty to disable
+
+# { $secret } is the signing secret itself, and must be kept as-is.
+config-webhook-url-set = When a voice session ends, a summary will now be sent to { $url }, including its transcript if transcripts were being recorded.
+  Requests are signed with this secret: check the `X-Scripty-Signature` header against the HMAC-SHA256 of the request body. It won't be shown again, and any old secret has stopped working.
+  `{ $secret }`
+config-webhook-url-disabled = Voice session summaries will no longer be sent anywhere.
+config-webhook-url-none = This server doesn't have a webhook URL set. Set one with `/config webhook_url`.
+config-webhook-url-invalid = That isn't a valid URL.
+config-webhook-url-not-https = The webhook URL must start with `https://`.
+config-webhook-url-not-public = The webhook URL must be reachable from the internet.
+
+## config - bridge command
+config_bridge = bridge
+    .description = Mirror transcripts to a Slack channel or Matrix room, as they come in.
+    .platform = platform
+    .platform-description = Where to send transcripts, or Off to stop.
+    .platform-choice-Slack = Slack
+    .platform-choice-Matrix = Matrix
+    .platform-choice-Off = Off
+    .url = url
+    .url-description = Slack incoming webhook URL, or Matrix homeserver URL
+    .matrix_room = matrix_room
+    .matrix_room-description = Matrix room ID, like !abc:example.org
+    .matrix_token = matrix_token
+    .matrix_token-description = Access token of the Matrix account to post as
+    .voice_channel = voice_channel
+    .voice_channel-description = Only bridge sessions in this voice chat. Defaults to all of them.
+
+config-bridge-enabled = Transcripts of voice chats started from now on will be sent there too, as they come in.
+# { $channelId } is the voice chat being bridged.
+config-bridge-enabled-channel = Transcripts of voice chats in <#{ $channelId }> started from now on will be sent there too, as they come in.
+config-bridge-disabled = Transcripts will no longer be bridged. Voice chats that are already being transcribed will keep being bridged until they end.
+config-bridge-none = This server doesn't have a bridge set up. Set one up with `/config bridge`.
+config-bridge-missing-options = Slack needs the `url` option. Matrix needs the `url`, `matrix_room`, and `matrix_token` options.
+config-bridge-invalid-slack-url = That isn't a Slack incoming webhook URL. They start with `https://hooks.slack.com/`.
+config-bridge-not-https = The homeserver URL must start with `https://`.
+config-bridge-invalid-homeserver = That isn't a homeserver URL that can be reached from the internet.
+config-bridge-invalid-matrix-room = That isn't a Matrix room ID. Room IDs start with `!`, and can be found in the room's advanced settings.
+# This is sent to the bridged Slack channel or Matrix room, to check it works.
+config-bridge-test-message = This room will now get transcripts from Discord.
+# { $error } is the error from Slack or Matrix, which is left untranslated.
+config-bridge-test-failed = Couldn't send a test message there, so the bridge wasn't set up: { $error }
+
+## config - speaker_labels command
+config_speaker_labels = speaker_labels
+    .description = Give each speaker their own color or emoji on transcripts, so it's easier to tell who's who.
+    .mode = mode
+    .mode-description = Defaults to Off
+    .mode-choice-Off = Off
+    .mode-choice-Color = Color
+    .mode-choice-Emoji = Emoji
+
+config-speaker-labels-disabled = Transcripts will no longer have speaker labels.
+config-speaker-labels-color = Transcripts will now be in embeds, colored by who's speaking. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-speaker-labels-emoji = Transcripts will now start with an emoji for who's speaking. Voice chats that are already being transcribed will pick this up within a few minutes.
+
+## config - merge_utterances command
+config_merge_utterances = merge_utterances
+    .description = Merge each speaker's back-to-back transcripts into one message, instead of sending one each.
+    .gap = gap
+    .gap-description = Seconds between transcripts to still merge them. 0 turns merging off.
+
+config-merge-utterances-enabled = Transcripts from the same person less than { $gap } seconds apart, with nobody else speaking in between, will now be merged into one message. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-merge-utterances-disabled = Transcripts will no longer be merged.
+config-merge-utterances-too-long = The gap can be at most { $maxGap } seconds.
+
+## config - profanity_filter command
+config_profanity_filter = profanity_filter
+    .description = Hide swear words in transcripts.
+    .mode = mode
+    .mode-description = Defaults to Off
+    .mode-choice-Off = Off
+    .mode-choice-Mask = Mask
+    .mode-choice-Spoiler = Spoiler
+
+config-profanity-filter-disabled = Swear words will no longer be hidden in transcripts.
+config-profanity-filter-mask = Swear words in transcripts will now have all but their first letter replaced with asterisks. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-profanity-filter-spoiler = Swear words in transcripts will now be hidden behind spoilers. Voice chats that are already being transcribed will pick this up within a few minutes.
+
+## config - timestamps command
+config_timestamps = timestamps
+    .description = Start each transcript with when it was said, for using transcripts as meeting minutes.
+    .mode = mode
+    .mode-description = Defaults to Off
+    .mode-choice-Off = Off
+    .mode-choice-Relative = Relative
+    .mode-choice-Absolute = Absolute
+
+config-timestamps-disabled = Transcripts will no longer have timestamps.
+config-timestamps-relative = Transcripts will now start with how long after Scripty joined they were said. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-timestamps-absolute = Transcripts will now start with the time they were said, in each reader's own timezone. Voice chats that are already being transcribed will pick this up within a few minutes.
+
+## config - transcript_style command
+config_transcript_style = transcript_style
+    .description = Choose how transcripts look.
+    .style = style
+    .style-description = Defaults to Webhook
+    .style-choice-Webhook = Webhook
+    .style-choice-Compact = Compact
+    .style-choice-Embed = Embed
+
+config-transcript-style-updated = Transcript style updated. Voice chats that are already being transcribed will pick it up within a few minutes.
+
+## config - filler_words command
+config_filler_words = filler_words
+    .description = Toggle removing filler words, like "um" and "uh", from transcripts.
+    .remove_filler_words = remove_filler_words
+    .remove_filler_words-description = Defaults to false
+
+config-filler-words-enabled = Filler words like "um" and "uh" will now be removed from transcripts. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-filler-words-disabled = Transcripts will now include filler words again.
+
+## config - min_length command
+config_min_length = min_length
+    .description = Drop transcripts shorter than this, like coughs or a lone "yeah", to keep the channel quieter.
+    .length = length
+    .length-description = Shortest transcript to send. 0 sends everything.
+    .unit = unit
+    .unit-description = Defaults to Characters
+    .unit-choice-Characters = Characters
+    .unit-choice-Words = Words
+
+config-min-length-characters = Transcripts shorter than { $length } characters will no longer be sent. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-min-length-words = Transcripts shorter than { $length } words will no longer be sent. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-min-length-disabled = Transcripts will now be sent however short they are.
+config-min-length-too-long = The minimum length can be at most { $maxLength }.
+
+## config - numbers command
+config_numbers = numbers
+    .description = Choose whether numbers in transcripts are written as words or digits.
+    .format = format
+    .format-description = Defaults to Words, or however the transcription model writes them
+    .format-choice-Words = Words
+    .format-choice-Digits = Digits
+
+config-numbers-digits = Numbers in transcripts will now be written as digits, ie "at 3:30 pm" instead of "at three thirty pm". This only works for English. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-numbers-words = Numbers in transcripts will now be left however they were transcribed.
+
+## config - silence_timeout command
+config_silence_timeout = silence_timeout
+    .description = How long someone has to stop talking for before their transcript is sent.
+    .timeout_ms = timeout_ms
+    .timeout_ms-description = In milliseconds. Defaults to 0, sending as soon as someone stops talking.
+
+config-silence-timeout-updated = Transcripts will now be sent once someone has stopped talking for { $timeout }ms. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-silence-timeout-too-long = The timeout can be at most { $maxTimeout }ms.
+
+## config - voice_commands command
+config_voice_commands = voice_commands
+    .description = Let members with this role control Scripty by voice, ie "Scripty, stop transcribing".
+    .role = role
+    .role-description = Role that can use voice commands: set empty to disable.
+
+config-voice-commands-enabled = Members with { $roleId } can now say "Scripty stop", "Scripty start" or "Scripty leave" in a voice chat to control transcription. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-voice-commands-disabled = Scripty will no longer respond to voice commands.
+
+## config - tts_relay command
+config_tts_relay = tts_relay
+    .description = Read out messages sent in the transcript channel by members with this role, in the voice chat.
+    .role = role
+    .role-description = Role whose messages are read out: set empty to disable.
+
+config-tts-relay-enabled = Messages members with { $roleId } send in the transcript channel will now be read out in the voice chat. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-tts-relay-disabled = Messages will no longer be read out in voice chats.
+config-tts-relay-unavailable = Reading messages out isn't available on this instance of Scripty.
+
+## config - scheduled_events command
+config_scheduled_events = scheduled_events
+    .description = Transcribe voice channel events automatically, from when they're started until they end.
+    .channel = channel
+    .channel-description = Send event transcripts here: set empty to disable.
+    .create_thread = create_thread
+    .create_thread-description = Create a thread in that channel for each event? Defaults to false.
+    .record_transcriptions = record_transcriptions
+    .record_transcriptions-description = Send the transcript as a file when the event ends? Users who spoke will be DMed it too. Defaults to false.
+
+config-scheduled-events-enabled = Voice channel events will now be transcribed in { $channelMention } once they're started. Scripty will leave when the event ends.
+config-scheduled-events-disabled = Events will no longer be transcribed automatically.
+config-scheduled-events-missing-permissions = I need these permissions in { $channelMention } to transcribe events there: { $missingPermissions }
+
+## config - anonymous_speakers command
+config_anonymous_speakers = anonymous_speakers
+    .description = Toggle numbering speakers instead of naming them, for transcripts that don't identify anyone.
+    .anonymous_speakers = anonymous_speakers
+    .anonymous_speakers-description = Defaults to false
+
+config-anonymous-speakers-enabled = Speakers will now be shown as "Speaker 1", "Speaker 2" and so on, numbered afresh each session. No user IDs will be stored with transcripts, and talk time won't be counted for `/analytics`. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-anonymous-speakers-disabled = Transcripts will now show speakers' names and avatars again.
+# Shown instead of a speaker's name when anonymous speakers are on. { $number } counts up from 1, in the order people first spoke.
+anonymous-speaker-name = Speaker { $number }
+
+## config - consent_dm command
+config_consent_dm = consent_dm
+    .description = Toggle DMing members that a voice chat they join is being transcribed, if they've never been told.
+    .consent_dm = consent_dm
+    .consent_dm-description = Defaults to false
+
+config-consent-dm-enabled = Members joining a voice chat while it's being transcribed will now be DMed about it, unless they've been told before. Members with DMs closed won't get the notice.
+config-consent-dm-disabled = Members will no longer be DMed when they join a voice chat being transcribed.
+
+## config - flag_emoji command
+config_flag_emoji = flag_emoji
+    .description = Set an emoji users can react to transcripts with to flag them as wrong.
+    .emoji = emoji
+    .emoji-description = Emoji to flag transcripts with: set empty to disable.
+
+config-flag-emoji-enabled = Reacting to a transcript with { $emoji } will now flag it as wrong. Transcripts of users who opted in to audio storage with `/data_storage` will be queued for a correction, to help improve Scripty's transcriptions.
+config-flag-emoji-disabled = Transcripts can no longer be flagged by reacting to them.
+config-flag-emoji-invalid = { $emoji } isn't an emoji. Use a standard emoji, or a custom one from this server.
+
+## config - highlight_emoji command
+config_highlight_emoji = highlight_emoji
+    .description = Set an emoji users can react to transcripts with to add them to the session's highlights.
+    .emoji = emoji
+    .emoji-description = Emoji to highlight transcripts with: set empty to disable.
+
+config-highlight-emoji-enabled = Reacting to a transcript with { $emoji } will now add it to the session's highlights, which are listed in `/minutes` and at the end of the transcript file.
+config-highlight-emoji-disabled = Transcripts can no longer be highlighted by reacting to them.
+config-highlight-emoji-invalid = { $emoji } isn't an emoji. Use a standard emoji, or a custom one from this server.
+
+## config - age_restricted command
+config_age_restricted = age_restricted
+    .description = Choose how age-restricted voice chats are transcribed.
+    .policy = policy
+    .policy-description = Defaults to Allow
+    .policy-choice-Allow = Transcribe them like any other
+    .policy-choice-Refuse = Never transcribe them
+    .policy-choice-Restricted = Only into age-restricted channels
+    .policy-choice-Spoiler = Hide transcripts behind spoilers
+
+config-age-restricted-updated = Age-restricted voice chat policy updated. It applies the next time Scripty joins a voice chat; if set to spoilers, voice chats already being transcribed will pick it up within a few minutes.
+
+## config - subscriptions command
+config_subscriptions = subscriptions
+    .description = Toggle letting members get voice chats' transcripts DMed to them with /subscribe.
+    .subscriptions = subscriptions
+    .subscriptions-description = Defaults to false
+
+config-subscriptions-enabled = Members can now use `/subscribe` to get a voice chat's transcripts DMed to them when it ends. They only get transcripts of voice chats they can see, sent into channels they can see.
+config-subscriptions-disabled = Transcripts will no longer be DMed to subscribed members. Their subscriptions are kept, in case this is turned back on.
+
+## config - join_leave_notices command
+config_join_leave_notices = join_leave_notices
+    .description = Toggle posting a line in the transcript when someone joins or leaves the voice chat.
+    .join_leave_notices = join_leave_notices
+    .join_leave_notices-description = Defaults to false
+config-join-leave-notices-enabled = I'll now post a line in the transcript whenever someone joins or leaves the voice chat. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-join-leave-notices-disabled = I'll no longer post a line in the transcript when someone joins or leaves the voice chat.
+
+## config - live_captions command
+config_live_captions = live_captions
+    .description = Toggle live captions of voice chats, for /captions and stream overlays.
+    .live_captions = live_captions
+    .live_captions-description = Defaults to false
+config-live-captions-enabled = Live captions are now on: members in a voice chat I'm transcribing can follow it with `/captions`. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-live-captions-disabled = Live captions are now off. Voice chats that are already being transcribed will stop publishing them within a few minutes.
+
+## config - hold_queue command
+config_hold_queue = hold_queue
+    .description = Hold transcripts in a private channel until a moderator approves them, ie during a public event.
+    .channel = channel
+    .channel-description = Review transcripts here before they're posted: set empty to disable.
+    .auto_approve_minutes = auto_approve_minutes
+    .auto_approve_minutes-description = Post transcripts nobody rejected after this many minutes. Defaults to waiting for a moderator.
+
+config-hold-queue-enabled = Transcripts will now be held in { $channelMention } until a moderator approves them. Anyone who can manage messages there can approve or reject them. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-hold-queue-enabled-auto-approve = Transcripts will now be held in { $channelMention }, and posted after { $minutes } minutes unless a moderator rejects them first. Anyone who can manage messages there can approve or reject them. Voice chats that are already being transcribed will pick this up within a few minutes.
+config-hold-queue-disabled = Transcripts will no longer be held for review. Ones that are already held can still be approved or rejected.
+config-hold-queue-invalid-timeout = Transcripts can be approved automatically after 1 to { $maxMinutes } minutes.
+config-hold-queue-missing-permissions = I need these permissions in { $channelMention } to hold transcripts there: { $missingPermissions }
+
+## consent notice DM
+consent-notice-title = You're being transcribed
+consent-notice-description = The voice chat you just joined in **{ $guildName }**, <#{ $voiceChannelId }>, is being transcribed by Scripty. What you say there is turned into text and sent to the server.
+
+    If you don't want to be transcribed, you can:
+    - leave the voice chat
+    - ask the server's moderators to stop transcription
+    - use `/data_storage` to choose what Scripty stores about you
+    - use `/delete_all_data` to delete everything Scripty has stored about you
+
+    You'll only get this message once.
+consent-notice-privacy-button = Privacy Policy
+consent-notice-terms-button = Terms of Service
+
+## onboarding message
+# Sent once when Scripty is added to a server, to its system channel, or to the owner if Scripty can't post there.
+onboarding-title = Thanks for adding Scripty!
+onboarding-description = Scripty turns what's said in your voice chats into text, live. Here's how to get going, and what it means for your members.
+onboarding-setup-title = Getting started
+onboarding-setup-description =
+    1. Run `/terms_of_service` to agree to Scripty's Terms of Service and Privacy Policy.
+    2. Join a voice chat, and run `/join` in the channel transcripts should be sent to.
+    3. Change how transcripts look and work with `/config`, like `/config language` for your server's language.
+onboarding-privacy-title = What Scripty keeps
+onboarding-privacy-description = By default, transcripts are only sent to your channel, and voice recordings aren't kept. Members choose what Scripty stores about them with `/data_storage`, and can delete all of it at any time with `/delete_all_data`.
+onboarding-consent-title = Letting members know
+onboarding-consent-description = Everyone in a voice chat Scripty is in will be transcribed, so make sure your members know. `/config consent_dm` DMs members when they first join a transcribed voice chat, and `/config transcribe_only_role` limits transcription to members with a role.
+onboarding-support-button = Support server
+
+## guild data cleanup
+# DMed to a server's owner once its data was deleted, some time after Scripty was removed from it. { $removedAt } is a Unix timestamp.
+guild-cleanup-title = Server data deleted
+guild-cleanup-description = Scripty was removed from **{ $guildName }** <t:{ $removedAt }:R>, so everything it kept for that server has now been deleted: its settings, transcript channels, replacements, automations and { $transcripts ->
+    [one] 1 stored transcript
+   *[other] { $transcripts } stored transcripts
+}.
+guild-cleanup-footer = If you add Scripty back, you'll need to set it up again.
+
+## config - replacements commands
+config_replacements = replacements
+    .description = Fix words Scripty keeps getting wrong in this server's transcripts.
+config_replacements_add = add
+    .description = Replace text matching a regex in every transcript. Matching ignores case.
+    .pattern = pattern
+    .pattern-description = Regex to find, like scriptee or \bteh\b
+    .replacement = replacement
+    .replacement-description = Text to put in its place, or empty to remove it. $1 inserts the first group
+config_replacements_remove = remove
+    .description = Stop replacing text in transcripts.
+    .id = id
+    .id-description = ID of the replacement to remove, from the list command
+config_replacements_list = list
+    .description = List this server's transcript replacements.
+
+# { $id } is the replacement's ID, for removing it later.
+config-replacements-added = Replacement { $id } added: `{ $pattern }` will be replaced with `{ $replacement }`. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-replacements-full = This server already has { $maxReplacements } replacements, which is the most it can have. Remove one with `/config replacements remove` first.
+config-replacements-too-long = Patterns and replacements can be at most { $maxLength } characters long.
+# { $error } is the regex parser's error, which is left untranslated.
+config-replacements-invalid-pattern = That isn't a valid regex:
+  ```
+  { $error }
+  ```
+config-replacements-matches-empty = That pattern matches nothing at all, so it would be replaced between every letter.
+config-replacements-removed = Replacement { $id } removed.
+config-replacements-not-found = This server doesn't have a replacement with ID { $id }. See them all with `/config replacements list`.
+config-replacements-none = This server doesn't have any replacements. Add one with `/config replacements add`.
+config-replacements-list-title = Transcript replacements
+config-replacements-list-field-name = Replacement { $id }
+config-replacements-list-field-value = `{ $pattern }` → `{ $replacement }`
+
+## config - watch commands
+config_watch = watch
+    .description = Only transcribe certain users or roles, ie the GM of a game, or an interviewee.
+config_watch_add = add
+    .description = Transcribe this user or role. Once anyone's watched, only those watched are transcribed.
+    .user = user
+    .user-description = User to transcribe
+    .role = role
+    .role-description = Role to transcribe the members of
+config_watch_remove = remove
+    .description = Stop only transcribing this user or role.
+    .user = user
+    .user-description = User to stop watching
+    .role = role
+    .role-description = Role to stop watching
+config_watch_list = list
+    .description = List who's being watched in this server.
+
+# { $target } mentions the user or role.
+config-watch-added = Watching { $target }. Only watched users, and members with watched roles, will be transcribed. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-watch-full = This server is already watching { $maxWatched } users and roles, which is the most it can. Remove one with `/config watch remove` first.
+config-watch-no-target = Pick either a user or a role.
+config-watch-removed = Stopped watching { $target }.
+config-watch-removed-last = Nobody is being watched anymore, so everyone will be transcribed again.
+config-watch-not-found = { $target } isn't being watched. See who is with `/config watch list`.
+config-watch-none = Nobody is being watched, so everyone is transcribed. Add someone with `/config watch add`.
+# { $watched } is a list of user and role mentions.
+config-watch-list = Only these users and roles are being transcribed: { $watched }
+
+## config - role_policy commands
+config_role_policy = role_policy
+    .description = Choose which roles are transcribed, ie never transcribing guests.
+config_role_policy_allow = allow
+    .description = Only transcribe members with this role, or any other allowed role.
+    .role = role
+    .role-description = Role to allow
+    .and_above = and_above
+    .and_above-description = Also allow every role above it? Defaults to false.
+config_role_policy_deny = deny
+    .description = Never transcribe members with this role.
+    .role = role
+    .role-description = Role to never transcribe
+config_role_policy_remove = remove
+    .description = Remove this role's policy.
+    .role = role
+    .role-description = Role to remove the policy of
+config_role_policy_list = list
+    .description = List this server's role policies.
+
+# { $role } mentions the role.
+config-role-policy-allowed = Only members with { $role }, or another allowed role, will be transcribed. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-role-policy-allowed-and-above = Only members with { $role } or a role above it, or another allowed role, will be transcribed. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-role-policy-denied = Members with { $role } will never be transcribed, even if they're watched or have an allowed role. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-role-policy-full = This server already has policies for { $maxPolicies } roles, which is the most it can have. Remove one with `/config role_policy remove` first.
+config-role-policy-removed = Removed the policy for { $role }.
+config-role-policy-not-found = { $role } doesn't have a policy. See them all with `/config role_policy list`.
+config-role-policy-none = This server doesn't have any role policies, so members are transcribed whatever roles they have.
+config-role-policy-list-title = Role policies
+config-role-policy-list-allowed = Allowed: { $role }
+config-role-policy-list-allowed-and-above = Allowed: { $role } and every role above it
+config-role-policy-list-denied = Never transcribed: { $role }
+
+## config - mirror commands
+config_mirror = mirror
+    .description = Also send a voice chat's transcripts to other channels, ie a moderation archive.
+config_mirror_add = add
+    .description = Also send this voice chat's transcripts to a channel, in its own style.
+    .voice_channel = voice_channel
+    .voice_channel-description = Voice chat to mirror the transcripts of
+    .channel = channel
+    .channel-description = Channel to send them to
+    .style = style
+    .style-description = How they look in that channel. Defaults to Webhook
+    .style-choice-Webhook = Webhook
+    .style-choice-Compact = Compact
+    .style-choice-Embed = Embed
+config_mirror_remove = remove
+    .description = Stop sending this voice chat's transcripts to a channel.
+    .voice_channel = voice_channel
+    .voice_channel-description = Voice chat being mirrored
+    .channel = channel
+    .channel-description = Channel to stop sending its transcripts to
+config_mirror_list = list
+    .description = List this server's transcript mirrors.
+
+# { $voiceChannel } and { $channel } mention the channels.
+config-mirror-added = Transcripts of { $voiceChannel } will also be sent to { $channel }. If sending them there fails, the voice chat's own transcripts are unaffected. Voice chats that are already being transcribed will pick it up within a few minutes.
+config-mirror-full = This server already has { $maxMirrors } mirrors, which is the most it can have. Remove one with `/config mirror remove` first.
+config-mirror-no-webhook = I couldn't set up a webhook in { $channel }. Please give me the Manage Webhooks permission there, and try again.
+config-mirror-removed = Transcripts of { $voiceChannel } will no longer be sent to { $channel }.
+config-mirror-not-found = Transcripts of { $voiceChannel } aren't being sent to { $channel }. See all mirrors with `/config mirror list`.
+config-mirror-none = This server doesn't have any transcript mirrors.
+config-mirror-list-title = Transcript mirrors
+# { $style } is Webhook, Compact, or Embed.
+config-mirror-list-entry = { $voiceChannel } → { $channel } ({ $style })
+
 ## Help menu translation strings
 
 command-not-found = No command with name `{ $commandName }` found.
@@ -241,14 +881,21 @@ cmds_ping = ping
     .description = Get the bot latency.
 
 # This message is shown when the user requests latency information.
-# Note: the numbers here will be formatted according to the language set for the context.
+# Each latency is either latency-value or latency-unavailable.
 latency-description =
-    WebSocket latency: { $wsLatencyMs }ms ({ $wsLatencyNs }ns)
-    HTTP latency: { $httpLatencyMs }ms ({ $httpLatencyNs }ns)
-    Database latency: { $pgLatencyMs }ms ({ $pgLatencyNs }ns)
-    
-    Note: if any latency is equal to 0ms, it means that specific latency could not be calculated right now.
-    Try again later.
+    WebSocket latency: { $wsLatency }
+    HTTP latency: { $httpLatency }
+    Database latency: { $pgLatency }
+    Redis latency: { $redisLatency }
+    STT latency: { $sttLatency }
+# One latency in the latency description.
+# Note: the numbers here will be formatted according to the language set for the context.
+latency-value = { $ms }ms ({ $ns }ns)
+# Shown in the latency description instead of a latency that couldn't be measured right now.
+latency-unavailable = unavailable, try again later
+
+# This message is appended to the latency description, summarizing recent WebSocket latency of the current shard.
+latency-ws-history = WebSocket latency over the last { $samples } samples: average { $avgMs }ms, min { $minMs }ms, max { $maxMs }ms, jitter { $jitterMs }ms
 
 ## data_storage command
 # This and all attributes show up exclusively in the slash command picker when `data_storage` is selected.
@@ -269,15 +916,66 @@ data-storage-embed-description =
     If you are opted in, and you decide later to opt out, your data is still stored, but you can request deletion of your voice data by running `{ $contextPrefix }delete_all_data`. However, it is impossible to delete your message data. This is because we do not store a link of what user sent what message.
     Your data is stored on servers that are locked down tightly. It would be extremely difficult for anyone attempting to gain access to successfully do so.
     
+    Separately, Scripty keeps how long you speak for in each server for a year, so you can see it with `/analytics me`. Server managers can see yours too. This is on unless you opt out, and opting out deletes what was kept.
+    
     You can toggle your choices using the below buttons.
 data-storage-toggle-audio-btn = Toggle Audio Storage
 data-storage-toggle-msgs-btn = Toggle Message Storage
+data-storage-toggle-analytics-btn = Toggle Talk Time Analytics
 data-storage-opted-in-audio = You are now opted into storing your audio for model training.
 data-storage-opted-out-audio = You are now opted out of storing your audio for model training.
 data-storage-opted-in-msgs = You are now opted into storing your messages for scorer training.
 data-storage-opted-out-msgs = You are now opted out of storing your messages for scorer training.
+data-storage-opted-in-analytics = How long you speak for will now be kept for analytics.
+data-storage-opted-out-analytics = You are now opted out of talk time analytics, and what was kept has been deleted.
 data-storage-command-timed-out = Timed out. Rerun this command if you still want to manage settings.
 
+## automation commands
+cmds_automation = automation
+    .description = Make things happen when someone says a phrase in a voice chat Scripty is transcribing.
+cmds_automation_add = add
+    .description = Do something whenever a phrase is said, ie ping @Raiders when someone says "start the raid".
+    .phrase = phrase
+    .phrase-description = What has to be said, like "start the raid"
+    .action = action
+    .action-description = What happens when it's said
+    .action-choice-Message = Post a message
+    .action-choice-PingRole = Ping a role
+    .action-choice-Webhook = Fire the server's webhook
+    .message = message
+    .message-description = Message to post. Sent after the ping, or along to the webhook, if set
+    .role = role
+    .role-description = Role to ping, for Ping a role
+cmds_automation_remove = remove
+    .description = Stop doing something when a phrase is said.
+    .id = id
+    .id-description = ID of the automation to remove, from the list command
+cmds_automation_list = list
+    .description = List this server's automations.
+
+# What an automation does, shown in the success message and the list. { $role } mentions the role.
+automation-action-message = post a message
+automation-action-ping-role = ping { $role }
+automation-action-webhook = fire this server's webhook
+# { $id } is the automation's ID, for removing it later. { $action } is one of the automation-action-* strings.
+automation-add-success = Automation { $id } added: when someone says "{ $phrase }", I'll { $action }. Voice chats that are already being transcribed will pick it up within a few minutes.
+automation-add-full = This server already has { $maxAutomations } automations, which is the most it can have. Remove one with `/automation remove` first.
+automation-add-invalid-phrase = Phrases have to be between { $minLength } and { $maxLength } characters long.
+automation-add-message-too-long = Messages can be at most { $maxLength } characters long.
+automation-add-needs-message = Set `message` to what should be posted.
+automation-add-needs-role = Set `role` to who should be pinged.
+automation-add-no-webhook = This server doesn't have a webhook to fire. Set one with `/config webhook_url` first.
+automation-removed = Automation { $id } removed.
+automation-not-found = This server doesn't have an automation with ID { $id }. See them all with `/automation list`.
+automation-none = This server doesn't have any automations. Add one with `/automation add`.
+automation-list-title = Automations
+automation-list-field-name = { $id }: "{ $phrase }"
+# { $action } is one of the automation-action-* strings.
+automation-list-field-value = Will { $action }.
+automation-list-field-value-message = Will { $action }: { $message }
+# Posted in the transcript channel by a Ping a role automation without a message of its own. { $role } mentions the role, { $speaker } is who said it.
+automation-ping = { $role } { $speaker } said "{ $phrase }".
+
 ## automod root command
 # This and all attributes show up exclusively in the slash command picker when `automod` is selected.
 cmds_automod = automod
@@ -315,6 +1013,7 @@ cmds_add_rule = add_rule
     .action-choice-DeleteAndLog = Delete and log
     .action-choice-DeleteLogAndKick = Delete, log, and remove user from voice
     .action-choice-DeleteLogAndSilence = Delete, log, and mute user
+    .action-choice-DeleteLogAndTimeout = Delete, log, and time out user
 automod-add-rule-embed-success-title = Rule { $ruleId } added!
 automod-add-rule-embed-success-description = { $rulesLeft } rules left out of { $maxRules }. { $extraDetails }
 automod-add-rule-embed-extra-details-free-limit = Free servers are limited to 25 regular rules. If you'd like to increase this limit, check out our Premium over at https://scripty.org/premium.
@@ -361,6 +1060,120 @@ cmds_vote_reminder = vote_reminder
 vote-reminders-enabled = Vote reminders enabled.
 vote-reminders-disabled = Vote reminders disabled.
 
+## reading language command
+cmds_reading_language = reading_language
+    .description = Set the language you read transcripts in, to get a button that translates them.
+    .language = language
+    .language-description = Language code, like de or pt-BR. Leave empty to stop translating.
+reading-language-set = Your reading language is now `{ $language }`. While you're in a voice chat being transcribed, transcripts will get a Translate button within a few minutes: press it to read that line in your language.
+reading-language-cleared = Your reading language has been cleared. The Translate button on transcripts will use the language you use Scripty in instead.
+# { $language } is what the user typed.
+reading-language-invalid = `{ $language }` isn't a language code. Use a code like `de`, `fr`, or `pt-BR`.
+reading-language-unavailable = Translating transcripts isn't available on this instance of Scripty.
+# Label of the button on transcript messages. Shown to everyone in the channel, in the server's language.
+transcript-translate-button = Translate
+transcript-translate-failed = Sorry, that transcript couldn't be translated right now. Try again later.
+
+## minutes command
+cmds_minutes = minutes
+    .description = Get meeting minutes of the voice chat being transcribed, or the one that just ended.
+minutes-title = Meeting minutes
+# { $startedAt } is when the voice chat started being transcribed, { $duration } how long it's been, ie 1h 5m 12s.
+minutes-description-ongoing = Started { $startedAt }, and still going after { $duration }.
+minutes-description-ended = Started { $startedAt }, and lasted { $duration }.
+minutes-attendees = Attendees
+minutes-decisions = Key decisions
+minutes-action-items = Action items
+# Lines members pinned by reacting to them with the server's highlight emoji.
+minutes-highlights = Highlights
+minutes-none = None found.
+# Shown instead of who's doing an action item, when nobody took it on.
+minutes-unassigned = Unassigned
+# Ends a list that was too long to show in full. The attached file has all of it.
+minutes-more = …and { $count } more, in the attached file.
+minutes-footer = Decisions and action items are picked out of what was said, in English only. Check them before sharing.
+minutes-no-session = There's nothing to take minutes of. Minutes are available while Scripty is transcribing a voice chat, and for an hour after it ends.
+
+## subscribe commands
+cmds_subscribe = subscribe
+    .description = Get a voice chat's transcripts DMed to you when its sessions end, for when you can't make it.
+    .voice_channel = voice_channel
+    .voice_channel-description = Voice chat to get the transcripts of
+    .summary_only = summary_only
+    .summary_only-description = Only get the meeting minutes, not the whole transcript. Defaults to false.
+cmds_unsubscribe = unsubscribe
+    .description = Stop getting a voice chat's transcripts DMed to you.
+    .voice_channel = voice_channel
+    .voice_channel-description = Voice chat to stop getting the transcripts of
+
+# { $voiceChannel } mentions the voice chat.
+subscribe-success = When a session in { $voiceChannel } ends, its transcript will be DMed to you. Make sure you can get DMs from members of this server.
+subscribe-summary-success = When a session in { $voiceChannel } ends, its meeting minutes will be DMed to you. Make sure you can get DMs from members of this server.
+subscribe-not-allowed = This server doesn't let members subscribe to transcripts. A server manager can allow it with `/config subscriptions`.
+subscribe-no-access = You can only subscribe to voice chats you can see.
+subscribe-full = That voice chat already has { $maxSubscribers } subscribers, which is the most it can have.
+unsubscribe-success = Transcripts of { $voiceChannel } will no longer be DMed to you.
+unsubscribe-not-subscribed = You aren't subscribed to { $voiceChannel }.
+# DMed with the transcript attached. { $guildName } is the server's name.
+subscription-transcript-message = The transcript of <#{ $voiceChannelId }> in **{ $guildName }** is attached. Stop getting these with `/unsubscribe` in that server.
+subscription-summary-message = The meeting minutes of <#{ $voiceChannelId }> in **{ $guildName }** are attached. Decisions and action items are picked out of what was said, in English only. Stop getting these with `/unsubscribe` in that server.
+
+## transcript hold queue
+# Buttons on transcripts held by `/config hold_queue`, in the review channel.
+hold-queue-approve-button = Approve
+hold-queue-reject-button = Reject
+# Footer of a held transcript. { $minutes } is how long until it's posted anyway.
+hold-queue-review-auto-approve = Posted automatically in { $minutes } minutes, unless it's rejected.
+hold-queue-review-waiting = Only posted once approved. Dropped if nobody reviews it within { $hours } hours.
+# Replaces the buttons once a held transcript is decided on. { $reviewer } mentions who decided.
+hold-queue-approved = Approved by { $reviewer }.
+hold-queue-rejected = Rejected by { $reviewer }, so it was never posted.
+hold-queue-post-failed = Approved by { $reviewer }, but it couldn't be posted. Check I can still manage webhooks in the transcript channel.
+hold-queue-auto-approved = Posted automatically, as nobody rejected it in time.
+hold-queue-auto-post-failed = Nobody rejected this in time, but it couldn't be posted. Check I can still manage webhooks in the transcript channel.
+hold-queue-expired = Dropped, as nobody reviewed it within { $hours } hours.
+# Only shown to whoever pressed the button.
+hold-queue-not-allowed = You need the Manage Messages permission in this channel to review transcripts.
+hold-queue-already-reviewed = This transcript has already been reviewed.
+
+## search command
+cmds_search = search
+    .description = Search this server's stored transcripts.
+    .query = query
+    .query-description = Words to look for. Put phrases in "quotes", and -exclude words.
+    .channel = channel
+    .channel-description = Only search transcripts of this voice chat.
+    .user = user
+    .user-description = Only search what this user said.
+    .range = range
+    .range-description = Only search transcripts from the past day, week, month or year.
+    .range-choice-Day = Past day
+    .range-choice-Week = Past week
+    .range-choice-Month = Past month
+    .range-choice-Year = Past year
+# { $query } is what the user searched for.
+search-title = Transcripts matching "{ $query }"
+search-results-found = { $count ->
+    [one] Found 1 match.
+   *[other] Found { $count } matches.
+}
+# { $max } is the most matches shown for one search, shown instead when there were more.
+search-results-limited = Found { $max } or more matches, so only the newest { $max } are shown. Try a narrower search to see older ones.
+# Below each match. { $timestamp } is a Unix timestamp, { $voiceChannel } the ID of the voice chat it was said in, and { $link } a link to the transcript message.
+search-result-footer = <t:{ $timestamp }:f> in <#{ $voiceChannel }> · [Jump to transcript]({ $link })
+search-no-results = No transcripts matched your search. Only transcripts stored for the transcript API, set up with `/config api_key`, can be searched, and only those of voice chats you can see.
+
+## correct transcript context menu
+correct-not-transcript = That isn't a transcript, so it can't be corrected.
+correct-not-allowed = Only the person who said this, or someone who can manage messages, can correct it.
+correct-modal-title = Correct transcript
+correct-modal-label = What was actually said
+correct-unchanged = Nothing was changed, so the transcript was left as it was.
+correct-failed = Couldn't edit the transcript. It may have been deleted.
+correct-success = Corrected the transcript. If the speaker opted in to audio storage, the correction will help improve Scripty's transcriptions.
+# Added after a transcript once it's been corrected, in italics and brackets.
+correct-marker = corrected
+
 ## blocked entities description
 
 blocked-entity-no-reason-given = No reason was given for the block.
@@ -368,6 +1181,25 @@ blocked-entity-reason-given = Reason given for the block: { $reason }.
 blocked-entity-guild = This guild is blocked from using Scripty. { $reason } You may attempt to appeal this block in the support server: { $supportServerInvite }.
 blocked-entity-user = You are blocked from using Scripty. { $reason } You may attempt to appeal this block in the support server: { $supportServerInvite }.
 
+## DM support
+# Title of the message sent when a user can't open a ticket, for any of the reasons below.
+dm-support-refused-title = Can't open a ticket
+# Sent when a user blocked from DM support tries to open a ticket. $reason is one of the blocked-entity-*-reason-given strings above.
+dm-support-blocked = Sorry, you can't open a support ticket at the moment. { $reason } If you think this is a mistake, you can ask about it in the support server.
+# $expiry is a timestamp that Discord shows in the user's own timezone.
+dm-support-blocked-until = Sorry, you can't open a support ticket until { $expiry }. { $reason } If you think this is a mistake, you can ask about it in the support server.
+# $expiry is a relative timestamp, ie "in 5 minutes".
+dm-support-cooldown = Your last support ticket was closed very recently, so you can open a new one { $expiry }. If it's urgent, you can also ask in the support server.
+dm-support-open-limit = You already have { $max } open support tickets, which is the most we allow. Please wait for them to be closed, or ask in the support server.
+# Sent instead of opening a ticket, if the user's first message looks like a common question.
+dm-support-faq-title = This might help
+dm-support-faq-footer = If this didn't answer your question, press the button below to talk to a human.
+dm-support-faq-talk-to-human = Talk to a human
+# Answers to common questions, picked by the patterns in the bot's config.
+dm-support-faq-setup = To get started, run `/setup` in your server and follow the steps. Once that's done, join a voice channel and run `/join` to start transcribing.
+dm-support-faq-privacy = Scripty doesn't store any audio or transcripts unless you choose to let it. You can see and change what's stored about you with `/data_storage`, and delete everything with `/delete_all_data`.
+dm-support-faq-premium = You can see what Premium includes and manage your subscription with `/premium`. For billing questions, including refunds, please press the button below so a human can help.
+
 ## voice connection errors
 
 voice-connection-error-internal-lib-error = library internal error
@@ -396,6 +1228,9 @@ general-error-command-process-description = ```
     { $errorFmt }
     ```
     This has been automatically reported. Please do not attempt to repeatedly use this command.
+# This is shown instead of the raw error when an unexpected error happens. $errorId is a short code like 3F9A0C1E.
+general-error-command-process-reference = Something went wrong. This has been automatically reported, please do not attempt to repeatedly use this command.
+    If you need help, give the support server this reference: `{ $errorId }`
 
 general-error-invalid-args-title = Invalid arguments while parsing { $command }.
 general-error-invalid-args-description = Failed to parse `{ $input }` because `{ $error }`
@@ -419,6 +1254,42 @@ general-error-user-missing-perms-description-not-owner = Not an owner of this bo
 general-error-command-check-failed-title = A precondition for { $command } failed.
 general-error-command-check-failed-description-no-reason = no reason provided
 
+## voice commands
+# This message is posted in the transcript channel when someone says "Scripty stop". { $speaker } is their display name.
+voice-command-stopped = { $speaker } stopped transcription. Say "Scripty start" to start it again.
+# This message is posted in the transcript channel when someone says "Scripty start".
+voice-command-started = { $speaker } started transcription again.
+# This message is posted in the transcript channel when someone says "Scripty leave".
+voice-command-leaving = { $speaker } asked Scripty to leave the voice chat.
+
+## voice sessions
+# Posted in the transcript channel when Scripty rejoins a voice chat it was transcribing before it restarted.
+voice-session-resumed = I restarted, but I'm back and transcribing again. Anything said while I was gone was not transcribed.
+# Posted in the transcript channel of every voice chat Scripty is in when it shuts down to restart.
+voice-session-restarting = I'm restarting, so this session has ended for now. I'll rejoin automatically once I'm back.
+
+## join and leave notices
+# Posted in the transcript, and added to the transcript file, by `/config join_leave_notices`. { $name } is the member's display name.
+join-leave-joined = — { $name } joined the voice chat
+join-leave-left = — { $name } left the voice chat
+# Used instead when the server has anonymous speakers turned on, so nobody is named.
+join-leave-anonymous-joined = — Someone joined the voice chat
+join-leave-anonymous-left = — Someone left the voice chat
+
+## scheduled events
+# Sent in the transcript channel or thread when an event starts being transcribed by `/config scheduled_events`.
+scheduled-event-started = Transcribing **{ $eventName }**. I'll leave when the event ends.
+# { $error } is the error message, which isn't translated.
+scheduled-event-join-failed = I couldn't join **{ $eventName }** to transcribe it: { $error }
+
+## tts relay
+# Read out in the voice chat by `/config tts_relay`. { $author } is the sender's display name, { $message } what they typed.
+tts-relay-message = { $author } says: { $message }
+
+## transcription post-processing
+# Words removed from transcripts by `/config filler_words`, separated by commas. Include only sounds people make while thinking, not real words, as they're removed wherever they come up.
+filler-words = um, uh, er, erm, hmm, mm, ah
+
 ## transcription info - verbose mode
 
 # This is shown as the number of transcriptions the algorithm has discovered.
@@ -438,10 +1309,19 @@ transcription-info-transcription-error =
     If possible, please contact the core devs in the support server: { $supportServerInvite }.
     Thanks!
 
+## transcription quota
+
+# This is sent in the transcription channel when the server has used most of its monthly transcription minutes.
+quota-warning = This server has used { $usedMinutes } of its { $limitMinutes } transcription minutes this month. Transcription will stop when they run out, until the start of next month. Get more minutes with Premium: <https://scripty.org/premium>
+# This is sent in the transcription channel when the server has used all of its monthly transcription minutes.
+quota-exhausted = This server has used all { $limitMinutes } of its transcription minutes this month, so I've stopped transcribing. They reset at the start of next month, or you can get more minutes with Premium: <https://scripty.org/premium>
+
 ## Data deletion command
 # This and all attributes show up exclusively in the slash command picker when `delete_all_data` is selected.
 cmds_delete_all_data = delete_all_data
     .description = Delete all your data.
+    .ban = ban
+    .ban-description = Also ban yourself from the bot, so you don't add yourself back by accident.
 
 delete-data-title = Delete data
 delete-data-description =
@@ -450,15 +1330,32 @@ delete-data-description =
     When we say "all of your data" we mean *all* of it. This includes your voice data, and your user in the database.
     This however, *does not* include any messages we may have stored from you if you opted into that. We cannot delete those messages, simply because we don't know what user sent what message.
     
-    If you would like to also be banned from using the bot after this action, that way you do not accidentally readd yourself, you can click the appropriate button below.
-    Note that doing so will require us to store your user ID to keep a record of banned users.
-    If at any point after this action you would like to be unbanned, you can contact the support server and ask for a manual unban.
+    If you would like to also be banned from using the bot after this action, that way you do not accidentally readd yourself, run this command again with the `ban` option set.
     
     Are you sure you want to delete all of your data?
-delete-data-confirm = Yes, delete all data
-delete-data-confirm-banned = Yes, delete all data and ban myself
-delete-data-cancel = No, cancel
+# Shown instead of delete-data-description when the user asked to be banned as well.
+delete-data-description-banned =
+    This will delete all of your data, and ban you from using the bot. This action is permanent, irreversible, and cannot be undone.
+    
+    When we say "all of your data" we mean *all* of it. This includes your voice data, and your user in the database.
+    This however, *does not* include any messages we may have stored from you if you opted into that. We cannot delete those messages, simply because we don't know what user sent what message.
+    
+    Being banned will require us to store your user ID to keep a record of banned users.
+    If at any point after this action you would like to be unbanned, you can contact the support server and ask for a manual unban.
+    
+    Are you sure you want to delete all of your data and ban yourself?
+delete-data-success-title = Data deleted
+delete-data-success-description = All of your data has been deleted.
+delete-data-success-banned-title = Data deleted and banned
+delete-data-success-banned-description = All of your data has been deleted, and you have been banned from using the bot. Contact the support server if you would like to be unbanned.
+delete-data-cancelled-title = Deletion cancelled
+delete-data-cancelled-description = Nothing was deleted.
 
 ## generic strings
+# Buttons shown under a prompt asking the user to confirm something that can't be undone.
+confirm-yes = Yes
+confirm-no = No
+# Shown above a confirmation prompt when the user didn't answer in time. Nothing was changed.
+confirm-timed-out = You didn't answer in time, so nothing was changed.
 # Message shown if a guild has not claimed their free trial of premium. Always appears on its own standalone line in the surrounding message.
-free-trial-upsell = We offer 3-day trials of Scripty Premium if you would like to try it out and see if it is right for you. Send the bot a DM to get started with a free trial.
+free-trial-upsell = We offer 7-day trials of Scripty Premium if you would like to try it out and see if it is right for you. Run `/premium trial` to get started with a free trial.
@@ -67,3 +67,6 @@
 
 # This is shown when the algorithm encounters an error
 
+
+## transcription post-processing
+filler-words = eh, em, mmm, hmm
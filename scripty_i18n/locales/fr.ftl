@@ -131,12 +131,9 @@ general-error-command-check-failed-description-no-reason = Aucune raison fournie
 # This message is shown when the user requests latency information.
 # Note: the numbers here will be formatted according to the language set for the context.
 latency-description =
-    Latence du WebSocket : { $wsLatencyMs }ms ({ $wsLatencyNs }ns)
-    Latence HTTP : { $httpLatencyMs }ms ({ $httpLatencyNs }ns)
-    Latence de la Base de Données : { $pgLatencyMs }ms ({ $pgLatencyNs }ns)
-
-    Note : Si une latence est égale à 0ms, cela indique qu'elle n'a pas pu être calculée maintenant.
-    Re-essayez plus tard.
+    Latence du WebSocket : { $wsLatency }
+    Latence HTTP : { $httpLatency }
+    Latence de la Base de Données : { $pgLatency }
 # data_storage command
 data-storage-toggle-audio-btn = Stockage Audio
 # data_storage command
@@ -524,3 +521,6 @@ context-menu-command-message =
     { "" }
     { $commandName } (sur un message)
     { "" }
+
+## transcription post-processing
+filler-words = euh, heu, bah, hum, hmm
@@ -187,12 +187,9 @@ cmds_automod = automod
 # This message is shown when the user requests latency information.
 # Note: the numbers here will be formatted according to the language set for the context.
 latency-description =
-    WebSocket latentie: { $wsLatencyMs } ms ({ $wsLatencyNs } ns)
-    HTTP latentie: { $httpLatencyMs } ms ({ $httpLatencyNs } ns)
-    Database latentie: { $pgLatencyMs } ms ({ $pgLatencyNs } ns)
-
-    Opmerking: als een latentie gelijk is aan 0 ms, betekent dit dat die specifieke latentie op dit moment niet berekend kan worden.
-    Probeer later opnieuw.
+    WebSocket latentie: { $wsLatency }
+    HTTP latentie: { $httpLatency }
+    Database latentie: { $pgLatency }
 # automod root command
 automod-root-response = Dit is het hoofdcommando, vanwege Discord-beperkingen doet het niets. Bekijk `{ $contextPrefix }help automod` voor meer informatie.
 # automod setup command
@@ -455,3 +452,6 @@ join-thread-title = Transcriptie vanaf { $timestamp }
 # join command
 # If the user specifies they would like to create a forum post, this is the contents of the initial message. { $timestamp } is the current timestamp, in ISO format, and { $authorMention } is the mention of the user who ran the command.
 join-forum-thread-content = { $authorMention } is begonnen met een transcriptie om { $timestamp }.
+
+## transcription post-processing
+filler-words = eh, ehm, uh, uhm, hmm
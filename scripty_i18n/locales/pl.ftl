@@ -246,12 +246,9 @@ join-forum-requires-tags = Kanał forum, który próbowałeś użyć, wymaga tag
 # This message is shown when the user requests latency information.
 # Note: the numbers here will be formatted according to the language set for the context.
 latency-description =
-    Opóźnienie WebSocket: { $wsLatencyMs } ms ({ $wsLatencyNs } ns)
-    Opóźnienie HTTP: { $httpLatencyMs } ms ({ $httpLatencyNs } ns)
-    Opóźnienie bazy danych: { $pgLatencyMs } ms ({ $pgLatencyNs } ns)
-
-    Uwaga: jeśli jakiekolwiek opóźnienie wynosi 0 ms, oznacza to, że obecnie nie można obliczyć tego konkretnego opóźnienia.
-    Spróbuj ponownie później.
+    Opóźnienie WebSocket: { $wsLatency }
+    Opóźnienie HTTP: { $httpLatency }
+    Opóźnienie bazy danych: { $pgLatency }
 # data_storage command
 data-storage-embed-title = Przechowywanie Danych
 # Leave command
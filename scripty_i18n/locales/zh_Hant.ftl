@@ -45,12 +45,9 @@ language-set-failure-title-db = 數據庫錯誤。
 # This message is shown when the user requests latency information.
 # Note: the numbers here will be formatted according to the language set for the context.
 latency-description =
-    WebSocket 延遲: { $wsLatencyMs }ms ({ $wsLatencyNs }ns)
-    網際網路 (HTTP) 延遲 { $httpLatencyMs }ms ({ $httpLatencyNs }ns)
-    數據庫延遲: { $pgLatencyMs }ms ({ $pgLatencyNs }ns)
-
-    注意：如果任何延遲等於 0ms，則表示當前無法計算該延遲。
-    請稍後再試。
+    WebSocket 延遲: { $wsLatency }
+    網際網路 (HTTP) 延遲 { $httpLatency }
+    數據庫延遲: { $pgLatency }
 data-storage-embed-title = 數據存儲
 data-storage-toggle-msgs-btn = 開啟或關閉信息存儲
 
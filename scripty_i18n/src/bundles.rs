@@ -17,36 +17,52 @@ pub fn get_all_bundle_languages() -> Vec<LanguageIdentifier> {
 	res
 }
 
-/// Get a bundle for a specific language by its identifier
-/// If the language is not available, returns English translations
+/// English variants used as the last resort, in order of preference.
+const ENGLISH_FALLBACKS: [&str; 4] = ["en", "en-US", "en-GB", "en-CA"];
+
+/// Get the languages to try, in order, when looking something up in `language`.
+///
+/// This is the language itself, then progressively less specific versions of it,
+/// then English: ie `pt-BR` → `pt` → `en`, or `zh-Hant-TW` → `zh-Hant` → `zh` → `en`.
+pub fn get_fallback_chain(language: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+	let mut chain = vec![language.clone()];
+	let mut push = |id: LanguageIdentifier| {
+		if !chain.contains(&id) {
+			chain.push(id);
+		}
+	};
+
+	push(LanguageIdentifier::from_parts(
+		language.language,
+		language.script,
+		None,
+		&[],
+	));
+	push(LanguageIdentifier::from_parts(
+		language.language,
+		None,
+		None,
+		&[],
+	));
+	for english in ENGLISH_FALLBACKS {
+		push(
+			LanguageIdentifier::from_str(english)
+				.expect("somehow english is not a valid language?"),
+		);
+	}
+
+	chain
+}
+
+/// Get a bundle for a specific language by its identifier.
+/// If the language is not available, returns the first available language in its fallback chain.
 pub fn get_bundle_for_language(
 	language: &LanguageIdentifier,
 ) -> Ref<'static, LanguageIdentifier, FluentBundle<FluentResource, IntlLangMemoizer>> {
-	get_bundle_for_language_no_fallback(language)
-		.or_else(|| {
-			get_i18n_store().get(
-				&LanguageIdentifier::from_str("en")
-					.expect("somehow english is not a valid language?"),
-			)
-		})
-		.or_else(|| {
-			get_i18n_store().get(
-				&LanguageIdentifier::from_str("en-US")
-					.expect("somehow english-us is not a valid language?"),
-			)
-		})
-		.or_else(|| {
-			get_i18n_store().get(
-				&LanguageIdentifier::from_str("en-GB")
-					.expect("somehow english-gb is not a valid language?"),
-			)
-		})
-		.or_else(|| {
-			get_i18n_store().get(
-				&LanguageIdentifier::from_str("en-CA")
-					.expect("somehow english-ca is not a valid language?"),
-			)
-		})
+	let i18n_store = get_i18n_store();
+	get_fallback_chain(language)
+		.iter()
+		.find_map(|lang| i18n_store.get(lang))
 		.expect("both selected language and some variant of english aren't available")
 }
 
@@ -131,9 +131,17 @@ pub async fn set_user_language(user_id: u64, language: &str) -> Result<(), Inval
 /// and if not in database, falling back to English (`en`).
 /// This is a guild-specific language, and is not the same as the user's language.
 pub async fn get_guild_language(guild_id: u64) -> LanguageIdentifier {
+	get_stored_guild_language(guild_id)
+		.await
+		.unwrap_or_else(|| LanguageIdentifier::from_str("en").expect("invalid language"))
+}
+
+/// Get a guild's language from the cache, falling back to a database query if not cached,
+/// and if not in database, returning None.
+pub async fn get_stored_guild_language(guild_id: u64) -> Option<LanguageIdentifier> {
 	let cache = get_cache();
 	if let Some(lang) = cache.get(&guild_id) {
-		return lang.value().clone();
+		return Some(lang.value().clone());
 	}
 
 	let db = scripty_db::get_db();
@@ -148,18 +156,17 @@ pub async fn get_guild_language(guild_id: u64) -> LanguageIdentifier {
 		e
 	})
 	.ok()
-	.flatten()
-	.map(|r| r.language)
-	.unwrap_or_else(|| "en".to_string());
+	.flatten()?
+	.language;
 	let lang = LanguageIdentifier::from_str(&guild_language).expect("invalid language");
 
 	cache.insert(guild_id, lang.clone());
-	lang
+	Some(lang)
 }
 
 /// Remove a guild's language from the cache.
 ///
-/// Not sure when this would be useful, but it's here just in case.
+/// Used when a guild's data is deleted, so it isn't still used afterwards.
 pub fn remove_guild_language(guild_id: u64) {
 	get_cache().remove(&guild_id);
 }
@@ -209,3 +216,45 @@ pub async fn get_resolved_language(user_id: u64, guild_id: Option<u64>) -> Langu
 		(None, None) => LanguageIdentifier::from_str("en").expect("invalid language"),
 	}
 }
+
+/// Get a resolved language for the current context, using the Discord client's locale when
+/// nothing has been stored, so first-time users see their own language straight away.
+///
+/// In order, this uses the first of:
+/// * the user's language, if they have one set
+/// * `locale`, the user's client language, if we have translations for it
+/// * the guild's language, if this is in a guild and it has one set
+/// * `guild_locale`, the guild's community language, if we have translations for it
+/// * English (`en`)
+pub async fn get_resolved_language_with_locale(
+	user_id: u64,
+	guild_id: Option<u64>,
+	locale: Option<&str>,
+	guild_locale: Option<&str>,
+) -> LanguageIdentifier {
+	if let Some(lang) = get_user_language(user_id).await {
+		return lang;
+	}
+	if let Some(lang) = locale.and_then(get_supported_language_for_locale) {
+		return lang;
+	}
+	if let Some(guild_id) = guild_id {
+		if let Some(lang) = get_stored_guild_language(guild_id).await {
+			return lang;
+		}
+	}
+	guild_locale
+		.and_then(get_supported_language_for_locale)
+		.unwrap_or_else(|| LanguageIdentifier::from_str("en").expect("invalid language"))
+}
+
+/// Find the translations we have that best match a Discord locale, ie `pt-BR` or `es-ES`.
+///
+/// Tries the exact locale, then just its language. Returns None if we have neither.
+pub fn get_supported_language_for_locale(locale: &str) -> Option<LanguageIdentifier> {
+	let lang_id = LanguageIdentifier::from_str(locale).ok()?;
+	let bare = LanguageIdentifier::from_parts(lang_id.language, None, None, &[]);
+	[lang_id, bare]
+		.into_iter()
+		.find(|lang| crate::get_bundle_for_language_no_fallback(lang).is_some())
+}
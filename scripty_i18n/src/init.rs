@@ -4,12 +4,46 @@ use dashmap::DashMap;
 use fluent::{bundle::FluentBundle, FluentResource};
 use unic_langid::LanguageIdentifier;
 
-use crate::{init_cache, set_i18n_store};
+use crate::{get_i18n_store, init_cache, set_i18n_store, store::FluentBundleMap};
 
 pub fn init_i18n() {
+	let (bundles, _) = load_bundles();
+	set_i18n_store(bundles);
+
+	init_cache();
+}
+
+/// Re-read every Fluent file from disk, and replace the loaded bundles with them.
+///
+/// Languages whose files were removed are unloaded. Files that fail to parse are loaded as far as
+/// possible, same as at startup, and their errors are returned so they can be fixed.
+pub fn reload_i18n() -> ReloadSummary {
+	let (bundles, errors) = load_bundles();
+	let store = get_i18n_store();
+
+	let languages: Vec<LanguageIdentifier> = bundles.iter().map(|b| b.key().clone()).collect();
+	store.retain(|lang_id, _| languages.contains(lang_id));
+	for (lang_id, bundle) in bundles {
+		store.insert(lang_id, bundle);
+	}
+	info!("reloaded {} language localizations", languages.len());
+
+	ReloadSummary { languages, errors }
+}
+
+/// The result of reloading translations.
+pub struct ReloadSummary {
+	/// Every language that is now loaded.
+	pub languages: Vec<LanguageIdentifier>,
+	/// Every problem encountered while loading, ie syntax errors.
+	pub errors:    Vec<String>,
+}
+
+fn load_bundles() -> (FluentBundleMap, Vec<String>) {
 	let cfg = scripty_config::get_config();
 
 	let bundles = DashMap::new();
+	let mut problems = Vec::new();
 	for i18n_file in
 		read_dir(&cfg.i18n_dir).expect("failed to read i18n dir: does it exist and is readable?")
 	{
@@ -50,12 +84,14 @@ pub fn init_i18n() {
 			Ok(Ok(r)) => r,
 			Err(e) => {
 				warn!(file=?path, %lang_id, "failed to read file to string: {}", e);
+				problems.push(format!("{}: failed to read: {}", lang_id, e));
 				continue;
 			}
 			Ok(Err((r, errs))) => {
 				warn!(file=?path, %lang_id, "failed to parse Fluent resource: {} errors", errs.len());
 				for err in errs {
 					warn!(file=?path, %lang_id, "failed to parse file as Fluent bundle: {:?}", err);
+					problems.push(format!("{}: {:?}", lang_id, err));
 				}
 				r
 			}
@@ -64,13 +100,13 @@ pub fn init_i18n() {
 		if let Err(errs) = bundle.add_resource(resource) {
 			for err in errs {
 				warn!(%lang_id, "failed to add Fluent resource: {:?}", err);
+				problems.push(format!("{}: {:?}", lang_id, err));
 			}
 		};
 		info!(%lang_id, "loaded lang successfully");
 		bundles.insert(lang_id, bundle);
 	}
 	info!("found {} language localizations", bundles.len());
-	set_i18n_store(bundles);
 
-	init_cache();
+	(bundles, problems)
 }
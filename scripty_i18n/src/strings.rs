@@ -3,11 +3,14 @@ use std::str::FromStr;
 use fluent::{FluentArgs, FluentError};
 use unic_langid::LanguageIdentifier;
 
-use crate::bundles::get_bundle_for_language;
+use crate::{bundles::get_fallback_chain, store::get_i18n_store};
 
-/// Given a language ID and a message ID, returns the formatted message in the given language,
-/// or fall back to English (only if `no_fallback` is false).
-/// If the message does not exist, returns None.
+/// Given a language ID and a message ID, returns the formatted message in the given language.
+///
+/// If the message, or the requested attribute of it, is missing in that language, it is looked up
+/// in each language of its fallback chain in turn (ie `pt-BR` → `pt` → `en`), unless `no_fallback`
+/// is true.
+/// If the message does not exist in any of them, returns None.
 ///
 /// If any errors are encountered during translation, the 2nd element of the returned tuple will contain the errors
 /// that happened. These are not fatal, and the message will still be translated.
@@ -18,29 +21,53 @@ pub fn get_formatted_message<'l>(
 	args: Option<&'l FluentArgs<'_>>,
 	no_fallback: bool,
 ) -> Option<(String, Vec<FluentError>)> {
-	let bundle_temp = get_bundle_for_language(language);
-	let en_bundle_temp = get_bundle_for_language(
-		&LanguageIdentifier::from_str("en").expect("english invalid identifier?"),
-	);
-	let bundle = bundle_temp.value();
-	let en_bundle = en_bundle_temp.value();
-	let message = bundle.get_message(message_id).or_else(|| {
-		if no_fallback {
-			None
-		} else {
-			en_bundle.get_message(message_id)
-		}
-	})?;
-	let message_pattern = if let Some(attribute) = attribute_id {
-		message.get_attribute(attribute)?.value()
+	let chain = if no_fallback {
+		vec![language.clone()]
 	} else {
-		message.value()?
+		get_fallback_chain(language)
 	};
-	let mut errors = Vec::new();
-	let res = bundle
-		.format_pattern(message_pattern, args, &mut errors)
-		.into_owned();
-	Some((res, errors))
+
+	let i18n_store = get_i18n_store();
+	for lang in chain {
+		let Some(bundle) = i18n_store.get(&lang) else {
+			continue;
+		};
+		let Some(message) = bundle.get_message(message_id) else {
+			continue;
+		};
+		// a translation can be missing just the attribute or value, so that falls through too
+		let message_pattern = match attribute_id {
+			Some(attribute) => message.get_attribute(attribute).map(|a| a.value()),
+			None => message.value(),
+		};
+		let Some(message_pattern) = message_pattern else {
+			continue;
+		};
+		let mut errors = Vec::new();
+		// format with the bundle the message came from, so it uses that language's formatting rules
+		let res = bundle
+			.format_pattern(message_pattern, args, &mut errors)
+			.into_owned();
+		return Some((res, errors));
+	}
+
+	None
+}
+
+/// Filler words, ie "um" and "uh", for speech in `language`, from its `filler-words` message.
+///
+/// Languages without their own list use the next one in their fallback chain, ending in English.
+pub fn get_filler_words(language: &str) -> Vec<String> {
+	let language = LanguageIdentifier::from_str(language).unwrap_or_default();
+	get_formatted_message(&language, "filler-words", None, None, false)
+		.map(|(words, _)| {
+			words
+				.split(',')
+				.map(|word| word.trim().to_lowercase())
+				.filter(|word| !word.is_empty())
+				.collect()
+		})
+		.unwrap_or_default()
 }
 
 /// Macro that expands to a valid call of get_formatted_message.
@@ -1,2 +1,37 @@
-#[allow(dead_code)]
-pub fn increment_command(_command_name: &str) {}
+//! Helpers to measure how long commands take to run, and whether they succeeded.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use once_cell::sync::OnceCell;
+
+/// Stores the time each command invocation started running.
+static COMMAND_START_TIME: OnceCell<DashMap<u64, Instant>> = OnceCell::new();
+
+/// Call this function in pre_command, with the ID of the invocation.
+pub fn measure_command_start(id: u64) {
+	COMMAND_START_TIME
+		.get_or_init(DashMap::new)
+		.insert(id, Instant::now());
+}
+
+/// Call this function once a command has finished, either in post_command or in the error handler.
+pub fn measure_command_end(id: u64, command_name: &str, success: bool) {
+	let Some((_, st)) = COMMAND_START_TIME.get_or_init(DashMap::new).remove(&id) else {
+		trace!(?id, "no command start time found");
+		return;
+	};
+
+	crate::get_metrics()
+		.command_duration
+		.with_label_values(&[command_name, if success { "success" } else { "failure" }])
+		.observe(st.elapsed().as_secs_f64());
+}
+
+/// Clear command start times older than ten minutes, to free up memory from commands that never finished.
+pub fn clear_command_start_times() {
+	const MAX_AGE: Duration = Duration::from_secs(600);
+	COMMAND_START_TIME
+		.get_or_init(DashMap::new)
+		.retain(|_, st| st.elapsed() < MAX_AGE);
+}
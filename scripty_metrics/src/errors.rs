@@ -0,0 +1,10 @@
+//! Helper to count errors across crates.
+
+/// Increment `errors_total` for the given crate and error kind.
+///
+/// Safe to call before metrics are initialized, in which case this does nothing.
+pub fn record_error(crate_name: &str, kind: &str) {
+	if let Some(metrics) = crate::METRICS.get() {
+		metrics.errors.with_label_values(&[crate_name, kind]).inc();
+	}
+}
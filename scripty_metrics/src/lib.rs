@@ -3,12 +3,17 @@ extern crate tracing;
 
 mod cmd_handler;
 mod cmd_latency;
+mod errors;
 mod get_metrics;
 mod metrics;
+mod profiling;
 mod rt_metrics;
 
+pub use cmd_handler::*;
 pub use cmd_latency::*;
+pub use errors::record_error;
 pub use get_metrics::get_formatted_metrics;
 use metrics::METRICS;
 pub use metrics::{get_metrics, Metrics};
+pub use profiling::{is_profiling_enabled, set_profiling_enabled};
 pub use rt_metrics::register_metrics;
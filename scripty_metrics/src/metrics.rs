@@ -6,8 +6,11 @@ use std::sync::Arc;
 use chrono::{NaiveDateTime, Utc};
 use once_cell::sync::OnceCell;
 use prometheus::{
+	Gauge,
+	GaugeVec,
 	Histogram,
 	HistogramOpts,
+	HistogramVec,
 	IntCounter,
 	IntCounterVec,
 	IntGauge,
@@ -164,15 +167,29 @@ pub struct Metrics {
 	pub ms_transcribed:           IntCounter,
 	pub audio_bytes_processed:    IntCounter,
 	pub total_events:             IntCounter,
-	// TODO: switch to Histogram
 	pub audio_tick_time:          Histogram,
 	pub audio_process_time:       Histogram,
+	pub stt_connect_time:         Histogram,
+	pub utterance_latency:        Histogram,
+	pub transcripts_too_short:    IntCounter,
 	pub total_commands:           IntCounter,
 	pub stt_server_fetch_success: IntCounter,
 	pub stt_server_fetch_failure: IntCounter,
 	pub commands:                 IntCounterVec,
+	pub command_duration:         HistogramVec,
 	pub runtime_metrics:          RuntimeMetricsVec,
 	pub latency:                  LatencyVec,
+	pub voice_connections:        IntGaugeVec,
+	pub stt_streams:              IntGaugeVec,
+	pub transcribed_users:        IntGaugeVec,
+	pub audio_packet_issues:      IntCounterVec,
+	pub errors:                   IntCounterVec,
+	pub shard_latency:            IntGaugeVec,
+	pub stt_worker_utilization:   GaugeVec,
+	pub stt_fleet_utilization:    Gauge,
+	pub bot_list_last_post:       IntGaugeVec,
+	pub guild_cleanups:           IntCounterVec,
+	pub orphans_reaped:           IntCounterVec,
 }
 
 impl Metrics {
@@ -232,6 +249,47 @@ impl Metrics {
 			.register(Box::new(audio_process_time.clone()))
 			.unwrap();
 
+		let stt_connect_time = Histogram::with_opts(
+			HistogramOpts::new(
+				"stt_connect_time",
+				"Time for an STT server to acknowledge a new stream",
+			)
+			// this is a network round trip, so buckets are on the order of milliseconds,
+			// up to the 5 second initialization timeout
+			.buckets(vec![
+				0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+			]),
+		)
+		.unwrap();
+		registry
+			.register(Box::new(stt_connect_time.clone()))
+			.unwrap();
+
+		let utterance_latency = Histogram::with_opts(
+			HistogramOpts::new(
+				"utterance_latency",
+				"Time from a user going silent to their transcript being sent",
+			)
+			// this includes the STT result and a Discord API call, so it ranges from
+			// tens of milliseconds up to the 30 second result timeout
+			.buckets(vec![
+				0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 5.0, 10.0, 20.0, 30.0,
+			]),
+		)
+		.unwrap();
+		registry
+			.register(Box::new(utterance_latency.clone()))
+			.unwrap();
+
+		let transcripts_too_short = IntCounter::new(
+			"transcripts_too_short",
+			"Transcripts dropped for being shorter than their guild's minimum length",
+		)
+		.unwrap();
+		registry
+			.register(Box::new(transcripts_too_short.clone()))
+			.unwrap();
+
 		let total_commands_used = IntCounter::new(
 			"total_commands_used",
 			"Overall total of commands used across the entire bot",
@@ -248,6 +306,22 @@ impl Metrics {
 		.unwrap();
 		registry.register(Box::new(commands_used.clone())).unwrap();
 
+		let command_duration = HistogramVec::new(
+			HistogramOpts::new(
+				"command_duration",
+				"Time taken to run each command, and whether it succeeded",
+			)
+			// most commands are a single Discord API call, but some (ie join) do quite a bit more
+			.buckets(vec![
+				0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+			]),
+			&["command_name", "status"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(command_duration.clone()))
+			.unwrap();
+
 		let runtime_metrics_stats = IntGaugeVec::new(
 			Opts::new("runtime_metrics", "Tokio runtime metrics"),
 			&["runtime_metrics"],
@@ -288,6 +362,117 @@ impl Metrics {
 			.register(Box::new(stt_server_fetch_failure.clone()))
 			.unwrap();
 
+		let voice_connections = IntGaugeVec::new(
+			Opts::new("voice_connections", "Active voice connections per shard"),
+			&["shard"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(voice_connections.clone()))
+			.unwrap();
+
+		let stt_streams = IntGaugeVec::new(
+			Opts::new("stt_streams", "Open STT streams per shard"),
+			&["shard"],
+		)
+		.unwrap();
+		registry.register(Box::new(stt_streams.clone())).unwrap();
+
+		let transcribed_users = IntGaugeVec::new(
+			Opts::new(
+				"transcribed_users",
+				"Users currently being transcribed per shard",
+			),
+			&["shard"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(transcribed_users.clone()))
+			.unwrap();
+
+		let audio_packet_issues = IntCounterVec::new(
+			Opts::new(
+				"audio_packet_issues",
+				"Dropped packets, decode errors, buffer underruns and unknown SSRCs per shard",
+			),
+			&["shard", "issue"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(audio_packet_issues.clone()))
+			.unwrap();
+
+		let errors = IntCounterVec::new(
+			Opts::new("errors_total", "Errors raised, by crate and kind"),
+			&["crate", "kind"],
+		)
+		.unwrap();
+		registry.register(Box::new(errors.clone())).unwrap();
+
+		let shard_latency = IntGaugeVec::new(
+			Opts::new(
+				"shard_latency",
+				"Recent WebSocket latency per shard, in nanoseconds",
+			),
+			&["shard", "stat"],
+		)
+		.unwrap();
+		registry.register(Box::new(shard_latency.clone())).unwrap();
+
+		let stt_worker_utilization = GaugeVec::new(
+			Opts::new(
+				"stt_worker_utilization",
+				"Utilization last reported by each STT worker, and the maximum before it is \
+				 overloaded",
+			),
+			&["worker", "stat"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(stt_worker_utilization.clone()))
+			.unwrap();
+
+		let stt_fleet_utilization = Gauge::new(
+			"stt_fleet_utilization",
+			"Total utilization of all STT workers as a fraction of their total capacity",
+		)
+		.unwrap();
+		registry
+			.register(Box::new(stt_fleet_utilization.clone()))
+			.unwrap();
+
+		let bot_list_last_post = IntGaugeVec::new(
+			Opts::new(
+				"bot_list_last_post",
+				"Unix timestamp of the last successful stats post to each bot list",
+			),
+			&["site"],
+		)
+		.unwrap();
+		registry
+			.register(Box::new(bot_list_last_post.clone()))
+			.unwrap();
+
+		let guild_cleanups = IntCounterVec::new(
+			Opts::new(
+				"guild_cleanups",
+				"Deletions of a guild's data after leaving it, by outcome",
+			),
+			&["outcome"],
+		)
+		.unwrap();
+		registry.register(Box::new(guild_cleanups.clone())).unwrap();
+
+		let orphans_reaped = IntCounterVec::new(
+			Opts::new(
+				"orphans_reaped",
+				"Leftover rows and cache keys removed by the orphan reaper, by kind",
+			),
+			&["kind"],
+		)
+		.unwrap();
+		registry.register(Box::new(orphans_reaped.clone())).unwrap();
+
 		let up = IntCounter::new("up", "Always 1").unwrap();
 		up.inc();
 		registry.register(Box::new(up)).unwrap();
@@ -304,12 +489,27 @@ impl Metrics {
 			total_events: events,
 			audio_tick_time,
 			audio_process_time,
+			stt_connect_time,
+			utterance_latency,
+			transcripts_too_short,
 			total_commands: total_commands_used,
 			commands: commands_used,
+			command_duration,
 			runtime_metrics: runtime_metrics_static,
 			latency: latency_static,
 			stt_server_fetch_success,
 			stt_server_fetch_failure,
+			voice_connections,
+			stt_streams,
+			transcribed_users,
+			audio_packet_issues,
+			errors,
+			shard_latency,
+			stt_worker_utilization,
+			stt_fleet_utilization,
+			bot_list_last_post,
+			guild_cleanups,
+			orphans_reaped,
 		})
 	}
 }
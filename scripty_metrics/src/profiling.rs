@@ -0,0 +1,18 @@
+//! Runtime toggle for the profiling endpoints.
+//!
+//! The endpoints themselves are only compiled in with the `profiling` feature,
+//! but even then they stay disabled until an owner turns them on.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PROFILING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the profiling endpoints.
+pub fn set_profiling_enabled(enabled: bool) {
+	PROFILING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the profiling endpoints are currently enabled.
+pub fn is_profiling_enabled() -> bool {
+	PROFILING_ENABLED.load(Ordering::Relaxed)
+}
@@ -1,5 +1,6 @@
 use std::{fmt::Formatter, num::NonZeroU64};
 
+pub use scripty_config::PremiumTierLimits;
 use time::OffsetDateTime;
 
 #[macro_use]
@@ -11,6 +12,32 @@ pub struct PremiumUserInfo {
 	pub tier:       PremiumTierList,
 }
 
+/// Where a guild's premium comes from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PremiumSource {
+	/// The guild has no premium.
+	None,
+	/// Claimed from a subscription bought through the dashboard.
+	Subscription,
+	/// Claimed by a user whose Patreon pledge gives them premium.
+	Patreon,
+	/// The guild's `/premium trial`.
+	Trial,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GuildPremiumStatus {
+	pub tier:           PremiumTierList,
+	pub source:         PremiumSource,
+	/// When premium ends, unless it's renewed first. None if it renews automatically.
+	pub expiration:     Option<OffsetDateTime>,
+	/// Milliseconds of audio transcribed this month.
+	pub ms_transcribed: u64,
+}
+
+/// Tier guilds get during their `/premium trial`.
+pub const TRIAL_TIER: PremiumTierList = PremiumTierList::Tier1;
+
 #[repr(i16)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd)]
 pub enum PremiumTierList {
@@ -45,6 +72,46 @@ impl Default for PremiumTierList {
 	}
 }
 
+impl PremiumTierList {
+	/// How many servers, and voice sessions at once, one subscription of this tier gets.
+	///
+	/// Set per tier with `premium_tiers` in the config. Free guilds have no subscription,
+	/// so they get nothing.
+	pub fn limits(self) -> PremiumTierLimits {
+		// the list in the config starts at tier 1
+		let configured = (self as usize)
+			.checked_sub(1)
+			.and_then(|idx| scripty_config::get_config().premium_tiers.get(idx).copied());
+
+		configured.unwrap_or_else(|| {
+			let default = match self {
+				Self::None => 0,
+				Self::Tier1 | Self::Tier2 => 1,
+				Self::Tier3 | Self::Tier4 => 3,
+				Self::Tier5 | Self::Tier6 => 5,
+			};
+			PremiumTierLimits {
+				max_guilds:   default,
+				max_sessions: default,
+			}
+		})
+	}
+}
+
+impl PremiumTierList {
+	/// How many milliseconds of audio a guild of this tier can transcribe each month.
+	/// None if unlimited.
+	///
+	/// Set with `transcription_quota.monthly_minutes` in the config.
+	pub fn monthly_quota_ms(self) -> Option<u64> {
+		scripty_config::get_config()
+			.transcription_quota
+			.monthly_minutes
+			.get(self as usize)
+			.map(|minutes| minutes * 60 * 1000)
+	}
+}
+
 impl std::fmt::Display for PremiumTierList {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		match self {
@@ -116,22 +183,75 @@ pub async fn get_user(user_id: NonZeroU64) -> Option<PremiumUserInfo> {
 }
 
 pub async fn get_guild(guild_id: u64) -> Option<PremiumTierList> {
+	match get_guild_status(guild_id).await {
+		Ok(status) => Some(status.tier),
+		Err(e) => {
+			error!("Error fetching guild info: {}", e);
+			None
+		}
+	}
+}
+
+/// Everything about `guild_id`'s premium: its tier, where that comes from, and its usage.
+///
+/// Guilds that haven't agreed to the ToS have no premium.
+pub async fn get_guild_status(guild_id: u64) -> Result<GuildPremiumStatus, sqlx::Error> {
 	// from here, fetch the user that corresponds to the guild table's premium_owner_id column
 	let db = scripty_db::get_db();
 	let r = sqlx::query!(
-		"SELECT premium_level FROM users INNER JOIN guilds g on users.user_id = \
-		 g.premium_owner_id WHERE guild_id = $1",
+		"SELECT u.premium_level AS \"premium_level?\", u.premium_expiry, u.is_trialing AS \
+		 \"is_trialing?\", g.trial_expires_at, g.ms_transcribed, EXISTS(SELECT 1 FROM \
+		 patreon_members p WHERE p.user_id = g.premium_owner_id AND p.premium_level > 0) AS \
+		 \"patreon!\" FROM guilds g LEFT JOIN users u ON u.user_id = g.premium_owner_id WHERE \
+		 g.guild_id = $1",
 		guild_id as i64
 	)
 	.fetch_optional(db)
-	.await;
+	.await?;
 
-	match r {
-		Ok(Some(r)) => Some(PremiumTierList::from(r.premium_level)),
-		Ok(None) => Some(PremiumTierList::None),
-		Err(e) => {
-			error!("Error fetching guild info: {}", e);
+	let Some(r) = r else {
+		return Ok(GuildPremiumStatus {
+			tier:           PremiumTierList::None,
+			source:         PremiumSource::None,
+			expiration:     None,
+			ms_transcribed: 0,
+		});
+	};
+	let ms_transcribed = r.ms_transcribed as u64;
+
+	let tier = PremiumTierList::from(r.premium_level.unwrap_or(0));
+	if tier != PremiumTierList::None {
+		let source = if r.patreon {
+			PremiumSource::Patreon
+		} else {
+			PremiumSource::Subscription
+		};
+		// same as get_user
+		let expiration = if r.is_trialing.unwrap_or(false) {
 			None
-		}
+		} else {
+			r.premium_expiry
+		};
+		return Ok(GuildPremiumStatus {
+			tier,
+			source,
+			expiration,
+			ms_transcribed,
+		});
+	}
+
+	match r.trial_expires_at.map(|t| t.assume_utc()) {
+		Some(trial_expiry) if trial_expiry > OffsetDateTime::now_utc() => Ok(GuildPremiumStatus {
+			tier: TRIAL_TIER,
+			source: PremiumSource::Trial,
+			expiration: Some(trial_expiry),
+			ms_transcribed,
+		}),
+		_ => Ok(GuildPremiumStatus {
+			tier: PremiumTierList::None,
+			source: PremiumSource::None,
+			expiration: None,
+			ms_transcribed,
+		}),
 	}
 }
@@ -1,12 +1,14 @@
 //! General wrapper around Redis.
 
 mod init;
+mod pubsub;
 mod transaction;
 
 use deadpool_redis::Pool;
 pub use deadpool_redis::PoolError;
 pub use init::init_redis;
 use once_cell::sync::OnceCell;
+pub use pubsub::{publish, subscribe};
 pub use redis;
 pub use transaction::{run_transaction, TransactionError};
 
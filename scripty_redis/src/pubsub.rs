@@ -0,0 +1,24 @@
+//! Pub/sub helpers for Redis.
+
+use redis::aio::PubSub;
+
+use crate::{run_transaction, TransactionError};
+
+/// Open a new connection, subscribed to `channel`.
+///
+/// Subscribed connections can't run anything else, so this doesn't come from the pool:
+/// drop it to unsubscribe.
+pub async fn subscribe(channel: &str) -> Result<PubSub, redis::RedisError> {
+	let client = redis::Client::open(scripty_config::get_config().redis_url.as_str())?;
+	let mut pubsub = client.get_async_connection().await?.into_pubsub();
+	pubsub.subscribe(channel).await?;
+	Ok(pubsub)
+}
+
+/// Publish `message` to `channel`. Returns how many subscribers received it.
+pub async fn publish(channel: &str, message: &str) -> Result<u64, TransactionError> {
+	run_transaction("PUBLISH", |cmd| {
+		cmd.arg(channel).arg(message);
+	})
+	.await
+}
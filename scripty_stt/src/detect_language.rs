@@ -0,0 +1,116 @@
+//! Working out which language a transcript is in, for guilds with `/config auto_detect_lang` on.
+//!
+//! The STT server only returns text, so this looks at the text instead of the audio.
+
+use whatlang::{Detector, Lang};
+
+/// ISO 639-1 codes, used everywhere else, and the ISO 639-3 codes the detector uses.
+const LANGUAGE_CODES: &[(&str, &str)] = &[
+	("af", "afr"),
+	("am", "amh"),
+	("ar", "ara"),
+	("az", "aze"),
+	("be", "bel"),
+	("bg", "bul"),
+	("bn", "ben"),
+	("ca", "cat"),
+	("cs", "ces"),
+	("da", "dan"),
+	("de", "deu"),
+	("el", "ell"),
+	("en", "eng"),
+	("eo", "epo"),
+	("es", "spa"),
+	("et", "est"),
+	("fa", "pes"),
+	("fi", "fin"),
+	("fr", "fra"),
+	("gu", "guj"),
+	("he", "heb"),
+	("hi", "hin"),
+	("hr", "hrv"),
+	("hu", "hun"),
+	("hy", "hye"),
+	("id", "ind"),
+	("it", "ita"),
+	("ja", "jpn"),
+	("jv", "jav"),
+	("ka", "kat"),
+	("km", "khm"),
+	("kn", "kan"),
+	("ko", "kor"),
+	("la", "lat"),
+	("lt", "lit"),
+	("lv", "lav"),
+	("mk", "mkd"),
+	("ml", "mal"),
+	("mr", "mar"),
+	("my", "mya"),
+	("nb", "nob"),
+	("ne", "nep"),
+	("nl", "nld"),
+	("pa", "pan"),
+	("pl", "pol"),
+	("pt", "por"),
+	("ro", "ron"),
+	("ru", "rus"),
+	("si", "sin"),
+	("sk", "slk"),
+	("sl", "slv"),
+	("sn", "sna"),
+	("sr", "srp"),
+	("sv", "swe"),
+	("ta", "tam"),
+	("te", "tel"),
+	("th", "tha"),
+	("tk", "tuk"),
+	("tl", "tgl"),
+	("tr", "tur"),
+	("uk", "ukr"),
+	("ur", "urd"),
+	("uz", "uzb"),
+	("vi", "vie"),
+	("yi", "yid"),
+	("zh", "cmn"),
+	("zu", "zul"),
+];
+
+/// Below this, short transcripts are often detected as a completely unrelated language.
+const MIN_CONFIDENCE: f64 = 0.3;
+
+fn to_lang(code: &str) -> Option<Lang> {
+	LANGUAGE_CODES
+		.iter()
+		.find(|(iso1, _)| *iso1 == code)
+		.and_then(|(_, iso3)| Lang::from_code(*iso3))
+}
+
+fn from_lang(lang: Lang) -> Option<&'static str> {
+	LANGUAGE_CODES
+		.iter()
+		.find(|(_, iso3)| *iso3 == lang.code())
+		.map(|(iso1, _)| *iso1)
+}
+
+/// Whether `code` is a language that can be detected.
+pub fn is_detectable_language(code: &str) -> bool {
+	to_lang(code).is_some()
+}
+
+/// Detect which language `text` is in, as an ISO 639-1 code.
+///
+/// If `allowed` isn't empty, only those languages are considered. None if it's too unclear,
+/// which is often the case for a few words.
+pub fn detect_language(text: &str, allowed: &[String]) -> Option<&'static str> {
+	let detector = if allowed.is_empty() {
+		Detector::new()
+	} else {
+		Detector::with_allowlist(allowed.iter().filter_map(|code| to_lang(code)).collect())
+	};
+	let info = detector.detect(text)?;
+	// with only one language to pick from, there's nothing to be unsure about
+	if info.confidence() < MIN_CONFIDENCE && allowed.len() != 1 {
+		return None;
+	}
+	from_lang(info.lang())
+}
@@ -4,18 +4,24 @@
 extern crate tracing;
 
 mod decode_ogg_opus;
+mod detect_language;
 mod ffprobe;
 mod init;
 mod load_balancer;
 mod models;
+mod post_process;
 mod process_audio;
 
 pub use decode_ogg_opus::decode_ogg_opus_file;
+pub use detect_language::*;
 pub use ffprobe::*;
 pub use init::init_stt;
+pub use load_balancer::WorkerStatus;
 pub use magnum::error::OpusSourceError;
 pub use models::*;
+pub use post_process::*;
 pub use process_audio::process_audio;
+use scripty_utils::latency::{measure, MeasureError, Service};
 
 /// Number of times to try to find an available STT service before giving up.
 const NUM_STT_SERVICE_TRIES: usize = 1024;
@@ -33,6 +39,37 @@ pub fn get_model_languages() -> Vec<String> {
 	scripty_config::get_config().languages.clone()
 }
 
+/// Reconnect to the STT services currently in the config, replacing the old ones.
+pub async fn reload_stt() -> Result<(), ModelError> {
+	load_balancer::LOAD_BALANCER
+		.get()
+		.expect("initialize load balancer before trying to reload it")
+		.reload_workers()
+		.await
+}
+
+/// Check if the STT pool is initialized, and at least one of its workers is healthy.
+pub fn is_available() -> bool {
+	load_balancer::LOAD_BALANCER
+		.get()
+		.map_or(false, |lb| lb.has_healthy_worker())
+}
+
+/// Get the state of every STT worker. Empty if the pool isn't initialized yet.
+pub fn list_workers() -> Vec<WorkerStatus> {
+	load_balancer::LOAD_BALANCER
+		.get()
+		.map_or_else(Vec::new, |lb| lb.worker_statuses())
+}
+
+/// Time a handshake with an STT worker, in nanoseconds, and record it in the latency history.
+pub async fn get_stt_latency() -> Result<u128, MeasureError<ModelError>> {
+	let lb = load_balancer::LOAD_BALANCER
+		.get()
+		.ok_or(MeasureError::Failed(ModelError::NoAvailableServers))?;
+	measure(Service::Stt, lb.handshake()).await
+}
+
 /// Get a new stream.
 pub async fn get_stream() -> Result<Stream, ModelError> {
 	load_balancer::LOAD_BALANCER
@@ -40,4 +77,5 @@ pub async fn get_stream() -> Result<Stream, ModelError> {
 		.expect("initialize load balancer before trying to get stream")
 		.get_stream()
 		.await
+		.map_err(ModelError::record)
 }
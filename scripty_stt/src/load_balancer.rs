@@ -1,5 +1,6 @@
 use std::{
 	collections::VecDeque,
+	io,
 	net::SocketAddr,
 	sync::{
 		atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -19,6 +20,7 @@ use scripty_common::stt_transport_models::{
 	StatusConnectionOpen,
 };
 use scripty_config::SttServiceDefinition;
+use scripty_utils::{spawn_logged, spawn_supervised};
 use tokio::{
 	io::{AsyncReadExt, AsyncWriteExt},
 	net::{
@@ -42,6 +44,63 @@ const MAXIMUM_QUEUE_SIZE: usize = 32;
 
 pub static LOAD_BALANCER: OnceCell<LoadBalancer> = OnceCell::new();
 
+/// Last reported utilization and maximum utilization of every worker, used to compute fleet-wide utilization.
+static WORKER_UTILIZATION: OnceCell<DashMap<SocketAddr, (f64, f64)>> = OnceCell::new();
+
+/// Export a worker's reported utilization, and recompute the fleet-wide utilization.
+fn report_worker_utilization(peer_address: SocketAddr, utilization: f64, max_utilization: f64) {
+	let metrics = scripty_metrics::get_metrics();
+	let worker = peer_address.to_string();
+	metrics
+		.stt_worker_utilization
+		.with_label_values(&[&worker, "current"])
+		.set(utilization);
+	metrics
+		.stt_worker_utilization
+		.with_label_values(&[&worker, "max"])
+		.set(max_utilization);
+
+	WORKER_UTILIZATION
+		.get_or_init(DashMap::new)
+		.insert(peer_address, (utilization, max_utilization));
+	report_fleet_utilization();
+}
+
+/// Stop exporting the utilization of a worker that's gone, and recompute the fleet-wide
+/// utilization without it.
+fn forget_worker_utilization(peer_address: SocketAddr) {
+	let metrics = scripty_metrics::get_metrics();
+	let worker = peer_address.to_string();
+	for kind in ["current", "max"] {
+		// errors if it never reported anything, which is fine
+		let _ = metrics
+			.stt_worker_utilization
+			.remove_label_values(&[&worker, kind]);
+	}
+
+	WORKER_UTILIZATION
+		.get_or_init(DashMap::new)
+		.remove(&peer_address);
+	report_fleet_utilization();
+}
+
+fn report_fleet_utilization() {
+	let (total, capacity) = WORKER_UTILIZATION
+		.get_or_init(DashMap::new)
+		.iter()
+		.fold((0.0, 0.0), |(total, capacity), entry| {
+			(total + entry.value().0, capacity + entry.value().1)
+		});
+	let fleet_utilization = if capacity > 0.0 {
+		total / capacity
+	} else {
+		0.0
+	};
+	scripty_metrics::get_metrics()
+		.stt_fleet_utilization
+		.set(fleet_utilization);
+}
+
 /// Round-robin load balancer that equally loads all tasks,
 /// until one notes that it is overloaded, at which point it is removed from the pool.
 ///
@@ -61,26 +120,34 @@ pub struct LoadBalancer {
 	///
 	/// Allows avoiding busy waiting in the background task.
 	new_worker_tx:  flume::Sender<()>,
+	/// Channel for workers to request the queue be purged.
+	purge_tx:       flume::Sender<()>,
 }
 
-impl LoadBalancer {
-	pub async fn new() -> Result<Self, ModelError> {
-		let stt_services = scripty_config::get_config().stt_services.clone();
-		let mut peer_addresses: Vec<SocketAddr> = Vec::new();
-		for service in stt_services {
-			match service {
-				SttServiceDefinition::HostString(host) => peer_addresses.extend(
-					lookup_host(host)
-						.await
-						.expect("Could not resolve stt hostname"),
-				),
-				SttServiceDefinition::IPTuple(addr, port) => peer_addresses.push(SocketAddr::new(
-					addr.parse()
-						.expect("Could not parse IP address for stt server"),
-					port,
-				)),
+/// Resolve the addresses of every STT service in the config.
+async fn resolve_peer_addresses() -> Result<Vec<SocketAddr>, ModelError> {
+	let stt_services = scripty_config::get_config().stt_services.clone();
+	let mut peer_addresses: Vec<SocketAddr> = Vec::new();
+	for service in stt_services {
+		match service {
+			SttServiceDefinition::HostString(host) => {
+				peer_addresses.extend(lookup_host(host).await?)
 			}
+			SttServiceDefinition::IPTuple(addr, port) => peer_addresses.push(SocketAddr::new(
+				addr.parse()
+					.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+				port,
+			)),
 		}
+	}
+	Ok(peer_addresses)
+}
+
+impl LoadBalancer {
+	pub async fn new() -> Result<Self, ModelError> {
+		let peer_addresses = resolve_peer_addresses()
+			.await
+			.expect("Could not resolve stt services");
 
 		let workers = Arc::new(DashMap::new());
 		let (purge_tx, purge_rx) = flume::bounded(1);
@@ -93,19 +160,26 @@ impl LoadBalancer {
 			workers,
 			queued_workers: Arc::new(Mutex::new(VecDeque::with_capacity(MAXIMUM_QUEUE_SIZE))),
 			new_worker_tx,
+			purge_tx,
 		};
 		let t2 = this.clone();
-		tokio::spawn(t2.new_worker_background_task(new_worker_rx));
+		spawn_supervised("stt_worker_queue", move || {
+			t2.clone().new_worker_background_task(new_worker_rx.clone())
+		});
 		let t3 = this.clone();
-		tokio::spawn(async move {
-			loop {
-				if purge_rx.recv_async().await.is_ok() {
-					t3.queued_workers.lock().clear();
-					// request the queue be refilled
-					if t3.new_worker_tx.send_async(()).await.is_err() {
-						break error!(
-							"error sending new worker request: all client queues dropped"
-						);
+		spawn_supervised("stt_queue_purge", move || {
+			let t3 = t3.clone();
+			let purge_rx = purge_rx.clone();
+			async move {
+				loop {
+					if purge_rx.recv_async().await.is_ok() {
+						t3.queued_workers.lock().clear();
+						// request the queue be refilled
+						if t3.new_worker_tx.send_async(()).await.is_err() {
+							break error!(
+								"error sending new worker request: all client queues dropped"
+							);
+						}
 					}
 				}
 			}
@@ -113,6 +187,37 @@ impl LoadBalancer {
 		Ok(this)
 	}
 
+	/// Re-resolve the STT services from the config, and replace the worker pool with them.
+	///
+	/// Streams that are already open keep using their old worker until they're finished.
+	pub async fn reload_workers(&self) -> Result<(), ModelError> {
+		let peer_addresses = resolve_peer_addresses().await?;
+
+		// connect to everything before touching the pool, so a bad config leaves it intact
+		let mut new_workers = Vec::with_capacity(peer_addresses.len());
+		for addr in peer_addresses {
+			new_workers.push(LoadBalancedStream::new(addr, self.purge_tx.clone()).await?);
+		}
+
+		info!(
+			"replacing STT worker pool with {} workers",
+			new_workers.len()
+		);
+		self.workers.clear();
+		for (n, worker) in new_workers.into_iter().enumerate() {
+			self.workers.insert(n, worker);
+		}
+		self.current_index.store(0, Ordering::Release);
+
+		// queued streams point at the old workers, refill the queue from the new ones
+		self.queued_workers.lock().clear();
+		if self.new_worker_tx.send_async(()).await.is_err() {
+			error!("error sending new worker request: all client queues dropped");
+		}
+
+		Ok(())
+	}
+
 	fn get_next_worker_idx(&self) -> usize {
 		self.current_index
 			.fetch_update(Ordering::Release, Ordering::Acquire, |x| {
@@ -239,6 +344,58 @@ impl LoadBalancer {
 		};
 		Ok(new_worker)
 	}
+
+	/// Whether any worker is currently able to take new streams.
+	pub fn has_healthy_worker(&self) -> bool {
+		self.workers.iter().any(|w| !w.is_in_error())
+	}
+
+	/// Connect to a worker that isn't errored and wait for it to greet us, then hang up.
+	///
+	/// No stream is opened, so this doesn't count against the worker's utilization.
+	pub async fn handshake(&self) -> Result<(), ModelError> {
+		let peer_address = self
+			.workers
+			.iter()
+			.find(|w| !w.is_in_error())
+			.map(|w| w.peer_address)
+			.ok_or(ModelError::NoAvailableServers)?;
+
+		let (mut stream_read, _stream_write) = TcpStream::connect(peer_address).await?.into_split();
+		match read_socket_message(&mut stream_read).await? {
+			ServerToClientMessage::StatusConnectionOpen(_) => Ok(()),
+			_ => Err(ModelError::PayloadOutOfOrder),
+		}
+	}
+
+	/// A snapshot of every worker's state, in pool order.
+	pub fn worker_statuses(&self) -> Vec<WorkerStatus> {
+		let mut workers: Vec<(usize, WorkerStatus)> = self
+			.workers
+			.iter()
+			.map(|w| {
+				(
+					*w.key(),
+					WorkerStatus {
+						address:    w.peer_address,
+						overloaded: w.is_overloaded(),
+						errored:    w.is_in_error(),
+					},
+				)
+			})
+			.collect();
+		workers.sort_unstable_by_key(|(n, _)| *n);
+		workers.into_iter().map(|(_, status)| status).collect()
+	}
+}
+
+/// The state of a single STT worker.
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerStatus {
+	pub address:    SocketAddr,
+	pub overloaded: bool,
+	/// The connection to this worker failed, and it isn't being given new streams.
+	pub errored:    bool,
 }
 
 pub struct LoadBalancedStream {
@@ -333,7 +490,7 @@ impl LoadBalancedStream {
 			stream_error_tx: stream_error_tx.clone(),
 			new_read_stream_rx,
 		};
-		tokio::spawn(async move {
+		spawn_logged("stt_read_stream", async move {
 			'outer: loop {
 				let error = 'inner: loop {
 					let message = tokio::select! {
@@ -411,7 +568,7 @@ impl LoadBalancedStream {
 			stream_error_tx,
 			new_write_stream_rx,
 		};
-		tokio::spawn(async move {
+		spawn_logged("stt_write_stream", async move {
 			'outer: loop {
 				let error = 'inner: loop {
 					let message = tokio::select! {
@@ -484,7 +641,7 @@ impl LoadBalancedStream {
 		let wfns2 = Arc::clone(&waiting_for_new_stream);
 		let purge_tx2 = purge_tx.clone();
 		// error handling task
-		tokio::spawn(async move {
+		spawn_logged("stt_stream_errors", async move {
 			loop {
 				let _error = stream_error_rx.recv().await;
 				warn!("got error from stream pair");
@@ -537,7 +694,7 @@ impl LoadBalancedStream {
 		let iso2 = Arc::clone(&is_overloaded);
 		let mut server_to_client_rx2 = server_to_client_tx.subscribe();
 		// monitoring task
-		tokio::spawn(async move {
+		spawn_logged("stt_worker_monitor", async move {
 			loop {
 				let Ok(res) = server_to_client_rx2.recv().await else {
 					// error happened, we are never going to get any more messages
@@ -548,8 +705,10 @@ impl LoadBalancedStream {
 				}) = res
 				{
 					iso2.store(utilization > max_utilization, Ordering::Relaxed);
+					report_worker_utilization(peer_address, utilization, max_utilization);
 				}
 			}
+			forget_worker_utilization(peer_address);
 		});
 
 		let is_errored = Arc::new(AtomicBool::new(false));
@@ -560,7 +719,7 @@ impl LoadBalancedStream {
 		// If in error state, clear out the queue
 		// and also try creating a new worker every few seconds.
 		// When one does succeed, unset the flag
-		tokio::spawn(async move {
+		spawn_logged("stt_worker_recovery", async move {
 			loop {
 				if ie2.load(Ordering::Relaxed) {
 					// try fetching a new worker
@@ -1,4 +1,7 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{
+	net::SocketAddr,
+	time::{Duration, Instant},
+};
 
 use scripty_common::stt_transport_models::{
 	AudioData,
@@ -36,6 +39,7 @@ impl Stream {
 	) -> Result<Self, ModelError> {
 		let session_id = Uuid::new_v4();
 		debug!(%session_id, %peer_address, "initializing stts stream to peer");
+		let connect_start = Instant::now();
 
 		tx.send(ClientToServerMessage::InitializeStreaming(
 			InitializeStreaming { id: session_id },
@@ -59,6 +63,9 @@ impl Stream {
 		match tokio::time::timeout(Duration::from_secs(5), stream_fut).await {
 			Ok(true) => {
 				debug!(%session_id, %peer_address, "stts stream initialized");
+				scripty_metrics::get_metrics()
+					.stt_connect_time
+					.observe(connect_start.elapsed().as_secs_f64());
 				Ok(Self {
 					tx,
 					rx,
@@ -85,7 +92,7 @@ impl Stream {
 				data,
 				id: self.session_id,
 			}))
-			.map_or(Err(ModelError::RemoteDisconnected), |_| Ok(()))
+			.map_or(Err(ModelError::RemoteDisconnected.record()), |_| Ok(()))
 	}
 
 	pub async fn get_result(
@@ -105,7 +112,7 @@ impl Stream {
 					id: self.session_id,
 				},
 			))
-			.map_err(|_| ModelError::RemoteDisconnected)?;
+			.map_err(|_| ModelError::RemoteDisconnected.record())?;
 		let stream_fut = async {
 			while let Ok(next) = self.rx.recv().await {
 				if let ServerToClientMessage::SttResult(SttSuccess { id, result }) = next {
@@ -125,11 +132,11 @@ impl Stream {
 		};
 		match tokio::time::timeout(Duration::from_secs(30), stream_fut).await {
 			Ok(Ok(res)) => Ok(res),
-			Ok(Err(e)) => Err(e),
+			Ok(Err(e)) => Err(e.record()),
 			Err(_) => {
 				warn!(%self.session_id, %self.peer_address, "timed out waiting for result");
 				self.purge_tx.send_async(()).await.ok();
-				Err(ModelError::TimedOutWaitingForResult)
+				Err(ModelError::TimedOutWaitingForResult.record())
 			}
 		}
 	}
@@ -155,6 +162,32 @@ pub enum ModelError {
 	},
 }
 
+impl ModelError {
+	/// Short name of this kind of error, used as a metric label.
+	pub fn kind(&self) -> &'static str {
+		match self {
+			ModelError::Io(_) => "io",
+			ModelError::MessagePackDecode(_) => "msgpack_decode",
+			ModelError::MessagePackEncode(_) => "msgpack_encode",
+			ModelError::SttsServer(_) => "stts_server",
+			ModelError::NoAvailableServers => "no_available_servers",
+			ModelError::InvalidMagicBytes(_) => "invalid_magic_bytes",
+			ModelError::PayloadOutOfOrder => "payload_out_of_order",
+			ModelError::OverloadedRemote => "overloaded_remote",
+			ModelError::InitializationTimedOut => "initialization_timed_out",
+			ModelError::TimedOutWaitingForResult => "timed_out_waiting_for_result",
+			ModelError::RemoteDisconnected => "remote_disconnected",
+			ModelError::InvalidPayload { .. } => "invalid_payload",
+		}
+	}
+
+	/// Count this error in `errors_total`, then pass it through unchanged.
+	pub(crate) fn record(self) -> Self {
+		scripty_metrics::record_error("scripty_stt", self.kind());
+		self
+	}
+}
+
 impl std::error::Error for ModelError {}
 
 impl From<io::Error> for ModelError {
@@ -0,0 +1,835 @@
+//! Clean-up of raw STT output, before it's posted anywhere.
+//!
+//! A [`Pipeline`] runs an ordered list of [`Transformer`]s over each transcript.
+//! The standard one strips model artifacts, then fixes up punctuation and casing:
+//! guild options are slotted in between, so they see clean text and their output is still cased.
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex, RegexBuilder};
+
+/// One step of a [`Pipeline`].
+pub trait Transformer: Send + Sync {
+	/// Transform `text`. Returning an empty string drops the transcript.
+	fn transform(&self, text: String) -> String;
+}
+
+#[derive(Clone, Default)]
+pub struct Pipeline {
+	transformers:    Vec<Arc<dyn Transformer>>,
+	/// Where the next guild stage is inserted.
+	guild_stage_end: usize,
+}
+
+impl Pipeline {
+	/// The clean-up every transcript gets, for speech in `language`.
+	/// Translated transcripts are always in English, whatever was spoken.
+	pub fn standard(language: &str, translate: bool) -> Self {
+		let language = if translate { "en" } else { language };
+		let mut pipeline = Self::default().then(ArtifactCleanup);
+		pipeline.guild_stage_end = pipeline.transformers.len();
+		pipeline
+			.then(Punctuation::new(language))
+			.then(SentenceCase::new(language))
+	}
+
+	/// Add `transformer` to the end of the pipeline.
+	pub fn then(mut self, transformer: impl Transformer + 'static) -> Self {
+		self.transformers.push(Arc::new(transformer));
+		self
+	}
+
+	/// Add a guild's own `transformer`, after artifacts are stripped but before punctuation
+	/// and casing. Guild stages run in the order they're added.
+	pub fn with_guild_stage(mut self, transformer: impl Transformer + 'static) -> Self {
+		self.transformers
+			.insert(self.guild_stage_end, Arc::new(transformer));
+		self.guild_stage_end += 1;
+		self
+	}
+
+	/// Run `text` through every stage, in order. Stops early if a stage empties it.
+	pub fn run(&self, text: String) -> String {
+		let mut text = text;
+		for transformer in &self.transformers {
+			if text.is_empty() {
+				break;
+			}
+			text = transformer.transform(text);
+		}
+		text
+	}
+}
+
+/// Strips what the model outputs for things that aren't speech, and tidies up spacing.
+pub struct ArtifactCleanup;
+
+/// A word repeated this many times in a row is the model looping, rather than someone talking.
+const MAX_REPEATS: usize = 4;
+
+impl Transformer for ArtifactCleanup {
+	fn transform(&self, text: String) -> String {
+		let text = strip_tags(&text);
+
+		// a transcript that's only a sound description, ie "(upbeat music)" or "*laughs*"
+		let trimmed = text.trim();
+		if is_wrapped(trimmed, '(', ')') || is_wrapped(trimmed, '*', '*') {
+			return String::new();
+		}
+
+		let mut words: Vec<String> = Vec::new();
+		// how many times in a row the last word has come up, and whether that's been collapsed
+		let mut run_len = 0;
+		let mut collapsed = false;
+		for word in trimmed.split_whitespace() {
+			// punctuation the model spaced out belongs to the word before it
+			if word
+				.chars()
+				.all(|c| matches!(c, ',' | '.' | '!' | '?' | ';' | ':'))
+			{
+				if let Some(last) = words.last_mut() {
+					last.push_str(word);
+					continue;
+				}
+			}
+
+			if words.last().map_or(false, |last| same_word(last, word)) {
+				if collapsed {
+					continue;
+				}
+				run_len += 1;
+				if run_len >= MAX_REPEATS {
+					// keep only the first of the run
+					words.truncate(words.len() + 2 - run_len);
+					collapsed = true;
+					continue;
+				}
+			} else {
+				run_len = 1;
+				collapsed = false;
+			}
+			words.push(word.to_owned());
+		}
+
+		words.join(" ")
+	}
+}
+
+/// Remove `[BLANK_AUDIO]`, `[MUSIC]` and the like: nobody says anything in square brackets.
+fn strip_tags(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find('[') {
+		let Some(len) = rest[start..].find(']') else {
+			break;
+		};
+		out.push_str(&rest[..start]);
+		out.push(' ');
+		rest = &rest[start + len + 1..];
+	}
+	out.push_str(rest);
+	out
+}
+
+fn is_wrapped(text: &str, open: char, close: char) -> bool {
+	text.len() >= 2
+		&& text.starts_with(open)
+		&& text.ends_with(close)
+		&& !text[1..text.len() - 1].contains([open, close])
+}
+
+/// Whether `a` and `b` are the same word, ignoring case and punctuation around them.
+fn same_word(a: &str, b: &str) -> bool {
+	let trim = |w: &str| {
+		w.trim_matches(|c: char| !c.is_alphanumeric())
+			.to_lowercase()
+	};
+	let a = trim(a);
+	!a.is_empty() && a == trim(b)
+}
+
+/// Ends transcripts the model left unpunctuated, with a question mark if it's an obvious
+/// English question.
+pub struct Punctuation {
+	terminator: Option<char>,
+	english:    bool,
+}
+
+const QUESTION_WORDS: &[&str] = &[
+	"who", "what", "when", "where", "why", "how", "which", "is", "are", "am", "was", "were", "do",
+	"does", "did", "can", "could", "would", "should", "will", "shall", "have", "has",
+];
+
+impl Punctuation {
+	pub fn new(language: &str) -> Self {
+		let terminator = match language {
+			"ja" | "zh" | "yue" => Some('。'),
+			"hi" | "mr" | "ne" | "bn" => Some('।'),
+			// these don't end sentences with a mark at all
+			"th" | "lo" | "km" | "my" | "bo" => None,
+			_ => Some('.'),
+		};
+		Self {
+			terminator,
+			english: language == "en",
+		}
+	}
+}
+
+impl Transformer for Punctuation {
+	fn transform(&self, mut text: String) -> String {
+		let Some(terminator) = self.terminator else {
+			return text;
+		};
+		if !text.ends_with(char::is_alphanumeric) {
+			return text;
+		}
+
+		let is_question = self.english
+			&& text.split_whitespace().next().map_or(false, |first| {
+				QUESTION_WORDS.contains(&first.to_lowercase().as_str())
+			});
+		text.push(if is_question { '?' } else { terminator });
+		text
+	}
+}
+
+/// Capitalizes the start of each sentence, and "I" in English.
+///
+/// Never lowercases anything, as the model's casing of names is usually right.
+pub struct SentenceCase {
+	/// Off for languages where uppercasing `i` gives the wrong letter.
+	enabled: bool,
+	english: bool,
+}
+
+impl SentenceCase {
+	pub fn new(language: &str) -> Self {
+		Self {
+			enabled: !matches!(language, "tr" | "az"),
+			english: language == "en",
+		}
+	}
+}
+
+impl Transformer for SentenceCase {
+	fn transform(&self, text: String) -> String {
+		if !self.enabled {
+			return text;
+		}
+
+		let mut out = String::with_capacity(text.len());
+		let mut sentence_start = true;
+		for (i, word) in text.split(' ').enumerate() {
+			if i > 0 {
+				out.push(' ');
+			}
+
+			let bare = word.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+			let is_pronoun = self.english && matches!(bare, "i" | "i'm" | "i'll" | "i've" | "i'd");
+			if sentence_start || is_pronoun {
+				capitalize_into(word, &mut out);
+			} else {
+				out.push_str(word);
+			}
+
+			if !word.is_empty() {
+				sentence_start = word
+					.trim_end_matches(['"', '\'', ')', '»'])
+					.ends_with(['.', '!', '?', '…']);
+			}
+		}
+		out
+	}
+}
+
+/// Push `word` to `out`, with its first letter uppercased.
+fn capitalize_into(word: &str, out: &mut String) {
+	match word.char_indices().find(|(_, c)| c.is_alphabetic()) {
+		Some((idx, c)) => {
+			out.push_str(&word[..idx]);
+			out.extend(c.to_uppercase());
+			out.push_str(&word[idx + c.len_utf8()..]);
+		}
+		None => out.push_str(word),
+	}
+}
+
+/// A guild's own find-and-replace rules, set with `/config replacements`, run in order.
+pub struct Replacements {
+	rules: Vec<(Regex, String)>,
+}
+
+impl Replacements {
+	/// Compile `pattern` the way rules are: ignoring case, and small enough to run on every line.
+	pub fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+		RegexBuilder::new(pattern)
+			.case_insensitive(true)
+			.size_limit(1 << 16)
+			.build()
+	}
+
+	/// `rules` are pattern and replacement pairs. Patterns that don't compile are skipped.
+	pub fn new(rules: impl IntoIterator<Item = (String, String)>) -> Self {
+		let rules = rules
+			.into_iter()
+			.filter_map(|(pattern, replacement)| match Self::compile(&pattern) {
+				Ok(regex) => Some((regex, replacement)),
+				Err(e) => {
+					warn!(%pattern, "skipping replacement that doesn't compile: {}", e);
+					None
+				}
+			})
+			.collect();
+		Self { rules }
+	}
+}
+
+impl Transformer for Replacements {
+	fn transform(&self, text: String) -> String {
+		let text = self.rules.iter().fold(text, |text, (regex, replacement)| {
+			regex.replace_all(&text, replacement.as_str()).into_owned()
+		});
+		// removing a word leaves the spaces either side of it
+		text.split_whitespace().collect::<Vec<_>>().join(" ")
+	}
+}
+
+/// Strips filler words, ie "um" and "uh", set with `/config filler_words`.
+///
+/// Drawn-out ones like "ummm" are stripped too. Off by default, as some servers want verbatim
+/// transcripts.
+pub struct FillerWords {
+	/// Lowercase, from the `filler-words` message of the transcript's language.
+	words: Vec<String>,
+}
+
+impl FillerWords {
+	pub fn new(words: Vec<String>) -> Self {
+		Self { words }
+	}
+
+	fn is_filler(&self, word: &str) -> bool {
+		let bare = word
+			.trim_matches(|c: char| !c.is_alphanumeric())
+			.to_lowercase();
+		self.words.iter().any(|filler| {
+			bare.strip_prefix(filler.as_str()).map_or(false, |rest| {
+				filler
+					.chars()
+					.last()
+					.map_or(false, |last| rest.chars().all(|c| c == last))
+			})
+		})
+	}
+}
+
+impl Transformer for FillerWords {
+	fn transform(&self, text: String) -> String {
+		let mut words: Vec<String> = Vec::new();
+		for word in text.split_whitespace() {
+			if !self.is_filler(word) {
+				words.push(word.to_owned());
+				continue;
+			}
+
+			// keep the end of a sentence that finished on a filler, ie "I think, um."
+			if word.ends_with(['.', '!', '?']) {
+				if let Some(last) = words.last_mut() {
+					let end = word.trim_start_matches(|c: char| !matches!(c, '.' | '!' | '?'));
+					let trimmed_len = last.trim_end_matches([',', ';', ':']).len();
+					last.truncate(trimmed_len);
+					last.push_str(end);
+				}
+			}
+		}
+		words.join(" ")
+	}
+}
+
+/// Writes spoken numbers as digits, set with `/config numbers`, ie "at three thirty pm" becomes
+/// "at 3:30 pm" and "twenty five dollars" becomes "$25". English only.
+///
+/// A lone number under ten stays a word, as in most style guides, unless it's a time or amount.
+/// Numbers said straight after each other, like years or phone numbers, are left alone, as
+/// there's no telling how they were meant to be split up.
+pub struct SpokenNumbers {
+	english: bool,
+}
+
+const UNITS: &[&str] = &[
+	"zero",
+	"one",
+	"two",
+	"three",
+	"four",
+	"five",
+	"six",
+	"seven",
+	"eight",
+	"nine",
+	"ten",
+	"eleven",
+	"twelve",
+	"thirteen",
+	"fourteen",
+	"fifteen",
+	"sixteen",
+	"seventeen",
+	"eighteen",
+	"nineteen",
+];
+const TENS: &[&str] = &[
+	"twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: &[(&str, u64)] = &[
+	("thousand", 1_000),
+	("million", 1_000_000),
+	("billion", 1_000_000_000),
+];
+/// Currency words, and the symbol written before the amount.
+const CURRENCIES: &[(&str, &str)] = &[
+	("dollar", "$"),
+	("dollars", "$"),
+	("bucks", "$"),
+	("euro", "€"),
+	("euros", "€"),
+];
+
+/// A word, split from the punctuation after it.
+struct Token<'a> {
+	/// Lowercase, for matching.
+	bare:  String,
+	word:  &'a str,
+	punct: &'a str,
+}
+
+impl<'a> Token<'a> {
+	fn new(word: &'a str) -> Self {
+		let end = word.trim_end_matches([',', '.', '!', '?', ';', ':']).len();
+		let (word, punct) = word.split_at(end);
+		Self {
+			bare: word.to_lowercase(),
+			word,
+			punct,
+		}
+	}
+
+	fn is_number(&self) -> bool {
+		UNITS.contains(&self.bare.as_str())
+			|| TENS.contains(&self.bare.as_str())
+			|| self.bare == "hundred"
+			|| SCALES.iter().any(|(name, _)| *name == self.bare)
+	}
+}
+
+impl SpokenNumbers {
+	pub fn new(language: &str) -> Self {
+		Self {
+			english: language == "en",
+		}
+	}
+
+	/// Parse the number starting at `tokens[0]`, returning its value and how many tokens it took.
+	fn parse(tokens: &[Token]) -> Option<(u64, usize)> {
+		let mut total = 0;
+		let mut current = 0;
+		let mut used = 0;
+		// whether the last word was a unit, so "three four" isn't read as 7
+		let mut after_unit = false;
+		while let Some(token) = tokens.get(used) {
+			let word = token.bare.as_str();
+			if let Some(value) = UNITS.iter().position(|unit| *unit == word) {
+				if after_unit || (current % 100 >= 10 && current % 10 == 0 && value >= 10) {
+					break;
+				}
+				current += value as u64;
+				after_unit = true;
+			} else if let Some(value) = TENS.iter().position(|tens| *tens == word) {
+				if current % 100 != 0 {
+					break;
+				}
+				current += (value as u64 + 2) * 10;
+				after_unit = false;
+			} else if word == "hundred" {
+				if used == 0 || current % 100 == 0 {
+					break;
+				}
+				current *= 100;
+				after_unit = false;
+			} else if let Some((_, scale)) = SCALES.iter().find(|(name, _)| *name == word) {
+				if used == 0 || current == 0 {
+					break;
+				}
+				total += current * scale;
+				current = 0;
+				after_unit = false;
+			} else if word == "and"
+				&& used > 0 && !after_unit
+				&& tokens.get(used + 1).map_or(false, Token::is_number)
+			{
+				// "one hundred and five"
+			} else {
+				break;
+			}
+			used += 1;
+			// punctuation ends the number
+			if !token.punct.is_empty() {
+				break;
+			}
+		}
+		(used > 0).then_some((total + current, used))
+	}
+
+	/// Read "thirty", "oh five" or "o'clock" after an hour, as minutes.
+	fn parse_minutes(tokens: &[Token]) -> Option<(u64, usize)> {
+		match tokens.first()?.bare.as_str() {
+			"o'clock" => Some((0, 1)),
+			"oh" if tokens[0].punct.is_empty() => {
+				let (minutes, used) = Self::parse(&tokens[1..])?;
+				(used == 1 && (1..10).contains(&minutes)).then_some((minutes, 2))
+			}
+			_ => Self::parse(tokens).filter(|(minutes, _)| (10..60).contains(minutes)),
+		}
+	}
+}
+
+fn is_meridiem(token: &Token) -> bool {
+	matches!(token.bare.as_str(), "am" | "pm" | "a.m" | "p.m")
+}
+
+/// `value` with thousands separators, if it's long enough to need them.
+fn group_digits(value: u64) -> String {
+	let digits = value.to_string();
+	if digits.len() <= 4 {
+		return digits;
+	}
+	let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+	for (i, c) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i) % 3 == 0 {
+			out.push(',');
+		}
+		out.push(c);
+	}
+	out
+}
+
+impl Transformer for SpokenNumbers {
+	fn transform(&self, text: String) -> String {
+		if !self.english {
+			return text;
+		}
+
+		// "twenty-five" is read like "twenty five"
+		let words: Vec<&str> = text
+			.split_whitespace()
+			.flat_map(|word| {
+				let parts: Vec<&str> = word.split('-').collect();
+				if parts.len() > 1 && parts.iter().all(|part| Token::new(part).is_number()) {
+					parts
+				} else {
+					vec![word]
+				}
+			})
+			.collect();
+		let tokens: Vec<Token> = words.iter().map(|word| Token::new(word)).collect();
+
+		let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+		let mut i = 0;
+		while i < tokens.len() {
+			let Some((value, used)) = Self::parse(&tokens[i..]) else {
+				out.push(words[i].to_owned());
+				i += 1;
+				continue;
+			};
+			let last = &tokens[i + used - 1];
+			let rest = if last.punct.is_empty() {
+				&tokens[i + used..]
+			} else {
+				&[]
+			};
+
+			// a time, ie "three thirty pm", "seven pm" or "ten o'clock"
+			if (1..=12).contains(&value) {
+				let (minutes, minutes_used) = Self::parse_minutes(rest).unwrap_or((0, 0));
+				let ends_minutes = minutes_used == 0 || rest[minutes_used - 1].punct.is_empty();
+				if let Some(meridiem) = rest
+					.get(minutes_used)
+					.filter(|token| ends_minutes && is_meridiem(token))
+				{
+					let time = if minutes_used == 0 {
+						value.to_string()
+					} else {
+						format!("{}:{:02}", value, minutes)
+					};
+					out.push(time);
+					out.push(format!("{}{}", meridiem.word, meridiem.punct));
+					i += used + minutes_used + 1;
+					continue;
+				}
+				if minutes_used > 0 && rest[minutes_used - 1].bare == "o'clock" {
+					let clock = &rest[minutes_used - 1];
+					out.push(format!("{} {}{}", value, clock.word, clock.punct));
+					i += used + minutes_used;
+					continue;
+				}
+			}
+
+			// an amount, ie "twenty five dollars"
+			if let Some((currency, symbol)) = rest
+				.first()
+				.and_then(|token| CURRENCIES.iter().find(|(name, _)| *name == token.bare))
+				.map(|(_, symbol)| (&rest[0], *symbol))
+			{
+				out.push(format!(
+					"{}{}{}",
+					symbol,
+					group_digits(value),
+					currency.punct
+				));
+				i += used + 1;
+				continue;
+			}
+
+			// straight into another number, so leave the whole run as it was said
+			if rest.first().map_or(false, Token::is_number) {
+				let mut end = i + used;
+				while tokens.get(end).map_or(false, Token::is_number) {
+					end += 1;
+					if !tokens[end - 1].punct.is_empty() {
+						break;
+					}
+				}
+				out.extend(words[i..end].iter().map(|word| (*word).to_owned()));
+				i = end;
+				continue;
+			}
+
+			if used == 1 && value < 10 {
+				out.push(words[i].to_owned());
+			} else {
+				out.push(format!("{}{}", group_digits(value), last.punct));
+			}
+			i += used;
+		}
+		out.join(" ")
+	}
+}
+
+/// What [`MinLength`] counts. Stored in `guilds.min_transcript_length_unit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthUnit {
+	/// Letters and digits, so punctuation and spaces don't count.
+	Characters,
+	Words,
+}
+
+impl LengthUnit {
+	/// Decode `guilds.min_transcript_length_unit`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Words,
+			_ => Self::Characters,
+		}
+	}
+}
+
+/// Drops transcripts shorter than a guild's minimum, set with `/config min_length`,
+/// as they're mostly coughs, breathing and "yeah" picked up as words.
+pub struct MinLength {
+	min:  usize,
+	unit: LengthUnit,
+}
+
+impl MinLength {
+	pub fn new(min: usize, unit: LengthUnit) -> Self {
+		Self { min, unit }
+	}
+}
+
+impl Transformer for MinLength {
+	fn transform(&self, text: String) -> String {
+		let len = match self.unit {
+			LengthUnit::Characters => text.chars().filter(|c| c.is_alphanumeric()).count(),
+			LengthUnit::Words => text.split_whitespace().count(),
+		};
+		if len < self.min {
+			scripty_metrics::get_metrics().transcripts_too_short.inc();
+			return String::new();
+		}
+		text
+	}
+}
+
+/// How [`ProfanityFilter`] hides swear words. Stored in `guilds.profanity_filter`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfanityMode {
+	Off,
+	/// Replace all but the first letter with asterisks.
+	Mask,
+	/// Wrap them in spoiler tags, so the transcript is intact for anyone who clicks.
+	Spoiler,
+}
+
+impl ProfanityMode {
+	/// Decode `guilds.profanity_filter`.
+	pub fn from_db(value: i16) -> Self {
+		match value {
+			1 => Self::Mask,
+			2 => Self::Spoiler,
+			_ => Self::Off,
+		}
+	}
+}
+
+/// English swear words. Ones ending in `\w*` match everything starting with them, the rest
+/// only match exactly, as they're also the start of innocent words, like "cock" in "cocktail".
+const PROFANITY_WORDS: &[&str] = &[
+	r"(?:mother)?fuck\w*",
+	r"(?:bull)?shit\w*",
+	r"bitch\w*",
+	r"cunt\w*",
+	r"(?:ass|arse)hole\w*",
+	r"bastard\w*",
+	r"twat\w*",
+	r"wank\w*",
+	r"dickhead\w*",
+	r"cocksucker\w*",
+	r"slut\w*",
+	r"whore\w*",
+	r"piss\w*",
+	"dicks?",
+	"cocks?",
+	"pricks?",
+];
+
+static PROFANITY: Lazy<Regex> = Lazy::new(|| {
+	RegexBuilder::new(&format!(r"\b(?:{})\b", PROFANITY_WORDS.join("|")))
+		.case_insensitive(true)
+		.build()
+		.expect("static regex is valid")
+});
+
+/// Hides swear words, set with `/config profanity_filter`.
+///
+/// Runs after casing and punctuation, so they still see the real words.
+pub struct ProfanityFilter {
+	mode: ProfanityMode,
+}
+
+impl ProfanityFilter {
+	pub fn new(mode: ProfanityMode) -> Self {
+		Self { mode }
+	}
+}
+
+impl Transformer for ProfanityFilter {
+	fn transform(&self, text: String) -> String {
+		if self.mode == ProfanityMode::Off {
+			return text;
+		}
+
+		PROFANITY
+			.replace_all(&text, |caps: &Captures| {
+				let word = &caps[0];
+				match self.mode {
+					ProfanityMode::Spoiler => format!("||{}||", word),
+					// escaped, or Discord reads the asterisks as formatting
+					_ => word
+						.chars()
+						.enumerate()
+						.map(|(i, c)| {
+							if i == 0 {
+								c.to_string()
+							} else {
+								"\\*".to_owned()
+							}
+						})
+						.collect(),
+				}
+			})
+			.into_owned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fillers() -> FillerWords {
+		FillerWords::new(vec!["um".to_owned(), "uh".to_owned()])
+	}
+
+	#[test]
+	fn test_punctuation() {
+		let english = Punctuation::new("en");
+		assert_eq!(english.transform("it works".to_owned()), "it works.");
+		assert_eq!(english.transform("how are you".to_owned()), "how are you?");
+		assert_eq!(english.transform("Is it done".to_owned()), "Is it done?");
+		assert_eq!(english.transform("done!".to_owned()), "done!");
+
+		// only English questions are spotted
+		assert_eq!(
+			Punctuation::new("de").transform("wie geht es".to_owned()),
+			"wie geht es."
+		);
+		assert_eq!(
+			Punctuation::new("ja").transform("こんにちは".to_owned()),
+			"こんにちは。"
+		);
+		assert_eq!(Punctuation::new("th").transform("สวัสดี".to_owned()), "สวัสดี");
+	}
+
+	#[test]
+	fn test_sentence_case() {
+		let english = SentenceCase::new("en");
+		assert_eq!(
+			english.transform("hello there. i think i'm fine".to_owned()),
+			"Hello there. I think I'm fine"
+		);
+		assert_eq!(
+			english.transform("he said \"stop.\" then left".to_owned()),
+			"He said \"stop.\" Then left"
+		);
+		// the model's casing is kept
+		assert_eq!(
+			english.transform("NASA launched".to_owned()),
+			"NASA launched"
+		);
+
+		// "i" is only a word in English
+		assert_eq!(
+			SentenceCase::new("fr").transform("oui i think".to_owned()),
+			"Oui i think"
+		);
+		// uppercasing "i" gives the wrong letter in Turkish
+		assert_eq!(
+			SentenceCase::new("tr").transform("istanbul".to_owned()),
+			"istanbul"
+		);
+	}
+
+	#[test]
+	fn test_filler_words() {
+		assert_eq!(
+			fillers().transform("um I think uh we should go".to_owned()),
+			"I think we should go"
+		);
+		assert_eq!(fillers().transform("Um, hello".to_owned()), "hello");
+		assert_eq!(fillers().transform("ummm okay".to_owned()), "okay");
+		assert_eq!(fillers().transform("I think, um.".to_owned()), "I think.");
+		assert_eq!(fillers().transform("my umbrella".to_owned()), "my umbrella");
+		assert_eq!(fillers().transform("uh um".to_owned()), "");
+	}
+
+	#[test]
+	fn test_guild_stages_are_cased() {
+		let pipeline = Pipeline::standard("en", false).with_guild_stage(fillers());
+		assert_eq!(
+			pipeline.run("um what time is it".to_owned()),
+			"What time is it?"
+		);
+		assert_eq!(pipeline.run("uh [BLANK_AUDIO] um".to_owned()), "");
+	}
+}
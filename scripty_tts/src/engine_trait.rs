@@ -4,6 +4,8 @@ pub trait TtsEngine {
 
 	/// Run the TTS engine on the given text, returning the resulting audio,
 	/// in either WAV or raw signed 16-bit PCM format.
+	///
+	/// This may block for as long as synthesis takes, so run it off the async runtime.
 	fn get_waveform(
 		&self,
 		text: &str,
@@ -13,7 +15,11 @@ pub trait TtsEngine {
 
 pub enum TtsEngineOutput {
 	Wav(Vec<u8>),
-	RawPcm(Vec<i16>),
+	/// Mono samples, at `sample_rate` Hz.
+	RawPcm {
+		samples:     Vec<i16>,
+		sample_rate: u32,
+	},
 }
 
 pub struct EngineParameters {
@@ -23,3 +29,16 @@ pub struct EngineParameters {
 	pub pitch:     u8,
 	pub speed:     u16,
 }
+
+impl Default for EngineParameters {
+	/// espeak-ng's own defaults, in English.
+	fn default() -> Self {
+		Self {
+			voice:     "en".to_string(),
+			amplitude: 100,
+			gap:       0,
+			pitch:     50,
+			speed:     175,
+		}
+	}
+}
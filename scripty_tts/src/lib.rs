@@ -1,3 +1,8 @@
+//! Text-to-speech, for reading messages out in voice chats with `/config tts_relay`.
+
 mod engine_trait;
 
 mod tts_engines;
+
+pub use engine_trait::{EngineParameters, TtsEngine, TtsEngineOutput};
+pub use tts_engines::*;
@@ -1,8 +1,50 @@
+use std::{
+	fmt,
+	io::{self, Write},
+	process::{Command, Stdio},
+};
+
 use crate::engine_trait::{EngineParameters, TtsEngine, TtsEngineOutput};
 
-pub struct EspeakNgEngine;
+/// Runs the `espeak-ng` binary for every request.
+pub struct EspeakNgEngine {
+	/// Path to the binary, or just its name to look it up in `PATH`.
+	path: String,
+}
+
+impl EspeakNgEngine {
+	pub fn new(path: impl Into<String>) -> Self {
+		Self { path: path.into() }
+	}
+}
 
-pub struct EspeakNgError(u8);
+#[derive(Debug)]
+pub enum EspeakNgError {
+	/// Couldn't start the binary or talk to it.
+	Io(io::Error),
+	/// The binary exited unsuccessfully, ie because the voice doesn't exist.
+	Failed(Option<i32>, String),
+}
+
+impl From<io::Error> for EspeakNgError {
+	fn from(e: io::Error) -> Self {
+		Self::Io(e)
+	}
+}
+
+impl fmt::Display for EspeakNgError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to run espeak-ng: {}", e),
+			Self::Failed(Some(code), stderr) => {
+				write!(f, "espeak-ng exited with code {}: {}", code, stderr)
+			}
+			Self::Failed(None, stderr) => write!(f, "espeak-ng was killed: {}", stderr),
+		}
+	}
+}
+
+impl std::error::Error for EspeakNgError {}
 
 impl TtsEngine for EspeakNgEngine {
 	type Error = EspeakNgError;
@@ -12,5 +54,31 @@ impl TtsEngine for EspeakNgEngine {
 		text: &str,
 		params: &EngineParameters,
 	) -> Result<TtsEngineOutput, Self::Error> {
+		// text goes in through stdin, so it can't be mistaken for an option
+		let mut child = Command::new(&self.path)
+			.arg("--stdout")
+			.arg("--stdin")
+			.args(["-v", &params.voice])
+			.args(["-a", &params.amplitude.to_string()])
+			.args(["-g", &params.gap.to_string()])
+			.args(["-p", &params.pitch.to_string()])
+			.args(["-s", &params.speed.to_string()])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()?;
+
+		if let Some(mut stdin) = child.stdin.take() {
+			stdin.write_all(text.as_bytes())?;
+		}
+		let output = child.wait_with_output()?;
+		if !output.status.success() {
+			return Err(EspeakNgError::Failed(
+				output.status.code(),
+				String::from_utf8_lossy(&output.stderr).into_owned(),
+			));
+		}
+
+		Ok(TtsEngineOutput::Wav(output.stdout))
 	}
 }
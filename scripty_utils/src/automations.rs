@@ -0,0 +1,144 @@
+//! Guild spoken-keyword automations, set with `/automation`: saying a phrase in a transcribed
+//! voice chat posts a message, pings a role, or fires the guild's webhook.
+//!
+//! Phrases are matched by `scripty_automod::keywords`, like automod rules.
+//! Sessions that are already going pick up changes the next time they reload their config.
+
+use serenity::model::id::{GuildId, RoleId};
+
+/// Most automations a guild can have.
+pub const MAX_AUTOMATIONS: i64 = 25;
+/// Longest a phrase can be.
+pub const MAX_PHRASE_LEN: usize = 100;
+/// Longest a canned message can be.
+pub const MAX_MESSAGE_LEN: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationAction {
+	/// Post the automation's message in the transcript channel.
+	Message,
+	/// Mention the automation's role in the transcript channel, with its message if it has one.
+	PingRole,
+	/// Send an event to the guild's webhook, set with `/config webhook_url`.
+	Webhook,
+}
+
+impl AutomationAction {
+	pub fn from_db(action: i16) -> Option<Self> {
+		match action {
+			0 => Some(Self::Message),
+			1 => Some(Self::PingRole),
+			2 => Some(Self::Webhook),
+			_ => None,
+		}
+	}
+
+	pub fn to_db(self) -> i16 {
+		match self {
+			Self::Message => 0,
+			Self::PingRole => 1,
+			Self::Webhook => 2,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct Automation {
+	pub id:      i32,
+	/// Lowercase.
+	pub phrase:  String,
+	pub action:  AutomationAction,
+	pub message: Option<String>,
+	/// Only set for `AutomationAction::PingRole`.
+	pub role_id: Option<RoleId>,
+}
+
+/// Get `guild_id`'s automations, in the order they were added.
+pub async fn get_automations(guild_id: GuildId) -> Result<Vec<Automation>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT id, phrase, action, message, role_id FROM automations WHERE guild_id = $1 ORDER \
+		 BY id ASC",
+		guild_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.filter_map(|row| {
+			Some(Automation {
+				id:      row.id,
+				phrase:  row.phrase,
+				action:  AutomationAction::from_db(row.action)?,
+				message: row.message,
+				role_id: row
+					.role_id
+					.and_then(|id| u64::try_from(id).ok())
+					.filter(|id| *id != 0)
+					.map(RoleId::new),
+			})
+		})
+		.collect())
+}
+
+/// Do `action` when `phrase` is said in `guild_id`, replacing what it does if it already has an
+/// automation. Returns the automation's ID, or None if the guild is full.
+pub async fn add_automation(
+	guild_id: GuildId,
+	phrase: &str,
+	action: AutomationAction,
+	message: Option<&str>,
+	role_id: Option<RoleId>,
+) -> Result<Option<i32>, sqlx::Error> {
+	let db = scripty_db::get_db();
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT (guild_id) DO NOTHING",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	// changing an existing automation doesn't count towards the limit
+	let row = sqlx::query!(
+		"INSERT INTO automations (guild_id, phrase, action, message, role_id) SELECT $1, $2, $3, \
+		 $4, $5 WHERE (SELECT COUNT(*) FROM automations WHERE guild_id = $1 AND phrase <> $2) < \
+		 $6 ON CONFLICT (guild_id, phrase) DO UPDATE SET action = $3, message = $4, role_id = $5 \
+		 RETURNING id",
+		guild_id.get() as i64,
+		phrase.to_lowercase(),
+		action.to_db(),
+		message,
+		role_id.map(|r| r.get() as i64),
+		MAX_AUTOMATIONS
+	)
+	.fetch_optional(db)
+	.await?;
+
+	Ok(row.map(|row| row.id))
+}
+
+/// Remove one of `guild_id`'s automations. Returns whether it existed.
+pub async fn remove_automation(guild_id: GuildId, id: i32) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM automations WHERE guild_id = $1 AND id = $2",
+		guild_id.get() as i64,
+		id
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
+
+/// Whether `guild_id` has a webhook URL for automations to fire.
+pub async fn has_guild_webhook(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT webhook_url FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.map_or(false, |row| row.webhook_url.is_some()))
+}
@@ -0,0 +1,229 @@
+//! Mirroring transcripts to Slack or Matrix, set up with `/config bridge`.
+//!
+//! Each guild can have one bridge, for every session or only those in one voice channel.
+//! Lines are sent in order as they're transcribed: ones that fail to send are dropped,
+//! rather than held up or sent out of order.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use serenity::model::id::{ChannelId, GuildId};
+use tokio::sync::mpsc;
+
+static BRIDGE_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+	reqwest::Client::builder()
+		.timeout(Duration::from_secs(10))
+		// the URL was checked when it was set, but where it redirects to wasn't
+		.redirect(reqwest::redirect::Policy::none())
+		.build()
+		.expect("static client config is valid")
+});
+
+/// Not `Debug`, so access tokens can't end up in logs.
+#[derive(Clone)]
+pub enum BridgeTarget {
+	/// A Slack incoming webhook.
+	Slack { webhook_url: String },
+	/// A Matrix room, posted to as the user `access_token` belongs to.
+	Matrix {
+		homeserver:   String,
+		room_id:      String,
+		access_token: String,
+	},
+}
+
+impl BridgeTarget {
+	fn kind(&self) -> i16 {
+		match self {
+			Self::Slack { .. } => 0,
+			Self::Matrix { .. } => 1,
+		}
+	}
+
+	fn from_row(
+		kind: i16,
+		url: String,
+		matrix_room_id: Option<String>,
+		matrix_access_token: Option<String>,
+	) -> Option<Self> {
+		match kind {
+			0 => Some(Self::Slack { webhook_url: url }),
+			1 => Some(Self::Matrix {
+				homeserver:   url,
+				room_id:      matrix_room_id?,
+				access_token: matrix_access_token?,
+			}),
+			_ => None,
+		}
+	}
+
+	/// Post what `speaker` said.
+	pub async fn send(&self, speaker: &str, content: &str) -> Result<(), reqwest::Error> {
+		let req = match self {
+			Self::Slack { webhook_url } => {
+				let text = format!("*{}*: {}", escape_slack(speaker), escape_slack(content));
+				BRIDGE_CLIENT
+					.post(webhook_url)
+					.json(&serde_json::json!({ "text": text }))
+			}
+			Self::Matrix {
+				homeserver,
+				room_id,
+				access_token,
+			} => {
+				// Matrix dedupes by transaction ID, so it must be unique per message
+				let url = format!(
+					"{}/_matrix/client/v3/rooms/{}/send/m.room.message/scripty-{}",
+					homeserver.trim_end_matches('/'),
+					urlencode(room_id),
+					uuid::Uuid::new_v4().simple()
+				);
+				BRIDGE_CLIENT
+					.put(url)
+					.bearer_auth(access_token)
+					.json(&serde_json::json!({
+						"msgtype": "m.text",
+						"body": format!("{}: {}", speaker, content),
+						"format": "org.matrix.custom.html",
+						"formatted_body": format!(
+							"<b>{}</b>: {}",
+							escape_html(speaker),
+							escape_html(content)
+						),
+					}))
+			}
+		};
+		req.send().await?.error_for_status()?;
+		Ok(())
+	}
+}
+
+/// Slack only needs these three escaped, so its formatting isn't triggered by accident.
+fn escape_slack(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+fn escape_html(text: &str) -> String {
+	escape_slack(text)
+		.replace('"', "&quot;")
+		.replace('\'', "&#39;")
+}
+
+/// Room IDs look like `!abc:example.org`, so they need escaping to go in a path.
+fn urlencode(text: &str) -> String {
+	text.bytes()
+		.map(|b| match b {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+				(b as char).to_string()
+			}
+			b => format!("%{:02X}", b),
+		})
+		.collect()
+}
+
+/// Get `guild_id`'s bridge, if it has one that applies to sessions in `voice_channel_id`.
+pub async fn get_bridge(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+) -> Result<Option<BridgeTarget>, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT kind, url, matrix_room_id, matrix_access_token FROM transcript_bridges WHERE \
+		 guild_id = $1 AND (voice_channel_id IS NULL OR voice_channel_id = $2)",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.and_then(|row| {
+		BridgeTarget::from_row(
+			row.kind,
+			row.url,
+			row.matrix_room_id,
+			row.matrix_access_token,
+		)
+	}))
+}
+
+/// Bridge `guild_id`'s sessions in `voice_channel_id`, or all of them if it's None,
+/// to `target`. Replaces any bridge it already has.
+pub async fn set_bridge(
+	guild_id: GuildId,
+	voice_channel_id: Option<ChannelId>,
+	target: &BridgeTarget,
+) -> Result<(), sqlx::Error> {
+	let (url, matrix_room_id, matrix_access_token) = match target {
+		BridgeTarget::Slack { webhook_url } => (webhook_url.as_str(), None, None),
+		BridgeTarget::Matrix {
+			homeserver,
+			room_id,
+			access_token,
+		} => (
+			homeserver.as_str(),
+			Some(room_id.as_str()),
+			Some(access_token.as_str()),
+		),
+	};
+
+	sqlx::query!(
+		"INSERT INTO transcript_bridges (guild_id, voice_channel_id, kind, url, matrix_room_id, \
+		 matrix_access_token) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (guild_id) DO UPDATE \
+		 SET voice_channel_id = $2, kind = $3, url = $4, matrix_room_id = $5, matrix_access_token \
+		 = $6",
+		guild_id.get() as i64,
+		voice_channel_id.map(|id| id.get() as i64),
+		target.kind(),
+		url,
+		matrix_room_id,
+		matrix_access_token
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(())
+}
+
+/// Stop bridging `guild_id`'s sessions. Returns whether it had a bridge.
+///
+/// Sessions that are already going keep being bridged until they end.
+pub async fn remove_bridge(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM transcript_bridges WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
+
+/// Sends one session's lines to its bridge, in order, in the background.
+///
+/// Stops once it's dropped and the lines already given to it are sent.
+#[derive(Debug)]
+pub struct BridgeSender {
+	tx: mpsc::UnboundedSender<(String, String)>,
+}
+
+impl BridgeSender {
+	pub fn new(guild_id: GuildId, target: BridgeTarget) -> Self {
+		let (tx, mut rx) = mpsc::unbounded_channel::<(String, String)>();
+		crate::spawn_logged("transcript_bridge", async move {
+			while let Some((speaker, content)) = rx.recv().await {
+				if let Err(e) = target.send(&speaker, &content).await {
+					debug!(%guild_id, "failed to bridge transcript line: {}", e);
+				}
+			}
+		});
+		Self { tx }
+	}
+
+	/// Queue what `speaker` said to be sent.
+	pub fn send(&self, speaker: String, content: String) {
+		// only fails if the task panicked, which is already logged
+		let _ = self.tx.send((speaker, content));
+	}
+}
@@ -0,0 +1,519 @@
+//! Durable delivery of outbound messages.
+//!
+//! Messages are sent immediately if possible. If Discord is having issues (5xx, rate limits,
+//! or the request not going through at all) they are persisted to Postgres,
+//! and retried with backoff by `replay_pending` until they go through or are given up on.
+//!
+//! Each delivery has a dedupe key: queueing the same key twice is a no-op,
+//! and channel and DM messages use it as an enforced nonce, so Discord drops replayed duplicates.
+//! Webhooks don't support nonces, so a crash between sending and dequeueing may duplicate one.
+//!
+//! Webhook tokens aren't stored: queued webhook deliveries look the token up by the webhook's ID
+//! when they're sent, which works because the bot owns every webhook it sends to.
+//! Each target's deliveries are replayed in the order they were queued, so transcripts stay in
+//! order even if some of them had to be retried.
+//!
+//! Guild webhooks (set with `/config webhook_url`) aren't Discord at all: they're POSTed to
+//! the guild's own endpoint, signed with its secret, and retried on the same terms.
+//! They get the dedupe key in `X-Scripty-Delivery`, so receivers can drop duplicates themselves.
+
+use std::{collections::HashMap, fmt, time::Duration};
+
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serenity::{
+	http::{Http, HttpError, StatusCode},
+	model::{
+		id::{ChannelId, GuildId, MessageId, UserId, WebhookId},
+		webhook::Webhook,
+	},
+};
+use sha2::{Digest, Sha256};
+
+/// How many times a delivery is retried before it's dropped.
+const MAX_ATTEMPTS: i32 = 12;
+
+/// Maximum number of queued deliveries retried in one call to `replay_pending`.
+const MAX_REPLAYS_PER_CALL: i64 = 50;
+
+/// Guild endpoints are slow or down far more often than Discord, so don't wait on them long.
+static GUILD_WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+	reqwest::Client::builder()
+		.timeout(Duration::from_secs(10))
+		// the URL was checked when it was set, but where it redirects to wasn't
+		.redirect(reqwest::redirect::Policy::none())
+		.user_agent("Scripty-Webhook/1.0")
+		.build()
+		.expect("static client config is valid")
+});
+
+#[derive(Debug, Clone)]
+pub enum DeliveryTarget {
+	Webhook {
+		id:        WebhookId,
+		token:     String,
+		thread_id: Option<ChannelId>,
+	},
+	Channel(ChannelId),
+	Dm(UserId),
+	/// The guild's configured webhook URL.
+	///
+	/// The URL and secret are looked up when sending, so queued deliveries follow changes to them,
+	/// and are dropped if the webhook is removed.
+	GuildWebhook(GuildId),
+}
+
+impl DeliveryTarget {
+	/// Target a webhook, optionally in a thread. Returns None if the webhook has no token.
+	pub fn webhook(webhook: &Webhook, thread_id: Option<ChannelId>) -> Option<Self> {
+		let url = webhook.url().ok()?;
+		let token = url.rsplit('/').next()?.to_string();
+		Some(Self::Webhook {
+			id: webhook.id,
+			token,
+			thread_id,
+		})
+	}
+
+	fn kind(&self) -> i16 {
+		match self {
+			Self::Webhook { .. } => 0,
+			Self::Channel(_) => 1,
+			Self::Dm(_) => 2,
+			Self::GuildWebhook(_) => 3,
+		}
+	}
+
+	fn id(&self) -> i64 {
+		match self {
+			Self::Webhook { id, .. } => id.get() as i64,
+			Self::Channel(id) => id.get() as i64,
+			Self::Dm(id) => id.get() as i64,
+			Self::GuildWebhook(id) => id.get() as i64,
+		}
+	}
+
+	/// Rebuild a queued delivery's target. Webhooks' tokens are looked up with `http`.
+	///
+	/// Returns None if the row is malformed, or its webhook was deleted.
+	async fn from_row(
+		http: &Http,
+		kind: i16,
+		id: i64,
+		thread_id: Option<i64>,
+	) -> Result<Option<Self>, SendError> {
+		let Some(id) = u64::try_from(id).ok().filter(|id| *id != 0) else {
+			return Ok(None);
+		};
+		Ok(match kind {
+			0 => {
+				let thread_id = thread_id
+					.and_then(|t| u64::try_from(t).ok())
+					.filter(|t| *t != 0)
+					.map(ChannelId::new);
+				match http.get_webhook(WebhookId::new(id)).await {
+					Ok(webhook) => Self::webhook(&webhook, thread_id),
+					Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(res)))
+						if res.status_code == StatusCode::NOT_FOUND =>
+					{
+						None
+					}
+					Err(e) => return Err(e.into()),
+				}
+			}
+			1 => Some(Self::Channel(ChannelId::new(id))),
+			2 => Some(Self::Dm(UserId::new(id))),
+			3 => Some(Self::GuildWebhook(GuildId::new(id))),
+			_ => None,
+		})
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeliveryStatus {
+	/// The message was delivered.
+	Sent,
+	/// The target couldn't take the message right now; it will be retried later.
+	Queued,
+}
+
+/// Send `body` to `target`, queueing it for later if it's having issues.
+///
+/// `dedupe_key` must uniquely identify this message. Non-transient errors are returned as-is,
+/// and the message is not queued.
+pub async fn deliver(
+	http: &Http,
+	target: DeliveryTarget,
+	body: &impl Serialize,
+	dedupe_key: &str,
+) -> Result<DeliveryStatus, DeliveryError> {
+	let body = with_nonce(&target, serde_json::to_value(body)?, dedupe_key);
+
+	match send(http, &target, &body, dedupe_key).await {
+		Ok(()) => Ok(DeliveryStatus::Sent),
+		Err(e) if is_transient(&e) => {
+			warn!(%dedupe_key, "failed to deliver message, queueing for retry: {}", e);
+			enqueue(&target, &body, dedupe_key).await?;
+			Ok(DeliveryStatus::Queued)
+		}
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Like `deliver`, but waits for webhooks to create the message and returns its ID,
+/// so it can be edited later. The ID is None if the message was queued, or not sent to a webhook.
+pub async fn deliver_with_id(
+	http: &Http,
+	target: DeliveryTarget,
+	body: &impl Serialize,
+	dedupe_key: &str,
+) -> Result<(DeliveryStatus, Option<MessageId>), DeliveryError> {
+	let DeliveryTarget::Webhook {
+		id,
+		ref token,
+		thread_id,
+	} = target
+	else {
+		return deliver(http, target, body, dedupe_key)
+			.await
+			.map(|status| (status, None));
+	};
+	let body = serde_json::to_value(body)?;
+
+	match http
+		.execute_webhook(id, thread_id, token, true, vec![], &body)
+		.await
+		.map_err(SendError::from)
+	{
+		Ok(message) => Ok((DeliveryStatus::Sent, message.map(|m| m.id))),
+		Err(e) if is_transient(&e) => {
+			warn!(%dedupe_key, "failed to deliver message, queueing for retry: {}", e);
+			enqueue(&target, &body, dedupe_key).await?;
+			Ok((DeliveryStatus::Queued, None))
+		}
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Retry queued deliveries that are due. Safe to call from several processes at once.
+pub async fn replay_pending(http: &Http) -> Result<(), sqlx::Error> {
+	let db = scripty_db::get_db();
+
+	// claim a batch, so no other process retries the same deliveries at the same time.
+	// deliveries waiting behind an earlier one to the same target that isn't due yet are left,
+	// so they can't overtake it
+	let mut rows = sqlx::query!(
+		"UPDATE delivery_queue SET locked_until = NOW() + INTERVAL '2 minutes' WHERE id IN \
+		 (SELECT id FROM delivery_queue q WHERE next_attempt <= NOW() AND (locked_until IS NULL \
+		 OR locked_until < NOW()) AND NOT EXISTS (SELECT 1 FROM delivery_queue e WHERE \
+		 e.target_kind = q.target_kind AND e.target_id = q.target_id AND (e.created_at, e.id) < \
+		 (q.created_at, q.id) AND e.next_attempt > NOW()) ORDER BY next_attempt LIMIT $1 FOR \
+		 UPDATE SKIP LOCKED) RETURNING id, dedupe_key, target_kind, target_id, thread_id, body, \
+		 attempts, created_at",
+		MAX_REPLAYS_PER_CALL
+	)
+	.fetch_all(db)
+	.await?;
+	// each target's deliveries are sent in the order they were queued
+	rows.sort_by_key(|row| (row.target_kind, row.target_id, row.created_at, row.id));
+
+	// targets that failed in this batch, and when they're retried:
+	// their later deliveries wait until then too
+	let mut failed_targets = HashMap::new();
+	for row in rows {
+		if let Some(backoff_secs) = failed_targets
+			.get(&(row.target_kind, row.target_id))
+			.copied()
+		{
+			sqlx::query!(
+				"UPDATE delivery_queue SET next_attempt = NOW() + make_interval(secs => $2), \
+				 locked_until = NULL WHERE id = $1",
+				row.id,
+				backoff_secs
+			)
+			.execute(db)
+			.await?;
+			continue;
+		}
+
+		let Ok(body) = serde_json::from_str::<serde_json::Value>(&row.body) else {
+			error!(dedupe_key = %row.dedupe_key, "dropping malformed queued delivery");
+			sqlx::query!("DELETE FROM delivery_queue WHERE id = $1", row.id)
+				.execute(db)
+				.await?;
+			continue;
+		};
+
+		let res =
+			match DeliveryTarget::from_row(http, row.target_kind, row.target_id, row.thread_id)
+				.await
+			{
+				Ok(Some(target)) => send(http, &target, &body, &row.dedupe_key).await,
+				Ok(None) => {
+					// malformed, or the webhook was deleted since this was queued
+					debug!(
+						dedupe_key = %row.dedupe_key,
+						"queued delivery has no target, dropping it"
+					);
+					Ok(())
+				}
+				Err(e) => Err(e),
+			};
+		let attempts = row.attempts + 1;
+		match res {
+			Ok(()) => {
+				debug!(dedupe_key = %row.dedupe_key, %attempts, "delivered queued message");
+			}
+			Err(e) if is_transient(&e) && attempts < MAX_ATTEMPTS => {
+				// 2s, 4s, 8s... capped at 10 minutes
+				let backoff_secs = 2f64.powi(attempts).min(600.0);
+				warn!(
+					dedupe_key = %row.dedupe_key,
+					%attempts,
+					"queued delivery failed again, retrying in {}s: {}", backoff_secs, e
+				);
+				sqlx::query!(
+					"UPDATE delivery_queue SET attempts = $2, next_attempt = NOW() + \
+					 make_interval(secs => $3), locked_until = NULL WHERE id = $1",
+					row.id,
+					attempts,
+					backoff_secs
+				)
+				.execute(db)
+				.await?;
+				failed_targets.insert((row.target_kind, row.target_id), backoff_secs);
+				continue;
+			}
+			Err(e) => {
+				error!(
+					dedupe_key = %row.dedupe_key,
+					%attempts,
+					"giving up on queued delivery: {}", e
+				);
+			}
+		}
+
+		sqlx::query!("DELETE FROM delivery_queue WHERE id = $1", row.id)
+			.execute(db)
+			.await?;
+	}
+
+	Ok(())
+}
+
+async fn enqueue(
+	target: &DeliveryTarget,
+	body: &serde_json::Value,
+	dedupe_key: &str,
+) -> Result<(), sqlx::Error> {
+	let thread_id = match target {
+		DeliveryTarget::Webhook { thread_id, .. } => thread_id.map(|t| t.get() as i64),
+		_ => None,
+	};
+
+	sqlx::query!(
+		"INSERT INTO delivery_queue (dedupe_key, target_kind, target_id, thread_id, body) VALUES \
+		 ($1, $2, $3, $4, $5) ON CONFLICT (dedupe_key) DO NOTHING",
+		dedupe_key,
+		target.kind(),
+		target.id(),
+		thread_id,
+		body.to_string()
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(())
+}
+
+/// Why sending a delivery failed.
+#[derive(Debug)]
+enum SendError {
+	Discord(serenity::Error),
+	Http(reqwest::Error),
+	Db(sqlx::Error),
+}
+
+impl From<serenity::Error> for SendError {
+	fn from(e: serenity::Error) -> Self {
+		Self::Discord(e)
+	}
+}
+
+impl From<reqwest::Error> for SendError {
+	fn from(e: reqwest::Error) -> Self {
+		Self::Http(e)
+	}
+}
+
+impl From<sqlx::Error> for SendError {
+	fn from(e: sqlx::Error) -> Self {
+		Self::Db(e)
+	}
+}
+
+impl fmt::Display for SendError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Discord(e) => e.fmt(f),
+			Self::Http(e) => e.fmt(f),
+			Self::Db(e) => write!(f, "failed to look up guild webhook: {}", e),
+		}
+	}
+}
+
+async fn send(
+	http: &Http,
+	target: &DeliveryTarget,
+	body: &serde_json::Value,
+	dedupe_key: &str,
+) -> Result<(), SendError> {
+	match target {
+		DeliveryTarget::Webhook {
+			id,
+			token,
+			thread_id,
+		} => {
+			http.execute_webhook(*id, *thread_id, token, false, vec![], body)
+				.await?;
+		}
+		DeliveryTarget::Channel(channel_id) => {
+			http.send_message(*channel_id, vec![], body).await?;
+		}
+		DeliveryTarget::Dm(user_id) => {
+			let channel = user_id.create_dm_channel(http).await?;
+			http.send_message(channel.id, vec![], body).await?;
+		}
+		DeliveryTarget::GuildWebhook(guild_id) => {
+			send_guild_webhook(*guild_id, body, dedupe_key).await?;
+		}
+	}
+	Ok(())
+}
+
+/// POST `body` to the guild's webhook URL, signed with its secret.
+async fn send_guild_webhook(
+	guild_id: GuildId,
+	body: &serde_json::Value,
+	dedupe_key: &str,
+) -> Result<(), SendError> {
+	let guild = sqlx::query!(
+		"SELECT webhook_url, webhook_secret FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+	let Some((url, secret)) = guild.and_then(|guild| guild.webhook_url.zip(guild.webhook_secret))
+	else {
+		// removed since this was queued, so there's nowhere to send it
+		debug!(%guild_id, %dedupe_key, "guild has no webhook, dropping delivery");
+		return Ok(());
+	};
+
+	let body = body.to_string();
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+	mac.update(body.as_bytes());
+	let signature = hex::encode(mac.finalize().into_bytes());
+
+	GUILD_WEBHOOK_CLIENT
+		.post(url)
+		.header(reqwest::header::CONTENT_TYPE, "application/json")
+		.header("X-Scripty-Delivery", dedupe_key)
+		.header("X-Scripty-Signature", format!("sha256={}", signature))
+		.body(body)
+		.send()
+		.await?
+		.error_for_status()?;
+	Ok(())
+}
+
+/// Channel messages support nonces: with `enforce_nonce` set,
+/// Discord drops a message if one with the same nonce was sent in the last few minutes.
+fn with_nonce(
+	target: &DeliveryTarget,
+	mut body: serde_json::Value,
+	dedupe_key: &str,
+) -> serde_json::Value {
+	if !matches!(target, DeliveryTarget::Channel(_) | DeliveryTarget::Dm(_)) {
+		return body;
+	}
+	if let Some(map) = body.as_object_mut() {
+		// nonces are limited to 25 characters
+		let mut nonce = hex::encode(Sha256::digest(dedupe_key.as_bytes()));
+		nonce.truncate(25);
+		map.insert("nonce".to_string(), nonce.into());
+		map.insert("enforce_nonce".to_string(), true.into());
+	}
+	body
+}
+
+/// Whether an error is likely to go away on its own: server errors, rate limits,
+/// and requests that never made it to the other end.
+fn is_transient(err: &SendError) -> bool {
+	match err {
+		SendError::Discord(serenity::Error::Http(HttpError::UnsuccessfulRequest(res))) => {
+			res.status_code.is_server_error() || res.status_code == StatusCode::TOO_MANY_REQUESTS
+		}
+		SendError::Discord(serenity::Error::Http(HttpError::Request(_))) => true,
+		SendError::Discord(_) => false,
+		SendError::Http(e) => match e.status() {
+			Some(status) => {
+				status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+			}
+			// a bad URL won't fix itself, but timeouts and refused connections might
+			None => !e.is_builder(),
+		},
+		SendError::Db(_) => true,
+	}
+}
+
+#[derive(Debug)]
+pub enum DeliveryError {
+	Serenity(serenity::Error),
+	Http(reqwest::Error),
+	Json(serde_json::Error),
+	Db(sqlx::Error),
+}
+
+impl From<SendError> for DeliveryError {
+	fn from(e: SendError) -> Self {
+		match e {
+			SendError::Discord(e) => Self::Serenity(e),
+			SendError::Http(e) => Self::Http(e),
+			SendError::Db(e) => Self::Db(e),
+		}
+	}
+}
+
+impl From<serenity::Error> for DeliveryError {
+	fn from(e: serenity::Error) -> Self {
+		Self::Serenity(e)
+	}
+}
+
+impl From<serde_json::Error> for DeliveryError {
+	fn from(e: serde_json::Error) -> Self {
+		Self::Json(e)
+	}
+}
+
+impl From<sqlx::Error> for DeliveryError {
+	fn from(e: sqlx::Error) -> Self {
+		Self::Db(e)
+	}
+}
+
+impl fmt::Display for DeliveryError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Serenity(e) => write!(f, "failed to send message: {}", e),
+			Self::Http(e) => write!(f, "failed to send webhook: {}", e),
+			Self::Json(e) => write!(f, "failed to serialize message: {}", e),
+			Self::Db(e) => write!(f, "failed to queue message: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for DeliveryError {}
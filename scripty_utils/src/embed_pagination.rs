@@ -3,6 +3,7 @@ use std::{str::FromStr, time::Duration};
 use serenity::{
 	all::{
 		ButtonStyle,
+		ComponentInteractionDataKind,
 		CreateQuickModal,
 		InputTextStyle,
 		InteractionResponseFlags,
@@ -17,12 +18,51 @@ use serenity::{
 		CreateInteractionResponse,
 		CreateInteractionResponseMessage,
 		CreateMessage,
+		CreateSelectMenu,
+		CreateSelectMenuKind,
+		CreateSelectMenuOption,
+		EditMessage,
 	},
 	collector::ComponentInteractionCollector,
 	futures::StreamExt,
-	model::id::{ChannelId, UserId},
+	model::id::{ChannelId, MessageId, UserId},
 };
 
+/// Discord allows at most 25 options in a select menu.
+const MAX_SECTIONS: usize = 25;
+
+/// Select menu option labels are limited to 100 characters.
+const MAX_SECTION_LABEL_LEN: usize = 100;
+
+/// Options for `do_paginate_with_options`.
+#[derive(Debug, Clone)]
+pub struct PaginateOptions {
+	/// Shown after the page number in the footer.
+	pub footer_additional: Option<String>,
+	/// Between 1 and 20. Defaults to 10.
+	pub max_per_page:      usize,
+	/// If set, only this user can use the controls.
+	pub allowed_user:      Option<UserId>,
+	/// How long to wait for someone to use the controls before removing them.
+	/// Each interaction resets the timer. Defaults to 2 minutes.
+	pub idle_timeout:      Duration,
+	/// Named sections to jump between with a select menu, as (name, index of the first item).
+	/// Only the first 25 are shown.
+	pub sections:          Vec<(String, usize)>,
+}
+
+impl Default for PaginateOptions {
+	fn default() -> Self {
+		Self {
+			footer_additional: None,
+			max_per_page:      10,
+			allowed_user:      None,
+			idle_timeout:      Duration::from_secs(120),
+			sections:          Vec::new(),
+		}
+	}
+}
+
 pub async fn do_paginate(
 	ctx: &serenity::client::Context,
 	target_channel: ChannelId,
@@ -32,124 +72,235 @@ pub async fn do_paginate(
 	max_per_page: Option<usize>,
 	allowed_user: Option<UserId>,
 ) -> Result<(), serenity::Error> {
-	let max_per_page = max_per_page.unwrap_or(10);
-	assert!(max_per_page > 0);
-	assert!(max_per_page <= 20);
-
-	// split items into pages
-	let pages = items
-		.chunks(max_per_page)
-		.map(|x| x.to_owned())
-		.collect::<Vec<_>>();
+	do_paginate_with_options(
+		ctx,
+		target_channel,
+		items,
+		title,
+		PaginateOptions {
+			footer_additional,
+			max_per_page: max_per_page.unwrap_or(10),
+			allowed_user,
+			..Default::default()
+		},
+	)
+	.await
+}
 
-	let base_embed = CreateEmbed::default().title(title);
-	let mut current_page = 0;
+/// Send `items` as a paginated embed, with buttons to move between pages,
+/// a modal to jump to a page number, and optionally a select menu to jump to a named section.
+///
+/// Returns once the controls time out.
+pub async fn do_paginate_with_options(
+	ctx: &serenity::client::Context,
+	target_channel: ChannelId,
+	items: Vec<(String, String)>,
+	title: String,
+	options: PaginateOptions,
+) -> Result<(), serenity::Error> {
+	let pagination = Pagination::new(items, title, options);
 
-	let m = target_channel
+	let mut m = target_channel
 		.send_message(
 			ctx,
 			CreateMessage::default()
-				.embed(format_embed_from_page(
-					base_embed.clone(),
-					&pages[0],
-					current_page,
-					pages.len(),
-					footer_additional.clone(),
-				))
-				.components(build_components()),
+				.embed(pagination.embed(0))
+				.components(pagination.components()),
 		)
 		.await?;
 
-	let mut collector = ComponentInteractionCollector::new(&ctx.shard)
-		.message_id(m.id)
-		.timeout(Duration::from_secs(120));
-	if let Some(user) = allowed_user {
-		collector = collector.author_id(user);
+	pagination.run(ctx, m.id).await?;
+
+	// nobody can use the controls anymore, so don't leave them there
+	if let Err(e) = m.edit(ctx, EditMessage::default().components(vec![])).await {
+		debug!("failed to remove pagination controls: {}", e);
 	}
-	let mut c = collector.stream();
-
-	// need StreamExt::next since otherwise types don't resolve
-	while let Some(c) = StreamExt::next(&mut c).await {
-		let did_respond = match c.data.custom_id.as_str() {
-			"first_page" => {
-				current_page = 0;
-				false
-			}
-			"previous_page" => {
-				current_page = current_page.saturating_sub(1);
-				false
-			}
-			"next_page" => {
-				if current_page < pages.len() - 1 {
-					current_page += 1;
-				}
-				false
-			}
-			"last_page" => {
-				current_page = pages.len() - 1;
-				false
-			}
-			"pick_page" => {
-				let modal = CreateQuickModal::new("Pick a page")
-					.field(
-						CreateInputText::new(InputTextStyle::Short, "Page number", "pg_n")
-							.required(true),
-					)
-					.timeout(Duration::from_secs(30));
 
-				let response = c.quick_modal(ctx, modal).await?;
+	Ok(())
+}
+
+/// Pages of items, for messages `do_paginate_with_options` can't send itself, like ephemeral
+/// replies.
+///
+/// Send a message with `embed(0)` and `components()`, then call `run` with its ID.
+pub struct Pagination {
+	pages:             Vec<Vec<(String, String)>>,
+	sections:          Vec<(String, usize)>,
+	base_embed:        CreateEmbed,
+	footer_additional: Option<String>,
+	allowed_user:      Option<UserId>,
+	idle_timeout:      Duration,
+}
+
+impl Pagination {
+	pub fn new(items: Vec<(String, String)>, title: String, options: PaginateOptions) -> Self {
+		let PaginateOptions {
+			footer_additional,
+			max_per_page,
+			allowed_user,
+			idle_timeout,
+			mut sections,
+		} = options;
+		assert!(max_per_page > 0);
+		assert!(max_per_page <= 20);
 
-				if let Some(QuickModalResponse {
-					interaction,
-					inputs,
-				}) = response
-				{
-					interaction
-						.create_response(ctx, CreateInteractionResponse::Acknowledge)
-						.await?;
+		// split items into pages
+		let mut pages = items
+			.chunks(max_per_page)
+			.map(|x| x.to_owned())
+			.collect::<Vec<_>>();
+		if pages.is_empty() {
+			pages.push(Vec::new());
+		}
+
+		// sections point at items, but we jump to pages
+		sections.truncate(MAX_SECTIONS);
+		let sections = sections
+			.into_iter()
+			.map(|(name, item)| (name, (item / max_per_page).min(pages.len() - 1)))
+			.collect::<Vec<_>>();
 
-					if let Some(Ok(page)) = inputs.first().map(|x| usize::from_str(x)) {
-						if page > 0 && page <= pages.len() {
+		Self {
+			pages,
+			sections,
+			base_embed: CreateEmbed::default().title(title),
+			footer_additional,
+			allowed_user,
+			idle_timeout,
+		}
+	}
+
+	/// The embed showing `page`.
+	pub fn embed(&self, page: usize) -> CreateEmbed {
+		format_embed_from_page(
+			self.base_embed.clone(),
+			&self.pages[page],
+			page,
+			self.pages.len(),
+			self.footer_additional.clone(),
+		)
+	}
+
+	/// The controls to attach to the message.
+	pub fn components(&self) -> Vec<CreateActionRow> {
+		build_components(&self.sections)
+	}
+
+	/// Handle the controls on `message` until they time out.
+	///
+	/// Returns the page shown last, since the caller has to remove the controls.
+	pub async fn run(
+		&self,
+		ctx: &serenity::client::Context,
+		message: MessageId,
+	) -> Result<usize, serenity::Error> {
+		let pages = &self.pages;
+		let mut current_page = 0;
+		let render_page = |page: usize| {
+			CreateInteractionResponseMessage::default()
+				.components(self.components())
+				.embed(self.embed(page))
+		};
+
+		let mut collector = ComponentInteractionCollector::new(&ctx.shard).message_id(message);
+		if let Some(user) = self.allowed_user {
+			collector = collector.author_id(user);
+		}
+		let mut c = collector.stream();
+
+		// the collector's own timeout counts from when it started, but we want to reset it on
+		// every interaction, so time out each wait separately
+		// need StreamExt::next since otherwise types don't resolve
+		while let Ok(Some(c)) =
+			tokio::time::timeout(self.idle_timeout, StreamExt::next(&mut c)).await
+		{
+			let did_respond = match c.data.custom_id.as_str() {
+				"first_page" => {
+					current_page = 0;
+					false
+				}
+				"previous_page" => {
+					current_page = current_page.saturating_sub(1);
+					false
+				}
+				"next_page" => {
+					if current_page < pages.len() - 1 {
+						current_page += 1;
+					}
+					false
+				}
+				"last_page" => {
+					current_page = pages.len() - 1;
+					false
+				}
+				"pick_section" => {
+					if let ComponentInteractionDataKind::StringSelect { values } = &c.data.kind {
+						if let Some(Ok(section)) = values.first().map(|x| usize::from_str(x)) {
+							if let Some((_, page)) = self.sections.get(section) {
+								current_page = *page;
+							}
+						}
+					}
+					false
+				}
+				"pick_page" => {
+					let modal = CreateQuickModal::new("Pick a page")
+						.field(
+							CreateInputText::new(InputTextStyle::Short, "Page number", "pg_n")
+								.placeholder(format!("1-{}", pages.len()))
+								.required(true),
+						)
+						.timeout(Duration::from_secs(30));
+
+					let response = c.quick_modal(ctx, modal).await?;
+
+					if let Some(QuickModalResponse {
+						interaction,
+						inputs,
+					}) = response
+					{
+						let page = inputs
+							.first()
+							.and_then(|x| usize::from_str(x.trim()).ok())
+							.filter(|page| *page > 0 && *page <= pages.len());
+						if let Some(page) = page {
 							current_page = page - 1;
 						}
-					};
+
+						interaction
+							.create_response(
+								ctx,
+								CreateInteractionResponse::UpdateMessage(render_page(current_page)),
+							)
+							.await?;
+					}
+					true
 				}
-				true
-			}
-			_ => {
+				_ => {
+					c.create_response(
+						&ctx,
+						CreateInteractionResponse::Message(
+							CreateInteractionResponseMessage::default()
+								.content("internal error")
+								.flags(InteractionResponseFlags::EPHEMERAL),
+						),
+					)
+					.await?;
+					true
+				}
+			};
+
+			if !did_respond {
 				c.create_response(
 					&ctx,
-					CreateInteractionResponse::Message(
-						CreateInteractionResponseMessage::default()
-							.content("internal error")
-							.flags(InteractionResponseFlags::EPHEMERAL),
-					),
+					CreateInteractionResponse::UpdateMessage(render_page(current_page)),
 				)
 				.await?;
-				true
 			}
-		};
-
-		if !did_respond {
-			c.create_response(
-				&ctx,
-				CreateInteractionResponse::UpdateMessage(
-					CreateInteractionResponseMessage::default()
-						.components(build_components())
-						.embed(format_embed_from_page(
-							base_embed.clone(),
-							&pages[current_page],
-							current_page,
-							pages.len(),
-							footer_additional.clone(),
-						)),
-				),
-			)
-			.await?;
 		}
-	}
 
-	Ok(())
+		Ok(current_page)
+	}
 }
 
 fn format_embed_from_page(
@@ -163,7 +314,7 @@ fn format_embed_from_page(
 		.fields(page.iter().map(|(name, value)| (name, value, false)))
 		.footer(CreateEmbedFooter::new(format!(
 			"Page {} of {}{}",
-			page_num,
+			page_num + 1,
 			total_pages,
 			footer_additional
 				.map(|s| format!(" | {}", s))
@@ -171,8 +322,8 @@ fn format_embed_from_page(
 		)))
 }
 
-fn build_components() -> Vec<CreateActionRow> {
-	vec![CreateActionRow::Buttons(vec![
+fn build_components(sections: &[(String, usize)]) -> Vec<CreateActionRow> {
+	let mut rows = vec![CreateActionRow::Buttons(vec![
 		CreateButton::new("first_page")
 			.style(ButtonStyle::Primary)
 			.emoji('⏮'),
@@ -188,5 +339,22 @@ fn build_components() -> Vec<CreateActionRow> {
 		CreateButton::new("pick_page")
 			.style(ButtonStyle::Primary)
 			.emoji('🔢'),
-	])]
+	])];
+
+	if !sections.is_empty() {
+		let options = sections
+			.iter()
+			.enumerate()
+			.map(|(i, (name, _))| {
+				let label: String = name.chars().take(MAX_SECTION_LABEL_LEN).collect();
+				CreateSelectMenuOption::new(label, i.to_string())
+			})
+			.collect();
+		rows.push(CreateActionRow::SelectMenu(
+			CreateSelectMenu::new("pick_section", CreateSelectMenuKind::String { options })
+				.placeholder("Jump to a section"),
+		));
+	}
+
+	rows
 }
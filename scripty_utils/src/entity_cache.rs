@@ -0,0 +1,171 @@
+//! Short-lived Redis cache for Discord REST lookups on the transcription path.
+//!
+//! Redis being down never fails a lookup: it just goes to Discord instead.
+
+use std::{future::Future, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serenity::{
+	builder::CreateWebhook,
+	model::{
+		id::{ChannelId, GuildId, RoleId, UserId, WebhookId},
+		webhook::Webhook,
+	},
+	prelude::Context,
+};
+
+/// Webhooks are only ever deleted by hand, so they can be kept a while.
+const WEBHOOK_TTL: Duration = Duration::from_secs(60 * 10);
+
+/// Short enough that nickname and role changes show up quickly.
+const MEMBER_TTL: Duration = Duration::from_secs(60 * 5);
+
+/// Get `key` from Redis, or run `fetch` and cache its result for `ttl`.
+///
+/// Values are stored as JSON. Errors from `fetch` aren't cached.
+pub async fn get_or_fetch<T, E, Fut>(
+	key: &str,
+	ttl: Duration,
+	fetch: impl FnOnce() -> Fut,
+) -> Result<T, E>
+where
+	T: Serialize + DeserializeOwned,
+	Fut: Future<Output = Result<T, E>>,
+{
+	match scripty_redis::run_transaction::<Option<String>>("GET", |cmd| {
+		cmd.arg(key);
+	})
+	.await
+	{
+		Ok(Some(cached)) => match serde_json::from_str(&cached) {
+			Ok(value) => return Ok(value),
+			Err(e) => warn!(%key, "discarding unreadable cached value: {}", e),
+		},
+		Ok(None) => {}
+		Err(e) => warn!(%key, "failed to read from cache: {}", e),
+	}
+
+	let value = fetch().await?;
+	match serde_json::to_string(&value) {
+		Ok(serialized) => {
+			if let Err(e) = scripty_redis::run_transaction::<()>("SETEX", |cmd| {
+				cmd.arg(key).arg(ttl.as_secs().max(1)).arg(serialized);
+			})
+			.await
+			{
+				warn!(%key, "failed to cache value: {}", e);
+			}
+		}
+		Err(e) => warn!(%key, "failed to serialize value for cache: {}", e),
+	}
+	Ok(value)
+}
+
+/// Drop `key` from the cache, so the next `get_or_fetch` goes to Discord.
+pub async fn invalidate(key: &str) {
+	if let Err(e) = scripty_redis::run_transaction::<()>("DEL", |cmd| {
+		cmd.arg(key);
+	})
+	.await
+	{
+		warn!(%key, "failed to invalidate cached value: {}", e);
+	}
+}
+
+fn webhook_key(channel_id: ChannelId) -> String {
+	format!("channel:{{{}}}:webhook", channel_id)
+}
+
+/// Webhook tokens are secret, so only the webhook's ID is cached, and the webhook is looked up by
+/// it each time. That works because the bot created it, so Discord includes the token.
+#[derive(Serialize, Deserialize)]
+struct CachedWebhook {
+	id: WebhookId,
+}
+
+/// Get a webhook in `channel_id` that the bot can execute, creating one if there isn't any.
+pub async fn get_channel_webhook(
+	ctx: &Context,
+	channel_id: ChannelId,
+) -> serenity::Result<Webhook> {
+	let key = webhook_key(channel_id);
+	let mut fetched = None;
+	let slot = &mut fetched;
+	let cached = get_or_fetch(&key, WEBHOOK_TTL, || async move {
+		let hook = find_or_create_webhook(ctx, channel_id).await?;
+		let cached = CachedWebhook { id: hook.id };
+		*slot = Some(hook);
+		Ok::<_, serenity::Error>(cached)
+	})
+	.await?;
+	if let Some(hook) = fetched {
+		return Ok(hook);
+	}
+
+	match ctx.http.get_webhook(cached.id).await {
+		Ok(hook) if hook.token.is_some() => Ok(hook),
+		res => {
+			// most likely deleted since it was cached
+			if let Err(e) = res {
+				debug!(%channel_id, "failed to fetch cached webhook: {}", e);
+			}
+			invalidate(&key).await;
+			find_or_create_webhook(ctx, channel_id).await
+		}
+	}
+}
+
+async fn find_or_create_webhook(ctx: &Context, channel_id: ChannelId) -> serenity::Result<Webhook> {
+	// thanks to Discord undocumented breaking changes, we have to do this
+	// <3 shitcord
+	let hooks = channel_id.webhooks(ctx).await?;
+	// only webhooks we created have a token
+	match hooks.into_iter().find(|hook| hook.token.is_some()) {
+		Some(hook) => Ok(hook),
+		None => {
+			channel_id
+				.create_webhook(ctx, CreateWebhook::new("Scripty Transcriptions"))
+				.await
+		}
+	}
+}
+
+/// Forget the cached webhook for `channel_id`, ie after executing it failed because it was deleted.
+pub async fn forget_channel_webhook(channel_id: ChannelId) {
+	invalidate(&webhook_key(channel_id)).await;
+}
+
+/// What transcripts need to know about who's speaking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberDetails {
+	/// Server nickname, falling back to global display name, then username.
+	pub display_name: String,
+	/// Server avatar, falling back to global avatar, then default avatar.
+	pub avatar_url:   String,
+	pub bot:          bool,
+	pub roles:        Vec<RoleId>,
+}
+
+/// Get the details of a member of `guild_id`, for attributing transcripts to them.
+pub async fn get_member_details(
+	ctx: &Context,
+	guild_id: GuildId,
+	user_id: UserId,
+) -> serenity::Result<MemberDetails> {
+	// user IDs are only ever stored hashed
+	let key = format!(
+		"guild:{{{}}}:member:{}",
+		guild_id,
+		hex::encode(crate::hash_user_id(user_id.get()))
+	);
+	get_or_fetch(&key, MEMBER_TTL, || async {
+		let member = guild_id.member(ctx, user_id).await?;
+		Ok(MemberDetails {
+			display_name: member.display_name().to_string(),
+			avatar_url:   member.face(),
+			bot:          member.user.bot,
+			roles:        member.roles.to_vec(),
+		})
+	})
+	.await
+}
@@ -1,17 +1,208 @@
-use sha2::Digest;
+use std::collections::HashSet;
+
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha512};
+
+/// Most users `REHASHED` remembers. Once it's full it's cleared: checking a user again is
+/// harmless, just a few queries that change nothing.
+const MAX_REHASHED: usize = 100_000;
+
+/// Users whose data has recently been checked for legacy hashes.
+static REHASHED: Lazy<Mutex<HashSet<u64>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Hashes a user ID with HMAC-SHA512, keyed with `user_id_hash_key`, and returns the hash.
+///
+/// Falls back to `legacy_hash_user_id` if no key is configured.
+pub fn hash_user_id(user_id: u64) -> Vec<u8> {
+	match scripty_config::get_config().user_id_hash_key.as_deref() {
+		Some(key) => keyed_hash_user_id(key, user_id),
+		None => legacy_hash_user_id(user_id),
+	}
+}
 
 /// Hashes a user ID with sha512 and returns the hash.
 ///
+/// This is how user IDs were hashed before `user_id_hash_key` existed.
+/// It's only kept around to find data stored that way: use `hash_user_id` instead.
+///
 /// # Examples
 /// ```
-/// use scripty_utils::hash_user_id;
+/// use scripty_utils::legacy_hash_user_id;
 /// # fn main() {
 /// let user_id = 123456789;
-/// let hash = hash_user_id(user_id);
+/// let hash = legacy_hash_user_id(user_id);
 /// assert_eq!(hash, b"d9e6762dd1c8eaf6d61b3c6192fc408d4d6d5f1176d0c29169bc24e71c3f274ad27fcd5811b313d681f7e55ec02d73d499c95455b6b5bb503acf574fba8ffe85".to_vec());
 /// # }
-pub fn hash_user_id(user_id: u64) -> Vec<u8> {
-	let mut hasher = sha2::Sha512::default();
+pub fn legacy_hash_user_id(user_id: u64) -> Vec<u8> {
+	let mut hasher = Sha512::default();
 	hasher.update(user_id.to_string().into_bytes());
 	hasher.finalize().to_vec()
 }
+
+fn keyed_hash_user_id(key: &str, user_id: u64) -> Vec<u8> {
+	let mut mac =
+		Hmac::<Sha512>::new_from_slice(key.as_bytes()).expect("HMAC takes keys of any length");
+	mac.update(user_id.to_string().as_bytes());
+	mac.finalize().into_bytes().to_vec()
+}
+
+/// Move everything stored under a user's legacy hash over to their current one.
+///
+/// Everything is moved in one transaction. Does nothing if `user_id_hash_key` isn't set, and
+/// usually only hits the database the first time it's called for each user.
+pub async fn rehash_user_id(user_id: u64) -> Result<(), sqlx::Error> {
+	let Some(key) = scripty_config::get_config().user_id_hash_key.as_deref() else {
+		return Ok(());
+	};
+	if REHASHED.lock().contains(&user_id) {
+		return Ok(());
+	}
+
+	let legacy = legacy_hash_user_id(user_id);
+	let current = keyed_hash_user_id(key, user_id);
+	let mut tx = scripty_db::get_db().begin().await?;
+
+	// everything referencing users follows along with ON UPDATE CASCADE
+	let moved = sqlx::query!(
+		"UPDATE users SET user_id = $1 WHERE user_id = $2 AND NOT EXISTS (SELECT 1 FROM users \
+		 WHERE user_id = $1)",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?
+	.rows_affected();
+	if moved == 0 {
+		// something already created a row under the current hash (ie a premium webhook),
+		// so keep that one and move over what references the legacy one
+		sqlx::query!(
+			"UPDATE audio_store SET source_id = $1 WHERE source_id = $2",
+			current,
+			legacy
+		)
+		.execute(&mut *tx)
+		.await?;
+		sqlx::query!(
+			"UPDATE blocked_users SET user_id = $1 WHERE user_id = $2 AND NOT EXISTS (SELECT 1 \
+			 FROM blocked_users WHERE user_id = $1)",
+			current,
+			legacy
+		)
+		.execute(&mut *tx)
+		.await?;
+		sqlx::query!(
+			"UPDATE patreon_members SET user_id = $1 WHERE user_id = $2 AND NOT EXISTS (SELECT 1 \
+			 FROM patreon_members WHERE user_id = $1)",
+			current,
+			legacy
+		)
+		.execute(&mut *tx)
+		.await?;
+		sqlx::query!("DELETE FROM users WHERE user_id = $1", legacy)
+			.execute(&mut *tx)
+			.await?;
+	}
+
+	// these don't reference users, so don't follow along
+	sqlx::query!(
+		"UPDATE guilds SET premium_owner_id = $1 WHERE premium_owner_id = $2",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	// merge hours both hashes have talk time in, then move over the rest
+	sqlx::query!(
+		"UPDATE user_talk_time c SET ms_spoken = c.ms_spoken + l.ms_spoken, utterances = \
+		 c.utterances + l.utterances FROM user_talk_time l WHERE c.user_id = $1 AND l.user_id = \
+		 $2 AND c.guild_id = l.guild_id AND c.hour = l.hour",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!(
+		"DELETE FROM user_talk_time l WHERE user_id = $2 AND EXISTS (SELECT 1 FROM user_talk_time \
+		 c WHERE c.user_id = $1 AND c.guild_id = l.guild_id AND c.hour = l.hour)",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!(
+		"UPDATE user_talk_time SET user_id = $1 WHERE user_id = $2",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!(
+		"UPDATE consent_notices SET user_id = $1 WHERE user_id = $2 AND NOT EXISTS (SELECT 1 FROM \
+		 consent_notices WHERE user_id = $1)",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!("DELETE FROM consent_notices WHERE user_id = $1", legacy)
+		.execute(&mut *tx)
+		.await?;
+	sqlx::query!(
+		"UPDATE transcript_segments SET speaker_id = $1 WHERE speaker_id = $2",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!(
+		"UPDATE dm_support_tickets SET user_id = $1 WHERE user_id = $2",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!(
+		"UPDATE dm_support_blocks SET user_id = $1 WHERE user_id = $2 AND NOT EXISTS (SELECT 1 \
+		 FROM dm_support_blocks WHERE user_id = $1)",
+		current,
+		legacy
+	)
+	.execute(&mut *tx)
+	.await?;
+	sqlx::query!("DELETE FROM dm_support_blocks WHERE user_id = $1", legacy)
+		.execute(&mut *tx)
+		.await?;
+
+	tx.commit().await?;
+
+	// Redis caches some of this by hash too: move the block over,
+	// and drop anything cached under the current hash before the move
+	let legacy = hex::encode(legacy);
+	let current = hex::encode(current);
+	if let Err(e) = scripty_redis::run_transaction::<()>("RENAME", |cmd| {
+		cmd.arg(format!("user:{{{}}}:blocked", legacy))
+			.arg(format!("user:{{{}}}:blocked", current));
+	})
+	.await
+	{
+		// fails if the user wasn't blocked, which is the usual case
+		trace!(%user_id, "didn't move blocked status: {}", e);
+	}
+	if let Err(e) = scripty_redis::run_transaction::<()>("DEL", |cmd| {
+		cmd.arg(format!("user:{{{}}}:store_audio", current))
+			.arg(format!("user:{{{}}}:store_msgs", current));
+	})
+	.await
+	{
+		warn!(%user_id, "failed to clear cached storage settings: {}", e);
+	}
+
+	let mut rehashed = REHASHED.lock();
+	if rehashed.len() >= MAX_REHASHED {
+		rehashed.clear();
+	}
+	rehashed.insert(user_id);
+	Ok(())
+}
@@ -0,0 +1,175 @@
+//! The moderation hold queue, set with `/config hold_queue`, for servers that need to check
+//! transcripts before they're public, ie during a stage event.
+//!
+//! Held transcripts wait in Postgres, with a message in the guild's review channel that has
+//! Approve and Reject buttons. They're only posted to the transcript channel once approved, or
+//! automatically after the guild's timeout if it has one.
+//! Transcripts nobody decided on within `MAX_HOLD_TIME` are dropped.
+//!
+//! Only the transcript channel is held: mirrors, live captions and the transcript file are set up
+//! by the server's managers, so they get everything straight away.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use serenity::{
+	client::Context,
+	model::id::{ChannelId, GuildId, MessageId},
+};
+
+use crate::{
+	delivery::{self, DeliveryError, DeliveryStatus, DeliveryTarget},
+	entity_cache::get_channel_webhook,
+};
+
+/// Custom ID of the Approve button on review messages, followed by the hold ID.
+pub const APPROVE_BUTTON_PREFIX: &str = "hold_approve:";
+/// Custom ID of the Reject button on review messages, followed by the hold ID.
+pub const REJECT_BUTTON_PREFIX: &str = "hold_reject:";
+
+/// How long held transcripts wait for a moderator, if they aren't approved automatically.
+pub const MAX_HOLD_TIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Whether `custom_id` is of a review message's buttons.
+pub fn is_review_button(custom_id: &str) -> bool {
+	custom_id.starts_with(APPROVE_BUTTON_PREFIX) || custom_id.starts_with(REJECT_BUTTON_PREFIX)
+}
+
+/// A transcript waiting for a moderator.
+#[derive(Clone, Debug)]
+pub struct HeldTranscript {
+	pub hold_id:           i64,
+	pub guild_id:          GuildId,
+	/// Channel of the webhook it's posted with.
+	pub channel_id:        ChannelId,
+	pub thread_id:         Option<ChannelId>,
+	/// The webhook message it's posted as.
+	pub body:              serde_json::Value,
+	pub review_channel_id: ChannelId,
+	/// None if the review message never got sent.
+	pub review_message_id: Option<MessageId>,
+}
+
+struct HeldRow {
+	hold_id:           i64,
+	guild_id:          i64,
+	channel_id:        i64,
+	thread_id:         Option<i64>,
+	body:              String,
+	review_channel_id: i64,
+	review_message_id: Option<i64>,
+}
+
+impl HeldRow {
+	fn into_held(self) -> Option<HeldTranscript> {
+		let id = |id: i64| u64::try_from(id).ok().filter(|id| *id != 0);
+		Some(HeldTranscript {
+			hold_id:           self.hold_id,
+			guild_id:          GuildId::new(id(self.guild_id)?),
+			channel_id:        ChannelId::new(id(self.channel_id)?),
+			thread_id:         self.thread_id.and_then(id).map(ChannelId::new),
+			body:              serde_json::from_str(&self.body).ok()?,
+			review_channel_id: ChannelId::new(id(self.review_channel_id)?),
+			review_message_id: self.review_message_id.and_then(id).map(MessageId::new),
+		})
+	}
+}
+
+/// Hold `body`, a webhook message for `channel_id`'s webhook, until it's reviewed in
+/// `review_channel_id`. Returns its hold ID, for the review message's buttons.
+pub async fn hold(
+	guild_id: GuildId,
+	channel_id: ChannelId,
+	thread_id: Option<ChannelId>,
+	body: &impl Serialize,
+	review_channel_id: ChannelId,
+	auto_approve_after: Option<Duration>,
+) -> Result<i64, DeliveryError> {
+	let body = serde_json::to_string(body)?;
+	let row = sqlx::query!(
+		"INSERT INTO held_transcripts (guild_id, channel_id, thread_id, body, review_channel_id, \
+		 auto_approve_at) VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(secs => $6)) \
+		 RETURNING hold_id",
+		guild_id.get() as i64,
+		channel_id.get() as i64,
+		thread_id.map(|t| t.get() as i64),
+		body,
+		review_channel_id.get() as i64,
+		auto_approve_after.map(|after| after.as_secs_f64())
+	)
+	.fetch_one(scripty_db::get_db())
+	.await?;
+
+	Ok(row.hold_id)
+}
+
+/// Remember the review message of `hold_id`, so it can be updated once it's decided on.
+pub async fn set_review_message(hold_id: i64, message_id: MessageId) -> Result<(), sqlx::Error> {
+	sqlx::query!(
+		"UPDATE held_transcripts SET review_message_id = $2 WHERE hold_id = $1",
+		hold_id,
+		message_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+	Ok(())
+}
+
+/// Take `hold_id` out of `guild_id`'s queue, so only one moderator can decide on it.
+/// None if someone already has, or it was approved automatically.
+pub async fn take(guild_id: GuildId, hold_id: i64) -> Result<Option<HeldTranscript>, sqlx::Error> {
+	let row = sqlx::query_as!(
+		HeldRow,
+		"DELETE FROM held_transcripts WHERE hold_id = $1 AND guild_id = $2 RETURNING hold_id, \
+		 guild_id, channel_id, thread_id, body, review_channel_id, review_message_id",
+		hold_id,
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.and_then(HeldRow::into_held))
+}
+
+/// Take every held transcript that's due to be approved automatically.
+/// Safe to call from several processes at once, as each is only taken once.
+pub async fn take_due() -> Result<Vec<HeldTranscript>, sqlx::Error> {
+	let rows = sqlx::query_as!(
+		HeldRow,
+		"DELETE FROM held_transcripts WHERE auto_approve_at <= NOW() RETURNING hold_id, guild_id, \
+		 channel_id, thread_id, body, review_channel_id, review_message_id"
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows.into_iter().filter_map(HeldRow::into_held).collect())
+}
+
+/// Take every held transcript nobody decided on within `MAX_HOLD_TIME`.
+pub async fn take_expired() -> Result<Vec<HeldTranscript>, sqlx::Error> {
+	let rows = sqlx::query_as!(
+		HeldRow,
+		"DELETE FROM held_transcripts WHERE auto_approve_at IS NULL AND held_at < NOW() - \
+		 make_interval(secs => $1) RETURNING hold_id, guild_id, channel_id, thread_id, body, \
+		 review_channel_id, review_message_id",
+		MAX_HOLD_TIME.as_secs_f64()
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows.into_iter().filter_map(HeldRow::into_held).collect())
+}
+
+/// Post an approved transcript to the channel it was held from.
+pub async fn post(ctx: &Context, held: &HeldTranscript) -> Result<DeliveryStatus, DeliveryError> {
+	let webhook = get_channel_webhook(ctx, held.channel_id).await?;
+	let target = DeliveryTarget::webhook(&webhook, held.thread_id)
+		.ok_or(serenity::Error::Other("transcript webhook has no token"))?;
+	delivery::deliver(
+		&ctx.http,
+		target,
+		&held.body,
+		&format!("held:{}", held.hold_id),
+	)
+	.await
+}
@@ -0,0 +1,119 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+
+/// Number of samples kept per source. With the latency updater running every 10 seconds,
+/// this is five minutes of history.
+const HISTORY_SIZE: usize = 30;
+
+static LATENCY_HISTORY: Lazy<Mutex<HashMap<Source, VecDeque<u128>>>> =
+	Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A backing service whose latency is measured by the helpers in this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Service {
+	Database,
+	Redis,
+	/// Handshake with an STT worker.
+	Stt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Source {
+	Shard(u16),
+	Service(Service),
+}
+
+/// Summary of recent latency samples of a single shard or service. All values are in nanoseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyStats {
+	pub current: u128,
+	pub average: u128,
+	pub min:     u128,
+	pub max:     u128,
+	/// Mean absolute difference between consecutive samples.
+	pub jitter:  u128,
+	pub samples: usize,
+}
+
+impl LatencyStats {
+	fn from_samples(samples: &VecDeque<u128>) -> Option<Self> {
+		let current = *samples.back()?;
+		let len = samples.len();
+
+		let jitter = if len > 1 {
+			samples
+				.iter()
+				.zip(samples.iter().skip(1))
+				.map(|(a, b)| a.abs_diff(*b))
+				.sum::<u128>()
+				/ (len as u128 - 1)
+		} else {
+			0
+		};
+
+		Some(Self {
+			current,
+			average: samples.iter().sum::<u128>() / len as u128,
+			min: *samples.iter().min()?,
+			max: *samples.iter().max()?,
+			jitter,
+			samples: len,
+		})
+	}
+}
+
+fn record(source: Source, latency: u128) {
+	let mut history = LATENCY_HISTORY.lock().expect("latency history poisoned");
+	let samples = history
+		.entry(source)
+		.or_insert_with(|| VecDeque::with_capacity(HISTORY_SIZE));
+	if samples.len() >= HISTORY_SIZE {
+		samples.pop_front();
+	}
+	samples.push_back(latency);
+}
+
+fn get_stats(source: Source) -> Option<LatencyStats> {
+	let history = LATENCY_HISTORY.lock().expect("latency history poisoned");
+	history.get(&source).and_then(LatencyStats::from_samples)
+}
+
+/// Record a new WebSocket latency sample for a shard, in nanoseconds.
+pub fn record_ws_latency(shard_id: u16, latency: u128) {
+	record(Source::Shard(shard_id), latency);
+}
+
+/// Record a new latency sample for a service, in nanoseconds.
+pub fn record_service_latency(service: Service, latency: u128) {
+	record(Source::Service(service), latency);
+}
+
+/// Get the recent latency stats for a single shard, if any samples have been recorded.
+pub fn get_latency_stats(shard_id: u16) -> Option<LatencyStats> {
+	get_stats(Source::Shard(shard_id))
+}
+
+/// Get the recent latency stats for a service, if any samples have been recorded.
+pub fn get_service_latency_stats(service: Service) -> Option<LatencyStats> {
+	get_stats(Source::Service(service))
+}
+
+/// Get the recent latency stats for every shard with recorded samples, sorted by shard ID.
+pub fn get_all_latency_stats() -> Vec<(u16, LatencyStats)> {
+	let history = LATENCY_HISTORY.lock().expect("latency history poisoned");
+	let mut stats: Vec<_> = history
+		.iter()
+		.filter_map(|(source, samples)| match source {
+			Source::Shard(shard_id) => {
+				LatencyStats::from_samples(samples).map(|stats| (*shard_id, stats))
+			}
+			Source::Service(_) => None,
+		})
+		.collect();
+	stats.sort_unstable_by_key(|(shard_id, _)| *shard_id);
+	stats
+}
@@ -1,7 +1,26 @@
+//! Latency measurements, shared by `/ping`, the health checks and the metrics updater.
+//!
+//! The STT handshake is timed by `scripty_stt::get_stt_latency`, since `scripty_stt` depends on
+//! this crate, but it's recorded in the same history.
+
 mod db;
+mod history;
 mod http;
+mod redis;
+mod service;
 mod ws;
 
 pub use db::get_db_latency;
+pub use history::{
+	get_all_latency_stats,
+	get_latency_stats,
+	get_service_latency_stats,
+	record_service_latency,
+	record_ws_latency,
+	LatencyStats,
+	Service,
+};
 pub use http::get_http_latency;
-pub use ws::get_ws_latency;
+pub use redis::get_redis_latency;
+pub use service::{measure, MeasureError};
+pub use ws::{get_all_ws_latencies, get_ws_latency};
@@ -0,0 +1,12 @@
+use scripty_redis::TransactionError;
+
+use super::{measure, MeasureError, Service};
+
+/// Time a Redis `PING`, in nanoseconds.
+pub async fn get_redis_latency() -> Result<u128, MeasureError<TransactionError>> {
+	measure(
+		Service::Redis,
+		scripty_redis::run_transaction::<String>("PING", |_| {}),
+	)
+	.await
+}
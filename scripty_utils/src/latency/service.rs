@@ -0,0 +1,46 @@
+use std::{
+	fmt,
+	future::Future,
+	time::{Duration, Instant},
+};
+
+use super::{record_service_latency, Service};
+
+/// Give up on a measurement after this long, so a hung service doesn't hang whoever asked.
+const MEASURE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum MeasureError<E> {
+	Failed(E),
+	TimedOut,
+}
+
+impl<E: fmt::Display> fmt::Display for MeasureError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			MeasureError::Failed(e) => e.fmt(f),
+			MeasureError::TimedOut => write!(f, "timed out after {:?}", MEASURE_TIMEOUT),
+		}
+	}
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for MeasureError<E> {}
+
+/// Time how long `probe` takes to succeed, in nanoseconds, and record it in the service's history.
+///
+/// Failed and timed out probes aren't recorded.
+pub async fn measure<T, E>(
+	service: Service,
+	probe: impl Future<Output = Result<T, E>>,
+) -> Result<u128, MeasureError<E>> {
+	let st = Instant::now();
+	match tokio::time::timeout(MEASURE_TIMEOUT, probe).await {
+		Ok(Ok(_)) => {
+			let latency = st.elapsed().as_nanos();
+			record_service_latency(service, latency);
+			Ok(latency)
+		}
+		Ok(Err(e)) => Err(MeasureError::Failed(e)),
+		Err(_) => Err(MeasureError::TimedOut),
+	}
+}
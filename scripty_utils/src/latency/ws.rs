@@ -11,3 +11,14 @@ pub async fn get_ws_latency(
 		.get(&ShardId(shard_id))
 		.and_then(|x| x.latency.map(|d| d.as_nanos()))
 }
+
+/// Get the current WebSocket latency of every shard, in nanoseconds.
+pub async fn get_all_ws_latencies(
+	shard_manager: &<ShardManagerWrapper as TypeMapKey>::Value,
+) -> Vec<(u16, Option<u128>)> {
+	let runners = shard_manager.runners.lock().await;
+	runners
+		.iter()
+		.map(|(id, runner)| (id.0, runner.latency.map(|d| d.as_nanos())))
+		.collect()
+}
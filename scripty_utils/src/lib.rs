@@ -1,16 +1,37 @@
+#[macro_use]
+extern crate tracing;
+
 use std::sync::Arc;
 
 use serenity::{gateway::ShardManager, prelude::TypeMapKey};
 
+pub mod automations;
+pub mod bridge;
+mod constant_time_eq;
+pub mod delivery;
 mod embed_pagination;
+pub mod entity_cache;
 mod hash_user_id;
 mod hex_vec;
+pub mod hold_queue;
 pub mod latency;
+pub mod live_captions;
+pub mod mirrors;
+pub mod panic;
+mod primitive_time;
+pub mod replacements;
+pub mod role_policy;
 mod separate_num;
+pub mod subscriptions;
+pub mod translation;
+pub mod watch;
 
-pub use embed_pagination::do_paginate;
-pub use hash_user_id::hash_user_id;
+pub use constant_time_eq::constant_time_eq;
+pub use embed_pagination::{do_paginate, do_paginate_with_options, PaginateOptions, Pagination};
+pub use hash_user_id::{hash_user_id, legacy_hash_user_id, rehash_user_id};
 pub use hex_vec::vec_to_hex;
+pub use panic::{spawn_logged, spawn_supervised};
+pub use primitive_time::to_primitive;
 pub use separate_num::separate_num;
 
 pub struct ShardManagerWrapper;
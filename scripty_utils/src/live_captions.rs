@@ -0,0 +1,203 @@
+//! Live transcripts of voice sessions, sent over Redis so any process's webserver can relay them.
+//!
+//! Only guilds that turned them on with `/config live_captions` have their sessions published,
+//! so nobody can follow a voice chat live unless the guild wants that.
+//! They're followed with a guild's API key, or with an overlay token from `/captions overlay`.
+
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{ChannelId, GuildId};
+
+/// Something that happened in one of a guild's voice sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveCaptionEvent {
+	SessionStarted {
+		/// None if the session isn't stored, as the guild has no API key.
+		session_id:       Option<i64>,
+		/// A string, like all Discord IDs, as it can be too big for a JSON number.
+		voice_channel_id: String,
+	},
+	Transcript {
+		session_id:       Option<i64>,
+		voice_channel_id: String,
+		/// Display name of the speaker.
+		speaker:          String,
+		/// Milliseconds from the start of the session.
+		start_ms:         i64,
+		end_ms:           i64,
+		content:          String,
+		/// Detected language, if the guild has auto-detect on and it was clear enough.
+		language:         Option<String>,
+	},
+	SessionEnded {
+		session_id:       Option<i64>,
+		voice_channel_id: String,
+	},
+}
+
+impl LiveCaptionEvent {
+	/// Whether this happened in `session`. A voice chat only has one session at a time, so
+	/// sessions that aren't stored are told apart by their voice chat.
+	pub fn is_in(&self, session: &OverlaySession) -> bool {
+		let (session_id, voice_channel_id) = match self {
+			Self::SessionStarted {
+				session_id,
+				voice_channel_id,
+			}
+			| Self::Transcript {
+				session_id,
+				voice_channel_id,
+				..
+			}
+			| Self::SessionEnded {
+				session_id,
+				voice_channel_id,
+			} => (session_id, voice_channel_id),
+		};
+		*session_id == session.session_id
+			&& *voice_channel_id == session.voice_channel_id.to_string()
+	}
+}
+
+/// The session an overlay token shows captions of.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OverlaySession {
+	pub guild_id:         GuildId,
+	pub voice_channel_id: ChannelId,
+	/// None if the session isn't stored, as the guild has no API key.
+	pub session_id:       Option<i64>,
+}
+
+/// How long an overlay URL from `/captions overlay` works for at most: long enough for any stream.
+/// URLs stop working as soon as their session ends.
+pub const OVERLAY_TOKEN_TTL: Duration = Duration::from_secs(60 * 60 * 12);
+
+fn overlay_token_key(token: &str) -> String {
+	format!("captions_overlay:{{{}}}", token)
+}
+
+/// Tokens made for the voice chat's current session, so they can be revoked when it ends.
+fn overlay_tokens_key(guild_id: GuildId, voice_channel_id: ChannelId) -> String {
+	format!("captions_overlays:{{{}:{}}}", guild_id, voice_channel_id)
+}
+
+/// The stored session going on in `voice_channel_id`, if there is one.
+pub async fn get_current_session_id(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+) -> Result<Option<i64>, sqlx::Error> {
+	sqlx::query_scalar!(
+		"SELECT session_id FROM transcript_sessions WHERE guild_id = $1 AND voice_channel_id = $2 \
+		 AND ended_at IS NULL ORDER BY started_at DESC LIMIT 1",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await
+}
+
+/// Make a token for following `session`'s live captions from a stream overlay, valid until the
+/// session ends or for `OVERLAY_TOKEN_TTL`, whichever comes first.
+pub async fn create_overlay_token(
+	session: OverlaySession,
+) -> Result<String, scripty_redis::TransactionError> {
+	let token = format!(
+		"{}{}",
+		uuid::Uuid::new_v4().simple(),
+		uuid::Uuid::new_v4().simple()
+	);
+	let value = serde_json::to_string(&session).expect("overlay sessions always serialize");
+	let tokens_key = overlay_tokens_key(session.guild_id, session.voice_channel_id);
+
+	let mut conn = scripty_redis::get_pool().get().await?;
+	scripty_redis::redis::pipe()
+		.set_ex(
+			overlay_token_key(&token),
+			value,
+			OVERLAY_TOKEN_TTL.as_secs() as usize,
+		)
+		.ignore()
+		.sadd(&tokens_key, &token)
+		.ignore()
+		.expire(&tokens_key, OVERLAY_TOKEN_TTL.as_secs() as usize)
+		.ignore()
+		.query_async(&mut conn)
+		.await?;
+	Ok(token)
+}
+
+/// The session an overlay token is for, if it's valid.
+pub async fn get_overlay_session(
+	token: &str,
+) -> Result<Option<OverlaySession>, scripty_redis::TransactionError> {
+	let session = scripty_redis::run_transaction::<Option<String>>("GET", |cmd| {
+		cmd.arg(overlay_token_key(token));
+	})
+	.await?;
+	// tokens from before they were bound to a session don't parse, and are treated as expired
+	Ok(session.and_then(|session| serde_json::from_str(&session).ok()))
+}
+
+/// Revoke the overlay tokens made for the session that just ended in `voice_channel_id`.
+pub async fn revoke_overlay_tokens(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+) -> Result<(), scripty_redis::TransactionError> {
+	let tokens_key = overlay_tokens_key(guild_id, voice_channel_id);
+	let tokens = scripty_redis::run_transaction::<Vec<String>>("SMEMBERS", |cmd| {
+		cmd.arg(&tokens_key);
+	})
+	.await?;
+
+	let mut pipe = scripty_redis::redis::pipe();
+	for token in &tokens {
+		pipe.del(overlay_token_key(token)).ignore();
+	}
+	pipe.del(&tokens_key).ignore();
+	let mut conn = scripty_redis::get_pool().get().await?;
+	pipe.query_async(&mut conn).await?;
+	Ok(())
+}
+
+/// Whether `guild_id` turned live captions on.
+pub async fn is_enabled(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	let enabled = sqlx::query_scalar!(
+		"SELECT live_captions FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+	Ok(enabled.unwrap_or(false))
+}
+
+fn channel_name(guild_id: GuildId) -> String {
+	format!("live_captions:{{{}}}", guild_id)
+}
+
+/// Send `event` to everyone following `guild_id`'s live captions.
+pub async fn publish(guild_id: GuildId, event: &LiveCaptionEvent) {
+	let message = match serde_json::to_string(event) {
+		Ok(message) => message,
+		Err(e) => {
+			warn!(%guild_id, "failed to serialize live caption event: {}", e);
+			return;
+		}
+	};
+	if let Err(e) = scripty_redis::publish(&channel_name(guild_id), &message).await {
+		warn!(%guild_id, "failed to publish live caption event: {}", e);
+	}
+}
+
+/// Follow `guild_id`'s live captions. Drop the stream to stop.
+pub async fn subscribe(
+	guild_id: GuildId,
+) -> Result<impl Stream<Item = LiveCaptionEvent>, scripty_redis::redis::RedisError> {
+	let pubsub = scripty_redis::subscribe(&channel_name(guild_id)).await?;
+	Ok(pubsub.into_on_message().filter_map(|msg| async move {
+		let payload: String = msg.get_payload().ok()?;
+		serde_json::from_str(&payload).ok()
+	}))
+}
@@ -0,0 +1,87 @@
+//! Guild transcript mirrors, set with `/config mirror`: extra channels a voice channel's
+//! transcripts are also sent to, ie a moderation archive alongside the session's own channel.
+//!
+//! Sessions that are already going pick up changes the next time they reload their config.
+
+use serenity::model::id::{ChannelId, GuildId};
+
+/// Most mirrors a guild can have, across all of its voice channels.
+pub const MAX_MIRRORS: i64 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mirror {
+	pub voice_channel_id: ChannelId,
+	pub channel_id:       ChannelId,
+	/// How transcripts look in this channel, stored like `guilds.transcript_style`.
+	pub style:            i16,
+}
+
+/// Get all of `guild_id`'s mirrors, grouped by voice channel.
+pub async fn get_mirrors(guild_id: GuildId) -> Result<Vec<Mirror>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT voice_channel_id, channel_id, style FROM transcript_mirrors WHERE guild_id = $1 \
+		 ORDER BY voice_channel_id ASC, channel_id ASC",
+		guild_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.map(|row| Mirror {
+			voice_channel_id: ChannelId::new(row.voice_channel_id as u64),
+			channel_id:       ChannelId::new(row.channel_id as u64),
+			style:            row.style,
+		})
+		.collect())
+}
+
+/// Mirror `guild_id`'s transcripts, replacing the style of the mirror if it already exists.
+/// Returns false if the guild already has as many mirrors as it can.
+pub async fn set_mirror(guild_id: GuildId, mirror: Mirror) -> Result<bool, sqlx::Error> {
+	let db = scripty_db::get_db();
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT (guild_id) DO NOTHING",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	// changing an existing mirror doesn't count towards the limit
+	let set = sqlx::query!(
+		"INSERT INTO transcript_mirrors (guild_id, voice_channel_id, channel_id, style) SELECT \
+		 $1, $2, $3, $4 WHERE (SELECT COUNT(*) FROM transcript_mirrors WHERE guild_id = $1 AND \
+		 NOT (voice_channel_id = $2 AND channel_id = $3)) < $5 ON CONFLICT (guild_id, \
+		 voice_channel_id, channel_id) DO UPDATE SET style = $4",
+		guild_id.get() as i64,
+		mirror.voice_channel_id.get() as i64,
+		mirror.channel_id.get() as i64,
+		mirror.style,
+		MAX_MIRRORS
+	)
+	.execute(db)
+	.await?
+	.rows_affected();
+
+	Ok(set > 0)
+}
+
+/// Stop mirroring `voice_channel_id`'s transcripts to `channel_id`. Returns whether it was.
+pub async fn remove_mirror(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	channel_id: ChannelId,
+) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM transcript_mirrors WHERE guild_id = $1 AND voice_channel_id = $2 AND \
+		 channel_id = $3",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64,
+		channel_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
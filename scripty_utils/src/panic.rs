@@ -0,0 +1,121 @@
+//! Panic capture.
+//!
+//! The panic hook logs every panic with a backtrace, and queues it to be reported to the error
+//! webhook. `spawn_logged` and `spawn_supervised` make sure a panicking task is noticed,
+//! and in the latter case restarted, instead of silently disappearing.
+
+use std::{
+	any::Any,
+	backtrace::Backtrace,
+	future::Future,
+	panic::{self, AssertUnwindSafe},
+	time::Duration,
+};
+
+use futures::FutureExt;
+use once_cell::sync::OnceCell;
+use parking_lot::{const_mutex, Mutex};
+use tokio::{
+	sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+	task::JoinHandle,
+};
+
+/// A panic, as captured by the panic hook.
+#[derive(Debug)]
+pub struct PanicReport {
+	pub message:   String,
+	/// `file:line:column` the panic happened at, if known.
+	pub location:  Option<String>,
+	pub thread:    Option<String>,
+	pub backtrace: String,
+}
+
+static PANIC_REPORT_TX: OnceCell<UnboundedSender<PanicReport>> = OnceCell::new();
+static PANIC_REPORT_RX: Mutex<Option<UnboundedReceiver<PanicReport>>> = const_mutex(None);
+
+/// Install the panic hook. Any hook installed before this one (ie Sentry's) is still called.
+pub fn install_panic_hook() {
+	let (tx, rx) = unbounded_channel();
+	if PANIC_REPORT_TX.set(tx).is_err() {
+		panic!("don't call `install_panic_hook()` more than once");
+	}
+	*PANIC_REPORT_RX.lock() = Some(rx);
+
+	let previous_hook = panic::take_hook();
+	panic::set_hook(Box::new(move |info| {
+		let report = PanicReport {
+			message:   panic_message(info.payload()),
+			location:  info.location().map(|l| l.to_string()),
+			thread:    std::thread::current().name().map(str::to_string),
+			backtrace: Backtrace::force_capture().to_string(),
+		};
+		error!(
+			location = ?report.location,
+			thread = ?report.thread,
+			"panicked: {}\n{}",
+			report.message,
+			report.backtrace
+		);
+		if let Some(tx) = PANIC_REPORT_TX.get() {
+			// only fails if the receiver was dropped, and then there's nothing to report to
+			let _ = tx.send(report);
+		}
+
+		previous_hook(info);
+	}));
+}
+
+/// Take the receiving end of the panic report queue. Returns None if it was already taken,
+/// or the panic hook isn't installed.
+pub fn take_panic_reports() -> Option<UnboundedReceiver<PanicReport>> {
+	PANIC_REPORT_RX.lock().take()
+}
+
+/// Get a readable message out of a panic payload.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+	if let Some(s) = payload.downcast_ref::<&str>() {
+		s.to_string()
+	} else if let Some(s) = payload.downcast_ref::<String>() {
+		s.clone()
+	} else {
+		"unknown panic payload".to_string()
+	}
+}
+
+/// Spawn a task, logging it by name if it panics.
+pub fn spawn_logged<F>(name: &'static str, fut: F) -> JoinHandle<()>
+where
+	F: Future + Send + 'static,
+	F::Output: Send + 'static,
+{
+	tokio::spawn(async move {
+		if let Err(payload) = AssertUnwindSafe(fut).catch_unwind().await {
+			error!(task = name, "task panicked: {}", panic_message(&*payload));
+		}
+	})
+}
+
+/// Spawn a long-running task, restarting it with a fresh future from `make_task` if it panics.
+///
+/// Restarts back off exponentially, up to a minute apart. Returns once a run finishes normally.
+pub fn spawn_supervised<F, Fut>(name: &'static str, mut make_task: F) -> JoinHandle<()>
+where
+	F: FnMut() -> Fut + Send + 'static,
+	Fut: Future<Output = ()> + Send + 'static,
+{
+	tokio::spawn(async move {
+		let mut restarts: u32 = 0;
+		while let Err(payload) = AssertUnwindSafe(make_task()).catch_unwind().await {
+			restarts += 1;
+			let backoff = Duration::from_secs(2u64.saturating_pow(restarts).min(60));
+			error!(
+				task = name,
+				%restarts,
+				"supervised task panicked, restarting in {:?}: {}",
+				backoff,
+				panic_message(&*payload)
+			);
+			tokio::time::sleep(backoff).await;
+		}
+	})
+}
@@ -0,0 +1,7 @@
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// Convert a time to UTC and drop the offset, for `TIMESTAMP` columns, which all store UTC.
+pub fn to_primitive(t: OffsetDateTime) -> PrimitiveDateTime {
+	let t = t.to_offset(UtcOffset::UTC);
+	PrimitiveDateTime::new(t.date(), t.time())
+}
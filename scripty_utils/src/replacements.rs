@@ -0,0 +1,74 @@
+//! Guild find-and-replace rules for transcripts, set with `/config replacements`.
+//!
+//! They're stored as written, and compiled by `scripty_stt::Replacements` when loaded.
+//! Sessions that are already going pick up changes the next time they reload their config.
+
+use serenity::model::id::GuildId;
+
+/// Most rules a guild can have.
+pub const MAX_REPLACEMENTS: i64 = 25;
+/// Longest a pattern or replacement can be.
+pub const MAX_REPLACEMENT_LEN: usize = 200;
+
+pub struct Replacement {
+	pub id:          i32,
+	pub pattern:     String,
+	pub replacement: String,
+}
+
+/// Get `guild_id`'s rules, in the order they should run.
+pub async fn get_replacements(guild_id: GuildId) -> Result<Vec<Replacement>, sqlx::Error> {
+	sqlx::query_as!(
+		Replacement,
+		"SELECT id, pattern, replacement FROM transcript_replacements WHERE guild_id = $1 ORDER \
+		 BY id ASC",
+		guild_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await
+}
+
+/// Replace `pattern` with `replacement` in `guild_id`'s transcripts, or change what it's
+/// replaced with if it already has a rule. Returns the rule's ID, or None if the guild is full.
+pub async fn add_replacement(
+	guild_id: GuildId,
+	pattern: &str,
+	replacement: &str,
+) -> Result<Option<i32>, sqlx::Error> {
+	let db = scripty_db::get_db();
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT (guild_id) DO NOTHING",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	// updating an existing rule doesn't count towards the limit
+	let row = sqlx::query!(
+		"INSERT INTO transcript_replacements (guild_id, pattern, replacement) SELECT $1, $2, $3 \
+		 WHERE (SELECT COUNT(*) FROM transcript_replacements WHERE guild_id = $1 AND pattern <> \
+		 $2) < $4 ON CONFLICT (guild_id, pattern) DO UPDATE SET replacement = $3 RETURNING id",
+		guild_id.get() as i64,
+		pattern,
+		replacement,
+		MAX_REPLACEMENTS
+	)
+	.fetch_optional(db)
+	.await?;
+
+	Ok(row.map(|row| row.id))
+}
+
+/// Remove one of `guild_id`'s rules. Returns whether it existed.
+pub async fn remove_replacement(guild_id: GuildId, id: i32) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM transcript_replacements WHERE guild_id = $1 AND id = $2",
+		guild_id.get() as i64,
+		id
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
@@ -0,0 +1,81 @@
+//! Guild role policies, set with `/config role_policy`: roles whose members are never
+//! transcribed, and roles members need to be transcribed at all.
+//!
+//! Sessions that are already going pick up changes the next time they reload their config.
+
+use serenity::model::id::{GuildId, RoleId};
+
+/// Most roles a guild can have a policy for.
+pub const MAX_ROLE_POLICIES: i64 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RolePolicy {
+	pub role_id:   RoleId,
+	/// False if members with the role are never transcribed.
+	pub allow:     bool,
+	/// Also allow every role above this one. Only for allowed roles.
+	pub and_above: bool,
+}
+
+/// Get `guild_id`'s role policies, allowed roles first.
+pub async fn get_role_policies(guild_id: GuildId) -> Result<Vec<RolePolicy>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT role_id, allow, and_above FROM role_policies WHERE guild_id = $1 ORDER BY allow \
+		 DESC, role_id ASC",
+		guild_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.map(|row| RolePolicy {
+			role_id:   RoleId::new(row.role_id as u64),
+			allow:     row.allow,
+			and_above: row.and_above,
+		})
+		.collect())
+}
+
+/// Set `guild_id`'s policy for a role, replacing any it already had.
+/// Returns false if the guild already has as many policies as it can.
+pub async fn set_role_policy(guild_id: GuildId, policy: RolePolicy) -> Result<bool, sqlx::Error> {
+	let db = scripty_db::get_db();
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT (guild_id) DO NOTHING",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	// changing an existing policy doesn't count towards the limit
+	let set = sqlx::query!(
+		"INSERT INTO role_policies (guild_id, role_id, allow, and_above) SELECT $1, $2, $3, $4 \
+		 WHERE (SELECT COUNT(*) FROM role_policies WHERE guild_id = $1 AND role_id <> $2) < $5 ON \
+		 CONFLICT (guild_id, role_id) DO UPDATE SET allow = $3, and_above = $4",
+		guild_id.get() as i64,
+		policy.role_id.get() as i64,
+		policy.allow,
+		policy.allow && policy.and_above,
+		MAX_ROLE_POLICIES
+	)
+	.execute(db)
+	.await?
+	.rows_affected();
+
+	Ok(set > 0)
+}
+
+/// Remove `guild_id`'s policy for a role. Returns whether it had one.
+pub async fn remove_role_policy(guild_id: GuildId, role_id: RoleId) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM role_policies WHERE guild_id = $1 AND role_id = $2",
+		guild_id.get() as i64,
+		role_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
@@ -1,11 +1,14 @@
-use std::fmt::Display;
+use num_format::{Locale, ToFormattedStr, ToFormattedString};
 
-use num::Num;
-use thousands::Separable;
+/// Format a number with the digit grouping and separators used in `language`, ie `en` or `pt-BR`.
+///
+/// Falls back to the base language (`pt`), then to English, if CLDR has no data for it.
+pub fn separate_num<T: ToFormattedStr>(num: T, language: &str) -> String {
+	num.to_formatted_string(&locale_for(language))
+}
 
-pub fn separate_num<T: Num>(num: T) -> String
-where
-	T: Display,
-{
-	num.separate_with_commas()
+fn locale_for(language: &str) -> Locale {
+	Locale::from_name(language)
+		.or_else(|_| Locale::from_name(language.split('-').next().unwrap_or(language)))
+		.unwrap_or(Locale::en)
 }
@@ -0,0 +1,99 @@
+//! Transcript subscriptions, made with `/subscribe`: members who can't make it to a voice channel
+//! get its transcripts DMed to them when its sessions end.
+//!
+//! Guilds have to allow them with `/config subscriptions` first. Turning them off keeps the
+//! subscriptions, but nothing is sent until they're allowed again.
+
+use serenity::model::id::{ChannelId, GuildId, UserId};
+
+/// Most members that can be subscribed to one voice channel.
+pub const MAX_SUBSCRIBERS: i64 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Subscriber {
+	pub user_id:      UserId,
+	/// Only send the minutes, not the whole transcript.
+	pub summary_only: bool,
+}
+
+/// Whether `guild_id` lets members subscribe to transcripts.
+pub async fn subscriptions_allowed(guild_id: GuildId) -> Result<bool, sqlx::Error> {
+	Ok(sqlx::query!(
+		"SELECT allow_subscriptions FROM guilds WHERE guild_id = $1",
+		guild_id.get() as i64
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?
+	.map_or(false, |row| row.allow_subscriptions))
+}
+
+/// Get who's subscribed to `voice_channel_id`, or nobody if `guild_id` doesn't allow
+/// subscriptions anymore.
+pub async fn get_subscribers(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+) -> Result<Vec<Subscriber>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT s.user_id, s.summary_only FROM transcript_subscriptions s INNER JOIN guilds g ON \
+		 g.guild_id = s.guild_id WHERE s.guild_id = $1 AND s.voice_channel_id = $2 AND \
+		 g.allow_subscriptions",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.map(|row| Subscriber {
+			user_id:      UserId::new(row.user_id as u64),
+			summary_only: row.summary_only,
+		})
+		.collect())
+}
+
+/// Subscribe `user_id` to `voice_channel_id`'s transcripts, or change what they get if they
+/// already are. Returns false if the voice channel already has as many subscribers as it can.
+pub async fn subscribe(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	subscriber: Subscriber,
+) -> Result<bool, sqlx::Error> {
+	// changing an existing subscription doesn't count towards the limit
+	let set = sqlx::query!(
+		"INSERT INTO transcript_subscriptions (guild_id, voice_channel_id, user_id, summary_only) \
+		 SELECT $1, $2, $3, $4 WHERE (SELECT COUNT(*) FROM transcript_subscriptions WHERE \
+		 guild_id = $1 AND voice_channel_id = $2 AND user_id != $3) < $5 ON CONFLICT (guild_id, \
+		 voice_channel_id, user_id) DO UPDATE SET summary_only = $4",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64,
+		subscriber.user_id.get() as i64,
+		subscriber.summary_only,
+		MAX_SUBSCRIBERS
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(set > 0)
+}
+
+/// Unsubscribe `user_id` from `voice_channel_id`'s transcripts. Returns whether they were.
+pub async fn unsubscribe(
+	guild_id: GuildId,
+	voice_channel_id: ChannelId,
+	user_id: UserId,
+) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM transcript_subscriptions WHERE guild_id = $1 AND voice_channel_id = $2 AND \
+		 user_id = $3",
+		guild_id.get() as i64,
+		voice_channel_id.get() as i64,
+		user_id.get() as i64
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
@@ -0,0 +1,144 @@
+//! Translating transcript lines for members who read a different language to the one spoken.
+//!
+//! Members set their reading language with `/reading_language`. While any of them are in a
+//! session, its transcripts get a button that shows whoever presses it a translation.
+//! Translations come from a LibreTranslate-compatible API, set in the `translation` config.
+
+use std::{fmt, time::Duration};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serenity::model::id::UserId;
+
+/// Custom ID of the translate button on transcript messages.
+pub const TRANSLATE_BUTTON_ID: &str = "transcript_translate";
+
+static TRANSLATION_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+	reqwest::Client::builder()
+		.timeout(Duration::from_secs(10))
+		.build()
+		.expect("static client config is valid")
+});
+
+#[derive(Debug)]
+pub enum TranslationError {
+	NotConfigured,
+	Http(reqwest::Error),
+}
+
+impl From<reqwest::Error> for TranslationError {
+	fn from(e: reqwest::Error) -> Self {
+		Self::Http(e)
+	}
+}
+
+impl fmt::Display for TranslationError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotConfigured => f.write_str("translation isn't configured"),
+			Self::Http(e) => write!(f, "translation request failed: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for TranslationError {}
+
+/// Whether a translation API is configured.
+pub fn is_enabled() -> bool {
+	scripty_config::get_config().translation.is_some()
+}
+
+/// Translate `text` to `target`, detecting what language it's in.
+pub async fn translate(text: &str, target: &str) -> Result<String, TranslationError> {
+	#[derive(Deserialize)]
+	struct TranslateResponse {
+		#[serde(rename = "translatedText")]
+		translated_text: String,
+	}
+
+	let cfg = scripty_config::get_config()
+		.translation
+		.as_ref()
+		.ok_or(TranslationError::NotConfigured)?;
+	let res: TranslateResponse = TRANSLATION_CLIENT
+		.post(format!("{}/translate", cfg.url.trim_end_matches('/')))
+		.json(&serde_json::json!({
+			"q": text,
+			"source": "auto",
+			"target": target,
+			"format": "text",
+			"api_key": cfg.api_key,
+		}))
+		.send()
+		.await?
+		.error_for_status()?
+		.json()
+		.await?;
+	Ok(res.translated_text)
+}
+
+/// Whether `code` looks like a language code, ie `de` or `zh-Hant`.
+///
+/// Which ones can actually be translated to is up to the translation API.
+pub fn is_valid_language_code(code: &str) -> bool {
+	let mut parts = code.splitn(2, '-');
+	let primary = parts.next().unwrap_or_default();
+	let primary_valid =
+		(2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase());
+	let region_valid = parts.next().map_or(true, |region| {
+		(2..=4).contains(&region.len()) && region.chars().all(|c| c.is_ascii_alphanumeric())
+	});
+	primary_valid && region_valid
+}
+
+/// Get the language `user_id` wants transcripts translated to, if they've set one.
+pub async fn get_reading_language(user_id: UserId) -> Result<Option<String>, sqlx::Error> {
+	let row = sqlx::query!(
+		"SELECT reading_language FROM users WHERE user_id = $1",
+		crate::hash_user_id(user_id.get())
+	)
+	.fetch_optional(scripty_db::get_db())
+	.await?;
+
+	Ok(row.and_then(|row| row.reading_language))
+}
+
+/// Set or clear the language `user_id` wants transcripts translated to.
+pub async fn set_reading_language(
+	user_id: UserId,
+	language: Option<&str>,
+) -> Result<(), sqlx::Error> {
+	sqlx::query!(
+		"INSERT INTO users (user_id, reading_language) VALUES ($1, $2) ON CONFLICT (user_id) DO \
+		 UPDATE SET reading_language = $2",
+		crate::hash_user_id(user_id.get()),
+		language
+	)
+	.execute(scripty_db::get_db())
+	.await?;
+
+	Ok(())
+}
+
+/// Whether any of `user_ids` read a different language to `language`, the one being transcribed.
+pub async fn any_need_translation(
+	user_ids: &[UserId],
+	language: &str,
+) -> Result<bool, sqlx::Error> {
+	let hashed_user_ids: Vec<Vec<u8>> = user_ids
+		.iter()
+		.map(|id| crate::hash_user_id(id.get()))
+		.collect();
+	// only the primary language matters: someone reading pt-BR can read pt transcripts
+	let primary_language = language.split('-').next().unwrap_or(language);
+
+	sqlx::query!(
+		"SELECT EXISTS(SELECT 1 FROM users WHERE user_id = ANY($1) AND \
+		 split_part(reading_language, '-', 1) <> $2) AS \"exists!\"",
+		&hashed_user_ids,
+		primary_language
+	)
+	.fetch_one(scripty_db::get_db())
+	.await
+	.map(|row| row.exists)
+}
@@ -0,0 +1,99 @@
+//! Guild watch lists, set with `/config watch`: when a guild has one, only the users and roles
+//! on it are transcribed.
+//!
+//! Sessions that are already going pick up changes the next time they reload their config.
+
+use serenity::model::id::{GuildId, RoleId, UserId};
+
+/// Most users and roles a guild can watch.
+pub const MAX_WATCHED: i64 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchTarget {
+	User(UserId),
+	Role(RoleId),
+}
+
+impl WatchTarget {
+	fn id(self) -> i64 {
+		match self {
+			WatchTarget::User(id) => id.get() as i64,
+			WatchTarget::Role(id) => id.get() as i64,
+		}
+	}
+}
+
+/// Get everyone `guild_id` is watching, in the order they were added.
+pub async fn get_watched(guild_id: GuildId) -> Result<Vec<WatchTarget>, sqlx::Error> {
+	let rows = sqlx::query!(
+		"SELECT target_id, is_role FROM watched_speakers WHERE guild_id = $1 ORDER BY added_at ASC",
+		guild_id.get() as i64
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(rows
+		.into_iter()
+		.map(|row| {
+			if row.is_role {
+				WatchTarget::Role(RoleId::new(row.target_id as u64))
+			} else {
+				WatchTarget::User(UserId::new(row.target_id as u64))
+			}
+		})
+		.collect())
+}
+
+/// Only transcribe `target`, and anyone else already watched, in `guild_id`.
+/// Returns false if the guild is already watching as many as it can.
+pub async fn add_watched(guild_id: GuildId, target: WatchTarget) -> Result<bool, sqlx::Error> {
+	let db = scripty_db::get_db();
+	sqlx::query!(
+		"INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT (guild_id) DO NOTHING",
+		guild_id.get() as i64
+	)
+	.execute(db)
+	.await?;
+
+	// adding someone who's already watched doesn't count towards the limit
+	let added = sqlx::query!(
+		"INSERT INTO watched_speakers (guild_id, target_id, is_role) SELECT $1, $2, $3 WHERE \
+		 (SELECT COUNT(*) FROM watched_speakers WHERE guild_id = $1 AND target_id <> $2) < $4 ON \
+		 CONFLICT (guild_id, target_id) DO NOTHING",
+		guild_id.get() as i64,
+		target.id(),
+		matches!(target, WatchTarget::Role(_)),
+		MAX_WATCHED
+	)
+	.execute(db)
+	.await?
+	.rows_affected();
+	if added > 0 {
+		return Ok(true);
+	}
+
+	let already_watched = sqlx::query!(
+		"SELECT EXISTS (SELECT 1 FROM watched_speakers WHERE guild_id = $1 AND target_id = $2) AS \
+		 \"exists!\"",
+		guild_id.get() as i64,
+		target.id()
+	)
+	.fetch_one(db)
+	.await?
+	.exists;
+	Ok(already_watched)
+}
+
+/// Stop watching `target` in `guild_id`. Returns whether it was watched.
+pub async fn remove_watched(guild_id: GuildId, target: WatchTarget) -> Result<bool, sqlx::Error> {
+	let removed = sqlx::query!(
+		"DELETE FROM watched_speakers WHERE guild_id = $1 AND target_id = $2",
+		guild_id.get() as i64,
+		target.id()
+	)
+	.execute(scripty_db::get_db())
+	.await?
+	.rows_affected();
+
+	Ok(removed > 0)
+}
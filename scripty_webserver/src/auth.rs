@@ -47,3 +47,44 @@ where
 		Err(WebServerError::AuthenticationFailed(3))
 	}
 }
+
+/// Authentication with a guild's API key, made with `/config api_key`.
+///
+/// Set it as the type of an argument to a server endpoint handler to only let guild API keys in.
+pub struct GuildAuthentication {
+	/// The guild the key belongs to.
+	pub guild_id: u64,
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for GuildAuthentication
+where
+	S: Send + Sync,
+{
+	type Rejection = WebServerError;
+
+	async fn from_request_parts(req: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+		let token = req
+			.headers
+			.get("Authorization")
+			.ok_or(WebServerError::AuthenticationFailed(1))?
+			.to_str()
+			.map_err(|_| WebServerError::AuthenticationFailed(2))?;
+		// take it either bare, like global tokens, or as a bearer token
+		let token = token.strip_prefix("Bearer ").unwrap_or(token);
+
+		GuildAuthentication::from_api_key(token).await
+	}
+}
+
+impl GuildAuthentication {
+	/// Authenticate with an API key that didn't come from the `Authorization` header.
+	pub async fn from_api_key(key: &str) -> Result<Self, WebServerError> {
+		match scripty_bot_utils::api_keys::authenticate_api_key(key).await? {
+			Some(guild_id) => Ok(GuildAuthentication {
+				guild_id: guild_id.get(),
+			}),
+			None => Err(WebServerError::AuthenticationFailed(3)),
+		}
+	}
+}
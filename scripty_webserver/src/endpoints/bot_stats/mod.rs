@@ -3,6 +3,7 @@
 //! Returns bot statistics.
 
 mod advanced;
+mod public;
 
 use axum::{routing::get, Json};
 use scripty_bot_utils::extern_utils::{
@@ -35,4 +36,5 @@ pub fn router() -> axum::Router {
 	axum::Router::new()
 		.route("/bot_stats", get(get_bot_stats))
 		.merge(advanced::router())
+		.merge(public::router())
 }
@@ -0,0 +1,69 @@
+//! GET `/stats.json`
+//!
+//! Public bot statistics for the website and status page, so they don't have to scrape metrics.
+//! Unauthenticated, so it's cached rather than recomputed for every request.
+
+use std::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+use axum::{routing::get, Json};
+use time::OffsetDateTime;
+
+use crate::errors::WebServerError;
+
+/// How long stats are served from the cache for.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+static CACHED_STATS: Mutex<Option<(Instant, PublicBotStats)>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicBotStats {
+	/// Across every cluster, like what's posted to bot lists.
+	pub guild_count:         u64,
+	pub shard_count:         u16,
+	/// Since public stats were added.
+	pub minutes_transcribed: u64,
+	/// Of the process that answered, in seconds.
+	pub uptime:              u64,
+}
+
+async fn fetch_public_bot_stats() -> Result<PublicBotStats, WebServerError> {
+	let cache = scripty_bot_utils::globals::CLIENT_CACHE
+		.get()
+		.ok_or(WebServerError::CacheUnavailable)?;
+	let (guild_count, shard_count) = scripty_bot_utils::cluster::get_bot_counts(cache).await?;
+
+	let ms_transcribed = sqlx::query!("SELECT ms_transcribed FROM transcription_totals")
+		.fetch_optional(scripty_db::get_db())
+		.await?
+		.map_or(0, |row| row.ms_transcribed);
+
+	let started_at = scripty_metrics::get_metrics().start_time.timestamp();
+	let uptime = OffsetDateTime::now_utc().unix_timestamp() - started_at;
+
+	Ok(PublicBotStats {
+		guild_count,
+		shard_count,
+		minutes_transcribed: ms_transcribed.max(0) as u64 / 60_000,
+		uptime: uptime.max(0) as u64,
+	})
+}
+
+pub async fn get_public_bot_stats() -> Result<Json<PublicBotStats>, WebServerError> {
+	if let Some((fetched_at, stats)) = &*CACHED_STATS.lock().expect("stats cache lock poisoned") {
+		if fetched_at.elapsed() < CACHE_TTL {
+			return Ok(Json(stats.clone()));
+		}
+	}
+
+	let stats = fetch_public_bot_stats().await?;
+	*CACHED_STATS.lock().expect("stats cache lock poisoned") =
+		Some((Instant::now(), stats.clone()));
+	Ok(Json(stats))
+}
+
+pub fn router() -> axum::Router {
+	axum::Router::new().route("/stats.json", get(get_public_bot_stats))
+}
@@ -0,0 +1,67 @@
+//! GET `/overlay/:token` and `/overlay/:token/events`
+//!
+//! Live captions for stream overlays, ie an OBS browser source, with a token from
+//! `/captions overlay` rather than an API key, as the URL ends up in streaming software.
+//! The page shows the last few lines, and streams them from the server-sent events endpoint.
+//! Tokens are bound to the session they were made in, and stop working once it ends.
+
+use axum::{
+	extract::Path,
+	response::{
+		sse::{Event, KeepAlive, Sse},
+		Html,
+	},
+	routing::get,
+};
+use scripty_utils::live_captions::{self, LiveCaptionEvent, OverlaySession};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::{endpoints::live_captions::FeedGuard, errors::WebServerError};
+
+/// Styled with custom CSS in the browser source, and configured with query parameters:
+/// see the comment at the top of the file.
+const OVERLAY_PAGE: &str = include_str!("caption_overlay.html");
+
+async fn overlay_session(token: &str) -> Result<OverlaySession, WebServerError> {
+	live_captions::get_overlay_session(token)
+		.await?
+		.ok_or(WebServerError::NotFound)
+}
+
+pub async fn overlay_page(Path(token): Path<String>) -> Result<Html<&'static str>, WebServerError> {
+	// fail here rather than in the page, so it's obvious when the URL has expired
+	overlay_session(&token).await?;
+	Ok(Html(OVERLAY_PAGE))
+}
+
+pub async fn overlay_events(
+	Path(token): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, WebServerError> {
+	let session = overlay_session(&token).await?;
+	let feed = FeedGuard::new(session.guild_id.get()).ok_or(WebServerError::TooManyConnections)?;
+	let events = live_captions::subscribe(session.guild_id).await?;
+
+	// the stream ends with the session, and the token was revoked, so the page won't reconnect
+	let mut ended = false;
+	let events = events
+		.filter(move |event| event.is_in(&session))
+		.map_while(move |event| {
+			if ended {
+				return None;
+			}
+			ended = matches!(event, LiveCaptionEvent::SessionEnded { .. });
+			Some(event)
+		})
+		.map(move |event| {
+			// counts for as long as the client is connected
+			let _feed = &feed;
+			Event::default().json_data(event)
+		});
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+pub fn router() -> axum::Router {
+	axum::Router::new()
+		.route("/overlay/:token", get(overlay_page))
+		.route("/overlay/:token/events", get(overlay_events))
+}
@@ -0,0 +1,128 @@
+//! GET `/api/live`
+//!
+//! Upgrades to a WebSocket that streams the guild's voice sessions as they're transcribed,
+//! one JSON event per message, for caption displays and stream overlays.
+//! Authenticated with a guild's API key, either in the `Authorization` header,
+//! or in the `key` query parameter for clients that can't set headers, like browsers.
+//! Nothing is sent until the guild turns live captions on with `/config live_captions`.
+
+use std::{collections::BTreeMap, pin::pin, sync::Mutex, time::Duration};
+
+use axum::{
+	extract::{
+		ws::{Message, WebSocket, WebSocketUpgrade},
+		Query,
+	},
+	response::Response,
+	routing::get,
+};
+use scripty_bot_utils::extern_utils::GuildId;
+use scripty_utils::live_captions;
+use tokio_stream::StreamExt;
+
+use crate::{auth::GuildAuthentication, errors::WebServerError};
+
+/// Most feeds one guild can have open at once, on each process, including overlays.
+const MAX_FEEDS_PER_GUILD: usize = 10;
+
+/// How often to ping clients, so idle connections aren't dropped by proxies.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+static OPEN_FEEDS: Mutex<BTreeMap<u64, usize>> = Mutex::new(BTreeMap::new());
+
+/// Counts towards `MAX_FEEDS_PER_GUILD` for as long as it's held.
+pub(crate) struct FeedGuard(u64);
+
+impl FeedGuard {
+	pub(crate) fn new(guild_id: u64) -> Option<Self> {
+		let mut feeds = OPEN_FEEDS.lock().expect("open feeds lock poisoned");
+		let open = feeds.entry(guild_id).or_default();
+		if *open >= MAX_FEEDS_PER_GUILD {
+			return None;
+		}
+		*open += 1;
+		Some(Self(guild_id))
+	}
+}
+
+impl Drop for FeedGuard {
+	fn drop(&mut self) {
+		let mut feeds = OPEN_FEEDS.lock().expect("open feeds lock poisoned");
+		if let Some(open) = feeds.get_mut(&self.0) {
+			*open -= 1;
+			if *open == 0 {
+				feeds.remove(&self.0);
+			}
+		}
+	}
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LiveCaptionsQuery {
+	pub key: Option<String>,
+}
+
+pub async fn live_captions(
+	ws: WebSocketUpgrade,
+	auth: Option<GuildAuthentication>,
+	Query(LiveCaptionsQuery { key }): Query<LiveCaptionsQuery>,
+) -> Result<Response, WebServerError> {
+	let GuildAuthentication { guild_id } = match (auth, key) {
+		(Some(auth), _) => auth,
+		(None, Some(key)) => GuildAuthentication::from_api_key(&key).await?,
+		(None, None) => return Err(WebServerError::AuthenticationFailed(1)),
+	};
+	let feed = FeedGuard::new(guild_id).ok_or(WebServerError::TooManyConnections)?;
+
+	Ok(ws.on_upgrade(move |socket| relay(socket, guild_id, feed)))
+}
+
+async fn relay(mut socket: WebSocket, guild_id: u64, _feed: FeedGuard) {
+	let events = match live_captions::subscribe(GuildId::new(guild_id)).await {
+		Ok(events) => events,
+		Err(e) => {
+			warn!(guild_id, "failed to subscribe to live captions: {}", e);
+			let _ = socket.close().await;
+			return;
+		}
+	};
+	let mut events = pin!(events);
+	let mut ping = tokio::time::interval(PING_INTERVAL);
+
+	loop {
+		tokio::select! {
+			event = events.next() => {
+				// the Redis connection went away: let the client reconnect
+				let Some(event) = event else {
+					break;
+				};
+				let message = match serde_json::to_string(&event) {
+					Ok(message) => message,
+					Err(e) => {
+						warn!(guild_id, "failed to serialize live caption event: {}", e);
+						continue;
+					}
+				};
+				if socket.send(Message::Text(message)).await.is_err() {
+					break;
+				}
+			}
+			_ = ping.tick() => {
+				if socket.send(Message::Ping(Vec::new())).await.is_err() {
+					break;
+				}
+			}
+			message = socket.recv() => match message {
+				Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+				// clients have nothing to say, and pongs are handled for us
+				Some(Ok(_)) => {}
+			},
+		}
+	}
+
+	let _ = socket.close().await;
+}
+
+pub fn router() -> axum::Router {
+	axum::Router::new().route("/api/live", get(live_captions))
+}
@@ -2,11 +2,31 @@
 //!
 //! Returns Prometheus compatible metrics.
 
-use axum::routing::get;
+use axum::{
+	http::{header::AUTHORIZATION, HeaderMap},
+	routing::get,
+};
+use scripty_utils::constant_time_eq;
 
-// no auth, metrics are public
-pub async fn get_metrics() -> Vec<u8> {
-	scripty_metrics::get_formatted_metrics()
+use crate::errors::WebServerError;
+
+// public unless a bearer token is configured
+pub async fn get_metrics(headers: HeaderMap) -> Result<Vec<u8>, WebServerError> {
+	if let Some(expected_token) = &scripty_config::get_config().metrics.bearer_token {
+		let token = headers
+			.get(AUTHORIZATION)
+			.ok_or(WebServerError::AuthenticationFailed(1))?
+			.to_str()
+			.map_err(|_| WebServerError::AuthenticationFailed(2))?;
+		let token = token
+			.strip_prefix("Bearer ")
+			.ok_or(WebServerError::AuthenticationFailed(3))?;
+		if !constant_time_eq(token.as_bytes(), expected_token.as_bytes()) {
+			return Err(WebServerError::AuthenticationFailed(3));
+		}
+	}
+
+	Ok(scripty_metrics::get_formatted_metrics())
 }
 
 pub fn router() -> axum::Router {
@@ -1,14 +1,33 @@
 pub mod bot_stats;
+pub mod caption_overlay;
 pub mod languages;
+pub mod live_captions;
 pub mod metrics;
 pub mod premium;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod transcripts;
 pub mod webhooks;
 
 pub fn router() -> axum::Router {
-	axum::Router::new()
+	let router = axum::Router::new()
 		.merge(bot_stats::router())
-		.merge(metrics::router())
 		.merge(premium::router())
 		.merge(languages::router())
-		.merge(webhooks::router())
+		.merge(transcripts::router())
+		.merge(live_captions::router())
+		.merge(caption_overlay::router())
+		.merge(webhooks::router());
+
+	// if metrics have their own bind address, they're served separately
+	let router = if scripty_config::get_config().metrics.bind_address.is_none() {
+		router.merge(metrics::router())
+	} else {
+		router
+	};
+
+	#[cfg(feature = "profiling")]
+	let router = router.merge(profiling::router());
+
+	router
 }
@@ -1,4 +1,7 @@
-use axum::{routing::post, Json};
+use axum::{
+	routing::{get, post},
+	Json,
+};
 use scripty_bot_utils::extern_utils::{CreateEmbed, CreateEmbedFooter, CreateMessage, UserId};
 use sqlx::types::time::OffsetDateTime;
 
@@ -13,6 +16,8 @@ use crate::{
 	},
 };
 
+mod patreon;
+
 pub async fn stripe_webhook(
 	Authentication {
 		user_id: auth_user_id,
@@ -487,5 +492,8 @@ ON CONFLICT
 }
 
 pub fn router() -> axum::Router {
-	axum::Router::new().route("/premium/stripe_webhook", post(stripe_webhook))
+	axum::Router::new()
+		.route("/premium/stripe_webhook", post(stripe_webhook))
+		.route("/premium/patreon_webhook", post(patreon::patreon_webhook))
+		.route("/premium/patreon/callback", get(patreon::patreon_callback))
 }
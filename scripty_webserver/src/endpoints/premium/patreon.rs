@@ -0,0 +1,124 @@
+use axum::{
+	body::Bytes,
+	extract::Query,
+	http::{HeaderMap, StatusCode},
+};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use scripty_bot_utils::patreon::{self, PatreonError};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::{
+	errors::WebServerError,
+	models::{PatreonCallback, PatreonWebhookEvent},
+};
+
+pub async fn patreon_webhook(headers: HeaderMap, body: Bytes) -> Result<(), WebServerError> {
+	let Some(cfg) = scripty_config::get_config().patreon.as_ref() else {
+		return Err(WebServerError::AuthenticationFailed(3));
+	};
+
+	// Patreon signs the raw body with HMAC-MD5, hex encoded
+	let signature = headers
+		.get("X-Patreon-Signature")
+		.ok_or(WebServerError::AuthenticationFailed(1))?
+		.to_str()
+		.map_err(|_| WebServerError::AuthenticationFailed(2))?;
+	let signature = hex::decode(signature).map_err(|_| WebServerError::AuthenticationFailed(3))?;
+	let mut mac = Hmac::<Md5>::new_from_slice(cfg.webhook_secret.as_bytes())
+		.expect("HMAC takes keys of any length");
+	mac.update(&body);
+	mac.verify_slice(&signature)
+		.map_err(|_| WebServerError::AuthenticationFailed(3))?;
+
+	let event = headers
+		.get("X-Patreon-Event")
+		.and_then(|event| event.to_str().ok())
+		.unwrap_or_default();
+	let PatreonWebhookEvent { data } =
+		serde_json::from_slice(&body).map_err(|_| WebServerError::InvalidRequestBody)?;
+
+	let patreon_user_id = data.relationships.user.data.id;
+	let active = event != "members:pledge:delete"
+		&& data.attributes.patron_status.as_deref() == Some("active_patron");
+	let premium_level = if active {
+		let tiers = data
+			.relationships
+			.currently_entitled_tiers
+			.map(|tiers| tiers.data)
+			.unwrap_or_default();
+		patreon::premium_level_for_tiers(tiers.iter().map(|tier| tier.id.as_str()))
+	} else {
+		0
+	};
+	let premium_expiry = (premium_level > 0).then(|| {
+		// pledges are charged monthly, so if there's no charge date, assume a month
+		data.attributes
+			.next_charge_date
+			.as_deref()
+			.and_then(|date| OffsetDateTime::parse(date, &Rfc3339).ok())
+			.unwrap_or_else(|| OffsetDateTime::now_utc() + time::Duration::days(31))
+	});
+
+	let linked = patreon::update_member(&patreon_user_id, premium_level, premium_expiry).await?;
+	debug!(
+		%event,
+		%patreon_user_id,
+		premium_level,
+		linked,
+		"processed Patreon pledge"
+	);
+
+	Ok(())
+}
+
+/// Where Patreon sends users back to after `/premium link`. Responses are shown to them as-is.
+pub async fn patreon_callback(
+	Query(PatreonCallback { code, state, error }): Query<PatreonCallback>,
+) -> (StatusCode, &'static str) {
+	if error.is_some() {
+		return (
+			StatusCode::OK,
+			"Linking was cancelled. You can close this page.",
+		);
+	}
+	let (Some(code), Some(state)) = (code, state) else {
+		return (
+			StatusCode::BAD_REQUEST,
+			"This link is missing some details.",
+		);
+	};
+
+	match patreon::finish_link(&code, &state).await {
+		Ok((user_id, premium_level)) => {
+			info!(%user_id, premium_level, "linked Patreon account");
+			if premium_level > 0 {
+				(
+					StatusCode::OK,
+					"Your Patreon account is linked, and your premium is active. Thanks for your \
+					 support! You can close this page.",
+				)
+			} else {
+				(
+					StatusCode::OK,
+					"Your Patreon account is linked. Your premium will be activated as soon as \
+					 Patreon lets us know about your pledge. You can close this page.",
+				)
+			}
+		}
+		Err(PatreonError::InvalidState) => (
+			StatusCode::BAD_REQUEST,
+			"This link has expired. Run `/premium link` again to get a new one.",
+		),
+		Err(PatreonError::NotConfigured) => {
+			(StatusCode::NOT_FOUND, "Patreon linking isn't enabled.")
+		}
+		Err(e) => {
+			error!("failed to link Patreon account: {}", e);
+			(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				"Something went wrong linking your account. Please try again later.",
+			)
+		}
+	}
+}
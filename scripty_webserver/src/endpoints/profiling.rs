@@ -0,0 +1,101 @@
+//! GET `/debug/pprof/profile`
+//!
+//! Samples the CPU for the requested number of seconds (default 10, max 60),
+//! and returns the result as a flamegraph SVG.
+//!
+//! GET `/debug/pprof/heap`
+//!
+//! Returns a profile of memory allocated right now, sampled by jemalloc, in pprof's format.
+//!
+//! Only available when built with the `profiling` feature,
+//! and only after being enabled at runtime with `~admin profiling true`.
+
+use std::time::Duration;
+
+use axum::{
+	extract::Query,
+	http::{header::CONTENT_TYPE, StatusCode},
+	response::{IntoResponse, Response},
+	routing::get,
+};
+
+use crate::auth::Authentication;
+
+#[derive(Deserialize)]
+pub struct ProfileQuery {
+	seconds: Option<u64>,
+}
+
+pub async fn get_profile(
+	_authentication: Authentication,
+	Query(ProfileQuery { seconds }): Query<ProfileQuery>,
+) -> Response {
+	if !scripty_metrics::is_profiling_enabled() {
+		return (StatusCode::NOT_FOUND, "profiling is disabled").into_response();
+	}
+
+	let seconds = seconds.unwrap_or(10).clamp(1, 60);
+	info!(%seconds, "starting CPU profile");
+
+	let guard = match pprof::ProfilerGuardBuilder::default()
+		.frequency(100)
+		.blocklist(&["libc", "libgcc", "pthread", "vdso"])
+		.build()
+	{
+		Ok(guard) => guard,
+		Err(e) => {
+			error!("failed to start profiler: {}", e);
+			return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+		}
+	};
+	tokio::time::sleep(Duration::from_secs(seconds)).await;
+
+	let mut svg = Vec::new();
+	if let Err(e) = guard
+		.report()
+		.build()
+		.and_then(|report| report.flamegraph(&mut svg))
+	{
+		error!("failed to build profile report: {}", e);
+		return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+	}
+
+	([(CONTENT_TYPE, "image/svg+xml")], svg).into_response()
+}
+
+pub async fn get_heap_profile(_authentication: Authentication) -> Response {
+	if !scripty_metrics::is_profiling_enabled() {
+		return (StatusCode::NOT_FOUND, "profiling is disabled").into_response();
+	}
+
+	// only set if jemalloc is the allocator and was started with profiling on
+	let Some(prof_ctl) = jemalloc_pprof::PROF_CTL.as_ref() else {
+		return (
+			StatusCode::INTERNAL_SERVER_ERROR,
+			"heap profiling is not available",
+		)
+			.into_response();
+	};
+	let mut prof_ctl = prof_ctl.lock().await;
+	if !prof_ctl.activated() {
+		return (
+			StatusCode::INTERNAL_SERVER_ERROR,
+			"heap profiling is not active",
+		)
+			.into_response();
+	}
+
+	match prof_ctl.dump_pprof() {
+		Ok(profile) => ([(CONTENT_TYPE, "application/octet-stream")], profile).into_response(),
+		Err(e) => {
+			error!("failed to dump heap profile: {}", e);
+			(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+		}
+	}
+}
+
+pub fn router() -> axum::Router {
+	axum::Router::new()
+		.route("/debug/pprof/profile", get(get_profile))
+		.route("/debug/pprof/heap", get(get_heap_profile))
+}
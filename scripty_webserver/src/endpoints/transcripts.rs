@@ -0,0 +1,243 @@
+//! GET `/api/sessions`, `/api/sessions/:session_id`, and `/api/usage`
+//!
+//! The transcript API. Authenticated with a guild's API key, from `/config api_key`,
+//! and only ever returns that guild's data.
+//! Only sessions that started while the guild had a key are stored.
+
+use std::fmt::Write;
+
+use axum::{
+	extract::{Path, Query},
+	http::header::CONTENT_TYPE,
+	response::{IntoResponse, Response},
+	routing::get,
+	Json,
+};
+
+use crate::{auth::GuildAuthentication, errors::WebServerError};
+
+/// Sessions listed per request if the request doesn't say.
+const DEFAULT_SESSION_LIMIT: i64 = 50;
+/// Most sessions listed per request.
+const MAX_SESSION_LIMIT: i64 = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct ListSessionsQuery {
+	/// Only list sessions older than this one, to page through them.
+	pub before: Option<i64>,
+	/// List at most this many sessions.
+	pub limit:  Option<i64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TranscriptSessionInfo {
+	pub session_id:       i64,
+	/// A string, like all Discord IDs, as it can be too big for a JSON number.
+	pub voice_channel_id: String,
+	/// Unix timestamp.
+	pub started_at:       i64,
+	/// Unix timestamp. Null while the session is going, or if it was cut short.
+	pub ended_at:         Option<i64>,
+	pub segment_count:    i64,
+}
+
+/// List the guild's sessions, newest first.
+pub async fn list_sessions(
+	GuildAuthentication { guild_id }: GuildAuthentication,
+	Query(ListSessionsQuery { before, limit }): Query<ListSessionsQuery>,
+) -> Result<Json<Vec<TranscriptSessionInfo>>, WebServerError> {
+	let limit = limit
+		.unwrap_or(DEFAULT_SESSION_LIMIT)
+		.clamp(1, MAX_SESSION_LIMIT);
+
+	let sessions = sqlx::query!(
+		"SELECT s.session_id, s.voice_channel_id, EXTRACT(EPOCH FROM s.started_at)::BIGINT AS \
+		 \"started_at!\", EXTRACT(EPOCH FROM s.ended_at)::BIGINT AS ended_at, (SELECT COUNT(*) \
+		 FROM transcript_segments t WHERE t.session_id = s.session_id) AS \"segment_count!\" FROM \
+		 transcript_sessions s WHERE s.guild_id = $1 AND ($2::BIGINT IS NULL OR s.session_id < \
+		 $2) ORDER BY s.session_id DESC LIMIT $3",
+		guild_id as i64,
+		before,
+		limit
+	)
+	.fetch_all(scripty_db::get_db())
+	.await?;
+
+	Ok(Json(
+		sessions
+			.into_iter()
+			.map(|row| TranscriptSessionInfo {
+				session_id:       row.session_id,
+				voice_channel_id: row.voice_channel_id.to_string(),
+				started_at:       row.started_at,
+				ended_at:         row.ended_at,
+				segment_count:    row.segment_count,
+			})
+			.collect(),
+	))
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+	#[default]
+	Json,
+	/// SubRip subtitles, with each line prefixed with who said it.
+	Srt,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TranscriptQuery {
+	#[serde(default)]
+	pub format: TranscriptFormat,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TranscriptSegment {
+	/// Display name of the speaker, at the time.
+	pub speaker:  String,
+	/// Milliseconds from the start of the session.
+	pub start_ms: i64,
+	pub end_ms:   i64,
+	pub content:  String,
+	/// Detected language, if the guild had auto-detect on and it was clear enough.
+	pub language: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Transcript {
+	pub session:  TranscriptSessionInfo,
+	pub segments: Vec<TranscriptSegment>,
+}
+
+/// Get the transcript of one of the guild's sessions.
+pub async fn get_transcript(
+	GuildAuthentication { guild_id }: GuildAuthentication,
+	Path(session_id): Path<i64>,
+	Query(TranscriptQuery { format }): Query<TranscriptQuery>,
+) -> Result<Response, WebServerError> {
+	let db = scripty_db::get_db();
+
+	let session = sqlx::query!(
+		"SELECT voice_channel_id, EXTRACT(EPOCH FROM started_at)::BIGINT AS \"started_at!\", \
+		 EXTRACT(EPOCH FROM ended_at)::BIGINT AS ended_at FROM transcript_sessions WHERE \
+		 session_id = $1 AND guild_id = $2",
+		session_id,
+		guild_id as i64
+	)
+	.fetch_optional(db)
+	.await?
+	.ok_or(WebServerError::NotFound)?;
+
+	let segments: Vec<_> = sqlx::query!(
+		"SELECT speaker, start_ms, end_ms, content, language FROM transcript_segments WHERE \
+		 session_id = $1 ORDER BY start_ms, segment_id",
+		session_id
+	)
+	.fetch_all(db)
+	.await?
+	.into_iter()
+	.map(|row| TranscriptSegment {
+		speaker:  row.speaker,
+		start_ms: row.start_ms,
+		end_ms:   row.end_ms,
+		content:  row.content,
+		language: row.language,
+	})
+	.collect();
+
+	Ok(match format {
+		TranscriptFormat::Json => Json(Transcript {
+			session: TranscriptSessionInfo {
+				session_id,
+				voice_channel_id: session.voice_channel_id.to_string(),
+				started_at: session.started_at,
+				ended_at: session.ended_at,
+				segment_count: segments.len() as i64,
+			},
+			segments,
+		})
+		.into_response(),
+		TranscriptFormat::Srt => (
+			[(CONTENT_TYPE, "application/x-subrip; charset=utf-8")],
+			format_srt(&segments),
+		)
+			.into_response(),
+	})
+}
+
+fn format_srt(segments: &[TranscriptSegment]) -> String {
+	let mut srt = String::new();
+	for (idx, segment) in segments.iter().enumerate() {
+		writeln!(
+			&mut srt,
+			"{}\n{} --> {}\n[{}]: {}\n",
+			idx + 1,
+			format_srt_timestamp(segment.start_ms),
+			format_srt_timestamp(segment.end_ms),
+			segment.speaker,
+			segment.content
+		)
+		.expect("failed to write to string");
+	}
+	srt
+}
+
+/// `HH:MM:SS,mmm`
+fn format_srt_timestamp(ms: i64) -> String {
+	let ms = ms.max(0);
+	format!(
+		"{:02}:{:02}:{:02},{:03}",
+		ms / 3_600_000,
+		ms / 60_000 % 60,
+		ms / 1000 % 60,
+		ms % 1000
+	)
+}
+
+#[derive(Serialize, Debug)]
+pub struct Usage {
+	/// Premium tier, 0 meaning none.
+	pub tier:                i16,
+	/// Milliseconds of audio transcribed this month, as of the last few seconds.
+	pub ms_transcribed:      u64,
+	/// Milliseconds of audio the guild can transcribe each month. Null if unlimited.
+	pub monthly_quota_ms:    Option<u64>,
+	/// Stored sessions that started this month.
+	pub sessions_this_month: i64,
+	/// Stored sessions, which are kept for `retention_days`.
+	pub stored_sessions:     i64,
+	pub retention_days:      i32,
+}
+
+/// Get the guild's usage this month.
+pub async fn get_usage(
+	GuildAuthentication { guild_id }: GuildAuthentication,
+) -> Result<Json<Usage>, WebServerError> {
+	let status = scripty_premium::get_guild_status(guild_id).await?;
+
+	// quotas reset at midnight UTC on the 1st
+	let sessions = sqlx::query!(
+		"SELECT COUNT(*) FILTER (WHERE started_at >= date_trunc('month', NOW(), 'UTC')) AS \
+		 \"this_month!\", COUNT(*) AS \"stored!\" FROM transcript_sessions WHERE guild_id = $1",
+		guild_id as i64
+	)
+	.fetch_one(scripty_db::get_db())
+	.await?;
+
+	Ok(Json(Usage {
+		tier:                status.tier as i16,
+		ms_transcribed:      status.ms_transcribed,
+		monthly_quota_ms:    status.tier.monthly_quota_ms(),
+		sessions_this_month: sessions.this_month,
+		stored_sessions:     sessions.stored,
+		retention_days:      scripty_config::get_config().transcript_api.retention_days,
+	}))
+}
+
+pub fn router() -> axum::Router {
+	axum::Router::new()
+		.route("/api/sessions", get(list_sessions))
+		.route("/api/sessions/:session_id", get(get_transcript))
+		.route("/api/usage", get(get_usage))
+}
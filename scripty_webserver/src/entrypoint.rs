@@ -2,6 +2,10 @@ pub async fn entrypoint() {
 	let cfg = scripty_config::get_config();
 	let bind_addr = cfg.bind_address.parse().expect("invalid bind address");
 
+	if let Some(metrics_bind_addr) = &cfg.metrics.bind_address {
+		tokio::spawn(metrics_entrypoint(metrics_bind_addr));
+	}
+
 	let router = crate::endpoints::router();
 
 	axum::Server::bind(&bind_addr)
@@ -9,3 +13,31 @@ pub async fn entrypoint() {
 		.await
 		.expect("failed to start server");
 }
+
+/// Serve only `/metrics`, on either a TCP address or a Unix socket.
+async fn metrics_entrypoint(bind_addr: &str) {
+	let router = crate::endpoints::metrics::router();
+
+	if let Some(path) = bind_addr.strip_prefix("unix:") {
+		// remove any stale socket left over from a previous run
+		let _ = std::fs::remove_file(path);
+		let listener =
+			tokio::net::UnixListener::bind(path).expect("failed to bind metrics unix socket");
+		info!(%path, "serving metrics on unix socket");
+
+		axum::Server::builder(hyper::server::accept::from_stream(
+			tokio_stream::wrappers::UnixListenerStream::new(listener),
+		))
+		.serve(router.into_make_service())
+		.await
+		.expect("failed to start metrics server");
+	} else {
+		let bind_addr = bind_addr.parse().expect("invalid metrics bind address");
+		info!(%bind_addr, "serving metrics");
+
+		axum::Server::bind(&bind_addr)
+			.serve(router.into_make_service())
+			.await
+			.expect("failed to start metrics server");
+	}
+}
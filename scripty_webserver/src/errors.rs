@@ -18,6 +18,8 @@ pub enum WebServerError {
 	/// Sub-code `1`: No token was provided in the `Authorization` header.
 	/// Sub-code `2`: The token was not valid UTF-8.
 	/// Sub-code `3`: The token was not a valid token.
+	///
+	/// Webhooks that are signed instead (ie Patreon's) use the same sub-codes for the signature.
 	AuthenticationFailed(i32),
 
 	/// Bot cache was unavailable at request time.
@@ -39,6 +41,31 @@ pub enum WebServerError {
 	///
 	/// Code `6`, no sub-code.
 	SerenityError,
+
+	/// The request body was not in the expected format.
+	///
+	/// Code `7`, no sub-code.
+	InvalidRequestBody,
+
+	/// Talking to Patreon failed.
+	///
+	/// Code `8`, no sub-code.
+	PatreonError(scripty_bot_utils::patreon::PatreonError),
+
+	/// The requested resource doesn't exist, or belongs to someone else.
+	///
+	/// Code `9`, no sub-code.
+	NotFound,
+
+	/// Too many connections are already open with these credentials.
+	///
+	/// Code `10`, no sub-code.
+	TooManyConnections,
+
+	/// Redis returned an error.
+	///
+	/// Code `11`, no sub-code.
+	RedisError,
 }
 
 impl From<scripty_bot_utils::extern_utils::CacheNotInitializedError> for WebServerError {
@@ -59,6 +86,31 @@ impl From<sqlx::Error> for WebServerError {
 	}
 }
 
+impl From<scripty_bot_utils::patreon::PatreonError> for WebServerError {
+	fn from(e: scripty_bot_utils::patreon::PatreonError) -> Self {
+		match e {
+			scripty_bot_utils::patreon::PatreonError::Database(e) => {
+				WebServerError::DatabaseError(Some(e))
+			}
+			e => WebServerError::PatreonError(e),
+		}
+	}
+}
+
+impl From<scripty_redis::TransactionError> for WebServerError {
+	fn from(e: scripty_redis::TransactionError) -> Self {
+		error!("redis error: {}", e);
+		WebServerError::RedisError
+	}
+}
+
+impl From<scripty_redis::redis::RedisError> for WebServerError {
+	fn from(e: scripty_redis::redis::RedisError) -> Self {
+		error!("redis error: {}", e);
+		WebServerError::RedisError
+	}
+}
+
 impl From<ComponentRange> for WebServerError {
 	fn from(_: ComponentRange) -> Self {
 		WebServerError::DatabaseError(None)
@@ -80,6 +132,11 @@ impl Display for WebServerError {
 			WebServerError::DatabaseError(None) => write!(f, "Database error"),
 			WebServerError::ParseIntError => write!(f, "Parse int error"),
 			WebServerError::SerenityError => write!(f, "Serenity error"),
+			WebServerError::InvalidRequestBody => write!(f, "Invalid request body"),
+			WebServerError::PatreonError(e) => write!(f, "Patreon error: {}", e),
+			WebServerError::NotFound => write!(f, "Not found"),
+			WebServerError::TooManyConnections => write!(f, "Too many connections"),
+			WebServerError::RedisError => write!(f, "Redis error"),
 		}
 	}
 }
@@ -128,6 +185,41 @@ impl IntoResponse for WebServerError {
 				},
 				StatusCode::INTERNAL_SERVER_ERROR,
 			),
+			WebServerError::InvalidRequestBody => (
+				ErrorJson {
+					code:     7,
+					sub_code: -1,
+				},
+				StatusCode::BAD_REQUEST,
+			),
+			WebServerError::PatreonError(_) => (
+				ErrorJson {
+					code:     8,
+					sub_code: -1,
+				},
+				StatusCode::INTERNAL_SERVER_ERROR,
+			),
+			WebServerError::NotFound => (
+				ErrorJson {
+					code:     9,
+					sub_code: -1,
+				},
+				StatusCode::NOT_FOUND,
+			),
+			WebServerError::TooManyConnections => (
+				ErrorJson {
+					code:     10,
+					sub_code: -1,
+				},
+				StatusCode::TOO_MANY_REQUESTS,
+			),
+			WebServerError::RedisError => (
+				ErrorJson {
+					code:     11,
+					sub_code: -1,
+				},
+				StatusCode::INTERNAL_SERVER_ERROR,
+			),
 		};
 
 		let bytes = match serde_json::to_vec(&body) {
@@ -176,3 +176,48 @@ impl fmt::Display for PlanInterval {
 		}
 	}
 }
+
+/// Body of a Patreon `members:pledge:*` webhook: a JSON:API member resource.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonWebhookEvent {
+	pub data: PatreonMember,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonMember {
+	pub attributes:    PatreonMemberAttributes,
+	pub relationships: PatreonMemberRelationships,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonMemberAttributes {
+	/// `active_patron`, `declined_patron`, or `former_patron`. Null if they never pledged.
+	pub patron_status:    Option<String>,
+	/// RFC 3339 timestamp.
+	pub next_charge_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonMemberRelationships {
+	pub user:                     PatreonRelationship<PatreonResourceId>,
+	pub currently_entitled_tiers: Option<PatreonRelationship<Vec<PatreonResourceId>>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonRelationship<T> {
+	pub data: T,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonResourceId {
+	pub id: String,
+}
+
+/// Query string Patreon sends users back to the callback with.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PatreonCallback {
+	pub code:  Option<String>,
+	pub state: Option<String>,
+	/// Set instead of `code` if the user declined.
+	pub error: Option<String>,
+}
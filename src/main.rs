@@ -1,3 +1,14 @@
+// with the `profiling` feature, allocate with jemalloc so heap profiles can be taken
+#[cfg(feature = "profiling")]
+#[global_allocator]
+static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+// sample allocations about every 512 KiB for the heap profiling endpoint
+#[cfg(feature = "profiling")]
+#[allow(non_upper_case_globals)]
+#[export_name = "_rjem_malloc_conf"]
+pub static malloc_conf: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
+
 fn main() {
 	scripty_core::start();
 }